@@ -9,22 +9,30 @@
 //! This module is part of the binary only — it depends on the `ui` layer and is
 //! intentionally excluded from the public library API (see the crate root docs).
 
-use anyhow::Result;
-use std::collections::HashMap;
-use std::path::PathBuf;
+use anyhow::{bail, Context, Result};
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::mpsc::{self, Receiver, Sender};
-use std::time::Instant;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
 
+use crate::commands::qemu_img::DiskUsage;
 use crate::commands::qemu_system::NetworkCapabilities;
 use crate::config::Config;
-use crate::hardware::{MultiGpuPassthroughStatus, PciDevice, SingleGpuConfig, UsbDevice};
+use crate::hardware::{
+    DiagnosticCheck, HostCapacity, LookingGlassConfig, MultiGpuPassthroughStatus, PciDevice,
+    SingleGpuConfig, UsbDevice,
+};
+use crate::metadata::qemu_profiles::USER_PROFILES_DIR;
 use crate::metadata::{
     AsciiArtStore, HierarchyConfig, MetadataStore, OsInfo, QemuProfileStore, SettingsHelpStore,
-    SharedFoldersHelpStore,
+    SharedFoldersHelpStore, SortBy, TemplateStore, VmTemplate,
 };
 use crate::ui::widgets::build_visual_order;
 use crate::vm::{
-    discover_vms, BootMode, DiscoveredVm, LaunchOptions, QemuProcess, SharedFolder, Snapshot,
+    discover_vms, BootMode, BootOrder, CpuConfig, DirectKernelBoot, DiscoveredVm, LaunchOptions,
+    MediaKind, QemuProcess, RemovableDrive, SharedFolder, Snapshot,
 };
 pub use crate::wizard_types::*;
 
@@ -49,6 +57,8 @@ pub enum Screen {
     BootOptions,
     /// Display options
     DisplayOptions,
+    /// CPU model and flags editor
+    CpuFlags,
     /// USB device selection
     UsbDevices,
     /// PCI device selection for passthrough
@@ -61,6 +71,8 @@ pub enum Screen {
     SingleGpuInstructions,
     /// Multi-GPU passthrough setup (Looking Glass)
     MultiGpuSetup,
+    /// GPU passthrough readiness checklist
+    PassthroughDiagnostics,
     /// Confirmation dialog
     Confirm(ConfirmAction),
     /// Help screen
@@ -77,8 +89,7 @@ pub enum Screen {
     CreateWizard,
     /// Custom OS metadata entry (secondary form during wizard)
     CreateWizardCustomOs,
-    /// ISO download progress screen (planned feature)
-    #[allow(dead_code)]
+    /// ISO download progress screen
     CreateWizardDownload,
     /// Network settings (backend + port forwarding)
     NetworkSettings,
@@ -88,6 +99,30 @@ pub enum Screen {
     ImportWizard,
     /// Notes editor
     EditNotes,
+    /// Pre-launch / post-stop hook scripts for the selected VM
+    LaunchHooks,
+    /// Diff preview before regenerating launch.sh from the parsed config
+    RegenerateScript,
+    /// Kernel/initrd/command line for direct kernel boot
+    DirectKernelBoot,
+    /// Insert, swap and eject floppies or CDs in a running VM
+    RemovableMedia,
+    /// Real-time clock base and clock source
+    RtcSettings,
+    /// Per-disk IOPS and bandwidth limits
+    DiskThrottle,
+    /// Backup, reset and Secure Boot of a UEFI VM's variable store
+    UefiNvram,
+    /// VMs not launched in a while, for bulk deletion
+    StaleVms,
+    /// Deleted VMs, for restoring or purging
+    Trash,
+    /// Install ISOs in the ISO library directory
+    IsoLibrary,
+    /// Host hardware and virtualization summary
+    HostInfo,
+    /// The VM library is missing or unreadable: create it or fix the path
+    LibrarySetup,
 }
 
 /// Context for text input dialogs
@@ -95,6 +130,30 @@ pub enum Screen {
 pub enum TextInputContext {
     SnapshotName,
     RenameVm,
+    /// Guest resolution for the IVSHMEM size calculator
+    IvshmemResolution,
+    /// Expected SHA-256 for the ISO being verified
+    IsoChecksum(PathBuf),
+    /// Direct ISO link for the wizard to download
+    IsoDownloadUrl,
+    /// New balloon target (MB) for the selected running VM
+    BalloonTarget,
+    /// Directory for the file browser to jump to
+    FileBrowserPath,
+    /// Kernel command line for direct kernel boot
+    KernelAppend,
+    /// Typed confirmation before permanently emptying the trash
+    EmptyTrash,
+    /// Fixed start date for the guest's real-time clock
+    RtcDate,
+    /// One IO limit of the disk on the disk I/O limits screen
+    DiskThrottle(crate::vm::qemu_config::ThrottleField),
+    /// Name to save the wizard's settings under as a template
+    TemplateName,
+    /// Login name for Connect via SSH
+    SshUser,
+    /// QEMU window title of the selected VM
+    WindowTitle,
 }
 
 /// Actions that need confirmation
@@ -104,6 +163,12 @@ pub enum ConfirmAction {
     ResetVm,
     DeleteVm,
     DeleteSnapshot(String),
+    /// Delete every marked snapshot, one after another
+    DeleteSnapshots(Vec<String>),
+    /// Move the marked stale VMs (ids) to trash
+    DeleteStaleVms(Vec<String>),
+    /// Run a batch action on the VMs marked in bulk mode
+    Batch(BatchAction),
     RestoreSnapshot(String),
     DiscardScriptChanges,
     DiscardNotesChanges,
@@ -111,6 +176,70 @@ pub enum ConfirmAction {
     ForceStopVm,
     /// Leaving a passthrough/shared-folders screen with unsaved changes.
     UnsavedChanges(UnsavedKind),
+    /// Quitting while VMs are still running: leave running / shut down all / cancel.
+    QuitWithRunningVms,
+    /// Reset every setting to its default: keep the library path / reset
+    /// it too / cancel
+    ResetSettings,
+    /// Save the running VM's memory to its suspend slot and shut it down
+    SuspendVm,
+    /// Delete the VM's suspended state so it boots fresh
+    DiscardSuspendState,
+    /// Replace the VM's UEFI variables with a fresh copy of the template
+    ResetNvram,
+    /// Switch the VM to (`true`) or from the Secure Boot firmware
+    SetSecureBoot(bool),
+    /// Delete one of the VM's launch hook scripts
+    DeleteHook(&'static str),
+    /// The VM is sized beyond the host (warnings attached); launch anyway?
+    HostCapacity(Vec<String>),
+    /// Give the VM a serial console socket for Attach Console
+    AddSerialConsole,
+    /// Drop the selected VM's own ASCII art and show its OS logo again
+    ResetAsciiArt,
+    /// Delete a saved wizard template (name attached)
+    DeleteTemplate(String),
+    /// Delete an ISO from the ISO library; `used_by` names the VMs whose
+    /// launch.sh refers to it
+    DeleteIso {
+        path: PathBuf,
+        used_by: Vec<String>,
+    },
+    /// A VM (id attached) launched in install mode has shut down; stop
+    /// booting its install ISO on normal launches?
+    FinishInstall(String),
+    /// The VM wants KVM but the host can't provide it (reason attached);
+    /// offers a one-shot TCG launch.
+    KvmUnavailable(String),
+    /// Apply the IVSHMEM size recommended for a resolution to the config.
+    ApplyIvshmemSize {
+        width: u32,
+        height: u32,
+        size_mb: u32,
+    },
+    /// Rewrite the VM's qcow2 disks to reclaim freed space. `snapshots` is
+    /// how many internal snapshots a plain `qemu-img convert` would drop
+    /// (zero when virt-sparsify compacts in place instead).
+    CompactDisk {
+        snapshots: usize,
+    },
+    /// Hot-add a USB device (index into `usb_devices`) that host drivers
+    /// still have bound; QEMU will detach them
+    UsbHotAdd {
+        index: usize,
+        drivers: Vec<String>,
+    },
+    /// Select (or with `hot_add`, attach) a device that looks like the host's
+    /// keyboard or mouse
+    PassHostInput {
+        index: usize,
+        hot_add: bool,
+    },
+    /// Hash a chosen ISO and compare it with the profile's published SHA-256
+    VerifyIsoChecksum {
+        path: PathBuf,
+        expected: String,
+    },
 }
 
 /// Which management screen has unsaved changes (see [`ConfirmAction::UnsavedChanges`]).
@@ -139,8 +268,74 @@ pub enum FileBrowserMode {
     ImportConfig,
     Bios,
     Floppy,
+    /// Floppy image kept in drive B: from boot options
+    FloppyDrive,
+    /// Floppy to insert into a running VM's drive (removable media screen)
+    FloppySwap,
+    /// ISO to insert into a running VM's CD-ROM drive (removable media screen)
+    CdromSwap,
     /// GPU vBIOS ROM for single-GPU passthrough (#44)
     SingleGpuRom,
+    /// Secondary "tools" CD-ROM (e.g. virtio-win drivers) from boot options
+    ToolsIso,
+    /// Kernel image for direct kernel boot
+    Kernel,
+    /// Initial ramdisk for direct kernel boot
+    Initrd,
+    /// Text file with ASCII art to use as a VM's logo
+    AsciiArt,
+    /// File path setting (OVMF images, Looking Glass client) from Settings
+    SettingsFile(crate::ui::screens::settings::SettingsItem),
+}
+
+impl FileBrowserMode {
+    /// Key for this mode in `Config::last_browser_dirs`
+    pub fn config_key(self) -> &'static str {
+        match self {
+            Self::Iso | Self::CdromSwap => "iso",
+            Self::RecoveryImage => "recovery_image",
+            Self::Disk => "disk",
+            Self::Directory => "directory",
+            Self::ImportConfig => "import_config",
+            Self::Bios => "bios",
+            Self::Floppy | Self::FloppyDrive | Self::FloppySwap => "floppy",
+            Self::SingleGpuRom => "single_gpu_rom",
+            Self::ToolsIso => "tools_iso",
+            Self::Kernel => "kernel",
+            Self::Initrd => "initrd",
+            Self::AsciiArt => "ascii_art",
+            Self::SettingsFile(item) => item.help_key(),
+        }
+    }
+}
+
+/// File browser sort order for files (directories always stay first, by name)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum FileBrowserSort {
+    #[default]
+    Name,
+    /// Largest first
+    Size,
+    /// Newest first
+    Date,
+}
+
+impl FileBrowserSort {
+    pub fn next(self) -> Self {
+        match self {
+            Self::Name => Self::Size,
+            Self::Size => Self::Date,
+            Self::Date => Self::Name,
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Name => "name",
+            Self::Size => "size",
+            Self::Date => "date",
+        }
+    }
 }
 
 // DiskAction, WizardStep, WizardQemuConfig, CreateWizardState, NetworkSettingsState,
@@ -148,6 +343,76 @@ pub enum FileBrowserMode {
 // ImportableVm, ImportWizardState and related types are defined in wizard_types.rs
 // and imported above via `use crate::wizard_types::*`.
 
+/// An auto-launched Looking Glass client tied to a VM's lifetime
+pub struct LookingGlassClient {
+    pub child: std::process::Child,
+    /// Set once the VM shows up as running, so a client started before the
+    /// process monitor notices the VM isn't torn down straight away
+    pub seen_running: bool,
+}
+
+/// An action run on every VM marked in bulk mode
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BatchAction {
+    Start,
+    Stop,
+    Snapshot,
+    /// Move to trash
+    Delete,
+}
+
+impl BatchAction {
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Start => "Start",
+            Self::Stop => "Stop",
+            Self::Snapshot => "Snapshot",
+            Self::Delete => "Delete",
+        }
+    }
+
+    /// e.g. "Started" for status messages
+    pub fn past_tense(self) -> &'static str {
+        match self {
+            Self::Start => "Started",
+            Self::Stop => "Stopped",
+            Self::Snapshot => "Snapshotted",
+            Self::Delete => "Moved to trash",
+        }
+    }
+}
+
+/// A VM listed by the stale VM cleanup screen
+#[derive(Debug, Clone)]
+pub struct StaleVm {
+    pub id: String,
+    pub name: String,
+    pub last_launched: Option<chrono::DateTime<chrono::Local>>,
+    /// Bytes allocated by the VM's directory
+    pub size_bytes: u64,
+    /// Running VMs are listed but can't be marked for deletion
+    pub running: bool,
+}
+
+/// The wizard's running ISO download
+#[derive(Debug, Clone)]
+pub struct IsoDownload {
+    /// Tags the thread's messages; ones from an earlier download are ignored
+    pub generation: u64,
+    /// Where the ISO lands (curl writes `<dest>.part` until it finishes)
+    pub dest: PathBuf,
+    pub cancel: Arc<AtomicBool>,
+}
+
+/// Label and completion of a long background operation
+#[derive(Debug, Clone)]
+pub struct ProgressState {
+    pub label: String,
+    /// Percent complete (0-100), or `None` when the tool reports no progress
+    pub percent: Option<f64>,
+    pub started: Instant,
+}
+
 /// Application state
 pub struct App {
     /// Current screen
@@ -170,12 +435,23 @@ pub struct App {
     pub snapshots: Vec<Snapshot>,
     /// Selected snapshot index
     pub selected_snapshot: usize,
+    /// Names of snapshots marked for a batch delete
+    pub marked_snapshots: HashSet<String>,
     /// USB devices (cached)
     pub usb_devices: Vec<UsbDevice>,
     /// Selected USB devices for passthrough
     pub selected_usb_devices: Vec<usize>,
     /// USB selection as it was on entry / last save, for unsaved-change detection
     pub usb_selection_baseline: Vec<usize>,
+    /// Selected USB devices pinned to their physical port instead of
+    /// matching any device with the same vendor:product
+    pub usb_port_matched: Vec<usize>,
+    /// Port pinning as it was on entry / last save
+    pub usb_port_baseline: Vec<usize>,
+    /// (bus, address) of USB devices hot-added to the selected running VM
+    pub usb_attached: Vec<(u8, u8)>,
+    /// List hubs on the USB passthrough screen too
+    pub usb_show_all: bool,
     /// PCI devices (cached)
     pub pci_devices: Vec<PciDevice>,
     /// Selected PCI devices for passthrough
@@ -190,10 +466,34 @@ pub struct App {
     pub shared_folder_selected: usize,
     /// Multi-GPU passthrough status (prerequisites)
     pub multi_gpu_status: Option<MultiGpuPassthroughStatus>,
+    /// Rows of the passthrough readiness report
+    pub passthrough_diagnostics: Vec<DiagnosticCheck>,
+    /// Host hardware summary shown from the settings screen
+    pub host_info: Option<crate::hardware::HostInfo>,
+    /// Host hardware summary scroll position
+    pub host_info_scroll: u16,
+    /// Why the VM library couldn't be used at startup (library setup screen)
+    pub library_issue: Option<crate::vm::LibraryIssue>,
     /// Selected management menu item
     pub selected_menu_item: usize,
+    /// Disk shown on the disk I/O limits screen
+    pub throttle_disk: usize,
     /// Current boot mode
     pub boot_mode: BootMode,
+    /// Boot order / boot menu setting of the selected VM (boot options screen)
+    pub boot_order: BootOrder,
+    /// Secondary CD-ROM attached to the selected VM (boot options screen)
+    pub tools_iso: Option<PathBuf>,
+    /// Floppy image kept in drive B: of the selected VM (boot options screen)
+    pub floppy_drive: Option<PathBuf>,
+    /// Which drives the removable media screen shows
+    pub media_kind: MediaKind,
+    /// Floppy or CD-ROM drives of the running selected VM (removable media screen)
+    pub media_drives: Vec<RemovableDrive>,
+    /// Direct kernel boot settings being edited for the selected VM
+    pub direct_kernel: DirectKernelBoot,
+    /// CPU model and flags of the selected VM (CPU editor screen)
+    pub cpu_config: CpuConfig,
     /// Search query
     pub search_query: String,
     /// Input mode
@@ -208,6 +508,8 @@ pub struct App {
     pub status_time: Option<Instant>,
     /// Whether the app should quit
     pub should_quit: bool,
+    /// The user already answered the running-VMs quit prompt
+    pub quit_confirmed: bool,
     /// File browser current directory
     pub file_browser_dir: PathBuf,
     /// File browser entries (directories first, then files)
@@ -216,14 +518,34 @@ pub struct App {
     pub file_browser_selected: usize,
     /// File browser mode (determines file filter and behavior)
     pub file_browser_mode: FileBrowserMode,
+    /// File browser sort order for files
+    pub file_browser_sort: FileBrowserSort,
+    /// Error shown in the file browser (e.g. a bad jump-to path)
+    pub file_browser_error: Option<String>,
+    /// Selected row while the bookmark quick-pick is open
+    pub file_browser_bookmarks: Option<usize>,
     /// Text input buffer (for dialogs)
     pub text_input_buffer: String,
+    /// Rename the user chose to keep despite matching another VM's name
+    pub rename_duplicate_acknowledged: Option<String>,
     /// Channel for background operation results
     pub background_rx: Receiver<BackgroundResult>,
     /// Sender for background operations (clone this for threads)
     pub background_tx: Sender<BackgroundResult>,
+    /// Library directory polled by the watcher thread; follows
+    /// `config.vm_library_path` (see [`App::set_library_path`])
+    pub watched_library: Arc<Mutex<PathBuf>>,
     /// Whether a background operation is in progress
     pub loading: bool,
+    /// Progress of the running background operation, shown while `loading`
+    pub progress: Option<ProgressState>,
+    /// The wizard's running ISO download
+    pub iso_download: Option<IsoDownload>,
+    /// Generation of the most recently started ISO download
+    iso_download_generation: u64,
+    /// Thread of the most recently started ISO download; the next download
+    /// waits for it so two curls never write the same `.part` file
+    iso_download_thread: Option<std::thread::JoinHandle<()>>,
     /// Error dialog content (for detailed errors)
     pub error_detail: Option<String>,
     /// Error dialog scroll position
@@ -240,12 +562,50 @@ pub struct App {
     pub script_editor_modified: bool,
     /// Horizontal scroll offset for the editor
     pub script_editor_h_scroll: usize,
+    /// Hook file open in the script editor (`None` means launch.sh)
+    pub script_editor_hook: Option<&'static str>,
+    /// Selected hook on the launch hooks screen
+    pub launch_hooks_selected: usize,
+    /// Regenerated launch.sh awaiting confirmation
+    pub regenerated_script: Option<String>,
+    /// Line diff from the current to the regenerated launch.sh, as
+    /// (' ' | '-' | '+', line)
+    pub regenerate_diff: Vec<(char, String)>,
+    /// Scroll offset of the regenerate diff
+    pub regenerate_scroll: usize,
+    /// VMs not launched within `stale_vm_days`, largest first
+    pub stale_vms: Vec<StaleVm>,
+    /// Selected row on the stale VMs screen
+    pub stale_vms_selected: usize,
+    /// Main menu multi-select: Space marks VMs for batch actions
+    pub bulk_mode: bool,
+    /// Ids of VMs marked in bulk mode
+    pub marked_vms: HashSet<String>,
+    /// Ids of VMs launched in install mode this session; each gets asked
+    /// once whether the install is done when it shuts down
+    pub install_launches: HashSet<String>,
+    /// Ids of stale VMs marked for deletion
+    pub stale_vms_marked: HashSet<String>,
+    /// Days without a launch before a VM counts as stale
+    pub stale_vm_days: u32,
+    /// Deleted VMs in the trash directory, most recent first
+    pub trash_entries: Vec<crate::vm::trash::TrashedVm>,
+    /// Selected row on the trash screen
+    pub trash_selected: usize,
+    /// ISOs in the ISO library directory, by name
+    pub iso_library_entries: Vec<crate::vm::iso_library::LibraryIso>,
+    /// Whether the ISO library is being read in the background
+    pub iso_library_loading: bool,
+    /// Selected row on the ISO library screen
+    pub iso_library_selected: usize,
     /// QEMU profiles for VM creation
     pub qemu_profiles: QemuProfileStore,
     /// Settings help text store
     pub settings_help: SettingsHelpStore,
     /// Shared folders help text store
     pub shared_folders_help: SharedFoldersHelpStore,
+    /// Named wizard settings saved by the user
+    pub vm_templates: TemplateStore,
     /// VM creation wizard state
     pub wizard_state: Option<CreateWizardState>,
     /// Selected disk image format for the VM creation wizard
@@ -258,18 +618,47 @@ pub struct App {
     pub settings_editing: bool,
     /// Settings screen edit buffer (for text fields)
     pub settings_edit_buffer: String,
+    /// Settings screen filter (matched against setting names and help text)
+    pub settings_filter: String,
+    /// Settings screen filter field has focus
+    pub settings_filtering: bool,
     /// GPU passthrough validation result for settings screen
     pub settings_gpu_validation: Option<crate::ui::screens::settings::GpuValidationResult>,
     /// Cached display capabilities per emulator (populated at startup)
     pub display_capabilities: HashMap<String, Vec<String>>,
+    /// QEMU system emulators found on PATH (populated at startup)
+    pub available_emulators: Vec<String>,
+    /// Host RAM and CPU count, to flag VMs sized beyond the host
+    pub host_capacity: Option<crate::hardware::HostCapacity>,
 
     // === VM Process Monitoring ===
     /// Receives QEMU process info from background detection thread
     pub vm_status_rx: Receiver<Vec<QemuProcess>>,
     /// Map of vm_id -> PID for currently running VMs
     pub running_vms: HashMap<String, u32>,
+    /// Running VMs whose guest agent answers `guest-ping`
+    pub guest_ready_vms: HashSet<String>,
+    /// Map of vm_id -> current balloon size in MB for running VMs with a balloon
+    pub balloon_vms: HashMap<String, u64>,
+    /// A launch waiting for the sudo password to be asked outside the TUI
+    /// (the value is the launch's `force_tcg`)
+    pub pending_sudo_launch: Option<bool>,
+    /// Hand the terminal to the selected VM's serial console on the next tick
+    pub pending_console_attach: bool,
+    /// ssh into the selected VM as this user on the next tick
+    pub pending_ssh: Option<String>,
+    /// (VM id, addresses) the guest agent of the VM on the info screen reported
+    pub guest_addresses: Option<(String, Vec<String>)>,
+    /// Disk image usage by path, with when it was read (`qemu-img info` isn't free)
+    pub disk_usage_cache: HashMap<PathBuf, (Instant, Option<DiskUsage>)>,
     /// Map of vm_id -> when SIGTERM was sent (for force-stop timeout)
     pub stopping_vms: HashMap<String, Instant>,
+    /// Looking Glass clients we auto-launched, keyed by vm_id
+    pub looking_glass_clients: HashMap<String, LookingGlassClient>,
+    /// Map of vm_id -> scheduled snapshot interval in minutes
+    pub snapshot_schedules: HashMap<String, u32>,
+    /// Map of vm_id -> when the next scheduled snapshot is due (running VMs only)
+    pub next_scheduled_snapshot: HashMap<String, Instant>,
 
     // === Single GPU Passthrough ===
     /// Single GPU passthrough configuration
@@ -290,6 +679,8 @@ pub struct App {
     pub wizard_pf_selected: usize,
     /// Wizard port forward adding state
     pub wizard_adding_pf: Option<AddingPortForward>,
+    /// Selected VM while picking one to copy QEMU settings from in the wizard
+    pub wizard_copy_from: Option<usize>,
 }
 
 /// Entry in file browser
@@ -298,6 +689,24 @@ pub struct FileBrowserEntry {
     pub name: String,
     pub path: PathBuf,
     pub is_dir: bool,
+    /// Size in bytes (files only)
+    pub size: Option<u64>,
+    /// Last modification time
+    pub modified: Option<std::time::SystemTime>,
+    /// Image format from `qemu-img info` (Disk mode only)
+    pub format: Option<String>,
+}
+
+/// Sort file entries in place for the given order. Ties fall back to name.
+pub fn sort_file_entries(files: &mut [FileBrowserEntry], sort: FileBrowserSort) {
+    files.sort_by(|a, b| {
+        let by_name = a.name.to_lowercase().cmp(&b.name.to_lowercase());
+        match sort {
+            FileBrowserSort::Name => by_name,
+            FileBrowserSort::Size => b.size.cmp(&a.size).then(by_name),
+            FileBrowserSort::Date => b.modified.cmp(&a.modified).then(by_name),
+        }
+    });
 }
 
 /// Background operation result
@@ -317,6 +726,144 @@ pub enum BackgroundResult {
         success: bool,
         error: Option<String>,
     },
+    /// One snapshot of a batch delete; `remaining` are still queued
+    SnapshotBatchProgress {
+        name: String,
+        error: Option<String>,
+        remaining: usize,
+    },
+    /// One VM of a bulk-mode batch finished (`remaining` still to go)
+    BatchProgress {
+        action: BatchAction,
+        vm_id: String,
+        vm_name: String,
+        error: Option<String>,
+        remaining: usize,
+    },
+    /// A bulk-mode batch finished; failures are (VM name, error) pairs
+    BatchDone {
+        action: BatchAction,
+        succeeded: usize,
+        failed: Vec<(String, String)>,
+    },
+    /// VMs were moved to the trash (`freed` allocated bytes); failures are
+    /// (VM name, error) pairs
+    VmsTrashed {
+        deleted: Vec<String>,
+        failed: Vec<(String, String)>,
+        freed: u64,
+    },
+    /// A VM was moved back from the trash into the library
+    VmRestored {
+        id: String,
+        name: String,
+        error: Option<String>,
+    },
+    /// A batch delete finished; failures are (name, error) pairs
+    SnapshotBatchDeleted {
+        deleted: usize,
+        failed: Vec<(String, String)>,
+    },
+    /// A periodic snapshot of a running VM finished; `pruned` old ones removed
+    ScheduledSnapshot {
+        vm_id: String,
+        vm_name: String,
+        name: Option<String>,
+        pruned: usize,
+        error: Option<String>,
+    },
+    /// A VM's memory state was saved to disk (or saving failed)
+    VmSuspended {
+        vm_name: String,
+        error: Option<String>,
+    },
+    /// A VM's qcow2 disks were compacted; sizes are allocated bytes summed
+    /// over every disk
+    DiskCompacted {
+        vm_name: String,
+        before: u64,
+        after: u64,
+        error: Option<String>,
+    },
+    /// A launching VM's script reported a stage (e.g. waiting on pkexec)
+    LaunchStage { vm_name: String, stage: String },
+    /// The startup health check of a launched VM finished
+    LaunchHealth {
+        vm_name: String,
+        health: crate::vm::StartupHealth,
+    },
+    /// A paused test launch of a VM finished
+    TestLaunched {
+        vm_name: String,
+        result: Result<crate::vm::TestLaunch, String>,
+    },
+    /// QEMU printed warnings while a launched VM was starting
+    LaunchWarnings {
+        vm_name: String,
+        warnings: Vec<String>,
+    },
+    /// Progress update from a running operation; does not end `loading`
+    Progress { label: String, percent: Option<f64> },
+    /// Bytes fetched by the wizard's ISO download
+    IsoDownloadProgress {
+        generation: u64,
+        downloaded: u64,
+        total: Option<u64>,
+    },
+    /// The ISO library directory `dir` was read
+    IsoLibraryLoaded {
+        dir: PathBuf,
+        isos: Vec<crate::vm::iso_library::LibraryIso>,
+    },
+    /// The wizard's ISO download finished, failed or was cancelled
+    IsoDownloaded {
+        generation: u64,
+        path: PathBuf,
+        error: Option<String>,
+    },
+    /// An ISO was hashed; `actual` is `None` when hashing failed
+    ChecksumVerified {
+        path: PathBuf,
+        expected: String,
+        actual: Option<String>,
+        error: Option<String>,
+    },
+    /// The create wizard finished writing the VM (`launch_script` is set on
+    /// success)
+    VmCreated {
+        vm_name: String,
+        launch_script: Option<PathBuf>,
+        auto_launch: bool,
+        error: Option<String>,
+    },
+    /// The import wizard finished copying/converting disks and writing the VM
+    ImportFinished {
+        vm_name: String,
+        error: Option<String>,
+    },
+    /// A VM's bug report was saved (and copied, if a clipboard tool worked)
+    BugReportSaved {
+        vm_name: String,
+        result: Result<(PathBuf, Option<&'static str>), String>,
+    },
+    /// The guest agent of a running VM reported its IP addresses
+    GuestAddresses {
+        vm_id: String,
+        addresses: Vec<String>,
+    },
+    /// The host hardware summary was gathered
+    HostInfoReady(crate::hardware::HostInfo),
+    /// The watcher saw the VM library change on disk and rescanned it
+    LibraryChanged {
+        library: PathBuf,
+        vms: Vec<DiscoveredVm>,
+    },
+    /// A rescan of the library asked for from the main menu finished
+    VmsRefreshed {
+        library: PathBuf,
+        vms: Vec<DiscoveredVm>,
+        error: Option<String>,
+    },
     /// Reserved for async snapshot loading
     #[allow(dead_code)]
     SnapshotsLoaded {
@@ -335,7 +882,13 @@ impl App {
 
         // Step 1: Discover VMs
         progress(1, TOTAL_STEPS, "Discovering VMs...");
-        let vms = discover_vms(&config.vm_library_path)?;
+        // A missing library is offered for creation once the TUI is up
+        let library_issue = crate::vm::library_issue(&config.vm_library_path);
+        let vms = if library_issue.is_some() {
+            Vec::new()
+        } else {
+            discover_vms(&config.vm_library_path)?
+        };
         progress(1, TOTAL_STEPS, &format!("Found {} VMs", vms.len()));
 
         // Step 2: Load metadata
@@ -358,12 +911,10 @@ impl App {
         // Step 5: Load QEMU profiles
         progress(5, TOTAL_STEPS, "Loading QEMU profiles...");
         let mut qemu_profiles = QemuProfileStore::load_embedded();
-        let config_dir = Config::config_file_path()
-            .parent()
-            .map(|p| p.to_path_buf())
-            .unwrap_or_else(|| PathBuf::from("."));
+        let config_dir = Config::config_dir();
         let user_profiles_path = config_dir.join("qemu_profiles.toml");
         qemu_profiles.load_user_overrides(&user_profiles_path);
+        qemu_profiles.load_user_dir(&config.metadata_path.join(USER_PROFILES_DIR));
 
         // Load settings help text
         let mut settings_help = SettingsHelpStore::load_embedded();
@@ -374,19 +925,33 @@ impl App {
         let mut shared_folders_help = SharedFoldersHelpStore::load_embedded();
         shared_folders_help.load_user_overrides(&config_dir.join("shared_folders_help.toml"));
 
+        // Load saved VM templates
+        let vm_templates = TemplateStore::load(&config_dir.join("templates.toml"));
+
         // Step 6: Build visual order and detect display capabilities
         progress(6, TOTAL_STEPS, "Building VM list...");
         let filtered_indices: Vec<usize> = (0..vms.len()).collect();
-        let visual_order = build_visual_order(&vms, &filtered_indices, &hierarchy, &metadata);
+        let visual_order = build_visual_order(
+            &vms,
+            &filtered_indices,
+            &hierarchy,
+            &metadata,
+            config.vm_list_sort,
+        );
         let (background_tx, background_rx) = mpsc::channel();
+        let snapshot_schedules = load_snapshot_schedules(&vms);
 
         // Detect network capabilities
         let network_caps = crate::commands::qemu_system::detect_network_capabilities();
 
-        // Detect display capabilities for each available emulator
+        // Detect display capabilities for each available emulator (cached
+        // until the binary changes)
         let mut display_capabilities = HashMap::new();
-        for emulator in crate::commands::qemu_system::list_available_emulators() {
-            let displays = crate::commands::qemu_system::get_supported_displays(&emulator);
+        let available_emulators = crate::commands::qemu_system::list_available_emulators();
+        for emulator in available_emulators.iter().cloned() {
+            let displays = crate::commands::qemu_caps::capabilities(&emulator)
+                .map(|caps| caps.displays)
+                .unwrap_or_default();
             if !displays.is_empty() {
                 display_capabilities.insert(emulator, displays);
             }
@@ -397,16 +962,38 @@ impl App {
         std::thread::spawn(move || {
             loop {
                 std::thread::sleep(std::time::Duration::from_secs(3));
-                let processes = crate::vm::detect_qemu_processes();
+                let mut processes = crate::vm::detect_qemu_processes();
+                // Only VMs created with a guest agent channel have a qga.sock
+                for proc in &mut processes {
+                    if let Some(ref cwd) = proc.cwd {
+                        if cwd.join("qga.sock").exists() {
+                            proc.guest_agent_ready = crate::vm::guest_agent_ping(cwd);
+                        }
+                        if proc.cmdline.contains("virtio-balloon") {
+                            proc.balloon_mb = crate::vm::query_balloon_mb(cwd).ok();
+                        }
+                    }
+                }
                 if vm_status_tx.send(processes).is_err() {
                     break; // Receiver dropped (app exited)
                 }
             }
         });
 
+        let watched_library = Arc::new(Mutex::new(config.vm_library_path.clone()));
+        spawn_library_watcher(Arc::clone(&watched_library), background_tx.clone());
+
         Ok(Self {
-            screen: Screen::MainMenu,
-            screen_stack: Vec::new(),
+            screen: if library_issue.is_some() {
+                Screen::LibrarySetup
+            } else {
+                Screen::MainMenu
+            },
+            screen_stack: if library_issue.is_some() {
+                vec![Screen::MainMenu]
+            } else {
+                Vec::new()
+            },
             config,
             vms,
             selected_vm: 0,
@@ -415,9 +1002,14 @@ impl App {
             hierarchy,
             snapshots: Vec::new(),
             selected_snapshot: 0,
+            marked_snapshots: HashSet::new(),
             usb_devices: Vec::new(),
             selected_usb_devices: Vec::new(),
             usb_selection_baseline: Vec::new(),
+            usb_port_matched: Vec::new(),
+            usb_port_baseline: Vec::new(),
+            usb_attached: Vec::new(),
+            usb_show_all: false,
             pci_devices: Vec::new(),
             selected_pci_devices: Vec::new(),
             pci_selection_baseline: Vec::new(),
@@ -425,8 +1017,20 @@ impl App {
             shared_folders_baseline: Vec::new(),
             shared_folder_selected: 0,
             multi_gpu_status: None,
+            passthrough_diagnostics: Vec::new(),
+            host_info: None,
+            host_info_scroll: 0,
+            library_issue,
             selected_menu_item: 0,
+            throttle_disk: 0,
             boot_mode: BootMode::Normal,
+            boot_order: BootOrder::default(),
+            tools_iso: None,
+            floppy_drive: None,
+            media_kind: MediaKind::default(),
+            media_drives: Vec::new(),
+            direct_kernel: DirectKernelBoot::default(),
+            cpu_config: CpuConfig::default(),
             search_query: String::new(),
             input_mode: InputMode::Normal,
             filtered_indices,
@@ -434,14 +1038,24 @@ impl App {
             status_message: None,
             status_time: None,
             should_quit: false,
+            quit_confirmed: false,
             file_browser_dir: dirs::home_dir().unwrap_or_else(|| PathBuf::from("/")),
             file_browser_entries: Vec::new(),
             file_browser_selected: 0,
             file_browser_mode: FileBrowserMode::Iso,
+            file_browser_sort: FileBrowserSort::default(),
+            file_browser_error: None,
+            file_browser_bookmarks: None,
             text_input_buffer: String::new(),
+            rename_duplicate_acknowledged: None,
             background_rx,
             background_tx,
+            watched_library,
             loading: false,
+            progress: None,
+            iso_download: None,
+            iso_download_generation: 0,
+            iso_download_thread: None,
             error_detail: None,
             error_scroll: 0,
             info_scroll: 0,
@@ -450,22 +1064,54 @@ impl App {
             script_editor_cursor: (0, 0),
             script_editor_modified: false,
             script_editor_h_scroll: 0,
+            script_editor_hook: None,
+            launch_hooks_selected: 0,
+            regenerated_script: None,
+            regenerate_diff: Vec::new(),
+            regenerate_scroll: 0,
+            stale_vms: Vec::new(),
+            stale_vms_selected: 0,
+            stale_vms_marked: HashSet::new(),
+            stale_vm_days: 90,
+            bulk_mode: false,
+            marked_vms: HashSet::new(),
+            install_launches: HashSet::new(),
+            trash_entries: Vec::new(),
+            trash_selected: 0,
+            iso_library_entries: Vec::new(),
+            iso_library_loading: false,
+            iso_library_selected: 0,
             qemu_profiles,
             settings_help,
             shared_folders_help,
+            vm_templates,
             wizard_state: None,
             create_wizard_disk_format: DiskImageFormat::default(),
             import_state: None,
             settings_selected: 0,
             settings_editing: false,
             settings_edit_buffer: String::new(),
+            settings_filter: String::new(),
+            settings_filtering: false,
             settings_gpu_validation: None,
             display_capabilities,
+            available_emulators,
+            host_capacity: crate::hardware::HostCapacity::detect(),
 
             // VM Process Monitoring
             vm_status_rx,
             running_vms: HashMap::new(),
+            guest_ready_vms: HashSet::new(),
+            balloon_vms: HashMap::new(),
+            pending_sudo_launch: None,
+            pending_console_attach: false,
+            pending_ssh: None,
+            guest_addresses: None,
+            disk_usage_cache: HashMap::new(),
             stopping_vms: HashMap::new(),
+            looking_glass_clients: HashMap::new(),
+            snapshot_schedules,
+            next_scheduled_snapshot: HashMap::new(),
 
             // Single GPU Passthrough
             single_gpu_config: None,
@@ -478,6 +1124,7 @@ impl App {
             wizard_editing_port_forwards: false,
             wizard_pf_selected: 0,
             wizard_adding_pf: None,
+            wizard_copy_from: None,
         })
     }
 
@@ -553,13 +1200,30 @@ impl App {
             .or_else(|| Some(crate::metadata::default_os_info(&vm.id)))
     }
 
-    /// Get ASCII art for the selected VM
+    /// Get ASCII art for the selected VM: its own art, else its OS logo
     pub fn selected_vm_ascii(&self) -> &str {
         self.selected_vm()
-            .map(|vm| self.ascii_art.get_or_fallback(&vm.id))
+            .map(|vm| match vm.custom_ascii {
+                Some(ref art) => art.as_str(),
+                None => self.ascii_art.get_or_fallback(&vm.id),
+            })
             .unwrap_or("")
     }
 
+    /// Give the selected VM its own ASCII art from a text file, or go back
+    /// to the OS logo with `None`
+    pub fn set_selected_vm_ascii(&mut self, source: Option<&Path>) -> Result<()> {
+        let Some(vm) = self.selected_vm() else {
+            return Ok(());
+        };
+        let id = vm.id.clone();
+        let art = crate::vm::lifecycle::set_custom_ascii(&vm.path, source)?;
+        if let Some(vm) = self.vms.iter_mut().find(|vm| vm.id == id) {
+            vm.custom_ascii = art;
+        }
+        Ok(())
+    }
+
     /// Navigate to a new screen
     pub fn push_screen(&mut self, screen: Screen) {
         self.screen_stack.push(self.screen.clone());
@@ -628,6 +1292,7 @@ impl App {
             &self.filtered_indices,
             &self.hierarchy,
             &self.metadata,
+            self.config.vm_list_sort,
         );
 
         // Reset selection if out of bounds
@@ -636,17 +1301,374 @@ impl App {
         }
     }
 
+    /// Cycle the VM list sort (category, name, last launched, memory) and
+    /// save it, keeping the selected VM selected
+    pub fn cycle_vm_list_sort(&mut self) {
+        self.config.vm_list_sort = SortBy::next_list_sort(self.config.vm_list_sort);
+        self.resort_vm_list();
+        let label = SortBy::list_label(self.config.vm_list_sort);
+        match self.config.save() {
+            Ok(()) => self.set_status(format!("Sorting VMs by {}", label)),
+            Err(e) => self.set_status(format!("Sorting VMs by {} (not saved: {})", label, e)),
+        }
+    }
+
+    /// Re-sort the VM list after `config.vm_list_sort` changed, keeping the
+    /// selected VM selected
+    pub fn resort_vm_list(&mut self) {
+        let selected_id = self.selected_vm().map(|vm| vm.id.clone());
+        self.update_filter();
+        if let Some(id) = selected_id {
+            self.select_vm_by_id(&id);
+        }
+    }
+
+    /// Move the selection to the VM with `id` if it is in the visible list
+    pub fn select_vm_by_id(&mut self, id: &str) {
+        if let Some(pos) = self.visual_order.iter().position(|&filtered_idx| {
+            self.filtered_indices
+                .get(filtered_idx)
+                .and_then(|&i| self.vms.get(i))
+                .is_some_and(|vm| vm.id == id)
+        }) {
+            self.selected_vm = pos;
+        }
+    }
+
+    /// Note that a VM was just launched, re-sorting the list when it is
+    /// ordered by last launch
+    pub fn mark_vm_launched(&mut self, id: &str) {
+        if let Some(vm) = self.vms.iter_mut().find(|vm| vm.id == id) {
+            vm.last_launched = Some(chrono::Local::now());
+            vm.launch_count = vm.launch_count.saturating_add(1);
+            // The second launch settles the first boot
+            vm.first_boot_done = crate::vm::lifecycle::load_first_boot_done(&vm.path);
+        }
+        if self.config.vm_list_sort == Some(SortBy::LastLaunched) {
+            self.update_filter();
+            self.select_vm_by_id(id);
+        }
+    }
+
+    /// Enter or leave bulk mode; leaving clears the marks
+    pub fn toggle_bulk_mode(&mut self) {
+        self.bulk_mode = !self.bulk_mode;
+        self.marked_vms.clear();
+        if self.bulk_mode {
+            self.set_status("Bulk mode: [Space] mark, [a] all, then [Enter] start, [x] stop, [n] snapshot, [D] delete; [v] to leave");
+        } else {
+            self.set_status("Left bulk mode");
+        }
+    }
+
+    /// Mark or unmark the selected VM and move to the next one
+    pub fn toggle_vm_mark(&mut self) {
+        if let Some(id) = self.selected_vm().map(|vm| vm.id.clone()) {
+            if !self.marked_vms.remove(&id) {
+                self.marked_vms.insert(id);
+            }
+            self.select_next();
+        }
+    }
+
+    /// Mark every VM in the (filtered) list, or clear the marks if all are
+    /// already marked
+    pub fn toggle_mark_all_vms(&mut self) {
+        let visible: Vec<String> = self
+            .filtered_indices
+            .iter()
+            .filter_map(|&i| self.vms.get(i))
+            .map(|vm| vm.id.clone())
+            .collect();
+        if visible.iter().all(|id| self.marked_vms.contains(id)) {
+            self.marked_vms.clear();
+        } else {
+            self.marked_vms.extend(visible);
+        }
+    }
+
+    /// Marked VMs in list order
+    pub fn marked_vms_in_order(&self) -> Vec<&DiscoveredVm> {
+        self.visual_order
+            .iter()
+            .filter_map(|&f| self.filtered_indices.get(f))
+            .filter_map(|&i| self.vms.get(i))
+            .filter(|vm| self.marked_vms.contains(&vm.id))
+            .collect()
+    }
+
+    /// Run `action` on the marked VMs one after another on a background
+    /// thread, reporting each VM as it finishes
+    pub fn start_batch(&mut self, action: BatchAction) {
+        let jobs: Vec<(DiscoveredVm, Option<u32>)> = self
+            .marked_vms_in_order()
+            .into_iter()
+            .map(|vm| (vm.clone(), self.running_vms.get(&vm.id).copied()))
+            .collect();
+        self.run_batch(action, jobs);
+        self.marked_vms.clear();
+    }
+
+    /// Start every autostart VM that isn't already running, as a batch
+    pub fn start_autostart_vms(&mut self) {
+        let processes = crate::vm::detect_qemu_processes();
+        let jobs: Vec<(DiscoveredVm, Option<u32>)> = self
+            .vms
+            .iter()
+            .filter(|vm| {
+                vm.autostart && crate::vm::lifecycle::vm_process_pid(vm, &processes).is_none()
+            })
+            .map(|vm| (vm.clone(), None))
+            .collect();
+        self.run_batch(BatchAction::Start, jobs);
+    }
+
+    /// Mark the first boot of VM `id` as done (or not), so normal launches
+    /// skip (or boot) its install ISO
+    pub fn set_first_boot_done(&mut self, id: &str, done: bool) -> Result<()> {
+        let Some(vm) = self.vms.iter_mut().find(|vm| vm.id == id) else {
+            return Ok(());
+        };
+        crate::vm::lifecycle::set_first_boot_done(&vm.path, done)?;
+        vm.first_boot_done = Some(done);
+        Ok(())
+    }
+
+    /// Turn autostart on or off for the selected VM
+    pub fn toggle_selected_autostart(&mut self) -> Result<bool> {
+        let Some(vm) = self.selected_vm() else {
+            return Ok(false);
+        };
+        let (id, enable) = (vm.id.clone(), !vm.autostart);
+        crate::vm::lifecycle::set_autostart(&vm.path, enable)?;
+        if let Some(vm) = self.vms.iter_mut().find(|vm| vm.id == id) {
+            vm.autostart = enable;
+        }
+        Ok(enable)
+    }
+
+    fn run_batch(&mut self, action: BatchAction, jobs: Vec<(DiscoveredVm, Option<u32>)>) {
+        if jobs.is_empty() {
+            return;
+        }
+        let settings = BatchSettings {
+            trash_dir: self.config.trash_dir(),
+            snapshot_prefix: self.config.snapshot_prefix.clone(),
+            spice_tcp: self.config.spice_tcp,
+            default_ivshmem_size_mb: self.config.default_ivshmem_size_mb,
+            start_timeout_secs: self.config.vm_start_timeout_secs,
+            host_capacity: self.host_capacity,
+        };
+        // Starts check the whole library for running VMs sharing a disk
+        let library = match action {
+            BatchAction::Start => self.vms.clone(),
+            _ => Vec::new(),
+        };
+        let tx = self.background_tx.clone();
+        self.start_progress(format!("{} {} VMs", action.label(), jobs.len()));
+
+        std::thread::spawn(move || {
+            let total = jobs.len();
+            let mut succeeded = 0;
+            let mut failed = Vec::new();
+            for (i, (vm, pid)) in jobs.into_iter().enumerate() {
+                let vm_name = vm.display_name();
+                let _ = tx.send(BackgroundResult::Progress {
+                    label: format!("{} {} ({}/{})", action.label(), vm_name, i + 1, total),
+                    percent: Some(i as f64 * 100.0 / total as f64),
+                });
+                let result = run_batch_action(action, &vm, pid, &library, &settings, &tx);
+                let error = result.err().map(|e| format!("{:#}", e));
+                match &error {
+                    None => succeeded += 1,
+                    Some(e) => failed.push((vm_name.clone(), e.clone())),
+                }
+                let _ = tx.send(BackgroundResult::BatchProgress {
+                    action,
+                    vm_id: vm.id.clone(),
+                    vm_name,
+                    error,
+                    remaining: total - i - 1,
+                });
+            }
+            let _ = tx.send(BackgroundResult::BatchDone {
+                action,
+                succeeded,
+                failed,
+            });
+        });
+    }
+
+    /// Rebuild the stale VM list for `stale_vm_days`, dropping marks on VMs
+    /// no longer listed or now running
+    pub fn load_stale_vms(&mut self) {
+        let now = chrono::Local::now();
+        let mut stale: Vec<StaleVm> = self
+            .vms
+            .iter()
+            .filter(|vm| crate::vm::discovery::is_stale(vm, self.stale_vm_days, now))
+            .map(|vm| StaleVm {
+                id: vm.id.clone(),
+                name: vm.display_name(),
+                last_launched: vm.last_launched,
+                size_bytes: crate::fs::allocated_size(&vm.path),
+                running: self.running_vms.contains_key(&vm.id),
+            })
+            .collect();
+        stale.sort_by_key(|vm| std::cmp::Reverse(vm.size_bytes));
+
+        self.stale_vms_marked
+            .retain(|id| stale.iter().any(|vm| &vm.id == id && !vm.running));
+        self.stale_vms = stale;
+        self.stale_vms_selected = self
+            .stale_vms_selected
+            .min(self.stale_vms.len().saturating_sub(1));
+    }
+
+    /// Move `vms` to the trash one after another on a background thread. A
+    /// trash on another filesystem means copying every disk, so each move
+    /// reports its progress. `skipped` (VM name, reason) pairs are reported
+    /// with the failures.
+    pub fn start_trashing_vms(&mut self, vms: Vec<DiscoveredVm>, skipped: Vec<(String, String)>) {
+        if vms.is_empty() && skipped.is_empty() {
+            return;
+        }
+        let trash_dir = self.config.trash_dir();
+        let tx = self.background_tx.clone();
+        self.start_progress("Moving VMs to trash");
+
+        std::thread::spawn(move || {
+            let total = vms.len();
+            let mut deleted = Vec::new();
+            let mut failed = skipped;
+            let mut freed = 0;
+            for (i, vm) in vms.iter().enumerate() {
+                let name = vm.display_name();
+                let label = if total > 1 {
+                    format!("Moving {} to trash ({}/{})", name, i + 1, total)
+                } else {
+                    format!("Moving {} to trash", name)
+                };
+                let _ = tx.send(BackgroundResult::Progress {
+                    label: label.clone(),
+                    percent: None,
+                });
+                let size = crate::fs::allocated_size(&vm.path);
+                let result = crate::vm::lifecycle::delete_vm(vm, Some(&trash_dir), |percent| {
+                    let _ = tx.send(BackgroundResult::Progress {
+                        label: label.clone(),
+                        percent: Some(percent),
+                    });
+                });
+                match result {
+                    Ok(()) => {
+                        freed += size;
+                        deleted.push(name);
+                    }
+                    Err(e) => failed.push((name, format!("{:#}", e))),
+                }
+            }
+            let _ = tx.send(BackgroundResult::VmsTrashed {
+                deleted,
+                failed,
+                freed,
+            });
+        });
+    }
+
+    /// Move a trashed VM back into the library on a background thread
+    pub fn start_restoring_vm(&mut self, entry: crate::vm::trash::TrashedVm) {
+        let tx = self.background_tx.clone();
+        let label = format!("Restoring {}", entry.name);
+        self.start_progress(label.clone());
+
+        std::thread::spawn(move || {
+            let result = crate::vm::trash::restore(&entry, |percent| {
+                let _ = tx.send(BackgroundResult::Progress {
+                    label: label.clone(),
+                    percent: Some(percent),
+                });
+            });
+            let _ = tx.send(BackgroundResult::VmRestored {
+                id: entry.id,
+                name: entry.name,
+                error: result.err().map(|e| format!("{:#}", e)),
+            });
+        });
+    }
+
+    /// Re-read the trash directory
+    pub fn load_trash(&mut self) {
+        self.trash_entries =
+            crate::vm::trash::list_trash(&self.config.trash_dir(), &self.config.vm_library_path);
+        self.trash_selected = self
+            .trash_selected
+            .min(self.trash_entries.len().saturating_sub(1));
+    }
+
+    /// Re-read the ISO library directory
+    pub fn load_iso_library(&mut self) {
+        // Detecting the OS reads each image, which is slow on large ones
+        let dir = self.config.iso_dir();
+        let tx = self.background_tx.clone();
+        self.iso_library_loading = true;
+        std::thread::spawn(move || {
+            let isos = crate::vm::iso_library::list_isos(&dir);
+            let _ = tx.send(BackgroundResult::IsoLibraryLoaded { dir, isos });
+        });
+    }
+
     /// Refresh VM list
     pub fn refresh_vms(&mut self) -> Result<()> {
-        self.vms = discover_vms(&self.config.vm_library_path)?;
-        self.update_filter();
+        let vms = discover_vms(&self.config.vm_library_path)?;
+        self.replace_vms(vms);
         Ok(())
     }
 
+    /// Rescan the library on a background thread (a network-mounted library
+    /// can take a while), keeping the selected VM once it finishes
+    pub fn start_vm_refresh(&mut self) {
+        let library = self.config.vm_library_path.clone();
+        let tx = self.background_tx.clone();
+        self.start_progress("Rescanning VM library");
+
+        std::thread::spawn(move || {
+            let (vms, error) = match discover_vms(&library) {
+                Ok(vms) => (vms, None),
+                Err(e) => (Vec::new(), Some(format!("{:#}", e))),
+            };
+            let _ = tx.send(BackgroundResult::VmsRefreshed {
+                library,
+                vms,
+                error,
+            });
+        });
+    }
+
+    /// Swap in a fresh VM list, keeping the same VM selected if it still exists
+    fn replace_vms(&mut self, vms: Vec<DiscoveredVm>) {
+        let selected_id = self.selected_vm().map(|vm| vm.id.clone());
+        self.vms = vms;
+        self.snapshot_schedules = load_snapshot_schedules(&self.vms);
+        self.update_filter();
+        if let Some(id) = selected_id {
+            self.select_vm_by_id(&id);
+        }
+    }
+
+    /// Point the app, and the library watcher, at another VM library
+    pub fn set_library_path(&mut self, path: PathBuf) {
+        if let Ok(mut watched) = self.watched_library.lock() {
+            *watched = path.clone();
+        }
+        self.config.vm_library_path = path;
+    }
+
     /// Load snapshots for the current VM
     pub fn load_snapshots(&mut self) -> Result<()> {
         self.snapshots.clear();
         self.selected_snapshot = 0;
+        self.marked_snapshots.clear();
 
         if let Some(vm) = self.selected_vm() {
             if let Some(disk) = vm.config.primary_disk() {
@@ -663,18 +1685,151 @@ impl App {
     pub fn load_usb_devices(&mut self) -> Result<()> {
         self.usb_devices = crate::hardware::enumerate_usb_devices()?;
         self.selected_usb_devices.clear();
+        self.usb_port_matched.clear();
         Ok(())
     }
 
+    /// Re-read which USB devices are hot-added to the selected VM; empty when
+    /// it isn't running or QMP doesn't answer
+    pub fn refresh_usb_attached(&mut self) {
+        self.usb_attached = match self.selected_vm() {
+            Some(vm) if self.running_vms.contains_key(&vm.id) => {
+                crate::vm::list_hotplugged_usb(&vm.path).unwrap_or_default()
+            }
+            _ => Vec::new(),
+        };
+    }
+
+    /// Indices into `usb_devices` shown on the passthrough screen; hubs are
+    /// hidden unless `usb_show_all` is set. `selected_menu_item` indexes this.
+    pub fn visible_usb_devices(&self) -> Vec<usize> {
+        self.usb_devices
+            .iter()
+            .enumerate()
+            .filter(|(_, d)| self.usb_show_all || !d.is_hub())
+            .map(|(i, _)| i)
+            .collect()
+    }
+
+    /// The `usb_devices` index under the cursor on the passthrough screen
+    pub fn usb_device_at_cursor(&self) -> Option<usize> {
+        self.visible_usb_devices()
+            .get(self.selected_menu_item)
+            .copied()
+    }
+
+    /// Show or hide hubs, keeping the cursor on the same device when it
+    /// stays visible
+    pub fn toggle_usb_show_all(&mut self) {
+        let current = self.usb_device_at_cursor();
+        self.usb_show_all = !self.usb_show_all;
+        let visible = self.visible_usb_devices();
+        self.selected_menu_item = current
+            .and_then(|i| visible.iter().position(|&v| v == i))
+            .unwrap_or(0);
+    }
+
     /// Toggle USB device selection
     pub fn toggle_usb_device(&mut self, index: usize) {
         if let Some(pos) = self.selected_usb_devices.iter().position(|&i| i == index) {
             self.selected_usb_devices.remove(pos);
+            self.usb_port_matched.retain(|&i| i != index);
         } else {
             self.selected_usb_devices.push(index);
         }
     }
 
+    /// Switch a device between matching by vendor:product and by physical
+    /// port, selecting it if needed. Returns false if its port is unknown.
+    pub fn toggle_usb_port_match(&mut self, index: usize) -> bool {
+        if self
+            .usb_devices
+            .get(index)
+            .is_none_or(|d| d.port_path.is_empty())
+        {
+            return false;
+        }
+        if let Some(pos) = self.usb_port_matched.iter().position(|&i| i == index) {
+            self.usb_port_matched.remove(pos);
+        } else {
+            self.usb_port_matched.push(index);
+            if !self.selected_usb_devices.contains(&index) {
+                self.selected_usb_devices.push(index);
+            }
+        }
+        true
+    }
+
+    /// Pre-select the devices saved in the VM's launch.sh: port-pinned
+    /// entries by bus and port, the rest by vendor:product. Each saved entry
+    /// claims one device, so two identical devices both get restored.
+    pub fn restore_usb_selections(&mut self) {
+        let saved = match self.selected_vm() {
+            Some(vm) => crate::vm::load_usb_passthrough(vm),
+            None => return,
+        };
+        self.selected_usb_devices.clear();
+        self.usb_port_matched.clear();
+        for saved_dev in &saved {
+            let found = self.usb_devices.iter().enumerate().position(|(i, dev)| {
+                !self.selected_usb_devices.contains(&i)
+                    && dev.vendor_id == saved_dev.vendor_id
+                    && dev.product_id == saved_dev.product_id
+                    && saved_dev
+                        .port
+                        .as_ref()
+                        .is_none_or(|p| p.bus == dev.bus_num && p.path == dev.port_path)
+            });
+            if let Some(i) = found {
+                self.selected_usb_devices.push(i);
+                if saved_dev.port.is_some() {
+                    self.usb_port_matched.push(i);
+                }
+            }
+        }
+    }
+
+    /// The selected USB devices as launch.sh / launch-time passthrough entries
+    pub fn usb_passthrough_selection(&self) -> Vec<crate::vm::UsbPassthrough> {
+        self.selected_usb_devices
+            .iter()
+            .filter_map(|&i| self.usb_devices.get(i).map(|d| (i, d)))
+            .map(|(i, d)| crate::vm::UsbPassthrough {
+                vendor_id: d.vendor_id,
+                product_id: d.product_id,
+                usb_version: d.usb_version,
+                port: self
+                    .usb_port_matched
+                    .contains(&i)
+                    .then(|| crate::vm::UsbPort {
+                        bus: d.bus_num,
+                        path: d.port_path.clone(),
+                    }),
+            })
+            .collect()
+    }
+
+    /// Refresh the passthrough status and build the readiness report for the
+    /// currently selected GPUs (or every passthrough-capable GPU if none are).
+    pub fn run_passthrough_diagnostics(&mut self) {
+        let status = crate::hardware::check_multi_gpu_passthrough_status();
+        let targets: Vec<PciDevice> = self
+            .selected_pci_devices
+            .iter()
+            .filter_map(|&i| self.pci_devices.get(i))
+            .filter(|d| d.is_gpu())
+            .cloned()
+            .collect();
+        self.passthrough_diagnostics = status.diagnostics(&targets);
+        self.passthrough_diagnostics.extend(
+            self.selected_pci_devices
+                .iter()
+                .filter_map(|&i| self.pci_devices.get(i))
+                .map(crate::hardware::pci::check_device_driver),
+        );
+        self.multi_gpu_status = Some(status);
+    }
+
     /// Load PCI devices
     pub fn load_pci_devices(&mut self) -> Result<()> {
         self.pci_devices = crate::hardware::enumerate_pci_devices()?;
@@ -709,6 +1864,7 @@ impl App {
     /// the user made during this visit (#52).
     pub fn snapshot_usb_baseline(&mut self) {
         self.usb_selection_baseline = self.selected_usb_devices.clone();
+        self.usb_port_baseline = self.usb_port_matched.clone();
     }
 
     /// Record the current PCI selection as the saved baseline.
@@ -726,11 +1882,9 @@ impl App {
     /// Compares against the on-entry baseline (not disk) so a saved-but-now
     /// disconnected device doesn't register as an unsaved change (#52).
     pub fn usb_selection_dirty(&self) -> bool {
-        let current: std::collections::BTreeSet<usize> =
-            self.selected_usb_devices.iter().copied().collect();
-        let baseline: std::collections::BTreeSet<usize> =
-            self.usb_selection_baseline.iter().copied().collect();
-        current != baseline
+        let as_set = |v: &[usize]| v.iter().copied().collect::<std::collections::BTreeSet<_>>();
+        as_set(&self.selected_usb_devices) != as_set(&self.usb_selection_baseline)
+            || as_set(&self.usb_port_matched) != as_set(&self.usb_port_baseline)
     }
 
     /// True if the PCI selection changed since it was last entered or saved.
@@ -805,23 +1959,47 @@ impl App {
     }
 
     /// Toggle PCI device selection
-    pub fn toggle_pci_device(&mut self, index: usize) {
+    ///
+    /// Checking a GPU also checks its HDMI/DP audio function, since the two
+    /// normally share an IOMMU group and must be passed through together.
+    /// Returns the index of the audio device that was selected alongside, so
+    /// the caller can tell the user; it can still be unchecked on its own.
+    pub fn toggle_pci_device(&mut self, index: usize) -> Option<usize> {
         // Don't allow selecting boot VGA
         if let Some(device) = self.pci_devices.get(index) {
             if device.is_boot_vga {
-                return;
+                return None;
             }
         }
 
         if let Some(pos) = self.selected_pci_devices.iter().position(|&i| i == index) {
             self.selected_pci_devices.remove(pos);
-        } else {
-            self.selected_pci_devices.push(index);
+            return None;
         }
-    }
+        self.selected_pci_devices.push(index);
 
-    /// Auto-select a GPU and its paired audio device
-    pub fn auto_select_gpu(&mut self, gpu_index: usize) {
+        if !self.pci_devices.get(index).is_some_and(|d| d.is_gpu()) {
+            return None;
+        }
+        let audio_idx = self.gpu_audio_pair_index(index)?;
+        if self.selected_pci_devices.contains(&audio_idx) {
+            return None;
+        }
+        self.selected_pci_devices.push(audio_idx);
+        Some(audio_idx)
+    }
+
+    /// Index into `pci_devices` of the audio function paired with a GPU
+    fn gpu_audio_pair_index(&self, gpu_index: usize) -> Option<usize> {
+        let gpu = self.pci_devices.get(gpu_index)?;
+        let audio = crate::hardware::find_gpu_audio_pair(gpu, &self.pci_devices)?;
+        self.pci_devices
+            .iter()
+            .position(|d| d.address == audio.address)
+    }
+
+    /// Auto-select a GPU and its paired audio device
+    pub fn auto_select_gpu(&mut self, gpu_index: usize) {
         // Clear existing selection
         self.selected_pci_devices.clear();
 
@@ -834,14 +2012,8 @@ impl App {
             self.selected_pci_devices.push(gpu_index);
 
             // Try to find and select the paired audio device
-            if let Some(audio) = crate::hardware::find_gpu_audio_pair(gpu, &self.pci_devices) {
-                if let Some(audio_idx) = self
-                    .pci_devices
-                    .iter()
-                    .position(|d| d.address == audio.address)
-                {
-                    self.selected_pci_devices.push(audio_idx);
-                }
+            if let Some(audio_idx) = self.gpu_audio_pair_index(gpu_index) {
+                self.selected_pci_devices.push(audio_idx);
             }
         }
     }
@@ -864,22 +2036,40 @@ impl App {
 
     /// Get launch options based on current state
     pub fn get_launch_options(&self) -> LaunchOptions {
-        let usb_devices = self
-            .selected_usb_devices
-            .iter()
-            .filter_map(|&i| self.usb_devices.get(i))
-            .map(|d| crate::vm::UsbPassthrough {
-                vendor_id: d.vendor_id,
-                product_id: d.product_id,
-                usb_version: d.usb_version,
-            })
-            .collect();
+        let usb_devices = self.usb_passthrough_selection();
 
         LaunchOptions {
             boot_mode: self.boot_mode.clone(),
             extra_args: Vec::new(),
             usb_devices,
+            force_tcg: false,
+            resume_suspended: false,
+            spice_tcp: self.config.spice_tcp,
+        }
+    }
+
+    /// Whether a pending quit should first ask what to do with running VMs
+    pub fn needs_quit_confirmation(&self) -> bool {
+        self.config.confirm_quit_with_running_vms
+            && !self.quit_confirmed
+            && !self.running_vms.is_empty()
+    }
+
+    /// Send a graceful stop to every running VM. Returns how many were signalled.
+    pub fn stop_all_running_vms(&mut self) -> usize {
+        let mut stopped = 0;
+        let running: Vec<(String, u32)> = self
+            .running_vms
+            .iter()
+            .map(|(id, pid)| (id.clone(), *pid))
+            .collect();
+        for (id, pid) in running {
+            if crate::vm::stop_vm_by_pid(pid).is_ok() {
+                self.stopping_vms.insert(id, Instant::now());
+                stopped += 1;
+            }
         }
+        stopped
     }
 
     /// Set a status message (auto-clears after 5 seconds)
@@ -901,6 +2091,16 @@ impl App {
         self.status_time = None;
     }
 
+    /// Mark a background operation as started and show its progress overlay
+    pub fn start_progress(&mut self, label: impl Into<String>) {
+        self.loading = true;
+        self.progress = Some(ProgressState {
+            label: label.into(),
+            percent: None,
+            started: Instant::now(),
+        });
+    }
+
     /// Check and clear status if expired (call in event loop)
     pub fn check_status_expiry(&mut self) {
         if let Some(time) = self.status_time {
@@ -914,7 +2114,26 @@ impl App {
     pub fn check_background_results(&mut self) {
         // Non-blocking check for results
         while let Ok(result) = self.background_rx.try_recv() {
-            self.loading = false;
+            // Scheduled snapshots and ISO downloads run without `loading`;
+            // progress messages arrive while the operation is still running
+            if !matches!(
+                result,
+                BackgroundResult::ScheduledSnapshot { .. }
+                    | BackgroundResult::Progress { .. }
+                    | BackgroundResult::SnapshotBatchProgress { .. }
+                    | BackgroundResult::BatchProgress { .. }
+                    | BackgroundResult::IsoDownloadProgress { .. }
+                    | BackgroundResult::IsoDownloaded { .. }
+                    | BackgroundResult::LaunchStage { .. }
+                    | BackgroundResult::LaunchHealth { .. }
+                    | BackgroundResult::LaunchWarnings { .. }
+                    | BackgroundResult::GuestAddresses { .. }
+                    | BackgroundResult::LibraryChanged { .. }
+                    | BackgroundResult::IsoLibraryLoaded { .. }
+            ) {
+                self.loading = false;
+                self.progress = None;
+            }
             match result {
                 BackgroundResult::SnapshotCreated {
                     name,
@@ -952,6 +2171,406 @@ impl App {
                         self.set_status(format!("Error deleting snapshot: {}", e));
                     }
                 }
+                BackgroundResult::SnapshotBatchProgress {
+                    name,
+                    error,
+                    remaining,
+                } => {
+                    // More deletions follow on the same thread; the batch
+                    // result ends `loading`
+                    match error {
+                        None => self
+                            .set_status(format!("Deleted snapshot: {} ({} left)", name, remaining)),
+                        Some(e) => self.set_status(format!(
+                            "Error deleting snapshot {}: {} ({} left)",
+                            name, e, remaining
+                        )),
+                    }
+                }
+                BackgroundResult::BatchProgress {
+                    action,
+                    vm_id,
+                    vm_name,
+                    error,
+                    remaining,
+                } => match error {
+                    None => {
+                        match action {
+                            BatchAction::Start => self.mark_vm_launched(&vm_id),
+                            BatchAction::Stop => {
+                                self.stopping_vms.insert(vm_id, Instant::now());
+                            }
+                            BatchAction::Snapshot | BatchAction::Delete => {}
+                        }
+                        self.set_status(format!(
+                            "{}: {} ({} left)",
+                            action.past_tense(),
+                            vm_name,
+                            remaining
+                        ));
+                    }
+                    Some(e) => self.set_status(format!(
+                        "{} failed for {}: {} ({} left)",
+                        action.label(),
+                        vm_name,
+                        e,
+                        remaining
+                    )),
+                },
+                BackgroundResult::BatchDone {
+                    action,
+                    succeeded,
+                    failed,
+                } => {
+                    if action == BatchAction::Delete {
+                        let _ = self.refresh_vms();
+                    }
+                    if failed.is_empty() {
+                        self.set_status(format!("{} {} VMs", action.past_tense(), succeeded));
+                    } else {
+                        let failures: Vec<String> = failed
+                            .iter()
+                            .map(|(name, e)| format!("{}: {}", name, e))
+                            .collect();
+                        self.show_error(format!(
+                            "{} {} of {} VMs. Failed:\n\n{}",
+                            action.past_tense(),
+                            succeeded,
+                            succeeded + failed.len(),
+                            failures.join("\n")
+                        ));
+                    }
+                }
+                BackgroundResult::SnapshotBatchDeleted { deleted, failed } => {
+                    if failed.is_empty() {
+                        self.set_status(format!("Deleted {} snapshots", deleted));
+                    } else {
+                        let failures: Vec<String> = failed
+                            .iter()
+                            .map(|(name, e)| format!("{} ({})", name, e))
+                            .collect();
+                        self.set_status(format!(
+                            "Deleted {} of {} snapshots; failed: {}",
+                            deleted,
+                            deleted + failed.len(),
+                            failures.join(", ")
+                        ));
+                    }
+                    let _ = self.load_snapshots();
+                }
+                BackgroundResult::VmsTrashed {
+                    deleted,
+                    failed,
+                    freed,
+                } => {
+                    let _ = self.refresh_vms();
+                    self.load_stale_vms();
+                    match (deleted.as_slice(), failed.is_empty()) {
+                        ([name], true) => self.set_status(format!("Deleted: {}", name)),
+                        (_, true) => self.set_status(format!(
+                            "Moved {} VMs to trash ({} once the trash is emptied)",
+                            deleted.len(),
+                            crate::vm::snapshot::format_size(freed)
+                        )),
+                        (_, false) => {
+                            let failures: Vec<String> = failed
+                                .iter()
+                                .map(|(name, e)| format!("{}: {}", name, e))
+                                .collect();
+                            self.show_error(format!(
+                                "Moved {} VMs to trash; {} failed:\n\n{}",
+                                deleted.len(),
+                                failed.len(),
+                                failures.join("\n")
+                            ));
+                        }
+                    }
+                }
+                BackgroundResult::VmRestored { id, name, error } => match error {
+                    None => {
+                        let _ = self.refresh_vms();
+                        self.select_vm_by_id(&id);
+                        self.load_trash();
+                        self.set_status(format!("Restored {}", name));
+                    }
+                    Some(e) => self.show_error(format!("Failed to restore {}\n\n{}", name, e)),
+                },
+                BackgroundResult::IsoDownloadProgress {
+                    generation,
+                    downloaded,
+                    total,
+                } => {
+                    if !self.is_current_iso_download(generation) {
+                        continue;
+                    }
+                    if let Some(ref mut state) = self.wizard_state {
+                        state.iso_download_bytes = downloaded;
+                        state.iso_download_progress = match total {
+                            Some(total) if total > 0 => downloaded as f32 / total as f32,
+                            _ => 0.0,
+                        };
+                    }
+                }
+                BackgroundResult::IsoLibraryLoaded { dir, isos } => {
+                    // A rescan of a directory since changed in Settings is stale
+                    if dir == self.config.iso_dir() {
+                        self.iso_library_loading = false;
+                        self.iso_library_entries = isos;
+                        self.iso_library_selected = self
+                            .iso_library_selected
+                            .min(self.iso_library_entries.len().saturating_sub(1));
+                    }
+                }
+                BackgroundResult::IsoDownloaded {
+                    generation,
+                    path,
+                    error,
+                } => {
+                    // A cancelled download reports once its curl has exited
+                    if self.is_current_iso_download(generation) {
+                        self.finish_iso_download(path, error)
+                    }
+                }
+                BackgroundResult::ChecksumVerified {
+                    path,
+                    expected,
+                    actual,
+                    error,
+                } => {
+                    let file = path
+                        .file_name()
+                        .map(|n| n.to_string_lossy().into_owned())
+                        .unwrap_or_else(|| path.display().to_string());
+                    match (actual, error) {
+                        (Some(actual), _) if actual == expected => {
+                            self.set_status(format!("Checksum OK: {} matches SHA-256", file))
+                        }
+                        (Some(actual), _) => self.show_error(format!(
+                            "Checksum MISMATCH for {}\n\nExpected: {}\nActual:   {}\n\n\
+                             The image is corrupt or not the release you expected. \
+                             Download it again before installing from it.",
+                            path.display(),
+                            expected,
+                            actual
+                        )),
+                        (None, e) => self.show_error(format!(
+                            "Could not verify {}: {}",
+                            path.display(),
+                            e.unwrap_or_default()
+                        )),
+                    }
+                }
+                BackgroundResult::VmCreated {
+                    vm_name,
+                    launch_script,
+                    auto_launch,
+                    error,
+                } => match (launch_script, error) {
+                    (Some(launch_script), None) => {
+                        crate::ui::screens::create_wizard::finish_create(
+                            self,
+                            &vm_name,
+                            &launch_script,
+                            auto_launch,
+                        )
+                    }
+                    (_, e) => {
+                        let e = e.unwrap_or_default();
+                        match self.wizard_state {
+                            Some(ref mut state) => {
+                                state.error_message = Some(format!("Failed to create VM: {}", e))
+                            }
+                            None => self.set_status(format!("Failed to create {}: {}", vm_name, e)),
+                        }
+                    }
+                },
+                BackgroundResult::ImportFinished { vm_name, error } => match error {
+                    None => {
+                        self.import_state = None;
+                        while self.screen == Screen::ImportWizard {
+                            self.pop_screen();
+                        }
+                        let _ = self.refresh_vms();
+                        self.set_status(format!("Imported: {}", vm_name));
+                    }
+                    Some(e) => {
+                        // Keep the wizard open on the review step with the reason
+                        match self.import_state {
+                            Some(ref mut state) => {
+                                state.error_message = Some(format!("Import failed: {}", e))
+                            }
+                            None => self.set_status(format!("Import of {} failed: {}", vm_name, e)),
+                        }
+                    }
+                },
+                BackgroundResult::DiskCompacted {
+                    vm_name,
+                    before,
+                    after,
+                    error,
+                } => {
+                    self.disk_usage_cache.clear();
+                    match error {
+                        None => self.set_status(format!(
+                            "Compacted {}: {} -> {} (freed {})",
+                            vm_name,
+                            crate::vm::snapshot::format_size(before),
+                            crate::vm::snapshot::format_size(after),
+                            crate::vm::snapshot::format_size(before.saturating_sub(after))
+                        )),
+                        Some(e) => {
+                            self.show_error(format!("Failed to compact {}\n\n{}", vm_name, e))
+                        }
+                    }
+                }
+                BackgroundResult::LaunchStage { vm_name, stage } => {
+                    self.set_status(format!("{}: {}", vm_name, stage));
+                }
+                BackgroundResult::LaunchHealth { vm_name, health } => match health {
+                    crate::vm::StartupHealth::Ready => {}
+                    crate::vm::StartupHealth::Exited(error) => {
+                        self.show_error(format!("Failed to launch {}\n\n{}", vm_name, error))
+                    }
+                    crate::vm::StartupHealth::Stuck { stage, output } => {
+                        let mut msg = format!(
+                            "{} may be stuck - QEMU has not answered yet. Check the log.",
+                            vm_name
+                        );
+                        if let Some(stage) = stage {
+                            msg.push_str(&format!("\n\nLast step: {}", stage));
+                        }
+                        if !output.is_empty() {
+                            msg.push_str(&format!("\n\n{}", output));
+                        }
+                        self.show_error(msg);
+                    }
+                },
+                BackgroundResult::TestLaunched { vm_name, result } => match result {
+                    Ok(crate::vm::TestLaunch::Passed { warnings }) if warnings.is_empty() => self
+                        .set_status(format!(
+                            "Test launch of {} passed: every device initialized",
+                            vm_name
+                        )),
+                    Ok(crate::vm::TestLaunch::Passed { warnings }) => self.show_error(format!(
+                        "Test launch of {} passed with warnings\n\n{}",
+                        vm_name,
+                        warnings.join("\n")
+                    )),
+                    Ok(crate::vm::TestLaunch::Failed { option, error }) => {
+                        let mut msg = format!("Test launch of {} failed", vm_name);
+                        if let Some(option) = option {
+                            msg.push_str(&format!("\n\nFailed at: {}", option));
+                        }
+                        msg.push_str(&format!("\n\n{}", error));
+                        self.show_error(msg)
+                    }
+                    Ok(crate::vm::TestLaunch::TimedOut { output }) => {
+                        let mut msg = format!(
+                            "Test launch of {} timed out - QEMU never answered.",
+                            vm_name
+                        );
+                        if !output.is_empty() {
+                            msg.push_str(&format!("\n\n{}", output));
+                        }
+                        self.show_error(msg)
+                    }
+                    Err(e) => {
+                        self.show_error(format!("Test launch of {} failed\n\n{}", vm_name, e))
+                    }
+                },
+                BackgroundResult::LaunchWarnings { vm_name, warnings } => {
+                    for warning in &warnings {
+                        log::warn!("{}: {}", vm_name, warning);
+                    }
+                    let mut msg = format!("{}: {}", vm_name, warnings[0]);
+                    if warnings.len() > 1 {
+                        msg.push_str(&format!(" (+{} more in the log)", warnings.len() - 1));
+                    }
+                    self.set_status(msg);
+                }
+                BackgroundResult::VmSuspended { vm_name, error } => match error {
+                    None => self.set_status(format!("Suspended: {}", vm_name)),
+                    Some(e) => self.show_error(format!("Failed to suspend {}\n\n{}", vm_name, e)),
+                },
+                BackgroundResult::VmsRefreshed {
+                    library,
+                    vms,
+                    error,
+                } => {
+                    if let Some(e) = error {
+                        self.set_status(format!("Failed to rescan VM library: {}", e));
+                    } else if library == self.config.vm_library_path {
+                        let count = vms.len();
+                        self.replace_vms(vms);
+                        self.set_status(format!(
+                            "Refreshed {} VM{}",
+                            count,
+                            if count == 1 { "" } else { "s" }
+                        ));
+                    }
+                }
+                BackgroundResult::BugReportSaved { vm_name, result } => match result {
+                    Ok((path, Some(tool))) => self.set_status(format!(
+                        "Bug report for {} saved to {} and copied to the clipboard ({})",
+                        vm_name,
+                        path.display(),
+                        tool
+                    )),
+                    Ok((path, None)) => self.set_status(format!(
+                        "Bug report for {} saved to {}",
+                        vm_name,
+                        path.display()
+                    )),
+                    Err(e) => self.show_error(format!(
+                        "Failed to generate a bug report for {}\n\n{}",
+                        vm_name, e
+                    )),
+                },
+                BackgroundResult::GuestAddresses { vm_id, addresses } => {
+                    self.guest_addresses = Some((vm_id, addresses));
+                }
+                BackgroundResult::HostInfoReady(info) => {
+                    self.host_info = Some(info);
+                    self.host_info_scroll = 0;
+                    self.push_screen(Screen::HostInfo);
+                }
+                BackgroundResult::LibraryChanged { library, vms } => {
+                    // A rescan of the old library can arrive after a switch
+                    if library == self.config.vm_library_path {
+                        self.replace_vms(vms);
+                    }
+                }
+                BackgroundResult::ScheduledSnapshot {
+                    vm_id,
+                    vm_name,
+                    name,
+                    pruned,
+                    error,
+                } => {
+                    match (name, error) {
+                        (_, Some(e)) => self
+                            .set_status(format!("Scheduled snapshot of {} failed: {}", vm_name, e)),
+                        (Some(name), None) if pruned > 0 => self.set_status(format!(
+                            "Scheduled snapshot of {}: {} (pruned {} old)",
+                            vm_name, name, pruned
+                        )),
+                        (Some(name), None) => {
+                            self.set_status(format!("Scheduled snapshot of {}: {}", vm_name, name))
+                        }
+                        (None, None) => {}
+                    }
+                    let showing = self.screen == Screen::Snapshots
+                        && self.selected_vm().is_some_and(|vm| vm.id == vm_id);
+                    if showing {
+                        let _ = self.load_snapshots();
+                    }
+                }
+                BackgroundResult::Progress { label, percent } => {
+                    if let Some(ref mut progress) = self.progress {
+                        progress.label = label;
+                        progress.percent = percent;
+                    }
+                }
                 BackgroundResult::SnapshotsLoaded { snapshots, error } => {
                     if let Some(e) = error {
                         self.set_status(format!("Error loading snapshots: {}", e));
@@ -972,42 +2591,192 @@ impl App {
             latest = Some(processes);
         }
         if let Some(processes) = latest {
-            self.running_vms = self.match_running_vms(&processes);
+            let running = self.match_running_vms(&processes);
+            let installs_finished: Vec<String> = self
+                .install_launches
+                .iter()
+                .filter(|id| self.running_vms.contains_key(*id) && !running.contains_key(*id))
+                .cloned()
+                .collect();
+            self.running_vms = running;
+            for id in installs_finished {
+                self.install_launches.remove(&id);
+                let undecided = self
+                    .vms
+                    .iter()
+                    .any(|vm| vm.id == id && vm.first_boot_done != Some(true));
+                if undecided {
+                    self.push_screen(Screen::Confirm(ConfirmAction::FinishInstall(id)));
+                }
+            }
+            self.guest_ready_vms = self
+                .running_vms
+                .iter()
+                .filter(|(_, pid)| {
+                    processes
+                        .iter()
+                        .any(|p| p.pid == **pid && p.guest_agent_ready)
+                })
+                .map(|(id, _)| id.clone())
+                .collect();
+            self.balloon_vms = self
+                .running_vms
+                .iter()
+                .filter_map(|(id, pid)| {
+                    let proc = processes.iter().find(|p| p.pid == *pid)?;
+                    Some((id.clone(), proc.balloon_mb?))
+                })
+                .collect();
             // Clean up stopping_vms for VMs that have actually stopped
             self.stopping_vms
                 .retain(|id, _| self.running_vms.contains_key(id));
+            self.reap_looking_glass_clients();
         }
     }
 
-    /// Match QEMU processes against known VMs using the process working directory.
+    /// Start scheduled snapshots that are due (call in event loop).
     ///
-    /// Launch scripts run QEMU from the VM's directory, so /proc/<pid>/cwd
-    /// reliably identifies which VM a process belongs to — unlike disk filenames
-    /// which are often generic (e.g., "disk.qcow2").
-    fn match_running_vms(&self, processes: &[QemuProcess]) -> HashMap<String, u32> {
-        let mut result = HashMap::new();
-        for vm in &self.vms {
-            for proc in processes {
-                if let Some(ref cwd) = proc.cwd {
-                    // cwd is available — use it as the authoritative match
-                    if cwd == &vm.path {
-                        result.insert(vm.id.clone(), proc.pid);
-                        break;
-                    }
-                } else {
-                    // No cwd available (permissions?) — fall back to full disk path in cmdline
-                    if let Some(disk) = vm.config.primary_disk() {
-                        if let Some(disk_path_str) = disk.path.to_str() {
-                            if !disk_path_str.is_empty() && proc.cmdline.contains(disk_path_str) {
-                                result.insert(vm.id.clone(), proc.pid);
-                                break;
-                            }
-                        }
-                    }
+    /// The first snapshot of a run is due one interval after the VM is seen
+    /// running; the countdown is dropped when the VM stops.
+    pub fn tick_snapshot_schedules(&mut self) {
+        let running = &self.running_vms;
+        let schedules = &self.snapshot_schedules;
+        self.next_scheduled_snapshot
+            .retain(|id, _| running.contains_key(id) && schedules.contains_key(id));
+
+        let now = Instant::now();
+        let mut due = Vec::new();
+        for (id, &minutes) in &self.snapshot_schedules {
+            if !self.running_vms.contains_key(id) {
+                continue;
+            }
+            let next = now + std::time::Duration::from_secs(u64::from(minutes) * 60);
+            match self.next_scheduled_snapshot.get(id) {
+                Some(at) if *at > now => {}
+                Some(_) => {
+                    due.push(id.clone());
+                    self.next_scheduled_snapshot.insert(id.clone(), next);
+                }
+                None => {
+                    self.next_scheduled_snapshot.insert(id.clone(), next);
                 }
             }
         }
-        result
+
+        for id in due {
+            let Some(vm) = self.vms.iter().find(|vm| vm.id == id) else {
+                continue;
+            };
+            let Some(disk) = vm
+                .config
+                .primary_disk()
+                .filter(|d| d.format.supports_snapshots())
+            else {
+                continue;
+            };
+            let vm_path = vm.path.clone();
+            let disk_path = disk.path.clone();
+            let vm_name = vm.display_name();
+            let keep = self.config.scheduled_snapshot_keep as usize;
+            let tx = self.background_tx.clone();
+            std::thread::spawn(move || {
+                let result =
+                    crate::vm::snapshot::take_scheduled_snapshot(&vm_path, &disk_path, keep);
+                let (name, pruned, error) = match result {
+                    Ok((name, pruned)) => (Some(name), pruned, None),
+                    Err(e) => (None, 0, Some(format!("{:#}", e))),
+                };
+                let _ = tx.send(BackgroundResult::ScheduledSnapshot {
+                    vm_id: id,
+                    vm_name,
+                    name,
+                    pruned,
+                    error,
+                });
+            });
+        }
+    }
+
+    /// Time until a running VM's next scheduled snapshot, e.g. "12m" or "1h05m"
+    pub fn snapshot_countdown(&self, vm_id: &str) -> Option<String> {
+        let due = self.next_scheduled_snapshot.get(vm_id)?;
+        let mins = due
+            .saturating_duration_since(Instant::now())
+            .as_secs()
+            .div_ceil(60);
+        Some(if mins >= 60 {
+            format!("{}h{:02}m", mins / 60, mins % 60)
+        } else {
+            format!("{}m", mins)
+        })
+    }
+
+    /// Close Looking Glass clients whose VM has stopped, and forget clients
+    /// the user already closed.
+    fn reap_looking_glass_clients(&mut self) {
+        let running = &self.running_vms;
+        self.looking_glass_clients.retain(|id, client| {
+            if matches!(client.child.try_wait(), Ok(Some(_))) {
+                return false;
+            }
+            if running.contains_key(id) {
+                client.seen_running = true;
+                return true;
+            }
+            if client.seen_running {
+                let _ = client.child.kill();
+                let _ = client.child.wait();
+                return false;
+            }
+            true
+        });
+    }
+
+    /// Match QEMU processes against known VMs (process tag, working
+    /// directory, then disk images)
+    fn match_running_vms(&self, processes: &[QemuProcess]) -> HashMap<String, u32> {
+        crate::vm::lifecycle::running_vm_pids(&self.vms, processes)
+    }
+
+    /// Re-read usage for the selected VM's disks unless read in the last 30s
+    pub fn refresh_selected_disk_usage(&mut self) {
+        let Some(vm) = self.selected_vm() else {
+            return;
+        };
+        let stale: Vec<PathBuf> = vm
+            .config
+            .disks
+            .iter()
+            .map(|d| vm.path.join(&d.path))
+            .filter(|path| {
+                self.disk_usage_cache
+                    .get(path)
+                    .is_none_or(|(at, _)| at.elapsed() > Duration::from_secs(30))
+            })
+            .collect();
+        for path in stale {
+            let usage = crate::commands::qemu_img::disk_usage(&path);
+            self.disk_usage_cache.insert(path, (Instant::now(), usage));
+        }
+    }
+
+    /// Ask the selected VM's guest agent for its IP addresses, if it is
+    /// running with one that answers. The reply arrives in the background.
+    pub fn refresh_guest_addresses(&mut self) {
+        self.guest_addresses = None;
+        let Some(vm) = self.selected_vm() else {
+            return;
+        };
+        if !self.guest_ready_vms.contains(&vm.id) {
+            return;
+        }
+        let (vm_id, vm_path) = (vm.id.clone(), vm.path.clone());
+        let tx = self.background_tx.clone();
+        std::thread::spawn(move || {
+            if let Some(addresses) = crate::vm::lifecycle::guest_agent_addresses(&vm_path) {
+                let _ = tx.send(BackgroundResult::GuestAddresses { vm_id, addresses });
+            }
+        });
     }
 
     /// Get PID of the currently selected VM if it's running.
@@ -1016,42 +2785,86 @@ impl App {
         self.running_vms.get(&vm.id).copied()
     }
 
-    /// Seed `file_browser_dir` for ISO selection: prefer the configured
-    /// default ISO path if it still exists, else the user's home directory.
-    pub fn seed_iso_browser_dir(&mut self) {
+    /// Seed `file_browser_dir` for a browser mode: the directory last picked
+    /// from in that mode, then (for ISOs) the configured default ISO path and
+    /// the ISO library, then the VM library, then the user's home directory.
+    /// Directories that no longer exist are skipped.
+    pub fn seed_file_browser_dir(&mut self, mode: FileBrowserMode) {
+        let iso_dirs = match mode {
+            FileBrowserMode::Iso | FileBrowserMode::ToolsIso => {
+                vec![
+                    self.config.default_iso_path.clone(),
+                    Some(self.config.iso_dir()),
+                ]
+            }
+            _ => Vec::new(),
+        };
         let target = self
             .config
-            .default_iso_path
-            .as_ref()
-            .filter(|p| p.is_dir())
+            .last_browser_dirs
+            .get(mode.config_key())
             .cloned()
+            .into_iter()
+            .chain(iso_dirs.into_iter().flatten())
+            .chain(Some(self.config.vm_library_path.clone()))
+            .find(|p| p.is_dir())
             .or_else(dirs::home_dir)
             .unwrap_or_else(|| PathBuf::from("/"));
         self.file_browser_dir = target;
     }
 
+    /// Open the file browser in `mode`, starting from its seeded directory
+    pub fn open_file_browser(&mut self, mode: FileBrowserMode) {
+        self.file_browser_bookmarks = None;
+        self.seed_file_browser_dir(mode);
+        self.load_file_browser(mode);
+    }
+
+    /// Remember the current directory as the last used for the current mode
+    fn remember_file_browser_dir(&mut self) {
+        let key = self.file_browser_mode.config_key();
+        if self.config.last_browser_dirs.get(key) != Some(&self.file_browser_dir) {
+            self.config
+                .last_browser_dirs
+                .insert(key.to_string(), self.file_browser_dir.clone());
+            // Best effort: a failed save only costs the starting directory
+            let _ = self.config.save();
+        }
+    }
+
     /// Load file browser entries for current directory
     pub fn load_file_browser(&mut self, mode: FileBrowserMode) {
         self.file_browser_mode = mode;
         self.file_browser_entries.clear();
         self.file_browser_selected = 0;
+        self.file_browser_error = None;
 
         // Determine file extensions to filter by based on mode
         let extensions: &[&str] = match mode {
-            FileBrowserMode::Iso => &[".iso", ".ISO"],
+            FileBrowserMode::Iso | FileBrowserMode::ToolsIso | FileBrowserMode::CdromSwap => {
+                &[".iso", ".ISO"]
+            }
             FileBrowserMode::RecoveryImage => &[".dmg", ".DMG", ".qcow2", ".QCOW2"],
             FileBrowserMode::Disk => &[
                 ".qcow2", ".QCOW2", ".qcow", ".QCOW", ".raw", ".RAW", ".img", ".IMG",
             ],
             FileBrowserMode::Directory => &[],
-            FileBrowserMode::ImportConfig => &[".xml", ".XML", ".conf"],
+            FileBrowserMode::ImportConfig => {
+                &[".xml", ".XML", ".conf", ".ova", ".OVA", ".ovf", ".OVF"]
+            }
             FileBrowserMode::Bios => &[
                 ".bin", ".BIN", ".rom", ".ROM", ".qcow2", ".QCOW2", ".fd", ".FD",
             ],
-            FileBrowserMode::Floppy => &[
+            FileBrowserMode::Floppy
+            | FileBrowserMode::FloppyDrive
+            | FileBrowserMode::FloppySwap => &[
                 ".img", ".IMG", ".ima", ".IMA", ".flp", ".FLP", ".vfd", ".VFD",
             ],
             FileBrowserMode::SingleGpuRom => &[".rom", ".ROM", ".bin", ".BIN"],
+            // Kernels and initrds rarely have a telling extension
+            FileBrowserMode::Kernel | FileBrowserMode::Initrd => &[],
+            FileBrowserMode::AsciiArt => &[".txt", ".TXT", ".ascii", ".asc"],
+            FileBrowserMode::SettingsFile(item) => item.file_extensions(),
         };
 
         // For Directory mode, add a [Select This Directory] sentinel entry first
@@ -1060,6 +2873,9 @@ impl App {
                 name: "[Select This Directory]".to_string(),
                 path: self.file_browser_dir.clone(),
                 is_dir: false, // So Enter returns it as a selection
+                size: None,
+                modified: None,
+                format: None,
             });
         }
 
@@ -1069,6 +2885,9 @@ impl App {
                 name: "..".to_string(),
                 path: parent.to_path_buf(),
                 is_dir: true,
+                size: None,
+                modified: None,
+                format: None,
             });
         }
 
@@ -1088,20 +2907,32 @@ impl App {
                         name,
                         path: entry.path(),
                         is_dir: metadata.is_dir(),
+                        size: (!metadata.is_dir()).then_some(metadata.len()),
+                        modified: metadata.modified().ok(),
+                        format: None,
                     };
                     if metadata.is_dir() {
                         dirs.push(entry);
                     } else if mode != FileBrowserMode::Directory
-                        && extensions.iter().any(|ext| entry.name.ends_with(ext))
+                        && (extensions.is_empty()
+                            || extensions.iter().any(|ext| entry.name.ends_with(ext)))
                     {
                         files.push(entry);
                     }
                 }
             }
 
-            // Sort alphabetically
+            // Only the listed disk images are probed; qemu-img is too slow
+            // to run on every file in a directory
+            if mode == FileBrowserMode::Disk {
+                for file in &mut files {
+                    file.format = crate::commands::qemu_img::detect_disk_format(&file.path);
+                }
+            }
+
+            // Directories alphabetically, files by the chosen order
             dirs.sort_by_key(|e| e.name.to_lowercase());
-            files.sort_by_key(|e| e.name.to_lowercase());
+            sort_file_entries(&mut files, self.file_browser_sort);
 
             self.file_browser_entries.extend(dirs);
             self.file_browser_entries.extend(files);
@@ -1119,13 +2950,97 @@ impl App {
                 None
             } else {
                 // Return selected file
-                Some(entry.path.clone())
+                let path = entry.path.clone();
+                self.remember_file_browser_dir();
+                Some(path)
             }
         } else {
             None
         }
     }
 
+    /// Jump the file browser to a typed path (`~` expanded). Leaves the
+    /// browser where it is and records an error if it isn't a directory.
+    pub fn file_browser_jump(&mut self, input: &str) {
+        let path = crate::fs::expand_home(input.trim());
+        if !path.is_absolute() {
+            self.file_browser_error = Some("Enter an absolute or ~/ path".to_string());
+        } else if !path.is_dir() {
+            self.file_browser_error = Some(format!("Not a directory: {}", path.display()));
+        } else {
+            self.file_browser_dir = path;
+            let mode = self.file_browser_mode;
+            self.load_file_browser(mode);
+        }
+    }
+
+    /// Bookmark the current directory, or remove it if already bookmarked.
+    /// Returns whether the directory is bookmarked afterwards.
+    pub fn toggle_file_browser_bookmark(&mut self) -> Result<bool> {
+        let dir = self.file_browser_dir.clone();
+        let bookmarks = &mut self.config.browser_bookmarks;
+        let added = match bookmarks.iter().position(|b| *b == dir) {
+            Some(idx) => {
+                bookmarks.remove(idx);
+                false
+            }
+            None => {
+                bookmarks.push(dir);
+                true
+            }
+        };
+        self.config.save()?;
+        Ok(added)
+    }
+
+    /// Remove the bookmark at `idx`
+    pub fn remove_file_browser_bookmark(&mut self, idx: usize) -> Result<()> {
+        if idx < self.config.browser_bookmarks.len() {
+            self.config.browser_bookmarks.remove(idx);
+            self.config.save()?;
+        }
+        Ok(())
+    }
+
+    /// Navigate to the bookmark at `idx`, recording an error if it's gone
+    pub fn file_browser_open_bookmark(&mut self, idx: usize) {
+        let Some(dir) = self.config.browser_bookmarks.get(idx).cloned() else {
+            return;
+        };
+        if dir.is_dir() {
+            self.file_browser_dir = dir;
+            let mode = self.file_browser_mode;
+            self.load_file_browser(mode);
+        } else {
+            self.file_browser_error = Some(format!("Bookmark not found: {}", dir.display()));
+        }
+    }
+
+    /// Cycle the file sort order, keeping the highlighted entry selected
+    pub fn file_browser_cycle_sort(&mut self) {
+        self.file_browser_sort = self.file_browser_sort.next();
+        let selected = self
+            .file_browser_entries
+            .get(self.file_browser_selected)
+            .map(|e| e.path.clone());
+        let first_file = self
+            .file_browser_entries
+            .iter()
+            .position(|e| !e.is_dir && e.size.is_some())
+            .unwrap_or(self.file_browser_entries.len());
+        sort_file_entries(
+            &mut self.file_browser_entries[first_file..],
+            self.file_browser_sort,
+        );
+        if let Some(path) = selected {
+            self.file_browser_selected = self
+                .file_browser_entries
+                .iter()
+                .position(|e| e.path == path)
+                .unwrap_or(0);
+        }
+    }
+
     /// Move selection up in file browser
     pub fn file_browser_prev(&mut self) {
         if self.file_browser_selected > 0 {
@@ -1151,12 +3066,36 @@ impl App {
             self.script_editor_cursor = (0, 0);
             self.script_editor_modified = false;
             self.script_editor_h_scroll = 0;
+            self.script_editor_hook = None;
             self.raw_script_scroll = 0;
         }
     }
 
-    /// Save the editor content back to the launch.sh file
+    /// Load one of the selected VM's hook scripts into the editor, starting
+    /// from a template when the hook doesn't exist yet
+    pub fn load_hook_into_editor(&mut self, hook: &'static str) {
+        if let Some(vm) = self.selected_vm() {
+            let content = std::fs::read_to_string(vm.path.join(hook))
+                .unwrap_or_else(|_| crate::vm::hook_template(hook));
+            self.script_editor_lines = content.lines().map(String::from).collect();
+            if self.script_editor_lines.is_empty() {
+                self.script_editor_lines.push(String::new());
+            }
+            self.script_editor_cursor = (0, 0);
+            self.script_editor_modified = false;
+            self.script_editor_h_scroll = 0;
+            self.script_editor_hook = Some(hook);
+            self.raw_script_scroll = 0;
+        }
+    }
+
+    /// Save the editor content back to the launch.sh file (or the hook
+    /// script being edited)
     pub fn save_script_from_editor(&mut self) -> Result<()> {
+        if let Some(hook) = self.script_editor_hook {
+            return self.save_hook_from_editor(hook);
+        }
+
         // Get the launch script path before we need mutable access
         let launch_script_path = self
             .selected_vm()
@@ -1172,10 +3111,42 @@ impl App {
         };
 
         std::fs::write(&launch_script_path, &content)?;
+        self.script_editor_modified = false;
+        self.refresh_after_script_write();
+        Ok(())
+    }
+
+    /// Write the pending regenerated launch.sh, backing up the old script.
+    /// Returns the backup path.
+    pub fn apply_regenerated_script(&mut self) -> Result<PathBuf> {
+        let content = self
+            .regenerated_script
+            .take()
+            .ok_or_else(|| anyhow::anyhow!("Nothing to regenerate"))?;
+        let vm = self
+            .selected_vm()
+            .ok_or_else(|| anyhow::anyhow!("No VM selected"))?;
+        let backup = crate::vm::create::replace_launch_script(vm, &content)?;
+        self.regenerate_diff.clear();
+        self.refresh_after_script_write();
+        Ok(backup)
+    }
+
+    /// Add a serial console socket to the selected VM's launch.sh
+    pub fn add_serial_console(&mut self) -> Result<()> {
+        let vm_path = self
+            .selected_vm()
+            .map(|vm| vm.path.clone())
+            .ok_or_else(|| anyhow::anyhow!("No VM selected"))?;
+        crate::vm::lifecycle::add_serial_socket_to_script(&vm_path)?;
+        self.refresh_after_script_write();
+        Ok(())
+    }
 
+    /// Pick up a rewritten launch.sh for the selected VM
+    fn refresh_after_script_write(&mut self) {
         // Update the cached raw_script in the VM
         self.reload_selected_vm_script();
-        self.script_editor_modified = false;
 
         // Re-parse the VM config since the script changed
         if let Ok(vms) = discover_vms(&self.config.vm_library_path) {
@@ -1195,7 +3166,25 @@ impl App {
                 };
             }
         }
+    }
+
+    /// Write the editor content to a hook script in the VM directory
+    fn save_hook_from_editor(&mut self, hook: &str) -> Result<()> {
+        use std::os::unix::fs::PermissionsExt;
+
+        let path = self
+            .selected_vm()
+            .map(|vm| vm.path.join(hook))
+            .ok_or_else(|| anyhow::anyhow!("No VM selected"))?;
+
+        let mut content = self.script_editor_lines.join("\n");
+        if !content.ends_with('\n') {
+            content.push('\n');
+        }
+        std::fs::write(&path, &content)?;
+        std::fs::set_permissions(&path, std::fs::Permissions::from_mode(0o755))?;
 
+        self.script_editor_modified = false;
         Ok(())
     }
 
@@ -1268,7 +3257,9 @@ impl App {
             disk_size_gb: self.config.default_disk_size_gb,
             qemu_config: WizardQemuConfig {
                 memory_mb: self.config.default_memory_mb,
+                max_memory_mb: None,
                 cpu_cores: self.config.default_cpu_cores,
+                cpu_topology: None,
                 enable_kvm: self.config.default_enable_kvm,
                 display: self.config.default_display.clone(),
                 ..WizardQemuConfig::default()
@@ -1294,10 +3285,28 @@ impl App {
 
     /// Move to the next wizard step
     pub fn wizard_next_step(&mut self) -> Result<(), String> {
+        let duplicate_name = self
+            .wizard_state
+            .as_ref()
+            .filter(|state| state.step == crate::wizard_types::WizardStep::SelectOs)
+            .and_then(|state| crate::vm::find_by_display_name(&self.vms, &state.vm_name, None))
+            .is_some();
+
         if let Some(ref mut state) = self.wizard_state {
             // Validate current step
             state.can_proceed()?;
 
+            // A name shared with an existing VM is allowed once the user has
+            // seen the warning and presses Enter again
+            let name = state.vm_name.trim();
+            if duplicate_name && state.duplicate_name_acknowledged.as_deref() != Some(name) {
+                state.duplicate_name_acknowledged = Some(name.to_string());
+                return Err(format!(
+                    "A VM named \"{}\" already exists - press Enter again to use the name anyway",
+                    name
+                ));
+            }
+
             // Move to next step
             if let Some(next) = state.step.next() {
                 state.step = next;
@@ -1323,6 +3332,171 @@ impl App {
         }
     }
 
+    /// Download an ISO for the wizard into the ISO library on a background
+    /// thread, resuming a partial file from an earlier attempt
+    pub fn start_iso_download(&mut self, url: String) {
+        use crate::commands::download;
+
+        let Some(file_name) = download::url_file_name(&url) else {
+            self.set_status(format!("Can't tell the file name from {}", url));
+            return;
+        };
+        let iso_dir = self.config.iso_dir();
+        if let Err(e) = std::fs::create_dir_all(&iso_dir) {
+            self.set_status(format!("Failed to create {}: {}", iso_dir.display(), e));
+            return;
+        }
+        let dest = iso_dir.join(file_name);
+
+        let cancel = Arc::new(AtomicBool::new(false));
+        self.iso_download_generation += 1;
+        let generation = self.iso_download_generation;
+        self.iso_download = Some(IsoDownload {
+            generation,
+            dest: dest.clone(),
+            cancel: cancel.clone(),
+        });
+        if let Some(ref mut state) = self.wizard_state {
+            state.iso_downloading = true;
+            state.iso_download_progress = 0.0;
+            state.iso_download_bytes = 0;
+        }
+        self.push_screen(Screen::CreateWizardDownload);
+
+        let tx = self.background_tx.clone();
+        let previous = self.iso_download_thread.take();
+        self.iso_download_thread = Some(std::thread::spawn(move || {
+            // A cancelled download may still be stopping its curl
+            if let Some(previous) = previous {
+                let _ = previous.join();
+            }
+            // A finished download from an earlier run is reused as-is
+            let result = if dest.exists() {
+                Ok(())
+            } else {
+                download::download_file(&url, &dest, &cancel, |downloaded, total| {
+                    let _ = tx.send(BackgroundResult::IsoDownloadProgress {
+                        generation,
+                        downloaded,
+                        total,
+                    });
+                })
+            };
+            let _ = tx.send(BackgroundResult::IsoDownloaded {
+                generation,
+                path: dest,
+                error: result.err().map(|e| format!("{:#}", e)),
+            });
+        }));
+    }
+
+    /// Whether a download thread's message belongs to the running download
+    fn is_current_iso_download(&self, generation: u64) -> bool {
+        self.iso_download
+            .as_ref()
+            .is_some_and(|d| d.generation == generation)
+    }
+
+    /// Stop the running ISO download; the partial file is kept for resuming
+    pub fn cancel_iso_download(&mut self) {
+        if let Some(download) = self.iso_download.take() {
+            download.cancel.store(true, Ordering::Relaxed);
+            self.set_status("ISO download cancelled; it resumes next time");
+        }
+        if let Some(ref mut state) = self.wizard_state {
+            state.iso_downloading = false;
+            state.iso_download_progress = 0.0;
+        }
+        if self.screen == Screen::CreateWizardDownload {
+            self.pop_screen();
+        }
+    }
+
+    fn finish_iso_download(&mut self, path: PathBuf, error: Option<String>) {
+        self.iso_download = None;
+        if self.screen == Screen::CreateWizardDownload {
+            self.pop_screen();
+        }
+        if let Some(ref mut state) = self.wizard_state {
+            state.iso_downloading = false;
+        }
+        match error {
+            Some(e) => self.show_error(format!("ISO download failed\n\n{}", e)),
+            None => {
+                self.set_status(format!("Downloaded {}", path.display()));
+                let Some(ref mut state) = self.wizard_state else {
+                    return;
+                };
+                state.iso_path = Some(path.clone());
+                state.is_recovery_image = false;
+                if state.step == crate::wizard_types::WizardStep::SelectIso
+                    && !self.wizard_detect_iso_os(&path)
+                {
+                    let _ = self.wizard_next_step();
+                    self.offer_iso_verification();
+                }
+            }
+        }
+    }
+
+    /// Hash an ISO on a background thread and report whether it matches
+    pub fn start_checksum_verification(&mut self, path: PathBuf, expected: String) {
+        let tx = self.background_tx.clone();
+        let label = format!(
+            "Verifying {}",
+            path.file_name().unwrap_or_default().to_string_lossy()
+        );
+        self.start_progress(label.clone());
+
+        std::thread::spawn(move || {
+            let result = crate::commands::checksum::sha256_file(&path, |percent| {
+                let _ = tx.send(BackgroundResult::Progress {
+                    label: label.clone(),
+                    percent: Some(percent),
+                });
+            });
+            let (actual, error) = match result {
+                Ok(hash) => (Some(hash), None),
+                Err(e) => (None, Some(format!("{:#}", e))),
+            };
+            let _ = tx.send(BackgroundResult::ChecksumVerified {
+                path,
+                expected,
+                actual,
+                error,
+            });
+        });
+    }
+
+    /// After the wizard picks an ISO, offer to verify it when the selected
+    /// profile publishes a SHA-256
+    pub fn offer_iso_verification(&mut self) {
+        let prompt = self
+            .wizard_state
+            .as_ref()
+            .and_then(|state| iso_verification_prompt(state, &self.qemu_profiles));
+        if let Some(prompt) = prompt {
+            self.push_screen(Screen::Confirm(prompt));
+        }
+    }
+
+    /// Look for a known OS on the wizard's ISO. Returns true (and stores the
+    /// suggestion) only when it names a profile other than the selected one.
+    pub fn wizard_detect_iso_os(&mut self, iso: &std::path::Path) -> bool {
+        let detection = crate::vm::detect_iso_os(iso).filter(|d| {
+            self.qemu_profiles.get(&d.profile_id).is_some()
+                && self
+                    .wizard_state
+                    .as_ref()
+                    .is_some_and(|s| s.selected_os.as_deref() != Some(d.profile_id.as_str()))
+        });
+        let found = detection.is_some();
+        if let Some(ref mut state) = self.wizard_state {
+            state.iso_detection = detection;
+        }
+        found
+    }
+
     /// Select an OS profile in the wizard
     pub fn wizard_select_os(&mut self, os_id: &str) {
         let library_path = self.config.vm_library_path.clone();
@@ -1359,6 +3533,7 @@ impl App {
         if let Some(ref mut state) = self.wizard_state {
             state.selected_os = Some(os_id.to_string());
             state.custom_os = None;
+            state.template = None;
 
             // Apply profile settings
             if let Some(profile) = self.qemu_profiles.get(os_id) {
@@ -1381,6 +3556,112 @@ impl App {
         }
     }
 
+    /// Start the wizard from a saved template: its OS profile is selected
+    /// and its QEMU settings and disk size replace the profile's
+    pub fn wizard_use_template(&mut self, name: &str) {
+        let Some(template) = self.vm_templates.get(name).cloned() else {
+            return;
+        };
+        if let Some(os_id) = template
+            .os_profile
+            .as_deref()
+            .filter(|id| self.qemu_profiles.get(id).is_some())
+        {
+            self.wizard_select_os(os_id);
+        }
+        if let Some(ref mut state) = self.wizard_state {
+            state.disk_size_gb = template.disk_size_gb;
+            state.qemu_config = template.qemu;
+            state.template = Some(name.to_string());
+        }
+    }
+
+    /// Save the wizard's current QEMU settings and disk size as a template
+    pub fn save_wizard_template(&mut self, name: &str) {
+        let Some(state) = self.wizard_state.as_ref() else {
+            return;
+        };
+        let template = VmTemplate {
+            os_profile: state
+                .selected_os
+                .clone()
+                .or_else(|| state.custom_os.as_ref().map(|os| os.base_profile.clone())),
+            disk_size_gb: state.disk_size_gb,
+            // A shared MAC would clash between VMs made from the template
+            qemu: WizardQemuConfig {
+                mac_address: None,
+                ..state.qemu_config.clone()
+            },
+        };
+        match self.vm_templates.insert(name, template) {
+            Ok(replaced) => {
+                if let Some(ref mut state) = self.wizard_state {
+                    state.template = Some(name.to_string());
+                }
+                self.set_status(if replaced {
+                    format!("Template '{}' updated", name)
+                } else {
+                    format!("Saved template '{}'", name)
+                });
+            }
+            Err(e) => self.set_status(format!("Failed to save template: {}", e)),
+        }
+    }
+
+    /// Save the wizard's custom OS as a user QEMU profile (a copy of its
+    /// base profile) with matching OS metadata, so later VMs can pick it
+    /// from the OS list. Returns the new profile id.
+    pub fn save_custom_os_profile(&mut self) -> Result<String> {
+        let custom = self
+            .wizard_state
+            .as_ref()
+            .and_then(|s| s.custom_os.clone())
+            .context("No custom OS to save")?;
+        let name = custom.name.trim();
+        let id = CreateWizardState::generate_folder_name(name);
+        if id.is_empty() {
+            bail!("The OS name needs at least one letter or digit");
+        }
+        if self.qemu_profiles.get(&id).is_some() && !self.qemu_profiles.is_user_profile(&id) {
+            bail!(
+                "\"{}\" is a built-in profile; give the OS a different name",
+                id
+            );
+        }
+
+        let base = self.qemu_profiles.get_or_default(&custom.base_profile);
+        let profile = crate::metadata::QemuProfile {
+            display_name: name.to_string(),
+            iso_url: None,
+            iso_sha256: None,
+            notes: Some(format!("Custom profile based on {}.", base.display_name)),
+            ..base
+        };
+        self.qemu_profiles.save_user_profile(
+            &self.config.metadata_path.join(USER_PROFILES_DIR),
+            &id,
+            profile,
+        )?;
+
+        let info = OsInfo {
+            display_name: None,
+            name: name.to_string(),
+            publisher: custom.publisher.trim().to_string(),
+            release_date: custom.release_date.unwrap_or_default(),
+            architecture: custom.architecture,
+            blurb: crate::metadata::os_info::OsBlurb {
+                short: custom.short_blurb,
+                long: custom.long_blurb,
+            },
+            fun_facts: custom.fun_facts,
+            install_steps: Vec::new(),
+        };
+        self.metadata
+            .save_to_dir(&self.config.metadata_path, &id, info)
+            .context("Failed to save OS metadata")?;
+        Ok(id)
+    }
+
     /// Set the wizard to use a custom OS
     pub fn wizard_use_custom_os(&mut self) {
         if let Some(ref mut state) = self.wizard_state {
@@ -1444,6 +3725,231 @@ fn pci_addr_from_arg(arg: &str) -> Option<String> {
     }
 }
 
+/// App settings a batch needs, copied for the batch thread
+struct BatchSettings {
+    trash_dir: PathBuf,
+    snapshot_prefix: String,
+    spice_tcp: bool,
+    default_ivshmem_size_mb: u32,
+    start_timeout_secs: u32,
+    host_capacity: Option<HostCapacity>,
+}
+
+/// Run one VM's part of a bulk-mode batch. `pid` is the VM's QEMU process
+/// when it is running; `library` is every VM, for the shared-disk check.
+fn run_batch_action(
+    action: BatchAction,
+    vm: &DiscoveredVm,
+    pid: Option<u32>,
+    library: &[DiscoveredVm],
+    settings: &BatchSettings,
+    tx: &Sender<BackgroundResult>,
+) -> Result<()> {
+    match action {
+        BatchAction::Start => {
+            if pid.is_some() {
+                anyhow::bail!("already running");
+            }
+            crate::vm::lifecycle::check_shared_disks_live(vm, library)?;
+            // Nothing can be confirmed mid-batch: a sudo prompt, the TCG
+            // fallback or a VM bigger than the host all need a single launch
+            if matches!(
+                crate::vm::vfio_auth_for_launch(vm),
+                crate::vm::VfioAuth::SudoPrompt | crate::vm::VfioAuth::Unavailable
+            ) {
+                anyhow::bail!("passthrough needs authentication; launch it on its own");
+            }
+            if let Some(reason) = kvm_unavailable_for(vm) {
+                anyhow::bail!("{}; launch it on its own to use TCG", reason);
+            }
+            let warnings = settings
+                .host_capacity
+                .map(|host| host.warnings(vm.config.memory_mb, vm.config.cpu_cores))
+                .unwrap_or_default();
+            if !warnings.is_empty() {
+                anyhow::bail!("{}; launch it on its own to confirm", warnings.join(", "));
+            }
+            prepare_launch(vm, settings.default_ivshmem_size_mb)?;
+            let resume_suspended = crate::vm::has_suspend_state(&vm.path);
+            let options = LaunchOptions {
+                boot_mode: BootMode::Normal,
+                extra_args: Vec::new(),
+                usb_devices: Vec::new(),
+                force_tcg: false,
+                resume_suspended,
+                spice_tcp: settings.spice_tcp,
+            };
+            let mut result = crate::vm::launch_vm_with_error_check(vm, &options);
+            watch_launch(&mut result, tx, settings.start_timeout_secs);
+            if !result.success {
+                anyhow::bail!(result.error.unwrap_or_else(|| "Unknown error".to_string()));
+            }
+            if resume_suspended {
+                crate::vm::finish_resume(&vm.path)?;
+            }
+            Ok(())
+        }
+        BatchAction::Stop => match pid {
+            Some(pid) => crate::vm::stop_vm_by_pid(pid),
+            None => anyhow::bail!("not running"),
+        },
+        BatchAction::Snapshot => {
+            let disk = vm
+                .config
+                .primary_disk()
+                .filter(|d| d.format.supports_snapshots())
+                .context("no disk that supports snapshots")?;
+            let name = format!(
+                "{}-{}",
+                settings.snapshot_prefix,
+                chrono::Local::now().format("%Y%m%d-%H%M%S")
+            );
+            crate::vm::snapshot::snapshot_vm(&vm.path, &disk.path, &name, pid.is_some())
+        }
+        BatchAction::Delete => {
+            if pid.is_some() {
+                anyhow::bail!("running; stop it first");
+            }
+            crate::vm::lifecycle::delete_vm(vm, Some(&settings.trash_dir), |_| {})
+        }
+    }
+}
+
+/// Create the Looking Glass shared memory file a VM's launch script maps, if
+/// it uses one. Every launch runs this first; returns the file's path.
+pub(crate) fn prepare_launch(
+    vm: &DiscoveredVm,
+    default_ivshmem_size_mb: u32,
+) -> Result<Option<PathBuf>> {
+    let shmem = std::fs::read_to_string(&vm.launch_script)
+        .ok()
+        .and_then(|content| LookingGlassConfig::shmem_from_script(&content));
+    let Some((path, size)) = shmem else {
+        return Ok(None);
+    };
+    let size = size.unwrap_or(default_ivshmem_size_mb as u64 * 1024 * 1024);
+    LookingGlassConfig::prepare_shmem(&path, size)
+        .context("Looking Glass shared memory is not ready")?;
+    Ok(Some(path))
+}
+
+/// Why the host can't give the VM the KVM it asks for, if it can't
+pub(crate) fn kvm_unavailable_for(vm: &DiscoveredVm) -> Option<String> {
+    vm.config
+        .enable_kvm
+        .then(crate::commands::qemu_system::kvm_unavailable_reason)
+        .flatten()
+}
+
+/// Hand a launch's startup watch and stderr to background threads that
+/// report a VM that never comes up, or the warnings QEMU prints on the way
+pub(crate) fn watch_launch(
+    result: &mut crate::vm::lifecycle::LaunchResult,
+    tx: &Sender<BackgroundResult>,
+    start_timeout_secs: u32,
+) {
+    if let (Some(mut watch), true) = (result.watch.take(), start_timeout_secs > 0) {
+        let (tx, vm_name) = (tx.clone(), result.vm_name.clone());
+        std::thread::spawn(move || {
+            let health = watch.wait(Duration::from_secs(start_timeout_secs as u64), |stage| {
+                let _ = tx.send(BackgroundResult::LaunchStage {
+                    vm_name: vm_name.clone(),
+                    stage: stage.to_string(),
+                });
+            });
+            let _ = tx.send(BackgroundResult::LaunchHealth { vm_name, health });
+        });
+    }
+    if let Some(stderr) = result.stderr.take() {
+        let (tx, vm_name) = (tx.clone(), result.vm_name.clone());
+        std::thread::spawn(move || {
+            std::thread::sleep(crate::vm::lifecycle::LAUNCH_WARNING_WINDOW);
+            let warnings = stderr.warnings();
+            if !warnings.is_empty() {
+                let _ = tx.send(BackgroundResult::LaunchWarnings { vm_name, warnings });
+            }
+        });
+    }
+}
+
+/// How long the library has to be quiet before a change is rescanned
+const LIBRARY_SETTLE_TIME: Duration = Duration::from_secs(1);
+
+/// How often the library watcher checks whether the library path changed
+const LIBRARY_PATH_CHECK_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Watch the VM library for VMs being added, removed or edited outside the
+/// app, and send a fresh scan once it settles. Filesystem notifications are
+/// debounced, so a VM being copied in is scanned once rather than on every
+/// file written, and disk image writes by running VMs are ignored.
+fn spawn_library_watcher(library: Arc<Mutex<PathBuf>>, tx: Sender<BackgroundResult>) {
+    use notify_debouncer_mini::notify::RecursiveMode;
+
+    std::thread::spawn(move || {
+        let current = || library.lock().map(|p| p.clone()).ok();
+        let (events_tx, events_rx) = mpsc::channel();
+        let mut debouncer =
+            match notify_debouncer_mini::new_debouncer(LIBRARY_SETTLE_TIME, events_tx) {
+                Ok(debouncer) => debouncer,
+                Err(e) => {
+                    log::warn!("library watcher unavailable: {e}");
+                    return;
+                }
+            };
+        let mut watching: Option<PathBuf> = None;
+        loop {
+            let Some(path) = current() else { return };
+            if watching.as_ref() != Some(&path) {
+                if let Some(old) = watching.take() {
+                    let _ = debouncer.watcher().unwatch(&old);
+                }
+                // A missing library is retried until it's created or changed
+                if debouncer
+                    .watcher()
+                    .watch(&path, RecursiveMode::Recursive)
+                    .is_ok()
+                {
+                    watching = Some(path.clone());
+                }
+            }
+            let events = match events_rx.recv_timeout(LIBRARY_PATH_CHECK_INTERVAL) {
+                Ok(Ok(events)) => events,
+                Ok(Err(e)) => {
+                    log::warn!("library watcher: {e}");
+                    continue;
+                }
+                Err(mpsc::RecvTimeoutError::Timeout) => continue,
+                Err(mpsc::RecvTimeoutError::Disconnected) => return,
+            };
+            if !events
+                .iter()
+                .any(|event| crate::vm::affects_discovery(&path, &event.path))
+            {
+                continue;
+            }
+            let Ok(vms) = discover_vms(&path) else {
+                continue;
+            };
+            let result = BackgroundResult::LibraryChanged {
+                library: path.clone(),
+                vms,
+            };
+            if tx.send(result).is_err() {
+                break; // Receiver dropped (app exited)
+            }
+        }
+    });
+}
+
+/// Read the snapshot interval of every VM that has one set
+fn load_snapshot_schedules(vms: &[DiscoveredVm]) -> HashMap<String, u32> {
+    vms.iter()
+        .filter_map(|vm| {
+            crate::vm::snapshot::load_snapshot_schedule(&vm.path).map(|m| (vm.id.clone(), m))
+        })
+        .collect()
+}
+
 /// Generate a mount tag from a host directory path
 fn generate_mount_tag(path: &str) -> String {
     let folder_name = std::path::Path::new(path)
@@ -1470,10 +3976,62 @@ fn generate_mount_tag(path: &str) -> String {
     )
 }
 
+/// The verify prompt for the wizard's ISO, when its selected profile has a
+/// valid `iso_sha256`
+fn iso_verification_prompt(
+    state: &crate::wizard_types::CreateWizardState,
+    profiles: &QemuProfileStore,
+) -> Option<ConfirmAction> {
+    let path = state
+        .iso_path
+        .clone()
+        .filter(|_| !state.is_recovery_image)?;
+    let expected = state
+        .selected_os
+        .as_ref()
+        .and_then(|id| profiles.get(id))
+        .and_then(|p| p.iso_sha256.as_deref())
+        .and_then(crate::commands::checksum::parse_sha256)?;
+    Some(ConfirmAction::VerifyIsoChecksum { path, expected })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn iso_with_profile_hash_is_offered_for_verification() {
+        let hash = "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855";
+        let dir = tempfile::tempdir().unwrap();
+        let mut profiles = QemuProfileStore::new();
+        for (id, iso_sha256) in [("hashed-os", Some(hash)), ("plain-os", None)] {
+            let profile = crate::metadata::QemuProfile {
+                iso_sha256: iso_sha256.map(|h| h.to_uppercase()),
+                ..Default::default()
+            };
+            profiles.save_user_profile(dir.path(), id, profile).unwrap();
+        }
+        let state = |os: &str| crate::wizard_types::CreateWizardState {
+            selected_os: Some(os.to_string()),
+            iso_path: Some(PathBuf::from("/isos/hashed.iso")),
+            ..Default::default()
+        };
+
+        match iso_verification_prompt(&state("hashed-os"), &profiles) {
+            Some(ConfirmAction::VerifyIsoChecksum { path, expected }) => {
+                assert_eq!(path, PathBuf::from("/isos/hashed.iso"));
+                assert_eq!(expected, hash);
+            }
+            other => panic!("expected a verify prompt, got {:?}", other),
+        }
+        assert!(iso_verification_prompt(&state("plain-os"), &profiles).is_none());
+        let recovery = crate::wizard_types::CreateWizardState {
+            is_recovery_image: true,
+            ..state("hashed-os")
+        };
+        assert!(iso_verification_prompt(&recovery, &profiles).is_none());
+    }
+
     #[test]
     fn pci_addr_from_arg_extracts_address() {
         assert_eq!(
@@ -1500,4 +4058,38 @@ mod tests {
     fn pci_addr_from_arg_none_for_empty_host() {
         assert_eq!(pci_addr_from_arg("-device vfio-pci,host="), None);
     }
+
+    fn file_entry(name: &str, size: u64, age_secs: u64) -> FileBrowserEntry {
+        FileBrowserEntry {
+            name: name.to_string(),
+            path: PathBuf::from(name),
+            is_dir: false,
+            size: Some(size),
+            modified: Some(
+                std::time::UNIX_EPOCH + std::time::Duration::from_secs(1_000_000 - age_secs),
+            ),
+            format: None,
+        }
+    }
+
+    #[test]
+    fn sort_file_entries_by_name_size_and_date() {
+        let mut files = vec![
+            file_entry("b.iso", 300, 10),
+            file_entry("A.iso", 100, 30),
+            file_entry("c.iso", 200, 20),
+        ];
+        let names =
+            |files: &[FileBrowserEntry]| files.iter().map(|f| f.name.clone()).collect::<Vec<_>>();
+
+        sort_file_entries(&mut files, FileBrowserSort::Name);
+        assert_eq!(names(&files), ["A.iso", "b.iso", "c.iso"]);
+        sort_file_entries(&mut files, FileBrowserSort::Size);
+        assert_eq!(names(&files), ["b.iso", "c.iso", "A.iso"]);
+        sort_file_entries(&mut files, FileBrowserSort::Date);
+        assert_eq!(names(&files), ["b.iso", "c.iso", "A.iso"]);
+        files[2].modified = Some(std::time::UNIX_EPOCH + std::time::Duration::from_secs(2_000_000));
+        sort_file_entries(&mut files, FileBrowserSort::Date);
+        assert_eq!(names(&files), ["A.iso", "b.iso", "c.iso"]);
+    }
 }
@@ -0,0 +1,109 @@
+//! CLI error categories
+//!
+//! Subcommand failures are sorted into a few categories, each with its own
+//! exit code, so scripts can branch on why `vm-curator` failed. Errors are
+//! printed to stderr as `error: <category>: <message>`.
+
+use std::fmt;
+
+/// Why a CLI command failed
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ErrorCategory {
+    /// Anything not covered below
+    Failed,
+    /// The named VM, snapshot or file doesn't exist
+    NotFound,
+    /// The VM, or another VM using its disk, is running
+    AlreadyRunning,
+    /// A QEMU binary (`qemu-system-*`, `qemu-img`) isn't installed
+    QemuMissing,
+    /// The OS refused access to a file or device
+    Permission,
+    /// The config file can't be read or parsed
+    ConfigInvalid,
+}
+
+impl ErrorCategory {
+    /// Name printed after `error:`
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Failed => "failed",
+            Self::NotFound => "not-found",
+            Self::AlreadyRunning => "already-running",
+            Self::QemuMissing => "qemu-missing",
+            Self::Permission => "permission",
+            Self::ConfigInvalid => "config-invalid",
+        }
+    }
+
+    /// Process exit code. 2 is left to clap for usage errors.
+    pub fn exit_code(self) -> i32 {
+        match self {
+            Self::Failed => 1,
+            Self::NotFound => 3,
+            Self::AlreadyRunning => 4,
+            Self::QemuMissing => 5,
+            Self::Permission => 6,
+            Self::ConfigInvalid => 7,
+        }
+    }
+}
+
+/// An error message with its category, carried inside an `anyhow::Error`
+#[derive(Debug)]
+pub struct CliError {
+    pub category: ErrorCategory,
+    message: String,
+}
+
+impl fmt::Display for CliError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.message)
+    }
+}
+
+impl std::error::Error for CliError {}
+
+/// A new error in `category`
+pub fn cli_error(category: ErrorCategory, message: impl Into<String>) -> anyhow::Error {
+    CliError {
+        category,
+        message: message.into(),
+    }
+    .into()
+}
+
+/// File an existing error under `category`, keeping its message as the cause
+pub fn categorized(error: anyhow::Error, category: ErrorCategory, message: &str) -> anyhow::Error {
+    error.context(CliError {
+        category,
+        message: message.to_string(),
+    })
+}
+
+/// Category of an error: from a [`CliError`] in it, else `Permission` for an
+/// OS permission error anywhere in its chain, else `Failed`
+pub fn categorize(error: &anyhow::Error) -> ErrorCategory {
+    if let Some(e) = error.downcast_ref::<CliError>() {
+        return e.category;
+    }
+    let permission_denied = error.chain().any(|cause| {
+        cause
+            .downcast_ref::<std::io::Error>()
+            .is_some_and(|e| e.kind() == std::io::ErrorKind::PermissionDenied)
+    });
+    if permission_denied {
+        ErrorCategory::Permission
+    } else {
+        ErrorCategory::Failed
+    }
+}
+
+/// `error: <category>: <message>`, with the error's causes after the message
+pub fn format_error(error: &anyhow::Error) -> String {
+    format!("error: {}: {:#}", categorize(error).label(), error)
+}
+
+#[cfg(test)]
+#[path = "tests/cli_error.rs"]
+mod tests;
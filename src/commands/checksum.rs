@@ -0,0 +1,126 @@
+//! File checksums
+//!
+//! Hashes install media with the coreutils `sha256sum` tool, streaming the
+//! file through its stdin so callers can show progress on multi-GB ISOs.
+
+use anyhow::{bail, Context, Result};
+use std::fs::File;
+use std::io::{Read, Write};
+use std::path::Path;
+use std::process::{Command, Stdio};
+
+const CHUNK_SIZE: usize = 1024 * 1024;
+
+/// Compute the SHA-256 of a file as lowercase hex.
+/// `on_progress` receives the percent of the file read so far.
+pub fn sha256_file(path: &Path, mut on_progress: impl FnMut(f64)) -> Result<String> {
+    let mut file =
+        File::open(path).with_context(|| format!("Failed to open {}", path.display()))?;
+    let total = file.metadata().map(|m| m.len()).unwrap_or(0);
+
+    let mut child = Command::new("sha256sum")
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .context("Failed to run sha256sum")?;
+
+    {
+        let mut stdin = child.stdin.take().context("sha256sum stdin unavailable")?;
+        let mut buf = vec![0u8; CHUNK_SIZE];
+        let mut done: u64 = 0;
+        let mut last_percent = -1.0;
+        loop {
+            let n = file
+                .read(&mut buf)
+                .with_context(|| format!("Failed to read {}", path.display()))?;
+            if n == 0 {
+                break;
+            }
+            stdin
+                .write_all(&buf[..n])
+                .context("Failed to feed sha256sum")?;
+            done += n as u64;
+            if total > 0 {
+                // Whole percents are plenty and keep the channel quiet
+                let percent = (done as f64 * 100.0 / total as f64).floor();
+                if percent > last_percent {
+                    last_percent = percent;
+                    on_progress(percent);
+                }
+            }
+        }
+        // Dropping stdin closes it so sha256sum can finish
+    }
+
+    let output = child
+        .wait_with_output()
+        .context("Failed to wait for sha256sum")?;
+    if !output.status.success() {
+        bail!(
+            "sha256sum failed: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+    parse_sha256(&String::from_utf8_lossy(&output.stdout)).context("sha256sum produced no hash")
+}
+
+/// Pull a SHA-256 hash out of user or tool input, normalized to lowercase.
+///
+/// Accepts a bare hash, a `sha256:` prefix, or a `SHA256SUMS`-style
+/// `<hash>  <filename>` line. Returns `None` if there is no 64-digit hex hash.
+pub fn parse_sha256(input: &str) -> Option<String> {
+    let input = input.trim();
+    let input = input
+        .strip_prefix("sha256:")
+        .or_else(|| input.strip_prefix("SHA256:"))
+        .unwrap_or(input);
+    let hash = input.split_whitespace().next()?;
+    (hash.len() == 64 && hash.chars().all(|c| c.is_ascii_hexdigit()))
+        .then(|| hash.to_ascii_lowercase())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EMPTY_SHA256: &str = "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855";
+
+    #[test]
+    fn test_parse_sha256() {
+        assert_eq!(parse_sha256(EMPTY_SHA256).as_deref(), Some(EMPTY_SHA256));
+        assert_eq!(
+            parse_sha256(&format!("  sha256:{}\n", EMPTY_SHA256.to_uppercase())).as_deref(),
+            Some(EMPTY_SHA256)
+        );
+        assert_eq!(
+            parse_sha256(&format!("{}  ubuntu-24.04-desktop-amd64.iso", EMPTY_SHA256)).as_deref(),
+            Some(EMPTY_SHA256)
+        );
+        assert_eq!(parse_sha256("abc123"), None);
+        assert_eq!(parse_sha256(&EMPTY_SHA256.replace('e', "g")), None);
+        assert_eq!(parse_sha256(""), None);
+    }
+
+    #[test]
+    fn test_sha256_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("empty.iso");
+        std::fs::write(&path, b"").unwrap();
+        // Skip quietly where coreutils isn't installed
+        let Ok(hash) = sha256_file(&path, |_| {}) else {
+            return;
+        };
+        assert_eq!(hash, EMPTY_SHA256);
+
+        let path = dir.path().join("abc.iso");
+        std::fs::write(&path, b"abc").unwrap();
+        let mut last = 0.0;
+        let hash = sha256_file(&path, |p| last = p).unwrap();
+        assert_eq!(
+            hash,
+            "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"
+        );
+        assert_eq!(last, 100.0);
+    }
+}
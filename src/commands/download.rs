@@ -0,0 +1,186 @@
+//! HTTP downloads
+//!
+//! Fetches install media with `curl`, following redirects and resuming a
+//! `.part` file left by an earlier attempt. Progress is read from the size of
+//! the partial file while curl runs.
+
+use anyhow::{bail, Context, Result};
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::Duration;
+
+/// curl exit code when the server ignores a resume range
+const CURL_RANGE_ERROR: i32 = 33;
+const POLL_INTERVAL: Duration = Duration::from_millis(250);
+
+/// Download `url` to `dest`, resuming `<dest>.part` if present.
+///
+/// `on_progress` receives bytes downloaded so far and the total size when
+/// the server reports it. Setting `cancel` stops curl and keeps the partial
+/// file so the next attempt resumes.
+pub fn download_file(
+    url: &str,
+    dest: &Path,
+    cancel: &AtomicBool,
+    mut on_progress: impl FnMut(u64, Option<u64>),
+) -> Result<()> {
+    let part = part_path(dest);
+    let total = content_length(url);
+
+    let mut restarted = false;
+    loop {
+        let existing = part.metadata().map(|m| m.len()).unwrap_or(0);
+        if existing > 0 && Some(existing) == total {
+            break; // Already complete from an earlier run
+        }
+
+        let mut child = Command::new("curl")
+            .args(["--location", "--fail", "--silent", "--show-error"])
+            .args(["--continue-at", "-", "--output"])
+            .arg(&part)
+            .args(["--write-out", "%{content_type}"])
+            .arg(url)
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()
+            .context("Failed to run curl")?;
+
+        let status = loop {
+            if cancel.load(Ordering::Relaxed) {
+                let _ = child.kill();
+                let _ = child.wait();
+                bail!("Download cancelled");
+            }
+            if let Some(status) = child.try_wait().context("Failed to wait for curl")? {
+                break status;
+            }
+            on_progress(part.metadata().map(|m| m.len()).unwrap_or(0), total);
+            std::thread::sleep(POLL_INTERVAL);
+        };
+        let output = child
+            .wait_with_output()
+            .context("Failed to read curl output")?;
+
+        if status.code() == Some(CURL_RANGE_ERROR) && !restarted {
+            // The server can't resume; start over once
+            restarted = true;
+            let _ = std::fs::remove_file(&part);
+            continue;
+        }
+        if !status.success() {
+            bail!(
+                "Download of {} failed: {}",
+                url,
+                String::from_utf8_lossy(&output.stderr).trim()
+            );
+        }
+
+        let content_type = String::from_utf8_lossy(&output.stdout).to_lowercase();
+        if content_type.starts_with("text/html") {
+            let _ = std::fs::remove_file(&part);
+            bail!(
+                "{} is a web page, not a disk image. Copy the direct ISO link from that page.",
+                url
+            );
+        }
+        break;
+    }
+
+    let size = part.metadata().map(|m| m.len()).unwrap_or(0);
+    on_progress(size, total.or(Some(size)));
+    std::fs::rename(&part, dest)
+        .with_context(|| format!("Failed to move download into place: {}", dest.display()))
+}
+
+/// Total size from a HEAD request, following redirects
+fn content_length(url: &str) -> Option<u64> {
+    let output = Command::new("curl")
+        .args(["--location", "--fail", "--silent", "--head", url])
+        .output()
+        .ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    parse_content_length(&String::from_utf8_lossy(&output.stdout))
+}
+
+/// The `Content-Length` of the final response in a redirect chain
+fn parse_content_length(headers: &str) -> Option<u64> {
+    // Each hop starts a new header block; only the last one describes the file
+    let last = headers
+        .split("\r\n\r\n")
+        .map(str::trim)
+        .filter(|block| !block.is_empty())
+        .last()?;
+    last.lines().find_map(|line| {
+        let (name, value) = line.split_once(':')?;
+        name.trim()
+            .eq_ignore_ascii_case("content-length")
+            .then(|| value.trim().parse().ok())
+            .flatten()
+    })
+}
+
+fn part_path(dest: &Path) -> PathBuf {
+    let mut name = dest.file_name().unwrap_or_default().to_os_string();
+    name.push(".part");
+    dest.with_file_name(name)
+}
+
+/// File name at the end of a URL's path, ignoring any query or fragment
+pub fn url_file_name(url: &str) -> Option<String> {
+    let path = url.split(['?', '#']).next()?;
+    let path = path.split_once("://").map_or(path, |(_, rest)| rest);
+    let (_, file) = path.rsplit_once('/')?;
+    (!file.is_empty()).then(|| file.to_string())
+}
+
+/// Whether a URL names an image file rather than a download page
+pub fn is_direct_image_url(url: &str) -> bool {
+    url_file_name(url).is_some_and(|name| {
+        let name = name.to_lowercase();
+        name.ends_with(".iso") || name.ends_with(".img")
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_url_file_name() {
+        assert_eq!(
+            url_file_name("https://example.org/releases/24.04/ubuntu.iso?mirror=1#x").as_deref(),
+            Some("ubuntu.iso")
+        );
+        assert_eq!(url_file_name("https://ubuntu.com/download/desktop/"), None);
+        assert_eq!(url_file_name("https://example.org"), None);
+    }
+
+    #[test]
+    fn test_is_direct_image_url() {
+        assert!(is_direct_image_url(
+            "https://example.org/alpine-virt-3.20.0-x86_64.ISO"
+        ));
+        assert!(is_direct_image_url("http://example.org/fd13.img"));
+        assert!(!is_direct_image_url("https://www.freedos.org/download/"));
+        assert!(!is_direct_image_url("https://get.opensuse.org/leap/"));
+    }
+
+    #[test]
+    fn test_parse_content_length_uses_final_response() {
+        let headers = "HTTP/2 302\r\nlocation: https://mirror/x.iso\r\ncontent-length: 0\r\n\r\n\
+                       HTTP/2 200\r\nContent-Type: application/octet-stream\r\nContent-Length: 6291456\r\n\r\n";
+        assert_eq!(parse_content_length(headers), Some(6_291_456));
+        assert_eq!(parse_content_length("HTTP/1.1 200 OK\r\n\r\n"), None);
+    }
+
+    #[test]
+    fn test_part_path() {
+        assert_eq!(
+            part_path(Path::new("/lib/isos/ubuntu.iso")),
+            PathBuf::from("/lib/isos/ubuntu.iso.part")
+        );
+    }
+}
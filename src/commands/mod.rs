@@ -1,2 +1,6 @@
+pub mod checksum;
+pub mod download;
+pub mod qemu_caps;
+pub mod qemu_compat;
 pub mod qemu_img;
 pub mod qemu_system;
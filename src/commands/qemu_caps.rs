@@ -0,0 +1,288 @@
+//! Cached QEMU capability probes
+//!
+//! Asking an emulator what it supports (`--version`, `-display help`,
+//! `-audiodev help`, `-machine help`) starts a QEMU process per question,
+//! which adds up at startup and in the wizard. The answers are kept per
+//! emulator in `qemu-caps.json` in the config directory, stamped with the
+//! binary's path, size and modification time; a changed binary (a QEMU
+//! upgrade) is probed again. `vm-curator emulators --refresh` re-probes
+//! everything.
+
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::time::UNIX_EPOCH;
+
+use serde::{Deserialize, Serialize};
+
+use super::qemu_system::{get_qemu_version, get_supported_audiodevs, get_supported_displays};
+
+/// What one emulator binary supports
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EmulatorCaps {
+    /// First line of `--version`
+    pub version: Option<String>,
+    /// `-display` backends
+    pub displays: Vec<String>,
+    /// `-audiodev` drivers
+    pub audiodevs: Vec<String>,
+    /// `-machine` types, with the default one first
+    pub machines: Vec<String>,
+}
+
+/// Identifies a binary build: probes are redone when any of these change
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+struct BinaryStamp {
+    path: PathBuf,
+    size: u64,
+    mtime_ns: u128,
+}
+
+impl BinaryStamp {
+    fn of(path: &Path) -> Option<Self> {
+        let meta = std::fs::metadata(path).ok()?;
+        let mtime_ns = meta
+            .modified()
+            .ok()?
+            .duration_since(UNIX_EPOCH)
+            .ok()?
+            .as_nanos();
+        Some(Self {
+            path: path.to_path_buf(),
+            size: meta.len(),
+            mtime_ns,
+        })
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct CacheEntry {
+    stamp: BinaryStamp,
+    caps: EmulatorCaps,
+}
+
+/// Cache file, keyed by emulator name
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct CapsCache {
+    emulators: BTreeMap<String, CacheEntry>,
+}
+
+impl CapsCache {
+    fn load(path: &Path) -> Self {
+        std::fs::read_to_string(path)
+            .ok()
+            .and_then(|s| serde_json::from_str(&s).ok())
+            .unwrap_or_default()
+    }
+
+    fn save(&self, path: &Path) {
+        let Ok(json) = serde_json::to_string_pretty(self) else {
+            return;
+        };
+        if let Some(dir) = path.parent() {
+            let _ = std::fs::create_dir_all(dir);
+        }
+        if let Err(e) = std::fs::write(path, json) {
+            log::warn!("could not write {}: {e}", path.display());
+        }
+    }
+}
+
+/// Where the cache lives
+pub fn cache_path() -> PathBuf {
+    crate::config::Config::config_dir().join("qemu-caps.json")
+}
+
+/// Capabilities of an emulator on `PATH`, from the cache when the binary
+/// hasn't changed. `None` if the emulator isn't installed.
+pub fn capabilities(emulator: &str) -> Option<EmulatorCaps> {
+    let binary = find_in_path(emulator)?;
+    Some(cached_or_probe(
+        &cache_path(),
+        emulator,
+        &binary,
+        false,
+        probe,
+    ))
+}
+
+/// Probe every installed emulator again and rewrite the cache
+pub fn refresh(emulators: &[String]) {
+    let path = cache_path();
+    for emulator in emulators {
+        if let Some(binary) = find_in_path(emulator) {
+            cached_or_probe(&path, emulator, &binary, true, probe);
+        }
+    }
+}
+
+/// Look up `emulator` in `cache_file`, probing with `probe` (and storing the
+/// result) when it's missing, stale or `force` is set. A probe that couldn't
+/// even get the version (QEMU not runnable yet) isn't stored, so the next
+/// lookup tries again.
+fn cached_or_probe(
+    cache_file: &Path,
+    emulator: &str,
+    binary: &Path,
+    force: bool,
+    probe: impl FnOnce(&str) -> EmulatorCaps,
+) -> EmulatorCaps {
+    let mut cache = CapsCache::load(cache_file);
+    let stamp = BinaryStamp::of(binary);
+    if let (false, Some(stamp), Some(entry)) = (force, &stamp, cache.emulators.get(emulator)) {
+        if entry.stamp == *stamp {
+            return entry.caps.clone();
+        }
+    }
+
+    let caps = probe(emulator);
+    if let (Some(stamp), true) = (stamp, caps.version.is_some()) {
+        cache.emulators.insert(
+            emulator.to_string(),
+            CacheEntry {
+                stamp,
+                caps: caps.clone(),
+            },
+        );
+        cache.save(cache_file);
+    }
+    caps
+}
+
+/// Ask the emulator itself
+fn probe(emulator: &str) -> EmulatorCaps {
+    EmulatorCaps {
+        version: get_qemu_version(emulator).ok(),
+        displays: get_supported_displays(emulator),
+        audiodevs: get_supported_audiodevs(emulator),
+        machines: get_supported_machines(emulator),
+    }
+}
+
+/// `-machine help`: one type per line after the header, the default one
+/// marked `(default)`. Returned with the default first.
+fn get_supported_machines(emulator: &str) -> Vec<String> {
+    match Command::new(emulator).args(["-machine", "help"]).output() {
+        Ok(output) => parse_machine_help(&String::from_utf8_lossy(&output.stdout)),
+        Err(_) => Vec::new(),
+    }
+}
+
+fn parse_machine_help(text: &str) -> Vec<String> {
+    let mut machines = Vec::new();
+    for line in text
+        .lines()
+        .skip_while(|l| !l.trim_end().ends_with(':'))
+        .skip(1)
+    {
+        let Some(name) = line.split_whitespace().next() else {
+            continue;
+        };
+        if line.contains("(default)") {
+            machines.insert(0, name.to_string());
+        } else {
+            machines.push(name.to_string());
+        }
+    }
+    machines
+}
+
+/// Full path of a program on `PATH`
+fn find_in_path(program: &str) -> Option<PathBuf> {
+    std::env::split_paths(&std::env::var_os("PATH")?)
+        .map(|dir| dir.join(program))
+        .find(|path| path.is_file())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::Cell;
+
+    #[test]
+    fn cache_reprobes_only_when_binary_changes() {
+        let dir = tempfile::tempdir().unwrap();
+        let cache = dir.path().join("qemu-caps.json");
+        let binary = dir.path().join("qemu-system-x86_64");
+        std::fs::write(&binary, "v1").unwrap();
+
+        let probes = Cell::new(0);
+        let probe = |_: &str| {
+            probes.set(probes.get() + 1);
+            EmulatorCaps {
+                version: Some(format!("QEMU emulator version 9.{}.0", probes.get())),
+                displays: vec!["gtk".to_string()],
+                ..Default::default()
+            }
+        };
+
+        let first = cached_or_probe(&cache, "qemu-system-x86_64", &binary, false, probe);
+        let second = cached_or_probe(&cache, "qemu-system-x86_64", &binary, false, probe);
+        assert_eq!(probes.get(), 1);
+        assert_eq!(first, second);
+
+        // A different size counts as a new build
+        std::fs::write(&binary, "version 2").unwrap();
+        let third = cached_or_probe(&cache, "qemu-system-x86_64", &binary, false, probe);
+        assert_eq!(probes.get(), 2);
+        assert_eq!(
+            third.version.as_deref(),
+            Some("QEMU emulator version 9.2.0")
+        );
+
+        cached_or_probe(&cache, "qemu-system-x86_64", &binary, true, probe);
+        assert_eq!(probes.get(), 3);
+    }
+
+    #[test]
+    fn unreadable_cache_is_rebuilt() {
+        let dir = tempfile::tempdir().unwrap();
+        let cache = dir.path().join("qemu-caps.json");
+        let binary = dir.path().join("qemu-system-i386");
+        std::fs::write(&cache, "not json").unwrap();
+        std::fs::write(&binary, "").unwrap();
+
+        cached_or_probe(&cache, "qemu-system-i386", &binary, false, |_| {
+            EmulatorCaps {
+                version: Some("QEMU emulator version 9.0.0".to_string()),
+                ..Default::default()
+            }
+        });
+        assert!(CapsCache::load(&cache)
+            .emulators
+            .contains_key("qemu-system-i386"));
+    }
+
+    #[test]
+    fn failed_probe_is_not_cached() {
+        let dir = tempfile::tempdir().unwrap();
+        let cache = dir.path().join("qemu-caps.json");
+        let binary = dir.path().join("qemu-system-aarch64");
+        std::fs::write(&binary, "").unwrap();
+
+        let probes = Cell::new(0);
+        let probe = |_: &str| {
+            probes.set(probes.get() + 1);
+            EmulatorCaps::default()
+        };
+        cached_or_probe(&cache, "qemu-system-aarch64", &binary, false, probe);
+        cached_or_probe(&cache, "qemu-system-aarch64", &binary, false, probe);
+        assert_eq!(probes.get(), 2);
+        assert!(!CapsCache::load(&cache)
+            .emulators
+            .contains_key("qemu-system-aarch64"));
+    }
+
+    #[test]
+    fn parse_machine_help_puts_default_first() {
+        let text = "Supported machines are:\n\
+                    microvm              microvm (i386)\n\
+                    pc                   Standard PC (i440FX + PIIX, 1996) (alias of pc-i440fx-9.0)\n\
+                    pc-i440fx-9.0        Standard PC (i440FX + PIIX, 1996) (default)\n\
+                    q35                  Standard PC (Q35 + ICH9, 2009) (alias of pc-q35-9.0)\n";
+        assert_eq!(
+            parse_machine_help(text),
+            vec!["pc-i440fx-9.0", "microvm", "pc", "q35"]
+        );
+    }
+}
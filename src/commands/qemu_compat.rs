@@ -0,0 +1,248 @@
+//! QEMU version compatibility
+//!
+//! Option syntax moves between QEMU releases: `-audiodev` only exists from
+//! 4.0, `virtio-vga-gl` from 6.1, the native `-netdev passt` from 10.1, and
+//! `-soundhw` is gone since 7.1. A small capability map records those
+//! releases so a launch script can be checked against (or rewritten for) the
+//! QEMU that is actually installed.
+
+use std::fmt;
+
+use super::qemu_caps;
+
+/// A QEMU release number
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct QemuVersion {
+    pub major: u32,
+    pub minor: u32,
+    pub micro: u32,
+}
+
+impl QemuVersion {
+    pub const fn new(major: u32, minor: u32, micro: u32) -> Self {
+        Self {
+            major,
+            minor,
+            micro,
+        }
+    }
+
+    /// Parse the first line of `qemu-system-* --version`, e.g.
+    /// `QEMU emulator version 8.2.2 (Debian 1:8.2.2+ds-0ubuntu1)`
+    pub fn parse(version_line: &str) -> Option<Self> {
+        let rest = &version_line[version_line.find("version ")? + "version ".len()..];
+        let number = rest.split_whitespace().next()?;
+        let mut parts = number
+            .split(|c: char| !c.is_ascii_digit())
+            .map(|p| p.parse::<u32>().ok());
+        let major = parts.next()??;
+        let minor = parts.next()??;
+        let micro = parts.next().flatten().unwrap_or(0);
+        Some(Self::new(major, minor, micro))
+    }
+}
+
+impl fmt::Display for QemuVersion {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}.{}.{}", self.major, self.minor, self.micro)
+    }
+}
+
+/// Version of an installed emulator, or `None` if it can't be run or its
+/// output isn't recognized
+pub fn installed_version(emulator: &str) -> Option<QemuVersion> {
+    QemuVersion::parse(&qemu_caps::capabilities(emulator)?.version?)
+}
+
+/// A launch script option whose availability depends on the QEMU version
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Feature {
+    /// `-audiodev` host audio backends
+    Audiodev,
+    /// `-display spice-app`
+    SpiceApp,
+    /// `-display dbus`
+    DbusDisplay,
+    /// `-audio` shorthand for backend plus device
+    AudioOption,
+    /// The `virtio-vga-gl` / `virtio-gpu-gl-pci` devices
+    VirglDevices,
+    /// `-audiodev pipewire`
+    PipewireAudio,
+    /// The native `-netdev passt` backend
+    PasstNetdev,
+    /// `-soundhw`, replaced by `-audio`
+    Soundhw,
+}
+
+impl Feature {
+    pub const ALL: [Feature; 8] = [
+        Feature::Audiodev,
+        Feature::SpiceApp,
+        Feature::DbusDisplay,
+        Feature::AudioOption,
+        Feature::VirglDevices,
+        Feature::PipewireAudio,
+        Feature::PasstNetdev,
+        Feature::Soundhw,
+    ];
+
+    /// Text that shows the feature is used on a script line
+    fn patterns(self) -> &'static [&'static str] {
+        match self {
+            Feature::Audiodev => &["-audiodev "],
+            Feature::SpiceApp => &["-display spice-app"],
+            Feature::DbusDisplay => &["-display dbus"],
+            Feature::AudioOption => &["-audio "],
+            Feature::VirglDevices => &["virtio-vga-gl", "virtio-gpu-gl"],
+            Feature::PipewireAudio => &["-audiodev pipewire"],
+            Feature::PasstNetdev => &["-netdev passt"],
+            Feature::Soundhw => &["-soundhw "],
+        }
+    }
+
+    /// First release with the feature
+    fn since(self) -> Option<QemuVersion> {
+        match self {
+            Feature::Audiodev | Feature::SpiceApp => Some(QemuVersion::new(4, 0, 0)),
+            Feature::DbusDisplay => Some(QemuVersion::new(7, 0, 0)),
+            Feature::AudioOption => Some(QemuVersion::new(7, 1, 0)),
+            Feature::VirglDevices => Some(QemuVersion::new(6, 1, 0)),
+            Feature::PipewireAudio => Some(QemuVersion::new(8, 1, 0)),
+            Feature::PasstNetdev => Some(QemuVersion::new(10, 1, 0)),
+            Feature::Soundhw => None,
+        }
+    }
+
+    /// First release without the feature
+    fn removed_in(self) -> Option<QemuVersion> {
+        match self {
+            Feature::Soundhw => Some(QemuVersion::new(7, 1, 0)),
+            _ => None,
+        }
+    }
+
+    /// The option as shown in warnings
+    pub fn name(self) -> &'static str {
+        self.patterns()[0].trim_end()
+    }
+
+    /// Whether `version` accepts the feature
+    pub fn supported_by(self, version: QemuVersion) -> bool {
+        self.since().is_none_or(|since| version >= since)
+            && self.removed_in().is_none_or(|removed| version < removed)
+    }
+
+    /// Whether an uncommented line of `script` uses the feature
+    fn used_in(self, script: &str) -> bool {
+        script
+            .lines()
+            .filter(|line| !line.trim_start().starts_with('#'))
+            .any(|line| self.patterns().iter().any(|p| line.contains(p)))
+    }
+
+    /// Why `version` can't run the feature
+    fn problem(self, version: QemuVersion) -> String {
+        match (self.since(), self.removed_in()) {
+            (_, Some(removed)) if version >= removed => format!(
+                "{} was removed in QEMU {} (installed: {})",
+                self.name(),
+                removed,
+                version
+            ),
+            (Some(since), _) => format!(
+                "{} needs QEMU {} or newer (installed: {})",
+                self.name(),
+                since,
+                version
+            ),
+            _ => format!("{} isn't supported by QEMU {}", self.name(), version),
+        }
+    }
+}
+
+/// Options in `script` that `version` doesn't accept, one message each
+pub fn check_script(script: &str, version: QemuVersion) -> Vec<String> {
+    Feature::ALL
+        .into_iter()
+        .filter(|f| !f.supported_by(version) && f.used_in(script))
+        .map(|f| f.problem(version))
+        .collect()
+}
+
+/// Rewrite options `version` doesn't accept into older spellings where one
+/// exists: virgl devices become `virtio-vga,virgl=on` and PipeWire audio
+/// falls back to PulseAudio (which pipewire-pulse also serves). Anything
+/// else is left for [`check_script`] to report.
+pub fn adapt_script(script: &str, version: QemuVersion) -> String {
+    let mut content = script.to_string();
+    if !Feature::VirglDevices.supported_by(version) {
+        content = content
+            .replace("virtio-vga-gl", "virtio-vga,virgl=on")
+            .replace("virtio-gpu-gl-pci", "virtio-gpu-pci,virgl=on");
+    }
+    if !Feature::PipewireAudio.supported_by(version) {
+        content = content.replace("-audiodev pipewire", "-audiodev pa");
+    }
+    content
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_version_lines() {
+        assert_eq!(
+            QemuVersion::parse("QEMU emulator version 8.2.2 (Debian 1:8.2.2+ds-0ubuntu1)"),
+            Some(QemuVersion::new(8, 2, 2))
+        );
+        assert_eq!(
+            QemuVersion::parse("QEMU emulator version 10.1.0"),
+            Some(QemuVersion::new(10, 1, 0))
+        );
+        assert_eq!(
+            QemuVersion::parse("QEMU emulator version 9.0.50 (v9.0.0-1234-gabc)"),
+            Some(QemuVersion::new(9, 0, 50))
+        );
+        assert_eq!(QemuVersion::parse("Unknown"), None);
+    }
+
+    #[test]
+    fn check_script_reports_new_and_removed_options() {
+        let script = "qemu-system-x86_64 \\\n\
+                      -audiodev pipewire,id=audio0 \\\n\
+                      -netdev passt,id=net0 \\\n\
+                      # -soundhw hda\n";
+        let issues = check_script(script, QemuVersion::new(8, 0, 4));
+        assert_eq!(
+            issues,
+            vec![
+                "-audiodev pipewire needs QEMU 8.1.0 or newer (installed: 8.0.4)",
+                "-netdev passt needs QEMU 10.1.0 or newer (installed: 8.0.4)",
+            ]
+        );
+        assert!(check_script(script, QemuVersion::new(10, 1, 0)).is_empty());
+
+        let issues = check_script(
+            "qemu-system-i386 -soundhw sb16\n",
+            QemuVersion::new(8, 2, 0),
+        );
+        assert_eq!(
+            issues,
+            vec!["-soundhw was removed in QEMU 7.1.0 (installed: 8.2.0)"]
+        );
+    }
+
+    #[test]
+    fn adapt_script_uses_older_spellings() {
+        let script = "qemu-system-x86_64 -device virtio-vga-gl -audiodev pipewire,id=audio0\n";
+        let adapted = adapt_script(script, QemuVersion::new(6, 0, 0));
+        assert_eq!(
+            adapted,
+            "qemu-system-x86_64 -device virtio-vga,virgl=on -audiodev pa,id=audio0\n"
+        );
+        assert!(check_script(&adapted, QemuVersion::new(6, 0, 0)).is_empty());
+        assert_eq!(adapt_script(script, QemuVersion::new(9, 2, 0)), script);
+    }
+}
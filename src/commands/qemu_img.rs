@@ -33,24 +33,80 @@ pub fn create_disk_with_format(path: &Path, format: &str, size: &str) -> Result<
     Ok(())
 }
 
-/// Convert a disk image from one format to another (e.g., DMG to qcow2)
-#[allow(dead_code)]
-pub fn convert_disk(source: &Path, dest: &Path, dest_format: &str) -> Result<()> {
+/// Convert a disk image from one format to another (e.g., DMG to qcow2).
+/// `on_progress` receives the percent complete as qemu-img reports it.
+pub fn convert_disk(
+    source: &Path,
+    dest: &Path,
+    dest_format: &str,
+    on_progress: impl FnMut(f64),
+) -> Result<()> {
     let source_str = path_to_str(source)?;
     let dest_str = path_to_str(dest)?;
-    let output = Command::new("qemu-img")
-        .args(["convert", "-O", dest_format, source_str, dest_str])
-        .output()
-        .context("Failed to run qemu-img convert")?;
+    run_with_progress(
+        &["convert", "-p", "-O", dest_format, source_str, dest_str],
+        on_progress,
+    )
+    .context("Failed to convert disk")
+}
 
-    if !output.status.success() {
-        let stderr = String::from_utf8_lossy(&output.stderr);
-        bail!("Failed to convert disk: {}", stderr);
+/// Run qemu-img with `-p` and report each progress update from stdout.
+///
+/// qemu-img redraws a single line like `    (45.01/100%)` using carriage
+/// returns, so stdout is split on `\r` as well as newlines.
+pub fn run_with_progress(args: &[&str], mut on_progress: impl FnMut(f64)) -> Result<()> {
+    use std::io::Read;
+    use std::process::Stdio;
+
+    let mut child = Command::new("qemu-img")
+        .args(args)
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .with_context(|| format!("Failed to run qemu-img {}", args.first().unwrap_or(&"")))?;
+
+    // Drain stderr on its own thread so a chatty failure can't block stdout
+    let mut stderr = child.stderr.take().context("qemu-img stderr unavailable")?;
+    let stderr_reader = std::thread::spawn(move || {
+        let mut buf = String::new();
+        let _ = stderr.read_to_string(&mut buf);
+        buf
+    });
+
+    let mut stdout = child.stdout.take().context("qemu-img stdout unavailable")?;
+    let mut pending = Vec::new();
+    let mut chunk = [0u8; 256];
+    loop {
+        let n = stdout.read(&mut chunk)?;
+        if n == 0 {
+            break;
+        }
+        for &b in &chunk[..n] {
+            if b == b'\r' || b == b'\n' {
+                if let Some(percent) = parse_progress_line(&String::from_utf8_lossy(&pending)) {
+                    on_progress(percent);
+                }
+                pending.clear();
+            } else {
+                pending.push(b);
+            }
+        }
     }
 
+    let status = child.wait().context("Failed to wait for qemu-img")?;
+    let stderr = stderr_reader.join().unwrap_or_default();
+    if !status.success() {
+        bail!("{}", stderr.trim());
+    }
     Ok(())
 }
 
+/// Parse a qemu-img progress line such as `    (45.01/100%)` into a percentage
+fn parse_progress_line(line: &str) -> Option<f64> {
+    let inner = line.trim().strip_prefix('(')?.strip_suffix("/100%)")?;
+    inner.trim().parse().ok()
+}
+
 /// Detect the format of a disk image (returns format string like "qcow2", "raw", etc.)
 pub fn detect_disk_format(path: &Path) -> Option<String> {
     let path_str = path_to_str(path).ok()?;
@@ -67,6 +123,162 @@ pub fn detect_disk_format(path: &Path) -> Option<String> {
     parse_format_from_info_json(&stdout)
 }
 
+/// qemu-img's error when another process holds QEMU's write lock on a disk
+/// image; `None` when the image is free or qemu-img can't tell
+pub fn image_lock_error(path: &Path) -> Option<String> {
+    let path_str = path_to_str(path).ok()?;
+    // Without -U, qemu-img takes the same shared lock QEMU checks on open
+    let output = Command::new("qemu-img")
+        .args(["info", "--output=json", path_str])
+        .output()
+        .ok()?;
+    if output.status.success() {
+        return None;
+    }
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    is_lock_error(&stderr).then(|| stderr.trim().to_string())
+}
+
+/// Whether qemu-img failed because the image is locked, e.g.
+/// `Failed to get shared "write" lock`
+fn is_lock_error(stderr: &str) -> bool {
+    stderr.contains("Failed to get") && stderr.contains("lock")
+}
+
+/// Host space a disk image occupies versus the size the guest sees
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DiskUsage {
+    /// Bytes allocated on the host (`actual-size`)
+    pub actual_bytes: u64,
+    /// Disk size as seen by the guest (`virtual-size`)
+    pub virtual_bytes: u64,
+}
+
+impl DiskUsage {
+    /// e.g. "12.3G used of 64.0G"
+    pub fn summary(&self) -> String {
+        format!(
+            "{} used of {}",
+            crate::vm::snapshot::format_size(self.actual_bytes),
+            crate::vm::snapshot::format_size(self.virtual_bytes)
+        )
+    }
+}
+
+/// How much of a disk image is in use. Uses `qemu-img info` (with `-U`, so it
+/// works while the VM holds the image lock), falling back to the file's
+/// allocated blocks and length when qemu-img can't read it.
+pub fn disk_usage(path: &Path) -> Option<DiskUsage> {
+    let from_qemu_img = path_to_str(path).ok().and_then(|path_str| {
+        let output = Command::new("qemu-img")
+            .args(["info", "-U", "--output=json", path_str])
+            .output()
+            .ok()?;
+        if !output.status.success() {
+            return None;
+        }
+        parse_usage_from_info_json(&String::from_utf8_lossy(&output.stdout))
+    });
+    from_qemu_img.or_else(|| {
+        use std::os::unix::fs::MetadataExt;
+        let metadata = std::fs::metadata(path).ok()?;
+        Some(DiskUsage {
+            actual_bytes: metadata.blocks() * 512,
+            virtual_bytes: metadata.len(),
+        })
+    })
+}
+
+/// Whether `virt-sparsify` (libguestfs) is installed. It compacts qcow2
+/// images in place, leaving their internal snapshots intact.
+pub fn has_virt_sparsify() -> bool {
+    Command::new("which")
+        .arg("virt-sparsify")
+        .output()
+        .map(|o| o.status.success())
+        .unwrap_or(false)
+}
+
+/// Reclaim space the guest has freed inside a qcow2 image. Returns the
+/// allocated size in bytes before and after.
+///
+/// Uses `virt-sparsify --in-place` when installed. Otherwise the image is
+/// rewritten with `qemu-img convert` into a temp file beside it, which then
+/// replaces the original; that drops internal snapshots, so warn first.
+/// `on_progress` gets `None` when the tool reports no percentage.
+pub fn compact_disk(path: &Path, mut on_progress: impl FnMut(Option<f64>)) -> Result<(u64, u64)> {
+    let path_str = path_to_str(path)?;
+    let output = Command::new("qemu-img")
+        .args(["info", "--output=json", path_str])
+        .output()
+        .context("Failed to run qemu-img info")?;
+    if !output.status.success() {
+        bail!("{}", String::from_utf8_lossy(&output.stderr).trim());
+    }
+    check_compactable(&String::from_utf8_lossy(&output.stdout))?;
+
+    let allocated = |path: &Path| disk_usage(path).map(|u| u.actual_bytes).unwrap_or(0);
+    let before = allocated(path);
+
+    if has_virt_sparsify() {
+        on_progress(None);
+        let output = Command::new("virt-sparsify")
+            .args(["--in-place", path_str])
+            .output()
+            .context("Failed to run virt-sparsify")?;
+        if !output.status.success() {
+            bail!(
+                "virt-sparsify failed: {}",
+                String::from_utf8_lossy(&output.stderr).trim()
+            );
+        }
+    } else {
+        let file_name = path
+            .file_name()
+            .context("Disk path has no file name")?
+            .to_string_lossy();
+        let temp = path.with_file_name(format!(".{}.compact", file_name));
+        let result = convert_disk(path, &temp, "qcow2", |p| on_progress(Some(p))).and_then(|_| {
+            std::fs::rename(&temp, path)
+                .with_context(|| format!("Failed to replace {}", path.display()))
+        });
+        if let Err(e) = result {
+            let _ = std::fs::remove_file(&temp);
+            return Err(e);
+        }
+    }
+
+    Ok((before, allocated(path)))
+}
+
+/// Refuse to compact anything but a standalone qcow2 image; converting an
+/// overlay would flatten its backing file into it.
+fn check_compactable(info_json: &str) -> Result<()> {
+    let json: serde_json::Value =
+        serde_json::from_str(info_json).context("Unreadable qemu-img info output")?;
+    match json["format"].as_str() {
+        Some("qcow2") => {}
+        Some(other) => bail!("Only qcow2 disks can be compacted (this one is {})", other),
+        None => bail!("Could not determine the disk format"),
+    }
+    if let Some(backing) = json["backing-filename"].as_str() {
+        bail!(
+            "Disk is an overlay on {}; compacting would merge the two",
+            backing
+        );
+    }
+    Ok(())
+}
+
+/// Extract `actual-size` and `virtual-size` from `qemu-img info --output=json`
+fn parse_usage_from_info_json(stdout: &str) -> Option<DiskUsage> {
+    let json: serde_json::Value = serde_json::from_str(stdout).ok()?;
+    Some(DiskUsage {
+        actual_bytes: json["actual-size"].as_u64()?,
+        virtual_bytes: json["virtual-size"].as_u64()?,
+    })
+}
+
 /// Extract the `format` field from the JSON emitted by `qemu-img info --output=json`.
 ///
 /// Returns `None` if the JSON is malformed or has no string `format` field. Kept
@@ -82,6 +294,17 @@ mod tests {
     use super::*;
     use std::path::PathBuf;
 
+    #[test]
+    fn lock_error_detection() {
+        assert!(is_lock_error(
+            "qemu-img: Could not open 'disk.qcow2': Failed to get shared \"write\" lock\n\
+             Is another process using the image [disk.qcow2]?"
+        ));
+        assert!(!is_lock_error(
+            "qemu-img: Could not open 'disk.qcow2': Could not open 'disk.qcow2': No such file or directory"
+        ));
+    }
+
     #[test]
     fn parse_format_qcow2() {
         let json = r#"{"virtual-size":42949672960,"filename":"disk.qcow2","format":"qcow2","actual-size":200704}"#;
@@ -112,6 +335,34 @@ mod tests {
         assert_eq!(parse_format_from_info_json(""), None);
     }
 
+    #[test]
+    fn parse_usage_qcow2() {
+        let json = r#"{"virtual-size":68719476736,"filename":"disk.qcow2","format":"qcow2","actual-size":13207024435}"#;
+        let usage = parse_usage_from_info_json(json).unwrap();
+        assert_eq!(usage.actual_bytes, 13207024435);
+        assert_eq!(usage.virtual_bytes, 68719476736);
+        assert_eq!(usage.summary(), "12.3G used of 64.0G");
+        assert_eq!(parse_usage_from_info_json(r#"{"format":"raw"}"#), None);
+    }
+
+    #[test]
+    fn compactable_only_standalone_qcow2() {
+        assert!(check_compactable(r#"{"format":"qcow2","virtual-size":1}"#).is_ok());
+        assert!(check_compactable(r#"{"format":"raw"}"#).is_err());
+        assert!(
+            check_compactable(r#"{"format":"qcow2","backing-filename":"base.qcow2"}"#).is_err()
+        );
+        assert!(check_compactable("not json").is_err());
+    }
+
+    #[test]
+    fn parse_progress_lines() {
+        assert_eq!(parse_progress_line("    (45.01/100%)"), Some(45.01));
+        assert_eq!(parse_progress_line("(100.00/100%)"), Some(100.0));
+        assert_eq!(parse_progress_line(""), None);
+        assert_eq!(parse_progress_line("qemu-img: error"), None);
+    }
+
     #[test]
     fn path_to_str_valid_utf8() {
         let path = PathBuf::from("/tmp/disk.qcow2");
@@ -69,6 +69,42 @@ pub fn get_supported_displays(emulator: &str) -> Vec<String> {
     parse_display_help(&text)
 }
 
+/// Host audio backends offered for `-audiodev`. `spice` only works with a
+/// SPICE display.
+pub const AUDIO_BACKENDS: &[&str] = &["pipewire", "pa", "alsa", "spice", "none"];
+
+/// Get the `-audiodev` drivers a QEMU emulator was built with.
+///
+/// `-audiodev help` lists them in the same layout as `-display help`.
+pub fn get_supported_audiodevs(emulator: &str) -> Vec<String> {
+    match Command::new(emulator).args(["-audiodev", "help"]).output() {
+        Ok(output) => parse_display_help(&String::from_utf8_lossy(&output.stdout)),
+        Err(_) => Vec::new(),
+    }
+}
+
+/// Pick a host audio backend for a new VM by probing the session's sound
+/// server sockets: PipeWire when it's running and QEMU has the driver (8.1+),
+/// else PulseAudio (which pipewire-pulse also serves), else ALSA.
+pub fn detect_audio_backend(emulator: &str) -> &'static str {
+    let runtime_dir = std::env::var_os("XDG_RUNTIME_DIR").map(PathBuf::from);
+    let has_socket = |name: &str| runtime_dir.as_ref().is_some_and(|d| d.join(name).exists());
+    let pipewire = has_socket("pipewire-0")
+        && super::qemu_caps::capabilities(emulator)
+            .is_some_and(|caps| caps.audiodevs.iter().any(|d| d == "pipewire"));
+    pick_audio_backend(pipewire, has_socket("pulse/native"))
+}
+
+fn pick_audio_backend(pipewire: bool, pulse: bool) -> &'static str {
+    if pipewire {
+        "pipewire"
+    } else if pulse {
+        "pa"
+    } else {
+        "alsa"
+    }
+}
+
 /// Parse the output of `<emulator> -display help`.
 ///
 /// QEMU prints a header line ending in ":", a list of backend names (one per
@@ -136,6 +172,90 @@ pub fn is_spice_viewer_available() -> bool {
     false
 }
 
+/// The SPICE viewer that opens `spice://` and `spice+unix://` URIs, if
+/// installed. `virt-viewer` only connects to libvirt domains.
+pub fn spice_uri_viewer() -> Option<&'static str> {
+    is_emulator_available("remote-viewer").then_some("remote-viewer")
+}
+
+/// Explain why KVM can't be used on this host, or `None` if `/dev/kvm` is
+/// present and openable read-write by the current user.
+pub fn kvm_unavailable_reason() -> Option<String> {
+    let dev = Path::new("/dev/kvm");
+    if !dev.exists() {
+        return Some(
+            "/dev/kvm does not exist. The KVM module isn't loaded, virtualization \
+             (VT-x/AMD-V) is disabled in firmware, or this host is itself a VM \
+             without nested virtualization."
+                .to_string(),
+        );
+    }
+    match std::fs::OpenOptions::new().read(true).write(true).open(dev) {
+        Ok(_) => None,
+        Err(e) if e.kind() == std::io::ErrorKind::PermissionDenied => Some(format!(
+            "No permission to open /dev/kvm (module: {}). Add your user to the \
+             'kvm' group (sudo usermod -aG kvm $USER) and log in again.",
+            get_kvm_info().unwrap_or_else(|| "unknown".to_string())
+        )),
+        Err(e) => Some(format!("Cannot open /dev/kvm: {}", e)),
+    }
+}
+
+/// Host-side nested virtualization state of the loaded KVM module
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NestedVirtSupport {
+    /// `kvm_intel` or `kvm_amd`
+    pub module: &'static str,
+    /// Whether the module's `nested` parameter is on
+    pub enabled: bool,
+}
+
+impl NestedVirtSupport {
+    /// Commands that turn nesting on now and across reboots
+    pub fn enable_hint(&self) -> String {
+        format!(
+            "echo 'options {m} nested=1' | sudo tee /etc/modprobe.d/kvm-nested.conf && \
+             sudo modprobe -r {m} && sudo modprobe {m}",
+            m = self.module
+        )
+    }
+}
+
+/// Virtualization CPU flag of this host: `svm` on AMD/Hygon, `vmx` otherwise
+pub fn host_virt_flag() -> &'static str {
+    let cpuinfo = std::fs::read_to_string("/proc/cpuinfo").unwrap_or_default();
+    let vendor = cpuinfo
+        .lines()
+        .find_map(|l| l.strip_prefix("vendor_id"))
+        .unwrap_or_default();
+    if vendor.contains("AuthenticAMD") || vendor.contains("HygonGenuine") {
+        "svm"
+    } else {
+        "vmx"
+    }
+}
+
+/// Check `/sys/module/kvm_{intel,amd}/parameters/nested`. Returns `None`
+/// when the vendor KVM module isn't loaded.
+pub fn nested_virt_support() -> Option<NestedVirtSupport> {
+    let module = if host_virt_flag() == "svm" {
+        "kvm_amd"
+    } else {
+        "kvm_intel"
+    };
+    let value =
+        std::fs::read_to_string(format!("/sys/module/{}/parameters/nested", module)).ok()?;
+    Some(NestedVirtSupport {
+        module,
+        enabled: nested_param_enabled(&value),
+    })
+}
+
+/// `kvm_intel` reports `Y`/`N`, `kvm_amd` reports `1`/`0`
+fn nested_param_enabled(value: &str) -> bool {
+    matches!(value.trim(), "Y" | "y" | "1")
+}
+
 /// Get KVM module info
 pub fn get_kvm_info() -> Option<String> {
     if !is_kvm_available() {
@@ -259,6 +379,38 @@ fn list_system_bridges() -> Vec<String> {
 mod tests {
     use super::*;
 
+    #[test]
+    fn nested_param_accepts_both_module_formats() {
+        assert!(nested_param_enabled("Y\n"));
+        assert!(nested_param_enabled("1\n"));
+        assert!(!nested_param_enabled("N\n"));
+        assert!(!nested_param_enabled("0"));
+    }
+
+    #[test]
+    fn audio_backend_prefers_pipewire_then_pulse() {
+        assert_eq!(pick_audio_backend(true, true), "pipewire");
+        assert_eq!(pick_audio_backend(false, true), "pa");
+        assert_eq!(pick_audio_backend(false, false), "alsa");
+    }
+
+    #[test]
+    fn parse_audiodev_help() {
+        let raw = "Available audio drivers:\nnone\nalsa\npa\npipewire\nspice\nwav\n";
+        let parsed = parse_display_help(raw);
+        assert!(parsed.contains(&"pipewire".to_string()));
+        assert_eq!(parsed.len(), 6);
+    }
+
+    #[test]
+    fn nested_hint_names_module() {
+        let amd = NestedVirtSupport {
+            module: "kvm_amd",
+            enabled: false,
+        };
+        assert!(amd.enable_hint().contains("options kvm_amd nested=1"));
+    }
+
     #[test]
     fn parse_display_help_extracts_only_backend_names() {
         // Real output from `qemu-system-x86_64 -display help` on QEMU 10.x.
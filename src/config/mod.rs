@@ -1,6 +1,55 @@
 use anyhow::{Context, Result};
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::ffi::OsString;
 use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+
+use crate::metadata::SortBy;
+use crate::wizard_types::OvmfPaths;
+
+/// Environment variable naming the config file, below `--config`
+pub const CONFIG_ENV: &str = "VM_CURATOR_CONFIG";
+
+/// Environment variable naming the VM library, below `--library` and above
+/// the config file's `vm_library_path`
+pub const LIBRARY_ENV: &str = "VM_CURATOR_LIBRARY";
+
+/// Config file given with `--config`, set once at startup
+static CONFIG_FILE_FLAG: OnceLock<PathBuf> = OnceLock::new();
+
+/// An environment variable's value, unless unset or empty
+fn env_path(env: &impl Fn(&str) -> Option<OsString>, key: &str) -> Option<PathBuf> {
+    env(key).filter(|v| !v.is_empty()).map(PathBuf::from)
+}
+
+/// Config file path by precedence: `flag` (`--config`), `$VM_CURATOR_CONFIG`,
+/// `$XDG_CONFIG_HOME/vm-curator/config.toml` (ignored unless absolute, as
+/// the XDG spec says), then the platform config directory. `env` looks up
+/// environment variables.
+pub(crate) fn resolve_config_file(
+    flag: Option<PathBuf>,
+    env: impl Fn(&str) -> Option<OsString>,
+) -> PathBuf {
+    if let Some(path) = flag.or_else(|| env_path(&env, CONFIG_ENV)) {
+        return path;
+    }
+    env_path(&env, "XDG_CONFIG_HOME")
+        .filter(|p| p.is_absolute())
+        .or_else(dirs::config_dir)
+        .unwrap_or_else(|| PathBuf::from(".config"))
+        .join("vm-curator")
+        .join("config.toml")
+}
+
+/// VM library given with `flag` (`--library`) or `$VM_CURATOR_LIBRARY`,
+/// which override the config file (`None` = use the config file's)
+pub fn library_override(
+    flag: Option<PathBuf>,
+    env: impl Fn(&str) -> Option<OsString>,
+) -> Option<PathBuf> {
+    flag.or_else(|| env_path(&env, LIBRARY_ENV))
+}
 
 /// Application configuration
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -35,6 +84,33 @@ pub struct Config {
     // === Behavior ===
     /// Show confirmation dialog before launching VMs
     pub confirm_before_launch: bool,
+    /// Ask what to do with running VMs when quitting
+    pub confirm_quit_with_running_vms: bool,
+    /// Scheduled snapshots to keep per VM (oldest are pruned; 0 = keep all)
+    pub scheduled_snapshot_keep: u32,
+    /// Seconds to wait for a launched VM's QMP socket before warning that it
+    /// may be stuck (0 = don't check)
+    pub vm_start_timeout_secs: u32,
+    /// Where deleted VMs are moved (`None` = `.trash` in the VM library)
+    pub trash_path: Option<PathBuf>,
+    /// Where install ISOs are kept and downloaded to
+    /// (`None` = `isos` in the VM library)
+    pub iso_library_path: Option<PathBuf>,
+    /// VM list order picked with the main menu's sort key
+    /// (`None` = each category's own order)
+    pub vm_list_sort: Option<SortBy>,
+    /// Serve `spice-app` displays on a TCP port instead of a socket in the
+    /// VM directory, for viewers on other hosts
+    pub spice_tcp: bool,
+    /// OVMF_CODE image for UEFI VMs (`None` = auto-detect). Only used
+    /// together with `ovmf_vars_path`.
+    pub ovmf_code_path: Option<PathBuf>,
+    /// OVMF_VARS template copied into each UEFI VM's folder
+    pub ovmf_vars_path: Option<PathBuf>,
+    /// Bug reports write the home directory as `~`
+    pub report_redact_paths: bool,
+    /// Bug reports blank out passwords, secrets and tokens
+    pub report_redact_secrets: bool,
 
     // === Multi-GPU Passthrough ===
     /// Enable multi-GPU passthrough features in the UI
@@ -55,14 +131,23 @@ pub struct Config {
     pub looking_glass_client_path: Option<PathBuf>,
     /// Auto-launch Looking Glass client when VM starts
     pub looking_glass_auto_launch: bool,
+
+    // === File Browser ===
+    /// Last directory a file was picked from, keyed by browser mode
+    pub last_browser_dirs: BTreeMap<String, PathBuf>,
+    /// Bookmarked directories offered by the browser's quick-pick
+    pub browser_bookmarks: Vec<PathBuf>,
+
+    /// `--library` or `$VM_CURATOR_LIBRARY` in effect, with the config
+    /// file's own library that is saved in its place
+    #[serde(skip)]
+    pub library_override: Option<(PathBuf, PathBuf)>,
 }
 
 impl Default for Config {
     fn default() -> Self {
         let home = dirs::home_dir().unwrap_or_else(|| PathBuf::from("."));
-        let config_dir = dirs::config_dir()
-            .unwrap_or_else(|| home.join(".config"))
-            .join("vm-curator");
+        let config_dir = Self::config_dir();
 
         Self {
             vm_library_path: home.join("vm-space"),
@@ -80,6 +165,17 @@ impl Default for Config {
 
             // Behavior
             confirm_before_launch: true,
+            confirm_quit_with_running_vms: true,
+            scheduled_snapshot_keep: 5,
+            vm_start_timeout_secs: 30,
+            trash_path: None,
+            iso_library_path: None,
+            vm_list_sort: None,
+            spice_tcp: false,
+            ovmf_code_path: None,
+            ovmf_vars_path: None,
+            report_redact_paths: true,
+            report_redact_secrets: true,
 
             // Multi-GPU Passthrough
             enable_multi_gpu_passthrough: false,
@@ -92,6 +188,11 @@ impl Default for Config {
             single_gpu_dm_override: None,
             looking_glass_client_path: None,
             looking_glass_auto_launch: true,
+
+            // File Browser
+            last_browser_dirs: BTreeMap::new(),
+            browser_bookmarks: Vec::new(),
+            library_override: None,
         }
     }
 }
@@ -132,19 +233,72 @@ impl Config {
                 .with_context(|| format!("Failed to create config directory {:?}", parent))?;
         }
 
-        let content = toml::to_string_pretty(self).context("Failed to serialize config")?;
+        // An overridden library stays out of the file unless it was changed
+        // since (in Settings)
+        let content = match &self.library_override {
+            Some((library, configured)) if *library == self.vm_library_path => {
+                toml::to_string_pretty(&Config {
+                    vm_library_path: configured.clone(),
+                    ..self.clone()
+                })
+            }
+            _ => toml::to_string_pretty(self),
+        }
+        .context("Failed to serialize config")?;
         std::fs::write(config_path, content)
             .with_context(|| format!("Failed to write config to {:?}", config_path))?;
 
         Ok(())
     }
 
-    /// Get the configuration file path
+    /// Use `library` (from [`library_override`]) for this run only, leaving
+    /// the config file's library as it is
+    pub fn override_library(&mut self, library: PathBuf) {
+        let configured = std::mem::replace(&mut self.vm_library_path, library.clone());
+        self.library_override = Some((library, configured));
+    }
+
+    /// Directory deleted VMs are moved to
+    pub fn trash_dir(&self) -> PathBuf {
+        self.trash_path
+            .clone()
+            .unwrap_or_else(|| self.vm_library_path.join(".trash"))
+    }
+
+    /// Directory of the ISO library
+    pub fn iso_dir(&self) -> PathBuf {
+        self.iso_library_path
+            .clone()
+            .unwrap_or_else(|| self.vm_library_path.join("isos"))
+    }
+
+    /// UEFI firmware override, when both the CODE and VARS paths are set
+    pub fn ovmf_override(&self) -> Option<OvmfPaths> {
+        Some(OvmfPaths {
+            code: self.ovmf_code_path.clone()?,
+            vars_template: self.ovmf_vars_path.clone()?,
+        })
+    }
+
+    /// Use `path` as the config file (`--config`). Call once at startup,
+    /// before the config is loaded.
+    pub fn use_config_file(path: PathBuf) {
+        let _ = CONFIG_FILE_FLAG.set(path);
+    }
+
+    /// Get the configuration file path (see [`resolve_config_file`] for the
+    /// precedence)
     pub fn config_file_path() -> PathBuf {
-        dirs::config_dir()
-            .unwrap_or_else(|| PathBuf::from(".config"))
-            .join("vm-curator")
-            .join("config.toml")
+        resolve_config_file(CONFIG_FILE_FLAG.get().cloned(), |key| std::env::var_os(key))
+    }
+
+    /// Directory holding the config file, user overrides, templates and
+    /// caches
+    pub fn config_dir() -> PathBuf {
+        Self::config_file_path()
+            .parent()
+            .map(|p| p.to_path_buf())
+            .unwrap_or_else(|| PathBuf::from("."))
     }
 }
 
@@ -171,6 +325,9 @@ mod tests {
             default_memory_mb: 8192,
             default_iso_path: Some(PathBuf::from("/tmp/isos")),
             single_gpu_enabled: true,
+            vm_list_sort: Some(SortBy::LastLaunched),
+            last_browser_dirs: BTreeMap::from([("disk".to_string(), PathBuf::from("/srv/images"))]),
+            browser_bookmarks: vec![PathBuf::from("/mnt/external/isos")],
             ..Config::default()
         };
 
@@ -183,6 +340,15 @@ mod tests {
         assert_eq!(loaded.default_memory_mb, 8192);
         assert_eq!(loaded.default_iso_path, Some(PathBuf::from("/tmp/isos")));
         assert!(loaded.single_gpu_enabled);
+        assert_eq!(loaded.vm_list_sort, Some(SortBy::LastLaunched));
+        assert_eq!(
+            loaded.last_browser_dirs.get("disk"),
+            Some(&PathBuf::from("/srv/images"))
+        );
+        assert_eq!(
+            loaded.browser_bookmarks,
+            vec![PathBuf::from("/mnt/external/isos")]
+        );
     }
 
     #[test]
@@ -213,7 +379,103 @@ mod tests {
 
     #[test]
     fn config_file_path_ends_with_expected_segments() {
-        let path = Config::config_file_path();
+        let path = resolve_config_file(None, |_| None);
         assert!(path.ends_with("vm-curator/config.toml"));
     }
+
+    /// An environment lookup that only knows `vars`
+    fn stub_env(vars: &[(&str, &str)]) -> impl Fn(&str) -> Option<OsString> {
+        let vars: Vec<(String, OsString)> = vars
+            .iter()
+            .map(|(k, v)| (k.to_string(), OsString::from(v)))
+            .collect();
+        move |key| vars.iter().find(|(k, _)| k == key).map(|(_, v)| v.clone())
+    }
+
+    #[test]
+    fn config_file_precedence_is_flag_env_xdg_default() {
+        let env = stub_env(&[
+            ("VM_CURATOR_CONFIG", "/etc/vm-curator/config.toml"),
+            ("XDG_CONFIG_HOME", "/home/alice/.cfg"),
+        ]);
+        assert_eq!(
+            resolve_config_file(Some(PathBuf::from("/tmp/test.toml")), &env),
+            PathBuf::from("/tmp/test.toml")
+        );
+        assert_eq!(
+            resolve_config_file(None, &env),
+            PathBuf::from("/etc/vm-curator/config.toml")
+        );
+
+        let env = stub_env(&[
+            ("VM_CURATOR_CONFIG", ""),
+            ("XDG_CONFIG_HOME", "/home/alice/.cfg"),
+        ]);
+        assert_eq!(
+            resolve_config_file(None, env),
+            PathBuf::from("/home/alice/.cfg/vm-curator/config.toml")
+        );
+    }
+
+    #[test]
+    fn relative_xdg_config_home_is_ignored() {
+        let env = stub_env(&[("XDG_CONFIG_HOME", "relative/cfg")]);
+        assert_eq!(
+            resolve_config_file(None, env),
+            resolve_config_file(None, |_| None)
+        );
+    }
+
+    #[test]
+    fn library_precedence_is_flag_then_env() {
+        let env = stub_env(&[("VM_CURATOR_LIBRARY", "/srv/vms")]);
+        assert_eq!(
+            library_override(Some(PathBuf::from("/mnt/vms")), &env),
+            Some(PathBuf::from("/mnt/vms"))
+        );
+        assert_eq!(
+            library_override(None, &env),
+            Some(PathBuf::from("/srv/vms"))
+        );
+        assert_eq!(
+            library_override(None, stub_env(&[("VM_CURATOR_LIBRARY", "")])),
+            None
+        );
+    }
+
+    #[test]
+    fn library_override_is_not_saved() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("config.toml");
+        let mut cfg = Config {
+            vm_library_path: PathBuf::from("/srv/vms"),
+            ..Config::default()
+        };
+        cfg.override_library(PathBuf::from("/mnt/vms"));
+        assert_eq!(cfg.vm_library_path, PathBuf::from("/mnt/vms"));
+        cfg.save_to(&path).unwrap();
+        assert_eq!(
+            Config::load_from(&path).unwrap().vm_library_path,
+            PathBuf::from("/srv/vms")
+        );
+
+        // A library picked in Settings afterwards is saved
+        cfg.vm_library_path = PathBuf::from("/data/vms");
+        cfg.save_to(&path).unwrap();
+        assert_eq!(
+            Config::load_from(&path).unwrap().vm_library_path,
+            PathBuf::from("/data/vms")
+        );
+    }
+
+    #[test]
+    fn iso_dir_defaults_to_the_library() {
+        let mut cfg = Config {
+            vm_library_path: PathBuf::from("/srv/vms"),
+            ..Config::default()
+        };
+        assert_eq!(cfg.iso_dir(), PathBuf::from("/srv/vms/isos"));
+        cfg.iso_library_path = Some(PathBuf::from("/mnt/media/isos"));
+        assert_eq!(cfg.iso_dir(), PathBuf::from("/mnt/media/isos"));
+    }
 }
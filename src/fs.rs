@@ -4,9 +4,59 @@
 //! particularly for BTRFS copy-on-write handling.
 
 use anyhow::{Context, Result};
-use std::path::Path;
+use std::path::{Path, PathBuf};
 use std::process::Command;
 
+/// Expand a leading `~` in a user-typed path to the home directory
+pub fn expand_home(input: &str) -> PathBuf {
+    let home = || dirs::home_dir().unwrap_or_else(|| PathBuf::from("/"));
+    if input == "~" {
+        home()
+    } else if let Some(rest) = input.strip_prefix("~/") {
+        home().join(rest)
+    } else {
+        PathBuf::from(input)
+    }
+}
+
+/// Tab-complete the last component of a typed directory path.
+///
+/// Extends `input` (keeping a `~` prefix as typed) to the longest prefix shared
+/// by the matching subdirectories, adding a trailing `/` when only one
+/// matches. Hidden directories are only offered once a `.` is typed. Returns
+/// `None` when nothing matches.
+pub fn complete_dir_path(input: &str) -> Option<String> {
+    if input == "~" {
+        return Some("~/".to_string());
+    }
+    let split = input.rfind('/')? + 1;
+    let (head, partial) = input.split_at(split);
+
+    let mut matches: Vec<String> = std::fs::read_dir(expand_home(head))
+        .ok()?
+        .flatten()
+        .filter(|e| e.path().is_dir())
+        .map(|e| e.file_name().to_string_lossy().to_string())
+        .filter(|name| name.starts_with(partial))
+        .filter(|name| !name.starts_with('.') || partial.starts_with('.'))
+        .collect();
+    matches.sort();
+
+    let first = matches.first()?;
+    if matches.len() == 1 {
+        return Some(format!("{}{}/", head, first));
+    }
+    let common_len = matches.iter().skip(1).fold(first.len(), |len, name| {
+        first[..len]
+            .chars()
+            .zip(name.chars())
+            .take_while(|(a, b)| a == b)
+            .map(|(a, _)| a.len_utf8())
+            .sum()
+    });
+    Some(format!("{}{}", head, &first[..common_len]))
+}
+
 /// BTRFS filesystem magic number (used by statfs)
 const BTRFS_SUPER_MAGIC: i64 = 0x9123683E;
 
@@ -129,6 +179,59 @@ pub fn setup_vm_directory(path: &Path) -> Result<bool> {
     Ok(false)
 }
 
+/// Bytes allocated on disk by `path` and everything under it. Symlinks are
+/// not followed; unreadable entries count as zero.
+pub fn allocated_size(path: &Path) -> u64 {
+    use std::os::unix::fs::MetadataExt;
+
+    let Ok(metadata) = std::fs::symlink_metadata(path) else {
+        return 0;
+    };
+    let own = metadata.blocks() * 512;
+    if !metadata.is_dir() {
+        return own;
+    }
+    std::fs::read_dir(path)
+        .map(|entries| {
+            entries
+                .flatten()
+                .map(|entry| allocated_size(&entry.path()))
+                .sum::<u64>()
+        })
+        .unwrap_or(0)
+        + own
+}
+
+/// Copy `source` to `dest` like `std::fs::copy` (so reflinks and in-kernel
+/// copies still apply), calling `on_progress` with the percent written so far
+/// while it runs.
+pub fn copy_with_progress(
+    source: &Path,
+    dest: &Path,
+    mut on_progress: impl FnMut(f64),
+) -> Result<u64> {
+    let total = std::fs::metadata(source)
+        .with_context(|| format!("Failed to read {}", source.display()))?
+        .len();
+    let copy = {
+        let (source, dest) = (source.to_path_buf(), dest.to_path_buf());
+        std::thread::spawn(move || std::fs::copy(source, dest))
+    };
+    while !copy.is_finished() {
+        if total > 0 {
+            let written = std::fs::metadata(dest).map(|m| m.len()).unwrap_or(0);
+            on_progress((written as f64 * 100.0 / total as f64).min(100.0));
+        }
+        std::thread::sleep(std::time::Duration::from_millis(250));
+    }
+    let copied = copy
+        .join()
+        .map_err(|_| anyhow::anyhow!("Copy thread panicked"))?
+        .with_context(|| format!("Failed to copy {} to {}", source.display(), dest.display()))?;
+    on_progress(100.0);
+    Ok(copied)
+}
+
 #[cfg(test)]
 #[path = "tests/fs.rs"]
 mod tests;
@@ -0,0 +1,67 @@
+//! Host capacity: total RAM and usable CPUs, used to flag VMs sized beyond
+//! the host. Overcommitting is valid, so this only ever produces warnings.
+
+/// Share of host RAM a VM can be given before it is flagged
+pub const MEMORY_WARN_PERCENT: u64 = 80;
+
+/// The host's RAM and CPU count
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct HostCapacity {
+    /// Total RAM in MB (`MemTotal` in /proc/meminfo)
+    pub memory_mb: u64,
+    /// CPUs this process may run on
+    pub cpus: u32,
+}
+
+impl HostCapacity {
+    /// Read the host's capacity; `None` if /proc/meminfo is unreadable
+    pub fn detect() -> Option<Self> {
+        let meminfo = std::fs::read_to_string("/proc/meminfo").ok()?;
+        Some(Self {
+            memory_mb: parse_mem_total_mb(&meminfo)?,
+            cpus: std::thread::available_parallelism().map_or(1, |n| n.get() as u32),
+        })
+    }
+
+    /// Warn when `memory_mb` is over [`MEMORY_WARN_PERCENT`] of host RAM
+    pub fn memory_warning(&self, memory_mb: u32) -> Option<String> {
+        let limit = self.memory_mb * MEMORY_WARN_PERCENT / 100;
+        (u64::from(memory_mb) > limit).then(|| {
+            format!(
+                "{} MB RAM is over {}% of the host's {} MB",
+                memory_mb, MEMORY_WARN_PERCENT, self.memory_mb
+            )
+        })
+    }
+
+    /// Warn when a VM has more vCPUs than the host has CPUs
+    pub fn cpu_warning(&self, cpus: u32) -> Option<String> {
+        (cpus > self.cpus)
+            .then(|| format!("{} vCPUs is more than the host's {} CPUs", cpus, self.cpus))
+    }
+
+    /// Both warnings for a VM with `memory_mb` of RAM and `cpus` vCPUs
+    pub fn warnings(&self, memory_mb: u32, cpus: u32) -> Vec<String> {
+        self.memory_warning(memory_mb)
+            .into_iter()
+            .chain(self.cpu_warning(cpus))
+            .collect()
+    }
+}
+
+/// `MemTotal` from /proc/meminfo, in MB
+fn parse_mem_total_mb(meminfo: &str) -> Option<u64> {
+    let kb = meminfo
+        .lines()
+        .find_map(|line| line.strip_prefix("MemTotal:"))?
+        .trim()
+        .strip_suffix("kB")?
+        .trim()
+        .parse::<u64>()
+        .ok()?;
+    Some(kb / 1024)
+}
+
+#[cfg(test)]
+#[path = "tests/host.rs"]
+mod tests;
@@ -0,0 +1,198 @@
+//! Host hardware summary: CPU, RAM, virtualization support, IOMMU, QEMU
+//! emulators and helper programs, gathered in one place for the settings
+//! screen and for pasting into bug reports.
+
+use std::path::Path;
+
+use super::host::HostCapacity;
+use crate::commands::qemu_system;
+use crate::wizard_types::OvmfPaths;
+
+/// Helper programs VMs may need, with what they are for
+const HELPERS: &[(&str, &str)] = &[
+    ("qemu-img", "disk images and snapshots"),
+    ("swtpm", "TPM 2.0 emulation"),
+    ("virtiofsd", "virtiofs shared folders"),
+    ("remote-viewer", "SPICE viewer"),
+    ("passt", "passt networking"),
+    ("looking-glass-client", "Looking Glass"),
+];
+
+/// Places distributions install virtiofsd outside PATH
+const VIRTIOFSD_PATHS: &[&str] = &[
+    "/usr/libexec/virtiofsd",
+    "/usr/lib/virtiofsd",
+    "/usr/lib/qemu/virtiofsd",
+];
+
+/// One row of the summary
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum HostInfoLine {
+    /// Section title
+    Section(&'static str),
+    /// Label and value
+    Field(String, String),
+}
+
+/// Everything the summary shows, detected once
+#[derive(Debug, Clone)]
+pub struct HostInfo {
+    pub lines: Vec<HostInfoLine>,
+}
+
+impl HostInfo {
+    /// Probe the host. Runs a few external commands (`which`, `--version`),
+    /// so call it off the UI thread.
+    pub fn detect(ovmf_paths: Option<&OvmfPaths>) -> Self {
+        let cpuinfo = std::fs::read_to_string("/proc/cpuinfo").unwrap_or_default();
+        let capacity = HostCapacity::detect();
+        let field = |label: &str, value: String| HostInfoLine::Field(label.to_string(), value);
+        let yes_no = |b: bool| if b { "yes" } else { "no" }.to_string();
+
+        let mut lines = vec![
+            HostInfoLine::Section("CPU"),
+            field(
+                "Model",
+                cpu_model(&cpuinfo).unwrap_or_else(|| "unknown".to_string()),
+            ),
+            field(
+                "CPUs",
+                capacity.map_or("unknown".to_string(), |c| c.cpus.to_string()),
+            ),
+            field(
+                "Virtualization",
+                virt_extension(&cpuinfo)
+                    .unwrap_or("none (VT-x/AMD-V missing or disabled in firmware)")
+                    .to_string(),
+            ),
+            field(
+                "Memory",
+                capacity.map_or("unknown".to_string(), |c| format!("{} MB", c.memory_mb)),
+            ),
+            HostInfoLine::Section("KVM"),
+            field(
+                "KVM",
+                match qemu_system::kvm_unavailable_reason() {
+                    None => format!(
+                        "available ({})",
+                        qemu_system::get_kvm_info().unwrap_or_else(|| "kvm".to_string())
+                    ),
+                    Some(reason) => reason,
+                },
+            ),
+            field(
+                "Nested virtualization",
+                match qemu_system::nested_virt_support() {
+                    Some(n) => format!("{} ({})", if n.enabled { "on" } else { "off" }, n.module),
+                    None => "unknown (vendor KVM module not loaded)".to_string(),
+                },
+            ),
+            HostInfoLine::Section("Passthrough"),
+            field("IOMMU groups", {
+                let groups = count_entries(Path::new("/sys/kernel/iommu_groups"));
+                if groups == 0 {
+                    "none (IOMMU off: add intel_iommu=on or amd_iommu=on)".to_string()
+                } else {
+                    groups.to_string()
+                }
+            }),
+            field(
+                "vfio-pci",
+                yes_no(
+                    Path::new("/sys/module/vfio_pci").exists()
+                        || Path::new("/sys/bus/pci/drivers/vfio-pci").exists(),
+                ),
+            ),
+            HostInfoLine::Section("QEMU"),
+        ];
+
+        let emulators = qemu_system::list_available_emulators();
+        if emulators.is_empty() {
+            lines.push(field("Emulators", "none installed".to_string()));
+        }
+        for emulator in emulators {
+            let version = qemu_system::get_qemu_version(&emulator)
+                .unwrap_or_else(|_| "version unknown".to_string());
+            lines.push(field(&emulator, version));
+        }
+        for (label, secboot) in [("OVMF", false), ("OVMF Secure Boot", true)] {
+            let value = match crate::vm::create::resolve_ovmf_firmware(ovmf_paths, secboot) {
+                Ok(firmware) => firmware.code,
+                Err(_) => "not found".to_string(),
+            };
+            lines.push(field(label, value));
+        }
+
+        lines.push(HostInfoLine::Section("Helpers"));
+        for (helper, purpose) in HELPERS {
+            let found = qemu_system::is_emulator_available(helper)
+                || (*helper == "virtiofsd"
+                    && VIRTIOFSD_PATHS.iter().any(|p| Path::new(p).exists()));
+            let value = if found {
+                format!("yes ({})", purpose)
+            } else {
+                format!("no ({})", purpose)
+            };
+            lines.push(field(helper, value));
+        }
+
+        Self { lines }
+    }
+
+    /// The summary as plain text, one `Label: value` per line under
+    /// `[Section]` titles
+    pub fn to_text(&self) -> String {
+        let width = self
+            .lines
+            .iter()
+            .filter_map(|l| match l {
+                HostInfoLine::Field(label, _) => Some(label.len()),
+                HostInfoLine::Section(_) => None,
+            })
+            .max()
+            .unwrap_or(0)
+            + 1;
+        let mut text = format!("vm-curator {}\n", env!("CARGO_PKG_VERSION"));
+        for line in &self.lines {
+            match line {
+                HostInfoLine::Section(title) => text.push_str(&format!("\n[{}]\n", title)),
+                HostInfoLine::Field(label, value) => text.push_str(&format!(
+                    "{:<width$} {}\n",
+                    format!("{}:", label),
+                    value,
+                    width = width
+                )),
+            }
+        }
+        text
+    }
+}
+
+/// `model name` from /proc/cpuinfo
+pub(crate) fn cpu_model(cpuinfo: &str) -> Option<String> {
+    cpuinfo.lines().find_map(|line| {
+        let (key, value) = line.split_once(':')?;
+        (key.trim() == "model name").then(|| value.trim().to_string())
+    })
+}
+
+/// Hardware virtualization extension the CPU advertises, if any
+pub(crate) fn virt_extension(cpuinfo: &str) -> Option<&'static str> {
+    let flags = cpuinfo.lines().find_map(|line| {
+        let (key, value) = line.split_once(':')?;
+        (key.trim() == "flags").then_some(value)
+    })?;
+    flags.split_whitespace().find_map(|flag| match flag {
+        "vmx" => Some("VT-x (vmx)"),
+        "svm" => Some("AMD-V (svm)"),
+        _ => None,
+    })
+}
+
+fn count_entries(dir: &Path) -> usize {
+    std::fs::read_dir(dir).map_or(0, |entries| entries.count())
+}
+
+#[cfg(test)]
+#[path = "tests/host_info.rs"]
+mod tests;
@@ -1,12 +1,16 @@
+pub mod host;
+pub mod host_info;
 pub mod multi_gpu;
 pub mod pci;
 pub mod single_gpu;
 pub mod usb;
 
-pub use multi_gpu::LookingGlassConfig;
+pub use host::HostCapacity;
+pub use host_info::{HostInfo, HostInfoLine};
+pub use multi_gpu::{parse_resolution, recommended_ivshmem_mb, LookingGlassConfig};
 pub use pci::{
     check_multi_gpu_passthrough_status, enumerate_pci_devices, find_gpu_audio_pair,
-    generate_passthrough_args, MultiGpuPassthroughStatus, PciDevice,
+    generate_passthrough_args, DiagnosticCheck, MultiGpuPassthroughStatus, PciDevice,
 };
 pub use single_gpu::{
     check_single_gpu_support, load_config, save_config, scripts_exist, SingleGpuConfig,
@@ -2,8 +2,14 @@
 //!
 //! Provides utilities for Looking Glass integration in multi-GPU passthrough scenarios.
 
-use std::path::PathBuf;
-use std::process::Command;
+use anyhow::{bail, Context, Result};
+use std::fs::{self, OpenOptions};
+use std::os::unix::fs::{OpenOptionsExt, PermissionsExt};
+use std::path::{Path, PathBuf};
+use std::process::{Child, Command, Stdio};
+
+/// Default shared memory file used by the IVSHMEM device and the client
+pub const LOOKING_GLASS_SHMEM: &str = "/dev/shm/looking-glass";
 
 /// Looking Glass configuration utilities
 pub struct LookingGlassConfig;
@@ -36,4 +42,140 @@ impl LookingGlassConfig {
 
         None
     }
+
+    /// Resolve the client binary: the configured path must be an executable
+    /// file; without one, fall back to auto-detection.
+    pub fn validate_client(configured: Option<&Path>) -> Result<PathBuf> {
+        let Some(path) = configured else {
+            return Self::find_client()
+                .context("looking-glass-client not found (set its path in Settings)");
+        };
+        let meta = fs::metadata(path)
+            .with_context(|| format!("Looking Glass client not found: {}", path.display()))?;
+        if !meta.is_file() {
+            bail!("Looking Glass client is not a file: {}", path.display());
+        }
+        if meta.permissions().mode() & 0o111 == 0 {
+            bail!("Looking Glass client is not executable: {}", path.display());
+        }
+        Ok(path.to_path_buf())
+    }
+
+    /// Find the IVSHMEM shared memory file a launch script maps, along with
+    /// the size (in bytes) it asks for, if the script states one.
+    ///
+    /// Returns `None` for scripts without an IVSHMEM device (not a Looking
+    /// Glass VM).
+    pub fn shmem_from_script(content: &str) -> Option<(PathBuf, Option<u64>)> {
+        if !content.contains("ivshmem") {
+            return None;
+        }
+        for line in content.lines() {
+            let line = line.trim();
+            if line.starts_with('#') || !line.contains("mem-path=") {
+                continue;
+            }
+            let arg = line
+                .split_whitespace()
+                .find(|t| t.contains("mem-path="))?
+                .trim_matches(|c| c == '"' || c == '\'');
+            let mut path = None;
+            let mut size = None;
+            for opt in arg.split(',') {
+                if let Some(p) = opt.strip_prefix("mem-path=") {
+                    path = Some(PathBuf::from(p));
+                } else if let Some(s) = opt.strip_prefix("size=") {
+                    size = parse_size(s);
+                }
+            }
+            return path.map(|p| (p, size));
+        }
+        Some((PathBuf::from(LOOKING_GLASS_SHMEM), None))
+    }
+
+    /// Make sure the shared memory file exists, is owned by us and has the
+    /// expected size. A missing file is created (mode 0660) so both QEMU and
+    /// the client, running as the current user, can map it.
+    pub fn prepare_shmem(path: &Path, size_bytes: u64) -> Result<()> {
+        if !path.exists() {
+            let file = OpenOptions::new()
+                .write(true)
+                .create_new(true)
+                .mode(0o660)
+                .open(path)
+                .with_context(|| format!("Failed to create {}", path.display()))?;
+            file.set_len(size_bytes)
+                .with_context(|| format!("Failed to size {}", path.display()))?;
+            // The umask may have stripped group access from the create mode
+            fs::set_permissions(path, fs::Permissions::from_mode(0o660))?;
+            return Ok(());
+        }
+
+        let file = OpenOptions::new().write(true).open(path).with_context(|| {
+            format!(
+                "{} is not writable by the current user (fix with: sudo chown $USER {})",
+                path.display(),
+                path.display()
+            )
+        })?;
+        let current = file.metadata()?.len();
+        if current != size_bytes {
+            file.set_len(size_bytes).with_context(|| {
+                format!(
+                    "{} is {}MB, expected {}MB",
+                    path.display(),
+                    current / (1024 * 1024),
+                    size_bytes / (1024 * 1024)
+                )
+            })?;
+        }
+        Ok(())
+    }
+
+    /// Start the client against the given shared memory file
+    pub fn spawn_client(client: &Path, shmem: &Path) -> Result<Child> {
+        Command::new(client)
+            .arg("-f")
+            .arg(shmem)
+            .stdin(Stdio::null())
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .spawn()
+            .with_context(|| format!("Failed to start {}", client.display()))
+    }
 }
+
+/// Minimum IVSHMEM size in MB for a guest resolution.
+///
+/// Looking Glass keeps two 32-bit frames in shared memory plus roughly 10MB
+/// for the cursor and bookkeeping; QEMU wants a power-of-two size, so the
+/// result is rounded up to the next one.
+pub fn recommended_ivshmem_mb(width: u32, height: u32) -> u32 {
+    let bytes = width as u64 * height as u64 * 4 * 2 + 10 * 1024 * 1024;
+    let mb = bytes.div_ceil(1024 * 1024);
+    mb.next_power_of_two() as u32
+}
+
+/// Parse a resolution such as `2560x1440`
+pub fn parse_resolution(s: &str) -> Option<(u32, u32)> {
+    let (w, h) = s.trim().split_once(['x', 'X'])?;
+    let w = w.trim().parse::<u32>().ok().filter(|&w| w > 0)?;
+    let h = h.trim().parse::<u32>().ok().filter(|&h| h > 0)?;
+    Some((w, h))
+}
+
+/// Parse a QEMU size such as `64M`, `1G` or a plain byte count
+fn parse_size(s: &str) -> Option<u64> {
+    let s = s.trim();
+    let (num, mult) = match s.chars().last()? {
+        'K' | 'k' => (&s[..s.len() - 1], 1024),
+        'M' | 'm' => (&s[..s.len() - 1], 1024 * 1024),
+        'G' | 'g' => (&s[..s.len() - 1], 1024 * 1024 * 1024),
+        _ => (s, 1),
+    };
+    num.parse::<u64>().ok().map(|n| n * mult)
+}
+
+#[cfg(test)]
+#[path = "tests/multi_gpu.rs"]
+mod tests;
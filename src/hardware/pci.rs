@@ -80,8 +80,7 @@ pub struct PciDevice {
     pub address: String,
     /// Vendor ID (e.g., 0x10de for NVIDIA)
     pub vendor_id: u16,
-    /// Device ID (reserved for future use with PCI ID database lookup)
-    #[allow(dead_code)]
+    /// Device ID (e.g., 0x2684 for an RTX 4090)
     pub device_id: u16,
     /// PCI class code (e.g., 0x030000 for VGA)
     pub class_code: u32,
@@ -350,6 +349,262 @@ impl MultiGpuPassthroughStatus {
     }
 }
 
+/// One row of the passthrough readiness report
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DiagnosticCheck {
+    /// What was checked, e.g. "IOMMU groups populated"
+    pub label: String,
+    pub passed: bool,
+    /// What was found (shown next to the label)
+    pub detail: Option<String>,
+    /// How to fix a failed check
+    pub hint: Option<String>,
+}
+
+impl DiagnosticCheck {
+    fn pass(label: impl Into<String>, detail: Option<String>) -> Self {
+        Self {
+            label: label.into(),
+            passed: true,
+            detail,
+            hint: None,
+        }
+    }
+
+    fn fail(label: impl Into<String>, detail: Option<String>, hint: impl Into<String>) -> Self {
+        Self {
+            label: label.into(),
+            passed: false,
+            detail,
+            hint: Some(hint.into()),
+        }
+    }
+}
+
+impl MultiGpuPassthroughStatus {
+    /// Expand the status into a pass/fail checklist for the given target
+    /// GPUs (all passthrough-capable GPUs when `targets` is empty).
+    pub fn diagnostics(&self, targets: &[PciDevice]) -> Vec<DiagnosticCheck> {
+        let mut checks = Vec::new();
+
+        let group_count = fs::read_dir("/sys/kernel/iommu_groups")
+            .map(|entries| entries.count())
+            .unwrap_or(0);
+        checks.push(if self.iommu_enabled {
+            DiagnosticCheck::pass(
+                "IOMMU groups populated",
+                Some(format!("{} groups", group_count)),
+            )
+        } else {
+            DiagnosticCheck::fail(
+                "IOMMU groups populated",
+                Some("/sys/kernel/iommu_groups is empty".to_string()),
+                "Enable VT-d / AMD-Vi in firmware setup, then check the kernel parameters",
+            )
+        });
+
+        let cmdline = fs::read_to_string("/proc/cmdline").unwrap_or_default();
+        checks.push(check_iommu_cmdline(&cmdline, self.iommu_enabled));
+
+        checks.push(if self.vfio_loaded {
+            DiagnosticCheck::pass("vfio-pci module available", Some("loaded".to_string()))
+        } else if vfio_module_installed() {
+            DiagnosticCheck::pass(
+                "vfio-pci module available",
+                Some("installed, loaded at launch".to_string()),
+            )
+        } else {
+            DiagnosticCheck::fail(
+                "vfio-pci module available",
+                Some("not found for the running kernel".to_string()),
+                "Install your distribution's kernel modules package, then: sudo modprobe vfio-pci",
+            )
+        });
+
+        checks.push(if self.passthrough_gpus.is_empty() {
+            DiagnosticCheck::fail(
+                "Secondary GPU available",
+                None,
+                "Passthrough needs a GPU that isn't the boot display (or use Single GPU mode)",
+            )
+        } else {
+            DiagnosticCheck::pass(
+                "Secondary GPU available",
+                Some(format!("{} found", self.passthrough_gpus.len())),
+            )
+        });
+
+        let targets = if targets.is_empty() {
+            &self.passthrough_gpus[..]
+        } else {
+            targets
+        };
+        for gpu in targets {
+            let label = format!("{} IOMMU group clean", gpu.address);
+            let Some(group) = gpu.iommu_group else {
+                checks.push(DiagnosticCheck::fail(
+                    label,
+                    Some("no IOMMU group".to_string()),
+                    "Enable the IOMMU; without a group the GPU cannot be passed through",
+                ));
+                continue;
+            };
+            let conflicts = group_conflicts(gpu, &find_iommu_group_devices(gpu));
+            checks.push(if conflicts.is_empty() {
+                DiagnosticCheck::pass(label, Some(format!("group {}", group)))
+            } else {
+                let names: Vec<String> = conflicts
+                    .iter()
+                    .map(|d| format!("{} {}", d.address, d.display_name()))
+                    .collect();
+                DiagnosticCheck::fail(
+                    label,
+                    Some(format!("group {} also has {}", group, names.join(", "))),
+                    "Pass these devices through too, move the GPU to another slot, \
+                     or use an ACS override kernel",
+                )
+            });
+        }
+
+        checks
+    }
+}
+
+/// Check the kernel command line for the IOMMU switch. AMD systems enable
+/// the IOMMU by default, so a populated group list also counts as a pass.
+pub fn check_iommu_cmdline(cmdline: &str, iommu_active: bool) -> DiagnosticCheck {
+    const LABEL: &str = "Kernel IOMMU parameter";
+    let flag = cmdline
+        .split_whitespace()
+        .find(|arg| *arg == "intel_iommu=on" || *arg == "amd_iommu=on");
+    match (flag, iommu_active) {
+        (Some(flag), _) => DiagnosticCheck::pass(LABEL, Some(flag.to_string())),
+        (None, true) => {
+            DiagnosticCheck::pass(LABEL, Some("not set, IOMMU on by default".to_string()))
+        }
+        (None, false) => DiagnosticCheck::fail(
+            LABEL,
+            Some("intel_iommu=on / amd_iommu=on missing".to_string()),
+            "Add intel_iommu=on (Intel) or amd_iommu=on (AMD) plus iommu=pt to the \
+             bootloader's kernel command line and reboot",
+        ),
+    }
+}
+
+/// Devices sharing a GPU's IOMMU group that would also have to be handed to
+/// the guest. The GPU's own functions (audio, USB-C, ...) and bridges are fine.
+pub fn group_conflicts(gpu: &PciDevice, group_devices: &[PciDevice]) -> Vec<PciDevice> {
+    let slot = gpu
+        .address
+        .rsplit_once('.')
+        .map_or(gpu.address.as_str(), |(s, _)| s);
+    group_devices
+        .iter()
+        .filter(|d| {
+            let same_slot = d.address.rsplit_once('.').is_some_and(|(s, _)| s == slot);
+            !same_slot && !d.is_infrastructure()
+        })
+        .cloned()
+        .collect()
+}
+
+/// Host display drivers. A GPU still bound to one of these at launch is
+/// usually in use by the host and can't be released to vfio-pci cleanly.
+const HOST_GPU_DRIVERS: &[&str] = &["nvidia", "nouveau", "amdgpu", "radeon", "i915", "xe"];
+
+/// Report the kernel driver a passthrough device is bound to right now.
+pub fn check_device_driver(device: &PciDevice) -> DiagnosticCheck {
+    let label = format!("{} driver", device.address);
+    match device.driver.as_deref() {
+        Some("vfio-pci") => DiagnosticCheck::pass(label, Some("vfio-pci".to_string())),
+        None => DiagnosticCheck::pass(label, Some("none, bound to vfio-pci at launch".to_string())),
+        Some(driver) if HOST_GPU_DRIVERS.contains(&driver) => DiagnosticCheck::fail(
+            label,
+            Some(format!("{} (host GPU driver)", driver)),
+            "Have vfio-pci claim it at boot instead: press [g] for the modprobe.d steps",
+        ),
+        Some(driver) => DiagnosticCheck::pass(
+            label,
+            Some(format!("{}, rebound to vfio-pci at launch", driver)),
+        ),
+    }
+}
+
+/// Step-by-step instructions for handing `devices` to vfio-pci, either at
+/// every boot (modprobe.d) or right now (driver_override).
+pub fn vfio_setup_steps(devices: &[PciDevice]) -> String {
+    let mut ids: Vec<String> = Vec::new();
+    for d in devices {
+        let id = format!("{:04x}:{:04x}", d.vendor_id, d.device_id);
+        if !ids.contains(&id) {
+            ids.push(id);
+        }
+    }
+    let mut host_drivers: Vec<&str> = devices
+        .iter()
+        .filter_map(|d| d.driver.as_deref())
+        .filter(|d| HOST_GPU_DRIVERS.contains(d))
+        .collect();
+    host_drivers.sort_unstable();
+    host_drivers.dedup();
+
+    let mut out = String::from("# VFIO setup for:\n");
+    for d in devices {
+        out.push_str(&format!(
+            "#   {} {} (driver: {})\n",
+            d.address,
+            d.display_name(),
+            d.driver.as_deref().unwrap_or("none")
+        ));
+    }
+
+    out.push_str(
+        "\n# Option 1: let vfio-pci claim the devices at every boot (best for GPUs).\n\
+         # Put these lines in /etc/modprobe.d/vfio.conf:\n\n",
+    );
+    out.push_str(&format!("options vfio-pci ids={}\n", ids.join(",")));
+    for driver in &host_drivers {
+        out.push_str(&format!("softdep {} pre: vfio-pci\n", driver));
+    }
+    out.push_str(
+        "\n# Note: ids= matches every device with the same vendor:device ID.\n\
+         # Then rebuild the initramfs and reboot:\n\
+         #   sudo update-initramfs -u   (Debian/Ubuntu)\n\
+         #   sudo dracut -f             (Fedora/RHEL)\n\
+         #   sudo mkinitcpio -P         (Arch)\n",
+    );
+
+    out.push_str("\n# Option 2: bind them now, until the next reboot:\n\n");
+    out.push_str("sudo modprobe vfio-pci\n");
+    for d in devices {
+        let dev = format!("/sys/bus/pci/devices/{}", d.address);
+        out.push_str(&format!(
+            "echo vfio-pci | sudo tee {}/driver_override\n",
+            dev
+        ));
+        if d.driver.as_deref().is_some_and(|drv| drv != "vfio-pci") {
+            out.push_str(&format!(
+                "echo {} | sudo tee {}/driver/unbind\n",
+                d.address, dev
+            ));
+        }
+        out.push_str(&format!(
+            "echo {} | sudo tee /sys/bus/pci/drivers_probe\n",
+            d.address
+        ));
+    }
+    out
+}
+
+/// Whether the vfio-pci module exists for the running kernel (loaded or not)
+fn vfio_module_installed() -> bool {
+    std::process::Command::new("modinfo")
+        .arg("vfio-pci")
+        .output()
+        .map(|o| o.status.success())
+        .unwrap_or(false)
+}
+
 /// Enumerate all PCI devices on the system
 pub fn enumerate_pci_devices() -> Result<Vec<PciDevice>> {
     let pci_path = Path::new("/sys/bus/pci/devices");
@@ -673,6 +928,11 @@ fn check_iommu_enabled() -> bool {
 
 /// Check if VFIO modules are loaded
 fn check_vfio_modules() -> bool {
+    // Built into the kernel: no /proc/modules entry, but it shows in sysfs
+    if Path::new("/sys/module/vfio_pci").exists() {
+        return true;
+    }
+
     // Check /proc/modules for vfio-pci
     if let Ok(modules) = fs::read_to_string("/proc/modules") {
         // vfio_pci is the kernel module name (underscore not hyphen)
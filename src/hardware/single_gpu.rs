@@ -20,6 +20,16 @@ pub enum DisplayManager {
 }
 
 impl DisplayManager {
+    /// Display manager for a systemd service name (`gdm`, `sddm`, ...)
+    pub fn from_service_name(name: &str) -> Self {
+        match name {
+            "gdm" => DisplayManager::Gdm,
+            "sddm" => DisplayManager::Sddm,
+            "lightdm" => DisplayManager::Lightdm,
+            other => DisplayManager::Unknown(other.to_string()),
+        }
+    }
+
     /// Get the systemd service name for this display manager
     pub fn service_name(&self) -> &str {
         match self {
@@ -524,12 +534,7 @@ pub fn load_config(vm_path: &Path) -> Option<SingleGpuConfig> {
         other => GpuDriver::Other(other.to_string()),
     };
 
-    let display_manager = match display_manager.as_str() {
-        "gdm" => DisplayManager::Gdm,
-        "sddm" => DisplayManager::Sddm,
-        "lightdm" => DisplayManager::Lightdm,
-        other => DisplayManager::Unknown(other.to_string()),
-    };
+    let display_manager = DisplayManager::from_service_name(&display_manager);
 
     Some(SingleGpuConfig {
         gpu,
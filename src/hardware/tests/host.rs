@@ -0,0 +1,26 @@
+use super::*;
+
+#[test]
+fn test_parse_mem_total_mb() {
+    let meminfo = "MemTotal:       32684148 kB\nMemFree:         1234567 kB\n";
+    assert_eq!(parse_mem_total_mb(meminfo), Some(31918));
+    assert_eq!(parse_mem_total_mb("MemFree: 1 kB\n"), None);
+}
+
+#[test]
+fn test_warnings_against_host_capacity() {
+    let host = HostCapacity {
+        memory_mb: 16384,
+        cpus: 8,
+    };
+
+    // 80% of 16384 MB is 13107 MB
+    assert!(host.warnings(13107, 8).is_empty());
+    assert_eq!(
+        host.warnings(14000, 12),
+        vec![
+            "14000 MB RAM is over 80% of the host's 16384 MB".to_string(),
+            "12 vCPUs is more than the host's 8 CPUs".to_string(),
+        ]
+    );
+}
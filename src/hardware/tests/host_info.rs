@@ -0,0 +1,30 @@
+use super::*;
+
+const CPUINFO: &str = "processor\t: 0\nvendor_id\t: AuthenticAMD\n\
+                       model name\t: AMD Ryzen 7 5800X 8-Core Processor\n\
+                       flags\t\t: fpu vme de pse svm sse4_2\n";
+
+#[test]
+fn test_cpu_model_and_virt_extension() {
+    assert_eq!(
+        cpu_model(CPUINFO).as_deref(),
+        Some("AMD Ryzen 7 5800X 8-Core Processor")
+    );
+    assert_eq!(virt_extension(CPUINFO), Some("AMD-V (svm)"));
+    assert_eq!(virt_extension("flags\t\t: fpu vme sse4_2\n"), None);
+    assert_eq!(cpu_model(""), None);
+}
+
+#[test]
+fn test_to_text_aligns_fields_under_sections() {
+    let info = HostInfo {
+        lines: vec![
+            HostInfoLine::Section("CPU"),
+            HostInfoLine::Field("Model".to_string(), "Ryzen".to_string()),
+            HostInfoLine::Field("Virtualization".to_string(), "AMD-V (svm)".to_string()),
+        ],
+    };
+    let text = info.to_text();
+    assert!(text.starts_with("vm-curator "));
+    assert!(text.contains("\n[CPU]\nModel:          Ryzen\nVirtualization: AMD-V (svm)\n"));
+}
@@ -0,0 +1,68 @@
+use super::*;
+
+#[test]
+fn test_shmem_from_script() {
+    let script = [
+        "#!/bin/bash",
+        "qemu-system-x86_64 \\",
+        "    -object memory-backend-file,id=ivshmem,share=on,mem-path=/dev/shm/lg-win,size=128M \\",
+        "    -device ivshmem-plain,memdev=ivshmem,bus=pcie.0",
+    ]
+    .join("\n");
+    let (path, size) = LookingGlassConfig::shmem_from_script(&script).unwrap();
+    assert_eq!(path, PathBuf::from("/dev/shm/lg-win"));
+    assert_eq!(size, Some(128 * 1024 * 1024));
+}
+
+#[test]
+fn test_shmem_from_script_without_ivshmem() {
+    let script = "qemu-system-x86_64 -m 4G -enable-kvm";
+    assert!(LookingGlassConfig::shmem_from_script(script).is_none());
+}
+
+#[test]
+fn test_prepare_shmem_creates_and_resizes() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("looking-glass");
+
+    LookingGlassConfig::prepare_shmem(&path, 32 * 1024 * 1024).unwrap();
+    let meta = fs::metadata(&path).unwrap();
+    assert_eq!(meta.len(), 32 * 1024 * 1024);
+    assert_eq!(meta.permissions().mode() & 0o777, 0o660);
+
+    LookingGlassConfig::prepare_shmem(&path, 64 * 1024 * 1024).unwrap();
+    assert_eq!(fs::metadata(&path).unwrap().len(), 64 * 1024 * 1024);
+}
+
+#[test]
+fn test_validate_client_rejects_non_executable() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("looking-glass-client");
+    fs::write(&path, "#!/bin/sh\n").unwrap();
+    fs::set_permissions(&path, fs::Permissions::from_mode(0o644)).unwrap();
+    assert!(LookingGlassConfig::validate_client(Some(&path)).is_err());
+
+    fs::set_permissions(&path, fs::Permissions::from_mode(0o755)).unwrap();
+    assert_eq!(
+        LookingGlassConfig::validate_client(Some(&path)).unwrap(),
+        path
+    );
+}
+
+#[test]
+fn test_recommended_ivshmem_mb() {
+    // 1080p: 15.8MB of frames + 10MB -> 26MB -> 32MB
+    assert_eq!(recommended_ivshmem_mb(1920, 1080), 32);
+    // 1440p: 28.1MB + 10MB -> 39MB -> 64MB
+    assert_eq!(recommended_ivshmem_mb(2560, 1440), 64);
+    // 4K: 63.3MB + 10MB -> 74MB -> 128MB
+    assert_eq!(recommended_ivshmem_mb(3840, 2160), 128);
+}
+
+#[test]
+fn test_parse_resolution() {
+    assert_eq!(parse_resolution("2560x1440"), Some((2560, 1440)));
+    assert_eq!(parse_resolution(" 1920X1080 "), Some((1920, 1080)));
+    assert_eq!(parse_resolution("1920"), None);
+    assert_eq!(parse_resolution("0x1080"), None);
+}
@@ -119,3 +119,90 @@ fn integrated_gpu_detection() {
     audio.class_code = 0x040300;
     assert!(!audio.is_integrated_gpu());
 }
+
+#[test]
+fn group_conflicts_ignores_own_functions_and_bridges() {
+    let gpu = gpu_device("0000:01:00.0", 0x10de, 0x2684);
+    let mut audio = gpu_device("0000:01:00.1", 0x10de, 0x22ba);
+    audio.class_code = class_codes::AUDIO_DEVICE;
+    let mut bridge = gpu_device("0000:00:01.0", 0x8086, 0x1901);
+    bridge.class_code = class_codes::PCI_BRIDGE;
+    let mut nic = gpu_device("0000:02:00.0", 0x8086, 0x15b8);
+    nic.class_code = class_codes::NETWORK_CONTROLLER;
+
+    let group = vec![gpu.clone(), audio, bridge.clone()];
+    assert!(group_conflicts(&gpu, &group).is_empty());
+
+    let group = vec![gpu.clone(), bridge, nic];
+    let conflicts = group_conflicts(&gpu, &group);
+    assert_eq!(conflicts.len(), 1);
+    assert_eq!(conflicts[0].address, "0000:02:00.0");
+}
+
+#[test]
+fn iommu_cmdline_check() {
+    let check = check_iommu_cmdline("BOOT_IMAGE=/vmlinuz intel_iommu=on iommu=pt", false);
+    assert!(check.passed);
+    assert_eq!(check.detail.as_deref(), Some("intel_iommu=on"));
+
+    // AMD enables the IOMMU without a parameter
+    assert!(check_iommu_cmdline("BOOT_IMAGE=/vmlinuz quiet", true).passed);
+
+    let check = check_iommu_cmdline("BOOT_IMAGE=/vmlinuz quiet", false);
+    assert!(!check.passed);
+    assert!(check.hint.is_some());
+}
+
+fn gpu_with_driver(address: &str, device_id: u16, driver: Option<&str>) -> PciDevice {
+    PciDevice {
+        address: address.to_string(),
+        vendor_id: 0x10de,
+        device_id,
+        class_code: 0x030000,
+        vendor_name: "NVIDIA".to_string(),
+        device_name: "GeForce RTX 4090".to_string(),
+        driver: driver.map(str::to_string),
+        iommu_group: Some(1),
+        is_boot_vga: false,
+        subsystem_vendor_id: 0,
+        subsystem_device_id: 0,
+    }
+}
+
+#[test]
+fn test_check_device_driver() {
+    let bound = check_device_driver(&gpu_with_driver("0000:01:00.0", 0x2684, Some("vfio-pci")));
+    assert!(bound.passed);
+
+    let unbound = check_device_driver(&gpu_with_driver("0000:01:00.0", 0x2684, None));
+    assert!(unbound.passed);
+
+    let host = check_device_driver(&gpu_with_driver("0000:01:00.0", 0x2684, Some("nvidia")));
+    assert!(!host.passed);
+    assert_eq!(host.label, "0000:01:00.0 driver");
+    assert!(host.detail.unwrap().contains("nvidia"));
+
+    let audio = check_device_driver(&gpu_with_driver(
+        "0000:01:00.1",
+        0x22ba,
+        Some("snd_hda_intel"),
+    ));
+    assert!(audio.passed);
+}
+
+#[test]
+fn test_vfio_setup_steps() {
+    let steps = vfio_setup_steps(&[
+        gpu_with_driver("0000:01:00.0", 0x2684, Some("nvidia")),
+        gpu_with_driver("0000:01:00.1", 0x22ba, Some("snd_hda_intel")),
+    ]);
+
+    assert!(steps.contains("options vfio-pci ids=10de:2684,10de:22ba\n"));
+    assert!(steps.contains("softdep nvidia pre: vfio-pci\n"));
+    assert!(!steps.contains("softdep snd_hda_intel"));
+    assert!(steps
+        .contains("echo vfio-pci | sudo tee /sys/bus/pci/devices/0000:01:00.0/driver_override"));
+    assert!(steps
+        .contains("echo 0000:01:00.1 | sudo tee /sys/bus/pci/devices/0000:01:00.1/driver/unbind"));
+    assert!(steps.contains("echo 0000:01:00.0 | sudo tee /sys/bus/pci/drivers_probe"));
+}
@@ -7,6 +7,18 @@ fn test_display_manager_service_names() {
     assert_eq!(DisplayManager::Lightdm.service_name(), "lightdm");
 }
 
+#[test]
+fn display_manager_from_service_name() {
+    assert_eq!(
+        DisplayManager::from_service_name("sddm"),
+        DisplayManager::Sddm
+    );
+    assert_eq!(
+        DisplayManager::from_service_name("ly"),
+        DisplayManager::Unknown("ly".to_string())
+    );
+}
+
 #[test]
 fn test_gpu_driver_modules() {
     assert_eq!(GpuDriver::Nvidia.module_name(), "nvidia");
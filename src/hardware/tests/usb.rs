@@ -11,10 +11,72 @@ fn test_usb_device_display() {
         dev_num: 3,
         device_class: 0,
         usb_version: UsbVersion::Usb2,
+        interfaces: vec![UsbInterface {
+            class: 0x03,
+            protocol: 2,
+        }],
+        port_path: "3".to_string(),
     };
 
     assert_eq!(device.display_name(), "Logitech M105 Mouse");
     assert!(!device.is_hub());
+    assert!(device.is_keyboard_or_mouse());
+    assert_eq!(
+        device.dev_node(),
+        std::path::PathBuf::from("/dev/bus/usb/001/003")
+    );
+}
+
+#[test]
+fn test_usb_device_classification() {
+    let mut device = UsbDevice {
+        vendor_id: 0x0781,
+        product_id: 0x5581,
+        vendor_name: "SanDisk".to_string(),
+        product_name: "Ultra".to_string(),
+        bus_num: 2,
+        dev_num: 4,
+        device_class: 0,
+        usb_version: UsbVersion::Usb3,
+        interfaces: vec![UsbInterface {
+            class: 0x08,
+            protocol: 0x50,
+        }],
+        port_path: "1.4".to_string(),
+    };
+    assert_eq!(device.class(), UsbClass::Storage);
+    assert!(!device.is_keyboard_or_mouse());
+
+    // A device-level class wins over the interfaces
+    device.device_class = 0x09;
+    assert_eq!(device.class(), UsbClass::Hub);
+    assert!(device.is_hub());
+
+    // Non-boot HID (e.g. a game controller) isn't flagged as host input
+    device.device_class = 0;
+    device.interfaces = vec![UsbInterface {
+        class: 0x03,
+        protocol: 0,
+    }];
+    assert_eq!(device.class(), UsbClass::Hid);
+    assert!(!device.is_keyboard_or_mouse());
+
+    device.interfaces.push(UsbInterface {
+        class: 0x03,
+        protocol: 1,
+    });
+    assert!(device.is_keyboard_or_mouse());
+
+    device.interfaces.clear();
+    assert_eq!(device.class(), UsbClass::Other);
+    assert_eq!(UsbClass::from_code(0xe0).label(), "wireless");
+}
+
+#[test]
+fn test_port_path_from_sysname() {
+    assert_eq!(port_path_from_sysname("1-2.3"), "2.3");
+    assert_eq!(port_path_from_sysname("3-1"), "1");
+    assert_eq!(port_path_from_sysname("usb1"), "");
 }
 
 #[test]
@@ -40,6 +40,66 @@ impl UsbVersion {
     }
 }
 
+/// What kind of device a USB class code describes
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UsbClass {
+    Hub,
+    /// Keyboards, mice, game controllers and other HID devices
+    Hid,
+    Storage,
+    Audio,
+    Video,
+    /// Bluetooth adapters and other wireless controllers
+    Wireless,
+    Network,
+    Printer,
+    SmartCard,
+    Other,
+}
+
+impl UsbClass {
+    /// Classify a `bDeviceClass` / `bInterfaceClass` code
+    pub fn from_code(code: u8) -> Self {
+        match code {
+            0x09 => UsbClass::Hub,
+            0x03 => UsbClass::Hid,
+            0x08 => UsbClass::Storage,
+            0x01 => UsbClass::Audio,
+            0x0e => UsbClass::Video,
+            0xe0 => UsbClass::Wireless,
+            0x02 | 0x0a => UsbClass::Network,
+            0x07 => UsbClass::Printer,
+            0x0b => UsbClass::SmartCard,
+            _ => UsbClass::Other,
+        }
+    }
+
+    /// Short label for the device list
+    pub fn label(&self) -> &'static str {
+        match self {
+            UsbClass::Hub => "hub",
+            UsbClass::Hid => "input",
+            UsbClass::Storage => "storage",
+            UsbClass::Audio => "audio",
+            UsbClass::Video => "video",
+            UsbClass::Wireless => "wireless",
+            UsbClass::Network => "network",
+            UsbClass::Printer => "printer",
+            UsbClass::SmartCard => "smart card",
+            UsbClass::Other => "other",
+        }
+    }
+}
+
+/// One interface of a USB device, as listed in sysfs
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct UsbInterface {
+    /// `bInterfaceClass`
+    pub class: u8,
+    /// `bInterfaceProtocol`; for HID boot devices 1 is a keyboard, 2 a mouse
+    pub protocol: u8,
+}
+
 /// Represents a USB device
 #[derive(Debug, Clone)]
 pub struct UsbDevice {
@@ -47,15 +107,18 @@ pub struct UsbDevice {
     pub product_id: u16,
     pub vendor_name: String,
     pub product_name: String,
-    /// Bus number - reserved for future bus-specific passthrough
-    #[allow(dead_code)]
+    /// Bus number (used for hot-plugging into a running VM)
     pub bus_num: u8,
-    /// Device number - reserved for future bus-specific passthrough
-    #[allow(dead_code)]
+    /// Device address on the bus; changes when the device is replugged
     pub dev_num: u8,
     pub device_class: u8,
     /// USB version/speed classification
     pub usb_version: UsbVersion,
+    /// Interfaces of the active configuration
+    pub interfaces: Vec<UsbInterface>,
+    /// Port path on the bus (QEMU's `hostport`, e.g. "2.3"); stays the same
+    /// across replugs into the same port. Empty if unknown.
+    pub port_path: String,
 }
 
 impl UsbDevice {
@@ -65,6 +128,66 @@ impl UsbDevice {
         self.device_class == 0x09
     }
 
+    /// Device class, or the first interface's when the device leaves it to
+    /// its interfaces (class 0x00, as most devices do)
+    pub fn class(&self) -> UsbClass {
+        match (self.device_class, self.interfaces.first()) {
+            (0x00 | 0xef, Some(interface)) => UsbClass::from_code(interface.class),
+            (code, _) => UsbClass::from_code(code),
+        }
+    }
+
+    /// Whether this looks like a keyboard or mouse the host may depend on.
+    /// Passing it through takes it away from the host while the VM runs.
+    pub fn is_keyboard_or_mouse(&self) -> bool {
+        self.interfaces
+            .iter()
+            .any(|i| i.class == 0x03 && matches!(i.protocol, 1 | 2))
+    }
+
+    /// The usbfs node QEMU opens to pass this device through
+    pub fn dev_node(&self) -> std::path::PathBuf {
+        std::path::PathBuf::from(format!(
+            "/dev/bus/usb/{:03}/{:03}",
+            self.bus_num, self.dev_num
+        ))
+    }
+
+    /// Whether the current user can open the device node read-write, as QEMU
+    /// must to take the device over
+    pub fn is_accessible(&self) -> bool {
+        std::fs::OpenOptions::new()
+            .read(true)
+            .write(true)
+            .open(self.dev_node())
+            .is_ok()
+    }
+
+    /// Host kernel drivers bound to this device's interfaces (e.g.
+    /// `usb-storage`, `usbhid`). QEMU detaches them when it takes the device,
+    /// so anything the host is doing with it (a mounted filesystem, typing)
+    /// stops. `usbfs` means QEMU or another user-space program already holds it.
+    pub fn host_drivers(&self) -> Vec<String> {
+        let Ok(entries) = std::fs::read_dir("/sys/bus/usb/devices") else {
+            return Vec::new();
+        };
+        let Some(device_dir) = entries.flatten().map(|e| e.path()).find(|path| {
+            read_sysfs_decimal(path, "busnum") == Some(self.bus_num as u32)
+                && read_sysfs_decimal(path, "devnum") == Some(self.dev_num as u32)
+        }) else {
+            return Vec::new();
+        };
+
+        let mut drivers: Vec<String> = sysfs_interface_dirs(&device_dir)
+            .iter()
+            .filter_map(|dir| std::fs::read_link(dir.join("driver")).ok())
+            .filter_map(|link| link.file_name().map(|n| n.to_string_lossy().into_owned()))
+            .collect();
+        drivers.sort();
+        drivers.dedup();
+        drivers
+    }
+
     /// Get a display string for this device
     pub fn display_name(&self) -> String {
         if !self.product_name.is_empty() {
@@ -82,7 +205,7 @@ impl UsbDevice {
 /// Enumerate USB devices using libudev
 pub fn enumerate_usb_devices() -> Result<Vec<UsbDevice>> {
     // Try using libudev, fall back to sysfs
-    let mut devices = match enumerate_via_udev() {
+    let devices = match enumerate_via_udev() {
         Ok(devs) => devs,
         Err(e) => {
             // Log the fallback for debugging purposes
@@ -94,9 +217,8 @@ pub fn enumerate_usb_devices() -> Result<Vec<UsbDevice>> {
         }
     };
 
-    // Filter out hubs and root hubs
-    devices.retain(|d| !d.is_hub());
-
+    // Root hubs are skipped while enumerating; other hubs are kept so the
+    // passthrough screen can show them on request (see `UsbDevice::is_hub`)
     Ok(devices)
 }
 
@@ -188,6 +310,14 @@ fn enumerate_via_udev() -> Result<Vec<UsbDevice>> {
                 dev_num,
                 device_class,
                 usb_version,
+                interfaces: device
+                    .syspath()
+                    .map(read_sysfs_interfaces)
+                    .unwrap_or_default(),
+                port_path: device
+                    .sysname()
+                    .map(|name| port_path_from_sysname(&name.to_string_lossy()))
+                    .unwrap_or_default(),
             });
         }
     }
@@ -254,12 +384,53 @@ fn enumerate_via_sysfs() -> Result<Vec<UsbDevice>> {
             dev_num,
             device_class,
             usb_version,
+            interfaces: read_sysfs_interfaces(&path),
+            port_path: port_path_from_sysname(&name_str),
         });
     }
 
     Ok(devices)
 }
 
+/// The port path from a sysfs device name: "1-2.3" (bus 1) gives "2.3"
+fn port_path_from_sysname(name: &str) -> String {
+    name.split_once('-')
+        .map(|(_, path)| path.to_string())
+        .unwrap_or_default()
+}
+
+/// Interface directories (`<device>:<config>.<interface>`) of a USB device
+fn sysfs_interface_dirs(device_dir: &std::path::Path) -> Vec<std::path::PathBuf> {
+    let Some(name) = device_dir
+        .file_name()
+        .map(|n| n.to_string_lossy().into_owned())
+    else {
+        return Vec::new();
+    };
+    let prefix = format!("{}:", name);
+    let mut dirs: Vec<_> = std::fs::read_dir(device_dir)
+        .into_iter()
+        .flatten()
+        .flatten()
+        .filter(|e| e.file_name().to_string_lossy().starts_with(&prefix))
+        .map(|e| e.path())
+        .collect();
+    dirs.sort();
+    dirs
+}
+
+fn read_sysfs_interfaces(device_dir: &std::path::Path) -> Vec<UsbInterface> {
+    sysfs_interface_dirs(device_dir)
+        .iter()
+        .filter_map(|dir| {
+            Some(UsbInterface {
+                class: read_sysfs_hex(dir, "bInterfaceClass")? as u8,
+                protocol: read_sysfs_hex(dir, "bInterfaceProtocol").unwrap_or(0) as u8,
+            })
+        })
+        .collect()
+}
+
 fn read_sysfs_hex(path: &std::path::Path, attr: &str) -> Option<u16> {
     let value = std::fs::read_to_string(path.join(attr)).ok()?;
     u16::from_str_radix(value.trim(), 16).ok()
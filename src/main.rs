@@ -6,6 +6,7 @@
 #![allow(clippy::collapsible_match)]
 
 mod app;
+mod cli_error;
 mod commands;
 mod config;
 mod fs;
@@ -18,15 +19,22 @@ mod wizard_types;
 use anyhow::{Context, Result};
 use clap::{Parser, Subcommand};
 use crossterm::{
-    event::{DisableMouseCapture, EnableMouseCapture},
-    execute,
-    terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
+    cursor,
+    event::{self as term_event, DisableMouseCapture, EnableMouseCapture, KeyCode, KeyModifiers},
+    execute, queue,
+    terminal::{
+        disable_raw_mode, enable_raw_mode, Clear, ClearType, EnterAlternateScreen,
+        LeaveAlternateScreen,
+    },
 };
 use ratatui::prelude::*;
-use std::io::{self, Write};
+use std::collections::HashMap;
+use std::io::{self, IsTerminal, Write};
 use std::path::PathBuf;
+use std::time::{Duration, Instant};
 
 use app::App;
+use cli_error::{cli_error, ErrorCategory};
 use config::Config;
 
 #[derive(Parser)]
@@ -35,10 +43,16 @@ use config::Config;
 #[command(version)]
 #[command(about = "A TUI application to manage your QEMU VM library")]
 struct Cli {
-    /// Path to VM library directory
+    /// Path to VM library directory [env: VM_CURATOR_LIBRARY; default:
+    /// vm_library_path in the config file]
     #[arg(short, long)]
     library: Option<PathBuf>,
 
+    /// Path to the config file [env: VM_CURATOR_CONFIG; default:
+    /// $XDG_CONFIG_HOME/vm-curator/config.toml]
+    #[arg(long)]
+    config: Option<PathBuf>,
+
     /// Subcommand to run
     #[command(subcommand)]
     command: Option<Commands>,
@@ -59,6 +73,25 @@ enum Commands {
         /// Boot with custom ISO
         #[arg(short, long)]
         cdrom: Option<PathBuf>,
+        /// Discard all disk changes when the VM shuts down (QEMU -snapshot)
+        #[arg(short, long, conflicts_with_all = ["install", "cdrom"])]
+        ephemeral: bool,
+    },
+
+    /// Check a VM's configuration by starting QEMU paused, then quitting it
+    Test {
+        /// VM name or ID
+        name: String,
+    },
+
+    /// Start every autostart VM that isn't already running
+    Autostart,
+
+    /// Show running VMs with their CPU, memory and uptime
+    Status {
+        /// Redraw the table every second until Ctrl-C
+        #[arg(short, long)]
+        watch: bool,
     },
 
     /// Show VM configuration
@@ -76,44 +109,85 @@ enum Commands {
     },
 
     /// List available QEMU emulators
-    Emulators,
+    Emulators {
+        /// Probe every emulator again instead of using the cached results
+        #[arg(long)]
+        refresh: bool,
+    },
+
+    /// Export a VM as libvirt domain XML
+    ExportXml {
+        /// VM name or ID
+        name: String,
+        /// Write to this file instead of stdout
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+    },
 }
 
 #[derive(Subcommand)]
 enum SnapshotAction {
     /// List snapshots
-    List,
+    List {
+        /// Print the snapshots as JSON
+        #[arg(long)]
+        json: bool,
+    },
     /// Create a snapshot
     Create {
         /// Snapshot name
         snapshot_name: String,
     },
-    /// Restore a snapshot
+    /// Restore a snapshot, discarding the disk's current state
     Restore {
         /// Snapshot name
         snapshot_name: String,
+        /// Don't ask for confirmation
+        #[arg(short, long)]
+        yes: bool,
     },
     /// Delete a snapshot
     Delete {
         /// Snapshot name
         snapshot_name: String,
+        /// Don't ask for confirmation
+        #[arg(short, long)]
+        yes: bool,
     },
 }
 
-fn main() -> Result<()> {
+fn main() {
     let cli = Cli::parse();
+    if let Err(e) = run(cli) {
+        eprintln!("{}", cli_error::format_error(&e));
+        std::process::exit(cli_error::categorize(&e).exit_code());
+    }
+}
 
+fn run(cli: Cli) -> Result<()> {
     // Load configuration
-    let mut config = Config::load()?;
+    if let Some(ref path) = cli.config {
+        Config::use_config_file(path.clone());
+    }
+    let mut config = Config::load().map_err(|e| {
+        cli_error::categorized(e, ErrorCategory::ConfigInvalid, "Invalid configuration")
+    })?;
 
-    // Override library path if provided
-    if let Some(ref library) = cli.library {
-        config.vm_library_path = library.clone();
+    // --library, then $VM_CURATOR_LIBRARY, override the config file
+    if let Some(library) =
+        config::library_override(cli.library.clone(), |key| std::env::var_os(key))
+    {
+        config.override_library(library);
     }
 
-    // Check if VM library exists, prompt for setup if not
-    if !config.vm_library_path.exists() {
-        config = prompt_vm_library_setup(config)?;
+    // The TUI offers to create a missing library; subcommands say where
+    // they expected it instead of acting on an empty one
+    if cli
+        .command
+        .as_ref()
+        .is_some_and(|c| !matches!(c, Commands::Emulators { .. }))
+    {
+        check_library(&config)?;
     }
 
     // Handle subcommands
@@ -123,88 +197,46 @@ fn main() -> Result<()> {
             name,
             install,
             cdrom,
-        }) => cmd_launch(&config, &name, install, cdrom),
+            ephemeral,
+        }) => cmd_launch(&config, &name, install, cdrom, ephemeral),
+        Some(Commands::Test { name }) => cmd_test(&config, &name),
+        Some(Commands::Autostart) => cmd_autostart(&config),
+        Some(Commands::Status { watch }) => cmd_status(&config, watch),
         Some(Commands::Info { name }) => cmd_info(&config, &name),
         Some(Commands::Snapshot { name, action }) => cmd_snapshot(&config, &name, action),
-        Some(Commands::Emulators) => cmd_emulators(),
+        Some(Commands::Emulators { refresh }) => cmd_emulators(refresh),
+        Some(Commands::ExportXml { name, output }) => cmd_export_xml(&config, &name, output),
         None => run_tui(config),
     }
 }
 
-/// Prompt user to set up VM library directory
-fn prompt_vm_library_setup(mut config: Config) -> Result<Config> {
-    println!();
-    println!("\x1b[1;36m╭─────────────────────────────────────╮\x1b[0m");
-    println!("\x1b[1;36m│\x1b[0m    \x1b[1;33mVM Curator\x1b[0m - First Time Setup    \x1b[1;36m│\x1b[0m");
-    println!("\x1b[1;36m╰─────────────────────────────────────╯\x1b[0m");
-    println!();
-    println!("VM library directory not found.");
-    println!();
-
-    // Show default path with ~ for home
-    let default_path = config.vm_library_path.display().to_string();
-    let display_path = if let Some(home) = dirs::home_dir() {
-        default_path.replace(&home.display().to_string(), "~")
-    } else {
-        default_path.clone()
+/// Fail with the expected path and how to fix it when the VM library is
+/// missing or unreadable
+fn check_library(config: &Config) -> Result<()> {
+    let Some(issue) = vm::library_issue(&config.vm_library_path) else {
+        return Ok(());
     };
-
-    println!("Where should VMs be stored?");
-    println!("\x1b[90mPress Enter to use default, or type a new path.\x1b[0m");
-    println!();
-    print!("\x1b[1;32m[\x1b[0m{}\x1b[1;32m]\x1b[0m: ", display_path);
-    io::stdout().flush()?;
-
-    // Read user input
-    let mut input = String::new();
-    io::stdin().read_line(&mut input)?;
-    let input = input.trim();
-
-    // Use input if provided, otherwise keep default
-    if !input.is_empty() {
-        // Expand ~ to home directory
-        let expanded = if let Some(rest) = input.strip_prefix("~/") {
-            if let Some(home) = dirs::home_dir() {
-                home.join(rest)
-            } else {
-                PathBuf::from(input)
-            }
-        } else if input == "~" {
-            dirs::home_dir().unwrap_or_else(|| PathBuf::from(input))
-        } else {
-            PathBuf::from(input)
-        };
-        config.vm_library_path = expanded;
-    }
-
-    // Create the directory
-    println!();
-    print!("Creating directory {:?}... ", config.vm_library_path);
-    io::stdout().flush()?;
-
-    let cow_disabled = fs::setup_vm_directory(&config.vm_library_path).with_context(|| {
+    let (category, hint) = match issue {
+        vm::LibraryIssue::Missing => (
+            ErrorCategory::NotFound,
+            "Run vm-curator without a command to create it, pass --library <dir>, export VM_CURATOR_LIBRARY, or set",
+        ),
+        vm::LibraryIssue::NotADirectory => {
+            (ErrorCategory::NotFound, "Pass --library <dir>, export VM_CURATOR_LIBRARY, or set")
+        }
+        vm::LibraryIssue::Unreadable(_) => {
+            (ErrorCategory::Permission, "Pass --library <dir>, export VM_CURATOR_LIBRARY, or set")
+        }
+    };
+    Err(cli_error(
+        category,
         format!(
-            "Failed to create VM library directory {:?}",
-            config.vm_library_path
-        )
-    })?;
-
-    println!("\x1b[32m✓\x1b[0m");
-
-    if cow_disabled {
-        println!("Disabled BTRFS copy-on-write for better VM performance \x1b[32m✓\x1b[0m");
-    }
-
-    // Save configuration
-    print!("Saving configuration... ");
-    io::stdout().flush()?;
-
-    config.save()?;
-
-    println!("\x1b[32m✓\x1b[0m");
-    println!();
-
-    Ok(config)
+            "{}\n  {} vm_library_path in {}",
+            issue.describe(&config.vm_library_path),
+            hint,
+            Config::config_file_path().display()
+        ),
+    ))
 }
 
 /// Guard that ensures terminal is restored on drop (even on panic)
@@ -246,6 +278,7 @@ fn run_tui(config: Config) -> Result<()> {
 
     // Run the app - guard will restore terminal even if this panics
     let mut app = app;
+    app.start_autostart_vms();
     ui::run(&mut terminal, &mut app)
 }
 
@@ -297,12 +330,22 @@ fn cmd_list(config: &Config) -> Result<()> {
             } else {
                 ""
             };
+            let last_launched = vm
+                .last_launched
+                .map(|t| {
+                    format!(
+                        "launched {}",
+                        vm::discovery::time_ago(t, chrono::Local::now())
+                    )
+                })
+                .unwrap_or_else(|| "never launched".to_string());
             println!(
-                "  {:24} {:8} {:4}MB {}",
+                "  {:24} {:8} {:4}MB {:12} {}",
                 vm.display_name(),
                 arch,
                 mem,
-                snapshot_support
+                snapshot_support,
+                last_launched
             );
         }
         println!();
@@ -312,46 +355,342 @@ fn cmd_list(config: &Config) -> Result<()> {
     Ok(())
 }
 
-fn cmd_launch(config: &Config, name: &str, install: bool, cdrom: Option<PathBuf>) -> Result<()> {
+/// Look up a VM by id or display name (case-insensitive)
+fn find_vm<'a>(vms: &'a [vm::DiscoveredVm], name: &str) -> Result<&'a vm::DiscoveredVm> {
+    vms.iter()
+        .find(|v| v.id == name || v.display_name().to_lowercase() == name.to_lowercase())
+        .ok_or_else(|| cli_error(ErrorCategory::NotFound, format!("VM '{}' not found", name)))
+}
+
+fn cmd_launch(
+    config: &Config,
+    name: &str,
+    install: bool,
+    cdrom: Option<PathBuf>,
+    ephemeral: bool,
+) -> Result<()> {
     let vms = vm::discover_vms(&config.vm_library_path)?;
 
-    let vm = vms
-        .iter()
-        .find(|v| v.id == name || v.display_name().to_lowercase() == name.to_lowercase())
-        .ok_or_else(|| anyhow::anyhow!("VM '{}' not found", name))?;
+    let vm = find_vm(&vms, name)?;
+
+    if vm::lifecycle::vm_process_pid(vm, &vm::detect_qemu_processes()).is_some() {
+        return Err(cli_error(
+            ErrorCategory::AlreadyRunning,
+            format!("{} is already running", vm.display_name()),
+        ));
+    }
+    let emulator = vm.config.emulator.command();
+    if !commands::qemu_system::is_emulator_available(emulator) {
+        return Err(cli_error(
+            ErrorCategory::QemuMissing,
+            format!("{} is not installed", emulator),
+        ));
+    }
+    if let Some(iso) = cdrom.as_ref().filter(|iso| !iso.exists()) {
+        return Err(cli_error(
+            ErrorCategory::NotFound,
+            format!("{} does not exist", iso.display()),
+        ));
+    }
 
     let boot_mode = if let Some(iso) = cdrom {
         vm::BootMode::Cdrom(iso)
     } else if install {
         vm::BootMode::Install
+    } else if ephemeral {
+        vm::BootMode::Ephemeral
     } else {
         vm::BootMode::Normal
     };
 
+    // A normal boot picks up a suspended state rather than leaving it stale
+    let resume_suspended =
+        matches!(boot_mode, vm::BootMode::Normal) && vm::has_suspend_state(&vm.path);
     let options = vm::LaunchOptions {
         boot_mode,
         extra_args: Vec::new(),
         usb_devices: Vec::new(),
+        force_tcg: false,
+        resume_suspended,
+        spice_tcp: config.spice_tcp,
     };
 
+    // Overcommit is allowed, but say so
+    if let Some(host) = hardware::HostCapacity::detect() {
+        for warning in host.warnings(vm.config.memory_mb, vm.config.cpu_cores) {
+            eprintln!("Warning: {}", warning);
+        }
+    }
+
+    vm::lifecycle::check_shared_disks_live(vm, &vms).map_err(|e| {
+        cli_error::categorized(
+            e,
+            ErrorCategory::AlreadyRunning,
+            "A disk of this VM is in use by a running VM",
+        )
+    })?;
     println!("Launching {}...", vm.display_name());
-    vm::launch_vm_sync(vm, &options)?;
-    println!("VM started.");
+    let spice = vm::launch_vm_sync(vm, &options)?;
+    if options.resume_suspended {
+        println!("Restoring suspended state...");
+        vm::finish_resume(&vm.path)?;
+    }
+    if ephemeral {
+        println!("VM started (ephemeral: disk changes are discarded at shutdown).");
+    } else {
+        println!("VM started.");
+    }
+    if let Some(vm::lifecycle::SpiceEndpoint::Tcp(port)) = spice {
+        println!(
+            "SPICE is on TCP port {} (remote-viewer spice://<host>:{})",
+            port, port
+        );
+    }
 
     Ok(())
 }
 
+fn cmd_test(config: &Config, name: &str) -> Result<()> {
+    let vms = vm::discover_vms(&config.vm_library_path)?;
+    let vm = find_vm(&vms, name)?;
+
+    if vm::lifecycle::vm_process_pid(vm, &vm::detect_qemu_processes()).is_some() {
+        return Err(cli_error(
+            ErrorCategory::AlreadyRunning,
+            format!("{} is already running", vm.display_name()),
+        ));
+    }
+    let emulator = vm.config.emulator.command();
+    if !commands::qemu_system::is_emulator_available(emulator) {
+        return Err(cli_error(
+            ErrorCategory::QemuMissing,
+            format!("{} is not installed", emulator),
+        ));
+    }
+
+    println!("Test launching {} (CPUs paused)...", vm.display_name());
+    match vm::test_launch(vm, vm::lifecycle::TEST_LAUNCH_TIMEOUT)? {
+        vm::TestLaunch::Passed { warnings } => {
+            for warning in &warnings {
+                eprintln!("Warning: {}", warning);
+            }
+            println!("OK: QEMU accepted the configuration and initialized every device.");
+            Ok(())
+        }
+        vm::TestLaunch::Failed { option, error } => {
+            let mut msg = String::from("QEMU failed to start");
+            if let Some(option) = option {
+                msg.push_str(&format!(" at {}", option));
+            }
+            Err(cli_error(
+                ErrorCategory::Failed,
+                format!("{}\n{}", msg, error),
+            ))
+        }
+        vm::TestLaunch::TimedOut { output } => Err(cli_error(
+            ErrorCategory::Failed,
+            format!(
+                "QEMU did not answer within {}s\n{}",
+                vm::lifecycle::TEST_LAUNCH_TIMEOUT.as_secs(),
+                output
+            ),
+        )),
+    }
+}
+
+fn cmd_autostart(config: &Config) -> Result<()> {
+    let vms = vm::discover_vms(&config.vm_library_path)?;
+    let autostart: Vec<&vm::DiscoveredVm> = vms.iter().filter(|v| v.autostart).collect();
+    if autostart.is_empty() {
+        println!("No VMs are set to autostart.");
+        return Ok(());
+    }
+
+    let processes = vm::detect_qemu_processes();
+    let mut failed = 0;
+    for vm in autostart {
+        if vm::lifecycle::vm_process_pid(vm, &processes).is_some() {
+            println!("{} is already running.", vm.display_name());
+            continue;
+        }
+        let options = vm::LaunchOptions {
+            boot_mode: vm::BootMode::Normal,
+            extra_args: Vec::new(),
+            usb_devices: Vec::new(),
+            force_tcg: false,
+            resume_suspended: vm::has_suspend_state(&vm.path),
+            spice_tcp: config.spice_tcp,
+        };
+        println!("Launching {}...", vm.display_name());
+        let result = vm::lifecycle::check_shared_disks_live(vm, &vms)
+            .and_then(|()| vm::launch_vm_sync(vm, &options))
+            .and_then(|_| {
+                if options.resume_suspended {
+                    vm::finish_resume(&vm.path)
+                } else {
+                    Ok(())
+                }
+            });
+        if let Err(e) = result {
+            eprintln!("Failed to start {}: {:#}", vm.display_name(), e);
+            failed += 1;
+        }
+    }
+
+    if failed > 0 {
+        anyhow::bail!("{} autostart VM(s) failed to start", failed);
+    }
+    Ok(())
+}
+
+/// A running VM's QEMU process and its latest resource sample
+struct StatusRow {
+    name: String,
+    pid: u32,
+    sample: vm::proc_stats::ProcSample,
+}
+
+/// Sample the QEMU process of every running VM in the library
+fn sample_running_vms(vms: &[vm::DiscoveredVm]) -> Vec<StatusRow> {
+    let processes = vm::detect_qemu_processes();
+    vms.iter()
+        .filter_map(|vm| {
+            let pid = vm::lifecycle::vm_process_pid(vm, &processes)?;
+            Some(StatusRow {
+                name: vm.display_name(),
+                pid,
+                sample: vm::proc_stats::sample(pid)?,
+            })
+        })
+        .collect()
+}
+
+/// Table of running VMs. CPU% compares each process with its sample in
+/// `previous`, taken `elapsed` earlier; a process not in it shows "-".
+fn status_lines(
+    rows: &[StatusRow],
+    previous: &HashMap<u32, vm::proc_stats::ProcSample>,
+    elapsed: Duration,
+) -> Vec<String> {
+    if rows.is_empty() {
+        return vec!["No VMs are running.".to_string()];
+    }
+    let mut lines = vec![format!(
+        "{:24} {:>8} {:>7} {:>8} {:>12}",
+        "NAME", "PID", "CPU%", "RSS", "UPTIME"
+    )];
+    for row in rows {
+        let cpu = previous
+            .get(&row.pid)
+            .map(|prev| {
+                format!(
+                    "{:.1}",
+                    vm::proc_stats::cpu_percent(prev, &row.sample, elapsed)
+                )
+            })
+            .unwrap_or_else(|| "-".to_string());
+        lines.push(format!(
+            "{:24} {:>8} {:>7} {:>8} {:>12}",
+            row.name,
+            row.pid,
+            cpu,
+            vm::snapshot::format_size(row.sample.rss_bytes),
+            vm::proc_stats::format_uptime(row.sample.uptime)
+        ));
+    }
+    lines
+}
+
+fn cmd_status(config: &Config, watch: bool) -> Result<()> {
+    let vms = vm::discover_vms(&config.vm_library_path)?;
+    let samples = |rows: &[StatusRow]| -> HashMap<u32, vm::proc_stats::ProcSample> {
+        rows.iter().map(|row| (row.pid, row.sample)).collect()
+    };
+
+    if !watch {
+        // CPU% is a rate, so take a short first sample to compare with
+        let first = samples(&sample_running_vms(&vms));
+        let start = Instant::now();
+        std::thread::sleep(Duration::from_millis(500));
+        let rows = sample_running_vms(&vms);
+        for line in status_lines(&rows, &first, start.elapsed()) {
+            println!("{}", line);
+        }
+        return Ok(());
+    }
+
+    if !io::stdin().is_terminal() || !io::stdout().is_terminal() {
+        anyhow::bail!("--watch needs a terminal");
+    }
+
+    // Raw mode turns Ctrl-C into a key press, so the cursor and terminal
+    // mode are always put back before exiting
+    enable_raw_mode()?;
+    let mut stdout = io::stdout();
+    execute!(stdout, cursor::Hide)?;
+    let result = watch_status(&vms, &mut stdout, samples);
+    execute!(stdout, cursor::Show)?;
+    disable_raw_mode()?;
+    result
+}
+
+fn watch_status(
+    vms: &[vm::DiscoveredVm],
+    stdout: &mut io::Stdout,
+    samples: impl Fn(&[StatusRow]) -> HashMap<u32, vm::proc_stats::ProcSample>,
+) -> Result<()> {
+    let mut previous = HashMap::new();
+    let mut last = Instant::now();
+    loop {
+        let rows = sample_running_vms(vms);
+        let now = Instant::now();
+        queue!(stdout, cursor::MoveTo(0, 0), Clear(ClearType::All))?;
+        write!(
+            stdout,
+            "vm-curator status - {} (Ctrl-C or q to quit)\r\n\r\n",
+            chrono::Local::now().format("%H:%M:%S")
+        )?;
+        for line in status_lines(&rows, &previous, now - last) {
+            write!(stdout, "{}\r\n", line)?;
+        }
+        stdout.flush()?;
+        previous = samples(&rows);
+        last = now;
+
+        // Wait out the rest of the second, watching for a quit key
+        let deadline = now + Duration::from_secs(1);
+        while let Some(timeout) = deadline.checked_duration_since(Instant::now()) {
+            if !term_event::poll(timeout)? {
+                break;
+            }
+            if let term_event::Event::Key(key) = term_event::read()? {
+                let ctrl_c =
+                    key.code == KeyCode::Char('c') && key.modifiers.contains(KeyModifiers::CONTROL);
+                if ctrl_c || matches!(key.code, KeyCode::Char('q') | KeyCode::Esc) {
+                    return Ok(());
+                }
+            }
+        }
+    }
+}
+
 fn cmd_info(config: &Config, name: &str) -> Result<()> {
     let vms = vm::discover_vms(&config.vm_library_path)?;
 
-    let vm = vms
-        .iter()
-        .find(|v| v.id == name || v.display_name().to_lowercase() == name.to_lowercase())
-        .ok_or_else(|| anyhow::anyhow!("VM '{}' not found", name))?;
+    let vm = find_vm(&vms, name)?;
 
     println!("VM: {}", vm.display_name());
     println!("ID: {}", vm.id);
     println!("Path: {:?}", vm.path);
+    match vm.last_launched {
+        Some(t) => println!(
+            "Last launched: {} ({})",
+            t.format("%Y-%m-%d %H:%M"),
+            vm::discovery::time_ago(t, chrono::Local::now())
+        ),
+        None => println!("Last launched: never"),
+    }
+    println!("Launches: {}", vm.launch_count);
     println!();
     println!("Configuration:");
     println!("  Emulator: {}", vm.config.emulator.command());
@@ -374,7 +713,16 @@ fn cmd_info(config: &Config, name: &str) -> Result<()> {
     println!();
     println!("Disks:");
     for disk in &vm.config.disks {
-        println!("  {:?} ({:?}, {})", disk.path, disk.format, disk.interface);
+        let usage = commands::qemu_img::disk_usage(&vm.path.join(&disk.path))
+            .map(|u| format!(" - {}", u.summary()))
+            .unwrap_or_default();
+        println!(
+            "  {:?} ({:?}, {}){}",
+            disk.path, disk.format, disk.interface, usage
+        );
+    }
+    for warning in vm::discovery::shared_disk_warnings(vm, &vms) {
+        println!("  Warning: {} - don't run both at once", warning);
     }
 
     println!();
@@ -396,13 +744,27 @@ fn cmd_info(config: &Config, name: &str) -> Result<()> {
     Ok(())
 }
 
+fn cmd_export_xml(config: &Config, name: &str, output: Option<PathBuf>) -> Result<()> {
+    let vms = vm::discover_vms(&config.vm_library_path)?;
+
+    let vm = find_vm(&vms, name)?;
+
+    let xml = vm::export::libvirt_domain_xml(&vm.id, &vm.config);
+    match output {
+        Some(path) => {
+            std::fs::write(&path, xml).with_context(|| format!("Failed to write {:?}", path))?;
+            println!("Exported {} to {:?}", vm.display_name(), path);
+        }
+        None => print!("{}", xml),
+    }
+
+    Ok(())
+}
+
 fn cmd_snapshot(config: &Config, name: &str, action: SnapshotAction) -> Result<()> {
     let vms = vm::discover_vms(&config.vm_library_path)?;
 
-    let vm = vms
-        .iter()
-        .find(|v| v.id == name || v.display_name().to_lowercase() == name.to_lowercase())
-        .ok_or_else(|| anyhow::anyhow!("VM '{}' not found", name))?;
+    let vm = find_vm(&vms, name)?;
 
     if !vm.config.supports_snapshots() {
         anyhow::bail!("VM '{}' does not support snapshots (raw disk format)", name);
@@ -413,10 +775,39 @@ fn cmd_snapshot(config: &Config, name: &str, action: SnapshotAction) -> Result<(
         .primary_disk()
         .ok_or_else(|| anyhow::anyhow!("VM has no disk configured"))?;
 
+    if !commands::qemu_system::is_emulator_available("qemu-img") {
+        return Err(cli_error(
+            ErrorCategory::QemuMissing,
+            "qemu-img is not installed",
+        ));
+    }
+
+    // qemu-img can't change snapshots of an image QEMU has open
+    let running = || vm::lifecycle::vm_process_pid(vm, &vm::detect_qemu_processes()).is_some();
+    let snapshot_exists = |snapshot_name: &str| -> Result<()> {
+        if vm::list_snapshots(&disk.path)?
+            .iter()
+            .any(|snap| snap.name == snapshot_name)
+        {
+            Ok(())
+        } else {
+            Err(cli_error(
+                ErrorCategory::NotFound,
+                format!(
+                    "{} has no snapshot named '{}'",
+                    vm.display_name(),
+                    snapshot_name
+                ),
+            ))
+        }
+    };
+
     match action {
-        SnapshotAction::List => {
+        SnapshotAction::List { json } => {
             let snapshots = vm::list_snapshots(&disk.path)?;
-            if snapshots.is_empty() {
+            if json {
+                println!("{}", serde_json::to_string_pretty(&snapshots)?);
+            } else if snapshots.is_empty() {
                 println!("No snapshots for {}", vm.display_name());
             } else {
                 println!("Snapshots for {}:", vm.display_name());
@@ -426,18 +817,60 @@ fn cmd_snapshot(config: &Config, name: &str, action: SnapshotAction) -> Result<(
             }
         }
         SnapshotAction::Create { snapshot_name } => {
-            println!("Creating snapshot '{}'...", snapshot_name);
-            vm::create_snapshot(&disk.path, &snapshot_name)?;
+            // A running VM is snapshotted live over QMP
+            let live = running();
+            let label = format!("Creating snapshot '{}'", snapshot_name);
+            with_elapsed(&label, || {
+                vm::snapshot::snapshot_vm(&vm.path, &disk.path, &snapshot_name, live)
+            })?;
             println!("Snapshot created.");
         }
-        SnapshotAction::Restore { snapshot_name } => {
-            println!("Restoring snapshot '{}'...", snapshot_name);
-            vm::restore_snapshot(&disk.path, &snapshot_name)?;
+        SnapshotAction::Restore { snapshot_name, yes } => {
+            if running() {
+                return Err(cli_error(
+                    ErrorCategory::AlreadyRunning,
+                    format!(
+                        "Cannot restore a snapshot while {} is running; shut it down first",
+                        vm.display_name()
+                    ),
+                ));
+            }
+            snapshot_exists(&snapshot_name)?;
+            let question = format!(
+                "Restore '{}' on {}? Changes since the snapshot are lost.",
+                snapshot_name,
+                vm.display_name()
+            );
+            if !confirm(&question, yes)? {
+                println!("Cancelled.");
+                return Ok(());
+            }
+            let label = format!("Restoring snapshot '{}'", snapshot_name);
+            with_elapsed(&label, || vm::restore_snapshot(&disk.path, &snapshot_name))?;
             println!("Snapshot restored.");
         }
-        SnapshotAction::Delete { snapshot_name } => {
-            println!("Deleting snapshot '{}'...", snapshot_name);
-            vm::delete_snapshot(&disk.path, &snapshot_name)?;
+        SnapshotAction::Delete { snapshot_name, yes } => {
+            if running() {
+                return Err(cli_error(
+                    ErrorCategory::AlreadyRunning,
+                    format!(
+                        "Cannot delete a snapshot while {} is running; shut it down first",
+                        vm.display_name()
+                    ),
+                ));
+            }
+            snapshot_exists(&snapshot_name)?;
+            let question = format!(
+                "Delete snapshot '{}' of {}?",
+                snapshot_name,
+                vm.display_name()
+            );
+            if !confirm(&question, yes)? {
+                println!("Cancelled.");
+                return Ok(());
+            }
+            let label = format!("Deleting snapshot '{}'", snapshot_name);
+            with_elapsed(&label, || vm::delete_snapshot(&disk.path, &snapshot_name))?;
             println!("Snapshot deleted.");
         }
     }
@@ -445,22 +878,77 @@ fn cmd_snapshot(config: &Config, name: &str, action: SnapshotAction) -> Result<(
     Ok(())
 }
 
-fn cmd_emulators() -> Result<()> {
-    println!("Available QEMU emulators:");
-    println!();
+/// Ask a yes/no question before a destructive command. `--yes` answers it;
+/// without a terminal to ask on, refuse instead of guessing.
+fn confirm(question: &str, yes: bool) -> Result<bool> {
+    if yes {
+        return Ok(true);
+    }
+    if !io::stdin().is_terminal() {
+        anyhow::bail!("{} Pass --yes to confirm non-interactively.", question);
+    }
+    print!("{} [y/N] ", question);
+    io::stdout().flush()?;
+    let mut input = String::new();
+    io::stdin().read_line(&mut input)?;
+    Ok(matches!(input.trim().to_lowercase().as_str(), "y" | "yes"))
+}
+
+/// Run a long qemu-img step, counting the seconds on the terminal while it
+/// runs. `qemu-img snapshot` reports no progress of its own.
+fn with_elapsed(label: &str, step: impl FnOnce() -> Result<()> + Send) -> Result<()> {
+    if !io::stderr().is_terminal() {
+        println!("{}...", label);
+        return step();
+    }
+    let start = std::time::Instant::now();
+    std::thread::scope(|scope| {
+        let handle = scope.spawn(step);
+        while !handle.is_finished() {
+            eprint!("\r{}... {}s", label, start.elapsed().as_secs());
+            std::thread::sleep(std::time::Duration::from_millis(200));
+        }
+        eprintln!("\r{}... {}s", label, start.elapsed().as_secs());
+        handle
+            .join()
+            .unwrap_or_else(|_| Err(anyhow::anyhow!("{} panicked", label)))
+    })
+}
 
+fn cmd_emulators(refresh: bool) -> Result<()> {
     let emulators = commands::qemu_system::list_available_emulators();
 
     if emulators.is_empty() {
-        println!("  No QEMU emulators found. Please install QEMU.");
-        return Ok(());
+        return Err(cli_error(
+            ErrorCategory::QemuMissing,
+            "No QEMU emulators found. Please install QEMU.",
+        ));
+    }
+
+    if refresh {
+        commands::qemu_caps::refresh(&emulators);
     }
 
+    println!("Available QEMU emulators:");
+    println!();
     for emulator in emulators {
-        if let Ok(version) = commands::qemu_system::get_qemu_version(&emulator) {
-            println!("  {} - {}", emulator, version);
-        } else {
-            println!("  {}", emulator);
+        let caps = commands::qemu_caps::capabilities(&emulator).unwrap_or_default();
+        match &caps.version {
+            Some(version) => println!("  {} - {}", emulator, version),
+            None => println!("  {}", emulator),
+        }
+        if !caps.displays.is_empty() {
+            println!("    displays: {}", caps.displays.join(", "));
+        }
+        if !caps.audiodevs.is_empty() {
+            println!("    audio:    {}", caps.audiodevs.join(", "));
+        }
+        if let Some(default) = caps.machines.first() {
+            println!(
+                "    machines: {} types (default {})",
+                caps.machines.len(),
+                default
+            );
         }
     }
 
@@ -27,13 +27,42 @@ pub struct Family {
 }
 
 /// Sort method for VMs within a subcategory
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
 pub enum SortBy {
     /// Sort by release date (oldest first) - for version-based subcategories
     Date,
     /// Sort alphabetically by display name - for non-version subcategories
     #[default]
     Name,
+    /// Most recently launched first; never-launched VMs last
+    LastLaunched,
+    /// Largest memory first
+    Memory,
+}
+
+impl SortBy {
+    /// Next VM list sort for the main menu's sort key. `None` keeps each
+    /// subcategory's own order from the hierarchy ("category").
+    pub fn next_list_sort(current: Option<SortBy>) -> Option<SortBy> {
+        match current {
+            None => Some(Self::Name),
+            Some(Self::Name) => Some(Self::LastLaunched),
+            Some(Self::LastLaunched) => Some(Self::Memory),
+            Some(Self::Memory) | Some(Self::Date) => None,
+        }
+    }
+
+    /// Label for a VM list sort, as shown in the list header
+    pub fn list_label(sort: Option<SortBy>) -> &'static str {
+        match sort {
+            None => "category",
+            Some(Self::Date) => "date",
+            Some(Self::Name) => "name",
+            Some(Self::LastLaunched) => "last launched",
+            Some(Self::Memory) => "memory",
+        }
+    }
 }
 
 /// A subcategory within an OS family
@@ -4,6 +4,7 @@ pub mod os_info;
 pub mod qemu_profiles;
 pub mod settings_help;
 pub mod shared_folders_help;
+pub mod templates;
 
 pub use ascii_art::AsciiArtStore;
 pub use hierarchy::{HierarchyConfig, SortBy};
@@ -11,3 +12,4 @@ pub use os_info::{default_os_info, MetadataStore, OsInfo};
 pub use qemu_profiles::{QemuProfile, QemuProfileStore};
 pub use settings_help::SettingsHelpStore;
 pub use shared_folders_help::SharedFoldersHelpStore;
+pub use templates::{TemplateStore, VmTemplate};
@@ -120,6 +120,17 @@ impl MetadataStore {
         None
     }
 
+    /// Write an entry to `<dir>/<id>.toml` and add it to the store
+    pub fn save_to_dir(&mut self, dir: &Path, id: &str, info: OsInfo) -> Result<()> {
+        std::fs::create_dir_all(dir)?;
+        std::fs::write(
+            dir.join(format!("{}.toml", id)),
+            toml::to_string_pretty(&info)?,
+        )?;
+        self.entries.insert(id.to_string(), info);
+        Ok(())
+    }
+
     /// Merge user overrides with embedded defaults
     pub fn merge(&mut self, overrides: MetadataStore) {
         for (id, info) in overrides.entries {
@@ -3,13 +3,22 @@
 //! This module provides OS-specific QEMU defaults that are used
 //! when creating new VMs through the creation wizard.
 
+use crate::vm::qemu_config::InputDevices;
+use anyhow::{Context, Result};
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
-use std::path::Path;
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
 
 /// Embedded QEMU profiles from assets/metadata/qemu_profiles.toml
 const EMBEDDED_PROFILES: &str = include_str!("../../assets/metadata/qemu_profiles.toml");
 
+/// Shown wherever a macOS profile is picked
+pub const MACOS_LICENSE_NOTE: &str = "Apple's license only allows macOS to run on Apple hardware. \
+     vm-curator does not download macOS; bring your own installer and OpenCore image.";
+
+/// Directory under the user metadata path holding one `<id>.toml` per profile
+pub const USER_PROFILES_DIR: &str = "qemu_profiles";
+
 /// BIOS/ROM file configuration for profiles that need a custom firmware
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct BiosRomConfig {
@@ -45,6 +54,11 @@ pub struct QemuProfile {
     /// Default RAM in megabytes
     pub memory_mb: u32,
 
+    /// Most RAM the OS copes with (Windows 9x fails to boot above ~512 MB);
+    /// the wizard warns above it but doesn't stop you
+    #[serde(default)]
+    pub memory_cap_mb: Option<u32>,
+
     /// Default CPU cores
     pub cpu_cores: u32,
 
@@ -96,6 +110,16 @@ pub struct QemuProfile {
     #[serde(default)]
     pub usb_tablet: bool,
 
+    /// Keyboard and pointer devices; overrides `usb_tablet` (`None` = USB
+    /// tablet if `usb_tablet`, else PS/2)
+    #[serde(default)]
+    pub input: Option<InputDevices>,
+
+    /// Intel macOS guest: adds the Apple SMC and SMBIOS table and uses the
+    /// OpenCore/AHCI disk layout (always on for `macos-*` profile ids)
+    #[serde(default)]
+    pub apple_smc: bool,
+
     /// Display output (gtk, sdl, spice, vnc)
     #[serde(default = "default_display")]
     pub display: String,
@@ -108,6 +132,10 @@ pub struct QemuProfile {
     #[serde(default)]
     pub iso_url: Option<String>,
 
+    /// Published SHA-256 of the ISO at `iso_url`, offered for verification
+    #[serde(default)]
+    pub iso_sha256: Option<String>,
+
     /// Tips/notes for this OS
     #[serde(default)]
     pub notes: Option<String>,
@@ -132,6 +160,7 @@ impl Default for QemuProfile {
             category: "alternative".to_string(),
             emulator: "qemu-system-x86_64".to_string(),
             memory_mb: 2048,
+            memory_cap_mb: None,
             cpu_cores: 2,
             cpu_model: Some("host".to_string()),
             machine: Some("q35".to_string()),
@@ -146,9 +175,12 @@ impl Default for QemuProfile {
             tpm: false,
             rtc_localtime: false,
             usb_tablet: true,
+            input: None,
+            apple_smc: false,
             display: "gtk".to_string(),
             extra_args: vec![],
             iso_url: None,
+            iso_sha256: None,
             notes: None,
             bios_rom: None,
         }
@@ -156,6 +188,31 @@ impl Default for QemuProfile {
 }
 
 impl QemuProfile {
+    /// Input devices for new VMs: old OSes without USB support get PS/2
+    pub fn input_devices(&self) -> InputDevices {
+        self.input.unwrap_or(if self.usb_tablet {
+            InputDevices::UsbTablet
+        } else {
+            InputDevices::Ps2
+        })
+    }
+
+    /// Warning when `memory_mb` is above what this OS copes with
+    pub fn memory_warning(&self, memory_mb: u32) -> Option<String> {
+        let cap = self.memory_cap_mb?;
+        (memory_mb > cap).then(|| {
+            format!(
+                "{} is unreliable with more than {} MB RAM",
+                self.display_name, cap
+            )
+        })
+    }
+
+    /// Licensing caveat to show before creating a VM from this profile
+    pub fn legal_note(&self) -> Option<&'static str> {
+        (self.category == "macos").then_some(MACOS_LICENSE_NOTE)
+    }
+
     /// Check if this profile supports free ISO download
     #[allow(dead_code)]
     pub fn has_free_iso(&self) -> bool {
@@ -204,6 +261,8 @@ impl QemuProfile {
 #[derive(Debug, Default)]
 pub struct QemuProfileStore {
     profiles: HashMap<String, QemuProfile>,
+    /// Ids loaded or saved from the user's files
+    user_ids: HashSet<String>,
 }
 
 impl QemuProfileStore {
@@ -211,6 +270,7 @@ impl QemuProfileStore {
     pub fn new() -> Self {
         Self {
             profiles: HashMap::new(),
+            user_ids: HashSet::new(),
         }
     }
 
@@ -241,6 +301,7 @@ impl QemuProfileStore {
                 Ok(user_profiles) => {
                     // Merge user profiles (override existing)
                     for (id, profile) in user_profiles {
+                        self.user_ids.insert(id.clone());
                         self.profiles.insert(id, profile);
                     }
                 }
@@ -254,13 +315,67 @@ impl QemuProfileStore {
         }
     }
 
+    /// Load user profiles from `<id>.toml` files in a directory, each
+    /// replacing the built-in profile with the same id
+    pub fn load_user_dir(&mut self, dir: &Path) {
+        let Ok(entries) = std::fs::read_dir(dir) else {
+            return;
+        };
+
+        for path in entries.flatten().map(|e| e.path()) {
+            if path.extension().map(|e| e != "toml").unwrap_or(true) {
+                continue;
+            }
+            let Some(id) = path.file_stem().and_then(|s| s.to_str()) else {
+                continue;
+            };
+            let profile = std::fs::read_to_string(&path)
+                .map_err(anyhow::Error::from)
+                .and_then(|content| Ok(toml::from_str::<QemuProfile>(&content)?));
+            match profile {
+                Ok(profile) => {
+                    self.user_ids.insert(id.to_string());
+                    self.profiles.insert(id.to_string(), profile);
+                }
+                Err(e) => {
+                    eprintln!(
+                        "Warning: Failed to load QEMU profile {}: {}",
+                        path.display(),
+                        e
+                    );
+                }
+            }
+        }
+    }
+
+    /// Write a user profile to `<dir>/<id>.toml` and add it to the store
+    pub fn save_user_profile(
+        &mut self,
+        dir: &Path,
+        id: &str,
+        profile: QemuProfile,
+    ) -> Result<PathBuf> {
+        std::fs::create_dir_all(dir)
+            .with_context(|| format!("Failed to create {}", dir.display()))?;
+        let path = dir.join(format!("{}.toml", id));
+        std::fs::write(&path, toml::to_string_pretty(&profile)?)
+            .with_context(|| format!("Failed to write {}", path.display()))?;
+        self.user_ids.insert(id.to_string());
+        self.profiles.insert(id.to_string(), profile);
+        Ok(path)
+    }
+
+    /// Whether a profile came from the user's files rather than the built-ins
+    pub fn is_user_profile(&self, os_id: &str) -> bool {
+        self.user_ids.contains(os_id)
+    }
+
     /// Get a profile by OS ID
     pub fn get(&self, os_id: &str) -> Option<&QemuProfile> {
         self.profiles.get(os_id)
     }
 
     /// Get a profile by OS ID, or return the default profile
-    #[allow(dead_code)]
     pub fn get_or_default(&self, os_id: &str) -> QemuProfile {
         self.profiles
             .get(os_id)
@@ -370,6 +485,7 @@ impl QemuProfileStore {
             "windows" => "generic-windows",
             "linux" => "generic-linux",
             "bsd" => "generic-bsd",
+            "macos" => "generic-macos",
             _ => "generic-other",
         }
     }
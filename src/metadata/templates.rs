@@ -0,0 +1,118 @@
+//! Named VM templates
+//!
+//! A template is a saved set of wizard QEMU settings plus a disk size, so
+//! VMs of the same shape can be created without re-entering them. Templates
+//! are kept in `templates.toml` in the config directory, keyed by name.
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+use crate::wizard_types::WizardQemuConfig;
+
+/// Settings saved under a template name
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VmTemplate {
+    /// OS profile the template was saved from; selected again when it's used
+    #[serde(default)]
+    pub os_profile: Option<String>,
+    pub disk_size_gb: u32,
+    pub qemu: WizardQemuConfig,
+}
+
+impl VmTemplate {
+    /// Short description for list views
+    pub fn summary(&self) -> String {
+        format!(
+            "{} MB, {} CPU, {} GB disk",
+            self.qemu.memory_mb, self.qemu.cpu_cores, self.disk_size_gb
+        )
+    }
+}
+
+/// Store for user templates, written back to its file on every change
+#[derive(Debug, Default)]
+pub struct TemplateStore {
+    path: PathBuf,
+    templates: HashMap<String, VmTemplate>,
+}
+
+impl TemplateStore {
+    /// Load templates from a file; a missing file gives an empty store
+    pub fn load(path: &Path) -> Self {
+        let mut store = Self {
+            path: path.to_path_buf(),
+            templates: HashMap::new(),
+        };
+        if !path.exists() {
+            return store;
+        }
+
+        match std::fs::read_to_string(path) {
+            Ok(content) => match toml::from_str::<HashMap<String, VmTemplate>>(&content) {
+                Ok(templates) => store.templates = templates,
+                Err(e) => eprintln!("Warning: Failed to parse VM templates: {}", e),
+            },
+            Err(e) => eprintln!("Warning: Failed to read VM templates: {}", e),
+        }
+        store
+    }
+
+    /// Get a template by name
+    pub fn get(&self, name: &str) -> Option<&VmTemplate> {
+        self.templates.get(name)
+    }
+
+    /// All templates, sorted by name
+    pub fn list(&self) -> Vec<(&String, &VmTemplate)> {
+        let mut templates: Vec<_> = self.templates.iter().collect();
+        templates.sort_by_key(|(name, _)| name.to_lowercase());
+        templates
+    }
+
+    /// Check if there are no templates
+    pub fn is_empty(&self) -> bool {
+        self.templates.is_empty()
+    }
+
+    /// Save a template under `name`, replacing one with the same name.
+    /// Returns whether one was replaced.
+    pub fn insert(&mut self, name: &str, template: VmTemplate) -> Result<bool> {
+        let replaced = self.templates.insert(name.to_string(), template);
+        if let Err(e) = self.save() {
+            match replaced {
+                Some(old) => self.templates.insert(name.to_string(), old),
+                None => self.templates.remove(name),
+            };
+            return Err(e);
+        }
+        Ok(replaced.is_some())
+    }
+
+    /// Delete a template
+    pub fn remove(&mut self, name: &str) -> Result<()> {
+        let Some(old) = self.templates.remove(name) else {
+            return Ok(());
+        };
+        if let Err(e) = self.save() {
+            self.templates.insert(name.to_string(), old);
+            return Err(e);
+        }
+        Ok(())
+    }
+
+    fn save(&self) -> Result<()> {
+        if let Some(parent) = self.path.parent() {
+            std::fs::create_dir_all(parent)
+                .with_context(|| format!("Failed to create {}", parent.display()))?;
+        }
+        let content = toml::to_string_pretty(&self.templates)?;
+        std::fs::write(&self.path, content)
+            .with_context(|| format!("Failed to write {}", self.path.display()))
+    }
+}
+
+#[cfg(test)]
+#[path = "tests/templates.rs"]
+mod tests;
@@ -11,6 +11,46 @@ fn test_load_embedded_profiles() {
     assert!(store.get("freebsd").is_some(), "Should have FreeBSD");
 }
 
+#[test]
+fn test_profile_input_devices() {
+    let store = QemuProfileStore::load_embedded();
+    let input = |id: &str| store.get(id).unwrap().input_devices();
+    assert_eq!(input("ms-dos"), InputDevices::Ps2);
+    assert_eq!(input("windows-95"), InputDevices::Ps2);
+    assert_eq!(input("windows-10"), InputDevices::UsbTablet);
+
+    let profile = QemuProfile {
+        usb_tablet: true,
+        input: Some(InputDevices::Virtio),
+        ..Default::default()
+    };
+    assert_eq!(profile.input_devices(), InputDevices::Virtio);
+}
+
+#[test]
+fn test_retro_memory_caps() {
+    let store = QemuProfileStore::load_embedded();
+    let win98 = store.get("windows-98").unwrap();
+    assert_eq!(win98.memory_cap_mb, Some(512));
+    assert!(win98.memory_mb <= 512);
+    assert_eq!(win98.memory_warning(512), None);
+    assert_eq!(
+        win98.memory_warning(1024).as_deref(),
+        Some("Windows 98 is unreliable with more than 512 MB RAM")
+    );
+    assert_eq!(store.get("windows-95").unwrap().memory_cap_mb, Some(480));
+    assert_eq!(
+        store.get("linux-debian").unwrap().memory_warning(65536),
+        None
+    );
+    // Every cap leaves room for the profile's own default
+    for (id, profile) in store.list_all() {
+        if let Some(cap) = profile.memory_cap_mb {
+            assert!(profile.memory_mb <= cap, "{} default exceeds its cap", id);
+        }
+    }
+}
+
 #[test]
 fn test_profile_summary() {
     let profile = QemuProfile {
@@ -97,6 +137,30 @@ fn test_classic_mac_bios_rom_config() {
     );
 }
 
+#[test]
+fn test_generic_macos_profile() {
+    let store = QemuProfileStore::load_embedded();
+    let macos = store
+        .get("generic-macos")
+        .expect("Should have generic-macos");
+    assert_eq!(macos.category, "macos");
+    assert!(macos.apple_smc);
+    assert_eq!(macos.vga, "none");
+    assert!(macos.extra_args.iter().any(|a| a.contains("vmware-svga")));
+    assert!(macos
+        .cpu_model
+        .as_deref()
+        .unwrap()
+        .contains("vendor=GenuineIntel"));
+    assert!(!macos.bios_rom.as_ref().unwrap().required);
+    assert_eq!(macos.legal_note(), Some(MACOS_LICENSE_NOTE));
+    assert_eq!(store.get("linux-debian").unwrap().legal_note(), None);
+    assert_eq!(
+        QemuProfileStore::generic_profile_for_category("macos"),
+        "generic-macos"
+    );
+}
+
 #[test]
 fn test_non_mac_profiles_no_bios_rom() {
     let store = QemuProfileStore::load_embedded();
@@ -129,3 +193,67 @@ fn test_free_iso_profiles() {
     let has_debian = free_profiles.iter().any(|(id, _)| *id == "linux-debian");
     assert!(has_debian, "Debian should have a free ISO URL");
 }
+
+#[test]
+fn test_user_profile_dir_overrides_and_adds() {
+    let dir = tempfile::tempdir().unwrap();
+    std::fs::write(
+        dir.path().join("linux-debian.toml"),
+        r#"
+display_name = "Debian (tuned)"
+category = "linux"
+emulator = "qemu-system-x86_64"
+memory_mb = 16384
+cpu_cores = 8
+vga = "virtio"
+network_model = "virtio"
+disk_interface = "virtio"
+disk_size_gb = 100
+"#,
+    )
+    .unwrap();
+    std::fs::write(dir.path().join("broken.toml"), "display_name = 1").unwrap();
+    std::fs::write(dir.path().join("notes.txt"), "not a profile").unwrap();
+
+    let mut store = QemuProfileStore::load_embedded();
+    let builtin_count = store.len();
+    store.load_user_dir(dir.path());
+
+    let debian = store.get("linux-debian").unwrap();
+    assert_eq!(debian.display_name, "Debian (tuned)");
+    assert_eq!(debian.memory_mb, 16384);
+    // Omitted optional fields fall back to their defaults
+    assert_eq!(debian.network_backend, "user");
+    assert_eq!(debian.display, "gtk");
+    assert!(store.is_user_profile("linux-debian"));
+    assert!(!store.is_user_profile("windows-10"));
+    assert!(store.get("broken").is_none());
+    assert_eq!(store.len(), builtin_count);
+}
+
+#[test]
+fn test_save_user_profile_round_trips() {
+    let dir = tempfile::tempdir().unwrap();
+    let profiles_dir = dir.path().join(USER_PROFILES_DIR);
+
+    let mut store = QemuProfileStore::load_embedded();
+    let profile = QemuProfile {
+        display_name: "House Linux".to_string(),
+        ..store.get_or_default("generic-linux")
+    };
+    let path = store
+        .save_user_profile(&profiles_dir, "house-linux", profile)
+        .unwrap();
+    assert_eq!(path, profiles_dir.join("house-linux.toml"));
+    assert!(store.is_user_profile("house-linux"));
+
+    let mut reloaded = QemuProfileStore::load_embedded();
+    reloaded.load_user_dir(&profiles_dir);
+    let saved = reloaded.get("house-linux").unwrap();
+    assert_eq!(saved.display_name, "House Linux");
+    assert_eq!(saved.category, "linux");
+    assert_eq!(
+        reloaded.list_by_category("linux").len(),
+        store.list_by_category("linux").len()
+    );
+}
@@ -0,0 +1,68 @@
+use super::*;
+use crate::vm::qemu_config::{CpuTopology, PortForward, PortProtocol};
+
+fn template() -> VmTemplate {
+    VmTemplate {
+        os_profile: Some("debian".to_string()),
+        disk_size_gb: 40,
+        qemu: WizardQemuConfig {
+            memory_mb: 8192,
+            cpu_cores: 4,
+            cpu_topology: Some(CpuTopology {
+                sockets: 2,
+                cores: 2,
+                threads: 1,
+            }),
+            port_forwards: vec![PortForward {
+                protocol: PortProtocol::Tcp,
+                host_port: 2222,
+                guest_port: 22,
+            }],
+            ..WizardQemuConfig::default()
+        },
+    }
+}
+
+#[test]
+fn test_templates_round_trip_through_file() -> Result<()> {
+    let dir = tempfile::tempdir()?;
+    let path = dir.path().join("config").join("templates.toml");
+
+    let mut store = TemplateStore::load(&path);
+    assert!(store.is_empty());
+    assert!(!store.insert("Build box", template())?);
+    assert!(store.insert("Build box", template())?);
+    store.insert("alpha", template())?;
+
+    let loaded = TemplateStore::load(&path);
+    let names: Vec<&String> = loaded.list().into_iter().map(|(name, _)| name).collect();
+    assert_eq!(names, ["alpha", "Build box"]);
+
+    let saved = loaded.get("Build box").unwrap();
+    assert_eq!(saved.os_profile.as_deref(), Some("debian"));
+    assert_eq!(saved.disk_size_gb, 40);
+    assert_eq!(saved.qemu.memory_mb, 8192);
+    assert_eq!(saved.qemu.cpu_topology, template().qemu.cpu_topology);
+    assert_eq!(saved.qemu.port_forwards, template().qemu.port_forwards);
+
+    store.remove("alpha")?;
+    assert!(TemplateStore::load(&path).get("alpha").is_none());
+    Ok(())
+}
+
+#[test]
+fn test_template_missing_fields_use_defaults() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("templates.toml");
+    std::fs::write(
+        &path,
+        "[small]\ndisk_size_gb = 8\n\n[small.qemu]\nmemory_mb = 512\n",
+    )
+    .unwrap();
+
+    let store = TemplateStore::load(&path);
+    let small = store.get("small").unwrap();
+    assert_eq!(small.os_profile, None);
+    assert_eq!(small.qemu.memory_mb, 512);
+    assert_eq!(small.qemu.cpu_cores, WizardQemuConfig::default().cpu_cores);
+}
@@ -0,0 +1,61 @@
+use super::*;
+use anyhow::Context;
+
+#[test]
+fn test_exit_codes_are_distinct() {
+    let categories = [
+        ErrorCategory::Failed,
+        ErrorCategory::NotFound,
+        ErrorCategory::AlreadyRunning,
+        ErrorCategory::QemuMissing,
+        ErrorCategory::Permission,
+        ErrorCategory::ConfigInvalid,
+    ];
+    let mut codes: Vec<i32> = categories.iter().map(|c| c.exit_code()).collect();
+    codes.sort();
+    codes.dedup();
+    assert_eq!(codes.len(), categories.len());
+    // 0 is success and 2 is clap's usage error
+    assert!(!codes.contains(&0));
+    assert!(!codes.contains(&2));
+}
+
+#[test]
+fn test_categorize_through_context() {
+    let err = cli_error(ErrorCategory::NotFound, "VM 'x' not found").context("Launching x");
+    assert_eq!(categorize(&err), ErrorCategory::NotFound);
+}
+
+#[test]
+fn test_categorize_permission_denied() {
+    let io = std::io::Error::new(std::io::ErrorKind::PermissionDenied, "denied");
+    let err = Err::<(), _>(io).context("Failed to open disk").unwrap_err();
+    assert_eq!(categorize(&err), ErrorCategory::Permission);
+}
+
+#[test]
+fn test_categorize_plain_error() {
+    assert_eq!(
+        categorize(&anyhow::anyhow!("something broke")),
+        ErrorCategory::Failed
+    );
+}
+
+#[test]
+fn test_format_error() {
+    let err = cli_error(ErrorCategory::AlreadyRunning, "VM 'x' is already running");
+    assert_eq!(
+        format_error(&err),
+        "error: already-running: VM 'x' is already running"
+    );
+
+    let err = categorized(
+        anyhow::anyhow!("expected `=`"),
+        ErrorCategory::ConfigInvalid,
+        "Failed to load config",
+    );
+    assert_eq!(
+        format_error(&err),
+        "error: config-invalid: Failed to load config: expected `=`"
+    );
+}
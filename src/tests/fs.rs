@@ -14,3 +14,71 @@ fn test_is_btrfs_root() {
     let _result = is_btrfs(&PathBuf::from("/"));
     // Just verify it doesn't panic
 }
+
+#[test]
+fn test_expand_home() {
+    let home = dirs::home_dir().unwrap_or_else(|| PathBuf::from("/"));
+    assert_eq!(expand_home("~"), home);
+    assert_eq!(expand_home("~/isos"), home.join("isos"));
+    assert_eq!(expand_home("/srv/isos"), PathBuf::from("/srv/isos"));
+}
+
+#[test]
+fn test_complete_dir_path() {
+    let tmp = tempfile::tempdir().unwrap();
+    let base = tmp.path().to_str().unwrap();
+    std::fs::create_dir(tmp.path().join("isos")).unwrap();
+    std::fs::create_dir(tmp.path().join("images")).unwrap();
+    std::fs::create_dir(tmp.path().join(".cache")).unwrap();
+    std::fs::write(tmp.path().join("isofile"), b"").unwrap();
+
+    // A single directory match completes fully; files are ignored
+    assert_eq!(
+        complete_dir_path(&format!("{}/iso", base)),
+        Some(format!("{}/isos/", base))
+    );
+    // Several matches extend to their common prefix
+    assert_eq!(
+        complete_dir_path(&format!("{}/", base)),
+        Some(format!("{}/i", base))
+    );
+    // Hidden directories need a typed dot
+    assert_eq!(
+        complete_dir_path(&format!("{}/.c", base)),
+        Some(format!("{}/.cache/", base))
+    );
+    assert_eq!(complete_dir_path(&format!("{}/x", base)), None);
+    assert_eq!(complete_dir_path("~"), Some("~/".to_string()));
+}
+
+#[test]
+fn test_allocated_size_counts_nested_files() {
+    let tmp = tempfile::tempdir().unwrap();
+    std::fs::create_dir(tmp.path().join("sub")).unwrap();
+    std::fs::write(tmp.path().join("a.bin"), vec![1u8; 64 * 1024]).unwrap();
+    std::fs::write(tmp.path().join("sub").join("b.bin"), vec![1u8; 64 * 1024]).unwrap();
+
+    // Filesystems differ in block accounting, but both files are allocated
+    assert!(allocated_size(tmp.path()) >= 128 * 1024);
+    assert_eq!(allocated_size(&tmp.path().join("missing")), 0);
+}
+
+#[test]
+fn test_copy_with_progress() {
+    let tmp = tempfile::tempdir().unwrap();
+    let source = tmp.path().join("disk.img");
+    let dest = tmp.path().join("copy.img");
+    std::fs::write(&source, vec![7u8; 64 * 1024]).unwrap();
+
+    let mut reported = Vec::new();
+    let copied = copy_with_progress(&source, &dest, |p| reported.push(p)).unwrap();
+    assert_eq!(copied, 64 * 1024);
+    assert_eq!(
+        std::fs::read(&dest).unwrap(),
+        std::fs::read(&source).unwrap()
+    );
+    assert_eq!(reported.last(), Some(&100.0));
+
+    let missing = copy_with_progress(&tmp.path().join("missing"), &dest, |_| {});
+    assert!(missing.is_err());
+}
@@ -0,0 +1,27 @@
+//! Copying text to the desktop clipboard through whichever command-line
+//! tool is installed (Wayland or X11)
+
+use std::io::Write;
+use std::process::{Command, Stdio};
+
+/// Clipboard tools tried in order, with their arguments
+const CLIPBOARD_TOOLS: &[(&str, &[&str])] = &[
+    ("wl-copy", &[]),
+    ("xclip", &["-selection", "clipboard"]),
+    ("xsel", &["--clipboard", "--input"]),
+];
+
+/// Copy `text` with the first clipboard tool that works, returning its name
+pub fn copy_to_clipboard(text: &str) -> Option<&'static str> {
+    CLIPBOARD_TOOLS.iter().find_map(|(tool, args)| {
+        let mut child = Command::new(tool)
+            .args(*args)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .spawn()
+            .ok()?;
+        child.stdin.take()?.write_all(text.as_bytes()).ok()?;
+        child.wait().ok()?.success().then_some(*tool)
+    })
+}
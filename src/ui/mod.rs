@@ -7,6 +7,7 @@
 //! and input logic stays in the individual `screens::*` modules; this file is the
 //! dispatcher that ties them to the [`crate::app::App`] state.
 
+pub mod clipboard;
 pub mod screens;
 pub mod widgets;
 
@@ -19,8 +20,11 @@ use std::io::Stdout;
 use std::time::{Duration, Instant};
 
 use crate::app::{
-    App, BackgroundResult, ConfirmAction, InputMode, Screen, TextInputContext, UnsavedKind,
+    App, BackgroundResult, BatchAction, ConfirmAction, InputMode, Screen, TextInputContext,
+    UnsavedKind,
 };
+use crate::hardware::LookingGlassConfig;
+use crate::ui::widgets::ProgressWidget;
 use crate::vm::{launch_vm_with_error_check, BootMode};
 use std::thread;
 
@@ -38,6 +42,27 @@ pub fn run(terminal: &mut Terminal<CrosstermBackend<Stdout>>, app: &mut App) ->
         // Check for VM status updates from background thread
         app.check_vm_status();
 
+        // Start any scheduled snapshots that are due
+        app.tick_snapshot_schedules();
+
+        // A passthrough launch needs the sudo password asked on the terminal
+        if let Some(force_tcg) = app.pending_sudo_launch.take() {
+            launch_after_sudo_prompt(terminal, app, force_tcg)?;
+            continue;
+        }
+
+        // The serial console takes over the terminal until detached
+        if std::mem::take(&mut app.pending_console_attach) {
+            attach_serial_console(terminal, app)?;
+            continue;
+        }
+
+        // ssh runs in the terminal until the session ends
+        if let Some(user) = app.pending_ssh.take() {
+            connect_ssh(terminal, app, &user)?;
+            continue;
+        }
+
         // Poll with timeout to allow periodic checks
         if event::poll(Duration::from_millis(100))? {
             match event::read()? {
@@ -57,7 +82,17 @@ pub fn run(terminal: &mut Terminal<CrosstermBackend<Stdout>>, app: &mut App) ->
         }
 
         if app.should_quit {
-            break;
+            if app.needs_quit_confirmation() {
+                app.should_quit = false;
+                if !matches!(
+                    app.screen,
+                    Screen::Confirm(ConfirmAction::QuitWithRunningVms)
+                ) {
+                    app.push_screen(Screen::Confirm(ConfirmAction::QuitWithRunningVms));
+                }
+            } else {
+                break;
+            }
         }
     }
 
@@ -124,6 +159,7 @@ fn handle_main_menu_click(app: &mut App, click_x: u16, click_y: u16) -> Result<(
                 &app.filtered_indices,
                 &app.hierarchy,
                 &app.metadata,
+                app.config.vm_list_sort,
                 &app.visual_order,
                 clicked_row,
             ) {
@@ -148,10 +184,22 @@ fn handle_confirm_click(
     click_x: u16,
     click_y: u16,
 ) -> Result<()> {
-    // The three-way unsaved-changes prompt has three buttons that don't map
-    // onto this two-region click model; require the keyboard (s/d/Esc) and treat
-    // any click as a cancel so a stray click never saves or discards.
-    if matches!(action, ConfirmAction::UnsavedChanges(_)) {
+    // The three-way prompts have three buttons that don't map onto this
+    // two-region click model, and the KVM and compaction prompts can be
+    // taller than the layout assumed below; require the keyboard and treat
+    // any click as a cancel so a stray click never saves, discards or launches.
+    if matches!(
+        action,
+        ConfirmAction::UnsavedChanges(_)
+            | ConfirmAction::QuitWithRunningVms
+            | ConfirmAction::ResetSettings
+            | ConfirmAction::KvmUnavailable(_)
+            | ConfirmAction::HostCapacity(_)
+            | ConfirmAction::ApplyIvshmemSize { .. }
+            | ConfirmAction::CompactDisk { .. }
+            | ConfirmAction::UsbHotAdd { .. }
+            | ConfirmAction::PassHostInput { .. }
+    ) {
         app.pop_screen();
         return Ok(());
     }
@@ -211,25 +259,49 @@ fn execute_confirm_action(app: &mut App, action: ConfirmAction) -> Result<()> {
             app.pop_screen();
 
             if let Some(vm) = app.selected_vm().cloned() {
+                let warnings = app
+                    .host_capacity
+                    .map(|host| host.warnings(vm.config.memory_mb, vm.config.cpu_cores))
+                    .unwrap_or_default();
+                let disk_check = crate::vm::lifecycle::check_shared_disks(&vm, &app.vms, |id| {
+                    app.running_vms.contains_key(id)
+                });
                 if app.running_vms.contains_key(&vm.id) {
                     app.set_status(format!("{} is already running", vm.display_name()));
+                } else if let Err(e) = disk_check {
+                    app.show_error(format!("Cannot launch {}\n\n{}", vm.display_name(), e));
+                } else if !warnings.is_empty() {
+                    app.push_screen(Screen::Confirm(ConfirmAction::HostCapacity(warnings)));
                 } else {
-                    let options = app.get_launch_options();
-                    let result = launch_vm_with_error_check(&vm, &options);
-
-                    if result.success {
-                        app.set_status(format!("Launched: {}", result.vm_name));
-                    } else {
-                        // Show error in the error dialog for better visibility
-                        let error_msg = result.error.unwrap_or_else(|| "Unknown error".to_string());
-                        app.show_error(format!(
-                            "Failed to launch {}\n\n{}",
-                            result.vm_name, error_msg
-                        ));
-                    }
+                    check_kvm_and_launch(app, &vm);
                 }
             }
         }
+        ConfirmAction::HostCapacity(_) => {
+            app.pop_screen();
+            if let Some(vm) = app.selected_vm().cloned() {
+                check_kvm_and_launch(app, &vm);
+            }
+        }
+        ConfirmAction::KvmUnavailable(_) => {
+            app.pop_screen();
+            launch_selected_vm(app, true);
+        }
+        ConfirmAction::VerifyIsoChecksum { path, expected } => {
+            app.pop_screen();
+            app.start_checksum_verification(path, expected);
+        }
+        ConfirmAction::ApplyIvshmemSize { size_mb, .. } => {
+            app.pop_screen();
+            app.config.default_ivshmem_size_mb = size_mb.clamp(16, 512);
+            match app.config.save() {
+                Ok(()) => app.set_status(format!(
+                    "IVSHMEM size set to {}MB",
+                    app.config.default_ivshmem_size_mb
+                )),
+                Err(e) => app.set_status(format!("Failed to save settings: {}", e)),
+            }
+        }
         ConfirmAction::ResetVm => {
             if let Some(vm) = app.selected_vm() {
                 if app.running_vms.contains_key(&vm.id) {
@@ -244,16 +316,11 @@ fn execute_confirm_action(app: &mut App, action: ConfirmAction) -> Result<()> {
             app.pop_screen();
         }
         ConfirmAction::DeleteVm => {
-            if let Some(vm) = app.selected_vm().cloned() {
-                if let Err(e) = crate::vm::lifecycle::delete_vm(&vm, false) {
-                    app.set_status(format!("Error: {}", e));
-                } else {
-                    app.set_status(format!("Deleted: {}", vm.display_name()));
-                    app.refresh_vms()?;
-                }
-            }
             app.pop_screen();
             app.pop_screen();
+            if let Some(vm) = app.selected_vm().cloned() {
+                app.start_trashing_vms(vec![vm], Vec::new());
+            }
         }
         ConfirmAction::RestoreSnapshot(name) => {
             if let Some(vm) = app.selected_vm() {
@@ -263,8 +330,7 @@ fn execute_confirm_action(app: &mut App, action: ConfirmAction) -> Result<()> {
                     let disk_path = disk.path.clone();
                     let snap_name = name.clone();
                     let tx = app.background_tx.clone();
-                    app.loading = true;
-                    app.set_status(format!("Restoring snapshot: {}...", name));
+                    app.start_progress(format!("Restoring snapshot: {}", name));
 
                     thread::spawn(move || {
                         let result = crate::vm::restore_snapshot(&disk_path, &snap_name);
@@ -286,8 +352,7 @@ fn execute_confirm_action(app: &mut App, action: ConfirmAction) -> Result<()> {
                     let disk_path = disk.path.clone();
                     let snap_name = name.clone();
                     let tx = app.background_tx.clone();
-                    app.loading = true;
-                    app.set_status(format!("Deleting snapshot: {}...", name));
+                    app.start_progress(format!("Deleting snapshot: {}", name));
 
                     thread::spawn(move || {
                         let result = crate::vm::delete_snapshot(&disk_path, &snap_name);
@@ -301,11 +366,68 @@ fn execute_confirm_action(app: &mut App, action: ConfirmAction) -> Result<()> {
             }
             app.pop_screen();
         }
+        ConfirmAction::DeleteSnapshots(names) => {
+            if let Some(vm) = app.selected_vm() {
+                if app.running_vms.contains_key(&vm.id) {
+                    app.set_status("Error: Cannot delete snapshots while VM is running. Please shut down the VM first.");
+                } else if let Some(disk) = vm.config.primary_disk() {
+                    let disk_path = disk.path.clone();
+                    let tx = app.background_tx.clone();
+                    app.start_progress(format!("Deleting {} snapshots", names.len()));
+
+                    // qemu-img locks the image, so deletions must run one at a time
+                    thread::spawn(move || {
+                        let mut deleted = 0;
+                        let mut failed = Vec::new();
+                        for (i, name) in names.iter().enumerate() {
+                            let _ = tx.send(BackgroundResult::Progress {
+                                label: format!("Deleting {} ({}/{})", name, i + 1, names.len()),
+                                percent: Some(i as f64 * 100.0 / names.len() as f64),
+                            });
+                            let result = crate::vm::delete_snapshot(&disk_path, name);
+                            let error = result.err().map(|e| e.to_string());
+                            match &error {
+                                None => deleted += 1,
+                                Some(e) => failed.push((name.clone(), e.clone())),
+                            }
+                            let _ = tx.send(BackgroundResult::SnapshotBatchProgress {
+                                name: name.clone(),
+                                error,
+                                remaining: names.len() - i - 1,
+                            });
+                        }
+                        let _ = tx.send(BackgroundResult::SnapshotBatchDeleted { deleted, failed });
+                    });
+                }
+            }
+            app.pop_screen();
+        }
+        ConfirmAction::Batch(action) => {
+            app.pop_screen();
+            app.start_batch(action);
+        }
+        ConfirmAction::DeleteStaleVms(ids) => {
+            app.pop_screen();
+            // A VM started since the screen opened is left alone
+            let (running, vms): (Vec<_>, Vec<_>) = app
+                .vms
+                .iter()
+                .filter(|vm| ids.contains(&vm.id))
+                .cloned()
+                .partition(|vm| app.running_vms.contains_key(&vm.id));
+            let skipped = running
+                .iter()
+                .map(|vm| (vm.display_name(), "running".to_string()))
+                .collect();
+            app.stale_vms_marked.clear();
+            app.start_trashing_vms(vms, skipped);
+        }
         ConfirmAction::DiscardScriptChanges => {
             // Discard changes and exit editor
             app.raw_script_scroll = 0;
             app.script_editor_lines.clear();
             app.script_editor_modified = false;
+            app.script_editor_hook = None;
             app.pop_screen(); // Close confirm dialog
             app.pop_screen(); // Close editor
         }
@@ -335,6 +457,155 @@ fn execute_confirm_action(app: &mut App, action: ConfirmAction) -> Result<()> {
                 }
             }
         }
+        ConfirmAction::SuspendVm => {
+            app.pop_screen();
+            if let Some(vm) = app.selected_vm().cloned() {
+                if app.running_vms.contains_key(&vm.id) {
+                    let vm_name = vm.display_name();
+                    let tx = app.background_tx.clone();
+                    app.start_progress(format!("Suspending {}", vm_name));
+                    thread::spawn(move || {
+                        let result = crate::vm::suspend_vm(&vm.path);
+                        let _ = tx.send(BackgroundResult::VmSuspended {
+                            vm_name,
+                            error: result.err().map(|e| format!("{:#}", e)),
+                        });
+                    });
+                } else {
+                    app.set_status(format!("{} is not running", vm.display_name()));
+                }
+            }
+        }
+        ConfirmAction::UsbHotAdd { index, .. } => {
+            app.pop_screen();
+            usb_hot_add(app, index);
+        }
+        ConfirmAction::PassHostInput { index, hot_add } => {
+            app.pop_screen();
+            if hot_add {
+                confirm_usb_hot_add(app, index);
+            } else {
+                app.toggle_usb_device(index);
+            }
+        }
+        ConfirmAction::CompactDisk { .. } => {
+            app.pop_screen();
+            if let Some(vm) = app.selected_vm().cloned() {
+                if app.running_vms.contains_key(&vm.id) {
+                    app.set_status("Stop the VM before compacting its disk");
+                } else {
+                    let vm_name = vm.display_name();
+                    let disks: Vec<std::path::PathBuf> = vm
+                        .config
+                        .disks
+                        .iter()
+                        .filter(|d| d.format.supports_snapshots())
+                        .map(|d| d.path.clone())
+                        .collect();
+                    let tx = app.background_tx.clone();
+                    app.start_progress(format!("Compacting {}", vm_name));
+                    thread::spawn(move || {
+                        let (mut before, mut after) = (0, 0);
+                        let mut error = None;
+                        for (i, disk) in disks.iter().enumerate() {
+                            let label = format!("Compacting disk {}/{}", i + 1, disks.len());
+                            let result = crate::commands::qemu_img::compact_disk(disk, |percent| {
+                                let _ = tx.send(BackgroundResult::Progress {
+                                    label: label.clone(),
+                                    percent,
+                                });
+                            });
+                            match result {
+                                Ok((b, a)) => {
+                                    before += b;
+                                    after += a;
+                                }
+                                Err(e) => {
+                                    error = Some(format!("{}: {:#}", disk.display(), e));
+                                    break;
+                                }
+                            }
+                        }
+                        let _ = tx.send(BackgroundResult::DiskCompacted {
+                            vm_name,
+                            before,
+                            after,
+                            error,
+                        });
+                    });
+                }
+            }
+        }
+        ConfirmAction::ResetAsciiArt => {
+            app.pop_screen();
+            match app.set_selected_vm_ascii(None) {
+                Ok(()) => app.set_status("Showing the OS logo again"),
+                Err(e) => app.set_status(format!("Failed to remove ASCII art: {}", e)),
+            }
+        }
+        ConfirmAction::DeleteIso { path, .. } => {
+            app.pop_screen();
+            screens::iso_library::delete_iso(app, &path);
+        }
+        ConfirmAction::DeleteTemplate(name) => {
+            app.pop_screen();
+            match app.vm_templates.remove(&name) {
+                Ok(()) => {
+                    if let Some(ref mut state) = app.wizard_state {
+                        state.os_list_selected = state.os_list_selected.saturating_sub(1);
+                        if state.template.as_deref() == Some(name.as_str()) {
+                            state.template = None;
+                        }
+                    }
+                    app.set_status(format!("Deleted template '{}'", name));
+                }
+                Err(e) => app.set_status(format!("Failed to delete template: {}", e)),
+            }
+        }
+        ConfirmAction::DeleteHook(hook) => {
+            app.pop_screen();
+            if let Some(vm) = app.selected_vm() {
+                match std::fs::remove_file(vm.path.join(hook)) {
+                    Ok(()) => app.set_status(format!("Deleted {}", hook)),
+                    Err(e) => app.set_status(format!("Error deleting {}: {}", hook, e)),
+                }
+            }
+        }
+        ConfirmAction::AddSerialConsole => {
+            app.pop_screen();
+            match app.add_serial_console() {
+                Ok(()) => {
+                    app.set_status("Serial console added; it is available from the next boot")
+                }
+                Err(e) => app.show_error(format!("Failed to add a serial console\n\n{:#}", e)),
+            }
+        }
+        ConfirmAction::FinishInstall(id) => {
+            app.pop_screen();
+            match app.set_first_boot_done(&id, true) {
+                Ok(()) => app.set_status(
+                    "Switched to disk boot; the install ISO stays attached but won't boot",
+                ),
+                Err(e) => app.set_status(format!("Failed to switch to disk boot: {}", e)),
+            }
+        }
+        ConfirmAction::ResetNvram => {
+            app.pop_screen();
+            screens::uefi_nvram::reset(app);
+        }
+        ConfirmAction::SetSecureBoot(enabled) => {
+            app.pop_screen();
+            screens::uefi_nvram::set_secure_boot(app, enabled);
+        }
+        ConfirmAction::DiscardSuspendState => {
+            app.pop_screen();
+            if let Some(vm) = app.selected_vm() {
+                match crate::vm::discard_suspend_state(&vm.path) {
+                    Ok(()) => app.set_status("Suspended state discarded"),
+                    Err(e) => app.set_status(format!("Error: {}", e)),
+                }
+            }
+        }
         ConfirmAction::ForceStopVm => {
             app.pop_screen();
             if let Some(vm) = app.selected_vm().cloned() {
@@ -359,7 +630,324 @@ fn execute_confirm_action(app: &mut App, action: ConfirmAction) -> Result<()> {
             // Primary action (Enter/click) is Save-and-exit.
             confirm_save_and_exit(app, kind);
         }
+        ConfirmAction::QuitWithRunningVms => {
+            // Primary action (Enter) keeps the old behavior: quit, leave VMs running.
+            app.pop_screen();
+            app.quit_confirmed = true;
+            app.should_quit = true;
+        }
+        ConfirmAction::ResetSettings => {
+            // Primary action (Enter) keeps the VM library path
+            app.pop_screen();
+            screens::settings::reset_all_settings(app, false)?;
+        }
+    }
+    Ok(())
+}
+
+/// Launch, or first offer the TCG fallback when the VM wants KVM and the
+/// host can't provide it.
+fn check_kvm_and_launch(app: &mut App, vm: &crate::vm::DiscoveredVm) {
+    if let Some(reason) = crate::app::kvm_unavailable_for(vm) {
+        app.push_screen(Screen::Confirm(ConfirmAction::KvmUnavailable(reason)));
+    } else {
+        launch_selected_vm(app, false);
+    }
+}
+
+/// How long to wait for the sudo password before giving up on a launch
+const SUDO_PROMPT_TIMEOUT: Duration = Duration::from_secs(120);
+
+/// Launch the selected VM, optionally with the one-shot TCG fallback.
+/// Passthrough VMs whose VFIO bind step would prompt for sudo are deferred to
+/// the event loop, which asks for the password outside the TUI first.
+fn launch_selected_vm(app: &mut App, force_tcg: bool) {
+    let Some(vm) = app.selected_vm() else {
+        return;
+    };
+    match crate::vm::vfio_auth_for_launch(vm) {
+        crate::vm::VfioAuth::NotNeeded => start_selected_vm(app, force_tcg),
+        crate::vm::VfioAuth::PolkitAgent => {
+            let vm_name = vm.display_name();
+            start_selected_vm(app, force_tcg);
+            if app.screen != Screen::ErrorDialog {
+                app.set_status(format!(
+                    "Launched: {} - approve the polkit prompt to bind its PCI devices",
+                    vm_name
+                ));
+            }
+        }
+        crate::vm::VfioAuth::SudoPrompt => app.pending_sudo_launch = Some(force_tcg),
+        crate::vm::VfioAuth::Unavailable => app.show_error(format!(
+            "Cannot launch {}\n\n\
+             Binding its PCI devices to vfio-pci needs root, but there is no \
+             polkit agent or sudo to ask for a password. Install sudo or a \
+             polkit agent, or bind the devices to vfio-pci at boot.",
+            vm.display_name()
+        )),
+    }
+}
+
+/// Leave the TUI so sudo can prompt on the terminal, then launch the selected
+/// VM with the cached credentials. The TUI is restored by [`SuspendedTui`]
+/// even if the prompt fails.
+fn launch_after_sudo_prompt(
+    terminal: &mut Terminal<CrosstermBackend<Stdout>>,
+    app: &mut App,
+    force_tcg: bool,
+) -> Result<()> {
+    let vm_name = app
+        .selected_vm()
+        .map(|vm| vm.display_name())
+        .unwrap_or_default();
+
+    let auth = {
+        let _suspended = SuspendedTui::enter()?;
+        println!(
+            "{} passes PCI devices through; binding them to vfio-pci needs root.",
+            vm_name
+        );
+        println!(
+            "(Giving up after {}s without an answer.)",
+            SUDO_PROMPT_TIMEOUT.as_secs()
+        );
+        crate::vm::lifecycle::authenticate_sudo(SUDO_PROMPT_TIMEOUT)
+    };
+    terminal.clear()?;
+
+    match auth {
+        Ok(()) => start_selected_vm(app, force_tcg),
+        Err(e) => app.show_error(format!("Did not launch {}\n\n{:#}", vm_name, e)),
+    }
+    Ok(())
+}
+
+/// Leave the TUI and relay the terminal to the selected VM's serial console
+/// until the user detaches, then restore the TUI.
+fn attach_serial_console(
+    terminal: &mut Terminal<CrosstermBackend<Stdout>>,
+    app: &mut App,
+) -> Result<()> {
+    let Some(vm) = app.selected_vm().cloned() else {
+        return Ok(());
+    };
+    let Some(socket) = vm.config.serial_socket.as_deref() else {
+        return Ok(());
+    };
+    let stream = match crate::vm::lifecycle::connect_serial_console(socket) {
+        Ok(stream) => stream,
+        Err(e) => {
+            app.show_error(format!("Cannot attach to {}\n\n{:#}", vm.display_name(), e));
+            return Ok(());
+        }
+    };
+
+    let result = {
+        let _suspended = SuspendedTui::enter()?;
+        println!(
+            "Connected to the serial console of {}. Press Ctrl+] to detach.",
+            vm.display_name()
+        );
+        println!("(Press Enter if no prompt appears.)");
+        crossterm::terminal::enable_raw_mode()?;
+        crate::vm::lifecycle::run_serial_console(stream)
+    };
+    terminal.clear()?;
+
+    match result {
+        Ok(()) => app.set_status(format!("Detached from {}", vm.display_name())),
+        Err(e) => app.show_error(format!(
+            "Console of {} failed\n\n{:#}",
+            vm.display_name(),
+            e
+        )),
+    }
+    Ok(())
+}
+
+/// ssh into the selected VM as `user`, handing it the terminal until the
+/// session ends
+fn connect_ssh(
+    terminal: &mut Terminal<CrosstermBackend<Stdout>>,
+    app: &mut App,
+    user: &str,
+) -> Result<()> {
+    let Some(vm) = app.selected_vm().cloned() else {
+        return Ok(());
+    };
+    let Some(network) = vm.config.network.as_ref() else {
+        return Ok(());
+    };
+    // Bridged guests are found through the guest agent
+    let addresses = if crate::vm::ssh::ssh_forward_port(network).is_none() {
+        crate::vm::lifecycle::guest_agent_addresses(&vm.path).unwrap_or_default()
+    } else {
+        Vec::new()
+    };
+    let Some(target) = crate::vm::ssh::ssh_target(network, &addresses) else {
+        app.show_error(format!(
+            "No address known for {}\n\nA bridged guest needs the QEMU guest agent running so its IP can be looked up.",
+            vm.display_name()
+        ));
+        return Ok(());
+    };
+
+    let status = {
+        let _suspended = SuspendedTui::enter()?;
+        println!(
+            "Connecting to {} at {} as {}...",
+            vm.display_name(),
+            target,
+            user
+        );
+        std::process::Command::new("ssh")
+            .args(target.ssh_args(user, &vm.id))
+            .status()
+    };
+    terminal.clear()?;
+
+    match status {
+        Ok(status) if status.code() == Some(255) => app.show_error(format!(
+            "ssh could not connect to {} at {}\n\nCheck that the guest's SSH server is running.",
+            vm.display_name(),
+            target
+        )),
+        Ok(_) => app.set_status(format!("SSH session to {} ended", vm.display_name())),
+        Err(e) => app.show_error(format!("Failed to run ssh\n\n{}", e)),
+    }
+    Ok(())
+}
+
+/// The TUI stepped out of its alternate screen and raw mode so a child
+/// process can use the terminal. Dropping it switches back.
+struct SuspendedTui;
+
+impl SuspendedTui {
+    fn enter() -> Result<Self> {
+        crossterm::terminal::disable_raw_mode()?;
+        crossterm::execute!(
+            std::io::stdout(),
+            crossterm::terminal::LeaveAlternateScreen,
+            crossterm::event::DisableMouseCapture,
+            crossterm::cursor::Show
+        )?;
+        Ok(SuspendedTui)
+    }
+}
+
+impl Drop for SuspendedTui {
+    fn drop(&mut self) {
+        // Best effort, like the TerminalGuard in main.rs
+        let _ = crossterm::terminal::enable_raw_mode();
+        let _ = crossterm::execute!(
+            std::io::stdout(),
+            crossterm::terminal::EnterAlternateScreen,
+            crossterm::event::EnableMouseCapture
+        );
+    }
+}
+
+/// Launch the selected VM right away, optionally with the one-shot TCG fallback.
+fn start_selected_vm(app: &mut App, force_tcg: bool) {
+    let Some(vm) = app.selected_vm().cloned() else {
+        return;
+    };
+    let mut options = app.get_launch_options();
+    options.force_tcg = force_tcg;
+    // A normal boot picks up a suspended state rather than leaving it stale
+    options.resume_suspended =
+        matches!(options.boot_mode, BootMode::Normal) && crate::vm::has_suspend_state(&vm.path);
+
+    let shmem = match crate::app::prepare_launch(&vm, app.config.default_ivshmem_size_mb) {
+        Ok(shmem) => shmem,
+        Err(e) => {
+            app.show_error(format!("Cannot launch {}\n\n{:#}", vm.display_name(), e));
+            return;
+        }
+    };
+
+    let mut result = launch_vm_with_error_check(&vm, &options);
+    crate::app::watch_launch(
+        &mut result,
+        &app.background_tx,
+        app.config.vm_start_timeout_secs,
+    );
+
+    if result.success {
+        app.mark_vm_launched(&vm.id);
+        // Ask about switching to disk boot once the install run ends, and
+        // don't carry install mode over to the next launch
+        if matches!(options.boot_mode, BootMode::Install) {
+            app.install_launches.insert(vm.id.clone());
+            app.boot_mode = BootMode::Normal;
+        }
+        // An ephemeral boot is a one-off; the next launch keeps changes again
+        let ephemeral = options.boot_mode == BootMode::Ephemeral;
+        if ephemeral {
+            app.boot_mode = BootMode::Normal;
+        }
+        if let (Some(path), true) = (&shmem, app.config.looking_glass_auto_launch) {
+            if let Err(e) = start_looking_glass_client(app, &vm.id, path) {
+                app.set_status(format!(
+                    "Launched: {} (Looking Glass not started: {:#})",
+                    result.vm_name, e
+                ));
+                return;
+            }
+        }
+        if options.resume_suspended {
+            let vm_path = vm.path.clone();
+            thread::spawn(move || {
+                if let Err(e) = crate::vm::finish_resume(&vm_path) {
+                    log::warn!("resume of {}: {e}", vm_path.display());
+                }
+            });
+            app.set_status(format!("Resumed: {}", result.vm_name));
+        } else if force_tcg {
+            app.set_status(format!(
+                "Launched: {} (TCG, no KVM - expect it to be slow)",
+                result.vm_name
+            ));
+        } else if ephemeral {
+            app.set_status(format!(
+                "Launched: {} (ephemeral - disk changes are discarded at shutdown)",
+                result.vm_name
+            ));
+        } else if let Some(crate::vm::lifecycle::SpiceEndpoint::Tcp(port)) = result.spice {
+            app.set_status(format!(
+                "Launched: {} (SPICE on TCP port {})",
+                result.vm_name, port
+            ));
+        } else {
+            app.set_status(format!("Launched: {}", result.vm_name));
+        }
+    } else {
+        // Show error in the error dialog for better visibility
+        let error_msg = result.error.unwrap_or_else(|| "Unknown error".to_string());
+        app.show_error(format!(
+            "Failed to launch {}\n\n{}",
+            result.vm_name, error_msg
+        ));
     }
+}
+
+/// Start the Looking Glass client for a freshly launched VM. It is closed
+/// again once the VM stops (see `App::check_vm_status`).
+fn start_looking_glass_client(
+    app: &mut App,
+    vm_id: &str,
+    shmem: &std::path::Path,
+) -> anyhow::Result<()> {
+    let client =
+        LookingGlassConfig::validate_client(app.config.looking_glass_client_path.as_deref())?;
+    let child = LookingGlassConfig::spawn_client(&client, shmem)?;
+    app.looking_glass_clients.insert(
+        vm_id.to_string(),
+        crate::app::LookingGlassClient {
+            child,
+            seen_running: false,
+        },
+    );
     Ok(())
 }
 
@@ -414,6 +1002,11 @@ fn render(app: &App, frame: &mut Frame) {
             render_dim_overlay(frame);
             screens::management::render_display_options(app, frame);
         }
+        Screen::CpuFlags => {
+            screens::main_menu::render(app, frame);
+            render_dim_overlay(frame);
+            screens::management::render_cpu_flags(app, frame);
+        }
         Screen::UsbDevices => {
             screens::main_menu::render(app, frame);
             render_dim_overlay(frame);
@@ -439,6 +1032,11 @@ fn render(app: &App, frame: &mut Frame) {
             render_dim_overlay(frame);
             screens::single_gpu_setup::render_instructions(app, frame);
         }
+        Screen::PassthroughDiagnostics => {
+            screens::main_menu::render(app, frame);
+            render_dim_overlay(frame);
+            screens::pci_passthrough::render_diagnostics(app, frame);
+        }
         Screen::MultiGpuSetup => {
             screens::main_menu::render(app, frame);
             render_dim_overlay(frame);
@@ -504,6 +1102,79 @@ fn render(app: &App, frame: &mut Frame) {
             render_dim_overlay(frame);
             screens::import_wizard::render(app, frame);
         }
+        Screen::LaunchHooks => {
+            screens::main_menu::render(app, frame);
+            render_dim_overlay(frame);
+            screens::launch_hooks::render(app, frame);
+        }
+        Screen::RegenerateScript => {
+            screens::main_menu::render(app, frame);
+            render_dim_overlay(frame);
+            screens::regenerate_script::render(app, frame);
+        }
+        Screen::DirectKernelBoot => {
+            screens::main_menu::render(app, frame);
+            render_dim_overlay(frame);
+            screens::direct_kernel_boot::render(app, frame);
+        }
+        Screen::RemovableMedia => {
+            screens::main_menu::render(app, frame);
+            render_dim_overlay(frame);
+            screens::removable_media::render(app, frame);
+        }
+        Screen::RtcSettings => {
+            screens::main_menu::render(app, frame);
+            render_dim_overlay(frame);
+            screens::rtc_settings::render(app, frame);
+        }
+        Screen::DiskThrottle => {
+            screens::main_menu::render(app, frame);
+            render_dim_overlay(frame);
+            screens::disk_throttle::render(app, frame);
+        }
+        Screen::UefiNvram => {
+            screens::main_menu::render(app, frame);
+            render_dim_overlay(frame);
+            screens::uefi_nvram::render(app, frame);
+        }
+        Screen::StaleVms => {
+            screens::main_menu::render(app, frame);
+            render_dim_overlay(frame);
+            screens::stale_vms::render(app, frame);
+        }
+        Screen::Trash => {
+            screens::main_menu::render(app, frame);
+            render_dim_overlay(frame);
+            screens::trash::render(app, frame);
+        }
+        Screen::IsoLibrary => {
+            screens::main_menu::render(app, frame);
+            render_dim_overlay(frame);
+            screens::iso_library::render(app, frame);
+        }
+        Screen::HostInfo => {
+            screens::main_menu::render(app, frame);
+            render_dim_overlay(frame);
+            screens::settings::render(app, frame);
+            screens::host_info::render(app, frame);
+        }
+        Screen::LibrarySetup => {
+            screens::main_menu::render(app, frame);
+            render_dim_overlay(frame);
+            screens::library_setup::render(app, frame);
+        }
+    }
+
+    // Long background operations draw on top of whatever screen started them
+    if app.loading {
+        if let Some(ref progress) = app.progress {
+            let widget = ProgressWidget {
+                label: &progress.label,
+                percent: progress.percent,
+                elapsed_ms: progress.started.elapsed().as_millis(),
+            };
+            widget.render(frame.area(), frame.buffer_mut());
+        }
     }
 }
 
@@ -511,6 +1182,10 @@ fn render(app: &App, frame: &mut Frame) {
 fn handle_key(app: &mut App, key: KeyEvent) -> Result<()> {
     // Global quit with Ctrl+C
     if key.code == KeyCode::Char('c') && key.modifiers.contains(KeyModifiers::CONTROL) {
+        // A second Ctrl+C at the running-VMs prompt quits without asking again
+        if app.screen == Screen::Confirm(ConfirmAction::QuitWithRunningVms) {
+            app.quit_confirmed = true;
+        }
         app.should_quit = true;
         return Ok(());
     }
@@ -542,6 +1217,7 @@ fn handle_key(app: &mut App, key: KeyEvent) -> Result<()> {
         Screen::DetailedInfo => handle_detailed_info(app, key)?,
         Screen::Snapshots => handle_snapshots(app, key)?,
         Screen::BootOptions => handle_boot_options(app, key)?,
+        Screen::CpuFlags => handle_cpu_flags(app, key)?,
         Screen::DisplayOptions => handle_display_options(app, key)?,
         Screen::UsbDevices => handle_usb_devices(app, key)?,
         Screen::PciPassthrough => screens::pci_passthrough::handle_key(app, key)?,
@@ -549,6 +1225,9 @@ fn handle_key(app: &mut App, key: KeyEvent) -> Result<()> {
         Screen::SingleGpuSetup => screens::single_gpu_setup::handle_key(app, key)?,
         Screen::SingleGpuInstructions => handle_single_gpu_instructions(app, key)?,
         Screen::MultiGpuSetup => screens::multi_gpu_setup::handle_input(app, key)?,
+        Screen::PassthroughDiagnostics => {
+            screens::pci_passthrough::handle_diagnostics_key(app, key)?
+        }
         Screen::Confirm(action) => handle_confirm(app, action.clone(), key)?,
         Screen::Help => handle_help(app, key)?,
         Screen::Search => handle_search(app, key)?,
@@ -563,13 +1242,59 @@ fn handle_key(app: &mut App, key: KeyEvent) -> Result<()> {
             screens::settings::handle_input(app, key)?;
         }
         Screen::ImportWizard => screens::import_wizard::handle_key(app, key)?,
+        Screen::LaunchHooks => screens::launch_hooks::handle_key(app, key)?,
+        Screen::RegenerateScript => screens::regenerate_script::handle_key(app, key)?,
+        Screen::DirectKernelBoot => screens::direct_kernel_boot::handle_key(app, key)?,
+        Screen::RemovableMedia => screens::removable_media::handle_key(app, key)?,
+        Screen::RtcSettings => screens::rtc_settings::handle_key(app, key)?,
+        Screen::DiskThrottle => screens::disk_throttle::handle_key(app, key)?,
+        Screen::UefiNvram => screens::uefi_nvram::handle_key(app, key)?,
+        Screen::StaleVms => screens::stale_vms::handle_key(app, key)?,
+        Screen::Trash => screens::trash::handle_key(app, key)?,
+        Screen::IsoLibrary => screens::iso_library::handle_key(app, key)?,
+        Screen::HostInfo => screens::host_info::handle_key(app, key)?,
+        Screen::LibrarySetup => screens::library_setup::handle_key(app, key)?,
     }
 
     Ok(())
 }
 
+/// Bulk mode keys on the main menu; returns false for keys it leaves to
+/// the normal handler
+fn handle_bulk_mode_key(app: &mut App, key: KeyEvent) -> bool {
+    let batch = match key.code {
+        KeyCode::Char('v') | KeyCode::Esc => {
+            app.toggle_bulk_mode();
+            return true;
+        }
+        KeyCode::Char(' ') => {
+            app.toggle_vm_mark();
+            return true;
+        }
+        KeyCode::Char('a') => {
+            app.toggle_mark_all_vms();
+            return true;
+        }
+        KeyCode::Enter => BatchAction::Start,
+        KeyCode::Char('x') => BatchAction::Stop,
+        KeyCode::Char('n') => BatchAction::Snapshot,
+        KeyCode::Char('D') | KeyCode::Delete => BatchAction::Delete,
+        _ => return false,
+    };
+    if app.marked_vms.is_empty() {
+        app.set_status("Mark VMs with [Space] first");
+    } else {
+        app.push_screen(Screen::Confirm(ConfirmAction::Batch(batch)));
+    }
+    true
+}
+
 fn handle_main_menu(app: &mut App, key: KeyEvent) -> Result<()> {
+    if app.bulk_mode && handle_bulk_mode_key(app, key) {
+        return Ok(());
+    }
     match key.code {
+        KeyCode::Char('v') | KeyCode::Char('V') => app.toggle_bulk_mode(),
         KeyCode::Char('j') | KeyCode::Down => app.select_next(),
         KeyCode::Char('k') | KeyCode::Up => app.select_prev(),
         KeyCode::PageDown => {
@@ -604,9 +1329,22 @@ fn handle_main_menu(app: &mut App, key: KeyEvent) -> Result<()> {
         KeyCode::Char('i') | KeyCode::Char('I') => {
             app.start_import_wizard();
         }
+        KeyCode::Char('d') | KeyCode::Char('D') => {
+            if app.selected_vm().is_some() {
+                app.refresh_selected_disk_usage();
+                app.refresh_guest_addresses();
+                app.info_scroll = 0;
+                app.push_screen(Screen::DetailedInfo);
+            }
+        }
         KeyCode::Char('s') | KeyCode::Char('S') => {
             app.push_screen(Screen::Settings);
         }
+        KeyCode::Char('o') | KeyCode::Char('O') => app.cycle_vm_list_sort(),
+        KeyCode::Char('r') | KeyCode::Char('R') | KeyCode::F(5) => app.start_vm_refresh(),
+        KeyCode::Char('h') | KeyCode::Char('H') => screens::stale_vms::open(app),
+        KeyCode::Char('t') | KeyCode::Char('T') => screens::trash::open(app),
+        KeyCode::Char('l') | KeyCode::Char('L') => screens::iso_library::open(app),
         KeyCode::Char('x') | KeyCode::Char('X') => {
             if let Some(vm) = app.selected_vm().cloned() {
                 if app.selected_vm_pid().is_some() {
@@ -692,33 +1430,164 @@ fn handle_management(app: &mut App, key: KeyEvent) -> Result<()> {
                                 }
                             }
                         }
+                        MenuAction::TestLaunch => {
+                            if let Some(vm) = app.selected_vm().cloned() {
+                                if app.selected_vm_pid().is_some() {
+                                    app.set_status("Stop the VM before a test launch");
+                                } else {
+                                    let vm_name = vm.display_name();
+                                    let tx = app.background_tx.clone();
+                                    app.start_progress(format!("Test launching {}", vm_name));
+                                    thread::spawn(move || {
+                                        let result = crate::vm::test_launch(
+                                            &vm,
+                                            crate::vm::lifecycle::TEST_LAUNCH_TIMEOUT,
+                                        )
+                                        .map_err(|e| format!("{:#}", e));
+                                        let _ = tx.send(BackgroundResult::TestLaunched {
+                                            vm_name,
+                                            result,
+                                        });
+                                    });
+                                }
+                            }
+                        }
+                        MenuAction::BugReport => {
+                            if let Some(vm) = app.selected_vm().cloned() {
+                                let vm_name = vm.display_name();
+                                let ovmf_paths = app.config.ovmf_override();
+                                let redaction = crate::vm::report::Redaction {
+                                    home: app
+                                        .config
+                                        .report_redact_paths
+                                        .then(dirs::home_dir)
+                                        .flatten(),
+                                    secrets: app.config.report_redact_secrets,
+                                };
+                                let dir = crate::config::Config::config_file_path()
+                                    .parent()
+                                    .map(|d| d.join("reports"))
+                                    .unwrap_or_else(|| vm.path.clone());
+                                let tx = app.background_tx.clone();
+                                app.start_progress(format!(
+                                    "Generating bug report for {}",
+                                    vm_name
+                                ));
+                                thread::spawn(move || {
+                                    let host =
+                                        crate::hardware::HostInfo::detect(ovmf_paths.as_ref());
+                                    let report = crate::vm::report::bug_report(
+                                        &vm,
+                                        &host.to_text(),
+                                        &redaction,
+                                    );
+                                    let result =
+                                        crate::vm::report::save_bug_report(&dir, &vm.id, &report)
+                                            .map(|path| {
+                                                (path, clipboard::copy_to_clipboard(&report))
+                                            })
+                                            .map_err(|e| format!("{:#}", e));
+                                    let _ = tx
+                                        .send(BackgroundResult::BugReportSaved { vm_name, result });
+                                });
+                            }
+                        }
+                        MenuAction::SuspendVm => {
+                            if app
+                                .selected_vm_pid()
+                                .is_some_and(crate::vm::lifecycle::is_ephemeral_process)
+                            {
+                                app.set_status(
+                                    "Ephemeral VMs can't be suspended: their disk changes \
+                                     are discarded at shutdown",
+                                );
+                            } else if app.selected_vm_pid().is_some() {
+                                app.push_screen(Screen::Confirm(ConfirmAction::SuspendVm));
+                            } else {
+                                app.set_status("VM is not running");
+                            }
+                        }
+                        MenuAction::ResumeVm => {
+                            if app.selected_vm_pid().is_some() {
+                                app.set_status("VM is already running");
+                            } else {
+                                app.boot_mode = BootMode::Normal;
+                                app.push_screen(Screen::Confirm(ConfirmAction::LaunchVm));
+                            }
+                        }
+                        MenuAction::DiscardSuspendState => {
+                            app.push_screen(Screen::Confirm(ConfirmAction::DiscardSuspendState));
+                        }
+                        MenuAction::BalloonTarget => {
+                            if let Some(vm) = app.selected_vm().cloned() {
+                                if app.selected_vm_pid().is_some() {
+                                    app.text_input_buffer = app
+                                        .balloon_vms
+                                        .get(&vm.id)
+                                        .map(|mb| mb.to_string())
+                                        .unwrap_or_default();
+                                    app.push_screen(Screen::TextInput(
+                                        TextInputContext::BalloonTarget,
+                                    ));
+                                } else {
+                                    app.set_status("VM is not running");
+                                }
+                            }
+                        }
+                        MenuAction::CompactDisk => {
+                            if let Some(vm) = app.selected_vm().cloned() {
+                                if app.selected_vm_pid().is_some() {
+                                    app.set_status("Stop the VM before compacting its disk");
+                                } else {
+                                    // Only a plain convert loses internal snapshots
+                                    let snapshots =
+                                        if crate::commands::qemu_img::has_virt_sparsify() {
+                                            0
+                                        } else {
+                                            vm.config
+                                                .disks
+                                                .iter()
+                                                .filter(|d| d.format.supports_snapshots())
+                                                .filter_map(|d| {
+                                                    crate::vm::list_snapshots(&d.path).ok()
+                                                })
+                                                .map(|s| s.len())
+                                                .sum()
+                                        };
+                                    app.push_screen(Screen::Confirm(ConfirmAction::CompactDisk {
+                                        snapshots,
+                                    }));
+                                }
+                            }
+                        }
                         MenuAction::BootOptions => {
                             app.selected_menu_item = 0;
+                            app.boot_order = app
+                                .selected_vm()
+                                .map(crate::vm::load_boot_order)
+                                .unwrap_or_default();
+                            app.tools_iso = app.selected_vm().and_then(crate::vm::load_tools_iso);
+                            app.floppy_drive =
+                                app.selected_vm().and_then(crate::vm::load_floppy_drive);
                             app.push_screen(Screen::BootOptions);
                         }
+                        MenuAction::DirectKernelBoot => {
+                            app.selected_menu_item = 0;
+                            app.direct_kernel = app
+                                .selected_vm()
+                                .and_then(crate::vm::load_direct_kernel)
+                                .unwrap_or_default();
+                            app.push_screen(Screen::DirectKernelBoot);
+                        }
                         MenuAction::Snapshots => {
                             app.load_snapshots()?;
                             app.push_screen(Screen::Snapshots);
                         }
                         MenuAction::UsbPassthrough => {
                             app.load_usb_devices()?;
-                            // Load saved USB passthrough config and pre-select matching devices
-                            if let Some(vm) = app.selected_vm() {
-                                let saved = crate::vm::load_usb_passthrough(vm);
-                                app.selected_usb_devices.clear();
-                                for saved_dev in &saved {
-                                    // Find matching device by vendor/product ID
-                                    for (i, dev) in app.usb_devices.iter().enumerate() {
-                                        if dev.vendor_id == saved_dev.vendor_id
-                                            && dev.product_id == saved_dev.product_id
-                                        {
-                                            app.selected_usb_devices.push(i);
-                                            break;
-                                        }
-                                    }
-                                }
-                            }
+                            app.restore_usb_selections();
                             app.snapshot_usb_baseline();
+                            app.refresh_usb_attached();
                             app.selected_menu_item = 0;
                             app.push_screen(Screen::UsbDevices);
                         }
@@ -761,6 +1630,7 @@ fn handle_management(app: &mut App, key: KeyEvent) -> Result<()> {
                                 let port_forwards =
                                     net.map(|n| n.port_forwards.clone()).unwrap_or_default();
                                 let mac_address = net.and_then(|n| n.mac_address.clone());
+                                let shaping = net.map(|n| n.shaping).unwrap_or_default();
 
                                 app.network_settings_state =
                                     Some(crate::app::NetworkSettingsState {
@@ -775,6 +1645,9 @@ fn handle_management(app: &mut App, key: KeyEvent) -> Result<()> {
                                         editing_port_forwards: false,
                                         pf_selected: 0,
                                         adding_pf: None,
+                                        shaping,
+                                        shaping_edit_buffer: String::new(),
+                                        editing_shaping: false,
                                     });
                                 app.push_screen(Screen::NetworkSettings);
                             }
@@ -782,6 +1655,10 @@ fn handle_management(app: &mut App, key: KeyEvent) -> Result<()> {
                         MenuAction::MultiGpuPassthrough => {
                             app.load_pci_devices()?;
                             app.restore_pci_selections();
+                            app.cpu_config = app
+                                .selected_vm()
+                                .map(crate::vm::load_cpu_config)
+                                .unwrap_or_default();
                             app.push_screen(Screen::MultiGpuSetup);
                         }
                         MenuAction::SingleGpuPassthrough => {
@@ -810,20 +1687,146 @@ fn handle_management(app: &mut App, key: KeyEvent) -> Result<()> {
                                 }
                             }
                         }
+                        MenuAction::ToggleClipboard => {
+                            if let Some(vm) = app.selected_vm() {
+                                let script_path = vm.launch_script.clone();
+                                let enable = !vm.config.has_spice_agent();
+                                match toggle_vm_clipboard_sharing(&script_path, enable) {
+                                    Ok(()) => {
+                                        app.set_status(if enable {
+                                            "Clipboard sharing enabled (install spice-vdagent in the guest)"
+                                        } else {
+                                            "Clipboard sharing disabled"
+                                        });
+                                        app.reload_selected_vm_script();
+                                    }
+                                    Err(e) => app.set_status(format!(
+                                        "Failed to toggle clipboard sharing: {}",
+                                        e
+                                    )),
+                                }
+                            }
+                        }
+                        MenuAction::ToggleInstallMedia => {
+                            if let Some(vm) = app.selected_vm() {
+                                let (id, done) = (vm.id.clone(), vm.first_boot_done != Some(true));
+                                match app.set_first_boot_done(&id, done) {
+                                    Ok(()) => app.set_status(if done {
+                                        "Normal launches skip the install ISO"
+                                    } else {
+                                        "The install ISO can boot on normal launches again"
+                                    }),
+                                    Err(e) => app.set_status(format!(
+                                        "Failed to change install media boot: {}",
+                                        e
+                                    )),
+                                }
+                            }
+                        }
+                        MenuAction::AsciiArt => {
+                            if app
+                                .selected_vm()
+                                .is_some_and(|vm| vm.custom_ascii.is_some())
+                            {
+                                app.push_screen(Screen::Confirm(ConfirmAction::ResetAsciiArt));
+                            } else {
+                                app.open_file_browser(crate::app::FileBrowserMode::AsciiArt);
+                                app.push_screen(Screen::FileBrowser);
+                            }
+                        }
+                        MenuAction::ToggleAutostart => match app.toggle_selected_autostart() {
+                            Ok(true) => {
+                                app.set_status("Autostart on - starts when vm-curator opens")
+                            }
+                            Ok(false) => app.set_status("Autostart off"),
+                            Err(e) => app.set_status(format!("Failed to toggle autostart: {}", e)),
+                        },
                         MenuAction::ChangeDisplay => {
                             app.selected_menu_item = 0;
                             app.push_screen(Screen::DisplayOptions);
                         }
+                        MenuAction::CpuFlags => {
+                            app.selected_menu_item = 0;
+                            app.cpu_config = app
+                                .selected_vm()
+                                .map(crate::vm::load_cpu_config)
+                                .unwrap_or_default();
+                            app.push_screen(Screen::CpuFlags);
+                        }
+                        MenuAction::RtcSettings => {
+                            app.selected_menu_item = 0;
+                            app.push_screen(Screen::RtcSettings);
+                        }
+                        MenuAction::DiskThrottle => {
+                            app.selected_menu_item = 0;
+                            app.throttle_disk = 0;
+                            app.push_screen(Screen::DiskThrottle);
+                        }
+                        MenuAction::UefiNvram => {
+                            app.selected_menu_item = 0;
+                            app.push_screen(Screen::UefiNvram);
+                        }
+                        MenuAction::LaunchHooks => {
+                            app.launch_hooks_selected = 0;
+                            app.push_screen(Screen::LaunchHooks);
+                        }
+                        MenuAction::AttachConsole => {
+                            if app.selected_vm_pid().is_some() {
+                                app.pending_console_attach = true;
+                            } else {
+                                app.set_status("VM is not running");
+                            }
+                        }
+                        MenuAction::ConnectSsh => {
+                            if let Some(vm) = app.selected_vm().cloned() {
+                                if app.selected_vm_pid().is_some() {
+                                    app.text_input_buffer = crate::vm::ssh::load_ssh_user(&vm.path)
+                                        .or_else(|| std::env::var("USER").ok())
+                                        .unwrap_or_default();
+                                    app.push_screen(Screen::TextInput(TextInputContext::SshUser));
+                                } else {
+                                    app.set_status("VM is not running");
+                                }
+                            }
+                        }
+                        MenuAction::FloppyDrives => {
+                            screens::removable_media::open(app, crate::vm::MediaKind::Floppy);
+                        }
+                        MenuAction::ChangeCd => {
+                            screens::removable_media::open(app, crate::vm::MediaKind::Cdrom);
+                        }
+                        MenuAction::AddSerialConsole => {
+                            app.push_screen(Screen::Confirm(ConfirmAction::AddSerialConsole));
+                        }
                         MenuAction::EditNotes => {
                             app.load_notes_into_editor();
                             app.push_screen(Screen::EditNotes);
                         }
+                        MenuAction::WindowTitle => {
+                            if let Some(vm) = app.selected_vm() {
+                                app.text_input_buffer = crate::vm::load_window_title(vm)
+                                    .unwrap_or_else(|| vm.display_name());
+                                app.push_screen(Screen::TextInput(TextInputContext::WindowTitle));
+                            }
+                        }
                         MenuAction::RenameVm => {
                             if let Some(vm) = app.selected_vm() {
                                 app.text_input_buffer = vm.display_name();
                             }
+                            app.rename_duplicate_acknowledged = None;
                             app.push_screen(Screen::TextInput(TextInputContext::RenameVm));
                         }
+                        MenuAction::ExportLibvirtXml => {
+                            if let Some(vm) = app.selected_vm() {
+                                match crate::vm::export::export_libvirt_xml(vm) {
+                                    Ok(path) => app.set_status(format!(
+                                        "Exported libvirt XML: {}",
+                                        path.display()
+                                    )),
+                                    Err(e) => app.set_status(format!("Export failed: {}", e)),
+                                }
+                            }
+                        }
                         MenuAction::ResetVm => {
                             app.push_screen(Screen::Confirm(ConfirmAction::ResetVm));
                         }
@@ -834,6 +1837,9 @@ fn handle_management(app: &mut App, key: KeyEvent) -> Result<()> {
                             app.load_script_into_editor();
                             app.push_screen(Screen::RawScript);
                         }
+                        MenuAction::RegenerateScript => {
+                            screens::regenerate_script::open(app);
+                        }
                     }
                 }
             }
@@ -874,6 +1880,7 @@ fn handle_raw_script(app: &mut App, key: KeyEvent) -> Result<()> {
             } else {
                 app.raw_script_scroll = 0;
                 app.script_editor_lines.clear();
+                app.script_editor_hook = None;
                 app.pop_screen();
             }
         }
@@ -1280,8 +2287,13 @@ fn handle_edit_notes(app: &mut App, key: KeyEvent) -> Result<()> {
 }
 
 fn handle_detailed_info(app: &mut App, key: KeyEvent) -> Result<()> {
-    if key.code == KeyCode::Esc {
-        app.pop_screen();
+    match key.code {
+        KeyCode::Esc => app.pop_screen(),
+        KeyCode::Char('j') | KeyCode::Down => app.info_scroll = app.info_scroll.saturating_add(1),
+        KeyCode::Char('k') | KeyCode::Up => app.info_scroll = app.info_scroll.saturating_sub(1),
+        KeyCode::PageDown => app.info_scroll = app.info_scroll.saturating_add(5),
+        KeyCode::PageUp => app.info_scroll = app.info_scroll.saturating_sub(5),
+        _ => {}
     }
     Ok(())
 }
@@ -1310,8 +2322,11 @@ fn handle_snapshots(app: &mut App, key: KeyEvent) -> Result<()> {
                     app.set_status("Warning: VM is running. Snapshot may be inconsistent.");
                 }
                 // Pre-fill with timestamp-based suggestion
-                app.text_input_buffer =
-                    format!("snapshot-{}", chrono::Local::now().format("%Y%m%d-%H%M%S"));
+                app.text_input_buffer = format!(
+                    "{}-{}",
+                    app.config.snapshot_prefix,
+                    chrono::Local::now().format("%Y%m%d-%H%M%S")
+                );
                 app.push_screen(Screen::TextInput(TextInputContext::SnapshotName));
             }
         }
@@ -1322,6 +2337,28 @@ fn handle_snapshots(app: &mut App, key: KeyEvent) -> Result<()> {
                 )));
             }
         }
+        KeyCode::Char(' ') => {
+            if let Some(snap) = app.snapshots.get(app.selected_snapshot) {
+                let name = snap.name.clone();
+                if !app.marked_snapshots.remove(&name) {
+                    app.marked_snapshots.insert(name);
+                }
+                if app.selected_snapshot < app.snapshots.len().saturating_sub(1) {
+                    app.selected_snapshot += 1;
+                }
+            }
+        }
+        KeyCode::Char('i') => cycle_snapshot_schedule(app),
+        KeyCode::Char('d') if !app.marked_snapshots.is_empty() => {
+            // Keep list order so deletions (and their reports) run top to bottom
+            let names: Vec<String> = app
+                .snapshots
+                .iter()
+                .filter(|s| app.marked_snapshots.contains(&s.name))
+                .map(|s| s.name.clone())
+                .collect();
+            app.push_screen(Screen::Confirm(ConfirmAction::DeleteSnapshots(names)));
+        }
         KeyCode::Char('d') => {
             if let Some(snap) = app.snapshots.get(app.selected_snapshot) {
                 app.push_screen(Screen::Confirm(ConfirmAction::DeleteSnapshot(
@@ -1334,25 +2371,72 @@ fn handle_snapshots(app: &mut App, key: KeyEvent) -> Result<()> {
     Ok(())
 }
 
+/// Snapshot intervals offered by the Snapshots screen, in minutes (0 = off)
+const SNAPSHOT_INTERVALS: [u32; 6] = [0, 15, 30, 60, 120, 240];
+
+/// Step the selected VM's scheduled snapshot interval to the next option
+fn cycle_snapshot_schedule(app: &mut App) {
+    let Some(vm) = app.selected_vm() else {
+        return;
+    };
+    let (vm_id, vm_path) = (vm.id.clone(), vm.path.clone());
+    let current = app.snapshot_schedules.get(&vm_id).copied().unwrap_or(0);
+    let next = SNAPSHOT_INTERVALS
+        .iter()
+        .copied()
+        .find(|&m| m > current)
+        .unwrap_or(0);
+    let minutes = (next > 0).then_some(next);
+
+    if let Err(e) = crate::vm::snapshot::save_snapshot_schedule(&vm_path, minutes) {
+        app.set_status(format!("Failed to save snapshot schedule: {}", e));
+        return;
+    }
+    // Restart the countdown from now with the new interval
+    app.next_scheduled_snapshot.remove(&vm_id);
+    match minutes {
+        Some(m) => {
+            app.snapshot_schedules.insert(vm_id, m);
+            app.set_status(format!("Scheduled snapshots every {} min", m));
+        }
+        None => {
+            app.snapshot_schedules.remove(&vm_id);
+            app.set_status("Scheduled snapshots off");
+        }
+    }
+}
+
 fn handle_boot_options(app: &mut App, key: KeyEvent) -> Result<()> {
     use crate::app::FileBrowserMode;
 
     match key.code {
         KeyCode::Esc => app.pop_screen(),
-        KeyCode::Char('j') | KeyCode::Down => app.menu_next(5),
+        KeyCode::Char('j') | KeyCode::Down => {
+            app.menu_next(screens::management::boot_options_count(app))
+        }
         KeyCode::Char('k') | KeyCode::Up => app.menu_prev(),
+        KeyCode::Char('x') | KeyCode::Delete if app.selected_menu_item == 8 => {
+            if app.tools_iso.is_some() {
+                save_tools_iso_config(app, None);
+            }
+        }
+        KeyCode::Char('x') | KeyCode::Delete if app.selected_menu_item == 9 => {
+            if app.floppy_drive.is_some() {
+                save_floppy_drive_config(app, None);
+            }
+        }
         KeyCode::Enter
         | KeyCode::Char('1')
         | KeyCode::Char('2')
         | KeyCode::Char('3')
         | KeyCode::Char('4')
-        | KeyCode::Char('5') => {
+        | KeyCode::Char('5')
+        | KeyCode::Char('6')
+        | KeyCode::Char('7')
+        | KeyCode::Char('8')
+        | KeyCode::Char('9') => {
             let item = match key.code {
-                KeyCode::Char('1') => 0,
-                KeyCode::Char('2') => 1,
-                KeyCode::Char('3') => 2,
-                KeyCode::Char('4') => 3,
-                KeyCode::Char('5') => 4,
+                KeyCode::Char(c @ '1'..='9') => c as usize - '1' as usize,
                 _ => app.selected_menu_item,
             };
 
@@ -1363,24 +2447,60 @@ fn handle_boot_options(app: &mut App, key: KeyEvent) -> Result<()> {
                     app.push_screen(Screen::Confirm(ConfirmAction::LaunchVm));
                 }
                 1 => {
-                    app.boot_mode = BootMode::Install;
+                    app.boot_mode = BootMode::Ephemeral;
                     app.pop_screen();
                     app.push_screen(Screen::Confirm(ConfirmAction::LaunchVm));
                 }
                 2 => {
+                    app.boot_mode = BootMode::Install;
+                    app.pop_screen();
+                    app.push_screen(Screen::Confirm(ConfirmAction::LaunchVm));
+                }
+                3 => {
                     // Open file browser for ISO selection
-                    app.seed_iso_browser_dir();
-                    app.load_file_browser(FileBrowserMode::Iso);
+                    app.open_file_browser(FileBrowserMode::Iso);
                     app.push_screen(Screen::FileBrowser);
                 }
-                3 => {
+                4 => {
                     // Open file browser for recovery image (DMG) selection
-                    app.load_file_browser(FileBrowserMode::RecoveryImage);
+                    app.open_file_browser(FileBrowserMode::RecoveryImage);
                     app.push_screen(Screen::FileBrowser);
                 }
-                4 => {
+                5 => {
                     // Open file browser for floppy image selection
-                    app.load_file_browser(FileBrowserMode::Floppy);
+                    app.open_file_browser(FileBrowserMode::Floppy);
+                    app.push_screen(Screen::FileBrowser);
+                }
+                6 => {
+                    app.selected_menu_item = 6;
+                    let mut boot = app.boot_order.clone();
+                    boot.cycle_order();
+                    save_boot_order_config(app, boot);
+                }
+                7 => {
+                    app.selected_menu_item = 7;
+                    let mut boot = app.boot_order.clone();
+                    boot.menu = !boot.menu;
+                    save_boot_order_config(app, boot);
+                }
+                8 => {
+                    // Open file browser for the secondary (tools) CD-ROM
+                    app.selected_menu_item = 8;
+                    match app.tools_iso.as_ref().and_then(|p| p.parent()) {
+                        Some(dir) if dir.is_dir() => app.file_browser_dir = dir.to_path_buf(),
+                        _ => app.seed_file_browser_dir(FileBrowserMode::ToolsIso),
+                    }
+                    app.load_file_browser(FileBrowserMode::ToolsIso);
+                    app.push_screen(Screen::FileBrowser);
+                }
+                9 if screens::management::boot_options_count(app) > 9 => {
+                    // Open file browser for the floppy kept in drive B:
+                    app.selected_menu_item = 9;
+                    match app.floppy_drive.as_ref().and_then(|p| p.parent()) {
+                        Some(dir) if dir.is_dir() => app.file_browser_dir = dir.to_path_buf(),
+                        _ => app.seed_file_browser_dir(FileBrowserMode::FloppyDrive),
+                    }
+                    app.load_file_browser(FileBrowserMode::FloppyDrive);
                     app.push_screen(Screen::FileBrowser);
                 }
                 _ => {}
@@ -1391,6 +2511,140 @@ fn handle_boot_options(app: &mut App, key: KeyEvent) -> Result<()> {
     Ok(())
 }
 
+fn handle_cpu_flags(app: &mut App, key: KeyEvent) -> Result<()> {
+    use crate::vm::qemu_config::{CPU_FLAG_OPTIONS, CPU_PRESETS};
+
+    let row = app.selected_menu_item;
+    let preset_base = 1 + CPU_FLAG_OPTIONS.len();
+    match key.code {
+        KeyCode::Esc => {
+            // Return to the CPU entry in the management menu
+            app.selected_menu_item = app
+                .selected_vm()
+                .map(|vm| screens::management::get_menu_items(vm, &app.config))
+                .and_then(|items| {
+                    items
+                        .iter()
+                        .position(|i| i.action == screens::management::MenuAction::CpuFlags)
+                })
+                .unwrap_or(0);
+            app.pop_screen();
+        }
+        KeyCode::Char('j') | KeyCode::Down => {
+            app.menu_next(screens::management::cpu_flags_row_count())
+        }
+        KeyCode::Char('k') | KeyCode::Up => app.menu_prev(),
+        KeyCode::Left | KeyCode::Right if row == 0 => {
+            let mut cpu = app.cpu_config.clone();
+            cpu.cycle_model(if key.code == KeyCode::Right { 1 } else { -1 });
+            save_cpu_flags_config(app, cpu);
+        }
+        KeyCode::Enter | KeyCode::Char(' ') => {
+            use crate::commands::qemu_system::{kvm_unavailable_reason, nested_virt_support};
+
+            let mut cpu = app.cpu_config.clone();
+            let mut warning = None;
+            if row == 0 {
+                cpu.cycle_model(1);
+            } else if let Some((flag, _)) = CPU_FLAG_OPTIONS.get(row - 1) {
+                // Nested virtualization only works with KVM on the host
+                if matches!(*flag, "+vmx" | "+svm") && !cpu.has_flag(flag) {
+                    if let Some(reason) = kvm_unavailable_reason() {
+                        app.set_status(format!("Nested virtualization needs KVM: {}", reason));
+                        return Ok(());
+                    }
+                    warning = nested_virt_support().filter(|n| !n.enabled).map(|n| {
+                        format!(
+                            "Warning: nesting is off in {} on this host. Enable it with: {}",
+                            n.module,
+                            n.enable_hint()
+                        )
+                    });
+                }
+                cpu.toggle_flag(flag);
+            } else if let Some((_, value)) = CPU_PRESETS.get(row - preset_base) {
+                cpu = crate::vm::CpuConfig::parse(value);
+            }
+            save_cpu_flags_config(app, cpu);
+            if let Some(warning) = warning.filter(|_| app.cpu_config.nested_enabled()) {
+                app.set_status(warning);
+            }
+        }
+        _ => {}
+    }
+    Ok(())
+}
+
+/// Persist a CPU model/flags change to the selected VM's launch.sh.
+fn save_cpu_flags_config(app: &mut App, cpu: crate::vm::CpuConfig) {
+    let Some(vm) = app.selected_vm() else {
+        return;
+    };
+    match crate::vm::save_cpu_config(vm, &cpu) {
+        Ok(()) => {
+            app.set_status(format!("CPU: -cpu {}", cpu.to_arg()));
+            app.cpu_config = cpu;
+            app.reload_selected_vm_script();
+        }
+        Err(e) => app.set_status(format!("Failed to save CPU settings: {}", e)),
+    }
+}
+
+/// Persist a new boot order / boot menu setting to the selected VM's launch.sh.
+fn save_boot_order_config(app: &mut App, boot: crate::vm::BootOrder) {
+    let Some(vm) = app.selected_vm() else {
+        return;
+    };
+    match crate::vm::save_boot_order(vm, &boot) {
+        Ok(()) => {
+            app.set_status(format!(
+                "Boot order: {}, boot menu {}",
+                boot.order_label(),
+                if boot.menu { "on" } else { "off" }
+            ));
+            app.boot_order = boot;
+            app.reload_selected_vm_script();
+        }
+        Err(e) => app.set_status(format!("Failed to save boot order: {}", e)),
+    }
+}
+
+/// Attach or detach the secondary (tools) CD-ROM in the selected VM's launch.sh.
+fn save_tools_iso_config(app: &mut App, iso: Option<std::path::PathBuf>) {
+    let Some(vm) = app.selected_vm() else {
+        return;
+    };
+    match crate::vm::save_tools_iso(vm, iso.as_deref()) {
+        Ok(()) => {
+            match &iso {
+                Some(path) => app.set_status(format!("Tools ISO attached: {}", path.display())),
+                None => app.set_status("Tools ISO removed"),
+            }
+            app.tools_iso = iso;
+            app.reload_selected_vm_script();
+        }
+        Err(e) => app.set_status(format!("Failed to save tools ISO: {}", e)),
+    }
+}
+
+/// Insert or eject the floppy kept in drive B: in the selected VM's launch.sh.
+fn save_floppy_drive_config(app: &mut App, image: Option<std::path::PathBuf>) {
+    let Some(vm) = app.selected_vm() else {
+        return;
+    };
+    match crate::vm::save_floppy_drive(vm, image.as_deref()) {
+        Ok(()) => {
+            match &image {
+                Some(path) => app.set_status(format!("Floppy in drive B: {}", path.display())),
+                None => app.set_status("Floppy ejected from drive B:"),
+            }
+            app.floppy_drive = image;
+            app.reload_selected_vm_script();
+        }
+        Err(e) => app.set_status(format!("Failed to save floppy drive: {}", e)),
+    }
+}
+
 fn handle_display_options(app: &mut App, key: KeyEvent) -> Result<()> {
     let display_options = screens::management::get_display_options(app);
     let option_count = display_options.len();
@@ -1402,6 +2656,12 @@ fn handle_display_options(app: &mut App, key: KeyEvent) -> Result<()> {
         }
         KeyCode::Char('j') | KeyCode::Down => app.menu_next(option_count),
         KeyCode::Char('k') | KeyCode::Up => app.menu_prev(),
+        KeyCode::Char('z') | KeyCode::Char('f') | KeyCode::Char('r') | KeyCode::Char('m') => {
+            edit_display_window(app, key.code);
+        }
+        KeyCode::Char('l') | KeyCode::Char('L') => {
+            edit_keymap(app, key.code == KeyCode::Char('L'));
+        }
         KeyCode::Enter
         | KeyCode::Char('1')
         | KeyCode::Char('2')
@@ -1450,6 +2710,86 @@ fn handle_display_options(app: &mut App, key: KeyEvent) -> Result<()> {
     Ok(())
 }
 
+/// Toggle zoom-to-fit (`z`) or full screen (`f`), or step the guest
+/// resolution (`r`) or monitor count (`m`), from the display options screen. Keys for options the
+/// VM's display backend doesn't support do nothing.
+fn edit_display_window(app: &mut App, key: KeyCode) {
+    use crate::vm::DisplayWindow;
+
+    let Some(vm) = app.selected_vm() else {
+        return;
+    };
+    let display = screens::management::extract_display_from_script(&vm.config.raw_script);
+    let mut window = crate::vm::lifecycle::parse_display_window(&vm.config.raw_script);
+    let on_off = |on: bool| if on { "on" } else { "off" };
+    let status = match key {
+        KeyCode::Char('z') if DisplayWindow::supports_zoom_to_fit(&display) => {
+            window.zoom_to_fit = !window.zoom_to_fit;
+            format!("Zoom to fit {}", on_off(window.zoom_to_fit))
+        }
+        KeyCode::Char('f') if DisplayWindow::supports_full_screen(&display) => {
+            window.full_screen = !window.full_screen;
+            format!("Start full screen {}", on_off(window.full_screen))
+        }
+        KeyCode::Char('r') if screens::management::supports_resolution_hint(vm) => {
+            window.resolution = window.next_resolution();
+            match window.resolution {
+                Some((w, h)) => format!("Guest resolution set to {}x{}", w, h),
+                None => "Guest resolution left to the guest".to_string(),
+            }
+        }
+        KeyCode::Char('m')
+            if DisplayWindow::supports_multiple_displays(&display)
+                && DisplayWindow::adapter_supports_multiple_displays(
+                    &crate::vm::lifecycle::script_graphics(&vm.config.raw_script),
+                ) =>
+        {
+            window.num_displays = window.next_num_displays();
+            format!("Guest monitors set to {}", window.num_displays)
+        }
+        _ => return,
+    };
+
+    match crate::vm::save_display_window(vm, &window) {
+        Ok(()) => {
+            app.reload_selected_vm_script();
+            app.set_status(format!("{} (applies on next start)", status));
+        }
+        Err(e) => app.set_status(format!("Failed to save display options: {}", e)),
+    }
+}
+
+/// Step the VM's keyboard layout forward (`l`) or back (`L`) through the
+/// keymaps QEMU ships, starting from the host's layout
+fn edit_keymap(app: &mut App, backward: bool) {
+    use crate::vm::keymap;
+
+    let Some(vm) = app.selected_vm() else {
+        return;
+    };
+    let next = keymap::next_keymap(
+        &keymap::available_keymaps(),
+        keymap::script_keymap(&vm.config.raw_script).as_deref(),
+        keymap::host_keymap(),
+        backward,
+    );
+    let display = screens::management::extract_display_from_script(&vm.config.raw_script);
+    match keymap::save_keymap(vm, next.as_deref()) {
+        Ok(()) => {
+            app.reload_selected_vm_script();
+            let mut status = match next {
+                Some(layout) => format!("Keyboard layout set to {}", layout),
+                None => "Keyboard layout left to QEMU (en-us)".to_string(),
+            };
+            if !display.starts_with("vnc") {
+                status.push_str(" - only VNC displays use it");
+            }
+            app.set_status(format!("{} (applies on next start)", status));
+        }
+        Err(e) => app.set_status(format!("Failed to save keyboard layout: {}", e)),
+    }
+}
+
 /// Result of toggling 3D acceleration on an existing VM.
 struct GlToggleResult {
     /// Whether the toggle also auto-swapped the display backend from gtk to sdl
@@ -1508,6 +2848,8 @@ fn toggle_vm_gl_acceleration(script_path: &std::path::Path) -> Result<GlToggleRe
         display_swapped_to_sdl = swapped;
         after_display
     };
+    // gtk -> sdl loses zoom-to-fit
+    let new_content = crate::vm::lifecycle::fit_display_window_to_backend(&new_content);
 
     std::fs::write(script_path, new_content)?;
     Ok(GlToggleResult {
@@ -1541,29 +2883,30 @@ fn update_vm_display(script_path: &std::path::Path, new_display: &str) -> Result
     };
 
     // Add the SPICE guest-agent channel (clipboard sharing) when switching to
-    // spice-app; remove it when switching away. Keeps copy/paste working without
-    // any extra UI (still requires spice-vdagent running in the guest).
+    // spice-app; remove it when switching away. Clipboard Sharing in the
+    // management menu can turn it back off (still requires spice-vdagent
+    // running in the guest).
     let new_content =
         crate::vm::create::set_spice_agent_args(&new_content, new_display == "spice-app");
+    let new_content = crate::vm::lifecycle::fit_display_window_to_backend(&new_content);
 
     std::fs::write(script_path, new_content)?;
     Ok(())
 }
 
+/// Add or remove the SPICE agent channel (clipboard sharing) in a VM's launch script
+fn toggle_vm_clipboard_sharing(script_path: &std::path::Path, enable: bool) -> Result<()> {
+    let content = std::fs::read_to_string(script_path)?;
+    let new_content = crate::vm::create::set_spice_agent_args(&content, enable);
+    std::fs::write(script_path, new_content)?;
+    Ok(())
+}
+
 /// Persist the current USB device selection to the VM's launch.sh, then set a
 /// status message. Shared by the `s` key and the unsaved-changes prompt.
 fn save_usb_passthrough_config(app: &mut App) {
     let save_result = if let Some(vm) = app.selected_vm() {
-        let devices: Vec<crate::vm::UsbPassthrough> = app
-            .selected_usb_devices
-            .iter()
-            .filter_map(|&i| app.usb_devices.get(i))
-            .map(|d| crate::vm::UsbPassthrough {
-                vendor_id: d.vendor_id,
-                product_id: d.product_id,
-                usb_version: d.usb_version,
-            })
-            .collect();
+        let devices = app.usb_passthrough_selection();
 
         let result = crate::vm::save_usb_passthrough(vm, &devices);
         Some((result, devices.len()))
@@ -1634,21 +2977,93 @@ fn handle_usb_devices(app: &mut App, key: KeyEvent) -> Result<()> {
         }
         KeyCode::Char('j') | KeyCode::Down => {
             app.selected_menu_item =
-                (app.selected_menu_item + 1).min(app.usb_devices.len().saturating_sub(1));
+                (app.selected_menu_item + 1).min(app.visible_usb_devices().len().saturating_sub(1));
         }
         KeyCode::Char('k') | KeyCode::Up => {
             if app.selected_menu_item > 0 {
                 app.selected_menu_item -= 1;
             }
         }
-        KeyCode::Char(' ') => {
-            // Space is the sole toggle; Enter saves (see below) so pressing
-            // Enter to "confirm" a selection no longer toggles it back off (#52).
-            app.toggle_usb_device(app.selected_menu_item);
+        KeyCode::Char(' ') => {
+            // Space is the sole toggle; Enter saves (see below) so pressing
+            // Enter to "confirm" a selection no longer toggles it back off (#52).
+            if let Some(index) = app.usb_device_at_cursor() {
+                let selecting = !app.selected_usb_devices.contains(&index);
+                if selecting && app.usb_devices[index].is_keyboard_or_mouse() {
+                    app.push_screen(Screen::Confirm(ConfirmAction::PassHostInput {
+                        index,
+                        hot_add: false,
+                    }));
+                } else {
+                    app.toggle_usb_device(index);
+                }
+            }
+        }
+        KeyCode::Char('h') | KeyCode::Char('H') => {
+            app.toggle_usb_show_all();
+        }
+        KeyCode::Char('p') | KeyCode::Char('P') => {
+            if let Some(index) = app.usb_device_at_cursor() {
+                if !app.toggle_usb_port_match(index) {
+                    app.set_status("This device's port is unknown; it can only match by ID");
+                }
+            }
         }
         KeyCode::Char('s') | KeyCode::Char('S') | KeyCode::Enter => {
             save_usb_passthrough_config(app);
         }
+        KeyCode::Char('a') | KeyCode::Char('A') => {
+            if let Some((index, device)) = app
+                .usb_device_at_cursor()
+                .map(|i| (i, app.usb_devices[i].clone()))
+            {
+                if app.selected_vm_pid().is_none() {
+                    app.set_status("VM is not running; [Space] saves devices for the next launch");
+                } else if app.usb_attached.contains(&(device.bus_num, device.dev_num)) {
+                    app.set_status(format!("{} is already attached", device.display_name()));
+                } else if !device.is_accessible() {
+                    app.set_status(format!(
+                        "No access to {}; press 'u' to install USB permissions",
+                        device.dev_node().display()
+                    ));
+                } else if device.is_keyboard_or_mouse() {
+                    app.push_screen(Screen::Confirm(ConfirmAction::PassHostInput {
+                        index,
+                        hot_add: true,
+                    }));
+                } else {
+                    confirm_usb_hot_add(app, index);
+                }
+            }
+        }
+        KeyCode::Char('d') | KeyCode::Char('D') => {
+            if let (Some(device), Some(vm)) = (
+                app.usb_device_at_cursor()
+                    .map(|i| app.usb_devices[i].clone()),
+                app.selected_vm().cloned(),
+            ) {
+                let key = (device.bus_num, device.dev_num);
+                if !app.usb_attached.contains(&key) {
+                    app.set_status(format!(
+                        "{} was not hot-plugged into the VM",
+                        device.display_name()
+                    ));
+                } else {
+                    match crate::vm::usb_hot_remove(&vm.path, device.bus_num, device.dev_num) {
+                        Ok(()) => {
+                            // device_del completes asynchronously, so don't re-query yet
+                            app.usb_attached.retain(|k| *k != key);
+                            app.set_status(format!("Detached {}", device.display_name()));
+                        }
+                        Err(e) => app.set_status(format!(
+                            "Failed to detach {}: {}",
+                            device.display_name(),
+                            e
+                        )),
+                    }
+                }
+            }
+        }
         KeyCode::Char('u') | KeyCode::Char('U') => {
             // Install udev rules for selected USB devices
             if app.selected_usb_devices.is_empty() {
@@ -1687,6 +3102,37 @@ fn handle_usb_devices(app: &mut App, key: KeyEvent) -> Result<()> {
     Ok(())
 }
 
+/// Hot-add `usb_devices[index]`, first asking when host drivers still hold it
+fn confirm_usb_hot_add(app: &mut App, index: usize) {
+    let drivers = app.usb_devices[index].host_drivers();
+    if drivers.is_empty() {
+        usb_hot_add(app, index);
+    } else {
+        app.push_screen(Screen::Confirm(ConfirmAction::UsbHotAdd { index, drivers }));
+    }
+}
+
+/// Hot-add `usb_devices[index]` to the selected running VM over QMP
+fn usb_hot_add(app: &mut App, index: usize) {
+    let (Some(device), Some(vm)) = (
+        app.usb_devices.get(index).cloned(),
+        app.selected_vm().cloned(),
+    ) else {
+        return;
+    };
+    match crate::vm::usb_hot_add(&vm.path, device.bus_num, device.dev_num) {
+        Ok(()) => {
+            app.refresh_usb_attached();
+            app.set_status(format!(
+                "Attached {} to {}",
+                device.display_name(),
+                vm.display_name()
+            ));
+        }
+        Err(e) => app.set_status(format!("Failed to attach {}: {}", device.display_name(), e)),
+    }
+}
+
 fn handle_confirm(app: &mut App, action: ConfirmAction, key: KeyEvent) -> Result<()> {
     // Three-way unsaved-changes prompt: Save / Discard / Cancel.
     if let ConfirmAction::UnsavedChanges(kind) = action {
@@ -1706,6 +3152,44 @@ fn handle_confirm(app: &mut App, action: ConfirmAction, key: KeyEvent) -> Result
         return Ok(());
     }
 
+    // Three-way quit prompt: Leave running / Shut down all / Cancel.
+    if action == ConfirmAction::QuitWithRunningVms {
+        match key.code {
+            KeyCode::Char('l') | KeyCode::Char('L') | KeyCode::Enter => {
+                execute_confirm_action(app, action)?;
+            }
+            KeyCode::Char('s') | KeyCode::Char('S') => {
+                app.stop_all_running_vms();
+                app.pop_screen();
+                app.quit_confirmed = true;
+                app.should_quit = true;
+            }
+            KeyCode::Esc | KeyCode::Char('c') | KeyCode::Char('C') => {
+                app.pop_screen();
+            }
+            _ => {}
+        }
+        return Ok(());
+    }
+
+    // Three-way reset prompt: Keep the library path / Reset it too / Cancel.
+    if action == ConfirmAction::ResetSettings {
+        match key.code {
+            KeyCode::Char('y') | KeyCode::Char('Y') | KeyCode::Enter => {
+                execute_confirm_action(app, action)?;
+            }
+            KeyCode::Char('a') | KeyCode::Char('A') => {
+                app.pop_screen();
+                screens::settings::reset_all_settings(app, true)?;
+            }
+            KeyCode::Esc | KeyCode::Char('n') | KeyCode::Char('N') => {
+                app.pop_screen();
+            }
+            _ => {}
+        }
+        return Ok(());
+    }
+
     match key.code {
         KeyCode::Esc | KeyCode::Char('n') => app.pop_screen(),
         KeyCode::Char('y') | KeyCode::Enter => {
@@ -1786,9 +3270,58 @@ fn render_detailed_info(app: &App, frame: &mut Frame) {
 
     let os_info = app.selected_vm_info();
 
+    // Usage is read by `refresh_selected_disk_usage` when the screen opens
+    let disks: Vec<(String, String)> = app
+        .selected_vm()
+        .map(|vm| {
+            vm.config
+                .disks
+                .iter()
+                .map(|disk| {
+                    let path = vm.path.join(&disk.path);
+                    let name = path
+                        .file_name()
+                        .map(|n| n.to_string_lossy().to_string())
+                        .unwrap_or_default();
+                    let usage = app
+                        .disk_usage_cache
+                        .get(&path)
+                        .and_then(|(_, usage)| *usage)
+                        .map(|u| u.summary())
+                        .unwrap_or_else(|| "unknown".to_string());
+                    (name, usage)
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+
+    let unrecognized_args = app
+        .selected_vm()
+        .map(|vm| vm.unrecognized_args.as_slice())
+        .unwrap_or_default();
+
+    let shared_disks = app
+        .selected_vm()
+        .map(|vm| crate::vm::discovery::shared_disk_warnings(vm, &app.vms))
+        .unwrap_or_default();
+
     DetailedInfoWidget {
         os_info: os_info.as_ref(),
         vm_name: &vm_name,
+        disks: &disks,
+        shared_disks: &shared_disks,
+        unrecognized_args,
+        network: app
+            .selected_vm()
+            .and_then(|vm| vm.config.network.as_ref())
+            .filter(|net| net.backend != crate::vm::NetworkBackend::None),
+        guest_addresses: app
+            .guest_addresses
+            .as_ref()
+            .filter(|(id, _)| app.selected_vm().is_some_and(|vm| &vm.id == id))
+            .map(|(_, addresses)| addresses.as_slice()),
+        notes: app.selected_vm().and_then(|vm| vm.notes.as_deref()),
+        scroll: app.info_scroll,
     }
     .render(dialog_area, frame.buffer_mut());
 }
@@ -1802,7 +3335,27 @@ fn render_confirm(app: &App, action: &ConfirmAction, frame: &mut Frame) {
                 .selected_vm()
                 .map(|vm| vm.display_name())
                 .unwrap_or_else(|| "VM".to_string());
-            ("Launch VM", format!("Launch {}?", name))
+            let resumes = matches!(app.boot_mode, BootMode::Normal)
+                && app
+                    .selected_vm()
+                    .is_some_and(|vm| crate::vm::has_suspend_state(&vm.path));
+            if resumes {
+                (
+                    "Resume VM",
+                    format!("Resume {} from its suspended state?", name),
+                )
+            } else if app.boot_mode == BootMode::Ephemeral {
+                (
+                    "Ephemeral Boot",
+                    format!(
+                        "Launch {} without saving anything? All disk changes are \
+                         discarded when the VM shuts down.",
+                        name
+                    ),
+                )
+            } else {
+                ("Launch VM", format!("Launch {}?", name))
+            }
         }
         ConfirmAction::ResetVm => (
             "Reset VM",
@@ -1827,6 +3380,46 @@ fn render_confirm(app: &App, action: &ConfirmAction, frame: &mut Frame) {
             "Delete Snapshot",
             format!("Delete snapshot '{}'? This cannot be undone.", name),
         ),
+        ConfirmAction::DeleteSnapshots(names) => (
+            "Delete Snapshots",
+            format!(
+                "Delete {} marked snapshots? This cannot be undone.",
+                names.len()
+            ),
+        ),
+        ConfirmAction::Batch(action) => {
+            let names: Vec<String> = app
+                .marked_vms_in_order()
+                .iter()
+                .map(|vm| vm.display_name())
+                .collect();
+            let question = match action {
+                BatchAction::Start => "Start",
+                BatchAction::Stop => "Stop",
+                BatchAction::Snapshot => "Take a snapshot of",
+                BatchAction::Delete => "Move to trash",
+            };
+            (
+                "Batch Action",
+                format!("{} {} VMs? {}", question, names.len(), names.join(", ")),
+            )
+        }
+        ConfirmAction::DeleteStaleVms(ids) => (
+            "Delete Stale VMs",
+            format!(
+                "Move {} VM{} to trash? This frees {} once the trash is emptied.",
+                ids.len(),
+                if ids.len() == 1 { "" } else { "s" },
+                crate::vm::snapshot::format_size(screens::stale_vms::marked_bytes(app))
+            ),
+        ),
+        ConfirmAction::VerifyIsoChecksum { path, .. } => (
+            "Verify ISO",
+            format!(
+                "This OS has a published SHA-256. Verify {} now?",
+                path.file_name().unwrap_or_default().to_string_lossy()
+            ),
+        ),
         ConfirmAction::DiscardScriptChanges | ConfirmAction::DiscardNotesChanges => (
             "Discard Changes",
             "You have unsaved changes. Discard them?".to_string(),
@@ -1838,6 +3431,163 @@ fn render_confirm(app: &App, action: &ConfirmAction, frame: &mut Frame) {
                 .unwrap_or_else(|| "VM".to_string());
             ("Stop VM", format!("Stop {}?", name))
         }
+        ConfirmAction::SuspendVm => {
+            let name = app
+                .selected_vm()
+                .map(|vm| vm.display_name())
+                .unwrap_or_else(|| "VM".to_string());
+            (
+                "Suspend VM",
+                format!(
+                    "Save {}'s memory to disk and shut it down? Its next launch resumes where it left off.",
+                    name
+                ),
+            )
+        }
+        ConfirmAction::PassHostInput { index, hot_add } => {
+            let name = app
+                .usb_devices
+                .get(*index)
+                .map(|d| d.display_name())
+                .unwrap_or_else(|| "This device".to_string());
+            let message = format!(
+                "WARNING: {} looks like a keyboard or mouse. If the host uses it, {} \
+                 takes it away from the host{} and you may be locked out until the VM \
+                 stops - especially on single-GPU setups.\n\nPass it through anyway?",
+                name,
+                if *hot_add {
+                    "attaching it"
+                } else {
+                    "passing it through"
+                },
+                if *hot_add { " now" } else { " at launch" }
+            );
+            let mut dialog = ConfirmDialog::new("Host Input Device", &message);
+            dialog.confirm_label = "Pass through (y)";
+            dialog.cancel_label = "Cancel (n)";
+            dialog.height = 12;
+            dialog.render(frame.area(), frame.buffer_mut());
+            return;
+        }
+        ConfirmAction::UsbHotAdd { index, drivers } => {
+            let name = app
+                .usb_devices
+                .get(*index)
+                .map(|d| d.display_name())
+                .unwrap_or_else(|| "This device".to_string());
+            let message = format!(
+                "{} is in use by the host ({}). QEMU will detach it from the host: \
+                 unmount any filesystems on it first or data may be lost.\n\nAttach it anyway?",
+                name,
+                drivers.join(", ")
+            );
+            let mut dialog = ConfirmDialog::new("Attach USB Device", &message);
+            dialog.confirm_label = "Attach (y)";
+            dialog.cancel_label = "Cancel (n)";
+            dialog.height = 11;
+            dialog.render(frame.area(), frame.buffer_mut());
+            return;
+        }
+        ConfirmAction::CompactDisk { snapshots } => {
+            let mut message = "Rewrite this VM's qcow2 disks to reclaim space the guest has \
+                               freed? This can take a while for large disks."
+                .to_string();
+            if *snapshots > 0 {
+                message.push_str(&format!(
+                    "\n\nWARNING: {} internal snapshot{} will be LOST. Install \
+                     virt-sparsify (libguestfs) to compact in place and keep them.",
+                    snapshots,
+                    if *snapshots == 1 { "" } else { "s" }
+                ));
+            }
+            let mut dialog = ConfirmDialog::new("Compact Disk", &message);
+            dialog.confirm_label = "Compact (y)";
+            dialog.cancel_label = "Cancel (n)";
+            dialog.height = if *snapshots > 0 { 12 } else { 8 };
+            dialog.render(frame.area(), frame.buffer_mut());
+            return;
+        }
+        ConfirmAction::ResetAsciiArt => (
+            "Reset ASCII Art",
+            format!(
+                "Remove this VM's own art ({}) and show its OS logo again?",
+                crate::vm::lifecycle::CUSTOM_ASCII_FILE
+            ),
+        ),
+        ConfirmAction::DeleteIso { path, used_by } => (
+            "Delete ISO",
+            if used_by.is_empty() {
+                format!(
+                    "Permanently delete {}? No VM in the library refers to it.",
+                    path.file_name().unwrap_or_default().to_string_lossy()
+                )
+            } else {
+                format!(
+                    "Permanently delete {}? These VMs boot from it and will need another ISO: {}.",
+                    path.file_name().unwrap_or_default().to_string_lossy(),
+                    used_by.join(", ")
+                )
+            },
+        ),
+        ConfirmAction::DeleteTemplate(name) => (
+            "Delete Template",
+            format!(
+                "Delete the template '{}'? VMs created from it are not affected.",
+                name
+            ),
+        ),
+        ConfirmAction::DeleteHook(hook) => (
+            "Delete Hook",
+            format!("Delete {}? It will no longer run around this VM.", hook),
+        ),
+        ConfirmAction::AddSerialConsole => (
+            "Add Serial Console",
+            format!(
+                "Expose the first serial port on {} in the VM folder? Linux guests also need \
+                 console=ttyS0 on their kernel command line. Takes effect from the next boot.",
+                crate::vm::lifecycle::SERIAL_SOCKET
+            ),
+        ),
+        ConfirmAction::FinishInstall(id) => {
+            let name = app
+                .vms
+                .iter()
+                .find(|vm| &vm.id == id)
+                .map(|vm| vm.display_name())
+                .unwrap_or_else(|| "The VM".to_string());
+            (
+                "Installation Done?",
+                format!(
+                    "{} has shut down after an install boot. Installation done? Switch to disk \
+                     boot? The ISO stays attached, but normal launches won't boot it.",
+                    name
+                ),
+            )
+        }
+        ConfirmAction::ResetNvram => (
+            "Reset UEFI Variables",
+            "Replace the VM's UEFI variables with the template? Boot entries are lost; \
+             the current variables are backed up first."
+                .to_string(),
+        ),
+        ConfirmAction::SetSecureBoot(enabled) => (
+            "Secure Boot",
+            format!(
+                "{} Secure Boot? The firmware is switched and the UEFI variables are reset \
+                 (the current ones are backed up first).{}",
+                if *enabled { "Enable" } else { "Disable" },
+                if *enabled {
+                    " An installed guest with an unsigned bootloader will no longer boot."
+                } else {
+                    ""
+                }
+            ),
+        ),
+        ConfirmAction::DiscardSuspendState => (
+            "Discard Suspended State",
+            "Delete the suspended state? The VM will boot fresh; unsaved work in it is lost."
+                .to_string(),
+        ),
         ConfirmAction::ForceStopVm => {
             let name = app
                 .selected_vm()
@@ -1862,6 +3612,82 @@ fn render_confirm(app: &App, action: &ConfirmAction, frame: &mut Frame) {
             dialog.render(frame.area(), frame.buffer_mut());
             return;
         }
+        ConfirmAction::KvmUnavailable(reason) => {
+            let message = format!(
+                "This VM uses KVM, but KVM isn't usable on this host:\n\n{}\n\n\
+                 Launch once with TCG software emulation instead? It works without \
+                 KVM but is much slower. launch.sh is not changed.",
+                reason
+            );
+            let mut dialog = ConfirmDialog::new("KVM Unavailable", &message);
+            dialog.confirm_label = "Use TCG (y)";
+            dialog.cancel_label = "Cancel (n)";
+            dialog.height = 14;
+            dialog.render(frame.area(), frame.buffer_mut());
+            return;
+        }
+        ConfirmAction::HostCapacity(warnings) => {
+            let message = format!(
+                "This VM is sized beyond the host:\n\n{}\n\n\
+                 Overcommitting works, but the host may swap or the VM may run \
+                 slowly. Launch anyway?",
+                warnings.join("\n")
+            );
+            let mut dialog = ConfirmDialog::new("Exceeds Host Capacity", &message);
+            dialog.confirm_label = "Launch (y)";
+            dialog.cancel_label = "Cancel (n)";
+            dialog.height = 12;
+            dialog.render(frame.area(), frame.buffer_mut());
+            return;
+        }
+        ConfirmAction::ApplyIvshmemSize {
+            width,
+            height,
+            size_mb,
+        } => {
+            let current = app.config.default_ivshmem_size_mb;
+            let message = format!(
+                "{width}x{height} needs two 32-bit frames ({width}x{height}x4x2) plus \
+                 ~10MB, rounded up to a power of two: {size_mb}MB (now {current}MB).\n\n\
+                 Too small and Looking Glass fails; larger only costs host RAM.\n\n\
+                 Apply {size_mb}MB?"
+            );
+            let mut dialog = ConfirmDialog::new("IVSHMEM Size", &message);
+            dialog.confirm_label = "Apply (y)";
+            dialog.cancel_label = "Cancel (n)";
+            dialog.height = 13;
+            dialog.render(frame.area(), frame.buffer_mut());
+            return;
+        }
+        ConfirmAction::QuitWithRunningVms => {
+            let count = app.running_vms.len();
+            let message = format!(
+                "{} VM{} still running. Leave {} running, or shut down all before quitting?",
+                count,
+                if count == 1 { " is" } else { "s are" },
+                if count == 1 { "it" } else { "them" },
+            );
+            let mut dialog = ConfirmDialog::new("Quit", &message);
+            dialog.confirm_label = "Leave (l)";
+            dialog.extra_label = Some("Shut down (s)");
+            dialog.cancel_label = "Cancel (Esc)";
+            dialog.render(frame.area(), frame.buffer_mut());
+            return;
+        }
+        ConfirmAction::ResetSettings => {
+            let message = format!(
+                "Reset every setting to its default?\n\nThe VM library path ({}) is kept \
+                 unless you reset it too.",
+                app.config.vm_library_path.display()
+            );
+            let mut dialog = ConfirmDialog::new("Reset Settings", &message);
+            dialog.confirm_label = "Reset (y)";
+            dialog.extra_label = Some("Library too (a)");
+            dialog.cancel_label = "Cancel (n)";
+            dialog.height = 11;
+            dialog.render(frame.area(), frame.buffer_mut());
+            return;
+        }
     };
 
     ConfirmDialog::new(title, &message).render(frame.area(), frame.buffer_mut());
@@ -1879,10 +3705,13 @@ fn render_usb_devices(app: &App, frame: &mut Frame) {
     frame.render_widget(Clear, dialog_area);
 
     let selected_count = app.selected_usb_devices.len();
-    let title = if selected_count > 0 {
-        format!(" USB Passthrough ({} selected) ", selected_count)
-    } else {
-        " USB Passthrough ".to_string()
+    let title = match (selected_count, app.usb_attached.len()) {
+        (0, 0) => " USB Passthrough ".to_string(),
+        (selected, 0) => format!(" USB Passthrough ({} selected) ", selected),
+        (selected, attached) => format!(
+            " USB Passthrough ({} selected, {} attached live) ",
+            selected, attached
+        ),
     };
 
     let block = Block::default()
@@ -1917,21 +3746,22 @@ fn render_usb_devices(app: &App, frame: &mut Frame) {
     let content_area = v_chunks[1];
     let help_area = v_chunks[2];
 
-    if app.usb_devices.is_empty() {
+    let visible = app.visible_usb_devices();
+    if visible.is_empty() {
         let msg =
             Paragraph::new("No USB devices found.\n\nConnect a USB device and reopen this screen.")
                 .style(Style::default().fg(Color::DarkGray))
                 .alignment(Alignment::Center);
         frame.render_widget(msg, content_area);
     } else {
-        let items: Vec<ListItem> = app
-            .usb_devices
+        let items: Vec<ListItem> = visible
             .iter()
             .enumerate()
-            .map(|(i, device)| {
+            .map(|(row, &i)| {
+                let device = &app.usb_devices[i];
                 let selected = app.selected_usb_devices.contains(&i);
                 let checkbox = if selected { "[✓]" } else { "[ ]" };
-                let style = if i == app.selected_menu_item {
+                let style = if row == app.selected_menu_item {
                     Style::default()
                         .fg(Color::Yellow)
                         .add_modifier(Modifier::BOLD)
@@ -1941,17 +3771,40 @@ fn render_usb_devices(app: &App, frame: &mut Frame) {
                     Style::default().fg(Color::White)
                 };
 
-                ListItem::new(format!(
-                    "{} {} ({:04x}:{:04x})",
-                    checkbox,
-                    device.display_name(),
-                    device.vendor_id,
-                    device.product_id
-                ))
-                .style(style)
+                let mut spans = vec![
+                    Span::styled(
+                        format!(
+                            "{} {} ({:04x}:{:04x})",
+                            checkbox,
+                            device.display_name(),
+                            device.vendor_id,
+                            device.product_id
+                        ),
+                        style,
+                    ),
+                    Span::styled(
+                        format!(" {}", device.class().label()),
+                        Style::default().fg(Color::DarkGray),
+                    ),
+                ];
+                if device.is_keyboard_or_mouse() {
+                    spans.push(Span::styled(
+                        " ⚠ host input",
+                        Style::default().fg(Color::Red),
+                    ));
+                }
+                if app.usb_port_matched.contains(&i) {
+                    spans.push(Span::styled(
+                        format!(" @port {}-{}", device.bus_num, device.port_path),
+                        Style::default().fg(Color::Magenta),
+                    ));
+                }
+                if app.usb_attached.contains(&(device.bus_num, device.dev_num)) {
+                    spans.push(Span::styled(" ● live", Style::default().fg(Color::Cyan)));
+                }
+                ListItem::new(Line::from(spans))
             })
             .collect();
-
         let mut state = ListState::default();
         state.select(Some(app.selected_menu_item));
 
@@ -1959,11 +3812,15 @@ fn render_usb_devices(app: &App, frame: &mut Frame) {
         frame.render_stateful_widget(list, content_area, &mut state);
     }
 
-    // Help text
-    let help =
-        Paragraph::new("[Space] Toggle  [Enter/s] Save  [u] Install USB permissions  [Esc] Back")
-            .style(Style::default().fg(Color::DarkGray))
-            .alignment(Alignment::Center);
+    // Help text; [Space] is the saved config, [a]/[d] act on the running VM
+    let help_text = if app.selected_vm_pid().is_some() {
+        "[Space] Toggle  [s] Save  [a] Attach now  [d] Detach  [u] Perms  [Esc] Back"
+    } else {
+        "[Space] Toggle  [Enter/s] Save  [u] Install USB permissions  [Esc] Back"
+    };
+    let help = Paragraph::new(help_text)
+        .style(Style::default().fg(Color::DarkGray))
+        .alignment(Alignment::Center);
     frame.render_widget(help, help_area);
 }
 
@@ -2000,7 +3857,8 @@ fn render_file_browser(app: &App, frame: &mut Frame) {
         app.wizard_state.is_some() && app.file_browser_mode == FileBrowserMode::Iso;
 
     let area = frame.area();
-    let dialog_width = 60.min(area.width.saturating_sub(4));
+    // Wide enough for the size/date (and disk format) columns
+    let dialog_width = 84.min(area.width.saturating_sub(4));
     let base_height: u16 = 20;
     let dialog_height = (base_height + if show_default_iso_checkbox { 2 } else { 0 })
         .min(area.height.saturating_sub(4));
@@ -2016,11 +3874,28 @@ fn render_file_browser(app: &App, frame: &mut Frame) {
         FileBrowserMode::ImportConfig => "Select Config File",
         FileBrowserMode::Bios => "Select BIOS/ROM File",
         FileBrowserMode::Floppy => "Select Floppy Image",
+        FileBrowserMode::FloppyDrive => "Select Floppy for Drive B:",
+        FileBrowserMode::FloppySwap => "Select Floppy to Insert",
+        FileBrowserMode::CdromSwap => "Select ISO to Insert",
         FileBrowserMode::SingleGpuRom => "Select GPU vBIOS ROM",
+        FileBrowserMode::ToolsIso => "Select Tools ISO",
+        FileBrowserMode::Kernel => "Select Kernel Image",
+        FileBrowserMode::Initrd => "Select Initrd",
+        FileBrowserMode::AsciiArt => "Select ASCII Art",
+        FileBrowserMode::SettingsFile(item) => item.display_name(),
     };
     let title = format!(" {} - {} ", title_prefix, app.file_browser_dir.display());
     let block = Block::default()
         .title(title)
+        .title_bottom(match app.file_browser_error {
+            Some(ref error) => {
+                Line::styled(format!(" {} ", error), Style::default().fg(Color::Red))
+            }
+            None => Line::raw(format!(
+                " [g] Go to path  [b] Bookmarks  [m] Mark dir  [s] Sort: {} ",
+                app.file_browser_sort.label()
+            )),
+        })
         .borders(Borders::ALL)
         .border_style(Style::default().fg(Color::Cyan))
         .style(Style::default().bg(Color::Black));
@@ -2068,14 +3943,19 @@ fn render_file_browser(app: &App, frame: &mut Frame) {
         let line = Line::from(vec![
             Span::styled(mark, Style::default().fg(Color::Yellow)),
             Span::raw(" Set as Default ISO Path "),
-            Span::styled("[d] toggle", Style::default().fg(Color::DarkGray)),
+            Span::styled(
+                "[d] toggle  [v] verify ISO",
+                Style::default().fg(Color::DarkGray),
+            ),
         ]);
         frame.render_widget(Paragraph::new(line), footer_area);
     }
 
     if app.file_browser_entries.is_empty() {
         let msg_text = match app.file_browser_mode {
-            FileBrowserMode::Iso => "No ISO files found in this directory.",
+            FileBrowserMode::Iso | FileBrowserMode::ToolsIso | FileBrowserMode::CdromSwap => {
+                "No ISO files found in this directory."
+            }
             FileBrowserMode::RecoveryImage => {
                 "No recovery images (.dmg, .qcow2) found in this directory."
             }
@@ -2087,12 +3967,24 @@ fn render_file_browser(app: &App, frame: &mut Frame) {
             FileBrowserMode::Bios => {
                 "No firmware files (.bin, .rom, .qcow2, .fd) found in this directory."
             }
-            FileBrowserMode::Floppy => {
+            FileBrowserMode::Floppy
+            | FileBrowserMode::FloppyDrive
+            | FileBrowserMode::FloppySwap => {
                 "No floppy images (.img, .ima, .flp, .vfd) found in this directory."
             }
             FileBrowserMode::SingleGpuRom => {
                 "No vBIOS ROM files (.rom, .bin) found in this directory."
             }
+            FileBrowserMode::Kernel | FileBrowserMode::Initrd => "No files in this directory.",
+            FileBrowserMode::SettingsFile(item) if item.file_extensions().is_empty() => {
+                "No files in this directory."
+            }
+            FileBrowserMode::SettingsFile(_) => {
+                "No firmware images (.fd, .bin, .raw, .qcow2) found in this directory."
+            }
+            FileBrowserMode::AsciiArt => {
+                "No text files (.txt, .ascii, .asc) found in this directory."
+            }
         };
         let msg = ratatui::widgets::Paragraph::new(msg_text)
             .style(Style::default().fg(Color::DarkGray))
@@ -2101,6 +3993,12 @@ fn render_file_browser(app: &App, frame: &mut Frame) {
         return;
     }
 
+    // Metadata columns: [format] size date, right of the name
+    let show_format = app.file_browser_mode == FileBrowserMode::Disk;
+    let meta_width = if show_format { 35 } else { 28 };
+    // Leave room for the highlight symbol and the 2-column icon
+    let name_width = (content_area.width as usize).saturating_sub(2 + 3 + meta_width);
+
     let items: Vec<ListItem> = app
         .file_browser_entries
         .iter()
@@ -2112,7 +4010,41 @@ fn render_file_browser(app: &App, frame: &mut Frame) {
             } else {
                 "💿 "
             };
-            ListItem::new(format!("{}{}", prefix, entry.name))
+            let mut name: String = entry.name.chars().take(name_width).collect();
+            if name.chars().count() < entry.name.chars().count() {
+                name.pop();
+                name.push('…');
+            }
+            let mut line = vec![Span::raw(format!(
+                "{}{:<width$}",
+                prefix,
+                name,
+                width = name_width
+            ))];
+            if entry.name != ".." && entry.name != "[Select This Directory]" {
+                let format = if show_format {
+                    format!(" {:>6}", entry.format.as_deref().unwrap_or(""))
+                } else {
+                    String::new()
+                };
+                let size = entry
+                    .size
+                    .map(crate::vm::snapshot::format_size)
+                    .unwrap_or_default();
+                let date = entry
+                    .modified
+                    .map(|t| {
+                        chrono::DateTime::<chrono::Local>::from(t)
+                            .format("%Y-%m-%d %H:%M")
+                            .to_string()
+                    })
+                    .unwrap_or_default();
+                line.push(Span::styled(
+                    format!("{} {:>8}  {:<16}", format, size, date),
+                    Style::default().fg(Color::DarkGray),
+                ));
+            }
+            ListItem::new(Line::from(line))
         })
         .collect();
 
@@ -2128,15 +4060,121 @@ fn render_file_browser(app: &App, frame: &mut Frame) {
         )
         .highlight_symbol("> ");
     frame.render_stateful_widget(list, content_area, &mut state);
+
+    if let Some(selected) = app.file_browser_bookmarks {
+        render_file_browser_bookmarks(app, selected, dialog_area, frame);
+    }
+}
+
+/// Bookmark quick-pick drawn over the file browser
+fn render_file_browser_bookmarks(app: &App, selected: usize, area: Rect, frame: &mut Frame) {
+    use ratatui::widgets::{Block, Borders, Clear, List, ListItem, ListState, Paragraph};
+
+    let bookmarks = &app.config.browser_bookmarks;
+    let width = 64.min(area.width.saturating_sub(4));
+    let height = (bookmarks.len().max(1) as u16 + 2).min(area.height.saturating_sub(2));
+    let popup = centered_rect(width, height, area);
+    frame.render_widget(Clear, popup);
+
+    let block = Block::default()
+        .title(" Bookmarks ")
+        .title_bottom(" [Enter] Go  [x] Remove  [Esc] Close ")
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::Yellow))
+        .style(Style::default().bg(Color::Black));
+    let inner = block.inner(popup);
+    frame.render_widget(block, popup);
+
+    if bookmarks.is_empty() {
+        let msg = Paragraph::new("No bookmarks yet - press [m] in a directory")
+            .style(Style::default().fg(Color::DarkGray))
+            .alignment(Alignment::Center);
+        frame.render_widget(msg, inner);
+        return;
+    }
+
+    let items: Vec<ListItem> = bookmarks
+        .iter()
+        .map(|dir| {
+            let style = if dir.is_dir() {
+                Style::default().fg(Color::White)
+            } else {
+                Style::default().fg(Color::DarkGray)
+            };
+            ListItem::new(Line::styled(format!("📁 {}", dir.display()), style))
+        })
+        .collect();
+    let mut state = ListState::default();
+    state.select(Some(selected));
+    let list = List::new(items)
+        .highlight_style(
+            Style::default()
+                .fg(Color::Yellow)
+                .add_modifier(Modifier::BOLD)
+                .bg(Color::DarkGray),
+        )
+        .highlight_symbol("> ");
+    frame.render_stateful_widget(list, inner, &mut state);
+}
+
+fn handle_file_browser_bookmarks(app: &mut App, selected: usize, key: KeyEvent) -> Result<()> {
+    let count = app.config.browser_bookmarks.len();
+    match key.code {
+        KeyCode::Esc | KeyCode::Char('b') => app.file_browser_bookmarks = None,
+        KeyCode::Char('j') | KeyCode::Down if selected + 1 < count => {
+            app.file_browser_bookmarks = Some(selected + 1);
+        }
+        KeyCode::Char('k') | KeyCode::Up if selected > 0 => {
+            app.file_browser_bookmarks = Some(selected - 1);
+        }
+        KeyCode::Enter if selected < count => {
+            app.file_browser_bookmarks = None;
+            app.file_browser_open_bookmark(selected);
+        }
+        KeyCode::Char('x') | KeyCode::Delete if selected < count => {
+            if let Err(e) = app.remove_file_browser_bookmark(selected) {
+                app.set_status(format!("Failed to save config: {}", e));
+            }
+            app.file_browser_bookmarks = Some(selected.min(count.saturating_sub(2)));
+        }
+        _ => {}
+    }
+    Ok(())
 }
 
 fn handle_file_browser(app: &mut App, key: KeyEvent) -> Result<()> {
     use crate::app::FileBrowserMode;
 
+    if let Some(selected) = app.file_browser_bookmarks {
+        return handle_file_browser_bookmarks(app, selected, key);
+    }
+
     match key.code {
         KeyCode::Esc => app.pop_screen(),
         KeyCode::Char('j') | KeyCode::Down => app.file_browser_next(),
         KeyCode::Char('k') | KeyCode::Up => app.file_browser_prev(),
+        KeyCode::Char('s') => app.file_browser_cycle_sort(),
+        KeyCode::Char('b') => {
+            app.file_browser_error = None;
+            app.file_browser_bookmarks = Some(0);
+        }
+        KeyCode::Char('m') => match app.toggle_file_browser_bookmark() {
+            Ok(true) => app.set_status(format!("Bookmarked {}", app.file_browser_dir.display())),
+            Ok(false) => app.set_status(format!(
+                "Removed bookmark {}",
+                app.file_browser_dir.display()
+            )),
+            Err(e) => app.set_status(format!("Failed to save config: {}", e)),
+        },
+        KeyCode::Char('g') | KeyCode::Char('~') => {
+            app.file_browser_error = None;
+            app.text_input_buffer = if key.code == KeyCode::Char('~') {
+                "~/".to_string()
+            } else {
+                String::new()
+            };
+            app.push_screen(Screen::TextInput(TextInputContext::FileBrowserPath));
+        }
         KeyCode::Char('d')
             if app.wizard_state.is_some() && app.file_browser_mode == FileBrowserMode::Iso =>
         {
@@ -2162,6 +4200,23 @@ fn handle_file_browser(app: &mut App, key: KeyEvent) -> Result<()> {
                 Err(e) => app.set_status(format!("Failed to save config: {}", e)),
             }
         }
+        KeyCode::Char('v')
+            if matches!(
+                app.file_browser_mode,
+                FileBrowserMode::Iso | FileBrowserMode::ToolsIso
+            ) =>
+        {
+            // Verify the highlighted ISO against a hash the user pastes in
+            let entry = app
+                .file_browser_entries
+                .get(app.file_browser_selected)
+                .filter(|e| !e.is_dir);
+            if let Some(entry) = entry {
+                let path = entry.path.clone();
+                app.text_input_buffer.clear();
+                app.push_screen(Screen::TextInput(TextInputContext::IsoChecksum(path)));
+            }
+        }
         KeyCode::Enter => {
             if let Some(selected_path) = app.file_browser_enter() {
                 match app.file_browser_mode {
@@ -2170,13 +4225,17 @@ fn handle_file_browser(app: &mut App, key: KeyEvent) -> Result<()> {
                         if app.wizard_state.is_some() {
                             // Set the ISO path in wizard state
                             if let Some(ref mut state) = app.wizard_state {
-                                state.iso_path = Some(selected_path);
+                                state.iso_path = Some(selected_path.clone());
                                 state.is_recovery_image = false;
                             }
                             app.pop_screen(); // Close file browser
 
-                            // Proceed to next step
-                            let _ = app.wizard_next_step();
+                            // Ask first if the ISO looks like a different OS;
+                            // otherwise proceed to next step
+                            if !app.wizard_detect_iso_os(&selected_path) {
+                                let _ = app.wizard_next_step();
+                                app.offer_iso_verification();
+                            }
                         } else {
                             // Normal boot mode - selected an ISO file
                             app.boot_mode = BootMode::Cdrom(selected_path);
@@ -2185,6 +4244,39 @@ fn handle_file_browser(app: &mut App, key: KeyEvent) -> Result<()> {
                             app.push_screen(Screen::Confirm(ConfirmAction::LaunchVm));
                         }
                     }
+                    FileBrowserMode::ToolsIso => {
+                        app.pop_screen(); // Return to boot options
+                        save_tools_iso_config(app, Some(selected_path));
+                    }
+                    FileBrowserMode::FloppyDrive => {
+                        app.pop_screen(); // Return to boot options
+                        save_floppy_drive_config(app, Some(selected_path));
+                    }
+                    FileBrowserMode::FloppySwap | FileBrowserMode::CdromSwap => {
+                        app.pop_screen(); // Return to removable media
+                        screens::removable_media::insert(app, selected_path);
+                    }
+                    FileBrowserMode::AsciiArt => {
+                        app.pop_screen(); // Return to management
+                        match app.set_selected_vm_ascii(Some(&selected_path)) {
+                            Ok(()) => app.set_status("ASCII art updated"),
+                            Err(e) => app.show_error(format!("Can't use that file\n\n{:#}", e)),
+                        }
+                    }
+                    FileBrowserMode::SettingsFile(item) => {
+                        app.pop_screen(); // Return to settings
+                        screens::settings::set_file_path(app, item, Some(selected_path))?;
+                    }
+                    FileBrowserMode::Kernel | FileBrowserMode::Initrd => {
+                        app.pop_screen(); // Return to direct kernel boot
+                        let mut boot = app.direct_kernel.clone();
+                        if app.file_browser_mode == FileBrowserMode::Kernel {
+                            boot.kernel = selected_path;
+                        } else {
+                            boot.initrd = Some(selected_path);
+                        }
+                        screens::direct_kernel_boot::save(app, boot);
+                    }
                     FileBrowserMode::RecoveryImage => {
                         if app.wizard_state.is_some() {
                             if let Some(ref mut state) = app.wizard_state {
@@ -2271,6 +4363,7 @@ fn handle_file_browser(app: &mut App, key: KeyEvent) -> Result<()> {
                                         );
                                     let has_notes = !vm.import_notes.is_empty();
                                     state.selected_vm = Some(vm);
+                                    screens::import_wizard::detect_disk_formats(state);
                                     state.error_message = None;
                                     state.field_focus = 0;
 
@@ -2299,7 +4392,7 @@ fn handle_file_browser(app: &mut App, key: KeyEvent) -> Result<()> {
                                         (crate::app::ImportStep::ConfigureDisk, true)
                                     };
 
-                                    app.import_state = Some(crate::app::ImportWizardState {
+                                    let mut state = crate::app::ImportWizardState {
                                         source: Some(source),
                                         vm_name,
                                         folder_name,
@@ -2307,7 +4400,9 @@ fn handle_file_browser(app: &mut App, key: KeyEvent) -> Result<()> {
                                         step,
                                         warnings_acknowledged,
                                         ..crate::app::ImportWizardState::default()
-                                    });
+                                    };
+                                    screens::import_wizard::detect_disk_formats(&mut state);
+                                    app.import_state = Some(state);
                                     app.push_screen(crate::app::Screen::ImportWizard);
                                 }
                             }
@@ -2334,11 +4429,41 @@ fn render_text_input(app: &App, context: &TextInputContext, frame: &mut Frame) {
     let title = match context {
         TextInputContext::SnapshotName => " Enter Snapshot Name ",
         TextInputContext::RenameVm => " Enter New VM Name ",
+        TextInputContext::IvshmemResolution => " Guest Resolution (e.g. 2560x1440) ",
+        TextInputContext::IsoChecksum(_) => " Expected SHA-256 ",
+        TextInputContext::IsoDownloadUrl => " Direct ISO Link (from the download page) ",
+        TextInputContext::BalloonTarget => " Balloon Target (MB) ",
+        TextInputContext::FileBrowserPath => " Go to Directory ([Tab] complete) ",
+        TextInputContext::KernelAppend => " Kernel Command Line ",
+        TextInputContext::EmptyTrash => " Empty Trash ",
+        TextInputContext::RtcDate => " RTC Start (YYYY-MM-DD[THH:MM:SS]) ",
+        TextInputContext::DiskThrottle(field) => screens::disk_throttle::input_title(*field),
+        TextInputContext::TemplateName => " Save Settings as Template ",
+        TextInputContext::SshUser => " SSH Login Name ",
+        TextInputContext::WindowTitle => " Window Title (empty = VM name) ",
     };
 
     let area = frame.area();
-    let dialog_width = 50.min(area.width.saturating_sub(4));
-    let dialog_height = 5;
+    // A SHA-256 is 64 characters and needs a wider box
+    let width = match context {
+        TextInputContext::IsoChecksum(_)
+        | TextInputContext::IsoDownloadUrl
+        | TextInputContext::FileBrowserPath
+        | TextInputContext::KernelAppend
+        | TextInputContext::EmptyTrash => 70,
+        _ => 50,
+    };
+    let dialog_width = width.min(area.width.saturating_sub(4));
+    let duplicate_warning = *context == TextInputContext::RenameVm
+        && app.rename_duplicate_acknowledged.as_deref() == Some(app.text_input_buffer.as_str());
+    let empty_trash = *context == TextInputContext::EmptyTrash;
+    let dialog_height = if empty_trash {
+        7
+    } else if duplicate_warning {
+        6
+    } else {
+        5
+    };
 
     let dialog_area = centered_rect(dialog_width, dialog_height, area);
     frame.render_widget(Clear, dialog_area);
@@ -2352,9 +4477,37 @@ fn render_text_input(app: &App, context: &TextInputContext, frame: &mut Frame) {
     let inner = block.inner(dialog_area);
     frame.render_widget(block, dialog_area);
 
-    let input = Paragraph::new(format!("{}_", app.text_input_buffer))
-        .style(Style::default().fg(Color::White));
-    frame.render_widget(input, inner);
+    let mut lines = Vec::new();
+    if empty_trash {
+        lines.push(Line::styled(
+            format!(
+                "Permanently delete {} VMs ({})? This cannot be undone.",
+                app.trash_entries.len(),
+                crate::vm::snapshot::format_size(screens::trash::total_bytes(app))
+            ),
+            Style::default().fg(Color::Red),
+        ));
+        lines.push(Line::styled(
+            format!(
+                "Type '{}' and press Enter:",
+                screens::trash::EMPTY_TRASH_WORD
+            ),
+            Style::default().fg(Color::Yellow),
+        ));
+        lines.push(Line::raw(""));
+    }
+    lines.push(Line::styled(
+        format!("{}_", app.text_input_buffer),
+        Style::default().fg(Color::White),
+    ));
+    if duplicate_warning {
+        lines.push(Line::raw(""));
+        lines.push(Line::styled(
+            "Name used by another VM - [Enter] keep it anyway",
+            Style::default().fg(Color::Yellow),
+        ));
+    }
+    frame.render_widget(Paragraph::new(lines), inner);
 }
 
 fn handle_text_input(app: &mut App, context: TextInputContext, key: KeyEvent) -> Result<()> {
@@ -2377,8 +4530,7 @@ fn handle_text_input(app: &mut App, context: TextInputContext, key: KeyEvent) ->
                                 let disk_path = disk.path.clone();
                                 let name = input.clone();
                                 let tx = app.background_tx.clone();
-                                app.loading = true;
-                                app.set_status(format!("Creating snapshot: {}...", name));
+                                app.start_progress(format!("Creating snapshot: {}", name));
 
                                 thread::spawn(move || {
                                     let result = crate::vm::create_snapshot(&disk_path, &name);
@@ -2395,6 +4547,19 @@ fn handle_text_input(app: &mut App, context: TextInputContext, key: KeyEvent) ->
                 TextInputContext::RenameVm => {
                     if !input.is_empty() {
                         if let Some(vm) = app.selected_vm().cloned() {
+                            let duplicate =
+                                crate::vm::find_by_display_name(&app.vms, &input, Some(&vm.id))
+                                    .is_some();
+                            if duplicate
+                                && app.rename_duplicate_acknowledged.as_deref() != Some(&input)
+                            {
+                                // Keep the dialog open with a warning; Enter
+                                // again keeps the duplicate name
+                                app.rename_duplicate_acknowledged = Some(input.clone());
+                                app.text_input_buffer = input;
+                                app.push_screen(Screen::TextInput(TextInputContext::RenameVm));
+                                return Ok(());
+                            }
                             match crate::vm::lifecycle::rename_vm(&vm, &input) {
                                 Ok(()) => {
                                     app.set_status(format!("Renamed to: {}", input));
@@ -2408,6 +4573,123 @@ fn handle_text_input(app: &mut App, context: TextInputContext, key: KeyEvent) ->
                         }
                     }
                 }
+                TextInputContext::IvshmemResolution => {
+                    match crate::hardware::parse_resolution(&input) {
+                        Some((width, height)) => {
+                            let size_mb = crate::hardware::recommended_ivshmem_mb(width, height);
+                            app.push_screen(Screen::Confirm(ConfirmAction::ApplyIvshmemSize {
+                                width,
+                                height,
+                                size_mb,
+                            }));
+                        }
+                        None if input.is_empty() => {}
+                        None => app.set_status(format!(
+                            "Invalid resolution '{}' (expected WIDTHxHEIGHT)",
+                            input
+                        )),
+                    }
+                }
+                TextInputContext::IsoDownloadUrl => {
+                    let url = input.trim().to_string();
+                    if url.is_empty() {
+                        // Nothing entered; back to the ISO step
+                    } else if !url.starts_with("http://") && !url.starts_with("https://") {
+                        app.set_status("Enter an http:// or https:// link");
+                    } else if !crate::commands::download::is_direct_image_url(&url) {
+                        app.set_status("That link doesn't end in .iso or .img");
+                    } else {
+                        app.start_iso_download(url);
+                    }
+                }
+                TextInputContext::BalloonTarget => match input.parse::<u64>() {
+                    Ok(target_mb) if target_mb > 0 => {
+                        if let Some(vm) = app.selected_vm().cloned() {
+                            match crate::vm::set_balloon_target(&vm.path, target_mb) {
+                                Ok(()) => app.set_status(format!(
+                                    "Balloon target for {} set to {} MB",
+                                    vm.display_name(),
+                                    target_mb
+                                )),
+                                Err(e) => {
+                                    app.set_status(format!("Error setting balloon target: {}", e))
+                                }
+                            }
+                        }
+                    }
+                    _ if input.is_empty() => {}
+                    _ => app.set_status("Balloon target must be a size in MB"),
+                },
+                TextInputContext::FileBrowserPath => {
+                    if !input.trim().is_empty() {
+                        app.file_browser_jump(&input);
+                    }
+                }
+                TextInputContext::EmptyTrash => {
+                    if input.trim() == screens::trash::EMPTY_TRASH_WORD {
+                        screens::trash::empty_trash(app);
+                    } else {
+                        app.set_status("Trash not emptied");
+                    }
+                }
+                TextInputContext::RtcDate => screens::rtc_settings::set_fixed_date(app, &input),
+                TextInputContext::DiskThrottle(field) => {
+                    screens::disk_throttle::set_limit(app, field, &input)
+                }
+                TextInputContext::TemplateName => {
+                    let name = input.trim();
+                    if !name.is_empty() {
+                        app.save_wizard_template(name);
+                    }
+                }
+                TextInputContext::SshUser => {
+                    let user = input.trim();
+                    if user.is_empty() {
+                        // Cancelled
+                    } else if !crate::vm::ssh::is_valid_user(user) {
+                        app.set_status(format!("Invalid login name '{}'", user));
+                    } else {
+                        if let Some(vm) = app.selected_vm() {
+                            if let Err(e) = crate::vm::ssh::save_ssh_user(&vm.path, user) {
+                                app.set_status(format!("Could not remember login name: {}", e));
+                            }
+                        }
+                        app.pending_ssh = Some(user.to_string());
+                    }
+                }
+                TextInputContext::WindowTitle => {
+                    if let Some(vm) = app.selected_vm().cloned() {
+                        let title = match input.trim() {
+                            "" => vm.display_name(),
+                            title => title.to_string(),
+                        };
+                        match crate::vm::save_window_title(&vm, &title) {
+                            Ok(()) => app.set_status(format!(
+                                "Window title set to '{}' (applies on next launch)",
+                                title
+                            )),
+                            Err(e) => app.set_status(format!("Failed to set window title: {}", e)),
+                        }
+                        app.reload_selected_vm_script();
+                    }
+                }
+                TextInputContext::KernelAppend => {
+                    let mut boot = app.direct_kernel.clone();
+                    boot.append = input.trim().to_string();
+                    screens::direct_kernel_boot::save(app, boot);
+                }
+                TextInputContext::IsoChecksum(path) => {
+                    match crate::commands::checksum::parse_sha256(&input) {
+                        Some(expected) => app.start_checksum_verification(path, expected),
+                        None if input.is_empty() => {}
+                        None => app.set_status("Invalid SHA-256 (expected 64 hex digits)"),
+                    }
+                }
+            }
+        }
+        KeyCode::Tab if context == TextInputContext::FileBrowserPath => {
+            if let Some(completed) = crate::fs::complete_dir_path(&app.text_input_buffer) {
+                app.text_input_buffer = completed;
             }
         }
         KeyCode::Backspace => {
@@ -2430,6 +4712,27 @@ fn handle_text_input(app: &mut App, context: TextInputContext, key: KeyEvent) ->
                         || c == '('
                         || c == ')'
                 }
+                TextInputContext::IvshmemResolution => c.is_ascii_digit() || c == 'x' || c == 'X',
+                TextInputContext::IsoChecksum(_) => c.is_ascii_hexdigit(),
+                TextInputContext::IsoDownloadUrl => c.is_ascii_graphic(),
+                TextInputContext::BalloonTarget => c.is_ascii_digit(),
+                TextInputContext::EmptyTrash => c.is_ascii_alphabetic(),
+                TextInputContext::RtcDate => c.is_ascii_digit() || matches!(c, '-' | ':' | 'T'),
+                TextInputContext::DiskThrottle(field) => {
+                    c.is_ascii_digit()
+                        || (matches!(
+                            field,
+                            crate::vm::qemu_config::ThrottleField::BpsRead
+                                | crate::vm::qemu_config::ThrottleField::BpsWrite
+                        ) && matches!(c, 'B' | 'b'))
+                }
+                TextInputContext::TemplateName => !c.is_control(),
+                TextInputContext::SshUser => {
+                    c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.')
+                }
+                TextInputContext::FileBrowserPath
+                | TextInputContext::KernelAppend
+                | TextInputContext::WindowTitle => !c.is_control(),
             };
             if allowed {
                 app.text_input_buffer.push(c);
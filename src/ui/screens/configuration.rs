@@ -236,7 +236,12 @@ pub fn render_raw_script(app: &App, frame: &mut Frame) {
     };
 
     let block = Block::default()
-        .title(format!(" {} - launch.sh{} ", vm_name, modified_indicator))
+        .title(format!(
+            " {} - {}{} ",
+            vm_name,
+            app.script_editor_hook.unwrap_or("launch.sh"),
+            modified_indicator
+        ))
         .borders(Borders::ALL)
         .border_style(if app.script_editor_modified {
             Style::default().fg(Color::Yellow)
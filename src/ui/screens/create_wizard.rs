@@ -9,12 +9,15 @@ use ratatui::{
     prelude::*,
     widgets::{Block, Borders, Clear, Paragraph, Wrap},
 };
+use std::path::Path;
 
 use crate::app::{
-    App, DiskAction, DiskImageFormat, FileBrowserMode, WizardField, WizardQemuConfig, WizardStep,
+    App, BackgroundResult, ConfirmAction, DiskAction, DiskImageFormat, FileBrowserMode, Screen,
+    TextInputContext, WizardField, WizardQemuConfig, WizardStep,
 };
-use crate::metadata::QemuProfileStore;
+use crate::metadata::{QemuProfileStore, VmTemplate};
 use crate::vm::create::create_vm_with_disk_format;
+use crate::vm::qemu_config::{CpuTopology, QemuEmulator, TopologyDim};
 
 /// Parse a size string with optional suffix (KB, MB, GB, case-insensitive)
 /// Returns value normalized to target unit.
@@ -87,6 +90,9 @@ pub fn render(app: &App, frame: &mut Frame) {
     if app.wizard_editing_port_forwards {
         render_wizard_port_forward_editor(app, frame, dialog_area);
     }
+    if app.wizard_copy_from.is_some() {
+        render_wizard_copy_picker(app, frame, dialog_area);
+    }
 }
 
 /// Render custom OS entry form
@@ -124,7 +130,7 @@ pub fn render_custom_os(app: &App, frame: &mut Frame) {
             Constraint::Length(3), // Architecture
             Constraint::Length(1), // Spacer
             Constraint::Length(5), // Base profile selection
-            Constraint::Length(1), // Spacer
+            Constraint::Length(1), // Save as profile toggle
             Constraint::Min(3),    // Tips
             Constraint::Length(2), // Help
         ])
@@ -244,6 +250,17 @@ pub fn render_custom_os(app: &App, frame: &mut Frame) {
     let base_text = Paragraph::new(base_lines);
     frame.render_widget(base_text, base_inner);
 
+    // Save as profile toggle
+    let save_to_user = custom_os.map(|c| c.save_to_user).unwrap_or(false);
+    frame.render_widget(
+        Paragraph::new(render_toggle_line(
+            "Save as profile for future VMs:",
+            save_to_user,
+            state.field_focus == 4,
+        )),
+        chunks[7],
+    );
+
     // Tips
     let tips_block = Block::default()
         .title(" Tip ")
@@ -255,6 +272,7 @@ pub fn render_custom_os(app: &App, frame: &mut Frame) {
 
     let tips_text = Paragraph::new(
         "You can adjust QEMU settings in step 4.\n\
+         Saved profiles go to the user metadata folder and show up in the OS list.\n\
          Consider contributing new OS profiles to the project!",
     )
     .style(Style::default().fg(Color::DarkGray))
@@ -262,10 +280,14 @@ pub fn render_custom_os(app: &App, frame: &mut Frame) {
     frame.render_widget(tips_text, tips_inner);
 
     // Help
-    let help = Paragraph::new("[Tab] Next field  [Enter] Continue  [Esc] Cancel")
-        .style(Style::default().fg(Color::DarkGray))
-        .alignment(Alignment::Center);
-    frame.render_widget(help, chunks[9]);
+    let help = match state.error_message {
+        Some(ref error) => Paragraph::new(error.as_str()).style(Style::default().fg(Color::Red)),
+        None => {
+            Paragraph::new("[Tab] Next field  [Space] Edit/toggle  [Enter] Continue  [Esc] Cancel")
+                .style(Style::default().fg(Color::DarkGray))
+        }
+    };
+    frame.render_widget(help.alignment(Alignment::Center), chunks[9]);
 }
 
 fn render_input_field(
@@ -354,6 +376,7 @@ fn get_base_profile_display(profile_id: &str) -> &'static str {
         "generic-linux" => "Generic Linux (modern, virtio)",
         "generic-windows" => "Generic Windows (SATA, e1000)",
         "generic-bsd" => "Generic BSD (IDE, pcnet)",
+        "generic-macos" => "Generic macOS (Intel, Apple SMC)",
         "linux-debian" => "Debian-based Linux",
         "linux-fedora" => "Fedora/RHEL-based Linux",
         "linux-arch" => "Arch Linux",
@@ -361,12 +384,17 @@ fn get_base_profile_display(profile_id: &str) -> &'static str {
     }
 }
 
+/// Fields on the custom OS form: name, publisher, architecture, base
+/// profile, save as profile
+const CUSTOM_OS_FIELDS: usize = 5;
+
 const ARCH_OPTIONS: &[&str] = &["x86_64", "i386", "arm64", "ppc64", "mips64", "riscv64"];
 const BASE_PROFILE_OPTIONS: &[&str] = &[
     "generic-other",
     "generic-linux",
     "generic-windows",
     "generic-bsd",
+    "generic-macos",
     "linux-debian",
     "linux-fedora",
     "linux-arch",
@@ -390,19 +418,49 @@ pub fn render_download(app: &App, frame: &mut Frame) {
     let inner = block.inner(dialog_area);
     frame.render_widget(block, dialog_area);
 
-    let progress = app
+    let (progress, bytes) = app
         .wizard_state
         .as_ref()
-        .map(|s| s.iso_download_progress)
-        .unwrap_or(0.0);
+        .map(|s| (s.iso_download_progress, s.iso_download_bytes))
+        .unwrap_or((0.0, 0));
+    let mb = bytes as f64 / (1024.0 * 1024.0);
 
-    let text = Paragraph::new(format!(
-        "Downloading... {:.0}%\n\n[Esc] Cancel",
-        progress * 100.0
-    ))
-    .style(Style::default().fg(Color::White))
-    .alignment(Alignment::Center);
-    frame.render_widget(text, inner);
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .margin(1)
+        .constraints([
+            Constraint::Length(1), // Status
+            Constraint::Length(1), // Spacer
+            Constraint::Length(1), // Gauge
+            Constraint::Min(0),    // Spacer
+            Constraint::Length(1), // Help
+        ])
+        .split(inner);
+
+    // Without a Content-Length only the byte count is known
+    let status = if progress > 0.0 {
+        format!("Downloading... {:.0}% ({:.0} MB)", progress * 100.0, mb)
+    } else {
+        format!("Downloading... {:.0} MB", mb)
+    };
+    frame.render_widget(
+        Paragraph::new(status)
+            .style(Style::default().fg(Color::White))
+            .alignment(Alignment::Center),
+        chunks[0],
+    );
+    if progress > 0.0 {
+        let gauge = ratatui::widgets::Gauge::default()
+            .gauge_style(Style::default().fg(Color::Yellow).bg(Color::DarkGray))
+            .ratio(f64::from(progress).clamp(0.0, 1.0));
+        frame.render_widget(gauge, chunks[2]);
+    }
+    frame.render_widget(
+        Paragraph::new("[Esc] Cancel (resumes next time)")
+            .style(Style::default().fg(Color::DarkGray))
+            .alignment(Alignment::Center),
+        chunks[4],
+    );
 }
 
 /// Handle key input for wizard
@@ -437,7 +495,7 @@ pub fn handle_custom_os_key(app: &mut App, key: KeyEvent) -> Result<()> {
                     state.editing_field = None;
                     if key.code == KeyCode::Tab {
                         // Move to next field
-                        state.field_focus = (state.field_focus + 1) % 4;
+                        state.field_focus = (state.field_focus + 1) % CUSTOM_OS_FIELDS;
                     }
                 }
             }
@@ -481,13 +539,13 @@ pub fn handle_custom_os_key(app: &mut App, key: KeyEvent) -> Result<()> {
             }
             KeyCode::Tab | KeyCode::Char('j') | KeyCode::Down => {
                 if let Some(ref mut state) = app.wizard_state {
-                    state.field_focus = (state.field_focus + 1) % 4;
+                    state.field_focus = (state.field_focus + 1) % CUSTOM_OS_FIELDS;
                 }
             }
             KeyCode::BackTab | KeyCode::Char('k') | KeyCode::Up => {
                 if let Some(ref mut state) = app.wizard_state {
                     state.field_focus = if state.field_focus == 0 {
-                        3
+                        CUSTOM_OS_FIELDS - 1
                     } else {
                         state.field_focus - 1
                     };
@@ -535,6 +593,11 @@ pub fn handle_custom_os_key(app: &mut App, key: KeyEvent) -> Result<()> {
                     match state.field_focus {
                         0 => state.editing_field = Some(WizardField::CustomOsName),
                         1 => state.editing_field = Some(WizardField::CustomOsPublisher),
+                        4 => {
+                            if let Some(ref mut custom) = state.custom_os {
+                                custom.save_to_user = !custom.save_to_user;
+                            }
+                        }
                         _ => {}
                     }
                 }
@@ -548,7 +611,31 @@ pub fn handle_custom_os_key(app: &mut App, key: KeyEvent) -> Result<()> {
                     .map(|c| !c.name.trim().is_empty())
                     .unwrap_or(false);
 
-                if valid {
+                let save_to_user = app
+                    .wizard_state
+                    .as_ref()
+                    .and_then(|s| s.custom_os.as_ref())
+                    .map(|c| c.save_to_user)
+                    .unwrap_or(false);
+
+                if valid && save_to_user {
+                    // The saved profile is picked like any built-in one
+                    match app.save_custom_os_profile() {
+                        Ok(id) => {
+                            if let Some(ref mut state) = app.wizard_state {
+                                state.error_message = None;
+                            }
+                            app.wizard_select_os(&id);
+                            app.pop_screen();
+                            app.set_status(format!("Saved OS profile '{}'", id));
+                        }
+                        Err(e) => {
+                            if let Some(ref mut state) = app.wizard_state {
+                                state.error_message = Some(format!("{:#}", e));
+                            }
+                        }
+                    }
+                } else if valid {
                     // Extract needed data first
                     let (base_profile_id, custom_name, vm_name_empty) = {
                         let state = app.wizard_state.as_ref().unwrap();
@@ -565,6 +652,7 @@ pub fn handle_custom_os_key(app: &mut App, key: KeyEvent) -> Result<()> {
 
                     // Now apply changes
                     if let Some(ref mut state) = app.wizard_state {
+                        state.error_message = None;
                         // Apply profile settings
                         if let Some(profile) = profile_settings {
                             state.qemu_config = WizardQemuConfig::from_profile(&profile);
@@ -607,12 +695,7 @@ pub fn handle_custom_os_key(app: &mut App, key: KeyEvent) -> Result<()> {
 /// Handle key input for download screen
 pub fn handle_download_key(app: &mut App, key: KeyEvent) -> Result<()> {
     if key.code == KeyCode::Esc {
-        // Cancel download
-        if let Some(ref mut state) = app.wizard_state {
-            state.iso_downloading = false;
-            state.iso_download_progress = 0.0;
-        }
-        app.pop_screen();
+        app.cancel_iso_download();
     }
     Ok(())
 }
@@ -646,7 +729,7 @@ fn render_step_select_os(app: &App, frame: &mut Frame, area: Rect) {
             Constraint::Min(10),   // OS list
             Constraint::Length(1), // Spacer
             Constraint::Length(3), // VM Name field
-            Constraint::Length(1), // Error message
+            Constraint::Length(2), // Error message / license note
             Constraint::Length(2), // Help text
         ])
         .split(inner);
@@ -698,17 +781,22 @@ fn render_step_select_os(app: &App, frame: &mut Frame, area: Rect) {
         frame.set_cursor_position((cursor_x, cursor_y));
     }
 
-    // Error message
+    // Error message, else the selected profile's license caveat
     if let Some(ref error) = state.error_message {
         let error_text = Paragraph::new(error.as_str()).style(Style::default().fg(Color::Red));
         frame.render_widget(error_text, chunks[4]);
+    } else if let Some(note) = app.wizard_selected_profile().and_then(|p| p.legal_note()) {
+        let note_text = Paragraph::new(note)
+            .style(Style::default().fg(Color::Yellow))
+            .wrap(Wrap { trim: true });
+        frame.render_widget(note_text, chunks[4]);
     }
 
     // Help text
     let help_text = if name_editing {
         "[Enter] Done editing  [Esc] Cancel"
     } else {
-        "[j/k] Select OS  [Tab] Edit name  [Enter] Next  [Esc] Cancel"
+        "[j/k] Select OS  [Tab] Edit name  [Enter] Next  [x] Delete template  [Esc] Cancel"
     };
     let help = Paragraph::new(help_text)
         .style(Style::default().fg(Color::DarkGray))
@@ -730,6 +818,52 @@ fn render_os_list(app: &App, frame: &mut Frame, area: Rect) {
     let mut lines: Vec<Line> = Vec::new();
     let mut item_index = 0;
 
+    // Saved templates come first, as their own collapsible group
+    let templates = listed_templates(app, &state.os_filter);
+    if !app.vm_templates.is_empty() {
+        let is_expanded = state.is_category_expanded(TEMPLATES_GROUP);
+        let is_selected = item_index == state.os_list_selected;
+        let style = if is_selected {
+            Style::default()
+                .fg(Color::Yellow)
+                .add_modifier(Modifier::BOLD)
+        } else {
+            Style::default()
+                .fg(Color::Cyan)
+                .add_modifier(Modifier::BOLD)
+        };
+        lines.push(Line::from(vec![
+            Span::styled(if is_selected { "> " } else { "  " }, style),
+            Span::styled(if is_expanded { "v" } else { ">" }, style),
+            Span::styled(" Templates", style),
+        ]));
+        item_index += 1;
+
+        if is_expanded {
+            for (name, template) in &templates {
+                let is_item_selected = item_index == state.os_list_selected;
+                let is_chosen = state.template.as_ref() == Some(*name);
+                let style = if is_item_selected {
+                    Style::default().fg(Color::Yellow)
+                } else if is_chosen {
+                    Style::default().fg(Color::Green)
+                } else {
+                    Style::default().fg(Color::White)
+                };
+                lines.push(Line::from(vec![
+                    Span::styled(if is_item_selected { "> " } else { "  " }, style),
+                    Span::styled(format!("   {}", if is_chosen { "*" } else { " " }), style),
+                    Span::styled(name.to_string(), style),
+                    Span::styled(
+                        format!("  ({})", template.summary()),
+                        Style::default().fg(Color::DarkGray),
+                    ),
+                ]));
+                item_index += 1;
+            }
+        }
+    }
+
     // Get categories in display order
     let category_order = [
         "windows",
@@ -917,6 +1051,11 @@ fn handle_step_select_os(app: &mut App, key: KeyEvent) -> Result<()> {
                 // Select OS or expand category, then proceed if valid
                 handle_os_list_action(app, true);
             }
+            KeyCode::Char('x') | KeyCode::Delete => {
+                if let Some(name) = selected_template(app) {
+                    app.push_screen(Screen::Confirm(ConfirmAction::DeleteTemplate(name)));
+                }
+            }
             _ => {}
         }
     }
@@ -941,6 +1080,12 @@ fn count_os_list_items(app: &App) -> usize {
     ];
 
     let mut count = 0;
+    if !app.vm_templates.is_empty() {
+        count += 1; // Templates header
+        if state.is_category_expanded(TEMPLATES_GROUP) {
+            count += listed_templates(app, &state.os_filter).len();
+        }
+    }
     for category in &category_order {
         let profiles = app.qemu_profiles.list_by_category(category);
         if profiles.is_empty() {
@@ -993,7 +1138,25 @@ fn handle_os_list_action(app: &mut App, proceed: bool) {
     let mut item_index = 0;
     let mut action: Option<OsListAction> = None;
 
+    if !app.vm_templates.is_empty() {
+        if item_index == selected {
+            action = Some(OsListAction::ToggleCategory(TEMPLATES_GROUP.to_string()));
+        }
+        item_index += 1;
+        if expanded_categories.iter().any(|c| c == TEMPLATES_GROUP) {
+            for (name, _) in listed_templates(app, &os_filter) {
+                if action.is_none() && item_index == selected {
+                    action = Some(OsListAction::UseTemplate(name.clone()));
+                }
+                item_index += 1;
+            }
+        }
+    }
+
     for category in &category_order {
+        if action.is_some() {
+            break;
+        }
         let profiles = app.qemu_profiles.list_by_category(category);
         if profiles.is_empty() {
             continue;
@@ -1054,6 +1217,16 @@ fn handle_os_list_action(app: &mut App, proceed: bool) {
                 }
             }
         }
+        Some(OsListAction::UseTemplate(name)) => {
+            app.wizard_use_template(&name);
+            if proceed {
+                if let Err(e) = app.wizard_next_step() {
+                    if let Some(ref mut state) = app.wizard_state {
+                        state.error_message = Some(e);
+                    }
+                }
+            }
+        }
         Some(OsListAction::CustomOs) => {
             app.wizard_use_custom_os();
         }
@@ -1065,9 +1238,46 @@ fn handle_os_list_action(app: &mut App, proceed: bool) {
 enum OsListAction {
     ToggleCategory(String),
     SelectOs(String),
+    UseTemplate(String),
     CustomOs,
 }
 
+/// Group key for saved templates in the OS list's expanded categories
+const TEMPLATES_GROUP: &str = "templates";
+
+/// Saved templates matching the OS list filter, sorted by name
+fn listed_templates<'a>(app: &'a App, filter: &str) -> Vec<(&'a String, &'a VmTemplate)> {
+    let filter = filter.to_lowercase();
+    app.vm_templates
+        .list()
+        .into_iter()
+        .filter(|(name, _)| filter.is_empty() || name.to_lowercase().contains(&filter))
+        .collect()
+}
+
+/// Name of the template under the OS list cursor, if it's on one
+fn selected_template(app: &App) -> Option<String> {
+    let state = app.wizard_state.as_ref()?;
+    if app.vm_templates.is_empty() || !state.is_category_expanded(TEMPLATES_GROUP) {
+        return None;
+    }
+    // Row 0 is the group header
+    let index = state.os_list_selected.checked_sub(1)?;
+    listed_templates(app, &state.os_filter)
+        .get(index)
+        .map(|(name, _)| name.to_string())
+}
+
+/// Ask for a name to save the wizard's current settings under
+fn open_save_template(app: &mut App) {
+    app.text_input_buffer = app
+        .wizard_state
+        .as_ref()
+        .and_then(|s| s.template.clone())
+        .unwrap_or_default();
+    app.push_screen(Screen::TextInput(TextInputContext::TemplateName));
+}
+
 // =============================================================================
 // Step 2: Select ISO
 // =============================================================================
@@ -1096,7 +1306,7 @@ fn render_step_select_iso(app: &App, frame: &mut Frame, area: Rect) {
             Constraint::Length(1), // Spacer
             Constraint::Length(1), // Header
             Constraint::Min(10),   // Options
-            Constraint::Length(1), // Selected path
+            Constraint::Length(2), // Selected path + detected OS
             Constraint::Length(2), // Help
         ])
         .split(inner);
@@ -1192,6 +1402,19 @@ fn render_step_select_iso(app: &App, frame: &mut Frame, area: Rect) {
             style,
         ));
         option_idx += 1;
+
+        let is_selected = state.field_focus == option_idx;
+        let style = if is_selected {
+            Style::default().fg(Color::Yellow)
+        } else {
+            Style::default().fg(Color::White)
+        };
+        let prefix = if is_selected { "> " } else { "  " };
+        lines.push(Line::styled(
+            format!("{}( ) Download ISO to VM library", prefix),
+            style,
+        ));
+        option_idx += 1;
     }
 
     // Floppy image option (for OSes that need a boot floppy, e.g., OS/2)
@@ -1262,19 +1485,59 @@ fn render_step_select_iso(app: &App, frame: &mut Frame, area: Rect) {
         } else {
             "Selected ISO"
         };
-        let path_text = Paragraph::new(format!("{}: {}", label, path.display()))
-            .style(Style::default().fg(Color::Green));
-        frame.render_widget(path_text, chunks[4]);
+        let mut lines = vec![Line::styled(
+            format!("{}: {}", label, path.display()),
+            Style::default().fg(Color::Green),
+        )];
+        if let Some(ref detected) = state.iso_detection {
+            lines.push(Line::styled(
+                format!(
+                    "Detected: {} \u{2014} use recommended settings?",
+                    detected.description
+                ),
+                Style::default()
+                    .fg(Color::Yellow)
+                    .add_modifier(Modifier::BOLD),
+            ));
+        }
+        frame.render_widget(Paragraph::new(lines), chunks[4]);
     }
 
     // Help
-    let help = Paragraph::new("[j/k] Select  [Enter] Choose  [Esc] Back")
+    let help_text = if state.iso_detection.is_some() {
+        "[y] Use recommended settings  [n] Keep current OS"
+    } else {
+        "[j/k] Select  [Enter] Choose  [Esc] Back"
+    };
+    let help = Paragraph::new(help_text)
         .style(Style::default().fg(Color::DarkGray))
         .alignment(Alignment::Center);
     frame.render_widget(help, chunks[5]);
 }
 
 fn handle_step_select_iso(app: &mut App, key: KeyEvent) -> Result<()> {
+    // A detected OS waits for an answer before moving on
+    if let Some(detected) = app
+        .wizard_state
+        .as_ref()
+        .and_then(|s| s.iso_detection.clone())
+    {
+        let accept = match key.code {
+            KeyCode::Char('y') | KeyCode::Char('Y') | KeyCode::Enter => true,
+            KeyCode::Char('n') | KeyCode::Char('N') | KeyCode::Esc => false,
+            _ => return Ok(()),
+        };
+        if let Some(ref mut state) = app.wizard_state {
+            state.iso_detection = None;
+        }
+        if accept {
+            app.wizard_select_os(&detected.profile_id);
+        }
+        let _ = app.wizard_next_step();
+        app.offer_iso_verification();
+        return Ok(());
+    }
+
     let has_download = app
         .wizard_state
         .as_ref()
@@ -1291,7 +1554,8 @@ fn handle_step_select_iso(app: &mut App, key: KeyEvent) -> Result<()> {
         .and_then(|p| p.bios_rom.as_ref())
         .is_some();
 
-    // Compute option indices matching the render order: ROM, download, floppy, browse, recovery, skip
+    // Compute option indices matching the render order:
+    // ROM, download page, download, floppy, browse, recovery, skip
     let mut idx = 0;
     let rom_idx = if has_bios_rom {
         let i = idx;
@@ -1302,11 +1566,12 @@ fn handle_step_select_iso(app: &mut App, key: KeyEvent) -> Result<()> {
     };
     let download_idx = if has_download {
         let i = idx;
-        idx += 1;
+        idx += 2;
         Some(i)
     } else {
         None
     };
+    let fetch_idx = download_idx.map(|i| i + 1);
     let floppy_idx = idx;
     idx += 1;
     let browse_idx = idx;
@@ -1344,7 +1609,7 @@ fn handle_step_select_iso(app: &mut App, key: KeyEvent) -> Result<()> {
 
             if Some(focus) == rom_idx {
                 // Browse for ROM/BIOS file
-                app.load_file_browser(crate::app::FileBrowserMode::Bios);
+                app.open_file_browser(crate::app::FileBrowserMode::Bios);
                 app.push_screen(crate::app::Screen::FileBrowser);
             } else if Some(focus) == download_idx {
                 // Open download page in browser
@@ -1362,18 +1627,34 @@ fn handle_step_select_iso(app: &mut App, key: KeyEvent) -> Result<()> {
                         app.set_status("Opened download page in browser. Use 'Browse for ISO' after downloading.");
                     }
                 }
+            } else if Some(focus) == fetch_idx {
+                let url = app
+                    .wizard_state
+                    .as_ref()
+                    .and_then(|s| s.selected_os.as_ref())
+                    .and_then(|id| app.qemu_profiles.get(id))
+                    .and_then(|p| p.iso_url.clone())
+                    .unwrap_or_default();
+                if crate::commands::download::is_direct_image_url(&url) {
+                    app.start_iso_download(url);
+                } else {
+                    // Profiles mostly link a download page; ask for the ISO link on it
+                    app.text_input_buffer.clear();
+                    app.push_screen(crate::app::Screen::TextInput(
+                        crate::app::TextInputContext::IsoDownloadUrl,
+                    ));
+                }
             } else if focus == floppy_idx {
                 // Browse for floppy image - open file browser
-                app.load_file_browser(crate::app::FileBrowserMode::Floppy);
+                app.open_file_browser(crate::app::FileBrowserMode::Floppy);
                 app.push_screen(crate::app::Screen::FileBrowser);
             } else if focus == browse_idx {
                 // Browse for ISO - open file browser
-                app.seed_iso_browser_dir();
-                app.load_file_browser(crate::app::FileBrowserMode::Iso);
+                app.open_file_browser(crate::app::FileBrowserMode::Iso);
                 app.push_screen(crate::app::Screen::FileBrowser);
             } else if focus == recovery_browse_idx {
                 // Browse for recovery image (DMG) - open file browser
-                app.load_file_browser(crate::app::FileBrowserMode::RecoveryImage);
+                app.open_file_browser(crate::app::FileBrowserMode::RecoveryImage);
                 app.push_screen(crate::app::Screen::FileBrowser);
             } else if focus == no_iso_idx {
                 // Skip - check if ROM is required but missing
@@ -1881,7 +2162,7 @@ fn handle_step_configure_disk(app: &mut App, key: KeyEvent) -> Result<()> {
         KeyCode::Enter => {
             // If on browse button in existing mode, open file browser
             if use_existing && field_focus == 1 {
-                app.load_file_browser(FileBrowserMode::Disk);
+                app.open_file_browser(FileBrowserMode::Disk);
                 app.push_screen(crate::app::Screen::FileBrowser);
             } else {
                 // Try to proceed to next step
@@ -1916,10 +2197,17 @@ const AUDIO_OPTIONS: &[(&str, &[&str])] = &[
 /// Fields in the QEMU config screen
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 enum QemuField {
+    Emulator,
     Memory,
+    MaxMemory,
     CpuCores,
+    Topology,
+    Sockets,
+    Cores,
+    Threads,
     Vga,
     Audio,
+    AudioBackend,
     Network,
     NetBackend,
     BridgeName,
@@ -1927,39 +2215,58 @@ enum QemuField {
     MacAddress,
     DiskInterface,
     Display,
+    Keymap,
     Kvm,
+    NestedVirt,
     GlAccel,
     Uefi,
     Tpm,
-    UsbTablet,
+    SecureBoot,
+    Input,
     RtcLocal,
+    GuestAgent,
+    Balloon,
+    Clipboard,
 }
 
 impl QemuField {
     fn from_index(idx: usize) -> Self {
         match idx {
-            0 => Self::Memory,
-            1 => Self::CpuCores,
-            2 => Self::Vga,
-            3 => Self::Audio,
-            4 => Self::Network,
-            5 => Self::NetBackend,
-            6 => Self::BridgeName,
-            7 => Self::PortForwards,
-            8 => Self::MacAddress,
-            9 => Self::DiskInterface,
-            10 => Self::Display,
-            11 => Self::Kvm,
-            12 => Self::GlAccel,
-            13 => Self::Uefi,
-            14 => Self::Tpm,
-            15 => Self::UsbTablet,
-            _ => Self::RtcLocal,
+            0 => Self::Emulator,
+            1 => Self::Memory,
+            2 => Self::MaxMemory,
+            3 => Self::CpuCores,
+            4 => Self::Topology,
+            5 => Self::Sockets,
+            6 => Self::Cores,
+            7 => Self::Threads,
+            8 => Self::Vga,
+            9 => Self::Audio,
+            10 => Self::AudioBackend,
+            11 => Self::Network,
+            12 => Self::NetBackend,
+            13 => Self::BridgeName,
+            14 => Self::PortForwards,
+            15 => Self::MacAddress,
+            16 => Self::DiskInterface,
+            17 => Self::Display,
+            18 => Self::Keymap,
+            19 => Self::Kvm,
+            20 => Self::NestedVirt,
+            21 => Self::GlAccel,
+            22 => Self::Uefi,
+            23 => Self::Tpm,
+            24 => Self::SecureBoot,
+            25 => Self::Input,
+            26 => Self::RtcLocal,
+            27 => Self::GuestAgent,
+            28 => Self::Balloon,
+            _ => Self::Clipboard,
         }
     }
 
     fn count() -> usize {
-        17
+        30
     }
 
     /// Whether this field is currently rendered in step 4. Mirrors the
@@ -1975,6 +2282,12 @@ impl QemuField {
             PortForwards => {
                 net_on && (config.network_backend == "user" || config.network_backend == "passt")
             }
+            NestedVirt => config.enable_kvm,
+            AudioBackend => !config.audio.is_empty(),
+            Clipboard => config.display == "spice-app",
+            Keymap => config.display != "none",
+            SecureBoot => config.uefi,
+            Sockets | Cores | Threads => config.cpu_topology.is_some(),
             _ => true,
         }
     }
@@ -2067,8 +2380,26 @@ fn render_step_configure_qemu(app: &App, frame: &mut Frame, area: Rect) {
     let editing = state.editing_field.is_some();
     let mut lines = Vec::new();
 
+    // Emulator (cycle through installed qemu-system-* binaries)
+    let emu_selected = focus == 0;
+    let emu_installed = app.available_emulators.contains(&config.emulator);
+    let emu_value = QemuEmulator::from_command(&config.emulator)
+        .architecture()
+        .to_string();
+    lines.push(render_field_line(
+        "Emulator:",
+        &emu_value,
+        emu_selected,
+        false,
+        if emu_installed {
+            "[←/→] cycle"
+        } else {
+            "[←/→] cycle  (not installed!)"
+        },
+    ));
+
     // Memory (editable)
-    let mem_selected = focus == 0;
+    let mem_selected = focus == 1;
     let mem_editing = matches!(state.editing_field, Some(WizardField::MemoryMb));
     let mem_value = if mem_editing {
         format!("{}|", state.wizard_edit_buffer)
@@ -2090,8 +2421,22 @@ fn render_step_configure_qemu(app: &App, frame: &mut Frame, area: Rect) {
         mem_hint,
     ));
 
+    // Max memory (hotplug ceiling, off by default)
+    let max_mem_selected = focus == 2;
+    let max_mem_value = match config.max_memory_mb {
+        Some(max) => format!("{} MB", max),
+        None => "off".to_string(),
+    };
+    lines.push(render_field_line(
+        "Max Memory:",
+        &max_mem_value,
+        max_mem_selected,
+        false,
+        "[←/→] ±1GB",
+    ));
+
     // CPU Cores (editable)
-    let cpu_selected = focus == 1;
+    let cpu_selected = focus == 3;
     let cpu_editing = matches!(state.editing_field, Some(WizardField::CpuCores));
     let cpu_value = if cpu_editing {
         format!("{}|", state.wizard_edit_buffer)
@@ -2113,8 +2458,37 @@ fn render_step_configure_qemu(app: &App, frame: &mut Frame, area: Rect) {
         cpu_hint,
     ));
 
+    // CPU topology (toggle an explicit sockets/cores/threads split)
+    let topo_selected = focus == 4;
+    let topo_value = match config.cpu_topology {
+        Some(t) => format!("{}s x {}c x {}t", t.sockets, t.cores, t.threads),
+        None => "1 socket".to_string(),
+    };
+    lines.push(render_field_line(
+        "Topology:",
+        &topo_value,
+        topo_selected,
+        false,
+        "[Space] custom",
+    ));
+    if let Some(topology) = config.cpu_topology {
+        for (idx, label, value) in [
+            (5, "  Sockets:", topology.sockets),
+            (6, "  Cores:", topology.cores),
+            (7, "  Threads:", topology.threads),
+        ] {
+            lines.push(render_field_line(
+                label,
+                &value.to_string(),
+                focus == idx,
+                false,
+                "[←/→] rebalance",
+            ));
+        }
+    }
+
     // VGA (cycle)
-    let vga_selected = focus == 2;
+    let vga_selected = focus == 8;
     lines.push(render_field_line(
         "Graphics:",
         &config.vga,
@@ -2124,7 +2498,7 @@ fn render_step_configure_qemu(app: &App, frame: &mut Frame, area: Rect) {
     ));
 
     // Audio (cycle)
-    let audio_selected = focus == 3;
+    let audio_selected = focus == 9;
     let audio_label = get_audio_label(&config.audio);
     lines.push(render_field_line(
         "Audio:",
@@ -2134,8 +2508,20 @@ fn render_step_configure_qemu(app: &App, frame: &mut Frame, area: Rect) {
         "[←/→] cycle",
     ));
 
+    // Host audio backend (cycle) - hidden when there are no audio devices
+    if QemuField::AudioBackend.is_visible(config) {
+        let backend_selected = focus == 10;
+        lines.push(render_field_line(
+            "Audio out:",
+            &config.audio_backend,
+            backend_selected,
+            false,
+            "[←/→] cycle",
+        ));
+    }
+
     // Network adapter (cycle)
-    let net_selected = focus == 4;
+    let net_selected = focus == 11;
     lines.push(render_field_line(
         "Network:",
         &config.network_model,
@@ -2146,7 +2532,7 @@ fn render_step_configure_qemu(app: &App, frame: &mut Frame, area: Rect) {
 
     // Network backend (cycle) - hidden if network model is "none"
     if QemuField::NetBackend.is_visible(config) {
-        let backend_selected = focus == 5;
+        let backend_selected = focus == 12;
         let backend_display = match config.network_backend.as_str() {
             "user" => "user/SLIRP (NAT)".to_string(),
             "passt" => "passt".to_string(),
@@ -2167,7 +2553,7 @@ fn render_step_configure_qemu(app: &App, frame: &mut Frame, area: Rect) {
 
         // Bridge name (only for bridge backend)
         if QemuField::BridgeName.is_visible(config) {
-            let bridge_selected = focus == 6;
+            let bridge_selected = focus == 13;
             let bridge_display = config.bridge_name.as_deref().unwrap_or("qemubr0");
             lines.push(render_field_line(
                 "Bridge:",
@@ -2180,7 +2566,7 @@ fn render_step_configure_qemu(app: &App, frame: &mut Frame, area: Rect) {
 
         // Port forwards (only for user/passt)
         if QemuField::PortForwards.is_visible(config) {
-            let pf_selected = focus == 7;
+            let pf_selected = focus == 14;
             let pf_display = if config.port_forwards.is_empty() {
                 "none".to_string()
             } else {
@@ -2196,7 +2582,7 @@ fn render_step_configure_qemu(app: &App, frame: &mut Frame, area: Rect) {
         }
 
         // MAC address (text input, hidden when network model is "none")
-        let mac_selected = focus == 8;
+        let mac_selected = focus == 15;
         let mac_editing = matches!(state.editing_field, Some(WizardField::MacAddress));
         let mac_value = if mac_editing {
             format!("{}|", state.wizard_edit_buffer)
@@ -2222,7 +2608,7 @@ fn render_step_configure_qemu(app: &App, frame: &mut Frame, area: Rect) {
     }
 
     // Disk Interface (cycle)
-    let disk_selected = focus == 9;
+    let disk_selected = focus == 16;
     lines.push(render_field_line(
         "Disk I/F:",
         &config.disk_interface,
@@ -2232,7 +2618,7 @@ fn render_step_configure_qemu(app: &App, frame: &mut Frame, area: Rect) {
     ));
 
     // Display (cycle)
-    let disp_selected = focus == 10;
+    let disp_selected = focus == 17;
     lines.push(render_field_line(
         "Display:",
         &config.display,
@@ -2241,6 +2627,18 @@ fn render_step_configure_qemu(app: &App, frame: &mut Frame, area: Rect) {
         "[←/→] cycle",
     ));
 
+    // Keyboard layout (cycle; headless VMs have no keyboard)
+    if QemuField::Keymap.is_visible(config) {
+        let keymap_selected = focus == 18;
+        lines.push(render_field_line(
+            "Keyboard:",
+            config.keymap.as_deref().unwrap_or("default"),
+            keymap_selected,
+            false,
+            "[←/→] cycle",
+        ));
+    }
+
     lines.push(Line::from(""));
     lines.push(Line::styled(
         "  Features (toggle with Space):",
@@ -2248,15 +2646,25 @@ fn render_step_configure_qemu(app: &App, frame: &mut Frame, area: Rect) {
     ));
 
     // KVM toggle
-    let kvm_selected = focus == 11;
+    let kvm_selected = focus == 19;
     lines.push(render_toggle_line(
         "KVM Accel:",
         config.enable_kvm,
         kvm_selected,
     ));
 
+    // Nested virtualization toggle (only meaningful with KVM)
+    if config.enable_kvm {
+        let nested_selected = focus == 20;
+        lines.push(render_toggle_line(
+            "Nested Virt:",
+            config.nested_virt.is_some(),
+            nested_selected,
+        ));
+    }
+
     // 3D/GL acceleration toggle
-    let gl_selected = focus == 12;
+    let gl_selected = focus == 21;
     lines.push(render_toggle_line(
         "3D Accel:",
         config.gl_acceleration,
@@ -2264,42 +2672,88 @@ fn render_step_configure_qemu(app: &App, frame: &mut Frame, area: Rect) {
     ));
 
     // UEFI toggle
-    let uefi_selected = focus == 13;
+    let uefi_selected = focus == 22;
     lines.push(render_toggle_line("UEFI Boot:", config.uefi, uefi_selected));
 
     // TPM toggle
-    let tpm_selected = focus == 14;
+    let tpm_selected = focus == 23;
     lines.push(render_toggle_line("TPM 2.0:", config.tpm, tpm_selected));
 
-    // USB Tablet toggle
-    let usb_selected = focus == 15;
-    lines.push(render_toggle_line(
-        "USB Tablet:",
-        config.usb_tablet,
-        usb_selected,
+    // Secure Boot toggle (UEFI only)
+    if config.uefi {
+        let secboot_selected = focus == 24;
+        lines.push(render_toggle_line(
+            "Secure Boot:",
+            config.secure_boot,
+            secboot_selected,
+        ));
+    }
+
+    // Input devices (cycle)
+    let input_selected = focus == 25;
+    lines.push(render_field_line(
+        "Input:",
+        config.input.label(),
+        input_selected,
+        false,
+        "[←/→] cycle",
     ));
 
     // RTC Local toggle
-    let rtc_selected = focus == 16;
+    let rtc_selected = focus == 26;
     lines.push(render_toggle_line(
         "RTC Local:",
         config.rtc_localtime,
         rtc_selected,
     ));
 
+    // Guest agent toggle
+    let qga_selected = focus == 27;
+    lines.push(render_toggle_line(
+        "Guest Agent:",
+        config.guest_agent,
+        qga_selected,
+    ));
+
+    // Memory balloon toggle
+    let balloon_selected = focus == 28;
+    lines.push(render_toggle_line(
+        "Balloon:",
+        config.balloon,
+        balloon_selected,
+    ));
+
+    // SPICE clipboard sharing toggle (only meaningful with spice-app)
+    if config.display == "spice-app" {
+        let clip_selected = focus == 29;
+        lines.push(render_toggle_line(
+            "Clipboard:",
+            config.clipboard_sharing,
+            clip_selected,
+        ));
+    }
+
     let settings = Paragraph::new(lines);
     frame.render_widget(settings, left_chunks[1]);
 
-    // Help text
-    let help_text = if editing {
-        "[Enter] Done  [Esc] Cancel  [←/→] Adjust"
+    // Help text (replaced by the validation error or a capacity warning)
+    let capacity_warnings = capacity_warnings(app, config);
+    let help = if let Some(ref error) = state.error_message {
+        Paragraph::new(error.as_str()).style(Style::default().fg(Color::Red))
+    } else if !editing && !capacity_warnings.is_empty() {
+        Paragraph::new(format!("Warning: {}", capacity_warnings.join("; ")))
+            .style(Style::default().fg(Color::Yellow))
+            .wrap(Wrap { trim: true })
+    } else if editing {
+        Paragraph::new("[Enter] Done  [Esc] Cancel  [←/→] Adjust")
+            .style(Style::default().fg(Color::DarkGray))
     } else {
-        "[j/k] Navigate  [Tab] Edit  [←/→] Change  [Space] Toggle  [Enter] Next"
+        Paragraph::new(
+            "[j/k] Navigate  [Tab] Edit  [←/→] Change  [Space] Toggle  [C] Copy from VM  [T] Save template  [Enter] Next",
+        )
+            .style(Style::default().fg(Color::DarkGray))
     };
-    let help = Paragraph::new(help_text)
-        .style(Style::default().fg(Color::DarkGray))
-        .alignment(Alignment::Center);
-    frame.render_widget(help, left_chunks[2]);
+    frame.render_widget(help.alignment(Alignment::Center), left_chunks[2]);
 
     // Right side: Notes header
     let notes_header = Paragraph::new("Why These Defaults?").style(
@@ -2413,12 +2867,29 @@ fn get_audio_label(audio: &[String]) -> &'static str {
     }
 }
 
+/// RAM and CPU warnings for the wizard's settings: above the profile's RAM
+/// cap, or more than the host can spare
+fn capacity_warnings(app: &App, config: &WizardQemuConfig) -> Vec<String> {
+    app.wizard_selected_profile()
+        .and_then(|p| p.memory_warning(config.memory_mb))
+        .into_iter()
+        .chain(
+            app.host_capacity
+                .map(|host| host.warnings(config.memory_mb, config.cpu_cores))
+                .unwrap_or_default(),
+        )
+        .collect()
+}
+
 fn get_field_notes(app: &App, focus: usize) -> String {
     let profile = app.wizard_selected_profile();
-    let profile_notes = profile
+    let mut profile_notes = profile
         .and_then(|p| p.notes.as_ref())
         .cloned()
         .unwrap_or_default();
+    if let Some(note) = profile.and_then(|p| p.legal_note()) {
+        profile_notes = format!("{}\n{}", note, profile_notes).trim().to_string();
+    }
     let os_name = profile
         .map(|p| p.display_name.as_str())
         .unwrap_or("this OS");
@@ -2426,22 +2897,80 @@ fn get_field_notes(app: &App, focus: usize) -> String {
     let field = QemuField::from_index(focus);
 
     let explanation = match field {
+        QemuField::Emulator => {
+            let emulator = app
+                .wizard_state
+                .as_ref()
+                .map(|s| s.qemu_config.emulator.as_str())
+                .unwrap_or("qemu-system-x86_64");
+            let installed = if app.available_emulators.is_empty() {
+                "none found".to_string()
+            } else {
+                app.available_emulators.join(", ")
+            };
+            format!(
+                "QEMU system emulator: {}.\n\n\
+                Changing it resets machine, CPU and devices to \
+                defaults for that architecture. KVM, UEFI and TPM \
+                are only available for x86 targets.\n\n\
+                Installed: {}",
+                emulator, installed
+            )
+        }
         QemuField::Memory => format!(
             "RAM for {}.\n\n\
             Modern OSes need 4GB+. Older systems may crash with too much RAM.\n\n\
             Windows 95: max 480MB\n\
             Windows 98/ME: max 512MB\n\
             Windows XP: 512MB-1GB\n\
-            Linux GUI: 2GB minimum",
-            os_name
+            Linux GUI: 2GB minimum{}{}",
+            os_name,
+            profile
+                .and_then(|p| p.memory_cap_mb)
+                .map(|cap| format!("\n\nThis profile warns above {} MB", cap))
+                .unwrap_or_default(),
+            app.host_capacity
+                .map(|host| format!(
+                    "\n\nThis host: {} MB (warns above {}%)",
+                    host.memory_mb,
+                    crate::hardware::host::MEMORY_WARN_PERCENT
+                ))
+                .unwrap_or_default()
         ),
+        QemuField::MaxMemory => "Memory hotplug ceiling.\n\n\
+            Off: the guest's RAM is fixed at Memory.\n\n\
+            When set, QEMU reserves hotplug slots so RAM can \
+            grow to this size while the VM runs. Pair it with \
+            the Balloon to hand memory back to the host."
+            .to_string(),
         QemuField::CpuCores => format!(
             "CPU cores for {}.\n\n\
             More cores = faster for multi-threaded tasks.\n\n\
             Old OSes (pre-2000) may not support multiple CPUs.\n\
-            Don't exceed your host's core count.",
-            os_name
+            Don't exceed your host's core count.\n\n\
+            Running Docker, WSL2 or Hyper-V inside the guest? \
+            Turn on Nested Virt under Features.{}",
+            os_name,
+            app.host_capacity
+                .map(|host| format!("\n\nThis host: {} CPUs", host.cpus))
+                .unwrap_or_default()
         ),
+        QemuField::Topology => "How the vCPUs are presented to the guest.\n\n\
+            Default: one socket with every vCPU as a core.\n\n\
+            Some guests only use a limited number of sockets \
+            (Windows client editions use 2 at most), and \
+            per-socket licensing cares about the split.\n\n\
+            Press Space to choose sockets, cores and threads."
+            .to_string(),
+        QemuField::Sockets | QemuField::Cores | QemuField::Threads => {
+            "Sockets x cores x threads always equals the CPU \
+            core count.\n\n\
+            Changing sockets or threads rebalances cores; \
+            changing cores rebalances sockets. Only values \
+            that divide the count evenly are offered.\n\n\
+            Threads > 1 models SMT (Hyper-Threading)."
+                .to_string()
+        }
         QemuField::Vga => format!(
             "Graphics adapter for {}.\n\n\
             std: Safe, universal\n\
@@ -2460,6 +2989,14 @@ fn get_field_notes(app: &App, focus: usize) -> String {
             None: Server/headless",
             os_name
         ),
+        QemuField::AudioBackend => "Host sound server the audio device plays through.\n\n\
+            pipewire: PipeWire (QEMU 8.1+)\n\
+            pa: PulseAudio / pipewire-pulse\n\
+            alsa: Direct ALSA\n\
+            spice: Sent to the SPICE viewer\n  (SPICE display only)\n\
+            none: Silent\n\n\
+            Detected from the running session; switch it if the VM has no sound."
+            .to_string(),
         QemuField::Network => format!(
             "Network adapter for {}.\n\n\
             virtio: Best perf (needs driver)\n\
@@ -2529,11 +3066,46 @@ fn get_field_notes(app: &App, focus: usize) -> String {
             none: Headless, no graphical output",
             os_name
         ),
+        QemuField::Keymap => format!(
+            "Guest keyboard layout (-k).\n\n\
+            Only VNC needs it: GTK, SDL and SPICE pass raw\n\
+            scancodes and the guest's own layout applies.\n\n\
+            default: {}",
+            match crate::vm::keymap::host_keymap() {
+                Some(host)
+                    if app
+                        .wizard_state
+                        .as_ref()
+                        .is_some_and(|s| s.qemu_config.display == "vnc") =>
+                    format!("your host's layout ({})", host),
+                _ => "en-us".to_string(),
+            }
+        ),
         QemuField::Kvm => "KVM hardware acceleration.\n\n\
             Enables near-native speed using CPU virtualization.\n\n\
             Requires: Linux host with Intel VT-x or AMD-V.\n\
             Disable for: Non-x86 guests, nested virt issues."
             .to_string(),
+        QemuField::NestedVirt => {
+            let host = match crate::commands::qemu_system::nested_virt_support() {
+                Some(n) if n.enabled => format!("Host: {} nested=1, ready.", n.module),
+                Some(n) => format!(
+                    "Host: {} has nesting OFF. Enable it with:\n{}",
+                    n.module,
+                    n.enable_hint()
+                ),
+                None => "Host: KVM module not loaded, can't check nesting.".to_string(),
+            };
+            format!(
+                "Nested virtualization.\n\n\
+                Lets the guest run its own hypervisor: Docker \
+                Desktop, WSL2, Hyper-V, VirtualBox.\n\n\
+                Uses -cpu host,+vmx (Intel) or +svm (AMD), \
+                so it needs KVM on this host.\n\n\
+                {}",
+                host
+            )
+        }
         QemuField::GlAccel => "3D/OpenGL acceleration.\n\n\
             Hardware-accelerated 3D graphics via virtio-gpu.\n\n\
             Requires: virtio VGA (auto-set when enabled)\n\
@@ -2554,16 +3126,45 @@ fn get_field_notes(app: &App, focus: usize) -> String {
             Optional: BitLocker, Secure Boot\n\
             Not needed: Most other OSes"
             .to_string(),
-        QemuField::UsbTablet => "USB tablet device.\n\n\
-            Provides seamless mouse integration (no capture).\n\n\
-            Recommended: Most modern systems\n\
-            Disable: Old OSes with USB issues"
+        QemuField::SecureBoot => "UEFI Secure Boot.\n\n\
+            Boots the Secure Boot OVMF build with Microsoft keys\n\
+            enrolled, and turns on SMM to protect them.\n\n\
+            Required: Windows 11 (always on with TPM)\n\
+            Optional: Linux with signed shim\n\n\
+            Enabling it after the guest is installed can stop\n\
+            it booting: unsigned bootloaders are refused."
+            .to_string(),
+        QemuField::Input => "Keyboard and pointer devices.\n\n\
+            PS/2: Built-in, for DOS, Windows 3.x/9x and other\n\
+            old OSes without USB mouse support\n\
+            USB tablet: Pointer follows the host cursor (no capture)\n\
+            virtio: Lowest latency; Linux has drivers, Windows\n\
+            needs virtio-win"
             .to_string(),
         QemuField::RtcLocal => "RTC in local time.\n\n\
             Sets hardware clock to local timezone.\n\n\
             Enable: Windows (expects local time)\n\
             Disable: Linux/Unix (expects UTC)"
             .to_string(),
+        QemuField::GuestAgent => "QEMU guest agent channel.\n\n\
+            Lets vm-curator see when the guest is fully up \
+            (shown as a check mark next to the running dot).\n\n\
+            Requires: qemu-guest-agent installed and running \
+            in the guest (virtio-win tools on Windows)."
+            .to_string(),
+        QemuField::Balloon => "virtio-balloon memory device.\n\n\
+            Lets you shrink or regrow a running guest's RAM from \
+            the Management menu; the current size shows next to \
+            the running dot.\n\n\
+            Requires: the virtio balloon driver in the guest \
+            (built into Linux, virtio-win on Windows)."
+            .to_string(),
+        QemuField::Clipboard => "SPICE clipboard sharing.\n\n\
+            Adds the SPICE agent channel so copy/paste works \
+            between the host and the remote-viewer window.\n\n\
+            Requires: spice-vdagent installed and running in \
+            the guest (spice-guest-tools on Windows)."
+            .to_string(),
     };
 
     if profile_notes.is_empty() {
@@ -2578,6 +3179,9 @@ fn handle_step_configure_qemu(app: &mut App, key: KeyEvent) -> Result<()> {
     if app.wizard_editing_port_forwards {
         return handle_wizard_port_forward_editor(app, key);
     }
+    if app.wizard_copy_from.is_some() {
+        return handle_wizard_copy_picker(app, key);
+    }
 
     // Check if we're in edit mode for Memory or CPU
     let editing_memory = app
@@ -2658,6 +3262,10 @@ fn handle_step_configure_qemu(app: &mut App, key: KeyEvent) -> Result<()> {
                         // Parse as plain number
                         if let Ok(value) = buffer.trim().parse::<u32>() {
                             state.qemu_config.cpu_cores = value.clamp(1, 256);
+                            let cpus = state.qemu_config.cpu_cores;
+                            if let Some(ref mut topology) = state.qemu_config.cpu_topology {
+                                topology.rebalance(cpus);
+                            }
                         }
                     }
                     state.editing_field = None;
@@ -2716,7 +3324,24 @@ fn handle_step_configure_qemu(app: &mut App, key: KeyEvent) -> Result<()> {
                 app.wizard_pf_selected = 0;
                 app.wizard_adding_pf = None;
             } else {
-                let _ = app.wizard_next_step();
+                // Don't let the user confirm a VM whose emulator isn't installed
+                let emulator = app
+                    .wizard_state
+                    .as_ref()
+                    .map(|s| s.qemu_config.emulator.clone())
+                    .unwrap_or_default();
+                if !crate::commands::qemu_system::is_emulator_available(&emulator) {
+                    if let Some(ref mut state) = app.wizard_state {
+                        state.error_message = Some(format!(
+                            "{} is not installed. Pick another emulator or install it.",
+                            emulator
+                        ));
+                    }
+                } else if let Err(e) = app.wizard_next_step() {
+                    if let Some(ref mut state) = app.wizard_state {
+                        state.error_message = Some(e);
+                    }
+                }
             }
         }
         KeyCode::Tab => {
@@ -2790,10 +3415,28 @@ fn handle_step_configure_qemu(app: &mut App, key: KeyEvent) -> Result<()> {
         }
         KeyCode::Char(' ') => {
             // Toggle for boolean fields
+            let mut warning = None;
+            let ovmf_paths = app.config.ovmf_override();
             if let Some(ref mut state) = app.wizard_state {
                 let field = QemuField::from_index(state.field_focus);
                 match field {
-                    QemuField::Kvm => state.qemu_config.enable_kvm = !state.qemu_config.enable_kvm,
+                    QemuField::Topology => {
+                        state.qemu_config.cpu_topology = match state.qemu_config.cpu_topology {
+                            Some(_) => None,
+                            None => Some(CpuTopology::flat(state.qemu_config.cpu_cores)),
+                        };
+                    }
+                    QemuField::Kvm => {
+                        state.qemu_config.enable_kvm = !state.qemu_config.enable_kvm;
+                        if !state.qemu_config.enable_kvm {
+                            state.qemu_config.nested_virt = None;
+                        }
+                    }
+                    QemuField::NestedVirt => {
+                        if state.qemu_config.nested_virt.take().is_none() {
+                            (state.qemu_config.nested_virt, warning) = nested_virt_toggle_on();
+                        }
+                    }
                     QemuField::GlAccel => {
                         state.qemu_config.gl_acceleration = !state.qemu_config.gl_acceleration;
                         // Enabling GL acceleration requires virtio VGA and works best with SDL
@@ -2809,15 +3452,47 @@ fn handle_step_configure_qemu(app: &mut App, key: KeyEvent) -> Result<()> {
                     }
                     QemuField::Uefi => state.qemu_config.uefi = !state.qemu_config.uefi,
                     QemuField::Tpm => state.qemu_config.tpm = !state.qemu_config.tpm,
-                    QemuField::UsbTablet => {
-                        state.qemu_config.usb_tablet = !state.qemu_config.usb_tablet
+                    QemuField::SecureBoot => {
+                        if state.qemu_config.secure_boot {
+                            state.qemu_config.secure_boot = false;
+                        } else {
+                            // Refuse up front rather than at create time
+                            match crate::vm::create::resolve_ovmf_firmware(
+                                ovmf_paths.as_ref(),
+                                true,
+                            ) {
+                                Ok(_) => state.qemu_config.secure_boot = true,
+                                Err(e) => warning = Some(e.to_string()),
+                            }
+                        }
+                    }
+                    QemuField::Input => {
+                        state.qemu_config.input = state.qemu_config.input.cycle(false)
                     }
                     QemuField::RtcLocal => {
                         state.qemu_config.rtc_localtime = !state.qemu_config.rtc_localtime
                     }
+                    QemuField::GuestAgent => {
+                        state.qemu_config.guest_agent = !state.qemu_config.guest_agent
+                    }
+                    QemuField::Balloon => state.qemu_config.balloon = !state.qemu_config.balloon,
+                    QemuField::Clipboard => {
+                        state.qemu_config.clipboard_sharing = !state.qemu_config.clipboard_sharing
+                    }
                     _ => {}
                 }
             }
+            if let Some(warning) = warning {
+                app.set_status(warning);
+            }
+        }
+        KeyCode::Char('T') => open_save_template(app),
+        KeyCode::Char('C') => {
+            if app.vms.is_empty() {
+                app.set_status("No VMs in the library to copy settings from");
+            } else {
+                app.wizard_copy_from = Some(0);
+            }
         }
         KeyCode::Char('r') | KeyCode::Char('R') => {
             // Reset to profile defaults
@@ -3063,6 +3738,116 @@ fn render_wizard_port_forward_editor(app: &App, frame: &mut Frame, parent: Rect)
     frame.render_widget(help, chunks[3]);
 }
 
+/// Render the "copy settings from another VM" picker over step 4.
+fn render_wizard_copy_picker(app: &App, frame: &mut Frame, parent: Rect) {
+    let selected = app.wizard_copy_from.unwrap_or(0);
+
+    let width = parent.width.saturating_sub(8).min(64);
+    let height = parent.height.saturating_sub(6).min(18);
+    let x = parent.x + (parent.width.saturating_sub(width)) / 2;
+    let y = parent.y + (parent.height.saturating_sub(height)) / 2;
+    let area = Rect {
+        x,
+        y,
+        width,
+        height,
+    };
+
+    frame.render_widget(Clear, area);
+
+    let block = Block::default()
+        .title(" Copy Settings From ")
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::Yellow))
+        .style(Style::default().bg(Color::Black));
+    let inner = block.inner(area);
+    frame.render_widget(block, area);
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .margin(1)
+        .constraints([
+            Constraint::Min(3),    // VM list
+            Constraint::Length(1), // Note
+            Constraint::Length(1), // Help
+        ])
+        .split(inner);
+
+    let visible = chunks[0].height as usize;
+    let skip = selected.saturating_sub(visible.saturating_sub(1));
+    let lines: Vec<Line> = app
+        .vms
+        .iter()
+        .enumerate()
+        .skip(skip)
+        .take(visible)
+        .map(|(i, vm)| {
+            let is_selected = i == selected;
+            let style = if is_selected {
+                Style::default()
+                    .fg(Color::Yellow)
+                    .add_modifier(Modifier::BOLD)
+            } else {
+                Style::default().fg(Color::White)
+            };
+            Line::from(vec![
+                Span::styled(
+                    format!(
+                        "{}{:<32}",
+                        if is_selected { "> " } else { "  " },
+                        vm.display_name()
+                    ),
+                    style,
+                ),
+                Span::styled(
+                    format!("{} MB, {} CPU", vm.config.memory_mb, vm.config.cpu_cores),
+                    Style::default().fg(Color::Gray),
+                ),
+            ])
+        })
+        .collect();
+    frame.render_widget(Paragraph::new(lines), chunks[0]);
+
+    let note = Paragraph::new("  Name, disk and ISO stay as they are; the MAC is left on auto.")
+        .style(Style::default().fg(Color::DarkGray));
+    frame.render_widget(note, chunks[1]);
+
+    let help = Paragraph::new("[j/k] Navigate  [Enter] Copy  [Esc] Cancel")
+        .style(Style::default().fg(Color::DarkGray))
+        .alignment(Alignment::Center);
+    frame.render_widget(help, chunks[2]);
+}
+
+fn handle_wizard_copy_picker(app: &mut App, key: KeyEvent) -> Result<()> {
+    let selected = app.wizard_copy_from.unwrap_or(0);
+    match key.code {
+        KeyCode::Esc => app.wizard_copy_from = None,
+        KeyCode::Char('j') | KeyCode::Down => {
+            app.wizard_copy_from = Some((selected + 1).min(app.vms.len().saturating_sub(1)));
+        }
+        KeyCode::Char('k') | KeyCode::Up => {
+            app.wizard_copy_from = Some(selected.saturating_sub(1));
+        }
+        KeyCode::Enter => {
+            app.wizard_copy_from = None;
+            let Some(vm) = app.vms.get(selected) else {
+                return Ok(());
+            };
+            let name = vm.display_name();
+            let config = crate::vm::create::wizard_config_template(&vm.config);
+            if let Some(ref mut state) = app.wizard_state {
+                state.qemu_config = config;
+                state.error_message = None;
+                // The copied settings may hide the previously-focused row.
+                state.field_focus = snap_focus_to_visible(state.field_focus, &state.qemu_config);
+            }
+            app.set_status(format!("Copied QEMU settings from {}", name));
+        }
+        _ => {}
+    }
+    Ok(())
+}
+
 /// Render the "add a port forward rule" prompt inside the editor popup.
 fn render_wizard_adding_pf(adding: &crate::app::AddingPortForward, frame: &mut Frame, area: Rect) {
     use crate::app::AddPfStep;
@@ -3188,6 +3973,13 @@ fn handle_qemu_field_change(app: &mut App, delta: i32) {
         .cloned()
         .or_else(|| Some("qemubr0".to_string()));
 
+    // Emulators to cycle through; keep the current one even if it's missing
+    let mut emulator_options = app.available_emulators.clone();
+    if !emulator_options.contains(&emulator) {
+        emulator_options.insert(0, emulator.clone());
+    }
+    let display_capabilities = app.display_capabilities.clone();
+
     let Some(ref mut state) = app.wizard_state else {
         return;
     };
@@ -3201,14 +3993,53 @@ fn handle_qemu_field_change(app: &mut App, delta: i32) {
     }
 
     match field {
+        QemuField::Emulator => {
+            let mut next = state.qemu_config.emulator.clone();
+            let options: Vec<&str> = emulator_options.iter().map(|s| s.as_str()).collect();
+            cycle_option(&mut next, &options, delta);
+            if next != state.qemu_config.emulator {
+                state.error_message = None;
+                state.qemu_config.apply_emulator_defaults(&next);
+                // Keep the display backend if the new binary supports it
+                if let Some(displays) = display_capabilities.get(&next) {
+                    if !displays.contains(&state.qemu_config.display) {
+                        if let Some(first) = displays.first() {
+                            state.qemu_config.display = first.clone();
+                        }
+                    }
+                }
+            }
+        }
         QemuField::Memory => {
             let change = 256 * delta;
             state.qemu_config.memory_mb =
                 (state.qemu_config.memory_mb as i32 + change).clamp(128, 1048576) as u32;
         }
+        QemuField::MaxMemory => {
+            // Stepping down to (or below) boot RAM turns the ceiling off
+            let memory = state.qemu_config.memory_mb as i32;
+            let current = state.qemu_config.max_memory_mb.map_or(memory, |m| m as i32);
+            let max = (current + 1024 * delta).min(1048576);
+            state.qemu_config.max_memory_mb = (max > memory).then_some(max as u32);
+        }
         QemuField::CpuCores => {
             state.qemu_config.cpu_cores =
                 (state.qemu_config.cpu_cores as i32 + delta).clamp(1, 256) as u32;
+            let cpus = state.qemu_config.cpu_cores;
+            if let Some(ref mut topology) = state.qemu_config.cpu_topology {
+                topology.rebalance(cpus);
+            }
+        }
+        QemuField::Sockets | QemuField::Cores | QemuField::Threads => {
+            let dim = match field {
+                QemuField::Sockets => TopologyDim::Sockets,
+                QemuField::Cores => TopologyDim::Cores,
+                _ => TopologyDim::Threads,
+            };
+            let cpus = state.qemu_config.cpu_cores;
+            if let Some(ref mut topology) = state.qemu_config.cpu_topology {
+                topology.step(dim, delta, cpus);
+            }
         }
         QemuField::Vga => {
             cycle_option(&mut state.qemu_config.vga, VGA_OPTIONS, delta);
@@ -3216,6 +4047,10 @@ fn handle_qemu_field_change(app: &mut App, delta: i32) {
         QemuField::Audio => {
             cycle_audio(&mut state.qemu_config.audio, delta);
         }
+        QemuField::AudioBackend => {
+            let options = audio_backend_options(&state.qemu_config.display);
+            cycle_option(&mut state.qemu_config.audio_backend, &options, delta);
+        }
         QemuField::Network => {
             cycle_option(&mut state.qemu_config.network_model, NETWORK_OPTIONS, delta);
         }
@@ -3262,6 +4097,26 @@ fn handle_qemu_field_change(app: &mut App, delta: i32) {
             } else {
                 cycle_option(&mut state.qemu_config.display, DISPLAY_OPTIONS, delta);
             }
+            // SPICE audio follows the SPICE display on and off
+            let config = &mut state.qemu_config;
+            if config.display == "spice-app" {
+                config.audio_backend = "spice".to_string();
+            } else if config.audio_backend == "spice" {
+                config.audio_backend =
+                    WizardQemuConfig::default_audio_backend(&config.emulator, &config.display);
+            }
+        }
+        QemuField::Input => {
+            state.qemu_config.input = state.qemu_config.input.cycle(delta < 0);
+        }
+        QemuField::Keymap => {
+            let keymaps = crate::vm::keymap::available_keymaps();
+            state.qemu_config.keymap = crate::vm::keymap::next_keymap(
+                &keymaps,
+                state.qemu_config.keymap.as_deref(),
+                crate::vm::keymap::host_keymap(),
+                delta < 0,
+            );
         }
         // Toggles use space, not left/right
         _ => {}
@@ -3276,6 +4131,15 @@ fn handle_qemu_field_change(app: &mut App, delta: i32) {
     state.field_focus = new_focus;
 }
 
+/// Host audio backends that work with `display`; SPICE audio needs SPICE
+fn audio_backend_options(display: &str) -> Vec<&'static str> {
+    crate::commands::qemu_system::AUDIO_BACKENDS
+        .iter()
+        .copied()
+        .filter(|b| *b != "spice" || display == "spice-app")
+        .collect()
+}
+
 fn cycle_option(current: &mut String, options: &[&str], delta: i32) {
     let current_idx = options
         .iter()
@@ -3401,6 +4265,14 @@ fn render_step_confirm(app: &App, frame: &mut Frame, area: Rect) {
             Span::styled("OS Type:        ", Style::default().fg(Color::Yellow)),
             Span::raw(os_name),
         ]),
+    ];
+    if let Some(ref template) = state.template {
+        lines.push(Line::from(vec![
+            Span::styled("Template:       ", Style::default().fg(Color::Yellow)),
+            Span::raw(template.as_str()),
+        ]));
+    }
+    lines.extend([
         Line::from(""),
         Line::from(vec![
             Span::styled("Disk:           ", Style::default().fg(Color::Yellow)),
@@ -3410,7 +4282,7 @@ fn render_step_confirm(app: &App, frame: &mut Frame, area: Rect) {
             Span::styled("ISO:            ", Style::default().fg(Color::Yellow)),
             Span::raw(iso_str),
         ]),
-    ];
+    ]);
     if let Some(ref floppy_path) = state.floppy_path {
         lines.push(Line::from(vec![
             Span::styled("Floppy:         ", Style::default().fg(Color::Yellow)),
@@ -3426,24 +4298,46 @@ fn render_step_confirm(app: &App, frame: &mut Frame, area: Rect) {
     lines.push(Line::from(""));
     lines.push(Line::from(vec![
         Span::styled("Hardware:       ", Style::default().fg(Color::Yellow)),
-        Span::raw(format!(
-            "{} cores, {} MB RAM",
-            config.cpu_cores, config.memory_mb
-        )),
+        Span::raw(match config.cpu_topology {
+            Some(t) => format!(
+                "{} cores ({}s x {}c x {}t), {} MB RAM",
+                config.cpu_cores, t.sockets, t.cores, t.threads, config.memory_mb
+            ),
+            None => format!("{} cores, {} MB RAM", config.cpu_cores, config.memory_mb),
+        }),
     ]));
+    for warning in capacity_warnings(app, config) {
+        lines.push(Line::from(vec![
+            Span::styled("                ", Style::default()),
+            Span::styled(
+                format!("Warning: {}", warning),
+                Style::default().fg(Color::Yellow),
+            ),
+        ]));
+    }
+    if config.max_memory_mb.is_some() || config.balloon {
+        let mut memory = Vec::new();
+        if let Some(max) = config.max_memory_mb {
+            memory.push(format!("hotplug up to {} MB", max));
+        }
+        if config.balloon {
+            memory.push("balloon".to_string());
+        }
+        lines.push(Line::from(vec![
+            Span::styled("Memory:         ", Style::default().fg(Color::Yellow)),
+            Span::raw(memory.join(", ")),
+        ]));
+    }
     lines.push(Line::from(vec![
         Span::styled("Graphics:       ", Style::default().fg(Color::Yellow)),
         Span::raw(&config.vga),
     ]));
     lines.push(Line::from(vec![
         Span::styled("Audio:          ", Style::default().fg(Color::Yellow)),
-        Span::raw(
-            config
-                .audio
-                .first()
-                .cloned()
-                .unwrap_or_else(|| "None".to_string()),
-        ),
+        Span::raw(match config.audio.first() {
+            Some(device) => format!("{} via {}", device, config.audio_backend),
+            None => "None".to_string(),
+        }),
     ]));
     let net_display = if config.network_model == "none" {
         "none".to_string()
@@ -3505,9 +4399,10 @@ fn render_step_confirm(app: &App, frame: &mut Frame, area: Rect) {
     }
 
     // Help
-    let help = Paragraph::new("[Enter] Create VM  [Space] Toggle launch  [Esc] Back")
-        .style(Style::default().fg(Color::DarkGray))
-        .alignment(Alignment::Center);
+    let help =
+        Paragraph::new("[Enter] Create VM  [Space] Toggle launch  [T] Save template  [Esc] Back")
+            .style(Style::default().fg(Color::DarkGray))
+            .alignment(Alignment::Center);
     frame.render_widget(help, chunks[5]);
 }
 
@@ -3516,12 +4411,14 @@ fn handle_step_confirm(app: &mut App, key: KeyEvent) -> Result<()> {
         KeyCode::Esc => {
             app.wizard_prev_step();
         }
+        KeyCode::Char('T') => open_save_template(app),
         KeyCode::Char(' ') => {
             if let Some(ref mut state) = app.wizard_state {
                 state.auto_launch = !state.auto_launch;
             }
         }
-        KeyCode::Enter => {
+        // Ignore a second Enter while the VM is being written
+        KeyCode::Enter if !app.loading => {
             // Create the VM
             let (library_path, auto_launch) = {
                 let state = app.wizard_state.as_ref().unwrap();
@@ -3531,69 +4428,85 @@ fn handle_step_confirm(app: &mut App, key: KeyEvent) -> Result<()> {
             };
 
             // Clone the state for creation
-            let state = app.wizard_state.as_ref().unwrap().clone();
+            let mut state = app.wizard_state.as_ref().unwrap().clone();
+            state.qemu_config.ovmf_paths = app.config.ovmf_override();
             let vm_name = state.vm_name.clone();
+            let disk_format = app.create_wizard_disk_format;
+            let tx = app.background_tx.clone();
+            app.start_progress(format!("Creating {}", vm_name));
+
+            // Copying an existing multi-GB disk can take minutes
+            std::thread::spawn(move || {
+                let result =
+                    create_vm_with_disk_format(&library_path, &state, disk_format, |label, p| {
+                        let _ = tx.send(BackgroundResult::Progress {
+                            label: label.to_string(),
+                            percent: Some(p),
+                        });
+                    });
+                let (launch_script, error) = match result {
+                    Ok(created) => (Some(created.launch_script), None),
+                    Err(e) => (None, Some(format!("{:#}", e))),
+                };
+                let _ = tx.send(BackgroundResult::VmCreated {
+                    vm_name,
+                    launch_script,
+                    auto_launch,
+                    error,
+                });
+            });
+        }
+        _ => {}
+    }
+    Ok(())
+}
 
-            match create_vm_with_disk_format(&library_path, &state, app.create_wizard_disk_format) {
-                Ok(created) => {
-                    // Cancel wizard first (closes screens)
-                    app.cancel_wizard();
+/// Close the wizard once the VM has been written, and launch it when asked to
+pub fn finish_create(app: &mut App, vm_name: &str, launch_script: &Path, auto_launch: bool) {
+    // Cancel wizard first (closes screens)
+    app.cancel_wizard();
 
-                    // Refresh VM list to include the new VM
-                    match app.refresh_vms() {
-                        Ok(()) => {
-                            app.set_status(format!("VM created: {}", vm_name));
-                        }
-                        Err(e) => {
-                            app.set_status(format!("VM created but refresh failed: {}", e));
-                        }
-                    }
+    // Refresh VM list to include the new VM
+    match app.refresh_vms() {
+        Ok(()) => {
+            app.set_status(format!("VM created: {}", vm_name));
+        }
+        Err(e) => {
+            app.set_status(format!("VM created but refresh failed: {}", e));
+        }
+    }
 
-                    // If auto_launch is enabled, find and launch the new VM
-                    if auto_launch {
-                        // Find the newly created VM and select it
-                        if let Some(idx) = app
-                            .vms
-                            .iter()
-                            .position(|vm| vm.launch_script == created.launch_script)
-                        {
-                            // Find in visual order
-                            if let Some(visual_idx) =
-                                app.visual_order.iter().position(|&filtered_idx| {
-                                    app.filtered_indices.get(filtered_idx) == Some(&idx)
-                                })
-                            {
-                                app.selected_vm = visual_idx;
-
-                                // Set boot mode to install
-                                app.boot_mode = crate::vm::BootMode::Install;
-
-                                // Launch the VM
-                                match launch_created_vm(app) {
-                                    Ok(()) => {
-                                        app.set_status(format!("Launched: {}", vm_name));
-                                    }
-                                    Err(e) => {
-                                        app.set_status(format!(
-                                            "VM created but launch failed: {}",
-                                            e
-                                        ));
-                                    }
-                                }
-                            }
-                        }
+    // If auto_launch is enabled, find and launch the new VM
+    if auto_launch {
+        // Find the newly created VM and select it
+        if let Some(idx) = app
+            .vms
+            .iter()
+            .position(|vm| vm.launch_script == launch_script)
+        {
+            // Find in visual order
+            if let Some(visual_idx) = app
+                .visual_order
+                .iter()
+                .position(|&filtered_idx| app.filtered_indices.get(filtered_idx) == Some(&idx))
+            {
+                app.selected_vm = visual_idx;
+
+                // Set boot mode to install
+                app.boot_mode = crate::vm::BootMode::Install;
+
+                // Launch the VM
+                match launch_created_vm(app) {
+                    Ok(()) => {
+                        app.set_status(format!("Launched: {}", vm_name));
                     }
-                }
-                Err(e) => {
-                    if let Some(ref mut state) = app.wizard_state {
-                        state.error_message = Some(format!("Failed to create VM: {}", e));
+                    Err(e) => {
+                        app.set_status(format!("VM created but launch failed: {}", e));
                     }
                 }
             }
         }
-        _ => {}
     }
-    Ok(())
 }
 
 /// Launch a newly created VM
@@ -3601,6 +4514,12 @@ fn launch_created_vm(app: &mut App) -> Result<()> {
     if let Some(vm) = app.selected_vm() {
         let options = app.get_launch_options();
         crate::vm::launch_vm_sync(vm, &options)?;
+        let id = vm.id.clone();
+        app.mark_vm_launched(&id);
+        if matches!(options.boot_mode, crate::vm::BootMode::Install) {
+            app.install_launches.insert(id);
+            app.boot_mode = crate::vm::BootMode::Normal;
+        }
     }
     Ok(())
 }
@@ -3630,6 +4549,28 @@ fn open_url_in_browser(url: &str) -> Result<()> {
 // Utility
 // =============================================================================
 
+/// Turn the nested virtualization toggle on: the host's vmx/svm flag to pass
+/// through, or nothing when KVM is unusable, plus a warning to show.
+fn nested_virt_toggle_on() -> (Option<String>, Option<String>) {
+    use crate::commands::qemu_system::{
+        host_virt_flag, kvm_unavailable_reason, nested_virt_support,
+    };
+
+    if let Some(reason) = kvm_unavailable_reason() {
+        return (
+            None,
+            Some(format!("Nested virtualization needs KVM: {}", reason)),
+        );
+    }
+    let warning = nested_virt_support().filter(|n| !n.enabled).map(|n| {
+        format!(
+            "Warning: nesting is off in {} on this host (see notes)",
+            n.module
+        )
+    });
+    (Some(host_virt_flag().to_string()), warning)
+}
+
 fn centered_rect(width: u16, height: u16, area: Rect) -> Rect {
     let x = area.x + (area.width.saturating_sub(width)) / 2;
     let y = area.y + (area.height.saturating_sub(height)) / 2;
@@ -0,0 +1,171 @@
+//! Direct Kernel Boot Screen
+//!
+//! Picks a kernel image, optional initrd and kernel command line for the
+//! selected VM. QEMU then boots the kernel itself instead of the disk's
+//! bootloader; choosing no kernel turns direct boot off again.
+
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{
+    layout::Rect,
+    prelude::*,
+    style::{Color, Modifier, Style},
+    widgets::{Block, Borders, Clear, Paragraph, Wrap},
+};
+
+use crate::app::{App, FileBrowserMode, Screen, TextInputContext};
+use crate::vm::DirectKernelBoot;
+
+/// Rows of the screen: kernel, initrd, command line
+const ROW_COUNT: usize = 3;
+
+/// Render the direct kernel boot screen
+pub fn render(app: &App, frame: &mut Frame) {
+    let area = frame.area();
+    let dialog_width = 70.min(area.width.saturating_sub(4));
+    let dialog_height = 16.min(area.height.saturating_sub(4));
+
+    let dialog_area = centered_rect(dialog_width, dialog_height, area);
+    frame.render_widget(Clear, dialog_area);
+
+    let block = Block::default()
+        .title(" Direct Kernel Boot ")
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::Cyan))
+        .style(Style::default().bg(Color::Black));
+
+    let inner = block.inner(dialog_area);
+    frame.render_widget(block, dialog_area);
+
+    let boot = &app.direct_kernel;
+    let kernel = if boot.is_enabled() {
+        boot.kernel.display().to_string()
+    } else {
+        "None (boot from disk)".to_string()
+    };
+    let initrd = boot
+        .initrd
+        .as_ref()
+        .map(|p| p.display().to_string())
+        .unwrap_or_else(|| "None".to_string());
+    let append = if boot.append.is_empty() {
+        "(empty)".to_string()
+    } else {
+        boot.append.clone()
+    };
+    let rows = [
+        ("Kernel", kernel, "-kernel"),
+        ("Initrd", initrd, "-initrd"),
+        ("Command line", append, "-append"),
+    ];
+
+    let mut lines = vec![Line::raw("")];
+    for (i, (name, value, flag)) in rows.iter().enumerate() {
+        let style = if i == app.selected_menu_item {
+            Style::default()
+                .fg(Color::Yellow)
+                .add_modifier(Modifier::BOLD)
+        } else {
+            Style::default().fg(Color::White)
+        };
+        lines.push(Line::from(vec![
+            Span::styled(format!("[{}] {} ", i + 1, name), style),
+            Span::styled(*flag, Style::default().fg(Color::DarkGray)),
+        ]));
+        lines.push(Line::styled(
+            format!("    {}", value),
+            Style::default().fg(Color::Gray),
+        ));
+    }
+
+    lines.push(Line::raw(""));
+    lines.push(Line::styled(
+        "Applies to every boot mode. Clear the kernel to boot from disk again.",
+        Style::default().fg(Color::DarkGray),
+    ));
+    lines.push(Line::raw(""));
+    lines.push(Line::styled(
+        "[Enter] Change  [x] Clear  [Esc] Back",
+        Style::default().fg(Color::DarkGray),
+    ));
+
+    let para = Paragraph::new(lines).wrap(Wrap { trim: false });
+    frame.render_widget(para, inner.inner(Margin::new(2, 0)));
+}
+
+/// Handle key input for the direct kernel boot screen
+pub fn handle_key(app: &mut App, key: KeyEvent) -> anyhow::Result<()> {
+    match key.code {
+        KeyCode::Esc => app.pop_screen(),
+        KeyCode::Char('j') | KeyCode::Down => app.menu_next(ROW_COUNT),
+        KeyCode::Char('k') | KeyCode::Up => app.menu_prev(),
+        KeyCode::Char('x') | KeyCode::Delete => {
+            let mut boot = app.direct_kernel.clone();
+            match app.selected_menu_item {
+                0 => boot = DirectKernelBoot::default(),
+                1 => boot.initrd = None,
+                _ => boot.append.clear(),
+            }
+            save(app, boot);
+        }
+        KeyCode::Enter | KeyCode::Char('1') | KeyCode::Char('2') | KeyCode::Char('3') => {
+            let row = match key.code {
+                KeyCode::Char('1') => 0,
+                KeyCode::Char('2') => 1,
+                KeyCode::Char('3') => 2,
+                _ => app.selected_menu_item,
+            };
+            app.selected_menu_item = row;
+            match row {
+                0 => open_browser(app, FileBrowserMode::Kernel),
+                1 => open_browser(app, FileBrowserMode::Initrd),
+                _ => {
+                    app.text_input_buffer = app.direct_kernel.append.clone();
+                    app.push_screen(Screen::TextInput(TextInputContext::KernelAppend));
+                }
+            }
+        }
+        _ => {}
+    }
+    Ok(())
+}
+
+/// Browse for a kernel or initrd, starting next to the current one
+fn open_browser(app: &mut App, mode: FileBrowserMode) {
+    let current = match mode {
+        FileBrowserMode::Initrd => app.direct_kernel.initrd.as_deref(),
+        _ => Some(app.direct_kernel.kernel.as_path()),
+    };
+    match current.and_then(|p| p.parent()) {
+        Some(dir) if dir.is_dir() => app.file_browser_dir = dir.to_path_buf(),
+        _ => app.seed_file_browser_dir(mode),
+    }
+    app.load_file_browser(mode);
+    app.push_screen(Screen::FileBrowser);
+}
+
+/// Persist new direct kernel boot settings to the selected VM's launch.sh.
+/// Without a kernel the section is removed, but the other fields are kept
+/// on screen so a kernel can still be picked.
+pub fn save(app: &mut App, boot: DirectKernelBoot) {
+    let Some(vm) = app.selected_vm() else {
+        return;
+    };
+    match crate::vm::save_direct_kernel(vm, Some(&boot)) {
+        Ok(()) => {
+            if boot.is_enabled() {
+                app.set_status(format!("Direct kernel boot: {}", boot.kernel.display()));
+            } else {
+                app.set_status("Direct kernel boot off (no kernel selected)");
+            }
+            app.direct_kernel = boot;
+            app.reload_selected_vm_script();
+        }
+        Err(e) => app.set_status(format!("Failed to save direct kernel boot: {}", e)),
+    }
+}
+
+fn centered_rect(width: u16, height: u16, area: Rect) -> Rect {
+    let x = area.x + (area.width.saturating_sub(width)) / 2;
+    let y = area.y + (area.height.saturating_sub(height)) / 2;
+    Rect::new(x, y, width, height)
+}
@@ -0,0 +1,232 @@
+//! Disk I/O Limits Screen
+//!
+//! Caps the read/write operations and bandwidth of each of the selected
+//! VM's disks. Limits are written as `throttling.*` options on the disk's
+//! `-drive` in launch.sh right away and apply from the next launch.
+
+use std::path::PathBuf;
+
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{
+    layout::Rect,
+    prelude::*,
+    style::{Color, Modifier, Style},
+    widgets::{Block, Borders, Clear, Paragraph, Wrap},
+};
+
+use crate::app::{App, Screen, TextInputContext};
+use crate::vm::qemu_config::{DiskThrottle, ThrottleField};
+
+/// Disks of the selected VM that can be limited, with their current limits
+fn disks(app: &App) -> Vec<(PathBuf, DiskThrottle)> {
+    let mut disks: Vec<(PathBuf, DiskThrottle)> = Vec::new();
+    if let Some(vm) = app.selected_vm() {
+        for disk in &vm.config.disks {
+            if crate::vm::discovery::is_disk_image(&disk.path)
+                && !disks.iter().any(|(path, _)| *path == disk.path)
+            {
+                disks.push((disk.path.clone(), disk.throttle));
+            }
+        }
+    }
+    disks
+}
+
+/// The disk being edited
+fn current_disk(app: &App) -> Option<(PathBuf, DiskThrottle)> {
+    let disks = disks(app);
+    let index = app.throttle_disk.min(disks.len().saturating_sub(1));
+    disks.into_iter().nth(index)
+}
+
+/// Render the disk I/O limits screen
+pub fn render(app: &App, frame: &mut Frame) {
+    let area = frame.area();
+    let dialog_width = 66.min(area.width.saturating_sub(4));
+    let dialog_height = 22.min(area.height.saturating_sub(4));
+
+    let dialog_area = centered_rect(dialog_width, dialog_height, area);
+    frame.render_widget(Clear, dialog_area);
+
+    let Some(vm) = app.selected_vm() else {
+        return;
+    };
+
+    let block = Block::default()
+        .title(format!(" {} - Disk I/O Limits ", vm.display_name()))
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::Cyan))
+        .style(Style::default().bg(Color::Black));
+
+    let inner = block.inner(dialog_area);
+    frame.render_widget(block, dialog_area);
+
+    let disk_count = disks(app).len();
+    let Some((path, throttle)) = current_disk(app) else {
+        let para = Paragraph::new(vec![
+            Line::raw(""),
+            Line::styled(
+                "This VM has no disk images to limit.",
+                Style::default().fg(Color::Gray),
+            ),
+            Line::raw(""),
+            Line::styled("[Esc] Back", Style::default().fg(Color::DarkGray)),
+        ]);
+        frame.render_widget(para, inner.inner(Margin::new(2, 0)));
+        return;
+    };
+
+    let name = path
+        .file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_else(|| path.display().to_string());
+    let mut lines = vec![
+        Line::raw(""),
+        Line::from(vec![
+            Span::styled("Disk: ", Style::default().fg(Color::Gray)),
+            Span::styled(name, Style::default().fg(Color::White)),
+            Span::styled(
+                if disk_count > 1 {
+                    format!("  ({}/{}, [Tab] next)", app.throttle_disk + 1, disk_count)
+                } else {
+                    String::new()
+                },
+                Style::default().fg(Color::DarkGray),
+            ),
+        ]),
+        Line::raw(""),
+    ];
+
+    for (i, field) in ThrottleField::ALL.into_iter().enumerate() {
+        let style = if i == app.selected_menu_item {
+            Style::default()
+                .fg(Color::Yellow)
+                .add_modifier(Modifier::BOLD)
+        } else {
+            Style::default().fg(Color::White)
+        };
+        lines.push(Line::from(vec![
+            Span::styled(format!("[{}] {} ", i + 1, field.label()), style),
+            Span::styled(field.option(), Style::default().fg(Color::DarkGray)),
+        ]));
+        lines.push(Line::styled(
+            format!("    {}", throttle.describe(field)),
+            Style::default().fg(Color::Gray),
+        ));
+    }
+
+    lines.push(Line::raw(""));
+    lines.push(Line::styled(
+        "Operations are requests per second (IOPS); bandwidth is in MiB/s. \
+         Blank or 0 means no limit. Applies from the next launch.",
+        Style::default().fg(Color::DarkGray),
+    ));
+    lines.push(Line::raw(""));
+    lines.push(Line::styled(
+        "[Enter] Change  [x] Remove limits  [Esc] Back",
+        Style::default().fg(Color::DarkGray),
+    ));
+
+    let para = Paragraph::new(lines).wrap(Wrap { trim: false });
+    frame.render_widget(para, inner.inner(Margin::new(2, 0)));
+}
+
+/// Handle key input for the disk I/O limits screen
+pub fn handle_key(app: &mut App, key: KeyEvent) -> anyhow::Result<()> {
+    let disk_count = disks(app).len();
+    if disk_count == 0 {
+        if key.code == KeyCode::Esc {
+            app.pop_screen();
+        }
+        return Ok(());
+    }
+
+    match key.code {
+        KeyCode::Esc => app.pop_screen(),
+        KeyCode::Char('j') | KeyCode::Down => app.menu_next(ThrottleField::ALL.len()),
+        KeyCode::Char('k') | KeyCode::Up => app.menu_prev(),
+        KeyCode::Tab => app.throttle_disk = (app.throttle_disk + 1) % disk_count,
+        KeyCode::BackTab => app.throttle_disk = (app.throttle_disk + disk_count - 1) % disk_count,
+        KeyCode::Char('x') | KeyCode::Delete => save(app, DiskThrottle::default()),
+        KeyCode::Enter | KeyCode::Char('1'..='4') => {
+            let row = match key.code {
+                KeyCode::Char(c) => c as usize - '1' as usize,
+                _ => app.selected_menu_item,
+            };
+            let Some(field) = ThrottleField::ALL.get(row).copied() else {
+                return Ok(());
+            };
+            app.selected_menu_item = row;
+            if let Some((_, throttle)) = current_disk(app) {
+                app.text_input_buffer = throttle.input_value(field);
+                app.push_screen(Screen::TextInput(TextInputContext::DiskThrottle(field)));
+            }
+        }
+        _ => {}
+    }
+    Ok(())
+}
+
+/// Text input title for a limit
+pub fn input_title(field: ThrottleField) -> &'static str {
+    match field {
+        ThrottleField::IopsRead => " Read Operations (IOPS, blank = unlimited) ",
+        ThrottleField::IopsWrite => " Write Operations (IOPS, blank = unlimited) ",
+        ThrottleField::BpsRead => {
+            " Read Bandwidth (MiB/s, or bytes/s ending in B; blank = unlimited) "
+        }
+        ThrottleField::BpsWrite => {
+            " Write Bandwidth (MiB/s, or bytes/s ending in B; blank = unlimited) "
+        }
+    }
+}
+
+/// Set a limit typed into the text input
+pub fn set_limit(app: &mut App, field: ThrottleField, input: &str) {
+    let value = match DiskThrottle::parse_input(field, input) {
+        Ok(value) => value,
+        Err(e) => {
+            app.set_status(format!("Invalid limit: {}", e));
+            return;
+        }
+    };
+    let Some((_, mut throttle)) = current_disk(app) else {
+        return;
+    };
+    throttle.set(field, value);
+    save(app, throttle);
+}
+
+/// Write the current disk's limits to the selected VM's launch.sh
+fn save(app: &mut App, throttle: DiskThrottle) {
+    let (Some(vm), Some((path, _))) = (app.selected_vm(), current_disk(app)) else {
+        return;
+    };
+    let id = vm.id.clone();
+    match crate::vm::save_disk_throttle(vm, &path, &throttle) {
+        Ok(()) => {
+            let name = path
+                .file_name()
+                .map(|n| n.to_string_lossy().to_string())
+                .unwrap_or_default();
+            app.set_status(if throttle.is_unlimited() {
+                format!("Removed I/O limits from {}", name)
+            } else {
+                format!("I/O limits saved for {}", name)
+            });
+            if let Some(vm) = app.vms.iter_mut().find(|vm| vm.id == id) {
+                for disk in vm.config.disks.iter_mut().filter(|d| d.path == path) {
+                    disk.throttle = throttle;
+                }
+            }
+            app.reload_selected_vm_script();
+        }
+        Err(e) => app.set_status(format!("Failed to save I/O limits: {}", e)),
+    }
+}
+
+fn centered_rect(width: u16, height: u16, area: Rect) -> Rect {
+    let x = area.x + (area.width.saturating_sub(width)) / 2;
+    let y = area.y + (area.height.saturating_sub(height)) / 2;
+    Rect::new(x, y, width, height)
+}
@@ -43,8 +43,24 @@ pub fn render(frame: &mut Frame) {
         Line::from(""),
         key_line("m", "Open Management menu"),
         key_line("x", "Stop selected VM (graceful shutdown)"),
+        key_line("d", "Details and disk usage"),
         key_line("c", "Create new VM"),
         key_line("/", "Search/filter VMs"),
+        key_line(
+            "o",
+            "Cycle VM list sort (category/name/last launched/memory)",
+        ),
+        key_line("r/F5", "Rescan the VM library"),
+        key_line("h", "Clean up VMs not launched in a while"),
+        key_line("t", "Trash: restore or permanently delete VMs"),
+        key_line(
+            "l",
+            "ISO Library: downloaded install media, delete to free space",
+        ),
+        key_line(
+            "v",
+            "Bulk mode: mark VMs with Space, then start/stop/snapshot/delete",
+        ),
         Line::from(""),
         Line::from(Span::styled(
             "Management Menu",
@@ -0,0 +1,149 @@
+//! Host Hardware Screen
+//!
+//! lscpu-style summary of what the host offers VMs: CPU and virtualization
+//! extensions, KVM, IOMMU, QEMU emulators, firmware and helper programs.
+//! `c` copies it as plain text for bug reports.
+
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{
+    layout::Rect,
+    prelude::*,
+    style::{Color, Modifier, Style},
+    widgets::{Block, Borders, Clear, Paragraph},
+};
+
+use crate::app::App;
+use crate::config::Config;
+use crate::hardware::HostInfoLine;
+use crate::ui::clipboard::copy_to_clipboard;
+
+/// Render the host hardware summary
+pub fn render(app: &App, frame: &mut Frame) {
+    let area = frame.area();
+    let dialog_width = 90.min(area.width.saturating_sub(4));
+    let dialog_height = 34.min(area.height.saturating_sub(4));
+
+    let dialog_area = centered_rect(dialog_width, dialog_height, area);
+    frame.render_widget(Clear, dialog_area);
+
+    let block = Block::default()
+        .title(" Host Hardware ")
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::Cyan))
+        .style(Style::default().bg(Color::Black));
+
+    let inner = block.inner(dialog_area);
+    frame.render_widget(block, dialog_area);
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Min(1), Constraint::Length(1)])
+        .split(inner);
+
+    let Some(info) = &app.host_info else {
+        return;
+    };
+    let label_width = info
+        .lines
+        .iter()
+        .filter_map(|l| match l {
+            HostInfoLine::Field(label, _) => Some(label.len()),
+            HostInfoLine::Section(_) => None,
+        })
+        .max()
+        .unwrap_or(0)
+        + 2;
+
+    let mut lines: Vec<Line> = Vec::new();
+    for line in &info.lines {
+        match line {
+            HostInfoLine::Section(title) => {
+                if !lines.is_empty() {
+                    lines.push(Line::raw(""));
+                }
+                lines.push(Line::styled(
+                    *title,
+                    Style::default()
+                        .fg(Color::Yellow)
+                        .add_modifier(Modifier::BOLD),
+                ));
+            }
+            HostInfoLine::Field(label, value) => {
+                let color = if value.starts_with("no") || value.starts_with("none") {
+                    Color::Red
+                } else {
+                    Color::White
+                };
+                lines.push(Line::from(vec![
+                    Span::styled(
+                        format!("  {:<width$}", format!("{}:", label), width = label_width),
+                        Style::default().fg(Color::Gray),
+                    ),
+                    Span::styled(value.clone(), Style::default().fg(color)),
+                ]));
+            }
+        }
+    }
+
+    let para = Paragraph::new(lines).scroll((app.host_info_scroll, 0));
+    frame.render_widget(para, chunks[0].inner(Margin::new(1, 0)));
+
+    let help = Paragraph::new("[j/k] Scroll  [c] Copy  [Esc] Back")
+        .style(Style::default().fg(Color::DarkGray))
+        .alignment(Alignment::Center);
+    frame.render_widget(help, chunks[1]);
+}
+
+/// Handle key input for the host hardware summary
+pub fn handle_key(app: &mut App, key: KeyEvent) -> anyhow::Result<()> {
+    match key.code {
+        KeyCode::Esc | KeyCode::Char('q') => {
+            app.host_info = None;
+            app.pop_screen();
+        }
+        KeyCode::Char('j') | KeyCode::Down => {
+            let rows = app.host_info.as_ref().map_or(0, |i| i.lines.len()) as u16;
+            if app.host_info_scroll < rows {
+                app.host_info_scroll += 1;
+            }
+        }
+        KeyCode::Char('k') | KeyCode::Up => {
+            app.host_info_scroll = app.host_info_scroll.saturating_sub(1);
+        }
+        KeyCode::Char('c') => {
+            if let Some(text) = app.host_info.as_ref().map(|i| i.to_text()) {
+                let msg = match copy_to_clipboard(&text) {
+                    Some(tool) => format!("Host summary copied to the clipboard ({})", tool),
+                    None => match save_report(&text) {
+                        Ok(path) => format!(
+                            "No clipboard tool (wl-copy, xclip, xsel); saved to {}",
+                            path.display()
+                        ),
+                        Err(e) => format!("Failed to save host summary: {}", e),
+                    },
+                };
+                app.set_status(msg);
+            }
+        }
+        _ => {}
+    }
+    Ok(())
+}
+
+/// Write the report next to the config file when there is no clipboard
+fn save_report(text: &str) -> anyhow::Result<std::path::PathBuf> {
+    let config_path = Config::config_file_path();
+    let dir = config_path
+        .parent()
+        .ok_or_else(|| anyhow::anyhow!("No config directory"))?;
+    std::fs::create_dir_all(dir)?;
+    let path = dir.join("host-info.txt");
+    std::fs::write(&path, text)?;
+    Ok(path)
+}
+
+fn centered_rect(width: u16, height: u16, area: Rect) -> Rect {
+    let x = area.x + (area.width.saturating_sub(width)) / 2;
+    let y = area.y + (area.height.saturating_sub(height)) / 2;
+    Rect::new(x, y, width, height)
+}
@@ -1,6 +1,7 @@
 //! VM Import Wizard screens
 //!
-//! A multi-step wizard for importing VMs from libvirt XML and quickemu .conf files.
+//! A multi-step wizard for importing VMs from libvirt XML, quickemu .conf files,
+//! OVA/OVF appliances, and QEMU processes started outside vm-curator.
 
 use anyhow::Result;
 use crossterm::event::{KeyCode, KeyEvent};
@@ -11,7 +12,8 @@ use ratatui::{
 };
 
 use crate::app::{
-    App, FileBrowserMode, ImportDiskAction, ImportSource, ImportStep, ImportWizardState,
+    App, BackgroundResult, FileBrowserMode, ImportDiskAction, ImportSource, ImportStep,
+    ImportWizardState,
 };
 use crate::vm::import;
 
@@ -68,22 +70,7 @@ fn render_step_select_source(state: &ImportWizardState, frame: &mut Frame, area:
         .style(Style::default().fg(Color::White));
     frame.render_widget(desc, chunks[0]);
 
-    let options: &[(&str, &str)] = &[
-        (
-            "libvirt (XML)",
-            "Import from libvirt/virt-manager domain XML",
-        ),
-        (
-            "quickemu (.conf)",
-            "Import from quickemu configuration file",
-        ),
-        (
-            "Browse for config file...",
-            "Browse filesystem for .xml or .conf file",
-        ),
-    ];
-
-    let items: Vec<ListItem> = options
+    let items: Vec<ListItem> = SOURCE_OPTIONS
         .iter()
         .enumerate()
         .map(|(i, (label, desc))| {
@@ -126,6 +113,8 @@ fn render_step_select_vm(state: &ImportWizardState, frame: &mut Frame, area: Rec
     let source_label = match state.source {
         Some(ImportSource::Libvirt) => "libvirt",
         Some(ImportSource::Quickemu) => "quickemu",
+        Some(ImportSource::Ova) => "OVA",
+        Some(ImportSource::Process) => "running QEMU",
         None => "unknown",
     };
 
@@ -324,11 +313,15 @@ fn render_step_configure_disk(state: &ImportWizardState, frame: &mut Frame, area
 
             let readable = vm.disks_readable.get(i).copied().unwrap_or(false);
             let status = if readable { "" } else { " (not readable!)" };
+            let format = match state.foreign_disk_formats.get(i) {
+                Some(Some(format)) => format!(" [{}]", format),
+                _ => String::new(),
+            };
 
             content_lines.push(Line::from(vec![
                 Span::styled("  Disk: ", Style::default().fg(Color::Gray)),
                 Span::styled(
-                    format!("{} ({}){}", disk.display(), size_str, status),
+                    format!("{} ({}){}{}", disk.display(), size_str, format, status),
                     Style::default().fg(if readable { Color::White } else { Color::Red }),
                 ),
             ]));
@@ -353,6 +346,11 @@ fn render_step_configure_disk(state: &ImportWizardState, frame: &mut Frame, area
             "Move",
             "Relocates disk to VM library.",
         ),
+        (
+            ImportDiskAction::Convert,
+            "Convert to qcow2",
+            "Copies into the VM folder as qcow2. Snapshots work.",
+        ),
     ];
 
     for (i, (action, label, desc)) in actions.iter().enumerate() {
@@ -378,19 +376,47 @@ fn render_step_configure_disk(state: &ImportWizardState, frame: &mut Frame, area
     let content = Paragraph::new(content_lines);
     frame.render_widget(content, chunks[2]);
 
-    let warning_text = match state.disk_action {
-        ImportDiskAction::Symlink => {
+    let foreign: Vec<&str> = state
+        .foreign_disk_formats
+        .iter()
+        .flatten()
+        .map(String::as_str)
+        .collect();
+    let (warning_text, warning_color) = match state.disk_action {
+        ImportDiskAction::Convert if foreign.is_empty() => (
+            "Note: No VMDK/VDI/VHD disks found; disks are copied unchanged.".to_string(),
+            Color::DarkGray,
+        ),
+        ImportDiskAction::Convert => (
+            "Note: qemu-img converts each disk into the VM folder; this can take a while.\nThe original files are not modified."
+                .to_string(),
+            Color::DarkGray,
+        ),
+        _ if !foreign.is_empty() => (
+            format!(
+                "Warning: {} disk(s) kept in their original format lose snapshot support.\nChoose \"Convert to qcow2\" to keep snapshots working.",
+                foreign.join("/")
+            ),
+            Color::Yellow,
+        ),
+        ImportDiskAction::Symlink => (
             "Note: Symlinked disks depend on the original file remaining in place.\nIf the original is deleted or moved, the VM will fail to start."
-        }
-        ImportDiskAction::Copy => {
+                .to_string(),
+            Color::DarkGray,
+        ),
+        ImportDiskAction::Copy => (
             "Note: Copying may take a long time for large disk images.\nThe original file is not modified."
-        }
-        ImportDiskAction::Move => {
+                .to_string(),
+            Color::DarkGray,
+        ),
+        ImportDiskAction::Move => (
             "Note: The original disk file will be moved to the VM library.\nThe source VM will no longer have access to it."
-        }
+                .to_string(),
+            Color::DarkGray,
+        ),
     };
     let warning = Paragraph::new(warning_text)
-        .style(Style::default().fg(Color::DarkGray))
+        .style(Style::default().fg(warning_color))
         .wrap(Wrap { trim: false });
     frame.render_widget(warning, chunks[3]);
 
@@ -514,6 +540,7 @@ fn render_step_review(state: &ImportWizardState, frame: &mut Frame, area: Rect)
             ImportDiskAction::Symlink => "Symlink",
             ImportDiskAction::Copy => "Copy",
             ImportDiskAction::Move => "Move",
+            ImportDiskAction::Convert => "Convert to qcow2",
         };
         lines.push(Line::from(vec![
             Span::styled("  Disk:       ", Style::default().fg(Color::Gray)),
@@ -571,6 +598,27 @@ pub fn handle_key(app: &mut App, key: KeyEvent) -> Result<()> {
     }
 }
 
+/// Rows of the source step (label, description), in the order
+/// [`handle_select_source`] acts on them
+const SOURCE_OPTIONS: &[(&str, &str)] = &[
+    (
+        "libvirt (XML)",
+        "Import from libvirt/virt-manager domain XML",
+    ),
+    (
+        "quickemu (.conf)",
+        "Import from quickemu configuration file",
+    ),
+    (
+        "Running QEMU process",
+        "Import a VM started by hand from its command line",
+    ),
+    (
+        "Browse for config file...",
+        "Browse for .xml, .conf, .ova or .ovf file",
+    ),
+];
+
 fn handle_select_source(app: &mut App, key: KeyEvent) -> Result<()> {
     match key.code {
         KeyCode::Esc => {
@@ -578,7 +626,7 @@ fn handle_select_source(app: &mut App, key: KeyEvent) -> Result<()> {
         }
         KeyCode::Char('j') | KeyCode::Down => {
             if let Some(ref mut state) = app.import_state {
-                if state.field_focus < 2 {
+                if state.field_focus + 1 < SOURCE_OPTIONS.len() {
                     state.field_focus += 1;
                 }
             }
@@ -620,8 +668,22 @@ fn handle_select_source(app: &mut App, key: KeyEvent) -> Result<()> {
                     }
                 }
                 2 => {
+                    // QEMU processes not matched to a library VM
+                    let managed: Vec<u32> = app.running_vms.values().copied().collect();
+                    let library_path = app.config.vm_library_path.clone();
+                    if let Some(ref mut state) = app.import_state {
+                        state.source = Some(ImportSource::Process);
+                        state.discovered_vms =
+                            import::discover_qemu_processes(&managed, &library_path);
+                        state.selected_vm_index = 0;
+                        state.step = ImportStep::SelectVm;
+                        state.field_focus = 0;
+                        state.error_message = None;
+                    }
+                }
+                3 => {
                     // Browse for config file
-                    app.load_file_browser(FileBrowserMode::ImportConfig);
+                    app.open_file_browser(FileBrowserMode::ImportConfig);
                     app.push_screen(crate::app::Screen::FileBrowser);
                 }
                 _ => {}
@@ -658,7 +720,7 @@ fn handle_select_vm(app: &mut App, key: KeyEvent) -> Result<()> {
         }
         KeyCode::Char('b') | KeyCode::Char('B') => {
             // Browse for config file
-            app.load_file_browser(FileBrowserMode::ImportConfig);
+            app.open_file_browser(FileBrowserMode::ImportConfig);
             app.push_screen(crate::app::Screen::FileBrowser);
         }
         KeyCode::Enter => {
@@ -671,6 +733,7 @@ fn handle_select_vm(app: &mut App, key: KeyEvent) -> Result<()> {
                         &crate::app::CreateWizardState::generate_folder_name(&vm.name),
                     );
                     state.selected_vm = Some(vm.clone());
+                    detect_disk_formats(state);
                     state.error_message = None;
                     state.field_focus = 0;
 
@@ -730,7 +793,7 @@ fn handle_configure_disk(app: &mut App, key: KeyEvent) -> Result<()> {
         }
         KeyCode::Char('j') | KeyCode::Down => {
             if let Some(ref mut state) = app.import_state {
-                if state.field_focus < 2 {
+                if state.field_focus < 3 {
                     state.field_focus += 1;
                 }
             }
@@ -749,6 +812,7 @@ fn handle_configure_disk(app: &mut App, key: KeyEvent) -> Result<()> {
                     0 => state.disk_action = ImportDiskAction::Symlink,
                     1 => state.disk_action = ImportDiskAction::Copy,
                     2 => state.disk_action = ImportDiskAction::Move,
+                    3 => state.disk_action = ImportDiskAction::Convert,
                     _ => {}
                 }
 
@@ -787,29 +851,9 @@ fn handle_review(app: &mut App, key: KeyEvent) -> Result<()> {
         }
         KeyCode::Enter => {
             // Execute import
-            let result = execute_import_from_state(app);
-            match result {
-                Ok(()) => {
-                    let vm_name = app
-                        .import_state
-                        .as_ref()
-                        .map(|s| s.vm_name.clone())
-                        .unwrap_or_default();
-                    app.import_state = None;
-
-                    // Pop the ImportWizard screen
-                    while app.screen == crate::app::Screen::ImportWizard {
-                        app.pop_screen();
-                    }
-
-                    // Refresh VM list
-                    let _ = app.refresh_vms();
-                    app.set_status(format!("Imported: {}", vm_name));
-                }
-                Err(e) => {
-                    if let Some(ref mut state) = app.import_state {
-                        state.error_message = Some(e.to_string());
-                    }
+            if let Err(e) = execute_import_from_state(app) {
+                if let Some(ref mut state) = app.import_state {
+                    state.error_message = Some(e.to_string());
                 }
             }
         }
@@ -846,8 +890,11 @@ fn handle_review_editing(app: &mut App, key: KeyEvent) -> Result<()> {
     Ok(())
 }
 
-/// Execute the import using current wizard state
-fn execute_import_from_state(app: &App) -> Result<()> {
+/// Start the import on a background thread using current wizard state.
+///
+/// Disk copies and conversions can take minutes, so progress is reported
+/// through the overlay and the result arrives as `ImportFinished`.
+fn execute_import_from_state(app: &mut App) -> Result<()> {
     let state = app
         .import_state
         .as_ref()
@@ -865,17 +912,54 @@ fn execute_import_from_state(app: &App) -> Result<()> {
         anyhow::bail!("Folder name cannot be empty");
     }
 
-    import::execute_import(
-        &app.config.vm_library_path,
-        vm,
-        &state.vm_name,
-        &state.folder_name,
-        state.disk_action,
-    )?;
+    let library_path = app.config.vm_library_path.clone();
+    let mut vm = vm.clone();
+    vm.qemu_config.ovmf_paths = app.config.ovmf_override();
+    let vm_name = state.vm_name.clone();
+    let folder_name = state.folder_name.clone();
+    let disk_action = state.disk_action;
+    let tx = app.background_tx.clone();
+    app.start_progress(format!("Importing {}", vm_name));
+
+    std::thread::spawn(move || {
+        let result = import::execute_import(
+            &library_path,
+            &vm,
+            &vm_name,
+            &folder_name,
+            disk_action,
+            |label, percent| {
+                let _ = tx.send(BackgroundResult::Progress {
+                    label: label.to_string(),
+                    percent: Some(percent),
+                });
+            },
+        );
+        let _ = tx.send(BackgroundResult::ImportFinished {
+            vm_name,
+            error: result.err().map(|e| format!("{:#}", e)),
+        });
+    });
 
     Ok(())
 }
 
+/// Record which of the selected VM's disks are in a VMware/VirtualBox/Hyper-V
+/// format and default to converting them so snapshots keep working
+pub fn detect_disk_formats(state: &mut ImportWizardState) {
+    state.foreign_disk_formats = state
+        .selected_vm
+        .iter()
+        .flat_map(|vm| vm.disk_paths.iter())
+        .map(|disk| import::foreign_disk_format(disk).map(str::to_string))
+        .collect();
+    state.disk_action = if state.foreign_disk_formats.iter().any(Option::is_some) {
+        ImportDiskAction::Convert
+    } else {
+        ImportDiskAction::Symlink
+    };
+}
+
 // =========================================================================
 // Helpers
 // =========================================================================
@@ -0,0 +1,200 @@
+//! ISO Library Screen
+//!
+//! Lists the install ISOs in the ISO library directory with their size and
+//! the OS detected on each, so images that are no longer needed can be
+//! deleted to reclaim space.
+
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{
+    layout::Rect,
+    prelude::*,
+    style::{Color, Modifier, Style},
+    widgets::{Block, Borders, Clear, Paragraph},
+};
+
+use crate::app::{App, ConfirmAction, Screen};
+use crate::vm::iso_library;
+use crate::vm::snapshot::format_size;
+
+/// Open the screen and read the ISO library directory in the background
+pub fn open(app: &mut App) {
+    app.iso_library_selected = 0;
+    app.iso_library_entries.clear();
+    app.load_iso_library();
+    app.push_screen(Screen::IsoLibrary);
+}
+
+/// Total size of every ISO in the library
+pub fn total_bytes(app: &App) -> u64 {
+    app.iso_library_entries.iter().map(|i| i.size_bytes).sum()
+}
+
+/// Render the ISO library screen
+pub fn render(app: &App, frame: &mut Frame) {
+    let area = frame.area();
+    let dialog_width = 90.min(area.width.saturating_sub(4));
+    let dialog_height = 24.min(area.height.saturating_sub(4));
+
+    let dialog_area = centered_rect(dialog_width, dialog_height, area);
+    frame.render_widget(Clear, dialog_area);
+
+    let block = Block::default()
+        .title(format!(
+            " ISO Library ({}, {}) ",
+            app.iso_library_entries.len(),
+            format_size(total_bytes(app))
+        ))
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::Cyan))
+        .style(Style::default().bg(Color::Black));
+
+    let inner = block.inner(dialog_area);
+    frame.render_widget(block, dialog_area);
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(2),
+            Constraint::Min(3),
+            Constraint::Length(2),
+        ])
+        .split(inner.inner(Margin::new(1, 0)));
+
+    frame.render_widget(
+        Paragraph::new(Line::styled(
+            app.config.iso_dir().display().to_string(),
+            Style::default().fg(Color::DarkGray),
+        )),
+        chunks[0],
+    );
+
+    let visible = chunks[1].height as usize;
+    let lines: Vec<Line> = if app.iso_library_entries.is_empty() && app.iso_library_loading {
+        vec![Line::styled(
+            "Reading the ISO library...",
+            Style::default().fg(Color::DarkGray),
+        )]
+    } else if app.iso_library_entries.is_empty() {
+        vec![Line::styled(
+            "No ISOs yet. ISOs downloaded from the Create VM wizard are saved here.",
+            Style::default().fg(Color::DarkGray),
+        )]
+    } else {
+        let skip = app
+            .iso_library_selected
+            .saturating_sub(visible.saturating_sub(1));
+        app.iso_library_entries
+            .iter()
+            .enumerate()
+            .skip(skip)
+            .take(visible)
+            .map(|(i, iso)| {
+                let selected = i == app.iso_library_selected;
+                let name_style = if selected {
+                    Style::default()
+                        .fg(Color::Yellow)
+                        .add_modifier(Modifier::BOLD)
+                } else {
+                    Style::default().fg(Color::White)
+                };
+                let (os, os_style) = if iso.partial {
+                    ("partial download".to_string(), Color::Red)
+                } else if let Some(ref os) = iso.os {
+                    (os.description.clone(), Color::Green)
+                } else {
+                    ("unknown".to_string(), Color::DarkGray)
+                };
+                Line::from(vec![
+                    Span::styled(if selected { "> " } else { "  " }, name_style),
+                    Span::styled(format!("{:<42.41}", iso.name), name_style),
+                    Span::styled(format!("{:<28.27}", os), Style::default().fg(os_style)),
+                    Span::styled(
+                        format!("{:>8}", format_size(iso.size_bytes)),
+                        Style::default().fg(Color::Cyan),
+                    ),
+                ])
+            })
+            .collect()
+    };
+    frame.render_widget(Paragraph::new(lines), chunks[1]);
+
+    let modified = app
+        .iso_library_entries
+        .get(app.iso_library_selected)
+        .and_then(|iso| iso.modified)
+        .map(|t| format!("Modified {}", t.format("%Y-%m-%d %H:%M")))
+        .unwrap_or_default();
+    frame.render_widget(
+        Paragraph::new(vec![
+            Line::styled(modified, Style::default().fg(Color::DarkGray)),
+            Line::styled(
+                "[j/k] Navigate  [d/Del] Delete  [r] Rescan  [Esc] Back",
+                Style::default().fg(Color::DarkGray),
+            ),
+        ]),
+        chunks[2],
+    );
+}
+
+/// Handle key input for the ISO library screen
+pub fn handle_key(app: &mut App, key: KeyEvent) -> anyhow::Result<()> {
+    match key.code {
+        KeyCode::Esc | KeyCode::Char('q') => app.pop_screen(),
+        KeyCode::Up | KeyCode::Char('k') => {
+            app.iso_library_selected = app.iso_library_selected.saturating_sub(1);
+        }
+        KeyCode::Down | KeyCode::Char('j') => {
+            app.iso_library_selected =
+                (app.iso_library_selected + 1).min(app.iso_library_entries.len().saturating_sub(1));
+        }
+        KeyCode::Char('d') | KeyCode::Delete => {
+            if let Some(iso) = app.iso_library_entries.get(app.iso_library_selected) {
+                let downloading = app.iso_download.as_ref().map(|d| d.dest.as_path());
+                if downloading.is_some_and(|dest| iso_library::is_download_target(iso, dest)) {
+                    app.set_status(format!(
+                        "{} is still downloading; cancel the download first",
+                        iso.name
+                    ));
+                    return Ok(());
+                }
+                let path = iso.path.clone();
+                let used_by = iso_library::vms_using_iso(&app.vms, &path);
+                app.push_screen(Screen::Confirm(ConfirmAction::DeleteIso { path, used_by }));
+            }
+        }
+        KeyCode::Char('r') | KeyCode::F(5) => app.load_iso_library(),
+        _ => {}
+    }
+    Ok(())
+}
+
+/// Permanently delete the ISO at `path` and re-read the library
+pub fn delete_iso(app: &mut App, path: &std::path::Path) {
+    let Some(iso) = app
+        .iso_library_entries
+        .iter()
+        .find(|iso| iso.path == path)
+        .cloned()
+    else {
+        return;
+    };
+    let downloading = app.iso_download.as_ref().map(|d| d.dest.as_path());
+    match iso_library::delete_iso(&iso, downloading) {
+        Ok(()) => {
+            app.iso_library_entries.retain(|i| i.path != iso.path);
+            app.load_iso_library();
+            app.set_status(format!(
+                "Deleted {}, {} freed",
+                iso.name,
+                format_size(iso.size_bytes)
+            ));
+        }
+        Err(e) => app.show_error(format!("{:#}", e)),
+    }
+}
+
+fn centered_rect(width: u16, height: u16, area: Rect) -> Rect {
+    let x = area.x + (area.width.saturating_sub(width)) / 2;
+    let y = area.y + (area.height.saturating_sub(height)) / 2;
+    Rect::new(x, y, width, height)
+}
@@ -0,0 +1,130 @@
+//! Launch Hooks Screen
+//!
+//! Lists the selected VM's pre-launch and post-stop hook scripts. Hooks are
+//! edited in the raw script editor and run by the launcher around launch.sh.
+
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{
+    layout::Rect,
+    prelude::*,
+    style::{Color, Modifier, Style},
+    widgets::{Block, Borders, Clear, Paragraph, Wrap},
+};
+
+use crate::app::{App, ConfirmAction, Screen};
+use crate::vm::{HOOK_ENV_VARS, LAUNCH_LOG, POST_STOP_HOOK, PRE_LAUNCH_HOOK};
+
+/// Hook files in display order, with when each one runs
+const HOOKS: &[(&str, &str)] = &[
+    (PRE_LAUNCH_HOOK, "before launch; non-zero exit aborts it"),
+    (POST_STOP_HOOK, "after the VM stops"),
+];
+
+/// Render the launch hooks screen
+pub fn render(app: &App, frame: &mut Frame) {
+    let area = frame.area();
+    let dialog_width = 70.min(area.width.saturating_sub(4));
+    let dialog_height = 20.min(area.height.saturating_sub(4));
+
+    let dialog_area = centered_rect(dialog_width, dialog_height, area);
+    frame.render_widget(Clear, dialog_area);
+
+    let Some(vm) = app.selected_vm() else {
+        return;
+    };
+
+    let block = Block::default()
+        .title(format!(" {} - Launch Hooks ", vm.display_name()))
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::Cyan))
+        .style(Style::default().bg(Color::Black));
+
+    let inner = block.inner(dialog_area);
+    frame.render_widget(block, dialog_area);
+
+    let mut lines = vec![Line::raw("")];
+    for (i, (hook, when)) in HOOKS.iter().enumerate() {
+        let selected = i == app.launch_hooks_selected;
+        let name_style = if selected {
+            Style::default()
+                .fg(Color::Yellow)
+                .add_modifier(Modifier::BOLD)
+        } else {
+            Style::default().fg(Color::White)
+        };
+        let (state, state_color) = if vm.path.join(hook).is_file() {
+            ("set", Color::Green)
+        } else {
+            ("not set", Color::DarkGray)
+        };
+        lines.push(Line::from(vec![
+            Span::styled(if selected { "> " } else { "  " }, name_style),
+            Span::styled(format!("{:<15}", hook), name_style),
+            Span::styled(format!("{:<9}", state), Style::default().fg(state_color)),
+            Span::styled(*when, Style::default().fg(Color::DarkGray)),
+        ]));
+    }
+
+    lines.push(Line::raw(""));
+    lines.push(Line::styled(
+        "Hooks run with your environment, plus:",
+        Style::default().fg(Color::White),
+    ));
+    for (name, desc) in HOOK_ENV_VARS {
+        lines.push(Line::from(vec![
+            Span::styled(format!("  {:<14}", name), Style::default().fg(Color::Cyan)),
+            Span::styled(*desc, Style::default().fg(Color::DarkGray)),
+        ]));
+    }
+    lines.push(Line::raw(""));
+    lines.push(Line::styled(
+        format!(
+            "Hook output is appended to {} in the VM folder.",
+            LAUNCH_LOG
+        ),
+        Style::default().fg(Color::DarkGray),
+    ));
+    lines.push(Line::raw(""));
+    lines.push(Line::styled(
+        "[Enter] Edit  [d] Delete  [Esc] Back",
+        Style::default().fg(Color::DarkGray),
+    ));
+
+    let para = Paragraph::new(lines).wrap(Wrap { trim: false });
+    frame.render_widget(para, inner.inner(Margin::new(1, 0)));
+}
+
+/// Handle key input for the launch hooks screen
+pub fn handle_key(app: &mut App, key: KeyEvent) -> anyhow::Result<()> {
+    let hook = HOOKS[app.launch_hooks_selected.min(HOOKS.len() - 1)].0;
+
+    match key.code {
+        KeyCode::Esc => app.pop_screen(),
+        KeyCode::Up | KeyCode::Char('k') => {
+            app.launch_hooks_selected = app.launch_hooks_selected.saturating_sub(1);
+        }
+        KeyCode::Down | KeyCode::Char('j') => {
+            app.launch_hooks_selected = (app.launch_hooks_selected + 1).min(HOOKS.len() - 1);
+        }
+        KeyCode::Enter => {
+            app.load_hook_into_editor(hook);
+            app.push_screen(Screen::RawScript);
+        }
+        KeyCode::Char('d') => {
+            let exists = app
+                .selected_vm()
+                .is_some_and(|vm| vm.path.join(hook).is_file());
+            if exists {
+                app.push_screen(Screen::Confirm(ConfirmAction::DeleteHook(hook)));
+            }
+        }
+        _ => {}
+    }
+    Ok(())
+}
+
+fn centered_rect(width: u16, height: u16, area: Rect) -> Rect {
+    let x = area.x + (area.width.saturating_sub(width)) / 2;
+    let y = area.y + (area.height.saturating_sub(height)) / 2;
+    Rect::new(x, y, width, height)
+}
@@ -0,0 +1,114 @@
+//! Library Setup Screen
+//!
+//! Shown at startup when the VM library directory is missing or unreadable
+//! (first run, a mistyped `--library`). Offers to create the directory or
+//! to pick another one in Settings, instead of an empty VM list.
+
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{
+    layout::Rect,
+    prelude::*,
+    style::{Color, Modifier, Style},
+    widgets::{Block, Borders, Clear, Paragraph, Wrap},
+};
+
+use crate::app::{App, Screen};
+use crate::vm::LibraryIssue;
+
+/// Render the library setup prompt
+pub fn render(app: &App, frame: &mut Frame) {
+    let area = frame.area();
+    let dialog_width = 70.min(area.width.saturating_sub(4));
+    let dialog_height = 15.min(area.height.saturating_sub(4));
+
+    let dialog_area = centered_rect(dialog_width, dialog_height, area);
+    frame.render_widget(Clear, dialog_area);
+
+    let block = Block::default()
+        .title(" VM Library ")
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::Yellow))
+        .style(Style::default().bg(Color::Black));
+
+    let inner = block.inner(dialog_area);
+    frame.render_widget(block, dialog_area);
+
+    let path = &app.config.vm_library_path;
+    let issue = app
+        .library_issue
+        .as_ref()
+        .map(|issue| issue.describe(path))
+        .unwrap_or_default();
+    let key = Style::default()
+        .fg(Color::Yellow)
+        .add_modifier(Modifier::BOLD);
+
+    let mut lines = vec![
+        Line::raw(""),
+        Line::styled(issue, Style::default().fg(Color::White)),
+        Line::raw(""),
+        Line::styled(
+            "VMs are stored one per directory in the library.",
+            Style::default().fg(Color::Gray),
+        ),
+        Line::raw(""),
+    ];
+    if app.library_issue == Some(LibraryIssue::Missing) {
+        lines.push(Line::from(vec![
+            Span::styled("[c] ", key),
+            Span::raw(format!("Create {}", path.display())),
+        ]));
+    }
+    lines.extend([
+        Line::from(vec![
+            Span::styled("[s] ", key),
+            Span::raw("Choose another directory in Settings"),
+        ]),
+        Line::from(vec![
+            Span::styled("[Esc] ", key),
+            Span::raw("Continue without VMs"),
+        ]),
+    ]);
+
+    let para = Paragraph::new(lines).wrap(Wrap { trim: false });
+    frame.render_widget(para, inner.inner(Margin::new(2, 0)));
+}
+
+/// Handle key input for the library setup prompt
+pub fn handle_key(app: &mut App, key: KeyEvent) -> anyhow::Result<()> {
+    match key.code {
+        KeyCode::Char('c') if app.library_issue == Some(LibraryIssue::Missing) => {
+            let path = app.config.vm_library_path.clone();
+            match crate::fs::setup_vm_directory(&path) {
+                Ok(cow_disabled) => {
+                    app.library_issue = None;
+                    app.pop_screen();
+                    app.refresh_vms()?;
+                    app.set_status(if cow_disabled {
+                        format!("Created {} (BTRFS copy-on-write disabled)", path.display())
+                    } else {
+                        format!("Created {}", path.display())
+                    });
+                }
+                Err(e) => app.show_error(format!("{:#}", e)),
+            }
+        }
+        KeyCode::Char('s') => {
+            app.library_issue = None;
+            app.pop_screen();
+            app.push_screen(Screen::Settings);
+        }
+        KeyCode::Esc | KeyCode::Char('q') => {
+            app.library_issue = None;
+            app.pop_screen();
+        }
+        _ => {}
+    }
+    Ok(())
+}
+
+fn centered_rect(width: u16, height: u16, area: Rect) -> Rect {
+    let x = area.x + (area.width.saturating_sub(width)) / 2;
+    let y = area.y + (area.height.saturating_sub(height)) / 2;
+    Rect::new(x, y, width, height)
+}
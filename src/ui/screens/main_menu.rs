@@ -42,6 +42,11 @@ pub fn render(app: &App, frame: &mut Frame) {
     let ascii_art = app.selected_vm_ascii();
 
     let notes = app.selected_vm().and_then(|vm| vm.notes.as_deref());
+    let launch_summary = app.selected_vm().map(|vm| vm.launch_summary());
+    let shared_disks = app
+        .selected_vm()
+        .map(|vm| crate::vm::discovery::shared_disk_warnings(vm, &app.vms))
+        .unwrap_or_default();
 
     AsciiInfoWidget {
         ascii_art,
@@ -49,6 +54,8 @@ pub fn render(app: &App, frame: &mut Frame) {
         vm_name: &vm_name,
         scroll: app.info_scroll,
         notes,
+        launch_summary: launch_summary.as_deref(),
+        shared_disks: &shared_disks,
     }
     .render(main_chunks[1], frame.buffer_mut());
 
@@ -99,6 +106,8 @@ fn render_help_bar(app: &App, area: Rect, frame: &mut Frame) {
         Span::raw(" Stop "),
         Span::styled(" [m]", Style::default().fg(Color::Yellow)),
         Span::raw(" Manage "),
+        Span::styled(" [d]", Style::default().fg(Color::Yellow)),
+        Span::raw(" Details "),
         Span::styled(" [c]", Style::default().fg(Color::Yellow)),
         Span::raw(" Create "),
         Span::styled(" [i]", Style::default().fg(Color::Yellow)),
@@ -107,11 +116,35 @@ fn render_help_bar(app: &App, area: Rect, frame: &mut Frame) {
         Span::raw(" Settings "),
         Span::styled(" [/]", Style::default().fg(Color::Yellow)),
         Span::raw(" Search "),
+        Span::styled(" [o]", Style::default().fg(Color::Yellow)),
+        Span::raw(" Sort "),
         Span::styled(" [?]", Style::default().fg(Color::Yellow)),
         Span::raw(" Help "),
         Span::styled(" [q]", Style::default().fg(Color::Yellow)),
         Span::raw(" Quit "),
     ];
+    if app.bulk_mode {
+        hints = vec![
+            Span::styled(
+                " BULK ",
+                Style::default().fg(Color::Black).bg(Color::Yellow),
+            ),
+            Span::styled(" [Space]", Style::default().fg(Color::Yellow)),
+            Span::raw(" Mark "),
+            Span::styled(" [a]", Style::default().fg(Color::Yellow)),
+            Span::raw(" All "),
+            Span::styled(" [Enter]", Style::default().fg(Color::Yellow)),
+            Span::raw(" Start "),
+            Span::styled(" [x]", Style::default().fg(Color::Yellow)),
+            Span::raw(" Stop "),
+            Span::styled(" [n]", Style::default().fg(Color::Yellow)),
+            Span::raw(" Snapshot "),
+            Span::styled(" [D]", Style::default().fg(Color::Yellow)),
+            Span::raw(" Delete "),
+            Span::styled(" [v/Esc]", Style::default().fg(Color::Yellow)),
+            Span::raw(" Leave "),
+        ];
+    }
 
     // Show stopping VM status
     if app.status_message.is_none() {
@@ -5,7 +5,9 @@ use ratatui::{
 
 use crate::app::App;
 use crate::config::Config;
+use crate::vm::qemu_config::{VgaType, CPU_FLAG_OPTIONS, CPU_PRESETS};
 use crate::vm::DiscoveredVm;
+use crate::vm::DisplayWindow;
 
 /// Menu item with name and description
 #[derive(Debug, Clone)]
@@ -19,7 +21,15 @@ pub struct MenuItem {
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum MenuAction {
     StopVm,
+    TestLaunch,
+    BugReport,
+    SuspendVm,
+    ResumeVm,
+    DiscardSuspendState,
+    BalloonTarget,
+    CompactDisk,
     BootOptions,
+    DirectKernelBoot,
     Snapshots,
     UsbPassthrough,
     PciPassthrough,
@@ -29,11 +39,28 @@ pub enum MenuAction {
     SingleGpuPassthrough,
     ChangeDisplay,
     Toggle3dAccel,
+    ToggleClipboard,
+    CpuFlags,
+    RtcSettings,
+    DiskThrottle,
+    UefiNvram,
+    LaunchHooks,
+    ToggleAutostart,
+    ToggleInstallMedia,
+    AttachConsole,
+    AddSerialConsole,
+    ConnectSsh,
+    FloppyDrives,
+    ChangeCd,
     EditNotes,
+    AsciiArt,
     RenameVm,
+    WindowTitle,
+    ExportLibvirtXml,
     ResetVm,
     DeleteVm,
     EditRawConfig,
+    RegenerateScript,
 }
 
 /// Get menu items based on config and VM state
@@ -44,6 +71,11 @@ pub fn get_menu_items(vm: &DiscoveredVm, config: &Config) -> Vec<MenuItem> {
             description: "Normal, install, or custom ISO boot",
             action: MenuAction::BootOptions,
         },
+        MenuItem {
+            name: "Direct Kernel Boot",
+            description: "Boot a kernel/initrd directly (-kernel, -append)",
+            action: MenuAction::DirectKernelBoot,
+        },
         MenuItem {
             name: "Snapshots",
             description: "Create, restore, or delete snapshots",
@@ -106,24 +138,195 @@ pub fn get_menu_items(vm: &DiscoveredVm, config: &Config) -> Vec<MenuItem> {
             description: gl_desc,
             action: MenuAction::Toggle3dAccel,
         },
+    ]);
+
+    // Clipboard sharing rides on the SPICE agent channel, so only offer it
+    // for SPICE displays
+    if vm.config.has_spice_display() {
+        items.push(MenuItem {
+            name: "Clipboard Sharing",
+            description: if vm.config.has_spice_agent() {
+                "Currently ON - toggle off (needs spice-vdagent in guest)"
+            } else {
+                "Currently OFF - toggle on (needs spice-vdagent in guest)"
+            },
+            action: MenuAction::ToggleClipboard,
+        });
+    }
+
+    items.extend([
+        MenuItem {
+            name: "CPU Model & Flags",
+            description: "CPU model, Hyper-V enlightenments, kvm=off",
+            action: MenuAction::CpuFlags,
+        },
+        MenuItem {
+            name: "Clock (RTC)",
+            description: "Guest clock start (UTC, local, fixed date) and source",
+            action: MenuAction::RtcSettings,
+        },
+        MenuItem {
+            name: "Disk I/O Limits",
+            description: "Cap read/write IOPS and bandwidth per disk",
+            action: MenuAction::DiskThrottle,
+        },
+    ]);
+
+    if vm.config.uefi {
+        items.push(MenuItem {
+            name: "UEFI Variables",
+            description: "Back up or reset NVRAM, toggle Secure Boot",
+            action: MenuAction::UefiNvram,
+        });
+    }
+
+    items.extend([
+        MenuItem {
+            name: "Launch Hooks",
+            description: "Scripts run before launch and after the VM stops",
+            action: MenuAction::LaunchHooks,
+        },
+        MenuItem {
+            name: "Autostart",
+            description: if vm.autostart {
+                "Currently ON - started when vm-curator opens"
+            } else {
+                "Currently OFF - start when vm-curator opens"
+            },
+            action: MenuAction::ToggleAutostart,
+        },
+        MenuItem {
+            name: "Install Media Boot",
+            description: if vm.first_boot_done == Some(true) {
+                "Skipped on normal launches - toggle to boot the ISO again"
+            } else {
+                "Bootable on normal launches - toggle to boot from disk"
+            },
+            action: MenuAction::ToggleInstallMedia,
+        },
         MenuItem {
             name: "Edit Notes",
             description: "Add or edit personal notes for this VM",
             action: MenuAction::EditNotes,
         },
+        MenuItem {
+            name: "ASCII Art",
+            description: if vm.custom_ascii.is_some() {
+                "Custom logo set - select to show the OS logo again"
+            } else {
+                "Use a text file as this VM's logo"
+            },
+            action: MenuAction::AsciiArt,
+        },
         MenuItem {
             name: "Rename VM",
             description: "Change the VM's display name",
             action: MenuAction::RenameVm,
         },
+        MenuItem {
+            name: "Window Title",
+            description: "Title of the QEMU window (defaults to the VM name)",
+            action: MenuAction::WindowTitle,
+        },
+        MenuItem {
+            name: "Export to libvirt XML",
+            description: "Write a domain XML for virt-manager / virsh define",
+            action: MenuAction::ExportLibvirtXml,
+        },
+        MenuItem {
+            name: "Generate Bug Report",
+            description: "Redacted launch.sh, config, log and host info, saved and copied",
+            action: MenuAction::BugReport,
+        },
     ]);
 
+    // Headless guests are reached over a serial socket
+    if vm.config.serial_socket.is_some() {
+        items.push(MenuItem {
+            name: "Attach Console",
+            description: "Serial console of the running VM (Ctrl+] detaches)",
+            action: MenuAction::AttachConsole,
+        });
+    } else {
+        items.push(MenuItem {
+            name: "Add Serial Console",
+            description: "Expose the serial port on a socket for Attach Console",
+            action: MenuAction::AddSerialConsole,
+        });
+    }
+
+    if has_floppy_controller(vm) {
+        items.push(MenuItem {
+            name: "Floppy Drives",
+            description: "Insert, swap or eject floppies in the running VM",
+            action: MenuAction::FloppyDrives,
+        });
+    }
+
+    items.push(MenuItem {
+        name: "Change CD",
+        description: "Insert, swap or eject the ISO in the running VM's CD-ROM",
+        action: MenuAction::ChangeCd,
+    });
+
+    if crate::vm::ssh::can_ssh(vm.config.network.as_ref()) {
+        items.push(MenuItem {
+            name: "Connect via SSH",
+            description: "ssh into the running guest (port forward or bridge address)",
+            action: MenuAction::ConnectSsh,
+        });
+    }
+
+    items.push(MenuItem {
+        name: "Test Launch",
+        description: "Start QEMU paused to check the config, then quit",
+        action: MenuAction::TestLaunch,
+    });
+
     items.push(MenuItem {
         name: "Stop VM",
         description: "Shut down the running VM (ACPI poweroff)",
         action: MenuAction::StopVm,
     });
 
+    // One suspend slot per VM, separate from disk snapshots
+    if crate::vm::has_suspend_state(&vm.path) {
+        items.extend([
+            MenuItem {
+                name: "Resume",
+                description: "Restore the suspended VM where it left off",
+                action: MenuAction::ResumeVm,
+            },
+            MenuItem {
+                name: "Discard Suspended State",
+                description: "Delete the suspend slot so the VM boots fresh",
+                action: MenuAction::DiscardSuspendState,
+            },
+        ]);
+    } else {
+        items.push(MenuItem {
+            name: "Suspend",
+            description: "Save memory to the VM's suspend slot and shut down",
+            action: MenuAction::SuspendVm,
+        });
+    }
+
+    if vm.config.has_balloon() {
+        items.push(MenuItem {
+            name: "Memory Balloon",
+            description: "Shrink or regrow the running guest's RAM",
+            action: MenuAction::BalloonTarget,
+        });
+    }
+
+    if vm.config.supports_snapshots() {
+        items.push(MenuItem {
+            name: "Compact Disk",
+            description: "Reclaim space the guest has freed (qcow2)",
+            action: MenuAction::CompactDisk,
+        });
+    }
+
     // Add dangerous operations at the end
     items.extend([
         MenuItem {
@@ -141,6 +344,11 @@ pub fn get_menu_items(vm: &DiscoveredVm, config: &Config) -> Vec<MenuItem> {
             description: "Edit the launch.sh script directly",
             action: MenuAction::EditRawConfig,
         },
+        MenuItem {
+            name: "Regenerate Script",
+            description: "Rebuild launch.sh from its settings (backs up the old one)",
+            action: MenuAction::RegenerateScript,
+        },
     ]);
 
     // Check for GPU passthrough script
@@ -193,6 +401,14 @@ pub fn get_display_options(app: &App) -> Vec<(String, String)> {
         .collect()
 }
 
+/// Whether the VM has virtio graphics, whose preferred resolution can be
+/// set, and a display to show it on
+pub fn supports_resolution_hint(vm: &DiscoveredVm) -> bool {
+    let raw = &vm.config.raw_script;
+    crate::vm::lifecycle::script_graphics(raw) == VgaType::Virtio
+        && extract_display_from_script(raw) != "none"
+}
+
 /// Render the management menu
 pub fn render(app: &App, frame: &mut Frame) {
     let area = frame.area();
@@ -288,10 +504,27 @@ pub fn render(app: &App, frame: &mut Frame) {
 }
 
 /// Render boot options submenu
+/// Whether a VM's machine has a floppy controller (the PC targets)
+pub fn has_floppy_controller(vm: &DiscoveredVm) -> bool {
+    matches!(
+        vm.config.emulator.command(),
+        "qemu-system-i386" | "qemu-system-x86_64"
+    )
+}
+
+/// Rows on the boot options screen; the floppy drive row is PC-only
+pub fn boot_options_count(app: &App) -> usize {
+    if app.selected_vm().is_some_and(has_floppy_controller) {
+        10
+    } else {
+        9
+    }
+}
+
 pub fn render_boot_options(app: &App, frame: &mut Frame) {
     let area = frame.area();
     let dialog_width = 50.min(area.width.saturating_sub(4));
-    let dialog_height = 16.min(area.height.saturating_sub(4));
+    let dialog_height = 26.min(area.height.saturating_sub(4));
 
     let dialog_area = centered_rect(dialog_width, dialog_height, area);
     frame.render_widget(Clear, dialog_area);
@@ -324,8 +557,43 @@ pub fn render_boot_options(app: &App, frame: &mut Frame) {
         ])
         .split(h_chunks[1]);
 
-    let boot_items = [
+    let boot_order_desc = format!("{} (Enter to cycle)", app.boot_order.order_label());
+    let boot_menu_name = format!(
+        "Boot menu: {}",
+        if app.boot_order.menu { "On" } else { "Off" }
+    );
+
+    let tools_iso_name = format!(
+        "Tools ISO: {}",
+        app.tools_iso
+            .as_ref()
+            .and_then(|p| p.file_name())
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_else(|| "None".to_string())
+    );
+    let tools_iso_desc = if app.tools_iso.is_some() {
+        "Second CD-ROM on every boot ([x] to remove)"
+    } else {
+        "Attach a second CD-ROM, e.g. virtio-win drivers"
+    };
+
+    let floppy_drive_name = format!(
+        "Floppy drive B: {}",
+        app.floppy_drive
+            .as_ref()
+            .and_then(|p| p.file_name())
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_else(|| "Empty".to_string())
+    );
+    let floppy_drive_desc = if app.floppy_drive.is_some() {
+        "Stays inserted on every boot ([x] to eject)"
+    } else {
+        "Insert a floppy image (.img, .ima) for every boot"
+    };
+
+    let mut boot_items = vec![
         ("Normal boot", "Start the VM normally"),
+        ("Ephemeral boot", "Normal boot; disk changes are NOT saved"),
         ("Install mode", "Boot from installation media"),
         ("Boot with custom ISO", "Select an ISO file to boot"),
         (
@@ -336,7 +604,16 @@ pub fn render_boot_options(app: &App, frame: &mut Frame) {
             "Boot with floppy image",
             "Select a floppy image (.img, .ima) to boot",
         ),
+        ("Boot order", boot_order_desc.as_str()),
+        (
+            boot_menu_name.as_str(),
+            "Show the firmware boot menu (press Esc/F12 at boot)",
+        ),
+        (tools_iso_name.as_str(), tools_iso_desc),
     ];
+    if boot_options_count(app) > 9 {
+        boot_items.push((floppy_drive_name.as_str(), floppy_drive_desc));
+    }
 
     let items: Vec<ListItem> = boot_items
         .iter()
@@ -367,11 +644,106 @@ pub fn render_boot_options(app: &App, frame: &mut Frame) {
     frame.render_stateful_widget(list, v_chunks[1], &mut state);
 }
 
+/// Number of rows in the CPU editor: model, each flag, then each preset.
+pub fn cpu_flags_row_count() -> usize {
+    1 + CPU_FLAG_OPTIONS.len() + CPU_PRESETS.len()
+}
+
+/// Render the CPU model/flags editor
+pub fn render_cpu_flags(app: &App, frame: &mut Frame) {
+    let area = frame.area();
+    let dialog_width = 64.min(area.width.saturating_sub(4));
+    let dialog_height = 24.min(area.height.saturating_sub(4));
+
+    let dialog_area = centered_rect(dialog_width, dialog_height, area);
+    frame.render_widget(Clear, dialog_area);
+
+    let cpu = &app.cpu_config;
+    let block = Block::default()
+        .title(" CPU Model & Flags ")
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::Cyan))
+        .style(Style::default().bg(Color::Black));
+
+    let inner = block.inner(dialog_area);
+    frame.render_widget(block, dialog_area);
+
+    // Add horizontal margins
+    let h_chunks = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([
+            Constraint::Length(2), // Left margin
+            Constraint::Min(1),    // Content
+            Constraint::Length(2), // Right margin
+        ])
+        .split(inner);
+
+    let v_chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(1), // Top padding
+            Constraint::Min(1),    // Rows
+            Constraint::Length(2), // Composed -cpu value
+            Constraint::Length(1), // Help
+        ])
+        .split(h_chunks[1]);
+
+    let row_style = |i: usize| {
+        if i == app.selected_menu_item {
+            Style::default()
+                .fg(Color::Yellow)
+                .add_modifier(Modifier::BOLD)
+        } else {
+            Style::default().fg(Color::White)
+        }
+    };
+    let dim = Style::default().fg(Color::DarkGray);
+
+    let mut items = vec![ListItem::new(Line::from(vec![
+        Span::styled(format!("Model: {}", cpu.model), row_style(0)),
+        Span::styled("  [←/→] change", dim),
+    ]))];
+    for (i, (flag, desc)) in CPU_FLAG_OPTIONS.iter().enumerate() {
+        let mark = if cpu.has_flag(flag) { "[x]" } else { "[ ]" };
+        items.push(ListItem::new(Line::from(vec![
+            Span::styled(format!("{} {:20}", mark, flag), row_style(i + 1)),
+            Span::styled(*desc, dim),
+        ])));
+    }
+    let preset_base = 1 + CPU_FLAG_OPTIONS.len();
+    let active_preset = cpu.preset_label();
+    for (i, (label, _)) in CPU_PRESETS.iter().enumerate() {
+        let marker = if active_preset == Some(*label) {
+            " *"
+        } else {
+            ""
+        };
+        items.push(ListItem::new(Line::styled(
+            format!("Preset: {}{}", label, marker),
+            row_style(preset_base + i),
+        )));
+    }
+
+    let mut state = ListState::default();
+    state.select(Some(app.selected_menu_item));
+    frame.render_stateful_widget(List::new(items), v_chunks[1], &mut state);
+
+    let composed = Paragraph::new(format!("-cpu {}", cpu.to_arg()))
+        .style(Style::default().fg(Color::Green))
+        .wrap(ratatui::widgets::Wrap { trim: true });
+    frame.render_widget(composed, v_chunks[2]);
+
+    let help = Paragraph::new("[Space/Enter] Toggle/apply  [Esc] Back  (saved immediately)")
+        .style(dim)
+        .alignment(Alignment::Center);
+    frame.render_widget(help, v_chunks[3]);
+}
+
 /// Render display options submenu
 pub fn render_display_options(app: &App, frame: &mut Frame) {
     let area = frame.area();
     let dialog_width = 50.min(area.width.saturating_sub(4));
-    let dialog_height = 16.min(area.height.saturating_sub(4));
+    let dialog_height = 24.min(area.height.saturating_sub(4));
 
     let dialog_area = centered_rect(dialog_width, dialog_height, area);
     frame.render_widget(Clear, dialog_area);
@@ -407,6 +779,7 @@ pub fn render_display_options(app: &App, frame: &mut Frame) {
         .constraints([
             Constraint::Length(1), // Top padding
             Constraint::Min(1),    // Content
+            Constraint::Length(7), // Window options and keyboard layout
             Constraint::Length(2), // Help
         ])
         .split(h_chunks[1]);
@@ -446,15 +819,89 @@ pub fn render_display_options(app: &App, frame: &mut Frame) {
     let list = List::new(items);
     frame.render_stateful_widget(list, v_chunks[1], &mut state);
 
+    // Window options the current backend supports
+    let window = app
+        .selected_vm()
+        .map(|vm| crate::vm::lifecycle::parse_display_window(&vm.config.raw_script))
+        .unwrap_or_default();
+    let resolution_hint = app.selected_vm().is_some_and(supports_resolution_hint);
+    let on_off = |on: bool| if on { "on" } else { "off" };
+    let option_line = |key: &str, label: &str, value: String| {
+        Line::from(vec![
+            Span::styled(format!("[{}] ", key), Style::default().fg(Color::Yellow)),
+            Span::styled(format!("{:<18}", label), Style::default().fg(Color::White)),
+            Span::styled(value, Style::default().fg(Color::Cyan)),
+        ])
+    };
+    let mut window_lines = vec![Line::styled(
+        format!("Window ({})", current_display),
+        Style::default().fg(Color::Gray),
+    )];
+    if DisplayWindow::supports_zoom_to_fit(&current_display) {
+        window_lines.push(option_line(
+            "z",
+            "Zoom to fit",
+            on_off(window.zoom_to_fit).to_string(),
+        ));
+    }
+    if DisplayWindow::supports_full_screen(&current_display) {
+        window_lines.push(option_line(
+            "f",
+            "Start full screen",
+            on_off(window.full_screen).to_string(),
+        ));
+    }
+    if resolution_hint {
+        window_lines.push(option_line(
+            "r",
+            "Guest resolution",
+            window
+                .resolution
+                .map(|(w, h)| format!("{}x{}", w, h))
+                .unwrap_or_else(|| "guest default".to_string()),
+        ));
+    }
+    let graphics = app
+        .selected_vm()
+        .map(|vm| crate::vm::lifecycle::script_graphics(&vm.config.raw_script))
+        .unwrap_or_default();
+    if !DisplayWindow::adapter_supports_multiple_displays(&graphics) {
+        window_lines.push(Line::styled(
+            "    Monitors          needs virtio or qxl graphics",
+            Style::default().fg(Color::DarkGray),
+        ));
+    } else if !DisplayWindow::supports_multiple_displays(&current_display) {
+        window_lines.push(Line::styled(
+            "    Monitors          1 (gtk, sdl or spice-app for more)",
+            Style::default().fg(Color::DarkGray),
+        ));
+    } else {
+        window_lines.push(option_line(
+            "m",
+            "Monitors",
+            window.num_displays.to_string(),
+        ));
+    }
+    let keymap = app
+        .selected_vm()
+        .and_then(|vm| crate::vm::keymap::script_keymap(&vm.config.raw_script));
+    window_lines.push(Line::raw(""));
+    window_lines.push(option_line(
+        "l",
+        "Keyboard layout",
+        keymap.unwrap_or_else(|| "default (en-us)".to_string()),
+    ));
+    frame.render_widget(Paragraph::new(window_lines), v_chunks[2]);
+
     // Help text
     let help = Paragraph::new("[Enter] Select  [Esc] Back")
         .style(Style::default().fg(Color::DarkGray))
         .alignment(Alignment::Center);
-    frame.render_widget(help, v_chunks[2]);
+    frame.render_widget(help, v_chunks[3]);
 }
 
 /// Extract display setting from launch script
-fn extract_display_from_script(script: &str) -> String {
+pub fn extract_display_from_script(script: &str) -> String {
     // Look for -display X pattern
     if let Some(pos) = script.find("-display ") {
         let rest = &script[pos + 9..];
@@ -488,7 +935,13 @@ pub fn render_snapshots(app: &App, frame: &mut Frame) {
         .map(|vm| vm.config.supports_snapshots())
         .unwrap_or(false);
 
-    let title = if supports_snapshots {
+    let title = if supports_snapshots && !app.marked_snapshots.is_empty() {
+        format!(
+            " Snapshots ({}, {} marked) ",
+            app.snapshots.len(),
+            app.marked_snapshots.len()
+        )
+    } else if supports_snapshots {
         format!(" Snapshots ({}) ", app.snapshots.len())
     } else {
         " Snapshots (not supported) ".to_string()
@@ -542,10 +995,24 @@ pub fn render_snapshots(app: &App, frame: &mut Frame) {
         .split(content_area);
 
     // Action buttons
-    let actions = Paragraph::new(vec![Line::from(vec![
-        Span::styled("[c]", Style::default().fg(Color::Yellow)),
-        Span::raw(" Create new snapshot"),
-    ])]);
+    let vm_id = app.selected_vm().map(|vm| vm.id.as_str()).unwrap_or("");
+    let schedule = match app.snapshot_schedules.get(vm_id) {
+        Some(minutes) => match app.snapshot_countdown(vm_id) {
+            Some(next) => format!(" Auto: every {} min (next in {})", minutes, next),
+            None => format!(" Auto: every {} min while running", minutes),
+        },
+        None => " Auto snapshots: off".to_string(),
+    };
+    let actions = Paragraph::new(vec![
+        Line::from(vec![
+            Span::styled("[c]", Style::default().fg(Color::Yellow)),
+            Span::raw(" Create new snapshot"),
+        ]),
+        Line::from(vec![
+            Span::styled("[i]", Style::default().fg(Color::Yellow)),
+            Span::raw(schedule),
+        ]),
+    ]);
     frame.render_widget(actions, chunks[0]);
 
     // Snapshot list
@@ -568,8 +1035,13 @@ pub fn render_snapshots(app: &App, frame: &mut Frame) {
                     Style::default().fg(Color::White)
                 };
 
+                let mark = if app.marked_snapshots.contains(&snap.name) {
+                    "* "
+                } else {
+                    "  "
+                };
                 ListItem::new(vec![
-                    Line::styled(format!("  {}", snap.name), style),
+                    Line::styled(format!("{}{}", mark, snap.name), style),
                     Line::styled(
                         format!("    {} - {}", snap.date, snap.size),
                         Style::default().fg(Color::DarkGray),
@@ -586,7 +1058,12 @@ pub fn render_snapshots(app: &App, frame: &mut Frame) {
     }
 
     // Help
-    let help = Paragraph::new("[r] Restore  [d] Delete  [Esc] Back")
+    let help_text = if app.marked_snapshots.is_empty() {
+        "[Space] Mark  [r] Restore  [d] Delete  [Esc] Back"
+    } else {
+        "[Space] Mark  [r] Restore  [d] Delete marked  [Esc] Back"
+    };
+    let help = Paragraph::new(help_text)
         .style(Style::default().fg(Color::DarkGray))
         .alignment(Alignment::Center);
     frame.render_widget(help, chunks[2]);
@@ -1,12 +1,24 @@
 pub mod configuration;
 pub mod create_wizard;
+pub mod direct_kernel_boot;
+pub mod disk_throttle;
 pub mod help;
+pub mod host_info;
 pub mod import_wizard;
+pub mod iso_library;
+pub mod launch_hooks;
+pub mod library_setup;
 pub mod main_menu;
 pub mod management;
 pub mod multi_gpu_setup;
 pub mod network_settings;
 pub mod pci_passthrough;
+pub mod regenerate_script;
+pub mod removable_media;
+pub mod rtc_settings;
 pub mod settings;
 pub mod shared_folders;
 pub mod single_gpu_setup;
+pub mod stale_vms;
+pub mod trash;
+pub mod uefi_nvram;
@@ -3,6 +3,7 @@
 //! Provides UI for configuring multi-GPU passthrough with Looking Glass:
 //! - System requirements status (IOMMU, VFIO, Looking Glass client)
 //! - GPU selection status
+//! - NVIDIA Code 43 (hypervisor detection) warning and workaround
 //! - Script generation and configuration
 
 use crossterm::event::{KeyCode, KeyEvent};
@@ -13,7 +14,7 @@ use ratatui::{
     widgets::{Block, Borders, Clear, Paragraph},
 };
 
-use crate::app::{App, Screen};
+use crate::app::{App, Screen, TextInputContext};
 use crate::hardware::{check_multi_gpu_passthrough_status, LookingGlassConfig};
 
 /// Render the multi-GPU setup screen
@@ -22,7 +23,7 @@ pub fn render(app: &App, frame: &mut Frame) {
 
     // Calculate dialog size
     let dialog_width = 72.min(area.width.saturating_sub(4));
-    let dialog_height = 24.min(area.height.saturating_sub(4));
+    let dialog_height = 26.min(area.height.saturating_sub(4));
 
     let dialog_area = centered_rect(dialog_width, dialog_height, area);
     frame.render_widget(Clear, dialog_area);
@@ -46,6 +47,7 @@ pub fn render(app: &App, frame: &mut Frame) {
             Constraint::Length(5), // GPU info
             Constraint::Length(1), // Separator
             Constraint::Length(4), // Looking Glass config
+            Constraint::Length(2), // NVIDIA Code 43 warning
             Constraint::Min(1),    // Spacer
             Constraint::Length(2), // Help
         ])
@@ -70,8 +72,40 @@ pub fn render(app: &App, frame: &mut Frame) {
     // Render Looking Glass config
     render_looking_glass_config(app, frame, chunks[4]);
 
+    // NVIDIA Code 43 warning
+    let needs_workaround = needs_nvidia_workaround(app);
+    if needs_workaround {
+        render_nvidia_warning(frame, chunks[5]);
+    }
+
     // Help
-    render_help(frame, chunks[6]);
+    render_help(frame, chunks[7], needs_workaround);
+}
+
+/// True when an NVIDIA GPU is selected for passthrough but the VM's CPU flags
+/// still let the guest driver detect the hypervisor (Code 43).
+fn needs_nvidia_workaround(app: &App) -> bool {
+    let has_nvidia_gpu = app
+        .selected_pci_devices
+        .iter()
+        .filter_map(|&idx| app.pci_devices.get(idx))
+        .any(|d| d.is_gpu() && d.is_nvidia());
+    has_nvidia_gpu && !app.cpu_config.hides_hypervisor()
+}
+
+/// Render the NVIDIA Code 43 warning
+fn render_nvidia_warning(frame: &mut Frame, area: Rect) {
+    let lines = vec![
+        Line::styled(
+            "[!] NVIDIA GPU: the driver may fail with Code 43 (hypervisor visible)",
+            Style::default().fg(Color::Yellow),
+        ),
+        Line::styled(
+            "    Press [n] to hide KVM (kvm=off, hv_vendor_id, hypervisor=off)",
+            Style::default().fg(Color::DarkGray),
+        ),
+    ];
+    frame.render_widget(Paragraph::new(lines), area);
 }
 
 /// Render system status panel
@@ -220,7 +254,7 @@ fn render_looking_glass_config(app: &App, frame: &mut Frame, area: Rect) {
             Style::default().fg(Color::White),
         ),
         Span::styled(
-            " (configure in Settings)",
+            " ([r] recommend from resolution)",
             Style::default().fg(Color::DarkGray),
         ),
     ]));
@@ -241,8 +275,13 @@ fn render_looking_glass_config(app: &App, frame: &mut Frame, area: Rect) {
 }
 
 /// Render help text
-fn render_help(frame: &mut Frame, area: Rect) {
-    let help = Paragraph::new("[p] PCI Passthrough  [s] Settings  [Esc] Back")
+fn render_help(frame: &mut Frame, area: Rect, nvidia_workaround: bool) {
+    let text = if nvidia_workaround {
+        "[p] PCI  [d] Diagnose  [r] IVSHMEM size  [n] NVIDIA fix  [s] Settings  [Esc] Back"
+    } else {
+        "[p] PCI Passthrough  [d] Diagnose  [r] IVSHMEM size  [s] Settings  [Esc] Back"
+    };
+    let help = Paragraph::new(text)
         .style(Style::default().fg(Color::DarkGray))
         .alignment(Alignment::Center);
     frame.render_widget(help, area);
@@ -265,12 +304,42 @@ pub fn handle_input(app: &mut App, key: KeyEvent) -> anyhow::Result<()> {
             // Go to Settings screen
             app.push_screen(Screen::Settings);
         }
+        KeyCode::Char('d') | KeyCode::Char('D') => {
+            app.run_passthrough_diagnostics();
+            app.push_screen(Screen::PassthroughDiagnostics);
+        }
+        KeyCode::Char('r') | KeyCode::Char('R') => {
+            // IVSHMEM size calculator
+            app.text_input_buffer.clear();
+            app.push_screen(Screen::TextInput(TextInputContext::IvshmemResolution));
+        }
+        KeyCode::Char('n') | KeyCode::Char('N') if needs_nvidia_workaround(app) => {
+            apply_nvidia_workaround(app);
+        }
         _ => {}
     }
 
     Ok(())
 }
 
+/// Add the Code 43 CPU flags to the selected VM's launch.sh
+fn apply_nvidia_workaround(app: &mut App) {
+    let mut cpu = app.cpu_config.clone();
+    cpu.apply_nvidia_workaround();
+    let result = match app.selected_vm() {
+        Some(vm) => crate::vm::save_cpu_config(vm, &cpu),
+        None => return,
+    };
+    match result {
+        Ok(()) => {
+            app.set_status(format!("NVIDIA workaround applied: -cpu {}", cpu.to_arg()));
+            app.cpu_config = cpu;
+            app.reload_selected_vm_script();
+        }
+        Err(e) => app.set_status(format!("Failed to apply NVIDIA workaround: {}", e)),
+    }
+}
+
 /// Helper function to create a centered rect
 fn centered_rect(width: u16, height: u16, area: Rect) -> Rect {
     let x = area.x + (area.width.saturating_sub(width)) / 2;
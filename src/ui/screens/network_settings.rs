@@ -10,11 +10,14 @@ use ratatui::{
 };
 
 use crate::app::{AddPfStep, AddingPortForward, App, NetworkSettingsState};
-use crate::vm::qemu_config::{PortForward, PortProtocol};
+use crate::vm::qemu_config::{PortForward, PortProtocol, ShapingField};
 
 /// Network adapter model options (same as create wizard)
 const NETWORK_OPTIONS: &[&str] = &["virtio", "e1000", "rtl8139", "ne2k_pci", "pcnet", "none"];
 
+/// Field index of the first network shaping setting (bridge backend only)
+const FIRST_SHAPING_FIELD: usize = 4;
+
 /// Render the network settings screen
 pub fn render(app: &App, frame: &mut Frame) {
     let area = frame.area();
@@ -57,6 +60,11 @@ pub fn render(app: &App, frame: &mut Frame) {
             Constraint::Length(1), // MAC field
             Constraint::Length(1), // Bridge name / Port forwards field
             Constraint::Length(1), // Spacer
+            Constraint::Length(1), // Network shaping header
+            Constraint::Length(1), // Rate
+            Constraint::Length(1), // Delay
+            Constraint::Length(1), // Loss
+            Constraint::Length(1), // Spacer
             Constraint::Min(6),    // Info area
             Constraint::Length(2), // Help
         ])
@@ -149,6 +157,39 @@ pub fn render(app: &App, frame: &mut Frame) {
         frame.render_widget(Paragraph::new(pf_line), chunks[5]);
     }
 
+    // Poor network emulation: shapes the tap device, which only bridge has
+    if is_bridge {
+        frame.render_widget(
+            Paragraph::new("Emulate poor network:").style(Style::default().fg(Color::Yellow)),
+            chunks[7],
+        );
+        for (i, field) in ShapingField::ALL.into_iter().enumerate() {
+            let selected = ns.selected_field == FIRST_SHAPING_FIELD + i;
+            let display = if selected && ns.editing_shaping {
+                format!("{}_ {}", ns.shaping_edit_buffer, field.unit())
+            } else {
+                ns.shaping.describe(field)
+            };
+            let hint = if selected && ns.editing_shaping {
+                "[Enter] save  [Esc] cancel"
+            } else if selected {
+                "[Enter] edit  [c] clear"
+            } else {
+                ""
+            };
+            let line = render_field_line(&format!("{}:", field.label()), &display, selected, hint);
+            frame.render_widget(Paragraph::new(line), chunks[8 + i]);
+        }
+    } else if show_pf {
+        frame.render_widget(
+            Paragraph::new(
+                "Emulate poor network: bridge backend only (SLIRP/passt have no tap device)",
+            )
+            .style(Style::default().fg(Color::DarkGray)),
+            chunks[7],
+        );
+    }
+
     // Info area: bridge status (when bridge) or port forward list (when user/passt)
     if is_bridge {
         let caps = &app.network_caps;
@@ -236,7 +277,7 @@ pub fn render(app: &App, frame: &mut Frame) {
         }
 
         let info = Paragraph::new(lines);
-        frame.render_widget(info, chunks[7]);
+        frame.render_widget(info, chunks[12]);
     } else if show_pf && !ns.port_forwards.is_empty() {
         let mut lines = Vec::new();
         lines.push(Line::styled(
@@ -250,14 +291,14 @@ pub fn render(app: &App, frame: &mut Frame) {
             )));
         }
         let list = Paragraph::new(lines);
-        frame.render_widget(list, chunks[7]);
+        frame.render_widget(list, chunks[12]);
     }
 
     // Help
     let help = Paragraph::new("[Enter] Apply  [Esc] Cancel  [j/k] Navigate  [Left/Right] Change")
         .style(Style::default().fg(Color::DarkGray))
         .alignment(Alignment::Center);
-    frame.render_widget(help, chunks[8]);
+    frame.render_widget(help, chunks[13]);
 }
 
 /// Render the port forward editor overlay
@@ -572,6 +613,39 @@ pub fn handle_key(app: &mut App, key: crossterm::event::KeyEvent) -> anyhow::Res
         return Ok(());
     }
 
+    // Network shaping edit mode
+    let editing_shaping = app
+        .network_settings_state
+        .as_ref()
+        .map(|ns| ns.editing_shaping)
+        .unwrap_or(false);
+    if editing_shaping {
+        let mut error: Option<String> = None;
+        if let Some(ref mut ns) = app.network_settings_state {
+            let field = ShapingField::ALL[ns.selected_field - FIRST_SHAPING_FIELD];
+            match key.code {
+                KeyCode::Esc => ns.editing_shaping = false,
+                KeyCode::Enter => match ns.shaping.set_input(field, &ns.shaping_edit_buffer) {
+                    Ok(()) => ns.editing_shaping = false,
+                    Err(e) => error = Some(e),
+                },
+                KeyCode::Backspace => {
+                    ns.shaping_edit_buffer.pop();
+                }
+                KeyCode::Char(c) if c.is_ascii_digit() || c == '.' => {
+                    if ns.shaping_edit_buffer.len() < 10 {
+                        ns.shaping_edit_buffer.push(c);
+                    }
+                }
+                _ => {}
+            }
+        }
+        if let Some(e) = error {
+            app.set_status(e);
+        }
+        return Ok(());
+    }
+
     // Normal settings mode
     let backend_options: Vec<String> = app
         .get_network_backend_options()
@@ -591,10 +665,13 @@ pub fn handle_key(app: &mut App, key: crossterm::event::KeyEvent) -> anyhow::Res
         let ns = app.network_settings_state.as_ref().unwrap();
         ns.backend != "none"
     };
-    // Field indices: 0=adapter, 1=backend, 2=mac (when show_mac), 3=bridge/forwards
+    // Field indices: 0=adapter, 1=backend, 2=mac (when show_mac), 3=bridge/forwards,
+    // 4..=6 network shaping (bridge only)
     let max_field = if !show_mac {
         1
-    } else if show_pf || is_bridge {
+    } else if is_bridge {
+        FIRST_SHAPING_FIELD + ShapingField::ALL.len() - 1
+    } else if show_pf {
         3
     } else {
         2
@@ -633,6 +710,9 @@ pub fn handle_key(app: &mut App, key: crossterm::event::KeyEvent) -> anyhow::Res
                 if ns.selected_field == 2 && ns.backend != "none" {
                     ns.mac_address = None;
                     ns.mac_edit_buffer.clear();
+                } else if is_bridge && ns.selected_field >= FIRST_SHAPING_FIELD {
+                    let field = ShapingField::ALL[ns.selected_field - FIRST_SHAPING_FIELD];
+                    let _ = ns.shaping.set_input(field, "");
                 }
             }
         }
@@ -702,6 +782,12 @@ pub fn handle_key(app: &mut App, key: crossterm::event::KeyEvent) -> anyhow::Res
                     ns.editing_port_forwards = true;
                     ns.pf_selected = 0;
                 }
+            } else if sel >= FIRST_SHAPING_FIELD && is_bridge {
+                if let Some(ref mut ns) = app.network_settings_state {
+                    let field = ShapingField::ALL[sel - FIRST_SHAPING_FIELD];
+                    ns.shaping_edit_buffer = ns.shaping.input_value(field);
+                    ns.editing_shaping = true;
+                }
             } else {
                 // Apply changes
                 apply_network_settings(app)?;
@@ -819,6 +905,13 @@ fn apply_network_settings(app: &mut App) -> anyhow::Result<()> {
             &ns.port_forwards,
             ns.mac_address.as_deref(),
         )?;
+        // Only a bridge backend has a tap device to shape
+        let shaping = if ns.backend == "bridge" {
+            ns.shaping
+        } else {
+            Default::default()
+        };
+        crate::vm::save_network_shaping(vm, &shaping)?;
 
         app.reload_selected_vm_script();
 
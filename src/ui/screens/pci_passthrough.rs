@@ -1,7 +1,8 @@
 //! PCI Passthrough Screen
 //!
 //! Displays PCI devices for passthrough selection with special handling for GPUs.
-//! Shows IOMMU groups, driver bindings, and prerequisite status.
+//! Shows IOMMU groups, driver bindings, and prerequisite status, plus a
+//! pass/fail readiness checklist for GPU passthrough.
 
 use ratatui::{
     layout::{Alignment, Constraint, Direction, Layout, Rect},
@@ -83,7 +84,7 @@ pub fn render(app: &App, frame: &mut Frame) {
 
     // Help text - show GPU options only when multi-GPU passthrough is enabled (not single GPU)
     let help_text = if app.config.enable_multi_gpu_passthrough && !app.config.single_gpu_enabled {
-        "[Space] Toggle  [g] Auto-select GPU  [Enter/s] Save  [p] Prerequisites  [Esc] Back"
+        "[Space] Toggle  [g] Auto-select GPU  [Enter/s] Save  [p] Readiness check  [Esc] Back"
     } else {
         "[Space] Toggle  [Enter/s] Save  [Esc] Back"
     };
@@ -293,19 +294,18 @@ fn render_device_list(app: &App, frame: &mut Frame, area: Rect) {
     frame.render_stateful_widget(list, area, &mut state);
 }
 
-/// Render the prerequisites screen
-#[allow(dead_code)]
-pub fn render_prerequisites(app: &App, frame: &mut Frame) {
+/// Render the passthrough readiness checklist
+pub fn render_diagnostics(app: &App, frame: &mut Frame) {
     let area = frame.area();
 
-    let dialog_width = 70.min(area.width.saturating_sub(4));
+    let dialog_width = 76.min(area.width.saturating_sub(4));
     let dialog_height = 26.min(area.height.saturating_sub(4));
 
     let dialog_area = centered_rect(dialog_width, dialog_height, area);
     frame.render_widget(Clear, dialog_area);
 
     let block = Block::default()
-        .title(" GPU Passthrough Prerequisites ")
+        .title(" GPU Passthrough Readiness ")
         .borders(Borders::ALL)
         .border_style(Style::default().fg(Color::Cyan))
         .style(Style::default().bg(Color::Black));
@@ -313,156 +313,108 @@ pub fn render_prerequisites(app: &App, frame: &mut Frame) {
     let inner = block.inner(dialog_area);
     frame.render_widget(block, dialog_area);
 
-    let status = app.multi_gpu_status.as_ref();
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Min(1), Constraint::Length(1)])
+        .split(inner);
 
     let mut lines: Vec<Line> = Vec::new();
-
-    lines.push(Line::styled(
-        "GPU Passthrough Prerequisites",
-        Style::default()
-            .fg(Color::White)
-            .add_modifier(Modifier::BOLD),
-    ));
-    lines.push(Line::raw(""));
-
-    if let Some(status) = status {
-        // IOMMU check
-        let iommu_icon = if status.iommu_enabled { " OK " } else { "FAIL" };
-        let iommu_color = if status.iommu_enabled {
-            Color::Green
-        } else {
-            Color::Red
-        };
-        lines.push(Line::from(vec![
-            Span::styled(
-                format!("[{}] ", iommu_icon),
-                Style::default().fg(iommu_color),
-            ),
-            Span::raw("IOMMU enabled in kernel"),
-        ]));
-        if !status.iommu_enabled {
-            lines.push(Line::styled(
-                "    Add intel_iommu=on or amd_iommu=on to kernel parameters",
-                Style::default().fg(Color::Yellow),
-            ));
-        }
-
-        // VFIO check
-        let vfio_icon = if status.vfio_loaded { " OK " } else { "FAIL" };
-        let vfio_color = if status.vfio_loaded {
-            Color::Green
+    for check in &app.passthrough_diagnostics {
+        let (icon, color) = if check.passed {
+            (" OK ", Color::Green)
         } else {
-            Color::Red
+            ("FAIL", Color::Red)
         };
-        lines.push(Line::from(vec![
-            Span::styled(format!("[{}] ", vfio_icon), Style::default().fg(vfio_color)),
-            Span::raw("VFIO modules loaded"),
-        ]));
-        if !status.vfio_loaded {
-            lines.push(Line::styled(
-                "    Run: sudo modprobe vfio-pci",
-                Style::default().fg(Color::Yellow),
-            ));
-        }
-
-        // GPU availability
-        let gpu_available = !status.passthrough_gpus.is_empty();
-        let gpu_icon = if gpu_available { " OK " } else { "FAIL" };
-        let gpu_color = if gpu_available {
-            Color::Green
-        } else {
-            Color::Red
-        };
-        lines.push(Line::from(vec![
-            Span::styled(format!("[{}] ", gpu_icon), Style::default().fg(gpu_color)),
-            Span::raw(format!(
-                "Secondary GPU available ({} found)",
-                status.passthrough_gpus.len()
-            )),
-        ]));
-
-        if let Some(ref boot_vga) = status.boot_vga {
-            lines.push(Line::styled(
-                format!(
-                    "    Boot VGA: {} (cannot be passed through)",
-                    boot_vga.display_name()
-                ),
+        let mut spans = vec![
+            Span::styled(format!("[{}] ", icon), Style::default().fg(color)),
+            Span::raw(check.label.clone()),
+        ];
+        if let Some(ref detail) = check.detail {
+            spans.push(Span::styled(
+                format!(" ({})", detail),
                 Style::default().fg(Color::DarkGray),
             ));
         }
-
-        for gpu in &status.passthrough_gpus {
-            let driver_status = if gpu.is_vfio_bound() {
-                "vfio-pci"
-            } else {
-                gpu.driver.as_deref().unwrap_or("no driver")
-            };
-            lines.push(Line::styled(
-                format!("    {} [{}]", gpu.display_name(), driver_status),
-                Style::default().fg(Color::Cyan),
-            ));
-        }
-
-        // Warnings
-        if !status.warnings.is_empty() {
-            lines.push(Line::raw(""));
+        lines.push(Line::from(spans));
+        if let Some(ref hint) = check.hint {
             lines.push(Line::styled(
-                "Warnings:",
+                format!("       {}", hint),
                 Style::default().fg(Color::Yellow),
             ));
-            for warning in &status.warnings {
-                lines.push(Line::styled(
-                    format!("  - {}", warning),
-                    Style::default().fg(Color::Yellow),
-                ));
-            }
         }
+    }
 
-        // VFIO binding info
-        lines.push(Line::raw(""));
-        lines.push(Line::styled(
-            "VFIO Driver Binding:",
-            Style::default()
-                .fg(Color::White)
-                .add_modifier(Modifier::BOLD),
-        ));
-        lines.push(Line::raw(
-            "  Devices are automatically bound to vfio-pci at launch",
-        ));
-        lines.push(Line::raw(
-            "  and restored to their original driver on VM exit.",
-        ));
-        lines.push(Line::styled(
-            "  Requires authentication via pkexec (polkit) or sudo.",
-            Style::default().fg(Color::Yellow),
-        ));
-
-        // Looking Glass info
-        lines.push(Line::raw(""));
+    let failed = app
+        .passthrough_diagnostics
+        .iter()
+        .filter(|c| !c.passed)
+        .count();
+    lines.push(Line::raw(""));
+    if failed == 0 {
         lines.push(Line::styled(
-            "For Looking Glass:",
-            Style::default()
-                .fg(Color::White)
-                .add_modifier(Modifier::BOLD),
+            "All checks passed. Devices are bound to vfio-pci at launch (pkexec/sudo).",
+            Style::default().fg(Color::Green),
         ));
-        lines.push(Line::raw("  - Install looking-glass-client on host"));
-        lines.push(Line::raw("  - Install Looking Glass Host in guest VM"));
-        lines.push(Line::raw("  - Connect HDMI/DP dummy plug to guest GPU"));
     } else {
         lines.push(Line::styled(
-            "Unable to check prerequisites",
+            format!(
+                "{} check{} failed. Launching now will likely hang or fail at VFIO binding.",
+                failed,
+                if failed == 1 { "" } else { "s" }
+            ),
             Style::default().fg(Color::Red),
         ));
     }
 
-    lines.push(Line::raw(""));
-    lines.push(Line::styled(
-        "[Press any key to close]",
-        Style::default().fg(Color::DarkGray),
-    ));
-
     let para = Paragraph::new(lines).wrap(Wrap { trim: false });
-    frame.render_widget(para, inner);
+    frame.render_widget(para, chunks[0]);
+
+    let help = Paragraph::new("[r] Re-run checks  [g] Write VFIO setup steps  [Esc] Back")
+        .style(Style::default().fg(Color::DarkGray))
+        .alignment(Alignment::Center);
+    frame.render_widget(help, chunks[1]);
+}
+
+/// Handle input for the passthrough readiness checklist
+pub fn handle_diagnostics_key(
+    app: &mut App,
+    key: crossterm::event::KeyEvent,
+) -> anyhow::Result<()> {
+    use crossterm::event::KeyCode;
+
+    match key.code {
+        KeyCode::Esc | KeyCode::Char('q') => app.pop_screen(),
+        KeyCode::Char('r') | KeyCode::Char('R') => {
+            app.run_passthrough_diagnostics();
+            app.set_status("Passthrough checks re-run");
+        }
+        KeyCode::Char('g') | KeyCode::Char('G') => write_vfio_setup_steps(app),
+        _ => {}
+    }
+    Ok(())
+}
+
+/// Write the modprobe.d / driver_override steps for the selected devices to
+/// `vfio-setup.txt` in the VM directory.
+fn write_vfio_setup_steps(app: &mut App) {
+    let devices: Vec<PciDevice> = app
+        .selected_pci_devices
+        .iter()
+        .filter_map(|&i| app.pci_devices.get(i))
+        .cloned()
+        .collect();
+    if devices.is_empty() {
+        app.set_status("Select the devices to pass through first");
+        return;
+    }
+    let Some(vm) = app.selected_vm() else {
+        return;
+    };
+    let path = vm.path.join("vfio-setup.txt");
+    match std::fs::write(&path, crate::hardware::pci::vfio_setup_steps(&devices)) {
+        Ok(()) => app.set_status(format!("VFIO setup steps written to {}", path.display())),
+        Err(e) => app.set_status(format!("Failed to write {}: {}", path.display(), e)),
+    }
 }
 
 /// Whether a PCI device should appear in the passthrough list.
@@ -571,7 +523,13 @@ pub fn handle_key(app: &mut App, key: crossterm::event::KeyEvent) -> anyhow::Res
         KeyCode::Char(' ') => {
             // Space is the sole toggle; Enter saves (see below) so pressing
             // Enter to "confirm" a selection no longer toggles it back off (#52).
-            app.toggle_pci_device(app.selected_menu_item);
+            if let Some(audio_idx) = app.toggle_pci_device(app.selected_menu_item) {
+                let address = app.pci_devices[audio_idx].address.clone();
+                app.set_status(format!(
+                    "Also selected GPU audio {} (same IOMMU group; Space to uncheck it)",
+                    address
+                ));
+            }
         }
         KeyCode::Char('g') | KeyCode::Char('G') if gpu_enabled => {
             // Auto-select current GPU and its audio pair
@@ -585,11 +543,9 @@ pub fn handle_key(app: &mut App, key: crossterm::event::KeyEvent) -> anyhow::Res
             save_selection_and_report(app);
         }
         KeyCode::Char('p') | KeyCode::Char('P') if gpu_enabled => {
-            // Refresh and show GPU prerequisites
-            app.multi_gpu_status = Some(crate::hardware::check_multi_gpu_passthrough_status());
-            // We'll need a separate screen state for prerequisites
-            // For now, just refresh the status
-            app.set_status("GPU prerequisites refreshed");
+            // Run and show the passthrough readiness checklist
+            app.run_passthrough_diagnostics();
+            app.push_screen(Screen::PassthroughDiagnostics);
         }
         _ => {}
     }
@@ -752,6 +708,8 @@ fn generate_pci_section(devices: &[&PciDevice]) -> String {
 _pci_elevated() {
     if [[ $EUID -eq 0 ]]; then
         sh -c "$1"
+    elif sudo -n true 2>/dev/null; then
+        sudo sh -c "$1"
     elif command -v pkexec >/dev/null 2>&1; then
         pkexec sh -c "$1"
     elif command -v sudo >/dev/null 2>&1; then
@@ -790,8 +748,9 @@ _pci_elevated() {
         bind_cmds+="echo '$dev' > /sys/bus/pci/drivers_probe; "
     done
     if [[ -n "$bind_cmds" ]]; then
-        echo "Binding PCI devices to vfio-pci..."
+        echo "vm-curator: binding PCI devices to vfio-pci (waiting for pkexec/sudo)" >&2
         _pci_elevated "$bind_cmds" || return 1
+        echo "vm-curator: PCI devices bound, starting QEMU" >&2
     fi
     sleep 0.5
 }
@@ -0,0 +1,214 @@
+//! Regenerate Script Screen
+//!
+//! Shows a line diff between the selected VM's launch.sh and a fresh one
+//! rendered from its parsed configuration, and writes the new script on
+//! confirmation (the old one is kept as launch.sh.bak).
+
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{
+    layout::Rect,
+    prelude::*,
+    style::{Color, Style},
+    widgets::{Block, Borders, Clear, Paragraph},
+};
+
+use crate::app::{App, Screen};
+use crate::vm::create::{regenerate_launch_script, LAUNCH_SCRIPT_BACKUP};
+
+/// Render the regenerated script and its diff against the current one for
+/// the selected VM, then open the preview screen
+pub fn open(app: &mut App) {
+    let Some(vm) = app.selected_vm() else {
+        return;
+    };
+    let current = std::fs::read_to_string(&vm.launch_script).unwrap_or_default();
+    match regenerate_launch_script(vm, app.config.ovmf_override()) {
+        Ok(content) => {
+            app.regenerate_diff = line_diff(&current, &content);
+            app.regenerated_script = Some(content);
+            app.regenerate_scroll = 0;
+            app.push_screen(Screen::RegenerateScript);
+        }
+        Err(e) => app.show_error(format!("Cannot regenerate launch.sh: {}", e)),
+    }
+}
+
+/// Line diff of `old` against `new` from their longest common subsequence,
+/// as (' ' unchanged | '-' removed | '+' added, line)
+pub fn line_diff(old: &str, new: &str) -> Vec<(char, String)> {
+    let a: Vec<&str> = old.lines().collect();
+    let b: Vec<&str> = new.lines().collect();
+
+    // lcs[i][j] = LCS length of a[i..] and b[j..]
+    let mut lcs = vec![vec![0u32; b.len() + 1]; a.len() + 1];
+    for i in (0..a.len()).rev() {
+        for j in (0..b.len()).rev() {
+            lcs[i][j] = if a[i] == b[j] {
+                lcs[i + 1][j + 1] + 1
+            } else {
+                lcs[i + 1][j].max(lcs[i][j + 1])
+            };
+        }
+    }
+
+    let mut diff = Vec::new();
+    let (mut i, mut j) = (0, 0);
+    while i < a.len() && j < b.len() {
+        if a[i] == b[j] {
+            diff.push((' ', a[i].to_string()));
+            i += 1;
+            j += 1;
+        } else if lcs[i + 1][j] >= lcs[i][j + 1] {
+            diff.push(('-', a[i].to_string()));
+            i += 1;
+        } else {
+            diff.push(('+', b[j].to_string()));
+            j += 1;
+        }
+    }
+    diff.extend(a[i..].iter().map(|l| ('-', l.to_string())));
+    diff.extend(b[j..].iter().map(|l| ('+', l.to_string())));
+    diff
+}
+
+/// Render the regenerate script screen
+pub fn render(app: &App, frame: &mut Frame) {
+    let area = frame.area();
+    let dialog_width = 100.min(area.width.saturating_sub(4));
+    let dialog_height = area.height.saturating_sub(4);
+
+    let dialog_area = centered_rect(dialog_width, dialog_height, area);
+    frame.render_widget(Clear, dialog_area);
+
+    let name = app
+        .selected_vm()
+        .map(|vm| vm.display_name())
+        .unwrap_or_default();
+    let block = Block::default()
+        .title(format!(" {} - Regenerate launch.sh ", name))
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::Cyan))
+        .style(Style::default().bg(Color::Black));
+
+    let inner = block.inner(dialog_area);
+    frame.render_widget(block, dialog_area);
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(3),
+            Constraint::Min(3),
+            Constraint::Length(1),
+        ])
+        .split(inner.inner(Margin::new(1, 0)));
+
+    let removed = app
+        .regenerate_diff
+        .iter()
+        .filter(|(t, _)| *t == '-')
+        .count();
+    let added = app
+        .regenerate_diff
+        .iter()
+        .filter(|(t, _)| *t == '+')
+        .count();
+    let header = vec![
+        Line::styled(
+            "Rebuilds launch.sh from the parsed settings. Managed sections and options",
+            Style::default().fg(Color::White),
+        ),
+        Line::styled(
+            format!(
+                "it doesn't model are kept; comments and layout are not. Backup: {}.",
+                LAUNCH_SCRIPT_BACKUP
+            ),
+            Style::default().fg(Color::White),
+        ),
+        Line::from(vec![
+            Span::styled(format!("-{} ", removed), Style::default().fg(Color::Red)),
+            Span::styled(format!("+{}", added), Style::default().fg(Color::Green)),
+        ]),
+    ];
+    frame.render_widget(Paragraph::new(header), chunks[0]);
+
+    let visible = chunks[1].height as usize;
+    let lines: Vec<Line> = if removed + added == 0 {
+        vec![Line::styled(
+            "No changes - launch.sh already matches its configuration.",
+            Style::default().fg(Color::DarkGray),
+        )]
+    } else {
+        app.regenerate_diff
+            .iter()
+            .skip(app.regenerate_scroll)
+            .take(visible)
+            .map(|(tag, line)| {
+                let color = match tag {
+                    '-' => Color::Red,
+                    '+' => Color::Green,
+                    _ => Color::DarkGray,
+                };
+                Line::styled(format!("{} {}", tag, line), Style::default().fg(color))
+            })
+            .collect()
+    };
+    frame.render_widget(Paragraph::new(lines), chunks[1]);
+
+    frame.render_widget(
+        Paragraph::new(Line::styled(
+            "[Enter] Regenerate  [j/k/PgUp/PgDn] Scroll  [Esc] Cancel",
+            Style::default().fg(Color::DarkGray),
+        )),
+        chunks[2],
+    );
+}
+
+/// Handle key input for the regenerate script screen
+pub fn handle_key(app: &mut App, key: KeyEvent) -> anyhow::Result<()> {
+    let max_scroll = app.regenerate_diff.len().saturating_sub(1);
+
+    match key.code {
+        KeyCode::Esc => {
+            app.regenerated_script = None;
+            app.regenerate_diff.clear();
+            app.pop_screen();
+        }
+        KeyCode::Up | KeyCode::Char('k') => {
+            app.regenerate_scroll = app.regenerate_scroll.saturating_sub(1);
+        }
+        KeyCode::Down | KeyCode::Char('j') => {
+            app.regenerate_scroll = (app.regenerate_scroll + 1).min(max_scroll);
+        }
+        KeyCode::PageUp => {
+            app.regenerate_scroll = app.regenerate_scroll.saturating_sub(20);
+        }
+        KeyCode::PageDown => {
+            app.regenerate_scroll = (app.regenerate_scroll + 20).min(max_scroll);
+        }
+        KeyCode::Enter if !app.regenerate_diff.iter().any(|(tag, _)| *tag != ' ') => {
+            app.regenerated_script = None;
+            app.regenerate_diff.clear();
+            app.pop_screen();
+            app.set_status("launch.sh already matches its configuration");
+        }
+        KeyCode::Enter => {
+            let result = app.apply_regenerated_script();
+            app.pop_screen();
+            match result {
+                Ok(backup) => app.set_status(format!(
+                    "launch.sh regenerated; previous script saved as {}",
+                    backup.display()
+                )),
+                Err(e) => app.show_error(format!("Failed to regenerate launch.sh: {}", e)),
+            }
+        }
+        _ => {}
+    }
+    Ok(())
+}
+
+fn centered_rect(width: u16, height: u16, area: Rect) -> Rect {
+    let x = area.x + (area.width.saturating_sub(width)) / 2;
+    let y = area.y + (area.height.saturating_sub(height)) / 2;
+    Rect::new(x, y, width, height)
+}
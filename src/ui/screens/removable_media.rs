@@ -0,0 +1,205 @@
+//! Removable Media Screen
+//!
+//! Inserts, swaps and ejects floppy or CD-ROM images in the selected running
+//! VM over QMP: disk 2 of N for floppy installers, guest tools after boot.
+//! Changes last until the VM stops; Boot Options keeps a floppy in drive B:
+//! or a tools ISO across launches.
+
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{
+    layout::Rect,
+    prelude::*,
+    style::{Color, Modifier, Style},
+    widgets::{Block, Borders, Clear, Paragraph, Wrap},
+};
+
+use crate::app::{App, FileBrowserMode, Screen};
+use crate::vm::MediaKind;
+
+/// Render the removable media screen
+pub fn render(app: &App, frame: &mut Frame) {
+    let area = frame.area();
+    let dialog_width = 70.min(area.width.saturating_sub(4));
+    let dialog_height = 14.min(area.height.saturating_sub(4));
+
+    let dialog_area = centered_rect(dialog_width, dialog_height, area);
+    frame.render_widget(Clear, dialog_area);
+
+    let (title, note) = match app.media_kind {
+        MediaKind::Floppy => (
+            " Floppy Drives ",
+            "Changes last until the VM stops. Boot Options keeps a floppy in B:.",
+        ),
+        MediaKind::Cdrom => (
+            " Change CD ",
+            "Changes last until the VM stops. Boot Options sets a tools ISO.",
+        ),
+    };
+    let block = Block::default()
+        .title(title)
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::Cyan))
+        .style(Style::default().bg(Color::Black));
+
+    let inner = block.inner(dialog_area);
+    frame.render_widget(block, dialog_area);
+
+    let mut lines = vec![Line::raw("")];
+    for (i, drive) in app.media_drives.iter().enumerate() {
+        let style = if i == app.selected_menu_item {
+            Style::default()
+                .fg(Color::Yellow)
+                .add_modifier(Modifier::BOLD)
+        } else {
+            Style::default().fg(Color::White)
+        };
+        lines.push(Line::styled(
+            format!("[{}] {}", i + 1, drive.label()),
+            style,
+        ));
+        let image = drive
+            .image
+            .as_ref()
+            .map(|p| p.display().to_string())
+            .unwrap_or_else(|| "Empty".to_string());
+        lines.push(Line::styled(
+            format!("    {}", image),
+            Style::default().fg(Color::Gray),
+        ));
+    }
+
+    lines.push(Line::raw(""));
+    lines.push(Line::styled(note, Style::default().fg(Color::DarkGray)));
+    lines.push(Line::raw(""));
+    lines.push(Line::styled(
+        "[Enter] Insert/Swap  [x] Eject  [r] Refresh  [Esc] Back",
+        Style::default().fg(Color::DarkGray),
+    ));
+
+    let para = Paragraph::new(lines).wrap(Wrap { trim: false });
+    frame.render_widget(para, inner.inner(Margin::new(2, 0)));
+}
+
+/// Handle key input for the removable media screen
+pub fn handle_key(app: &mut App, key: KeyEvent) -> anyhow::Result<()> {
+    match key.code {
+        KeyCode::Esc => app.pop_screen(),
+        KeyCode::Char('j') | KeyCode::Down => app.menu_next(app.media_drives.len()),
+        KeyCode::Char('k') | KeyCode::Up => app.menu_prev(),
+        KeyCode::Char('r') => {
+            if let Err(e) = refresh(app) {
+                app.set_status(format!("Failed to read drives: {}", e));
+            }
+        }
+        KeyCode::Char('x') | KeyCode::Delete => eject(app),
+        KeyCode::Enter | KeyCode::Char('1'..='9') => {
+            let row = match key.code {
+                KeyCode::Char(c) => c.to_digit(10).unwrap_or(1) as usize - 1,
+                _ => app.selected_menu_item,
+            };
+            let Some(drive) = app.media_drives.get(row) else {
+                return Ok(());
+            };
+            app.selected_menu_item = row;
+            let mode = match app.media_kind {
+                MediaKind::Floppy => FileBrowserMode::FloppySwap,
+                MediaKind::Cdrom => FileBrowserMode::CdromSwap,
+            };
+            // Start next to the current disk; the next one is usually beside it
+            match drive.image.as_deref().and_then(|p| p.parent()) {
+                Some(dir) if dir.is_dir() => app.file_browser_dir = dir.to_path_buf(),
+                _ => app.seed_file_browser_dir(mode),
+            }
+            app.load_file_browser(mode);
+            app.push_screen(Screen::FileBrowser);
+        }
+        _ => {}
+    }
+    Ok(())
+}
+
+/// Open the screen for the selected running VM's drives of `kind`, or say
+/// why there are none
+pub fn open(app: &mut App, kind: MediaKind) {
+    if app.selected_vm_pid().is_none() {
+        app.set_status(match kind {
+            MediaKind::Floppy => "VM is not running - Boot Options sets the floppy in drive B:",
+            MediaKind::Cdrom => "VM is not running - Boot Options boots an ISO or sets a tools ISO",
+        });
+        return;
+    }
+    app.media_kind = kind;
+    app.selected_menu_item = 0;
+    match refresh(app) {
+        Ok(()) if app.media_drives.is_empty() => app.set_status(match kind {
+            MediaKind::Floppy => "This machine type has no floppy controller",
+            MediaKind::Cdrom => {
+                "No CD-ROM drive: the VM was started without one and QEMU can't hot-add it. \
+                 Boot with an ISO or set a tools ISO in Boot Options."
+            }
+        }),
+        Ok(()) => app.push_screen(Screen::RemovableMedia),
+        Err(e) => app.set_status(format!("Failed to read drives: {}", e)),
+    }
+}
+
+/// Re-read the selected VM's drives
+pub fn refresh(app: &mut App) -> anyhow::Result<()> {
+    let Some(vm) = app.selected_vm() else {
+        return Ok(());
+    };
+    app.media_drives = crate::vm::list_removable_drives(&vm.path, app.media_kind)?;
+    if app.selected_menu_item >= app.media_drives.len() {
+        app.selected_menu_item = app.media_drives.len().saturating_sub(1);
+    }
+    Ok(())
+}
+
+/// Put `image` into the selected drive
+pub fn insert(app: &mut App, image: std::path::PathBuf) {
+    let (Some(vm), Some(drive)) = (
+        app.selected_vm().cloned(),
+        app.media_drives.get(app.selected_menu_item).cloned(),
+    ) else {
+        return;
+    };
+    match crate::vm::change_medium(&vm.path, &drive.device, &image) {
+        Ok(()) => {
+            let _ = refresh(app);
+            let name = image.file_name().unwrap_or_default().to_string_lossy();
+            app.set_status(format!("Inserted {} into {}", name, drive.label()));
+        }
+        Err(e) => app.show_error(format!(
+            "Failed to insert the image into {}\n\n{:#}",
+            drive.label(),
+            e
+        )),
+    }
+}
+
+/// Eject the disk in the selected drive
+fn eject(app: &mut App) {
+    let (Some(vm), Some(drive)) = (
+        app.selected_vm().cloned(),
+        app.media_drives.get(app.selected_menu_item).cloned(),
+    ) else {
+        return;
+    };
+    if drive.image.is_none() {
+        app.set_status(format!("{} is already empty", drive.label()));
+        return;
+    }
+    match crate::vm::eject_medium(&vm.path, &drive.device) {
+        Ok(()) => {
+            let _ = refresh(app);
+            app.set_status(format!("Ejected {}", drive.label()));
+        }
+        Err(e) => app.set_status(format!("Failed to eject {}: {}", drive.label(), e)),
+    }
+}
+
+fn centered_rect(width: u16, height: u16, area: Rect) -> Rect {
+    let x = area.x + (area.width.saturating_sub(width)) / 2;
+    let y = area.y + (area.height.saturating_sub(height)) / 2;
+    Rect::new(x, y, width, height)
+}
@@ -0,0 +1,195 @@
+//! RTC Settings Screen
+//!
+//! Sets where the selected VM's real-time clock starts (UTC, host local time
+//! or a fixed date) and what drives it afterwards. Changes are written to
+//! the `-rtc` option in launch.sh right away.
+
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{
+    layout::Rect,
+    prelude::*,
+    style::{Color, Modifier, Style},
+    widgets::{Block, Borders, Clear, Paragraph, Wrap},
+};
+
+use crate::app::{App, Screen, TextInputContext};
+use crate::vm::{RtcBase, RtcConfig};
+
+/// Rows of the screen: base, clock
+const ROW_COUNT: usize = 2;
+
+/// Render the RTC settings screen
+pub fn render(app: &App, frame: &mut Frame) {
+    let area = frame.area();
+    let dialog_width = 64.min(area.width.saturating_sub(4));
+    let dialog_height = 16.min(area.height.saturating_sub(4));
+
+    let dialog_area = centered_rect(dialog_width, dialog_height, area);
+    frame.render_widget(Clear, dialog_area);
+
+    let Some(vm) = app.selected_vm() else {
+        return;
+    };
+
+    let block = Block::default()
+        .title(format!(" {} - Clock (RTC) ", vm.display_name()))
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::Cyan))
+        .style(Style::default().bg(Color::Black));
+
+    let inner = block.inner(dialog_area);
+    frame.render_widget(block, dialog_area);
+
+    let rtc = &vm.config.rtc;
+    let base = match &rtc.base {
+        RtcBase::Utc => "UTC".to_string(),
+        RtcBase::Localtime => "Host local time".to_string(),
+        RtcBase::Fixed(date) => format!("Fixed date {}", date),
+    };
+    let rows = [
+        ("Starts at", base, "base="),
+        (
+            "Driven by",
+            format!("{} ({})", rtc.clock.value(), rtc.clock.description()),
+            "clock=",
+        ),
+    ];
+
+    let mut lines = vec![Line::raw("")];
+    for (i, (name, value, opt)) in rows.iter().enumerate() {
+        let style = if i == app.selected_menu_item {
+            Style::default()
+                .fg(Color::Yellow)
+                .add_modifier(Modifier::BOLD)
+        } else {
+            Style::default().fg(Color::White)
+        };
+        lines.push(Line::from(vec![
+            Span::styled(format!("[{}] {} ", i + 1, name), style),
+            Span::styled(*opt, Style::default().fg(Color::DarkGray)),
+        ]));
+        lines.push(Line::styled(
+            format!("    {}", value),
+            Style::default().fg(Color::Gray),
+        ));
+    }
+
+    lines.push(Line::raw(""));
+    lines.push(Line::styled(
+        rtc.to_arg()
+            .unwrap_or_else(|| "QEMU default (no -rtc option)".to_string()),
+        Style::default().fg(Color::Cyan),
+    ));
+    lines.push(Line::raw(""));
+    lines.push(Line::styled(
+        "A fixed date with clock=vm gives every boot the same start time.",
+        Style::default().fg(Color::DarkGray),
+    ));
+    lines.push(Line::raw(""));
+    lines.push(Line::styled(
+        "[Enter] Change  [d] Set date  [x] Reset  [Esc] Back",
+        Style::default().fg(Color::DarkGray),
+    ));
+
+    let para = Paragraph::new(lines).wrap(Wrap { trim: false });
+    frame.render_widget(para, inner.inner(Margin::new(2, 0)));
+}
+
+/// Handle key input for the RTC settings screen
+pub fn handle_key(app: &mut App, key: KeyEvent) -> anyhow::Result<()> {
+    let Some(mut rtc) = app.selected_vm().map(|vm| vm.config.rtc.clone()) else {
+        app.pop_screen();
+        return Ok(());
+    };
+
+    match key.code {
+        KeyCode::Esc => app.pop_screen(),
+        KeyCode::Char('j') | KeyCode::Down => app.menu_next(ROW_COUNT),
+        KeyCode::Char('k') | KeyCode::Up => app.menu_prev(),
+        KeyCode::Char('d') => open_date_input(app, &rtc),
+        KeyCode::Char('x') | KeyCode::Delete => save(app, RtcConfig::default()),
+        KeyCode::Enter | KeyCode::Char(' ') | KeyCode::Char('1') | KeyCode::Char('2') => {
+            let row = match key.code {
+                KeyCode::Char('1') => 0,
+                KeyCode::Char('2') => 1,
+                _ => app.selected_menu_item,
+            };
+            app.selected_menu_item = row;
+            if row == 0 {
+                match rtc.base {
+                    RtcBase::Utc => {
+                        rtc.base = RtcBase::Localtime;
+                        save(app, rtc);
+                    }
+                    // A fixed date needs the date first; saved from the input
+                    RtcBase::Localtime => open_date_input(app, &rtc),
+                    RtcBase::Fixed(_) => {
+                        rtc.base = RtcBase::Utc;
+                        save(app, rtc);
+                    }
+                }
+            } else {
+                rtc.clock = rtc.clock.next();
+                save(app, rtc);
+            }
+        }
+        _ => {}
+    }
+    Ok(())
+}
+
+/// Ask for a fixed start date, starting from the current one or today
+fn open_date_input(app: &mut App, rtc: &RtcConfig) {
+    app.text_input_buffer = match &rtc.base {
+        RtcBase::Fixed(date) => date.clone(),
+        _ => chrono::Local::now().format("%Y-%m-%d").to_string(),
+    };
+    app.push_screen(Screen::TextInput(TextInputContext::RtcDate));
+}
+
+/// Set a fixed start date typed into the text input
+pub fn set_fixed_date(app: &mut App, input: &str) {
+    let date = input.trim();
+    if date.is_empty() {
+        return;
+    }
+    if !RtcBase::is_valid_datetime(date) {
+        app.set_status(format!(
+            "Invalid date '{}' (expected YYYY-MM-DD or YYYY-MM-DDTHH:MM:SS)",
+            date
+        ));
+        return;
+    }
+    let Some(mut rtc) = app.selected_vm().map(|vm| vm.config.rtc.clone()) else {
+        return;
+    };
+    rtc.base = RtcBase::Fixed(date.to_string());
+    save(app, rtc);
+}
+
+/// Write new RTC settings to the selected VM's launch.sh
+fn save(app: &mut App, rtc: RtcConfig) {
+    let Some(vm) = app.selected_vm() else {
+        return;
+    };
+    let id = vm.id.clone();
+    match crate::vm::save_rtc(vm, &rtc) {
+        Ok(()) => {
+            app.set_status(match rtc.to_arg() {
+                Some(arg) => format!("RTC set: {}", arg),
+                None => "RTC reset to QEMU's default (UTC, host clock)".to_string(),
+            });
+            if let Some(vm) = app.vms.iter_mut().find(|vm| vm.id == id) {
+                vm.config.rtc = rtc;
+            }
+            app.reload_selected_vm_script();
+        }
+        Err(e) => app.set_status(format!("Failed to save RTC settings: {}", e)),
+    }
+}
+
+fn centered_rect(width: u16, height: u16, area: Rect) -> Rect {
+    let x = area.x + (area.width.saturating_sub(width)) / 2;
+    let y = area.y + (area.height.saturating_sub(height)) / 2;
+    Rect::new(x, y, width, height)
+}
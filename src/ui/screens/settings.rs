@@ -3,18 +3,23 @@
 //! Allows users to configure application settings with a tree-view structure
 //! that shows dependent settings only when their parent is enabled.
 //! Features a two-column layout with contextual help text and GPU validation.
+//! A filter field (`/`) narrows the list by setting name and help text.
+//! `d` resets the selected setting to its default; an action resets them all.
 
 use crossterm::event::{KeyCode, KeyEvent};
 use ratatui::prelude::*;
 use ratatui::widgets::{Block, Borders, Clear, List, ListItem, Paragraph, Wrap};
 
-use crate::app::App;
+use std::path::PathBuf;
+
+use crate::app::{App, FileBrowserMode};
 use crate::config::Config;
 use crate::fs;
 use crate::hardware::{
     check_multi_gpu_passthrough_status, check_single_gpu_support, LookingGlassConfig,
     MultiGpuPassthroughStatus, SingleGpuSupport,
 };
+use crate::metadata::SortBy;
 use crate::vm::single_gpu_scripts::{run_system_setup, SystemSetupResult};
 
 /// GPU passthrough validation result
@@ -30,12 +35,28 @@ pub enum SettingsItem {
     // General settings
     VmLibraryPath,
     DefaultIsoPath,
+    TrashPath,
+    IsoLibraryPath,
+    MetadataPath,
+    AsciiArtPath,
     DefaultMemory,
     DefaultCpuCores,
     DefaultDiskSize,
     DefaultDisplay,
     DefaultEnableKvm,
     ConfirmBeforeLaunch,
+    ConfirmQuitWithRunningVms,
+    VmListSort,
+    SnapshotPrefix,
+    ScheduledSnapshotKeep,
+    VmStartTimeout,
+    SpiceTcp,
+    OvmfCodePath, // File browser
+    OvmfVarsPath, // File browser
+    ReportRedactPaths,
+    ReportRedactSecrets,
+    HostInfo, // Action button - opens the host hardware summary
+    ResetAll, // Action button - resets every setting after a confirmation
     // GPU Passthrough section header (not selectable, just a label)
     GpuPassthroughHeader,
     // GPU Passthrough disabled - radio button
@@ -45,11 +66,13 @@ pub enum SettingsItem {
     // Multi-GPU sub-settings (only visible when multi-GPU is enabled)
     MultiGpuIvshmemSize,
     MultiGpuShowWarnings,
+    LookingGlassClientPath, // File browser
     MultiGpuAutoLaunchLookingGlass,
     // GPU Passthrough (Single GPU) - radio button
     EnableSingleGpuPassthrough,
     // Single GPU sub-settings (only visible when single-GPU is enabled)
     SingleGpuRunSetup, // Action button - runs system setup
+    SingleGpuDisplayManager,
     SingleGpuAutoTty,
     SingleGpuShowWarnings,
 }
@@ -70,21 +93,39 @@ impl SettingsItem {
         match self {
             SettingsItem::VmLibraryPath => "VM Library Path",
             SettingsItem::DefaultIsoPath => "Default ISO Path",
+            SettingsItem::TrashPath => "Trash Directory",
+            SettingsItem::IsoLibraryPath => "ISO Library Directory",
+            SettingsItem::MetadataPath => "Metadata Overrides Path",
+            SettingsItem::AsciiArtPath => "ASCII Art Overrides Path",
             SettingsItem::DefaultMemory => "Default Memory (MB)",
             SettingsItem::DefaultCpuCores => "Default CPU Cores",
             SettingsItem::DefaultDiskSize => "Default Disk Size (GB)",
             SettingsItem::DefaultDisplay => "Default Display",
             SettingsItem::DefaultEnableKvm => "Enable KVM by Default",
             SettingsItem::ConfirmBeforeLaunch => "Confirm Before Launch",
+            SettingsItem::ConfirmQuitWithRunningVms => "Confirm Quit With Running VMs",
+            SettingsItem::VmListSort => "VM List Sort",
+            SettingsItem::SnapshotPrefix => "Snapshot Name Prefix",
+            SettingsItem::ScheduledSnapshotKeep => "Scheduled Snapshots to Keep",
+            SettingsItem::VmStartTimeout => "VM Start Timeout (s)",
+            SettingsItem::SpiceTcp => "SPICE Over TCP",
+            SettingsItem::OvmfCodePath => "OVMF Code Image",
+            SettingsItem::OvmfVarsPath => "OVMF Vars Template",
+            SettingsItem::ReportRedactPaths => "Redact Home Paths in Reports",
+            SettingsItem::ReportRedactSecrets => "Redact Secrets in Reports",
+            SettingsItem::HostInfo => "[Host Hardware Summary]",
+            SettingsItem::ResetAll => "[Reset All to Defaults]",
             // GPU Passthrough
             SettingsItem::GpuPassthroughHeader => "GPU Passthrough",
             SettingsItem::GpuPassthroughDisabled => "Disabled",
             SettingsItem::EnableMultiGpuPassthrough => "Multiple GPUs",
             SettingsItem::MultiGpuIvshmemSize => "IVSHMEM Size (MB)",
             SettingsItem::MultiGpuShowWarnings => "Show GPU Warnings",
+            SettingsItem::LookingGlassClientPath => "Looking Glass Client",
             SettingsItem::MultiGpuAutoLaunchLookingGlass => "Auto-launch Looking Glass",
             SettingsItem::EnableSingleGpuPassthrough => "Single GPU",
             SettingsItem::SingleGpuRunSetup => "[Run System Setup]",
+            SettingsItem::SingleGpuDisplayManager => "Display Manager",
             SettingsItem::SingleGpuAutoTty => "Auto TTY Switch (Experimental)",
             SettingsItem::SingleGpuShowWarnings => "Show GPU Warnings",
         }
@@ -99,23 +140,55 @@ impl SettingsItem {
                 .as_ref()
                 .map(|p| p.display().to_string())
                 .unwrap_or_default(),
+            SettingsItem::TrashPath => config
+                .trash_path
+                .as_ref()
+                .map(|p| p.display().to_string())
+                .unwrap_or_default(),
+            SettingsItem::IsoLibraryPath => config
+                .iso_library_path
+                .as_ref()
+                .map(|p| p.display().to_string())
+                .unwrap_or_default(),
+            SettingsItem::MetadataPath => config.metadata_path.display().to_string(),
+            SettingsItem::AsciiArtPath => config.ascii_art_path.display().to_string(),
             SettingsItem::DefaultMemory => config.default_memory_mb.to_string(),
             SettingsItem::DefaultCpuCores => config.default_cpu_cores.to_string(),
             SettingsItem::DefaultDiskSize => config.default_disk_size_gb.to_string(),
             SettingsItem::DefaultDisplay => config.default_display.clone(),
             SettingsItem::DefaultEnableKvm => bool_to_yes_no(config.default_enable_kvm),
             SettingsItem::ConfirmBeforeLaunch => bool_to_yes_no(config.confirm_before_launch),
+            SettingsItem::ConfirmQuitWithRunningVms => {
+                bool_to_yes_no(config.confirm_quit_with_running_vms)
+            }
+            SettingsItem::VmListSort => SortBy::list_label(config.vm_list_sort).to_string(),
+            SettingsItem::SnapshotPrefix => config.snapshot_prefix.clone(),
+            SettingsItem::ScheduledSnapshotKeep => config.scheduled_snapshot_keep.to_string(),
+            SettingsItem::VmStartTimeout => config.vm_start_timeout_secs.to_string(),
+            SettingsItem::SpiceTcp => bool_to_yes_no(config.spice_tcp),
+            SettingsItem::OvmfCodePath => path_or_auto(config.ovmf_code_path.as_deref()),
+            SettingsItem::OvmfVarsPath => path_or_auto(config.ovmf_vars_path.as_deref()),
+            SettingsItem::ReportRedactPaths => bool_to_yes_no(config.report_redact_paths),
+            SettingsItem::ReportRedactSecrets => bool_to_yes_no(config.report_redact_secrets),
+            SettingsItem::HostInfo | SettingsItem::ResetAll => String::new(), // Action buttons
             // GPU Passthrough
             SettingsItem::GpuPassthroughHeader => String::new(),
             SettingsItem::GpuPassthroughDisabled => String::new(), // Radio button, no value display
             SettingsItem::EnableMultiGpuPassthrough => String::new(), // Radio button, no value display
             SettingsItem::MultiGpuIvshmemSize => config.default_ivshmem_size_mb.to_string(),
             SettingsItem::MultiGpuShowWarnings => bool_to_yes_no(config.show_gpu_warnings),
+            SettingsItem::LookingGlassClientPath => {
+                path_or_auto(config.looking_glass_client_path.as_deref())
+            }
             SettingsItem::MultiGpuAutoLaunchLookingGlass => {
                 bool_to_yes_no(config.looking_glass_auto_launch)
             }
             SettingsItem::EnableSingleGpuPassthrough => String::new(), // Radio button, no value display
             SettingsItem::SingleGpuRunSetup => String::new(), // Action button, no value display
+            SettingsItem::SingleGpuDisplayManager => config
+                .single_gpu_dm_override
+                .clone()
+                .unwrap_or_else(|| "auto".to_string()),
             SettingsItem::SingleGpuAutoTty => bool_to_yes_no(config.single_gpu_auto_tty),
             SettingsItem::SingleGpuShowWarnings => bool_to_yes_no(config.show_gpu_warnings),
         }
@@ -127,6 +200,10 @@ impl SettingsItem {
             self,
             SettingsItem::DefaultEnableKvm
                 | SettingsItem::ConfirmBeforeLaunch
+                | SettingsItem::ConfirmQuitWithRunningVms
+                | SettingsItem::SpiceTcp
+                | SettingsItem::ReportRedactPaths
+                | SettingsItem::ReportRedactSecrets
                 | SettingsItem::MultiGpuShowWarnings
                 | SettingsItem::MultiGpuAutoLaunchLookingGlass
                 | SettingsItem::SingleGpuAutoTty
@@ -144,9 +221,36 @@ impl SettingsItem {
         )
     }
 
-    /// Check if this is a cycle setting (display backend)
+    /// Check if this is a cycle setting (display backend, list sort,
+    /// display manager)
     pub fn is_cycle(&self) -> bool {
-        matches!(self, SettingsItem::DefaultDisplay)
+        matches!(
+            self,
+            SettingsItem::DefaultDisplay
+                | SettingsItem::VmListSort
+                | SettingsItem::SingleGpuDisplayManager
+        )
+    }
+
+    /// Check if this is a file path picked with the file browser
+    pub fn is_file_path(&self) -> bool {
+        matches!(
+            self,
+            SettingsItem::OvmfCodePath
+                | SettingsItem::OvmfVarsPath
+                | SettingsItem::LookingGlassClientPath
+        )
+    }
+
+    /// File extensions the file browser shows for a file path setting
+    /// (empty = all files)
+    pub fn file_extensions(&self) -> &'static [&'static str] {
+        match self {
+            SettingsItem::OvmfCodePath | SettingsItem::OvmfVarsPath => {
+                &[".fd", ".FD", ".bin", ".BIN", ".raw", ".qcow2"]
+            }
+            _ => &[],
+        }
     }
 
     /// Check if this is a section header (not editable)
@@ -156,13 +260,17 @@ impl SettingsItem {
 
     /// Check if this is an action button (executes something when pressed)
     pub fn is_action(&self) -> bool {
-        matches!(self, SettingsItem::SingleGpuRunSetup)
+        matches!(
+            self,
+            SettingsItem::SingleGpuRunSetup | SettingsItem::HostInfo | SettingsItem::ResetAll
+        )
     }
 
     /// Get cycle options for this setting
     pub fn cycle_options(&self) -> Option<&'static [&'static str]> {
         match self {
             SettingsItem::DefaultDisplay => Some(&["gtk", "sdl", "spice-app"]),
+            SettingsItem::SingleGpuDisplayManager => Some(&["auto", "gdm", "sddm", "lightdm"]),
             _ => None,
         }
     }
@@ -172,12 +280,28 @@ impl SettingsItem {
         match self {
             SettingsItem::VmLibraryPath => "vm_library_path",
             SettingsItem::DefaultIsoPath => "default_iso_path",
+            SettingsItem::TrashPath => "trash_path",
+            SettingsItem::IsoLibraryPath => "iso_library_path",
+            SettingsItem::MetadataPath => "metadata_path",
+            SettingsItem::AsciiArtPath => "ascii_art_path",
             SettingsItem::DefaultMemory => "default_memory",
             SettingsItem::DefaultCpuCores => "default_cpu_cores",
             SettingsItem::DefaultDiskSize => "default_disk_size",
             SettingsItem::DefaultDisplay => "default_display",
             SettingsItem::DefaultEnableKvm => "default_enable_kvm",
             SettingsItem::ConfirmBeforeLaunch => "confirm_before_launch",
+            SettingsItem::ConfirmQuitWithRunningVms => "confirm_quit_with_running_vms",
+            SettingsItem::VmListSort => "vm_list_sort",
+            SettingsItem::SnapshotPrefix => "snapshot_prefix",
+            SettingsItem::ScheduledSnapshotKeep => "scheduled_snapshot_keep",
+            SettingsItem::VmStartTimeout => "vm_start_timeout",
+            SettingsItem::SpiceTcp => "spice_tcp",
+            SettingsItem::OvmfCodePath => "ovmf_code_path",
+            SettingsItem::OvmfVarsPath => "ovmf_vars_path",
+            SettingsItem::ReportRedactPaths => "report_redact_paths",
+            SettingsItem::ReportRedactSecrets => "report_redact_secrets",
+            SettingsItem::HostInfo => "host_info",
+            SettingsItem::ResetAll => "reset_all",
             SettingsItem::GpuPassthroughHeader => "gpu_passthrough_header",
             SettingsItem::GpuPassthroughDisabled => "gpu_passthrough_disabled",
             SettingsItem::EnableMultiGpuPassthrough => "enable_multi_gpu_passthrough",
@@ -185,9 +309,11 @@ impl SettingsItem {
             SettingsItem::MultiGpuShowWarnings | SettingsItem::SingleGpuShowWarnings => {
                 "show_gpu_warnings"
             }
+            SettingsItem::LookingGlassClientPath => "looking_glass_client_path",
             SettingsItem::MultiGpuAutoLaunchLookingGlass => "auto_launch_looking_glass",
             SettingsItem::EnableSingleGpuPassthrough => "enable_single_gpu_passthrough",
             SettingsItem::SingleGpuRunSetup => "single_gpu_run_setup",
+            SettingsItem::SingleGpuDisplayManager => "single_gpu_dm_override",
             SettingsItem::SingleGpuAutoTty => "single_gpu_auto_tty",
         }
     }
@@ -197,6 +323,12 @@ fn bool_to_yes_no(b: bool) -> String {
     if b { "Yes" } else { "No" }.to_string()
 }
 
+/// An optional path, or "auto-detect" when unset
+fn path_or_auto(path: Option<&std::path::Path>) -> String {
+    path.map(|p| p.display().to_string())
+        .unwrap_or_else(|| "auto-detect".to_string())
+}
+
 /// Helper to create a visible item from a settings item
 fn make_visible(item: SettingsItem, indent: usize) -> VisibleItem {
     VisibleItem {
@@ -213,6 +345,10 @@ fn build_visible_items(config: &Config) -> Vec<VisibleItem> {
     let mut items = vec![
         make_visible(SettingsItem::VmLibraryPath, 0),
         make_visible(SettingsItem::DefaultIsoPath, 0),
+        make_visible(SettingsItem::TrashPath, 0),
+        make_visible(SettingsItem::IsoLibraryPath, 0),
+        make_visible(SettingsItem::MetadataPath, 0),
+        make_visible(SettingsItem::AsciiArtPath, 0),
         make_visible(SettingsItem::DefaultMemory, 0),
         make_visible(SettingsItem::DefaultCpuCores, 0),
         make_visible(SettingsItem::DefaultDiskSize, 0),
@@ -220,6 +356,18 @@ fn build_visible_items(config: &Config) -> Vec<VisibleItem> {
         make_visible(SettingsItem::DefaultEnableKvm, 0),
     ];
     items.push(make_visible(SettingsItem::ConfirmBeforeLaunch, 0));
+    items.push(make_visible(SettingsItem::ConfirmQuitWithRunningVms, 0));
+    items.push(make_visible(SettingsItem::VmListSort, 0));
+    items.push(make_visible(SettingsItem::SnapshotPrefix, 0));
+    items.push(make_visible(SettingsItem::ScheduledSnapshotKeep, 0));
+    items.push(make_visible(SettingsItem::VmStartTimeout, 0));
+    items.push(make_visible(SettingsItem::SpiceTcp, 0));
+    items.push(make_visible(SettingsItem::OvmfCodePath, 0));
+    items.push(make_visible(SettingsItem::OvmfVarsPath, 0));
+    items.push(make_visible(SettingsItem::ReportRedactPaths, 0));
+    items.push(make_visible(SettingsItem::ReportRedactSecrets, 0));
+    items.push(make_visible(SettingsItem::HostInfo, 0)); // Action button
+    items.push(make_visible(SettingsItem::ResetAll, 0)); // Action button
 
     // GPU Passthrough section
     items.push(make_visible(SettingsItem::GpuPassthroughHeader, 0));
@@ -234,6 +382,7 @@ fn build_visible_items(config: &Config) -> Vec<VisibleItem> {
     if config.enable_multi_gpu_passthrough {
         items.push(make_visible(SettingsItem::MultiGpuIvshmemSize, 2));
         items.push(make_visible(SettingsItem::MultiGpuShowWarnings, 2));
+        items.push(make_visible(SettingsItem::LookingGlassClientPath, 2));
         items.push(make_visible(
             SettingsItem::MultiGpuAutoLaunchLookingGlass,
             2,
@@ -248,6 +397,7 @@ fn build_visible_items(config: &Config) -> Vec<VisibleItem> {
     // the display goes directly to physical monitors connected to the GPU.
     if config.single_gpu_enabled {
         items.push(make_visible(SettingsItem::SingleGpuRunSetup, 2)); // Action button
+        items.push(make_visible(SettingsItem::SingleGpuDisplayManager, 2));
         items.push(make_visible(SettingsItem::SingleGpuAutoTty, 2));
         items.push(make_visible(SettingsItem::SingleGpuShowWarnings, 2));
     }
@@ -255,6 +405,64 @@ fn build_visible_items(config: &Config) -> Vec<VisibleItem> {
     items
 }
 
+/// Narrow `items` to the settings whose name or help text contains `filter`
+/// (case-insensitive). The parents of a match (section header, radio
+/// button) are kept so the tree still reads correctly.
+fn filter_visible_items(
+    items: Vec<VisibleItem>,
+    filter: &str,
+    help_store: &crate::metadata::SettingsHelpStore,
+) -> Vec<VisibleItem> {
+    let query = filter.trim().to_lowercase();
+    if query.is_empty() {
+        return items;
+    }
+    let matches = |item: SettingsItem| {
+        let (title, description) = help_store.get_or_default(item.help_key());
+        [item.display_name(), title, description]
+            .iter()
+            .any(|text| text.to_lowercase().contains(&query))
+    };
+
+    let mut filtered = Vec::new();
+    // The most recent item at each indent level above the current one, and
+    // whether it has been kept already
+    let mut ancestors: Vec<(VisibleItem, bool)> = Vec::new();
+    for vi in items {
+        ancestors.truncate(vi.indent);
+        let is_match = matches(vi.item);
+        if is_match {
+            for (ancestor, kept) in ancestors.iter_mut().filter(|(_, kept)| !*kept) {
+                filtered.push(ancestor.clone());
+                *kept = true;
+            }
+            filtered.push(vi.clone());
+        }
+        ancestors.push((vi, is_match));
+    }
+    filtered
+}
+
+/// The settings rows currently shown: those the config makes visible,
+/// narrowed by the filter
+fn shown_items(app: &App) -> Vec<VisibleItem> {
+    filter_visible_items(
+        build_visible_items(&app.config),
+        &app.settings_filter,
+        &app.settings_help,
+    )
+}
+
+/// Keep `previous` selected after the filter changes, or else select the
+/// first setting that is still shown
+fn reselect(app: &mut App, previous: Option<SettingsItem>) {
+    let items = shown_items(app);
+    app.settings_selected = previous
+        .and_then(|item| items.iter().position(|vi| vi.item == item))
+        .or_else(|| items.iter().position(|vi| !vi.is_header))
+        .unwrap_or(0);
+}
+
 /// Render the settings screen
 pub fn render(app: &App, frame: &mut Frame) {
     let area = frame.area();
@@ -280,17 +488,20 @@ pub fn render(app: &App, frame: &mut Frame) {
         inner.height.saturating_sub(2),
     );
 
-    // Split into main content and bottom status bar
+    // Split into filter field, main content and bottom status bar
     let main_and_status = Layout::default()
         .direction(Direction::Vertical)
         .constraints([
+            Constraint::Length(2), // Filter field
             Constraint::Min(10),   // Main content
             Constraint::Length(1), // Status bar
         ])
         .split(content_area);
 
-    // Build visible items based on current config
-    let visible_items = build_visible_items(&app.config);
+    render_filter_field(app, frame, main_and_status[0]);
+
+    // Build visible items based on current config and filter
+    let visible_items = shown_items(app);
 
     // Check if validation panel should be shown
     let show_validation = app.settings_gpu_validation.is_some();
@@ -299,7 +510,7 @@ pub fn render(app: &App, frame: &mut Frame) {
     let main_chunks = Layout::default()
         .direction(Direction::Horizontal)
         .constraints([Constraint::Percentage(45), Constraint::Percentage(55)])
-        .split(main_and_status[0]);
+        .split(main_and_status[1]);
 
     // Right panel: help text + optional validation
     let right_constraints = if show_validation {
@@ -329,11 +540,45 @@ pub fn render(app: &App, frame: &mut Frame) {
     }
 
     // Render bottom status bar with version and config path
-    render_status_bar(app, frame, main_and_status[1], &visible_items);
+    render_status_bar(app, frame, main_and_status[2], &visible_items);
+}
+
+/// Render the filter field above the settings list
+fn render_filter_field(app: &App, frame: &mut Frame, area: Rect) {
+    let line = if app.settings_filtering {
+        Line::from(vec![
+            Span::styled("Filter: ", Style::default().fg(Color::Cyan)),
+            Span::styled(
+                format!("{}|", app.settings_filter),
+                Style::default().fg(Color::Yellow),
+            ),
+        ])
+    } else if !app.settings_filter.is_empty() {
+        Line::from(vec![
+            Span::styled("Filter: ", Style::default().fg(Color::Cyan)),
+            Span::styled(
+                app.settings_filter.clone(),
+                Style::default().fg(Color::White),
+            ),
+        ])
+    } else {
+        Line::styled("[/] Filter settings", Style::default().fg(Color::DarkGray))
+    };
+    frame.render_widget(Paragraph::new(line), area);
 }
 
 /// Render the settings list
 fn render_settings_list(app: &App, frame: &mut Frame, area: Rect, visible_items: &[VisibleItem]) {
+    if visible_items.is_empty() {
+        let message = Paragraph::new(format!(
+            "No settings match \"{}\"",
+            app.settings_filter.trim()
+        ))
+        .style(Style::default().fg(Color::DarkGray));
+        frame.render_widget(message, area);
+        return;
+    }
+
     let items: Vec<ListItem> = visible_items
         .iter()
         .enumerate()
@@ -702,19 +947,41 @@ fn render_status_bar(app: &App, frame: &mut Frame, area: Rect, visible_items: &[
         .unwrap_or(false);
 
     let key_hints = if app.settings_editing {
-        "[Enter] Save  [Esc] Cancel"
-    } else if is_header {
-        "[j/k] Navigate  [Esc] Back"
-    } else if is_action {
-        "[Enter] Run  [j/k] Navigate  [Esc] Back"
-    } else if is_radio {
-        "[Enter/Space] Select  [j/k] Navigate  [Esc] Back"
-    } else if current_item.map(|i| i.is_toggle()).unwrap_or(false) {
-        "[Enter/Space] Toggle  [j/k] Navigate  [Esc] Back"
-    } else if current_item.map(|i| i.is_cycle()).unwrap_or(false) {
-        "[Enter/Space] Cycle  [j/k] Navigate  [Esc] Back"
+        "[Enter] Save  [Esc] Cancel".to_string()
+    } else if app.settings_filtering {
+        "[Enter] Done  [Esc] Clear filter".to_string()
     } else {
-        "[Enter] Edit  [j/k] Navigate  [Esc] Back"
+        let item_hint = if is_header {
+            ""
+        } else if is_action {
+            "[Enter] Run  "
+        } else if is_radio {
+            "[Enter/Space] Select  "
+        } else if current_item.map(|i| i.is_toggle()).unwrap_or(false) {
+            "[Enter/Space] Toggle  "
+        } else if current_item.map(|i| i.is_cycle()).unwrap_or(false) {
+            "[Enter/Space] Cycle  "
+        } else if current_item.map(|i| i.is_file_path()).unwrap_or(false) {
+            "[Enter] Browse  [x] Auto-detect  "
+        } else if current_item.is_some() {
+            "[Enter] Edit  "
+        } else {
+            ""
+        };
+        let esc_hint = if app.settings_filter.is_empty() {
+            "Back"
+        } else {
+            "Clear filter"
+        };
+        let default_hint = if is_header || is_action || current_item.is_none() {
+            ""
+        } else {
+            "[d] Default  "
+        };
+        format!(
+            "{}{}[j/k] Navigate  [/] Filter  [Esc] {}",
+            item_hint, default_hint, esc_hint
+        )
     };
 
     // Build status line: version | key hints | config path
@@ -729,7 +996,7 @@ fn render_status_bar(app: &App, frame: &mut Frame, area: Rect, visible_items: &[
 
 /// Handle input for the settings screen
 pub fn handle_input(app: &mut App, key: KeyEvent) -> anyhow::Result<bool> {
-    let visible_items = build_visible_items(&app.config);
+    let visible_items = shown_items(app);
 
     if app.settings_editing {
         // Editing mode
@@ -754,12 +1021,54 @@ pub fn handle_input(app: &mut App, key: KeyEvent) -> anyhow::Result<bool> {
             }
             _ => {}
         }
+    } else if app.settings_filtering {
+        // Filter field has focus
+        let previous = visible_items.get(app.settings_selected).map(|vi| vi.item);
+        match key.code {
+            KeyCode::Enter => {
+                app.settings_filtering = false;
+            }
+            KeyCode::Esc => {
+                app.settings_filtering = false;
+                app.settings_filter.clear();
+            }
+            KeyCode::Backspace => {
+                app.settings_filter.pop();
+            }
+            KeyCode::Char(c) => {
+                app.settings_filter.push(c);
+            }
+            _ => {}
+        }
+        reselect(app, previous);
     } else {
         // Navigation mode
         match key.code {
+            KeyCode::Char('/') => {
+                app.settings_filtering = true;
+            }
+            KeyCode::Esc if !app.settings_filter.is_empty() => {
+                let previous = visible_items.get(app.settings_selected).map(|vi| vi.item);
+                app.settings_filter.clear();
+                reselect(app, previous);
+            }
             KeyCode::Esc => {
                 app.pop_screen();
             }
+            KeyCode::Char('d') => {
+                if let Some(vi) = visible_items.get(app.settings_selected) {
+                    if !vi.is_header && !vi.is_action {
+                        reset_to_default(app, vi.item)?;
+                    }
+                }
+            }
+            KeyCode::Char('x') => {
+                if let Some(vi) = visible_items.get(app.settings_selected) {
+                    if vi.item.is_file_path() {
+                        set_file_path(app, vi.item, None)?;
+                    }
+                }
+            }
             KeyCode::Up | KeyCode::Char('k') => {
                 if app.settings_selected > 0 {
                     app.settings_selected -= 1;
@@ -802,6 +1111,8 @@ pub fn handle_input(app: &mut App, key: KeyEvent) -> anyhow::Result<bool> {
                         toggle_setting(app, vi.item)?;
                     } else if vi.item.is_cycle() {
                         cycle_setting(app, vi.item)?;
+                    } else if vi.item.is_file_path() {
+                        browse_file_path(app, vi.item);
                     } else {
                         // Start editing
                         app.settings_edit_buffer = vi.item.get_value(&app.config);
@@ -814,7 +1125,7 @@ pub fn handle_input(app: &mut App, key: KeyEvent) -> anyhow::Result<bool> {
     }
 
     // Clamp selection to visible items after any change
-    let visible_items = build_visible_items(&app.config);
+    let visible_items = shown_items(app);
     if app.settings_selected >= visible_items.len() {
         app.settings_selected = visible_items.len().saturating_sub(1);
     }
@@ -863,6 +1174,18 @@ fn toggle_setting(app: &mut App, item: SettingsItem) -> anyhow::Result<()> {
         SettingsItem::ConfirmBeforeLaunch => {
             app.config.confirm_before_launch = !app.config.confirm_before_launch;
         }
+        SettingsItem::ConfirmQuitWithRunningVms => {
+            app.config.confirm_quit_with_running_vms = !app.config.confirm_quit_with_running_vms;
+        }
+        SettingsItem::SpiceTcp => {
+            app.config.spice_tcp = !app.config.spice_tcp;
+        }
+        SettingsItem::ReportRedactPaths => {
+            app.config.report_redact_paths = !app.config.report_redact_paths;
+        }
+        SettingsItem::ReportRedactSecrets => {
+            app.config.report_redact_secrets = !app.config.report_redact_secrets;
+        }
         SettingsItem::MultiGpuShowWarnings | SettingsItem::SingleGpuShowWarnings => {
             app.config.show_gpu_warnings = !app.config.show_gpu_warnings;
         }
@@ -880,36 +1203,95 @@ fn toggle_setting(app: &mut App, item: SettingsItem) -> anyhow::Result<()> {
 
 /// Cycle through options for a setting
 fn cycle_setting(app: &mut App, item: SettingsItem) -> anyhow::Result<()> {
+    if item == SettingsItem::VmListSort {
+        // Same as the main menu's sort key (re-sorts the list and saves)
+        app.cycle_vm_list_sort();
+        return Ok(());
+    }
     if let Some(options) = item.cycle_options() {
         let current = item.get_value(&app.config);
         let current_idx = options.iter().position(|&o| o == current).unwrap_or(0);
         let next_idx = (current_idx + 1) % options.len();
 
-        if item == SettingsItem::DefaultDisplay {
-            app.config.default_display = options[next_idx].to_string();
+        match item {
+            SettingsItem::DefaultDisplay => {
+                app.config.default_display = options[next_idx].to_string();
+            }
+            SettingsItem::SingleGpuDisplayManager => {
+                app.config.single_gpu_dm_override =
+                    Some(options[next_idx].to_string()).filter(|dm| dm != "auto");
+            }
+            _ => {}
         }
         save_config(app)?;
     }
     Ok(())
 }
 
+/// Open the file browser for a file path setting, starting next to the
+/// current file
+fn browse_file_path(app: &mut App, item: SettingsItem) {
+    let mode = FileBrowserMode::SettingsFile(item);
+    app.open_file_browser(mode);
+    let current = match item {
+        SettingsItem::OvmfCodePath => app.config.ovmf_code_path.clone(),
+        SettingsItem::OvmfVarsPath => app.config.ovmf_vars_path.clone(),
+        SettingsItem::LookingGlassClientPath => app
+            .config
+            .looking_glass_client_path
+            .clone()
+            .or_else(LookingGlassConfig::find_client),
+        _ => None,
+    };
+    if let Some(dir) = current.as_deref().and_then(|p| p.parent()) {
+        if dir.is_dir() {
+            app.file_browser_dir = dir.to_path_buf();
+            app.load_file_browser(mode);
+        }
+    }
+}
+
+/// Set (or with `None` clear back to auto-detection) a file path setting
+/// picked in the file browser
+pub fn set_file_path(
+    app: &mut App,
+    item: SettingsItem,
+    path: Option<PathBuf>,
+) -> anyhow::Result<()> {
+    match item {
+        SettingsItem::OvmfCodePath => app.config.ovmf_code_path = path,
+        SettingsItem::OvmfVarsPath => app.config.ovmf_vars_path = path,
+        SettingsItem::LookingGlassClientPath => {
+            if let Some(ref path) = path {
+                if let Err(e) = LookingGlassConfig::validate_client(Some(path)) {
+                    app.show_error(format!("{:#}", e));
+                    return Ok(());
+                }
+            }
+            app.config.looking_glass_client_path = path;
+        }
+        _ => return Ok(()),
+    }
+    save_config(app)?;
+    // The firmware override only applies with both images set
+    if matches!(
+        item,
+        SettingsItem::OvmfCodePath | SettingsItem::OvmfVarsPath
+    ) && app.config.ovmf_override().is_none()
+        && (app.config.ovmf_code_path.is_some() || app.config.ovmf_vars_path.is_some())
+    {
+        app.set_status("Saved; set both OVMF images to override auto-detection");
+    }
+    Ok(())
+}
+
 /// Apply an edit to a setting
 fn apply_edit(app: &mut App, item: SettingsItem) -> anyhow::Result<()> {
     let value = app.settings_edit_buffer.trim();
 
     match item {
         SettingsItem::VmLibraryPath => {
-            let path = std::path::PathBuf::from(value);
-            // Expand ~ to home directory
-            let path = if let Some(rest) = value.strip_prefix("~/") {
-                if let Some(home) = dirs::home_dir() {
-                    home.join(rest)
-                } else {
-                    path
-                }
-            } else {
-                path
-            };
+            let path = crate::fs::expand_home(value);
 
             // Create directory if it doesn't exist, with BTRFS CoW optimization
             if !path.exists() {
@@ -928,22 +1310,13 @@ fn apply_edit(app: &mut App, item: SettingsItem) -> anyhow::Result<()> {
                 }
             }
 
-            app.config.vm_library_path = path;
+            app.set_library_path(path);
         }
         SettingsItem::DefaultIsoPath => {
             if value.is_empty() {
                 app.config.default_iso_path = None;
             } else {
-                let path = std::path::PathBuf::from(value);
-                let path = if let Some(rest) = value.strip_prefix("~/") {
-                    if let Some(home) = dirs::home_dir() {
-                        home.join(rest)
-                    } else {
-                        path
-                    }
-                } else {
-                    path
-                };
+                let path = crate::fs::expand_home(value);
 
                 if !path.is_dir() {
                     app.set_status(format!(
@@ -956,6 +1329,36 @@ fn apply_edit(app: &mut App, item: SettingsItem) -> anyhow::Result<()> {
                 app.config.default_iso_path = Some(path);
             }
         }
+        SettingsItem::TrashPath => {
+            // Created on first use; empty = `.trash` in the VM library
+            app.config.trash_path = (!value.is_empty()).then(|| crate::fs::expand_home(value));
+        }
+        SettingsItem::IsoLibraryPath => {
+            // Created by the first download; empty = `isos` in the VM library
+            app.config.iso_library_path =
+                (!value.is_empty()).then(|| crate::fs::expand_home(value));
+        }
+        SettingsItem::MetadataPath | SettingsItem::AsciiArtPath => {
+            if value.is_empty() {
+                return Ok(());
+            }
+            let path = crate::fs::expand_home(value);
+            if item == SettingsItem::MetadataPath {
+                app.config.metadata_path = path;
+            } else {
+                app.config.ascii_art_path = path;
+            }
+            save_config(app)?;
+            app.settings_edit_buffer.clear();
+            // Overrides are loaded once at startup
+            app.set_status("Settings saved; restart vm-curator to load the new overrides");
+            return Ok(());
+        }
+        SettingsItem::SnapshotPrefix => {
+            if !value.is_empty() {
+                app.config.snapshot_prefix = value.to_string();
+            }
+        }
         SettingsItem::DefaultMemory => {
             if let Ok(mb) = value.parse::<u32>() {
                 app.config.default_memory_mb = mb;
@@ -974,6 +1377,16 @@ fn apply_edit(app: &mut App, item: SettingsItem) -> anyhow::Result<()> {
         SettingsItem::DefaultDisplay => {
             app.config.default_display = value.to_string();
         }
+        SettingsItem::ScheduledSnapshotKeep => {
+            if let Ok(keep) = value.parse::<u32>() {
+                app.config.scheduled_snapshot_keep = keep;
+            }
+        }
+        SettingsItem::VmStartTimeout => {
+            if let Ok(secs) = value.parse::<u32>() {
+                app.config.vm_start_timeout_secs = secs;
+            }
+        }
         SettingsItem::MultiGpuIvshmemSize => {
             if let Ok(mb) = value.parse::<u32>() {
                 // Clamp to reasonable range (16-512 MB)
@@ -990,6 +1403,22 @@ fn apply_edit(app: &mut App, item: SettingsItem) -> anyhow::Result<()> {
 
 /// Execute an action button
 fn execute_action(app: &mut App, item: SettingsItem) -> anyhow::Result<()> {
+    if item == SettingsItem::HostInfo {
+        let ovmf_paths = app.config.ovmf_override();
+        let tx = app.background_tx.clone();
+        app.start_progress("Checking host hardware");
+        std::thread::spawn(move || {
+            let info = crate::hardware::HostInfo::detect(ovmf_paths.as_ref());
+            let _ = tx.send(crate::app::BackgroundResult::HostInfoReady(info));
+        });
+        return Ok(());
+    }
+    if item == SettingsItem::ResetAll {
+        app.push_screen(crate::app::Screen::Confirm(
+            crate::app::ConfirmAction::ResetSettings,
+        ));
+        return Ok(());
+    }
     if item == SettingsItem::SingleGpuRunSetup {
         // Get the GPU driver - use nvidia by default, or detect from system
         let gpu_driver = detect_gpu_driver();
@@ -1027,6 +1456,164 @@ fn detect_gpu_driver() -> String {
     "nvidia".to_string()
 }
 
+/// Put one setting back to its value in `defaults`. The GPU passthrough
+/// radio buttons reset the passthrough mode as a whole.
+fn reset_setting(config: &mut Config, defaults: &Config, item: SettingsItem) {
+    match item {
+        SettingsItem::VmLibraryPath => config.vm_library_path = defaults.vm_library_path.clone(),
+        SettingsItem::DefaultIsoPath => config.default_iso_path = defaults.default_iso_path.clone(),
+        SettingsItem::TrashPath => config.trash_path = defaults.trash_path.clone(),
+        SettingsItem::IsoLibraryPath => config.iso_library_path = defaults.iso_library_path.clone(),
+        SettingsItem::MetadataPath => config.metadata_path = defaults.metadata_path.clone(),
+        SettingsItem::AsciiArtPath => config.ascii_art_path = defaults.ascii_art_path.clone(),
+        SettingsItem::DefaultMemory => config.default_memory_mb = defaults.default_memory_mb,
+        SettingsItem::DefaultCpuCores => config.default_cpu_cores = defaults.default_cpu_cores,
+        SettingsItem::DefaultDiskSize => {
+            config.default_disk_size_gb = defaults.default_disk_size_gb
+        }
+        SettingsItem::DefaultDisplay => config.default_display = defaults.default_display.clone(),
+        SettingsItem::DefaultEnableKvm => config.default_enable_kvm = defaults.default_enable_kvm,
+        SettingsItem::ConfirmBeforeLaunch => {
+            config.confirm_before_launch = defaults.confirm_before_launch
+        }
+        SettingsItem::ConfirmQuitWithRunningVms => {
+            config.confirm_quit_with_running_vms = defaults.confirm_quit_with_running_vms
+        }
+        SettingsItem::VmListSort => config.vm_list_sort = defaults.vm_list_sort,
+        SettingsItem::SnapshotPrefix => config.snapshot_prefix = defaults.snapshot_prefix.clone(),
+        SettingsItem::ScheduledSnapshotKeep => {
+            config.scheduled_snapshot_keep = defaults.scheduled_snapshot_keep
+        }
+        SettingsItem::VmStartTimeout => {
+            config.vm_start_timeout_secs = defaults.vm_start_timeout_secs
+        }
+        SettingsItem::SpiceTcp => config.spice_tcp = defaults.spice_tcp,
+        SettingsItem::OvmfCodePath => config.ovmf_code_path = defaults.ovmf_code_path.clone(),
+        SettingsItem::OvmfVarsPath => config.ovmf_vars_path = defaults.ovmf_vars_path.clone(),
+        SettingsItem::ReportRedactPaths => {
+            config.report_redact_paths = defaults.report_redact_paths
+        }
+        SettingsItem::ReportRedactSecrets => {
+            config.report_redact_secrets = defaults.report_redact_secrets
+        }
+        SettingsItem::GpuPassthroughDisabled
+        | SettingsItem::EnableMultiGpuPassthrough
+        | SettingsItem::EnableSingleGpuPassthrough => {
+            config.enable_multi_gpu_passthrough = defaults.enable_multi_gpu_passthrough;
+            config.single_gpu_enabled = defaults.single_gpu_enabled;
+        }
+        SettingsItem::MultiGpuIvshmemSize => {
+            config.default_ivshmem_size_mb = defaults.default_ivshmem_size_mb
+        }
+        SettingsItem::MultiGpuShowWarnings | SettingsItem::SingleGpuShowWarnings => {
+            config.show_gpu_warnings = defaults.show_gpu_warnings
+        }
+        SettingsItem::LookingGlassClientPath => {
+            config.looking_glass_client_path = defaults.looking_glass_client_path.clone()
+        }
+        SettingsItem::MultiGpuAutoLaunchLookingGlass => {
+            config.looking_glass_auto_launch = defaults.looking_glass_auto_launch
+        }
+        SettingsItem::SingleGpuDisplayManager => {
+            config.single_gpu_dm_override = defaults.single_gpu_dm_override.clone()
+        }
+        SettingsItem::SingleGpuAutoTty => config.single_gpu_auto_tty = defaults.single_gpu_auto_tty,
+        SettingsItem::GpuPassthroughHeader
+        | SettingsItem::HostInfo
+        | SettingsItem::ResetAll
+        | SettingsItem::SingleGpuRunSetup => {}
+    }
+}
+
+/// `Config::default()`, keeping `current`'s VM library path unless
+/// `include_library`. File browser history and bookmarks aren't settings
+/// and are always kept.
+fn reset_config(current: &Config, include_library: bool) -> Config {
+    let defaults = Config::default();
+    Config {
+        vm_library_path: if include_library {
+            defaults.vm_library_path.clone()
+        } else {
+            current.vm_library_path.clone()
+        },
+        last_browser_dirs: current.last_browser_dirs.clone(),
+        browser_bookmarks: current.browser_bookmarks.clone(),
+        library_override: current.library_override.clone(),
+        ..defaults
+    }
+}
+
+/// Bring state derived from the config (library watcher and VM list, list
+/// order, GPU validation) in line after settings changed from `old`
+fn refresh_derived_state(app: &mut App, old: &Config) {
+    if app.config.vm_library_path != old.vm_library_path {
+        app.set_library_path(app.config.vm_library_path.clone());
+        app.start_vm_refresh();
+    }
+    if app.config.vm_list_sort != old.vm_list_sort {
+        app.resort_vm_list();
+    }
+    if !app.config.enable_multi_gpu_passthrough && !app.config.single_gpu_enabled {
+        app.settings_gpu_validation = None;
+    }
+}
+
+/// Save after a reset and report `done`. Metadata and ASCII art overrides are
+/// only loaded at startup.
+fn save_reset(app: &mut App, old: &Config, done: &str) {
+    let restart = app.config.metadata_path != old.metadata_path
+        || app.config.ascii_art_path != old.ascii_art_path;
+    match app.config.save() {
+        Ok(()) if restart => app.set_status(format!(
+            "{}; restart vm-curator to load the default overrides",
+            done
+        )),
+        Ok(()) => app.set_status(done),
+        Err(e) => app.set_status(format!("Failed to save settings: {}", e)),
+    }
+}
+
+/// Reset one setting to its default and save
+fn reset_to_default(app: &mut App, item: SettingsItem) -> anyhow::Result<()> {
+    let old = app.config.clone();
+    reset_setting(&mut app.config, &Config::default(), item);
+    if app.config.vm_library_path != old.vm_library_path && !app.config.vm_library_path.exists() {
+        // Same as typing the path in: create it rather than point at nothing
+        if let Err(e) = fs::setup_vm_directory(&app.config.vm_library_path) {
+            app.config = old;
+            app.set_status(format!("Failed to create directory: {}", e));
+            return Ok(());
+        }
+    }
+    refresh_derived_state(app, &old);
+    save_reset(
+        app,
+        &old,
+        &format!("{} reset to default", item.display_name()),
+    );
+    Ok(())
+}
+
+/// Reset every setting to its default (the VM library path too with
+/// `include_library`) and save
+pub fn reset_all_settings(app: &mut App, include_library: bool) -> anyhow::Result<()> {
+    let old = app.config.clone();
+    app.config = reset_config(&old, include_library);
+    if include_library && !app.config.vm_library_path.exists() {
+        if let Err(e) = fs::setup_vm_directory(&app.config.vm_library_path) {
+            app.config.vm_library_path = old.vm_library_path.clone();
+            app.show_error(format!(
+                "Kept the VM library path: can't create the default one\n\n{:#}",
+                e
+            ));
+        }
+    }
+    refresh_derived_state(app, &old);
+    reselect(app, Some(SettingsItem::ResetAll));
+    save_reset(app, &old, "All settings reset to defaults");
+    Ok(())
+}
+
 /// Save config and show status
 fn save_config(app: &mut App) -> anyhow::Result<()> {
     match app.config.save() {
@@ -1039,3 +1626,7 @@ fn save_config(app: &mut App) -> anyhow::Result<()> {
     }
     Ok(())
 }
+
+#[cfg(test)]
+#[path = "tests/settings.rs"]
+mod tests;
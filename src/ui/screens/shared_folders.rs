@@ -278,7 +278,7 @@ pub fn handle_key(app: &mut App, key: KeyEvent) -> Result<()> {
             }
         }
         KeyCode::Char('a') | KeyCode::Char('A') => {
-            app.load_file_browser(FileBrowserMode::Directory);
+            app.open_file_browser(FileBrowserMode::Directory);
             app.push_screen(Screen::FileBrowser);
         }
         KeyCode::Char('d') | KeyCode::Char('D') => {
@@ -18,7 +18,7 @@ use ratatui::{
 
 use crate::app::{App, Screen};
 use crate::hardware::single_gpu::{check_single_gpu_support, is_running_from_tty};
-use crate::hardware::{scripts_exist, SingleGpuConfig};
+use crate::hardware::{scripts_exist, DisplayManager, SingleGpuConfig};
 use crate::vm::single_gpu_scripts;
 
 /// Fields that can be focused in the setup screen
@@ -212,7 +212,14 @@ fn render_gpu_info(app: &App, frame: &mut Frame, area: Rect) {
         lines.push(Line::from(vec![
             Span::styled("Display Manager: ", Style::default().fg(Color::White)),
             Span::styled(
-                format!("{} (auto-detected)", config.display_manager.display_name()),
+                if app.config.single_gpu_dm_override.is_some() {
+                    format!(
+                        "{} (set in Settings)",
+                        config.display_manager.display_name()
+                    )
+                } else {
+                    format!("{} (auto-detected)", config.display_manager.display_name())
+                },
                 Style::default().fg(Color::White),
             ),
         ]));
@@ -337,7 +344,7 @@ pub fn render_instructions(app: &App, frame: &mut Frame) {
     let area = frame.area();
 
     let dialog_width = 64.min(area.width.saturating_sub(4));
-    let dialog_height = 20.min(area.height.saturating_sub(4));
+    let dialog_height = 23.min(area.height.saturating_sub(4));
 
     let dialog_area = centered_rect(dialog_width, dialog_height, area);
     frame.render_widget(Clear, dialog_area);
@@ -403,6 +410,9 @@ pub fn render_instructions(app: &App, frame: &mut Frame) {
             Style::default().fg(Color::Cyan),
         ),
         Line::raw(""),
+        Line::raw("bind.sh / unbind.sh in the same folder run just the"),
+        Line::raw("vfio-pci bind or unbind step, for debugging or hooks."),
+        Line::raw(""),
         Line::styled("[Enter/Esc] Close", Style::default().fg(Color::DarkGray)),
     ];
 
@@ -441,7 +451,7 @@ pub fn handle_key(app: &mut App, key: KeyEvent) -> anyhow::Result<()> {
         KeyCode::Char('r') => {
             // Choose a GPU vBIOS ROM file (romfile=) — see #44
             if app.single_gpu_config.is_some() {
-                app.load_file_browser(crate::app::FileBrowserMode::SingleGpuRom);
+                app.open_file_browser(crate::app::FileBrowserMode::SingleGpuRom);
                 app.push_screen(Screen::FileBrowser);
             } else {
                 app.set_status("No GPU configured for passthrough");
@@ -506,16 +516,17 @@ fn generate_scripts(app: &mut App) -> anyhow::Result<()> {
                 Ok(scripts) => {
                     // Log generated script paths
                     let dir = scripts.start_script.parent().unwrap();
-                    app.set_status(format!(
-                        "Generated: {}, {} in {}",
-                        scripts.start_script.file_name().unwrap().to_string_lossy(),
-                        scripts
-                            .restore_script
-                            .file_name()
-                            .unwrap()
-                            .to_string_lossy(),
-                        dir.display()
-                    ));
+                    let names = [
+                        &scripts.start_script,
+                        &scripts.restore_script,
+                        &scripts.bind_script,
+                        &scripts.unbind_script,
+                    ]
+                    .iter()
+                    .map(|p| p.file_name().unwrap().to_string_lossy().into_owned())
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                    app.set_status(format!("Generated: {} in {}", names, dir.display()));
                     // Show instructions dialog
                     app.single_gpu_show_instructions = true;
                     app.push_screen(Screen::SingleGpuInstructions);
@@ -582,6 +593,9 @@ pub fn init_single_gpu_config(app: &mut App) {
 
     if let Some(gpu) = gpu {
         let mut config = SingleGpuConfig::new(gpu, &app.pci_devices);
+        if let Some(ref dm) = app.config.single_gpu_dm_override {
+            config.display_manager = DisplayManager::from_service_name(dm);
+        }
         // Carry over a previously-saved vBIOS ROM path (#44) so it survives
         // re-entry; the rest of the config is re-detected from live hardware.
         if let Some(vm) = app.selected_vm() {
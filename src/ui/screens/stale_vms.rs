@@ -0,0 +1,220 @@
+//! Stale VMs Screen
+//!
+//! Lists VMs that haven't been launched in a while with the space their
+//! folders take, so several can be moved to trash in one go. Running VMs
+//! are shown but can't be marked.
+
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{
+    layout::Rect,
+    prelude::*,
+    style::{Color, Modifier, Style},
+    widgets::{Block, Borders, Clear, Paragraph},
+};
+
+use crate::app::{App, ConfirmAction, Screen};
+use crate::vm::snapshot::format_size;
+
+/// Thresholds offered by `[t]`, in days without a launch
+const STALE_DAYS: [u32; 4] = [30, 90, 180, 365];
+
+/// Scan for stale VMs and open the screen
+pub fn open(app: &mut App) {
+    app.stale_vms_selected = 0;
+    app.stale_vms_marked.clear();
+    app.load_stale_vms();
+    app.push_screen(Screen::StaleVms);
+}
+
+/// Total size of the marked VMs
+pub fn marked_bytes(app: &App) -> u64 {
+    app.stale_vms
+        .iter()
+        .filter(|vm| app.stale_vms_marked.contains(&vm.id))
+        .map(|vm| vm.size_bytes)
+        .sum()
+}
+
+/// Render the stale VMs screen
+pub fn render(app: &App, frame: &mut Frame) {
+    let area = frame.area();
+    let dialog_width = 80.min(area.width.saturating_sub(4));
+    let dialog_height = 24.min(area.height.saturating_sub(4));
+
+    let dialog_area = centered_rect(dialog_width, dialog_height, area);
+    frame.render_widget(Clear, dialog_area);
+
+    let block = Block::default()
+        .title(format!(
+            " Stale VMs - not launched in {} days ",
+            app.stale_vm_days
+        ))
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::Cyan))
+        .style(Style::default().bg(Color::Black));
+
+    let inner = block.inner(dialog_area);
+    frame.render_widget(block, dialog_area);
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(2),
+            Constraint::Min(3),
+            Constraint::Length(2),
+        ])
+        .split(inner.inner(Margin::new(1, 0)));
+
+    frame.render_widget(
+        Paragraph::new(Line::styled(
+            "Never-launched VMs count from when launch.sh last changed.",
+            Style::default().fg(Color::DarkGray),
+        )),
+        chunks[0],
+    );
+
+    let visible = chunks[1].height as usize;
+    let lines: Vec<Line> = if app.stale_vms.is_empty() {
+        vec![Line::styled(
+            format!(
+                "Every VM was launched in the last {} days.",
+                app.stale_vm_days
+            ),
+            Style::default().fg(Color::DarkGray),
+        )]
+    } else {
+        let skip = app
+            .stale_vms_selected
+            .saturating_sub(visible.saturating_sub(1));
+        app.stale_vms
+            .iter()
+            .enumerate()
+            .skip(skip)
+            .take(visible)
+            .map(|(i, vm)| {
+                let selected = i == app.stale_vms_selected;
+                let checkbox = if vm.running {
+                    "   "
+                } else if app.stale_vms_marked.contains(&vm.id) {
+                    "[x]"
+                } else {
+                    "[ ]"
+                };
+                let name_style = if selected {
+                    Style::default()
+                        .fg(Color::Yellow)
+                        .add_modifier(Modifier::BOLD)
+                } else if vm.running {
+                    Style::default().fg(Color::DarkGray)
+                } else {
+                    Style::default().fg(Color::White)
+                };
+                let last = match vm.last_launched {
+                    Some(t) => t.format("%Y-%m-%d").to_string(),
+                    None => "never".to_string(),
+                };
+                let mut spans = vec![
+                    Span::styled(
+                        format!("{} {} ", if selected { ">" } else { " " }, checkbox),
+                        name_style,
+                    ),
+                    Span::styled(format!("{:<36}", vm.name), name_style),
+                    Span::styled(format!("{:<12}", last), Style::default().fg(Color::Gray)),
+                    Span::styled(
+                        format!("{:>8}", format_size(vm.size_bytes)),
+                        Style::default().fg(Color::Cyan),
+                    ),
+                ];
+                if vm.running {
+                    spans.push(Span::styled(" running", Style::default().fg(Color::Green)));
+                }
+                Line::from(spans)
+            })
+            .collect()
+    };
+    frame.render_widget(Paragraph::new(lines), chunks[1]);
+
+    let marked = app.stale_vms_marked.len();
+    frame.render_widget(
+        Paragraph::new(vec![
+            Line::styled(
+                format!(
+                    "{} marked, {} to free",
+                    marked,
+                    format_size(marked_bytes(app))
+                ),
+                Style::default().fg(Color::White),
+            ),
+            Line::styled(
+                "[Space] Mark  [a] Mark all  [t] Threshold  [d] Delete marked  [Esc] Back",
+                Style::default().fg(Color::DarkGray),
+            ),
+        ]),
+        chunks[2],
+    );
+}
+
+/// Handle key input for the stale VMs screen
+pub fn handle_key(app: &mut App, key: KeyEvent) -> anyhow::Result<()> {
+    let last = app.stale_vms.len().saturating_sub(1);
+
+    match key.code {
+        KeyCode::Esc => app.pop_screen(),
+        KeyCode::Up | KeyCode::Char('k') => {
+            app.stale_vms_selected = app.stale_vms_selected.saturating_sub(1);
+        }
+        KeyCode::Down | KeyCode::Char('j') => {
+            app.stale_vms_selected = (app.stale_vms_selected + 1).min(last);
+        }
+        KeyCode::Char(' ') => {
+            if let Some(vm) = app.stale_vms.get(app.stale_vms_selected) {
+                if vm.running {
+                    app.set_status(format!("{} is running and can't be deleted", vm.name));
+                } else if !app.stale_vms_marked.remove(&vm.id) {
+                    app.stale_vms_marked.insert(vm.id.clone());
+                }
+                app.stale_vms_selected = (app.stale_vms_selected + 1).min(last);
+            }
+        }
+        KeyCode::Char('a') => {
+            let all: Vec<String> = app
+                .stale_vms
+                .iter()
+                .filter(|vm| !vm.running)
+                .map(|vm| vm.id.clone())
+                .collect();
+            if all.iter().all(|id| app.stale_vms_marked.contains(id)) {
+                app.stale_vms_marked.clear();
+            } else {
+                app.stale_vms_marked.extend(all);
+            }
+        }
+        KeyCode::Char('t') => {
+            app.stale_vm_days = STALE_DAYS
+                .iter()
+                .copied()
+                .find(|&d| d > app.stale_vm_days)
+                .unwrap_or(STALE_DAYS[0]);
+            app.load_stale_vms();
+        }
+        KeyCode::Char('d') if !app.stale_vms_marked.is_empty() => {
+            // Keep list order so the report reads like the screen
+            let ids: Vec<String> = app
+                .stale_vms
+                .iter()
+                .filter(|vm| app.stale_vms_marked.contains(&vm.id))
+                .map(|vm| vm.id.clone())
+                .collect();
+            app.push_screen(Screen::Confirm(ConfirmAction::DeleteStaleVms(ids)));
+        }
+        KeyCode::Char('d') => app.set_status("Mark VMs with Space first"),
+        _ => {}
+    }
+    Ok(())
+}
+
+fn centered_rect(width: u16, height: u16, area: Rect) -> Rect {
+    let x = area.x + (area.width.saturating_sub(width)) / 2;
+    let y = area.y + (area.height.saturating_sub(height)) / 2;
+    Rect::new(x, y, width, height)
+}
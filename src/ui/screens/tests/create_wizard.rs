@@ -107,56 +107,119 @@ fn is_visible_passt_backend_hides_bridge_keeps_forwards() {
 
 #[test]
 fn next_visible_field_skips_hidden_when_network_none() {
-    // Direct repro of issue #31: Down from Network (idx 4) must skip
-    // NetBackend/Bridge/Forwards/MAC and land on DiskInterface (idx 9).
+    // Direct repro of issue #31: Down from Network (idx 11) must skip
+    // NetBackend/Bridge/Forwards/MAC and land on DiskInterface (idx 16).
     let cfg = cfg_with("none", "user");
-    assert_eq!(next_visible_field(4, &cfg, 1), 9, "Down from Network");
+    assert_eq!(next_visible_field(11, &cfg, 1), 16, "Down from Network");
     // And Up from DiskInterface must skip back to Network.
-    assert_eq!(next_visible_field(9, &cfg, -1), 4, "Up from DiskInterface");
+    assert_eq!(
+        next_visible_field(16, &cfg, -1),
+        11,
+        "Up from DiskInterface"
+    );
 }
 
 #[test]
 fn next_visible_field_skips_bridge_with_user_backend() {
     let cfg = cfg_with("virtio", "user");
-    // Down from NetBackend (idx 5) skips BridgeName (idx 6) → PortForwards (idx 7).
-    assert_eq!(next_visible_field(5, &cfg, 1), 7);
-    // Up from PortForwards (idx 7) returns to NetBackend (idx 5).
-    assert_eq!(next_visible_field(7, &cfg, -1), 5);
+    // Down from NetBackend (idx 12) skips BridgeName (idx 13) → PortForwards (idx 14).
+    assert_eq!(next_visible_field(12, &cfg, 1), 14);
+    // Up from PortForwards (idx 14) returns to NetBackend (idx 12).
+    assert_eq!(next_visible_field(14, &cfg, -1), 12);
 }
 
 #[test]
 fn next_visible_field_skips_forwards_with_bridge_backend() {
     let cfg = cfg_with("virtio", "bridge");
-    // Down from BridgeName (idx 6) skips PortForwards (idx 7) → MAC (idx 8).
-    assert_eq!(next_visible_field(6, &cfg, 1), 8);
+    // Down from BridgeName (idx 13) skips PortForwards (idx 14) → MAC (idx 15).
+    assert_eq!(next_visible_field(13, &cfg, 1), 15);
     // Up from MAC returns to BridgeName.
-    assert_eq!(next_visible_field(8, &cfg, -1), 6);
+    assert_eq!(next_visible_field(15, &cfg, -1), 13);
 }
 
 #[test]
 fn next_visible_field_stays_put_at_bounds() {
     let cfg = WizardQemuConfig::default();
-    // No visible row beyond RtcLocal (idx 16) → stay put.
-    assert_eq!(next_visible_field(16, &cfg, 1), 16);
-    // No row before Memory (idx 0) → stay put.
+    // No visible row beyond Balloon (idx 28) → stay put.
+    assert_eq!(next_visible_field(28, &cfg, 1), 28);
+    // No row before Emulator (idx 0) → stay put.
     assert_eq!(next_visible_field(0, &cfg, -1), 0);
 }
 
 #[test]
 fn snap_focus_to_visible_moves_off_hidden_field() {
     // After a 'r' reset to a profile with network_model = "none", a focus
-    // parked on NetBackend (idx 5) must snap forward to DiskInterface (9).
+    // parked on NetBackend (idx 12) must snap forward to DiskInterface (16).
     let cfg = cfg_with("none", "user");
-    assert_eq!(snap_focus_to_visible(5, &cfg), 9);
-    // MAC (idx 8) is also hidden in this config — snap forward to 9.
-    assert_eq!(snap_focus_to_visible(8, &cfg), 9);
+    assert_eq!(snap_focus_to_visible(12, &cfg), 16);
+    // MAC (idx 15) is also hidden in this config — snap forward to 16.
+    assert_eq!(snap_focus_to_visible(15, &cfg), 16);
 }
 
 #[test]
 fn snap_focus_to_visible_keeps_visible_focus_put() {
     let cfg = WizardQemuConfig::default();
-    // Memory (0), Network (4), and RtcLocal (16) are always visible.
-    assert_eq!(snap_focus_to_visible(0, &cfg), 0);
-    assert_eq!(snap_focus_to_visible(4, &cfg), 4);
-    assert_eq!(snap_focus_to_visible(16, &cfg), 16);
+    // Memory (1), Network (11), and RtcLocal (26) are always visible.
+    assert_eq!(snap_focus_to_visible(1, &cfg), 1);
+    assert_eq!(snap_focus_to_visible(11, &cfg), 11);
+    assert_eq!(snap_focus_to_visible(26, &cfg), 26);
+}
+
+#[test]
+fn nested_virt_hidden_without_kvm() {
+    let mut cfg = WizardQemuConfig::default();
+    assert!(QemuField::NestedVirt.is_visible(&cfg));
+    cfg.enable_kvm = false;
+    assert!(!QemuField::NestedVirt.is_visible(&cfg));
+    // Down from KVM (idx 19) skips NestedVirt → GlAccel (idx 21).
+    assert_eq!(next_visible_field(19, &cfg, 1), 21);
+}
+
+#[test]
+fn topology_rows_hidden_until_enabled() {
+    let mut cfg = WizardQemuConfig::default();
+    assert!(QemuField::Topology.is_visible(&cfg));
+    assert!(!QemuField::Sockets.is_visible(&cfg));
+    // Down from Topology (idx 4) skips Sockets/Cores/Threads → Vga (idx 8).
+    assert_eq!(next_visible_field(4, &cfg, 1), 8);
+    cfg.cpu_topology = Some(crate::vm::qemu_config::CpuTopology::flat(cfg.cpu_cores));
+    assert!(QemuField::Threads.is_visible(&cfg));
+    assert_eq!(next_visible_field(4, &cfg, 1), 5);
+}
+
+#[test]
+fn audio_backend_row_follows_audio_and_spice() {
+    let mut cfg = WizardQemuConfig::default();
+    assert!(QemuField::AudioBackend.is_visible(&cfg));
+    cfg.audio.clear();
+    assert!(!QemuField::AudioBackend.is_visible(&cfg));
+    // Down from Audio (idx 9) skips the hidden backend → Network (idx 11).
+    assert_eq!(next_visible_field(9, &cfg, 1), 11);
+
+    assert!(!audio_backend_options("gtk").contains(&"spice"));
+    assert!(audio_backend_options("spice-app").contains(&"spice"));
+}
+
+#[test]
+fn secure_boot_row_only_with_uefi() {
+    let mut cfg = WizardQemuConfig {
+        uefi: false,
+        ..WizardQemuConfig::default()
+    };
+    assert!(!QemuField::SecureBoot.is_visible(&cfg));
+    // Down from TPM (idx 23) skips Secure Boot → Input (idx 25).
+    assert_eq!(next_visible_field(23, &cfg, 1), 25);
+    cfg.uefi = true;
+    assert!(QemuField::SecureBoot.is_visible(&cfg));
+    assert_eq!(next_visible_field(23, &cfg, 1), 24);
+}
+
+#[test]
+fn keymap_row_hidden_when_headless() {
+    let mut cfg = WizardQemuConfig::default();
+    assert!(QemuField::Keymap.is_visible(&cfg));
+    cfg.display = "none".to_string();
+    assert!(!QemuField::Keymap.is_visible(&cfg));
+    // Down from Display (idx 17) skips the keyboard → KVM (idx 19).
+    assert_eq!(next_visible_field(17, &cfg, 1), 19);
 }
@@ -0,0 +1,143 @@
+use super::*;
+use crate::metadata::SettingsHelpStore;
+
+fn shown(items: &[VisibleItem]) -> Vec<SettingsItem> {
+    items.iter().map(|vi| vi.item).collect()
+}
+
+#[test]
+fn empty_filter_shows_everything() {
+    let config = Config::default();
+    let help = SettingsHelpStore::load_embedded();
+    let all = build_visible_items(&config);
+    assert_eq!(
+        shown(&filter_visible_items(all.clone(), "  ", &help)),
+        shown(&all)
+    );
+}
+
+#[test]
+fn filter_matches_names_case_insensitively() {
+    let config = Config::default();
+    let help = SettingsHelpStore::load_embedded();
+    let items = filter_visible_items(build_visible_items(&config), "MEMORY (mb)", &help);
+    assert_eq!(shown(&items), vec![SettingsItem::DefaultMemory]);
+}
+
+#[test]
+fn filter_matches_help_text() {
+    let config = Config::default();
+    let help = SettingsHelpStore::load_embedded();
+    let (_, description) = help.get_or_default(SettingsItem::VmLibraryPath.help_key());
+    let phrase = description
+        .split_whitespace()
+        .take(4)
+        .collect::<Vec<_>>()
+        .join(" ");
+    let items = filter_visible_items(build_visible_items(&config), &phrase, &help);
+    assert!(shown(&items).contains(&SettingsItem::VmLibraryPath));
+}
+
+#[test]
+fn filter_keeps_the_parents_of_a_match() {
+    let config = Config {
+        enable_multi_gpu_passthrough: true,
+        ..Config::default()
+    };
+    let help = SettingsHelpStore::load_embedded();
+    let items = filter_visible_items(build_visible_items(&config), "ivshmem size", &help);
+    assert_eq!(
+        shown(&items),
+        vec![
+            SettingsItem::GpuPassthroughHeader,
+            SettingsItem::EnableMultiGpuPassthrough,
+            SettingsItem::MultiGpuIvshmemSize,
+        ]
+    );
+    assert!(items[0].is_header);
+}
+
+#[test]
+fn unmatched_filter_shows_nothing() {
+    let config = Config::default();
+    let help = SettingsHelpStore::load_embedded();
+    assert!(filter_visible_items(build_visible_items(&config), "zzzqqq", &help).is_empty());
+}
+
+#[test]
+fn every_setting_has_help_text() {
+    let help = SettingsHelpStore::load_embedded();
+    let multi = Config {
+        enable_multi_gpu_passthrough: true,
+        ..Config::default()
+    };
+    let single = Config {
+        single_gpu_enabled: true,
+        ..Config::default()
+    };
+    for vi in build_visible_items(&multi)
+        .into_iter()
+        .chain(build_visible_items(&single))
+    {
+        assert!(
+            help.get(vi.item.help_key()).is_some(),
+            "no help for {:?}",
+            vi.item
+        );
+    }
+}
+
+#[test]
+fn reset_setting_restores_one_field() {
+    let defaults = Config::default();
+    let mut config = Config {
+        default_memory_mb: 512,
+        spice_tcp: !defaults.spice_tcp,
+        single_gpu_enabled: true,
+        single_gpu_dm_override: Some("sddm".to_string()),
+        ..Config::default()
+    };
+    reset_setting(&mut config, &defaults, SettingsItem::DefaultMemory);
+    assert_eq!(config.default_memory_mb, defaults.default_memory_mb);
+    assert_ne!(config.spice_tcp, defaults.spice_tcp);
+
+    reset_setting(
+        &mut config,
+        &defaults,
+        SettingsItem::SingleGpuDisplayManager,
+    );
+    assert_eq!(config.single_gpu_dm_override, None);
+    assert!(config.single_gpu_enabled);
+
+    // Any of the radio buttons resets the passthrough mode
+    reset_setting(
+        &mut config,
+        &defaults,
+        SettingsItem::EnableSingleGpuPassthrough,
+    );
+    assert!(!config.single_gpu_enabled);
+    assert!(!config.enable_multi_gpu_passthrough);
+}
+
+#[test]
+fn reset_config_keeps_the_library_unless_asked() {
+    let defaults = Config::default();
+    let mut current = Config {
+        vm_library_path: PathBuf::from("/srv/vms"),
+        default_cpu_cores: 16,
+        browser_bookmarks: vec![PathBuf::from("/srv/isos")],
+        ..Config::default()
+    };
+    current
+        .last_browser_dirs
+        .insert("iso".to_string(), PathBuf::from("/srv/isos"));
+
+    let reset = reset_config(&current, false);
+    assert_eq!(reset.vm_library_path, PathBuf::from("/srv/vms"));
+    assert_eq!(reset.default_cpu_cores, defaults.default_cpu_cores);
+    assert_eq!(reset.browser_bookmarks, current.browser_bookmarks);
+    assert_eq!(reset.last_browser_dirs, current.last_browser_dirs);
+
+    let reset = reset_config(&current, true);
+    assert_eq!(reset.vm_library_path, defaults.vm_library_path);
+}
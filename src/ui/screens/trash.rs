@@ -0,0 +1,179 @@
+//! Trash Screen
+//!
+//! Lists deleted VMs in the trash directory. A VM can be restored to where
+//! it was deleted from, and the whole trash can be emptied after typing a
+//! confirmation word.
+
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{
+    layout::Rect,
+    prelude::*,
+    style::{Color, Modifier, Style},
+    widgets::{Block, Borders, Clear, Paragraph},
+};
+
+use crate::app::{App, Screen, TextInputContext};
+use crate::vm::snapshot::format_size;
+
+/// Word to type before the trash is emptied
+pub const EMPTY_TRASH_WORD: &str = "delete";
+
+/// Read the trash directory and open the screen
+pub fn open(app: &mut App) {
+    app.trash_selected = 0;
+    app.load_trash();
+    app.push_screen(Screen::Trash);
+}
+
+/// Total size of everything in the trash
+pub fn total_bytes(app: &App) -> u64 {
+    app.trash_entries.iter().map(|e| e.size_bytes).sum()
+}
+
+/// Render the trash screen
+pub fn render(app: &App, frame: &mut Frame) {
+    let area = frame.area();
+    let dialog_width = 80.min(area.width.saturating_sub(4));
+    let dialog_height = 24.min(area.height.saturating_sub(4));
+
+    let dialog_area = centered_rect(dialog_width, dialog_height, area);
+    frame.render_widget(Clear, dialog_area);
+
+    let block = Block::default()
+        .title(format!(
+            " Trash ({}, {}) ",
+            app.trash_entries.len(),
+            format_size(total_bytes(app))
+        ))
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::Cyan))
+        .style(Style::default().bg(Color::Black));
+
+    let inner = block.inner(dialog_area);
+    frame.render_widget(block, dialog_area);
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(2),
+            Constraint::Min(3),
+            Constraint::Length(2),
+        ])
+        .split(inner.inner(Margin::new(1, 0)));
+
+    frame.render_widget(
+        Paragraph::new(Line::styled(
+            app.config.trash_dir().display().to_string(),
+            Style::default().fg(Color::DarkGray),
+        )),
+        chunks[0],
+    );
+
+    let visible = chunks[1].height as usize;
+    let lines: Vec<Line> = if app.trash_entries.is_empty() {
+        vec![Line::styled(
+            "The trash is empty.",
+            Style::default().fg(Color::DarkGray),
+        )]
+    } else {
+        let skip = app.trash_selected.saturating_sub(visible.saturating_sub(1));
+        app.trash_entries
+            .iter()
+            .enumerate()
+            .skip(skip)
+            .take(visible)
+            .map(|(i, entry)| {
+                let selected = i == app.trash_selected;
+                let name_style = if selected {
+                    Style::default()
+                        .fg(Color::Yellow)
+                        .add_modifier(Modifier::BOLD)
+                } else {
+                    Style::default().fg(Color::White)
+                };
+                let deleted = entry
+                    .deleted_at
+                    .map(|t| t.format("%Y-%m-%d %H:%M").to_string())
+                    .unwrap_or_else(|| "unknown".to_string());
+                Line::from(vec![
+                    Span::styled(if selected { "> " } else { "  " }, name_style),
+                    Span::styled(format!("{:<36}", entry.name), name_style),
+                    Span::styled(format!("{:<18}", deleted), Style::default().fg(Color::Gray)),
+                    Span::styled(
+                        format!("{:>8}", format_size(entry.size_bytes)),
+                        Style::default().fg(Color::Cyan),
+                    ),
+                ])
+            })
+            .collect()
+    };
+    frame.render_widget(Paragraph::new(lines), chunks[1]);
+
+    let restore_to = app
+        .trash_entries
+        .get(app.trash_selected)
+        .map(|e| format!("Restores to {}", e.original_path.display()))
+        .unwrap_or_default();
+    frame.render_widget(
+        Paragraph::new(vec![
+            Line::styled(restore_to, Style::default().fg(Color::DarkGray)),
+            Line::styled(
+                "[Enter/r] Restore  [E] Empty trash  [Esc] Back",
+                Style::default().fg(Color::DarkGray),
+            ),
+        ]),
+        chunks[2],
+    );
+}
+
+/// Handle key input for the trash screen
+pub fn handle_key(app: &mut App, key: KeyEvent) -> anyhow::Result<()> {
+    match key.code {
+        KeyCode::Esc => app.pop_screen(),
+        KeyCode::Up | KeyCode::Char('k') => {
+            app.trash_selected = app.trash_selected.saturating_sub(1);
+        }
+        KeyCode::Down | KeyCode::Char('j') => {
+            app.trash_selected =
+                (app.trash_selected + 1).min(app.trash_entries.len().saturating_sub(1));
+        }
+        KeyCode::Enter | KeyCode::Char('r') if !app.loading => {
+            let Some(entry) = app.trash_entries.get(app.trash_selected).cloned() else {
+                return Ok(());
+            };
+            app.start_restoring_vm(entry);
+        }
+        KeyCode::Char('E') if !app.trash_entries.is_empty() => {
+            app.text_input_buffer.clear();
+            app.push_screen(Screen::TextInput(TextInputContext::EmptyTrash));
+        }
+        _ => {}
+    }
+    Ok(())
+}
+
+/// Permanently delete everything in the trash
+pub fn empty_trash(app: &mut App) {
+    let freed = total_bytes(app);
+    let mut failed = Vec::new();
+    for entry in &app.trash_entries {
+        if let Err(e) = crate::vm::trash::purge(entry) {
+            failed.push(format!("{}: {:#}", entry.name, e));
+        }
+    }
+    app.load_trash();
+    if failed.is_empty() {
+        app.set_status(format!("Trash emptied, {} freed", format_size(freed)));
+    } else {
+        app.show_error(format!(
+            "Some VMs could not be deleted:\n\n{}",
+            failed.join("\n")
+        ));
+    }
+}
+
+fn centered_rect(width: u16, height: u16, area: Rect) -> Rect {
+    let x = area.x + (area.width.saturating_sub(width)) / 2;
+    let y = area.y + (area.height.saturating_sub(height)) / 2;
+    Rect::new(x, y, width, height)
+}
@@ -0,0 +1,215 @@
+//! UEFI Variables Screen
+//!
+//! Backs up or resets the selected VM's UEFI variable store (NVRAM) and
+//! switches it between the plain and Secure Boot firmware. Resetting fixes
+//! VMs that boot to the UEFI shell after their boot entries got corrupted.
+
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{
+    layout::Rect,
+    prelude::*,
+    style::{Color, Modifier, Style},
+    widgets::{Block, Borders, Clear, Paragraph, Wrap},
+};
+
+use crate::app::{App, ConfirmAction, Screen};
+use crate::vm::uefi::{self, UefiVars, NVRAM_BACKUP_DIR};
+
+/// Rows of the screen
+const ACTIONS: [&str; 3] = ["Back up variables", "Reset to template", "Secure Boot"];
+
+/// UEFI setup of the selected VM
+fn current(app: &App) -> Option<UefiVars> {
+    let vm = app.selected_vm()?;
+    uefi::read_uefi_vars(&vm.path, &vm.config.raw_script)
+}
+
+/// Render the UEFI variables screen
+pub fn render(app: &App, frame: &mut Frame) {
+    let area = frame.area();
+    let dialog_width = 70.min(area.width.saturating_sub(4));
+    let dialog_height = 18.min(area.height.saturating_sub(4));
+
+    let dialog_area = centered_rect(dialog_width, dialog_height, area);
+    frame.render_widget(Clear, dialog_area);
+
+    let Some(vm) = app.selected_vm() else {
+        return;
+    };
+
+    let block = Block::default()
+        .title(format!(" {} - UEFI Variables ", vm.display_name()))
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::Cyan))
+        .style(Style::default().bg(Color::Black));
+
+    let inner = block.inner(dialog_area);
+    frame.render_widget(block, dialog_area);
+
+    let Some(nvram) = current(app) else {
+        let para = Paragraph::new(vec![
+            Line::raw(""),
+            Line::styled(
+                "This VM's launch.sh doesn't keep a UEFI variable store in the VM \
+                 folder. Regenerate the script to add one.",
+                Style::default().fg(Color::Gray),
+            ),
+            Line::raw(""),
+            Line::styled("[Esc] Back", Style::default().fg(Color::DarkGray)),
+        ])
+        .wrap(Wrap { trim: false });
+        frame.render_widget(para, inner.inner(Margin::new(2, 0)));
+        return;
+    };
+
+    let backups = std::fs::read_dir(vm.path.join(NVRAM_BACKUP_DIR))
+        .map(|entries| entries.count())
+        .unwrap_or(0);
+    let label = |text: &'static str| Span::styled(text, Style::default().fg(Color::Gray));
+    let value = |text: String| Span::styled(text, Style::default().fg(Color::White));
+    let mut lines = vec![
+        Line::raw(""),
+        Line::from(vec![
+            label("Variables: "),
+            value(if nvram.vars.exists() {
+                nvram.vars.display().to_string()
+            } else {
+                "created on first launch".to_string()
+            }),
+        ]),
+        Line::from(vec![
+            label("Template:  "),
+            value(
+                nvram
+                    .template
+                    .as_ref()
+                    .map(|t| t.display().to_string())
+                    .unwrap_or_else(|| "none".to_string()),
+            ),
+        ]),
+        Line::from(vec![
+            label("Backups:   "),
+            value(format!("{} in {}/", backups, NVRAM_BACKUP_DIR)),
+        ]),
+        Line::raw(""),
+    ];
+
+    for (i, action) in ACTIONS.iter().enumerate() {
+        let style = if i == app.selected_menu_item {
+            Style::default()
+                .fg(Color::Yellow)
+                .add_modifier(Modifier::BOLD)
+        } else {
+            Style::default().fg(Color::White)
+        };
+        let mut spans = vec![Span::styled(format!("[{}] {}", i + 1, action), style)];
+        if i == 2 {
+            spans.push(Span::styled(
+                if nvram.secure_boot { "  ON" } else { "  OFF" },
+                Style::default().fg(Color::DarkGray),
+            ));
+        }
+        lines.push(Line::from(spans));
+    }
+
+    lines.push(Line::raw(""));
+    lines.push(Line::styled(
+        "Resetting and switching Secure Boot replace the variables (boot entries, \
+         keys) after backing them up. Windows 11 needs Secure Boot.",
+        Style::default().fg(Color::DarkGray),
+    ));
+    lines.push(Line::raw(""));
+    lines.push(Line::styled(
+        "[Enter] Select  [Esc] Back",
+        Style::default().fg(Color::DarkGray),
+    ));
+
+    let para = Paragraph::new(lines).wrap(Wrap { trim: false });
+    frame.render_widget(para, inner.inner(Margin::new(2, 0)));
+}
+
+/// Handle key input for the UEFI variables screen
+pub fn handle_key(app: &mut App, key: KeyEvent) -> anyhow::Result<()> {
+    let Some(nvram) = current(app) else {
+        if key.code == KeyCode::Esc {
+            app.pop_screen();
+        }
+        return Ok(());
+    };
+
+    match key.code {
+        KeyCode::Esc => app.pop_screen(),
+        KeyCode::Char('j') | KeyCode::Down => app.menu_next(ACTIONS.len()),
+        KeyCode::Char('k') | KeyCode::Up => app.menu_prev(),
+        KeyCode::Enter | KeyCode::Char('1'..='3') => {
+            let row = match key.code {
+                KeyCode::Char(c) => c as usize - '1' as usize,
+                _ => app.selected_menu_item,
+            };
+            app.selected_menu_item = row;
+            if row == 0 {
+                backup(app, &nvram);
+            } else if app.selected_vm_pid().is_some() {
+                app.set_status("Stop the VM before changing its UEFI variables");
+            } else if row == 1 {
+                app.push_screen(Screen::Confirm(ConfirmAction::ResetNvram));
+            } else {
+                app.push_screen(Screen::Confirm(ConfirmAction::SetSecureBoot(
+                    !nvram.secure_boot,
+                )));
+            }
+        }
+        _ => {}
+    }
+    Ok(())
+}
+
+fn backup(app: &mut App, nvram: &UefiVars) {
+    let Some(vm) = app.selected_vm() else {
+        return;
+    };
+    match uefi::backup_nvram(&vm.path, nvram) {
+        Ok(path) => app.set_status(format!("UEFI variables backed up to {}", path.display())),
+        Err(e) => app.set_status(format!("Failed to back up UEFI variables: {}", e)),
+    }
+}
+
+/// Reset the selected VM's variables to the template (confirmed)
+pub fn reset(app: &mut App) {
+    let (Some(vm), Some(nvram)) = (app.selected_vm(), current(app)) else {
+        return;
+    };
+    match uefi::reset_nvram(&vm.path, &nvram) {
+        Ok(Some(backup)) => app.set_status(format!(
+            "UEFI variables reset (old ones saved to {})",
+            backup.display()
+        )),
+        Ok(None) => app.set_status("UEFI variables reset"),
+        Err(e) => app.set_status(format!("Failed to reset UEFI variables: {}", e)),
+    }
+}
+
+/// Switch the selected VM's firmware to or from Secure Boot (confirmed)
+pub fn set_secure_boot(app: &mut App, enabled: bool) {
+    let Some(vm) = app.selected_vm() else {
+        return;
+    };
+    let ovmf_paths = app.config.ovmf_override();
+    match uefi::set_secure_boot(vm, enabled, ovmf_paths.as_ref()) {
+        Ok(_) => {
+            app.set_status(if enabled {
+                "Secure Boot enabled; UEFI variables reset to the Secure Boot template"
+            } else {
+                "Secure Boot disabled; UEFI variables reset to the template"
+            });
+            app.reload_selected_vm_script();
+        }
+        Err(e) => app.set_status(format!("Failed to switch Secure Boot: {}", e)),
+    }
+}
+
+fn centered_rect(width: u16, height: u16, area: Rect) -> Rect {
+    let x = area.x + (area.width.saturating_sub(width)) / 2;
+    let y = area.y + (area.height.saturating_sub(height)) / 2;
+    Rect::new(x, y, width, height)
+}
@@ -4,6 +4,85 @@ use ratatui::{
 };
 
 use crate::metadata::OsInfo;
+use crate::vm::qemu_config::NetworkConfig;
+
+/// Note lines previewed near the top of the main info panel
+const NOTES_PREVIEW_LINES: usize = 4;
+
+/// A "Notes" heading and the note lines, cut to `max_lines` with a count of
+/// what was left out
+fn notes_section(notes: &str, max_lines: Option<usize>) -> Vec<Line<'static>> {
+    let total = notes.lines().count();
+    let shown = max_lines.unwrap_or(total).min(total);
+
+    let mut lines = vec![Line::from(Span::styled(
+        "Notes",
+        Style::default()
+            .fg(Color::Yellow)
+            .add_modifier(Modifier::BOLD),
+    ))];
+    lines.extend(
+        notes
+            .lines()
+            .take(shown)
+            .map(|line| Line::styled(line.to_string(), Style::default().fg(Color::Cyan))),
+    );
+    if shown < total {
+        lines.push(Line::styled(
+            format!("… {} more lines (scroll down for all notes)", total - shown),
+            Style::default().fg(Color::DarkGray),
+        ));
+    }
+    lines
+}
+
+/// A red warning for each disk the VM shares with other VMs
+fn shared_disks_section(warnings: &[String]) -> Vec<Line<'static>> {
+    let red = Style::default().fg(Color::Red);
+    let mut lines = vec![Line::styled(
+        "\u{26a0} Shared Disk",
+        red.add_modifier(Modifier::BOLD),
+    )];
+    lines.extend(warnings.iter().map(|w| Line::styled(w.clone(), red)));
+    lines.push(Line::styled(
+        "Running these VMs at the same time corrupts the disk.",
+        Style::default().fg(Color::Gray),
+    ));
+    lines
+}
+
+/// How the guest is addressed, its port forwards and, when its guest agent
+/// answered, the addresses it actually has
+fn network_section(net: &NetworkConfig, guest_addresses: Option<&[String]>) -> Vec<Line<'static>> {
+    let yellow = Style::default().fg(Color::Yellow);
+    let mut lines = vec![Line::from(Span::styled(
+        "Network",
+        yellow.add_modifier(Modifier::BOLD),
+    ))];
+    if let Some(addressing) = net.addressing() {
+        lines.push(Line::raw(addressing));
+    }
+    for pf in &net.port_forwards {
+        lines.push(Line::styled(
+            format!("• {}", pf.describe()),
+            Style::default().fg(Color::Cyan),
+        ));
+    }
+    match guest_addresses {
+        Some(addresses) if !addresses.is_empty() => {
+            lines.push(Line::styled("Guest agent reports:", yellow));
+            lines.extend(addresses.iter().map(|a| Line::raw(format!("  {}", a))));
+        }
+        _ if matches!(net.backend, crate::vm::NetworkBackend::Bridge(_)) => {
+            lines.push(Line::styled(
+                "Run the VM with the QEMU guest agent to see its address here",
+                Style::default().fg(Color::DarkGray),
+            ));
+        }
+        _ => {}
+    }
+    lines
+}
 
 /// ASCII art and info display widget with scrolling support
 pub struct AsciiInfoWidget<'a> {
@@ -12,6 +91,10 @@ pub struct AsciiInfoWidget<'a> {
     pub vm_name: &'a str,
     pub scroll: u16,
     pub notes: Option<&'a str>,
+    /// e.g. "Last launched 3 days ago (12 launches)"
+    pub launch_summary: Option<&'a str>,
+    /// Disks shared with other VMs, from `shared_disk_warnings`
+    pub shared_disks: &'a [String],
 }
 
 impl<'a> AsciiInfoWidget<'a> {
@@ -36,6 +119,10 @@ impl<'a> AsciiInfoWidget<'a> {
 
         // Build the full content as a single scrollable text
         let mut lines: Vec<Line> = Vec::new();
+        let notes = self.notes.filter(|n| !n.trim().is_empty());
+        // Long notes get a preview up top and the full text at the end
+        let notes_truncated = notes.is_some_and(|n| n.lines().count() > NOTES_PREVIEW_LINES);
+        let notes_preview = notes.map(|n| notes_section(n, Some(NOTES_PREVIEW_LINES)));
 
         // ASCII art - preserve exact spacing (no trimming)
         for line in self.ascii_art.trim_start_matches('\n').lines() {
@@ -58,8 +145,21 @@ impl<'a> AsciiInfoWidget<'a> {
                 Span::raw(" | "),
                 Span::styled(&info.architecture, Style::default().fg(Color::Gray)),
             ]));
+            if let Some(launch) = self.launch_summary {
+                lines.push(Line::styled(launch, Style::default().fg(Color::DarkGray)));
+            }
             lines.push(Line::from(""));
 
+            if !self.shared_disks.is_empty() {
+                lines.extend(shared_disks_section(self.shared_disks));
+                lines.push(Line::from(""));
+            }
+
+            if let Some(preview) = notes_preview {
+                lines.extend(preview);
+                lines.push(Line::from(""));
+            }
+
             // Short blurb
             if !info.blurb.short.is_empty() {
                 for line in info.blurb.short.lines() {
@@ -95,18 +195,9 @@ impl<'a> AsciiInfoWidget<'a> {
                 }
             }
 
-            // User notes
-            if let Some(notes) = self.notes {
+            if let Some(notes) = notes.filter(|_| notes_truncated) {
                 lines.push(Line::from(""));
-                lines.push(Line::from(Span::styled(
-                    "Notes",
-                    Style::default()
-                        .fg(Color::Yellow)
-                        .add_modifier(Modifier::BOLD),
-                )));
-                for line in notes.lines() {
-                    lines.push(Line::from(line.to_string()));
-                }
+                lines.extend(notes_section(notes, None));
             }
         } else {
             // Just show the VM name
@@ -116,19 +207,19 @@ impl<'a> AsciiInfoWidget<'a> {
                     .fg(Color::White)
                     .add_modifier(Modifier::BOLD),
             )));
+            if let Some(launch) = self.launch_summary {
+                lines.push(Line::styled(launch, Style::default().fg(Color::DarkGray)));
+            }
 
-            // User notes (even without OS info)
-            if let Some(notes) = self.notes {
+            if !self.shared_disks.is_empty() {
                 lines.push(Line::from(""));
-                lines.push(Line::from(Span::styled(
-                    "Notes",
-                    Style::default()
-                        .fg(Color::Yellow)
-                        .add_modifier(Modifier::BOLD),
-                )));
-                for line in notes.lines() {
-                    lines.push(Line::from(line.to_string()));
-                }
+                lines.extend(shared_disks_section(self.shared_disks));
+            }
+
+            // User notes (even without OS info); nothing else competes for space
+            if let Some(notes) = notes {
+                lines.push(Line::from(""));
+                lines.extend(notes_section(notes, None));
             }
         }
 
@@ -144,6 +235,19 @@ impl<'a> AsciiInfoWidget<'a> {
 pub struct DetailedInfoWidget<'a> {
     pub os_info: Option<&'a OsInfo>,
     pub vm_name: &'a str,
+    /// (disk file name, usage summary) for each of the VM's disks
+    pub disks: &'a [(String, String)],
+    /// Disks shared with other VMs, from `shared_disk_warnings`
+    pub shared_disks: &'a [String],
+    /// launch.sh options the structured config doesn't capture
+    pub unrecognized_args: &'a [String],
+    /// The VM's NIC, if it has one
+    pub network: Option<&'a NetworkConfig>,
+    /// Addresses the running guest's agent reported
+    pub guest_addresses: Option<&'a [String]>,
+    /// The user's notes for the VM
+    pub notes: Option<&'a str>,
+    pub scroll: u16,
 }
 
 impl<'a> DetailedInfoWidget<'a> {
@@ -156,8 +260,60 @@ impl<'a> DetailedInfoWidget<'a> {
         let inner = block.inner(area);
         block.render(area, buf);
 
+        let mut text = Vec::new();
+        if !self.shared_disks.is_empty() {
+            text.extend(shared_disks_section(self.shared_disks));
+            text.push(Line::from(""));
+        }
+        if !self.disks.is_empty() {
+            text.push(Line::from(Span::styled(
+                "Disks",
+                Style::default()
+                    .fg(Color::Yellow)
+                    .add_modifier(Modifier::BOLD),
+            )));
+            for (name, usage) in self.disks {
+                text.push(Line::from(vec![
+                    Span::styled(format!("{}: ", name), Style::default().fg(Color::Yellow)),
+                    Span::raw(usage.as_str()),
+                ]));
+            }
+            text.push(Line::from(""));
+        }
+
+        if let Some(net) = self.network {
+            text.extend(network_section(net, self.guest_addresses));
+            text.push(Line::from(""));
+        }
+
+        if let Some(notes) = self.notes.filter(|n| !n.trim().is_empty()) {
+            text.extend(notes_section(notes, None));
+            text.push(Line::from(""));
+        }
+
+        if !self.unrecognized_args.is_empty() {
+            text.push(Line::from(Span::styled(
+                "Unrecognized Options",
+                Style::default()
+                    .fg(Color::Yellow)
+                    .add_modifier(Modifier::BOLD),
+            )));
+            text.push(Line::styled(
+                "vm-curator doesn't model these launch.sh options, so menus and \
+                 settings won't show or change them:",
+                Style::default().fg(Color::Gray),
+            ));
+            for arg in self.unrecognized_args {
+                text.push(Line::styled(
+                    format!("• {}", arg),
+                    Style::default().fg(Color::Red),
+                ));
+            }
+            text.push(Line::from(""));
+        }
+
         if let Some(info) = self.os_info {
-            let mut text = vec![
+            text.extend([
                 Line::from(vec![
                     Span::styled("Name: ", Style::default().fg(Color::Yellow)),
                     Span::raw(&info.name),
@@ -175,7 +331,7 @@ impl<'a> DetailedInfoWidget<'a> {
                     Span::raw(&info.architecture),
                 ]),
                 Line::from(""),
-            ];
+            ]);
 
             // Add long description
             if !info.blurb.long.is_empty() {
@@ -203,13 +359,16 @@ impl<'a> DetailedInfoWidget<'a> {
                     text.push(Line::from(format!("• {}", fact)));
                 }
             }
-
-            let para = Paragraph::new(text).wrap(Wrap { trim: true });
-            para.render(inner, buf);
         } else {
-            let text = Paragraph::new("No detailed information available for this VM.")
-                .style(Style::default().fg(Color::Gray));
-            text.render(inner, buf);
+            text.push(Line::styled(
+                "No detailed information available for this VM.",
+                Style::default().fg(Color::Gray),
+            ));
         }
+
+        let para = Paragraph::new(text)
+            .wrap(Wrap { trim: true })
+            .scroll((self.scroll, 0));
+        para.render(inner, buf);
     }
 }
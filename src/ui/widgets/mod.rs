@@ -1,7 +1,9 @@
 pub mod ascii_display;
 pub mod dialog;
+pub mod progress;
 pub mod vm_list;
 
 pub use ascii_display::{AsciiInfoWidget, DetailedInfoWidget};
 pub use dialog::ConfirmDialog;
+pub use progress::ProgressWidget;
 pub use vm_list::{build_visual_order, click_row_to_visual_index, VmListWidget};
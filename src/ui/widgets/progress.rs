@@ -0,0 +1,54 @@
+//! Progress overlay for long-running background operations
+
+use ratatui::{
+    prelude::*,
+    widgets::{Block, Borders, Clear, Gauge, Paragraph},
+};
+
+/// Spinner frames for operations that report no progress
+const SPINNER: [char; 10] = ['⠋', '⠙', '⠹', '⠸', '⠼', '⠴', '⠦', '⠧', '⠇', '⠏'];
+
+/// Small centered box showing a gauge (known percentage) or a spinner
+pub struct ProgressWidget<'a> {
+    pub label: &'a str,
+    /// Percent complete (0-100); `None` shows an animated spinner
+    pub percent: Option<f64>,
+    /// Milliseconds since the operation started, drives the spinner
+    pub elapsed_ms: u128,
+}
+
+impl ProgressWidget<'_> {
+    pub fn render(self, area: Rect, buf: &mut Buffer) {
+        let width = 50.min(area.width.saturating_sub(4));
+        let height = 3.min(area.height);
+        let x = area.x + area.width.saturating_sub(width) / 2;
+        let y = area.y + area.height.saturating_sub(height) / 2;
+        let dialog_area = Rect::new(x, y, width, height);
+
+        Clear.render(dialog_area, buf);
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Color::Cyan))
+            .style(Style::default().bg(Color::Black));
+        let inner = block.inner(dialog_area);
+        block.render(dialog_area, buf);
+
+        match self.percent {
+            Some(percent) => {
+                let percent = percent.clamp(0.0, 100.0);
+                Gauge::default()
+                    .gauge_style(Style::default().fg(Color::Cyan).bg(Color::DarkGray))
+                    .ratio(percent / 100.0)
+                    .label(format!("{} {:.0}%", self.label, percent))
+                    .render(inner, buf);
+            }
+            None => {
+                let frame = SPINNER[(self.elapsed_ms / 100) as usize % SPINNER.len()];
+                Paragraph::new(format!("{} {}", frame, self.label))
+                    .style(Style::default().fg(Color::White))
+                    .alignment(Alignment::Center)
+                    .render(inner, buf);
+            }
+        }
+    }
+}
@@ -0,0 +1,93 @@
+use super::*;
+use crate::vm::QemuConfig;
+use chrono::{Local, TimeZone};
+use std::path::PathBuf;
+
+/// Uncategorized VMs (all in one subcategory) as (id, memory MB, launched at)
+fn vms(specs: &[(&str, u32, Option<i64>)]) -> Vec<DiscoveredVm> {
+    specs
+        .iter()
+        .map(|&(id, memory_mb, launched)| DiscoveredVm {
+            id: id.to_string(),
+            path: PathBuf::from("/test").join(id),
+            launch_script: PathBuf::from("/test").join(id).join("launch.sh"),
+            config: QemuConfig {
+                memory_mb,
+                ..QemuConfig::default()
+            },
+            custom_name: Some(id.to_string()),
+            os_profile: None,
+            notes: None,
+            unrecognized_args: Vec::new(),
+            last_launched: launched.map(|t| Local.timestamp_opt(t, 0).unwrap()),
+            launch_count: 0,
+            autostart: false,
+            first_boot_done: None,
+            custom_ascii: None,
+            shared_disks: Vec::new(),
+        })
+        .collect()
+}
+
+fn ordered_ids(vms: &[DiscoveredVm], sort: Option<SortBy>) -> Vec<&str> {
+    let filtered: Vec<usize> = (0..vms.len()).collect();
+    let order = build_visual_order(
+        vms,
+        &filtered,
+        &HierarchyConfig::load_embedded(),
+        &MetadataStore::default(),
+        sort,
+    );
+    order
+        .iter()
+        .map(|&i| vms[filtered[i]].id.as_str())
+        .collect()
+}
+
+#[test]
+fn test_visual_order_follows_list_sort() {
+    let vms = vms(&[
+        ("zz-bravo", 2048, Some(1_700_000_000)),
+        ("zz-alpha", 4096, None),
+        ("zz-charlie", 8192, Some(1_800_000_000)),
+    ]);
+
+    assert_eq!(
+        ordered_ids(&vms, Some(SortBy::Name)),
+        ["zz-alpha", "zz-bravo", "zz-charlie"]
+    );
+    // Most recent first, never launched last
+    assert_eq!(
+        ordered_ids(&vms, Some(SortBy::LastLaunched)),
+        ["zz-charlie", "zz-bravo", "zz-alpha"]
+    );
+    assert_eq!(
+        ordered_ids(&vms, Some(SortBy::Memory)),
+        ["zz-charlie", "zz-alpha", "zz-bravo"]
+    );
+    // Category order falls back to the subcategory's own sort (name)
+    assert_eq!(
+        ordered_ids(&vms, None),
+        ["zz-alpha", "zz-bravo", "zz-charlie"]
+    );
+}
+
+#[test]
+fn test_list_sort_cycle_returns_to_category() {
+    let mut sort = None;
+    let mut labels = Vec::new();
+    for _ in 0..4 {
+        sort = SortBy::next_list_sort(sort);
+        labels.push(SortBy::list_label(sort));
+    }
+    assert_eq!(labels, ["name", "last launched", "memory", "category"]);
+}
+
+#[test]
+fn test_os_tag_per_family() {
+    assert_eq!(os_tag("microsoft", "Microsoft"), ('W', Color::LightBlue));
+    assert_eq!(os_tag("linux", "Linux"), ('L', Color::Yellow));
+    // Families without a color of their own fall back to a gray letter
+    assert_eq!(os_tag("research", "Research"), ('R', Color::DarkGray));
+    assert_eq!(os_tag("x", "🔬"), ('?', Color::DarkGray));
+}
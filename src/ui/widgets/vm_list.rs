@@ -6,18 +6,20 @@ use ratatui::{
 use crate::app::App;
 use crate::metadata::{HierarchyConfig, MetadataStore, SortBy};
 use crate::vm::DiscoveredVm;
-use std::collections::{BTreeMap, HashMap};
+use std::collections::{BTreeMap, HashMap, HashSet};
 use std::time::Instant;
 
 /// Build the visual order of VMs based on hierarchy (used for navigation)
-/// Returns a Vec where index is visual position and value is filtered_idx
+/// Returns a Vec where index is visual position and value is filtered_idx.
+/// `sort` overrides each subcategory's own order when set.
 pub fn build_visual_order(
     vms: &[DiscoveredVm],
     filtered_indices: &[usize],
     hierarchy: &HierarchyConfig,
     metadata: &MetadataStore,
+    sort: Option<SortBy>,
 ) -> Vec<usize> {
-    let vm_hierarchy = build_vm_hierarchy(vms, filtered_indices, hierarchy, metadata);
+    let vm_hierarchy = build_vm_hierarchy(vms, filtered_indices, hierarchy, metadata, sort);
     let mut order = Vec::new();
 
     for family in &hierarchy.families {
@@ -42,10 +44,11 @@ pub fn click_row_to_visual_index(
     filtered_indices: &[usize],
     hierarchy: &HierarchyConfig,
     metadata: &MetadataStore,
+    sort: Option<SortBy>,
     visual_order: &[usize],
     clicked_row: usize,
 ) -> Option<usize> {
-    let vm_hierarchy = build_vm_hierarchy(vms, filtered_indices, hierarchy, metadata);
+    let vm_hierarchy = build_vm_hierarchy(vms, filtered_indices, hierarchy, metadata, sort);
 
     // Build index_map to map row -> filtered_idx (None for headers)
     let mut index_map: Vec<Option<usize>> = Vec::new();
@@ -86,8 +89,26 @@ pub struct VmListWidget<'a> {
     pub selected: usize,
     pub hierarchy: &'a HierarchyConfig,
     pub metadata: &'a crate::metadata::MetadataStore,
+    /// VM list sort picked on the main menu (None = category order)
+    pub sort: Option<SortBy>,
     pub running_vms: &'a HashMap<String, u32>,
     pub stopping_vms: &'a HashMap<String, Instant>,
+    pub guest_ready_vms: &'a HashSet<String>,
+    /// vm_id -> current balloon size in MB
+    pub balloon_vms: &'a HashMap<String, u64>,
+    /// vm_id -> time until the next scheduled snapshot (running VMs only)
+    pub snapshot_countdowns: HashMap<String, String>,
+    /// VM ids marked for a batch action; `Some` only in bulk mode
+    pub marked_vms: Option<&'a HashSet<String>>,
+}
+
+/// Per-VM runtime state shown as badges next to running VMs
+struct RunBadges<'b> {
+    running_vms: &'b HashMap<String, u32>,
+    stopping_vms: &'b HashMap<String, Instant>,
+    guest_ready_vms: &'b HashSet<String>,
+    balloon_vms: &'b HashMap<String, u64>,
+    snapshot_countdowns: &'b HashMap<String, String>,
 }
 
 impl<'a> VmListWidget<'a> {
@@ -99,8 +120,17 @@ impl<'a> VmListWidget<'a> {
             selected: app.selected_vm,
             hierarchy: &app.hierarchy,
             metadata: &app.metadata,
+            sort: app.config.vm_list_sort,
             running_vms: &app.running_vms,
             stopping_vms: &app.stopping_vms,
+            guest_ready_vms: &app.guest_ready_vms,
+            balloon_vms: &app.balloon_vms,
+            snapshot_countdowns: app
+                .next_scheduled_snapshot
+                .keys()
+                .filter_map(|id| Some((id.clone(), app.snapshot_countdown(id)?)))
+                .collect(),
+            marked_vms: app.bulk_mode.then_some(&app.marked_vms),
         }
     }
 
@@ -109,7 +139,14 @@ impl<'a> VmListWidget<'a> {
         // when the list content changes (e.g., during search filtering)
         Clear.render(area, buf);
 
-        let title = format!(" VMs ({}) ", self.filtered_indices.len());
+        let mut title = format!(
+            " VMs ({}) · sort: {} ",
+            self.filtered_indices.len(),
+            SortBy::list_label(self.sort)
+        );
+        if let Some(marked) = self.marked_vms {
+            title.push_str(&format!("· bulk: {} marked ", marked.len()));
+        }
 
         // Build hierarchical structure
         let vm_hierarchy = build_vm_hierarchy(
@@ -117,6 +154,7 @@ impl<'a> VmListWidget<'a> {
             self.filtered_indices,
             self.hierarchy,
             self.metadata,
+            self.sort,
         );
 
         // Available width for list items: area minus borders minus highlight symbol ("→ ")
@@ -127,8 +165,14 @@ impl<'a> VmListWidget<'a> {
             &vm_hierarchy,
             self.hierarchy,
             self.metadata,
-            self.running_vms,
-            self.stopping_vms,
+            &RunBadges {
+                running_vms: self.running_vms,
+                stopping_vms: self.stopping_vms,
+                guest_ready_vms: self.guest_ready_vms,
+                balloon_vms: self.balloon_vms,
+                snapshot_countdowns: &self.snapshot_countdowns,
+            },
+            self.marked_vms,
             inner_width,
         );
 
@@ -171,12 +215,14 @@ struct VmEntry<'a> {
 
 /// Build hierarchical structure from VMs
 /// Returns: family_id -> subcategory_id -> Vec<VmEntry>
-/// VMs within each subcategory are sorted by release_date (oldest first)
+/// VMs within each subcategory are sorted by `sort`, or by the subcategory's
+/// own sort_by when it is None
 fn build_vm_hierarchy<'a>(
     vms: &'a [DiscoveredVm],
     filtered_indices: &[usize],
     hierarchy: &HierarchyConfig,
     metadata: &MetadataStore,
+    sort: Option<SortBy>,
 ) -> BTreeMap<String, BTreeMap<String, Vec<VmEntry<'a>>>> {
     let mut result: BTreeMap<String, BTreeMap<String, Vec<VmEntry>>> = BTreeMap::new();
 
@@ -194,12 +240,15 @@ fn build_vm_hierarchy<'a>(
             .push(VmEntry { vm, filtered_idx });
     }
 
-    // Sort VMs within each subcategory based on subcategory's sort_by setting
+    // Sort VMs within each subcategory based on the chosen list sort, or the
+    // subcategory's sort_by setting
     for (subcat_id, vm_entries) in result.values_mut().flat_map(|subcats| subcats.iter_mut()) {
-        let sort_by = hierarchy
-            .get_subcategory(subcat_id)
-            .map(|s| s.sort_by)
+        let sort_by = sort
+            .or_else(|| hierarchy.get_subcategory(subcat_id).map(|s| s.sort_by))
             .unwrap_or(SortBy::Name);
+        let by_name = |a: &VmEntry, b: &VmEntry| {
+            get_display_name(a.vm, metadata).cmp(&get_display_name(b.vm, metadata))
+        };
 
         vm_entries.sort_by(|a, b| {
             match sort_by {
@@ -230,9 +279,19 @@ fn build_vm_hierarchy<'a>(
                 }
                 SortBy::Name => {
                     // Sort alphabetically by display name
-                    let name_a = get_display_name(a.vm, metadata);
-                    let name_b = get_display_name(b.vm, metadata);
-                    name_a.cmp(&name_b)
+                    by_name(a, b)
+                }
+                SortBy::LastLaunched => {
+                    // Most recent first; never-launched VMs (None) sort last
+                    b.vm.last_launched
+                        .cmp(&a.vm.last_launched)
+                        .then_with(|| by_name(a, b))
+                }
+                SortBy::Memory => {
+                    b.vm.config
+                        .memory_mb
+                        .cmp(&a.vm.config.memory_mb)
+                        .then_with(|| by_name(a, b))
                 }
             }
         });
@@ -264,13 +323,35 @@ fn get_display_name(vm: &DiscoveredVm, metadata: &crate::metadata::MetadataStore
     vm.display_name()
 }
 
+/// Letter tag and color marking a VM's OS type, picked by its hierarchy
+/// family. Plain ASCII so it lines up in terminals without emoji; families
+/// without their own color get the first letter of their name in gray.
+fn os_tag(family_id: &str, family_name: &str) -> (char, Color) {
+    match family_id {
+        "microsoft" => ('W', Color::LightBlue),
+        "linux" => ('L', Color::Yellow),
+        "bsd" => ('B', Color::LightRed),
+        "apple" => ('M', Color::Gray),
+        "unix" => ('U', Color::LightCyan),
+        "be" => ('H', Color::LightGreen),
+        "mobile" => ('A', Color::Green),
+        _ => (
+            family_name
+                .chars()
+                .find(|c| c.is_ascii_alphabetic())
+                .map_or('?', |c| c.to_ascii_uppercase()),
+            Color::DarkGray,
+        ),
+    }
+}
+
 /// Render hierarchy as list items with tree characters
 fn render_hierarchy_items<'a>(
     vm_hierarchy: &BTreeMap<String, BTreeMap<String, Vec<VmEntry<'a>>>>,
     hierarchy: &'a HierarchyConfig,
     metadata: &crate::metadata::MetadataStore,
-    running_vms: &HashMap<String, u32>,
-    stopping_vms: &HashMap<String, Instant>,
+    badges: &RunBadges,
+    marked_vms: Option<&HashSet<String>>,
     inner_width: usize,
 ) -> (Vec<ListItem<'a>>, Vec<Option<usize>>) {
     let mut items = Vec::new();
@@ -279,6 +360,7 @@ fn render_hierarchy_items<'a>(
     // Iterate families in order
     for family in &hierarchy.families {
         if let Some(subcats) = vm_hierarchy.get(&family.id) {
+            let (tag, tag_color) = os_tag(&family.id, &family.name);
             // Family header with icon
             items.push(ListItem::new(Line::from(vec![
                 Span::raw(format!("{} ", family.icon)),
@@ -322,31 +404,123 @@ fn render_hierarchy_items<'a>(
                         // Get display name from metadata
                         let display_name = get_display_name(entry.vm, metadata);
 
-                        let is_stopping = stopping_vms.contains_key(&entry.vm.id);
-                        let is_running = running_vms.contains_key(&entry.vm.id);
+                        let is_stopping = badges.stopping_vms.contains_key(&entry.vm.id);
+                        let is_running = badges.running_vms.contains_key(&entry.vm.id);
+                        // Autostart VMs get a "↻" at the right edge, running or not
+                        let autostart = entry.vm.autostart.then(|| {
+                            Span::styled(" \u{21bb}", Style::default().fg(Color::DarkGray))
+                        });
+                        let autostart_width = if autostart.is_some() { 2 } else { 0 };
 
                         let prefix = format!("  {}{} ", subcat_cont, vm_branch);
+                        // VMs sharing a disk with another VM get a red warning sign
+                        let shared = (!entry.vm.shared_disks.is_empty()).then(|| {
+                            Span::styled(
+                                "\u{26a0} ",
+                                Style::default().fg(Color::Red).add_modifier(Modifier::BOLD),
+                            )
+                        });
+                        // Bulk mode shows a checkbox before each name
+                        let mark = marked_vms.map(|marked| {
+                            if marked.contains(&entry.vm.id) {
+                                Span::styled(
+                                    "[x] ",
+                                    Style::default()
+                                        .fg(Color::Yellow)
+                                        .add_modifier(Modifier::BOLD),
+                                )
+                            } else {
+                                Span::styled("[ ] ", Style::default().fg(Color::DarkGray))
+                            }
+                        });
+                        let os = Span::styled(
+                            format!("{} ", tag),
+                            Style::default().fg(tag_color).add_modifier(Modifier::BOLD),
+                        );
                         // +2 for the indicator "●" and its leading space
-                        let used_width = prefix.len() + display_name.len();
+                        let used_width = prefix.len()
+                            + mark.as_ref().map_or(0, |m| m.width())
+                            + os.width()
+                            + shared.as_ref().map_or(0, |s| s.width())
+                            + display_name.len();
 
                         if is_stopping || is_running {
-                            let padding = inner_width.saturating_sub(used_width + 2);
+                            // VMs with a guest agent channel get a check before the dot:
+                            // cyan once the agent answers, dim while the guest boots
+                            let has_agent = !is_stopping && entry.vm.config.has_guest_agent();
+                            let guest_ready =
+                                has_agent && badges.guest_ready_vms.contains(&entry.vm.id);
+                            // Countdown to the next scheduled snapshot, e.g. " 12m"
+                            let countdown = badges
+                                .snapshot_countdowns
+                                .get(&entry.vm.id)
+                                .filter(|_| !is_stopping)
+                                .map(|c| format!(" {}", c));
+                            // Current guest RAM for VMs with a memory balloon, e.g. " 2048M"
+                            let balloon = badges
+                                .balloon_vms
+                                .get(&entry.vm.id)
+                                .filter(|_| !is_stopping)
+                                .map(|mb| format!(" {}M", mb));
+                            let badge_width = if has_agent { 4 } else { 2 }
+                                + autostart_width
+                                + countdown.as_ref().map_or(0, |c| c.len())
+                                + balloon.as_ref().map_or(0, |b| b.len());
+                            let padding = inner_width.saturating_sub(used_width + badge_width);
                             let color = if is_stopping {
                                 Color::Yellow
                             } else {
                                 Color::Green
                             };
-                            items.push(ListItem::new(Line::from(vec![
-                                Span::styled(prefix, Style::default().fg(Color::DarkGray)),
+                            let mut spans =
+                                vec![Span::styled(prefix, Style::default().fg(Color::DarkGray))];
+                            spans.extend(mark);
+                            spans.push(os);
+                            spans.extend(shared);
+                            spans.extend([
                                 Span::styled(display_name, Style::default().fg(Color::White)),
                                 Span::raw(" ".repeat(padding)),
-                                Span::styled(" \u{25cf}", Style::default().fg(color)),
-                            ])));
+                            ]);
+                            if let Some(balloon) = balloon {
+                                spans.push(Span::styled(
+                                    balloon,
+                                    Style::default().fg(Color::DarkGray),
+                                ));
+                            }
+                            if let Some(countdown) = countdown {
+                                spans.push(Span::styled(
+                                    countdown,
+                                    Style::default().fg(Color::DarkGray),
+                                ));
+                            }
+                            spans.extend(autostart);
+                            if has_agent {
+                                let check = if guest_ready {
+                                    Color::Cyan
+                                } else {
+                                    Color::DarkGray
+                                };
+                                spans.push(Span::styled(" \u{2713}", Style::default().fg(check)));
+                            }
+                            spans.push(Span::styled(" \u{25cf}", Style::default().fg(color)));
+                            items.push(ListItem::new(Line::from(spans)));
                         } else {
-                            items.push(ListItem::new(Line::from(vec![
-                                Span::styled(prefix, Style::default().fg(Color::DarkGray)),
-                                Span::styled(display_name, Style::default().fg(Color::White)),
-                            ])));
+                            let mut spans =
+                                vec![Span::styled(prefix, Style::default().fg(Color::DarkGray))];
+                            spans.extend(mark);
+                            spans.push(os);
+                            spans.extend(shared);
+                            spans.push(Span::styled(
+                                display_name,
+                                Style::default().fg(Color::White),
+                            ));
+                            if let Some(autostart) = autostart {
+                                let padding =
+                                    inner_width.saturating_sub(used_width + autostart_width);
+                                spans.push(Span::raw(" ".repeat(padding)));
+                                spans.push(autostart);
+                            }
+                            items.push(ListItem::new(Line::from(spans)));
                         }
                         index_map.push(Some(entry.filtered_idx));
                     }
@@ -357,3 +531,7 @@ fn render_hierarchy_items<'a>(
 
     (items, index_map)
 }
+
+#[cfg(test)]
+#[path = "tests/vm_list.rs"]
+mod tests;
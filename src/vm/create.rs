@@ -24,8 +24,15 @@ fn shell_escape(s: &str) -> String {
 }
 
 use crate::commands::qemu_img;
-use crate::vm::qemu_config::{PortForward, PortProtocol};
-use crate::wizard_types::{CreateWizardState, DiskAction, DiskImageFormat, WizardQemuConfig};
+use crate::vm::discovery::DiscoveredVm;
+use crate::vm::qemu_config::{
+    apple_smc_args, has_apple_smc, memory_arg, smp_arg, AudioDevice, CpuConfig, CpuTopology,
+    DiskConfig, DiskThrottle, InputDevices, NetworkBackend, PortForward, PortProtocol, QemuConfig,
+    RtcBase, VgaType, WINDOW_NAME_ARG, WINDOW_TITLE_VAR,
+};
+use crate::wizard_types::{
+    CreateWizardState, DiskAction, DiskImageFormat, OvmfPaths, WizardQemuConfig,
+};
 
 /// Install media type for QEMU command generation
 pub enum InstallMedia<'a> {
@@ -102,13 +109,14 @@ fn generate_serial() -> String {
 /// (raw vs qcow2); mixing them produces a VM that either won't boot or fails
 /// to expose Secure Boot / TPM 2.0 correctly. Selecting them as a pair (rather
 /// than via two independent searches) guarantees they always match.
-struct OvmfFirmware {
+#[derive(Debug)]
+pub(crate) struct OvmfFirmware {
     /// Read-only OVMF_CODE path.
-    code: String,
+    pub code: String,
     /// OVMF_VARS template to copy into the VM directory.
-    vars_template: String,
+    pub vars_template: String,
     /// On-disk image format for the QEMU `-drive ...,format=` flag.
-    format: &'static str,
+    pub format: &'static str,
 }
 
 /// Secure Boot OVMF pairs `(code, vars, format)` in priority order.
@@ -266,6 +274,116 @@ fn default_ovmf_firmware() -> OvmfFirmware {
     }
 }
 
+impl OvmfFirmware {
+    /// Name of the VM's writable VARS copy; the extension follows the format
+    pub fn vars_filename(&self) -> &'static str {
+        if self.format == "qcow2" {
+            "OVMF_VARS.qcow2"
+        } else {
+            "OVMF_VARS.fd"
+        }
+    }
+}
+
+/// Shown when no OVMF firmware is installed
+const OVMF_INSTALL_HINT: &str = "install edk2-ovmf (Fedora, Arch), ovmf (Debian, Ubuntu) or \
+     qemu-ovmf-x86_64 (openSUSE), or set ovmf_code_path and ovmf_vars_path in config.toml";
+
+/// Whether an OVMF_CODE image is a Secure Boot (SMM) build, going by the
+/// names distributions give them (`OVMF_CODE.secboot.fd`, `OVMF_CODE_4M.ms.fd`)
+fn is_secure_boot_code(code: &Path) -> bool {
+    code.file_name()
+        .map(|n| n.to_string_lossy().to_lowercase())
+        .is_some_and(|n| n.contains("secboot") || n.contains(".ms."))
+}
+
+/// The firmware pair a UEFI VM boots with: the config.toml override when set,
+/// otherwise the first pair found on disk. The override has to be the same
+/// kind of build (Secure Boot or not) that the tables would have picked.
+pub(crate) fn resolve_ovmf_firmware(
+    ovmf_paths: Option<&OvmfPaths>,
+    secboot: bool,
+) -> Result<OvmfFirmware> {
+    if let Some(paths) = ovmf_paths {
+        for path in [&paths.code, &paths.vars_template] {
+            if !path.exists() {
+                bail!(
+                    "OVMF firmware set in config.toml not found: {}",
+                    path.display()
+                );
+            }
+        }
+        match (secboot, is_secure_boot_code(&paths.code)) {
+            (true, false) => bail!(
+                "OVMF firmware set in config.toml is not a Secure Boot build: {}. \
+                 Point ovmf_code_path and ovmf_vars_path at a secboot pair, or turn Secure Boot off.",
+                paths.code.display()
+            ),
+            (false, true) => bail!(
+                "OVMF firmware set in config.toml is a Secure Boot build, which needs Secure Boot on: {}",
+                paths.code.display()
+            ),
+            _ => {}
+        }
+        let format = if paths.code.extension().is_some_and(|e| e == "qcow2") {
+            "qcow2"
+        } else {
+            "raw"
+        };
+        return Ok(OvmfFirmware {
+            code: paths.code.display().to_string(),
+            vars_template: paths.vars_template.display().to_string(),
+            format,
+        });
+    }
+    find_ovmf_firmware(secboot).with_context(|| {
+        let kind = if secboot { "Secure Boot " } else { "" };
+        format!(
+            "No {}UEFI firmware (OVMF) found: {}",
+            kind, OVMF_INSTALL_HINT
+        )
+    })
+}
+
+/// Whether a VM boots with Secure Boot: asked for, or implied by a TPM (as
+/// Windows 11 needs both). Only UEFI VMs can.
+fn needs_secure_boot(config: &WizardQemuConfig, os_profile: Option<&str>) -> bool {
+    let needs_uefi = config.uefi || is_windows_11(os_profile);
+    let needs_tpm = config.tpm || is_windows_11(os_profile);
+    needs_uefi && (config.secure_boot || needs_tpm)
+}
+
+/// Firmware for a VM that boots UEFI, or `None` for a BIOS VM. Errors with an
+/// install hint when UEFI is needed but no firmware is available.
+pub(crate) fn uefi_firmware(
+    config: &WizardQemuConfig,
+    os_profile: Option<&str>,
+) -> Result<Option<OvmfFirmware>> {
+    let needs_uefi = config.uefi || is_windows_11(os_profile);
+    if !needs_uefi {
+        return Ok(None);
+    }
+    let needs_secboot = needs_secure_boot(config, os_profile);
+    resolve_ovmf_firmware(config.ovmf_paths.as_ref(), needs_secboot).map(Some)
+}
+
+/// Give a UEFI VM its own NVRAM: a copy of the VARS template in its folder.
+/// An existing copy is kept, since it holds the guest's boot entries.
+pub(crate) fn copy_ovmf_vars(vm_dir: &Path, firmware: &OvmfFirmware) -> Result<()> {
+    let dest = vm_dir.join(firmware.vars_filename());
+    if dest.exists() {
+        return Ok(());
+    }
+    fs::copy(&firmware.vars_template, &dest).with_context(|| {
+        format!(
+            "Failed to copy UEFI variables from {} to {}",
+            firmware.vars_template,
+            dest.display()
+        )
+    })?;
+    Ok(())
+}
+
 /// Select a matched OVMF CODE+VARS firmware pair, preferring 4M builds.
 ///
 /// Both the CODE and VARS file of a candidate pair must exist on disk before it
@@ -304,14 +422,16 @@ pub struct CreatedVm {
 /// Create a new VM from wizard state
 #[allow(dead_code)]
 pub fn create_vm(library_path: &Path, state: &CreateWizardState) -> Result<CreatedVm> {
-    create_vm_with_disk_format(library_path, state, DiskImageFormat::Qcow2)
+    create_vm_with_disk_format(library_path, state, DiskImageFormat::Qcow2, |_, _| {})
 }
 
 /// Create a new VM from wizard state using the requested new-disk format.
+/// `on_progress` gets a label and percent while an existing disk is copied.
 pub fn create_vm_with_disk_format(
     library_path: &Path,
     state: &CreateWizardState,
     new_disk_format: DiskImageFormat,
+    mut on_progress: impl FnMut(&str, f64),
 ) -> Result<CreatedVm> {
     // Validate inputs
     if state.vm_name.trim().is_empty() {
@@ -337,8 +457,14 @@ pub fn create_vm_with_disk_format(
         None
     };
 
+    // Fail before creating anything if a UEFI VM has no firmware to boot
+    let firmware = uefi_firmware(&state.qemu_config, state.selected_os.as_deref())?;
+
     // Create VM directory
     let vm_dir = create_vm_directory(library_path, &state.folder_name)?;
+    if let Some(ref firmware) = firmware {
+        copy_ovmf_vars(&vm_dir, firmware)?;
+    }
 
     // Create or copy/move disk image
     let disk_format = existing_disk_path
@@ -351,6 +477,7 @@ pub fn create_vm_with_disk_format(
             &disk_filename,
             existing_disk_path,
             &state.existing_disk_action,
+            |percent| on_progress("Copying disk image", percent),
         )?
     } else {
         create_disk_image_with_format(&vm_dir, &disk_filename, state.disk_size_gb, disk_format)?
@@ -358,6 +485,10 @@ pub fn create_vm_with_disk_format(
 
     // Copy BIOS/ROM file to VM directory if provided
     let mut qemu_config = state.qemu_config.clone();
+    // VNC translates keysyms with the keymap, so follow the host's layout
+    if qemu_config.keymap.is_none() && qemu_config.display == "vnc" {
+        qemu_config.keymap = super::keymap::host_keymap().map(str::to_string);
+    }
     if let Some(ref rom_path) = state.bios_rom_path {
         let rom_filename = rom_path
             .file_name()
@@ -410,30 +541,19 @@ fn handle_existing_disk(
     filename: &str,
     source: &Path,
     action: &DiskAction,
+    on_progress: impl FnMut(f64),
 ) -> Result<PathBuf> {
     let dest = vm_dir.join(filename);
 
     match action {
         DiskAction::Copy => {
-            fs::copy(source, &dest).with_context(|| {
-                format!(
-                    "Failed to copy disk from {} to {}",
-                    source.display(),
-                    dest.display()
-                )
-            })?;
+            crate::fs::copy_with_progress(source, &dest, on_progress)?;
         }
         DiskAction::Move => {
             // Try rename first (works if on same filesystem)
             if fs::rename(source, &dest).is_err() {
                 // Rename failed (likely different filesystem), fall back to copy+delete
-                fs::copy(source, &dest).with_context(|| {
-                    format!(
-                        "Failed to copy disk from {} to {}",
-                        source.display(),
-                        dest.display()
-                    )
-                })?;
+                crate::fs::copy_with_progress(source, &dest, on_progress)?;
                 fs::remove_file(source).with_context(|| {
                     format!(
                         "Failed to remove original disk after copying: {}",
@@ -467,6 +587,17 @@ pub fn write_vm_metadata(
         content.push_str(&format!("os_profile = \"{}\"\n", profile));
     }
 
+    // Keep per-VM settings written by `set_vm_metadata_value`
+    if let Ok(existing) = fs::read_to_string(&metadata_path) {
+        let lines: Vec<&str> = existing.lines().collect();
+        for (idx, key) in crate::vm::discovery::metadata_key_lines(&existing) {
+            if !matches!(key.as_str(), "display_name" | "os_profile" | "notes") {
+                content.push_str(lines[idx].trim());
+                content.push('\n');
+            }
+        }
+    }
+
     if let Some(notes_text) = notes {
         if notes_text.contains('\n') {
             // Multi-line: use TOML literal string
@@ -485,6 +616,31 @@ pub fn write_vm_metadata(
     Ok(())
 }
 
+/// Set (or with `None`, remove) a single per-VM setting in vm-curator.toml.
+/// `value` is written verbatim, so strings must carry their own quotes.
+pub fn set_vm_metadata_value(vm_dir: &Path, key: &str, value: Option<&str>) -> Result<()> {
+    let metadata_path = vm_dir.join("vm-curator.toml");
+    let existing = fs::read_to_string(&metadata_path)
+        .unwrap_or_else(|_| "# VM Curator metadata\n\n".to_string());
+
+    let skip: Option<usize> = crate::vm::discovery::metadata_key_lines(&existing)
+        .into_iter()
+        .find(|(_, k)| k == key)
+        .map(|(idx, _)| idx);
+    let mut content: String = existing
+        .lines()
+        .enumerate()
+        .filter(|(i, _)| Some(*i) != skip)
+        .map(|(_, l)| format!("{}\n", l))
+        .collect();
+    if let Some(value) = value {
+        content.push_str(&format!("{} = {}\n", key, value));
+    }
+
+    fs::write(&metadata_path, content)
+        .with_context(|| format!("Failed to write VM metadata: {}", metadata_path.display()))
+}
+
 /// Create the VM directory
 pub fn create_vm_directory(library_path: &Path, folder_name: &str) -> Result<PathBuf> {
     let vm_dir = library_path.join(folder_name);
@@ -536,12 +692,14 @@ fn is_windows_11(os_profile: Option<&str>) -> bool {
     matches!(os_profile, Some("windows-11"))
 }
 
-/// Check if an OS profile is an Intel (x86_64) macOS
-fn is_intel_macos(os_profile: Option<&str>, emulator: &str) -> bool {
-    if !emulator.contains("x86_64") {
+/// Check if a VM is an Intel (x86_64) macOS: its profile asks for the Apple
+/// SMC, or it is one of the versioned macOS profiles
+fn is_intel_macos(config: &WizardQemuConfig, os_profile: Option<&str>) -> bool {
+    if !config.emulator.contains("x86_64") {
         return false;
     }
-    os_profile.is_some_and(|p| p.starts_with("macos-") || p.starts_with("mac-osx-"))
+    config.apple_smc
+        || os_profile.is_some_and(|p| p.starts_with("macos-") || p.starts_with("mac-osx-"))
 }
 
 /// Check if an OS profile is a modern macOS that requires OpenCore
@@ -653,21 +811,17 @@ trap cleanup EXIT
 /// Generate OVMF variables setup for UEFI.
 ///
 /// Copies the VARS template from the same firmware pair the QEMU command uses
-/// (via [`find_ovmf_firmware`]) so CODE and VARS always match in size/format.
+/// (via [`resolve_ovmf_firmware`]) so CODE and VARS always match in size/format.
 /// The writable copy's extension mirrors the firmware format (`.qcow2` vs
 /// `.fd`) and the QEMU `-drive ...,format=` flag is derived from the same pair.
-fn generate_ovmf_vars_setup(needs_secboot: bool) -> String {
-    let firmware = find_ovmf_firmware(needs_secboot).unwrap_or_else(default_ovmf_firmware);
-    let vars_ext = if firmware.format == "qcow2" {
-        "qcow2"
-    } else {
-        "fd"
-    };
+fn generate_ovmf_vars_setup(config: &WizardQemuConfig, needs_secboot: bool) -> String {
+    let firmware = resolve_ovmf_firmware(config.ovmf_paths.as_ref(), needs_secboot)
+        .unwrap_or_else(|_| default_ovmf_firmware());
 
     format!(
         r#"# UEFI variables (writable copy per VM)
 OVMF_VARS_TEMPLATE="{template}"
-OVMF_VARS="$VM_DIR/OVMF_VARS.{ext}"
+OVMF_VARS="$VM_DIR/{vars_file}"
 
 # Create a writable copy of OVMF_VARS if it doesn't exist
 if [[ ! -f "$OVMF_VARS" ]]; then
@@ -682,7 +836,7 @@ fi
 
 "#,
         template = firmware.vars_template,
-        ext = vars_ext
+        vars_file = firmware.vars_filename()
     )
 }
 
@@ -699,7 +853,7 @@ pub fn generate_launch_script_with_os(
     let mut script = String::new();
 
     let is_windows = is_windows_10_or_11(os_profile);
-    let is_intel_macos_vm = is_intel_macos(os_profile, &config.emulator);
+    let is_intel_macos_vm = is_intel_macos(config, os_profile);
     let needs_tpm = config.tpm || is_windows_11(os_profile);
     let needs_uefi = config.uefi || is_windows_11(os_profile);
 
@@ -719,13 +873,14 @@ pub fn generate_launch_script_with_os(
     if needs_tpm {
         script.push_str("# TPM 2.0 enabled (requires swtpm package)\n");
     }
-    if needs_tpm && needs_uefi {
+    if needs_secure_boot(config, os_profile) {
         script.push_str("# Secure Boot enabled (OVMF secboot + SMM)\n");
     }
     script.push_str("# Generated by vm-curator\n\n");
 
     // Variables
     script.push_str("VM_DIR=\"$(dirname \"$(readlink -f \"$0\")\")\"\n");
+    script.push_str(&format!("{}={}\n", WINDOW_TITLE_VAR, shell_escape(vm_name)));
     script.push_str(&format!("DISK=\"$VM_DIR/{}\"\n", disk_filename));
 
     if is_recovery_image {
@@ -791,7 +946,10 @@ fi
 
     // UEFI setup with writable OVMF_VARS
     if needs_uefi {
-        script.push_str(&generate_ovmf_vars_setup(needs_tpm));
+        script.push_str(&generate_ovmf_vars_setup(
+            config,
+            needs_secure_boot(config, os_profile),
+        ));
     }
 
     // TPM functions
@@ -976,7 +1134,25 @@ pub(crate) const SPICE_AGENT_ARGS: &[&str] = &[
     "-device virtserialport,chardev=spicechannel0,name=com.redhat.spice.0",
 ];
 
+/// QEMU guest agent channel. The agent (`qemu-guest-agent` in the guest) answers
+/// on `qga.sock` in the VM directory, which `lifecycle::guest_agent_ping` uses to
+/// tell when the guest is actually up. Gets its own virtio-serial bus so it
+/// doesn't depend on the SPICE agent channel being present.
+pub(crate) const GUEST_AGENT_ARGS: &[&str] = &[
+    "-device virtio-serial-pci,id=qga-serial",
+    "-chardev socket,path=\"$VM_DIR/qga.sock\",server=on,wait=off,id=qga0",
+    "-device virtserialport,bus=qga-serial.0,chardev=qga0,name=org.qemu.guest_agent.0",
+];
+
+/// Memory balloon device. Its presence on a QEMU command line is what the
+/// status monitor looks for before polling `query-balloon`.
+pub(crate) const BALLOON_DEVICE: &str = "virtio-balloon-pci,id=balloon0";
+
 fn disk_format_for_filename(disk_filename: &str) -> &'static str {
+    // Imported VMware/VirtualBox/Hyper-V disks may be kept as-is
+    if let Some(format) = super::import::foreign_format_from_extension(Path::new(disk_filename)) {
+        return format;
+    }
     DiskImageFormat::from_path(Path::new(disk_filename))
         .unwrap_or(DiskImageFormat::Qcow2)
         .as_str()
@@ -993,13 +1169,16 @@ fn build_qemu_command_with_os(
     let mut args: Vec<String> = Vec::new();
 
     let is_windows = is_windows_10_or_11(os_profile);
-    let is_intel_macos_vm = is_intel_macos(os_profile, &config.emulator);
+    let is_intel_macos_vm = is_intel_macos(config, os_profile);
     let needs_tpm = config.tpm || is_windows_11(os_profile);
     let needs_uefi = config.uefi || is_windows_11(os_profile);
 
     // Emulator
     args.push(config.emulator.clone());
 
+    // Window title and process name
+    args.push(WINDOW_NAME_ARG.to_string());
+
     // KVM acceleration
     if config.enable_kvm {
         args.push("-enable-kvm".to_string());
@@ -1013,7 +1192,7 @@ fn build_qemu_command_with_os(
     // Machine type (escaped to prevent injection)
     if let Some(ref machine) = config.machine {
         let safe_machine = shell_escape(machine);
-        let needs_secboot = needs_tpm && needs_uefi;
+        let needs_secboot = needs_secure_boot(config, os_profile);
         let mut machine_opts = vec![safe_machine.to_string()];
         if config.enable_kvm {
             machine_opts.push("accel=kvm".to_string());
@@ -1024,19 +1203,22 @@ fn build_qemu_command_with_os(
         args.push(format!("-machine {}", machine_opts.join(",")));
     }
 
-    // CPU (escaped to prevent injection)
-    if let Some(ref cpu_model) = config.cpu_model {
+    // CPU (escaped to prevent injection). Nested virtualization passes the
+    // vmx/svm flag chosen in the wizard through, which forces a host CPU
+    // model and so only applies with KVM.
+    if let Some(virt_flag) = config.nested_virt.as_deref().filter(|_| config.enable_kvm) {
+        let mut cpu = CpuConfig::parse(config.cpu_model.as_deref().unwrap_or("host"));
+        cpu.enable_nested(virt_flag);
+        args.push(format!("-cpu {}", shell_escape(&cpu.to_arg())));
+    } else if let Some(ref cpu_model) = config.cpu_model {
         args.push(format!("-cpu {}", shell_escape(cpu_model)));
     }
 
-    // SMP (CPU cores)
-    args.push(format!(
-        "-smp {},sockets=1,cores={},threads=1",
-        config.cpu_cores, config.cpu_cores
-    ));
+    // SMP (CPU cores, optionally split into sockets/cores/threads)
+    args.push(smp_arg(config.cpu_cores, config.cpu_topology.as_ref()));
 
     // Memory
-    args.push(format!("-m {}M", config.memory_mb));
+    args.push(memory_arg(config.memory_mb, config.max_memory_mb));
 
     // SMBIOS options for Windows (reference the variable defined in script)
     if is_windows {
@@ -1045,17 +1227,17 @@ fn build_qemu_command_with_os(
 
     // Apple SMC and SMBIOS for Intel macOS
     if is_intel_macos_vm {
-        args.push("-device \"isa-applesmc,osk=ourhardworkbythesewordsguardedpleasedontsteal(c)AppleComputerInc\"".to_string());
-        args.push("-smbios type=2".to_string());
+        args.extend(apple_smc_args());
     }
 
     // UEFI boot with writable OVMF_VARS. The CODE path and format come from the
     // same firmware pair that `generate_ovmf_vars_setup` copies VARS from (both
-    // call `find_ovmf_firmware` with the same flag), so CODE and VARS always
+    // call `resolve_ovmf_firmware` with the same flag), so CODE and VARS always
     // agree in size and on-disk format (raw vs qcow2).
     if needs_uefi {
-        let needs_secboot = needs_tpm;
-        let firmware = find_ovmf_firmware(needs_secboot).unwrap_or_else(default_ovmf_firmware);
+        let needs_secboot = needs_secure_boot(config, os_profile);
+        let firmware = resolve_ovmf_firmware(config.ovmf_paths.as_ref(), needs_secboot)
+            .unwrap_or_else(|_| default_ovmf_firmware());
         // OVMF_CODE is read-only
         args.push(format!(
             "-drive if=pflash,format={},readonly=on,file={}",
@@ -1233,23 +1415,37 @@ fn build_qemu_command_with_os(
     } else {
         args.push(format!("-display {}", shell_escape(&config.display)));
     }
+    if let Some(keymap) = &config.keymap {
+        args.push(format!("-k {}", shell_escape(keymap)));
+    }
 
     // Audio backend (must be declared before devices that use it)
     if !config.audio.is_empty() {
-        if config.display == "spice-app" {
-            args.push("-audiodev spice,id=audio0".to_string());
-        } else {
-            args.push("-audiodev pa,id=audio0".to_string());
-        }
+        args.push(format!(
+            "-audiodev {},id=audio0",
+            shell_escape(&config.audio_backend)
+        ));
     }
 
     // SPICE guest-agent channel for clipboard sharing (needs spice-vdagent in the guest)
-    if config.display == "spice-app" {
+    if config.display == "spice-app" && config.clipboard_sharing {
         for a in SPICE_AGENT_ARGS {
             args.push((*a).to_string());
         }
     }
 
+    // QEMU guest agent channel (opt-in; needs qemu-guest-agent in the guest)
+    if config.guest_agent {
+        for a in GUEST_AGENT_ARGS {
+            args.push((*a).to_string());
+        }
+    }
+
+    // Memory balloon (opt-in; lets the host shrink or regrow guest RAM via QMP)
+    if config.balloon {
+        args.push(format!("-device {}", BALLOON_DEVICE));
+    }
+
     // Audio devices (known safe values from profiles, but escape for safety)
     for audio in &config.audio {
         match audio.as_str() {
@@ -1318,19 +1514,18 @@ fn build_qemu_command_with_os(
         }
     }
 
-    // USB tablet for mouse (+ keyboard for macOS)
-    if config.usb_tablet {
+    // Keyboard and pointer (+ USB keyboard for macOS, which has no PS/2)
+    let input_args = config.input.to_args();
+    if config.input == InputDevices::UsbTablet && is_intel_macos_vm {
         args.push("-usb".to_string());
-        if is_intel_macos_vm {
-            args.push("-device usb-kbd".to_string());
-        }
-        args.push("-device usb-tablet".to_string());
+        args.push("-device usb-kbd".to_string());
+        args.extend(input_args.into_iter().filter(|a| a != "-usb"));
+    } else {
+        args.extend(input_args);
     }
 
-    // RTC local time (for Windows)
-    if config.rtc_localtime {
-        args.push("-rtc base=localtime".to_string());
-    }
+    // RTC base and clock (local time for Windows)
+    args.extend(config.rtc_config().to_arg());
 
     // TPM 2.0 (if enabled, uses socket set up by start_tpm function)
     if needs_tpm {
@@ -1371,6 +1566,332 @@ pub fn write_launch_script(vm_dir: &Path, content: &str) -> Result<PathBuf> {
     Ok(script_path)
 }
 
+/// Where "Regenerate Script" keeps the script it replaced
+pub const LAUNCH_SCRIPT_BACKUP: &str = "launch.sh.bak";
+
+/// Re-render `vm`'s launch.sh from its parsed configuration through the same
+/// generator the wizard uses.
+///
+/// The disk, install media and SMBIOS identity lines and every vm-curator
+/// managed section (passthrough, shared folders, boot order, tools ISO) are
+/// carried over from the current script. Other hand edits are dropped.
+pub fn regenerate_launch_script(
+    vm: &DiscoveredVm,
+    ovmf_paths: Option<OvmfPaths>,
+) -> Result<String> {
+    let old = fs::read_to_string(&vm.launch_script).context("Failed to read launch.sh")?;
+    let parsed = crate::vm::launch_parser::parse_launch_script(&vm.launch_script, &old)?;
+    let mut config = wizard_config_from_parsed(&parsed);
+    config.ovmf_paths = ovmf_paths;
+    // Options the wizard doesn't model (custom devices, a serial console)
+    // go back in as written
+    config.extra_args = crate::vm::launch_parser::carried_over_args(&old);
+    uefi_firmware(&config, vm.os_profile.as_deref())?;
+
+    let disk_line = match script_variable_line(&old, "DISK") {
+        Some(line) => line.to_string(),
+        None => {
+            let disk = parsed
+                .disks
+                .iter()
+                .find(|d| is_guest_disk(d, &parsed))
+                .context("No disk image found in launch.sh; edit the script by hand instead")?;
+            match disk.path.strip_prefix(&vm.path) {
+                Ok(rel) => format!("DISK=\"$VM_DIR/{}\"", rel.display()),
+                Err(_) => format!("DISK={}", shell_escape(&disk.path.display().to_string())),
+            }
+        }
+    };
+    let disk_filename = disk_line
+        .trim_end_matches('"')
+        .rsplit('/')
+        .next()
+        .unwrap_or("disk.qcow2")
+        .to_string();
+    if let Some(disk) = parsed
+        .disks
+        .iter()
+        .find(|d| d.path.file_name().and_then(|n| n.to_str()) == Some(disk_filename.as_str()))
+    {
+        config.disk_interface = disk.interface.clone();
+    }
+
+    let is_recovery = script_variable_line(&old, "RECOVERY_IMG").is_some();
+    let floppy = script_variable_line(&old, "FLOPPY")
+        .and_then(|line| line.split_once('='))
+        .map(|(_, value)| PathBuf::from(value.trim_matches(|c| c == '"' || c == '\'')));
+
+    let mut content = generate_launch_script_with_os(
+        &vm.display_name(),
+        &disk_filename,
+        None,
+        is_recovery,
+        &config,
+        vm.os_profile.as_deref(),
+        floppy.as_deref(),
+    );
+
+    let generated_disk_line = format!("DISK=\"$VM_DIR/{}\"", disk_filename);
+    content = content.replacen(&generated_disk_line, &disk_line, 1);
+    for var in ["ISO", "RECOVERY_IMG", "FLOPPY", WINDOW_TITLE_VAR] {
+        if let (Some(new_line), Some(old_line)) = (
+            script_variable_line(&content, var),
+            script_variable_line(&old, var),
+        ) {
+            content = content.replacen(new_line, old_line, 1);
+        }
+    }
+    if let (Some(new_block), Some(old_block)) = (smbios_block(&content), smbios_block(&old)) {
+        content = content.replacen(&new_block, &old_block, 1);
+    }
+    // Disk IO limits live in the -drive options the generator rewrites
+    for disk in &parsed.disks {
+        if disk.throttle != DiskThrottle::default() {
+            if let Ok(throttled) = crate::vm::lifecycle::apply_disk_throttle(
+                &content,
+                &vm.path,
+                &disk.path,
+                &disk.throttle,
+            ) {
+                content = throttled;
+            }
+        }
+    }
+
+    Ok(crate::vm::lifecycle::carry_over_managed_sections(
+        &old, &content,
+    ))
+}
+
+/// Replace `vm`'s launch.sh with `content`, keeping the previous script as
+/// [`LAUNCH_SCRIPT_BACKUP`].
+pub fn replace_launch_script(vm: &DiscoveredVm, content: &str) -> Result<PathBuf> {
+    let backup = vm.path.join(LAUNCH_SCRIPT_BACKUP);
+    fs::copy(&vm.launch_script, &backup)
+        .with_context(|| format!("Failed to back up launch.sh to {}", backup.display()))?;
+    write_launch_script(&vm.path, content)?;
+    Ok(backup)
+}
+
+/// Wizard settings equivalent to a parsed launch.sh. Options the parser does
+/// not model (display, audio backend, topology, guest channels) are read
+/// back from the raw script.
+pub fn wizard_config_from_parsed(config: &QemuConfig) -> WizardQemuConfig {
+    let raw = &config.raw_script;
+    let emulator = config.emulator.command().to_string();
+    let display = script_arg(raw, "-display")
+        .and_then(|v| v.split(',').next().map(str::to_string))
+        .unwrap_or_else(|| "gtk".to_string());
+    let audio_backend = script_arg(raw, "-audiodev")
+        .and_then(|v| v.split(',').next().map(str::to_string))
+        .unwrap_or_else(|| WizardQemuConfig::default_audio_backend(&emulator, &display));
+
+    let vga = if raw.contains("virtio-vga-gl") {
+        "virtio".to_string()
+    } else {
+        match &config.vga {
+            VgaType::Std => "std".to_string(),
+            VgaType::Cirrus => "cirrus".to_string(),
+            VgaType::Vmware => "vmware".to_string(),
+            VgaType::Qxl => "qxl".to_string(),
+            VgaType::Virtio => "virtio".to_string(),
+            VgaType::None => "none".to_string(),
+            VgaType::Other(other) => other.clone(),
+        }
+    };
+
+    let mut audio = Vec::new();
+    for device in &config.audio_devices {
+        match device {
+            AudioDevice::Hda => {
+                audio.push("intel-hda".to_string());
+                audio.push("hda-duplex".to_string());
+            }
+            AudioDevice::Ac97 => audio.push("ac97".to_string()),
+            AudioDevice::Sb16 => audio.push("sb16".to_string()),
+            AudioDevice::Es1370 => audio.push("es1370".to_string()),
+            AudioDevice::PcSpk => {}
+            AudioDevice::Other(other) => audio.push(other.clone()),
+        }
+    }
+    // mac99's built-in Screamer only shows up as the -audiodev backend
+    if audio.is_empty() && config.machine.as_deref() == Some("mac99") && raw.contains("-audiodev") {
+        audio.push("screamer".to_string());
+    }
+
+    let (network_model, network_backend) = match &config.network {
+        None => ("none".to_string(), "none".to_string()),
+        Some(net) => {
+            let model = match net.model.as_str() {
+                "virtio-net" | "virtio-net-pci" => "virtio".to_string(),
+                other => other.to_string(),
+            };
+            let backend = match net.backend {
+                NetworkBackend::User => "user",
+                NetworkBackend::Passt => "passt",
+                NetworkBackend::Bridge(_) => "bridge",
+                NetworkBackend::None => "none",
+            };
+            (model, backend.to_string())
+        }
+    };
+    let bridge_name = config.network.as_ref().and_then(|net| match &net.backend {
+        NetworkBackend::Bridge(name) => Some(name.clone()),
+        _ => net.bridge.clone(),
+    });
+
+    let memory = script_arg(raw, "-m").unwrap_or_default();
+    let max_memory_mb = memory
+        .split(',')
+        .find_map(|opt| opt.strip_prefix("maxmem="))
+        .and_then(parse_size_mb);
+
+    let smp = script_arg(raw, "-smp").unwrap_or_default();
+    let smp_value = |key: &str| -> Option<u32> {
+        smp.split(',')
+            .find_map(|opt| opt.strip_prefix(key)?.strip_prefix('='))
+            .and_then(|v| v.parse().ok())
+    };
+    let cpu_topology = match (
+        smp_value("sockets"),
+        smp_value("cores"),
+        smp_value("threads"),
+    ) {
+        (Some(sockets), Some(cores), Some(threads)) => Some(CpuTopology {
+            sockets,
+            cores,
+            threads,
+        })
+        .filter(|t| (t.sockets > 1 || t.threads > 1) && t.total() == config.cpu_cores),
+        _ => None,
+    };
+
+    WizardQemuConfig {
+        memory_mb: config.memory_mb,
+        max_memory_mb,
+        cpu_cores: config.cpu_cores,
+        cpu_topology,
+        cpu_model: config.cpu_model.clone(),
+        machine: config.machine.clone(),
+        vga,
+        audio,
+        audio_backend,
+        network_model,
+        disk_interface: config
+            .disks
+            .iter()
+            .find(|d| is_guest_disk(d, config))
+            .map(|d| d.interface.clone())
+            .unwrap_or_else(|| "ide".to_string()),
+        enable_kvm: config.enable_kvm,
+        gl_acceleration: config.has_gl_acceleration(),
+        uefi: config.uefi,
+        tpm: config.tpm,
+        secure_boot: config.secure_boot,
+        rtc_localtime: config.rtc.base == RtcBase::Localtime,
+        rtc: config.rtc.clone(),
+        input: InputDevices::from_script(raw),
+        apple_smc: has_apple_smc(raw),
+        guest_agent: config.has_guest_agent(),
+        balloon: config.has_balloon(),
+        clipboard_sharing: config.has_spice_agent(),
+        nested_virt: config
+            .cpu_model
+            .as_deref()
+            .and_then(|cpu| CpuConfig::parse(cpu).nested_flag())
+            .map(str::to_string),
+        display,
+        keymap: script_arg(raw, "-k"),
+        network_backend,
+        port_forwards: config
+            .network
+            .as_ref()
+            .map(|net| net.port_forwards.clone())
+            .unwrap_or_default(),
+        bridge_name,
+        mac_address: config
+            .network
+            .as_ref()
+            .and_then(|net| net.mac_address.clone()),
+        extra_args: Vec::new(),
+        bios_path: config.bios_path.clone(),
+        ovmf_paths: None,
+        emulator,
+    }
+}
+
+/// Wizard settings copied from another VM for a new one. Everything but
+/// the MAC address carries over, since two VMs sharing one would clash on
+/// the same network.
+pub fn wizard_config_template(config: &QemuConfig) -> WizardQemuConfig {
+    WizardQemuConfig {
+        mac_address: None,
+        ..wizard_config_from_parsed(config)
+    }
+}
+
+/// Whether a parsed drive is the guest's own disk rather than firmware,
+/// install media or the ROM.
+pub(crate) fn is_guest_disk(disk: &DiskConfig, config: &QemuConfig) -> bool {
+    let name = disk
+        .path
+        .file_name()
+        .map(|n| n.to_string_lossy().to_lowercase())
+        .unwrap_or_default();
+    let is_media = [".iso", ".dmg", ".fd"]
+        .iter()
+        .any(|ext| name.ends_with(ext));
+    !is_media
+        && !name.contains("ovmf")
+        && !name.contains("vars")
+        && config.bios_path.as_deref() != Some(disk.path.as_path())
+}
+
+/// Value of the first uncommented `flag <value>` argument in a script.
+fn script_arg(script: &str, flag: &str) -> Option<String> {
+    let pattern = format!("{} ", flag);
+    script
+        .lines()
+        .filter(|line| !line.trim_start().starts_with('#'))
+        .find_map(|line| {
+            let idx = line
+                .match_indices(&pattern)
+                .map(|(i, _)| i)
+                .find(|&i| i == 0 || line[..i].ends_with(char::is_whitespace))?;
+            let value: String = line[idx + pattern.len()..]
+                .trim_start()
+                .chars()
+                .take_while(|c| !c.is_whitespace() && *c != '\\')
+                .collect();
+            let value = value.trim_matches('\'').trim_matches('"').to_string();
+            (!value.is_empty()).then_some(value)
+        })
+}
+
+/// Parse a QEMU size such as `8192M` or `8G` into megabytes.
+fn parse_size_mb(size: &str) -> Option<u32> {
+    let digits: String = size.chars().take_while(|c| c.is_ascii_digit()).collect();
+    let value: u32 = digits.parse().ok()?;
+    match size[digits.len()..].to_ascii_uppercase().as_str() {
+        "G" => Some(value * 1024),
+        "" | "M" => Some(value),
+        _ => None,
+    }
+}
+
+/// The top-level `NAME=...` assignment line of a script, if any.
+fn script_variable_line<'a>(script: &'a str, name: &str) -> Option<&'a str> {
+    let prefix = format!("{}=", name);
+    script.lines().find(|line| line.starts_with(&prefix))
+}
+
+/// The `SMBIOS_OPTS=( ... )` block of a script, if any.
+fn smbios_block(script: &str) -> Option<String> {
+    let start = script.find("SMBIOS_OPTS=(")?;
+    let end = script[start..].find("\n)")? + start + 2;
+    Some(script[start..end].to_string())
+}
+
 /// Update network arguments in an existing launch.sh script
 pub fn update_network_in_script(
     vm_path: &Path,
@@ -1,8 +1,9 @@
 use anyhow::{Context, Result};
 use log::warn;
+use std::collections::HashMap;
 use std::path::{Path, PathBuf};
 
-use super::launch_parser::parse_launch_script;
+use super::launch_parser::{parse_launch_script, unrecognized_args};
 use super::qemu_config::QemuConfig;
 
 /// A discovered VM in the library
@@ -22,6 +23,29 @@ pub struct DiscoveredVm {
     pub os_profile: Option<String>,
     /// User notes from vm-curator.toml (if set)
     pub notes: Option<String>,
+    /// QEMU options in launch.sh that the parsed `config` doesn't capture
+    pub unrecognized_args: Vec<String>,
+    /// When the VM was last launched from vm-curator (from vm-curator.toml)
+    pub last_launched: Option<chrono::DateTime<chrono::Local>>,
+    /// How many times the VM has been launched from vm-curator
+    pub launch_count: u32,
+    /// Whether the VM is started when vm-curator opens
+    pub autostart: bool,
+    /// Whether the first boot is done, so normal launches skip the install
+    /// ISO (`None` until the second launch or the user decides)
+    pub first_boot_done: Option<bool>,
+    /// ASCII art from the VM folder, shown instead of the OS logo
+    pub custom_ascii: Option<String>,
+    /// Disk images other VMs in the library also use (see [`mark_shared_disks`])
+    pub shared_disks: Vec<SharedDisk>,
+}
+
+/// A disk image a VM shares with other VMs in the library
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SharedDisk {
+    pub path: PathBuf,
+    /// Ids of the other VMs using it
+    pub vm_ids: Vec<String>,
 }
 
 impl DiscoveredVm {
@@ -32,10 +56,67 @@ impl DiscoveredVm {
         }
         format_os_display_name(&self.id)
     }
+
+    /// e.g. "Last launched 3 days ago (12 launches)" or "Never launched"
+    pub fn launch_summary(&self) -> String {
+        let Some(last) = self.last_launched else {
+            return "Never launched".to_string();
+        };
+        let ago = time_ago(last, chrono::Local::now());
+        match self.launch_count {
+            0 => format!("Last launched {}", ago),
+            1 => format!("Last launched {} (1 launch)", ago),
+            n => format!("Last launched {} ({} launches)", ago, n),
+        }
+    }
+}
+
+/// Whether the VM hasn't been launched from vm-curator in `days` days. A VM
+/// that was never launched counts from its launch.sh's last change, so a
+/// freshly created VM isn't flagged.
+pub fn is_stale(vm: &DiscoveredVm, days: u32, now: chrono::DateTime<chrono::Local>) -> bool {
+    let last_activity = vm.last_launched.or_else(|| {
+        std::fs::metadata(&vm.launch_script)
+            .and_then(|m| m.modified())
+            .ok()
+            .map(chrono::DateTime::<chrono::Local>::from)
+    });
+    last_activity.is_some_and(|t| now - t >= chrono::Duration::days(days.into()))
+}
+
+/// Coarse age of `then` relative to `now`, e.g. "just now", "5 minutes ago",
+/// "3 days ago"
+pub fn time_ago(
+    then: chrono::DateTime<chrono::Local>,
+    now: chrono::DateTime<chrono::Local>,
+) -> String {
+    let secs = (now - then).num_seconds().max(0);
+    let (n, unit) = match secs {
+        0..=59 => return "just now".to_string(),
+        60..=3599 => (secs / 60, "minute"),
+        3600..=86_399 => (secs / 3600, "hour"),
+        86_400..=2_591_999 => (secs / 86_400, "day"),
+        2_592_000..=31_535_999 => (secs / 2_592_000, "month"),
+        _ => (secs / 31_536_000, "year"),
+    };
+    format!("{} {}{} ago", n, unit, if n == 1 { "" } else { "s" })
+}
+
+/// Find a VM whose display name matches `name`, ignoring case and surrounding
+/// whitespace. The VM with id `except_id` (one being renamed) is skipped.
+pub fn find_by_display_name<'a>(
+    vms: &'a [DiscoveredVm],
+    name: &str,
+    except_id: Option<&str>,
+) -> Option<&'a DiscoveredVm> {
+    let name = name.trim().to_lowercase();
+    vms.iter().find(|vm| {
+        Some(vm.id.as_str()) != except_id && vm.display_name().trim().to_lowercase() == name
+    })
 }
 
 /// Format an OS display name with proper naming conventions, trademarks, and publisher names
-fn format_os_display_name(id: &str) -> String {
+pub(crate) fn format_os_display_name(id: &str) -> String {
     let id_lower = id.to_lowercase();
 
     // Check for custom named VMs that should show OS + (custom name)
@@ -497,6 +578,49 @@ fn read_vm_metadata(vm_path: &Path) -> (Option<String>, Option<String>, Option<S
     (display_name, os_profile, notes)
 }
 
+/// Line index and key of every `key = value` line in vm-curator.toml,
+/// skipping comments and the body of a multi-line `notes` string.
+pub(crate) fn metadata_key_lines(content: &str) -> Vec<(usize, String)> {
+    let mut keys = Vec::new();
+    let mut in_multiline = false;
+    for (i, line) in content.lines().enumerate() {
+        if in_multiline {
+            if line.contains("'''") {
+                in_multiline = false;
+            }
+            continue;
+        }
+        let trimmed = line.trim();
+        if trimmed.starts_with('#') {
+            continue;
+        }
+        let Some((key, value)) = trimmed.split_once('=') else {
+            continue;
+        };
+        if let Some(rest) = value.trim().strip_prefix("'''") {
+            in_multiline = !rest.contains("'''");
+        }
+        keys.push((i, key.trim().to_string()));
+    }
+    keys
+}
+
+/// Read a single per-VM setting from vm-curator.toml. String values are
+/// returned without their quotes; other values as written.
+pub fn read_vm_metadata_value(vm_path: &Path, key: &str) -> Option<String> {
+    let content = std::fs::read_to_string(vm_path.join("vm-curator.toml")).ok()?;
+    let lines: Vec<&str> = content.lines().collect();
+    let (idx, _) = metadata_key_lines(&content)
+        .into_iter()
+        .find(|(_, k)| k == key)?;
+    let line = lines[idx].trim();
+    extract_toml_string_value(line).or_else(|| {
+        line.split_once('=')
+            .map(|(_, v)| v.trim().to_string())
+            .filter(|v| !v.is_empty())
+    })
+}
+
 /// Extract a string value from a TOML line like: key = "value"
 fn extract_toml_string_value(line: &str) -> Option<String> {
     let parts: Vec<&str> = line.splitn(2, '=').collect();
@@ -512,6 +636,64 @@ fn extract_toml_string_value(line: &str) -> Option<String> {
     }
 }
 
+/// Files in a VM directory that [`discover_vms`] reads
+const DISCOVERY_FILES: &[&str] = &[
+    "launch.sh",
+    "vm-curator.toml",
+    super::lifecycle::CUSTOM_ASCII_FILE,
+];
+
+/// Whether a change to `changed` can alter what [`discover_vms`] finds in
+/// the library: a VM directory appearing or going away, or one of the files
+/// it reads being written. Disk images and runtime files don't count.
+pub fn affects_discovery(library_path: &Path, changed: &Path) -> bool {
+    let Ok(relative) = changed.strip_prefix(library_path) else {
+        return false;
+    };
+    let parts: Vec<_> = relative.components().collect();
+    match parts.as_slice() {
+        [_vm_dir] => true,
+        [_vm_dir, file] => DISCOVERY_FILES.iter().any(|f| file.as_os_str() == *f),
+        _ => false,
+    }
+}
+
+/// Why the VM library directory can't be used
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LibraryIssue {
+    /// Nothing at the path (fresh install, mistyped `--library`)
+    Missing,
+    /// The path is a file
+    NotADirectory,
+    /// The directory exists but can't be listed
+    Unreadable(String),
+}
+
+impl LibraryIssue {
+    /// One-line explanation mentioning `path`
+    pub fn describe(&self, path: &Path) -> String {
+        match self {
+            Self::Missing => format!("VM library {} does not exist", path.display()),
+            Self::NotADirectory => {
+                format!("VM library {} is a file, not a directory", path.display())
+            }
+            Self::Unreadable(e) => format!("Can't read VM library {}: {}", path.display(), e),
+        }
+    }
+}
+
+/// What stops `library_path` from being used as the VM library, if anything
+pub fn library_issue(library_path: &Path) -> Option<LibraryIssue> {
+    match std::fs::metadata(library_path) {
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Some(LibraryIssue::Missing),
+        Err(e) => Some(LibraryIssue::Unreadable(e.to_string())),
+        Ok(meta) if !meta.is_dir() => Some(LibraryIssue::NotADirectory),
+        Ok(_) => std::fs::read_dir(library_path)
+            .err()
+            .map(|e| LibraryIssue::Unreadable(e.to_string())),
+    }
+}
+
 /// Scan the VM library directory for VMs
 pub fn discover_vms(library_path: &Path) -> Result<Vec<DiscoveredVm>> {
     let mut vms = Vec::new();
@@ -544,6 +726,7 @@ pub fn discover_vms(library_path: &Path) -> Result<Vec<DiscoveredVm>> {
 
         // Try to parse the launch script
         let script_content = std::fs::read_to_string(&launch_script).unwrap_or_default();
+        let unrecognized_args = unrecognized_args(&script_content);
 
         let config = match parse_launch_script(&launch_script, &script_content) {
             Ok(cfg) => cfg,
@@ -562,6 +745,11 @@ pub fn discover_vms(library_path: &Path) -> Result<Vec<DiscoveredVm>> {
 
         // Read vm-curator.toml metadata if it exists
         let (custom_name, os_profile, notes) = read_vm_metadata(&path);
+        let last_launched = super::lifecycle::load_last_launched(&path);
+        let launch_count = super::lifecycle::load_launch_count(&path);
+        let autostart = super::lifecycle::load_autostart(&path);
+        let first_boot_done = super::lifecycle::load_first_boot_done(&path);
+        let custom_ascii = super::lifecycle::load_custom_ascii(&path);
 
         vms.push(DiscoveredVm {
             id,
@@ -571,15 +759,142 @@ pub fn discover_vms(library_path: &Path) -> Result<Vec<DiscoveredVm>> {
             custom_name,
             os_profile,
             notes,
+            unrecognized_args,
+            last_launched,
+            launch_count,
+            autostart,
+            first_boot_done,
+            custom_ascii,
+            shared_disks: Vec::new(),
         });
     }
 
     // Sort by display name
     vms.sort_by_key(|v| v.display_name());
+    mark_shared_disks(&mut vms);
 
     Ok(vms)
 }
 
+/// Deepest backing chain followed, so a loop of overlays can't hang discovery
+const MAX_BACKING_DEPTH: usize = 16;
+
+/// Whether a drive is a disk image rather than firmware or install media,
+/// which QEMU opens read-only and VMs can share safely
+pub(crate) fn is_disk_image(path: &Path) -> bool {
+    let name = path
+        .file_name()
+        .map(|n| n.to_string_lossy().to_lowercase())
+        .unwrap_or_default();
+    let ext = name.rsplit_once('.').map(|(_, ext)| ext).unwrap_or("");
+    !matches!(ext, "iso" | "fd" | "rom" | "bin") && !name.contains("ovmf") && !name.contains("edk2")
+}
+
+/// Backing file of a qcow2 image, read from its header. Relative names are
+/// resolved against the image's directory, as QEMU does.
+pub(crate) fn qcow2_backing_file(path: &Path) -> Option<PathBuf> {
+    use std::io::{Read, Seek, SeekFrom};
+
+    let mut file = std::fs::File::open(path).ok()?;
+    let mut header = [0u8; 20];
+    file.read_exact(&mut header).ok()?;
+    if header[..4] != *b"QFI\xfb" {
+        return None;
+    }
+    let offset = u64::from_be_bytes(header[8..16].try_into().ok()?);
+    let size = u32::from_be_bytes(header[16..20].try_into().ok()?);
+    // The qcow2 spec caps backing file names at 1023 bytes
+    if offset == 0 || size == 0 || size > 1023 {
+        return None;
+    }
+    file.seek(SeekFrom::Start(offset)).ok()?;
+    let mut name = vec![0u8; size as usize];
+    file.read_exact(&mut name).ok()?;
+    let name = String::from_utf8(name).ok()?;
+    // A `json:` block device description doesn't name a local file
+    if name.starts_with("json:") {
+        return None;
+    }
+    let backing = PathBuf::from(name);
+    if backing.is_absolute() {
+        Some(backing)
+    } else {
+        Some(path.parent()?.join(backing))
+    }
+}
+
+/// Flag disk images used by more than one VM, e.g. after a botched clone.
+/// A disk counts as shared when two VMs attach it, or when one VM attaches
+/// an image that another VM's disk is an overlay on. Overlays on a common
+/// base image aren't flagged, since QEMU opens backing files read-only.
+pub fn mark_shared_disks(vms: &mut [DiscoveredVm]) {
+    // Image -> (VM index, whether the VM attaches it rather than backs onto it)
+    let mut users: HashMap<PathBuf, Vec<(usize, bool)>> = HashMap::new();
+    for (i, vm) in vms.iter().enumerate() {
+        for disk in vm.config.disks.iter().filter(|d| is_disk_image(&d.path)) {
+            let mut image = std::fs::canonicalize(&disk.path).unwrap_or_else(|_| disk.path.clone());
+            users.entry(image.clone()).or_default().push((i, true));
+            for _ in 0..MAX_BACKING_DEPTH {
+                let Some(base) = qcow2_backing_file(&image) else {
+                    break;
+                };
+                image = std::fs::canonicalize(&base).unwrap_or(base);
+                users.entry(image.clone()).or_default().push((i, false));
+            }
+        }
+    }
+
+    for vm in vms.iter_mut() {
+        vm.shared_disks.clear();
+    }
+    let mut images: Vec<_> = users.into_iter().collect();
+    images.sort_by(|a, b| a.0.cmp(&b.0));
+    for (path, users) in images {
+        let attaches = |i: usize| users.iter().any(|&(j, direct)| j == i && direct);
+        let mut indices: Vec<usize> = users.iter().map(|&(i, _)| i).collect();
+        indices.sort_unstable();
+        indices.dedup();
+        for &i in &indices {
+            let vm_ids: Vec<String> = indices
+                .iter()
+                .filter(|&&j| j != i && (attaches(i) || attaches(j)))
+                .map(|&j| vms[j].id.clone())
+                .collect();
+            if !vm_ids.is_empty() {
+                vms[i].shared_disks.push(SharedDisk {
+                    path: path.clone(),
+                    vm_ids,
+                });
+            }
+        }
+    }
+}
+
+/// One line per disk `vm` shares, e.g. "/vms/a/disk.qcow2 is also used by
+/// Windows 98"
+pub fn shared_disk_warnings(vm: &DiscoveredVm, vms: &[DiscoveredVm]) -> Vec<String> {
+    vm.shared_disks
+        .iter()
+        .map(|shared| {
+            let names: Vec<String> = shared
+                .vm_ids
+                .iter()
+                .map(|id| {
+                    vms.iter()
+                        .find(|v| &v.id == id)
+                        .map(|v| v.display_name())
+                        .unwrap_or_else(|| id.clone())
+                })
+                .collect();
+            format!(
+                "{} is also used by {}",
+                shared.path.display(),
+                names.join(", ")
+            )
+        })
+        .collect()
+}
+
 /// Group VMs by category (extracted from naming conventions)
 pub fn group_vms_by_category(vms: &[DiscoveredVm]) -> Vec<(&'static str, Vec<&DiscoveredVm>)> {
     let mut windows: Vec<&DiscoveredVm> = Vec::new();
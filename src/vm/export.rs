@@ -0,0 +1,391 @@
+//! VM Export Logic
+//!
+//! Serializes a VM's parsed launch configuration into a libvirt `<domain>` XML
+//! document (the inverse of the libvirt importer in `import.rs`), so VMs can be
+//! defined in virt-manager with `virsh define`.
+
+use anyhow::{Context, Result};
+use std::fmt::Write as _;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use super::discovery::DiscoveredVm;
+use super::qemu_config::{
+    AudioDevice, DiskConfig, DiskFormat, NetworkBackend, QemuConfig, QemuEmulator, VgaType,
+};
+
+/// Write `<vm_dir>/<vm_id>.xml` for the VM and return its path
+pub fn export_libvirt_xml(vm: &DiscoveredVm) -> Result<PathBuf> {
+    let path = vm.path.join(format!("{}.xml", vm.id));
+    let xml = libvirt_domain_xml(&vm.id, &vm.config);
+    fs::write(&path, xml)
+        .with_context(|| format!("Failed to write libvirt XML: {}", path.display()))?;
+    Ok(path)
+}
+
+/// Build a libvirt domain XML document for `config`.
+///
+/// Settings libvirt can't express (or that only partly translate) are kept as
+/// XML comments next to the closest equivalent, so nothing is silently lost.
+pub fn libvirt_domain_xml(name: &str, config: &QemuConfig) -> String {
+    let mut xml = String::new();
+    let domain_type = if config.enable_kvm { "kvm" } else { "qemu" };
+    let (firmware, disks): (Vec<&DiskConfig>, Vec<&DiskConfig>) =
+        config.disks.iter().partition(|d| is_firmware_file(&d.path));
+
+    let _ = writeln!(xml, "<domain type='{}'>", domain_type);
+    let _ = writeln!(xml, "  <!-- Exported by vm-curator -->");
+    let _ = writeln!(xml, "  <name>{}</name>", escape(name));
+    let _ = writeln!(xml, "  <memory unit='MiB'>{}</memory>", config.memory_mb);
+    let _ = writeln!(xml, "  <vcpu>{}</vcpu>", config.cpu_cores.max(1));
+
+    // <os>: architecture, machine type and firmware
+    let _ = writeln!(xml, "  <os>");
+    let machine = config
+        .machine
+        .as_deref()
+        .map(|m| format!(" machine='{}'", escape(m)))
+        .unwrap_or_default();
+    let _ = writeln!(
+        xml,
+        "    <type arch='{}'{}>hvm</type>",
+        libvirt_arch(&config.emulator),
+        machine
+    );
+    if config.uefi {
+        let code = firmware
+            .iter()
+            .find(|d| !is_vars_file(&d.path))
+            .map(|d| d.path.display().to_string())
+            .unwrap_or_else(|| "/usr/share/edk2/x64/OVMF_CODE.fd".to_string());
+        let _ = writeln!(
+            xml,
+            "    <loader readonly='yes' type='pflash'>{}</loader>",
+            escape(&code)
+        );
+        if let Some(vars) = firmware.iter().find(|d| is_vars_file(&d.path)) {
+            let _ = writeln!(
+                xml,
+                "    <nvram>{}</nvram>",
+                escape(&vars.path.display().to_string())
+            );
+        }
+    }
+    if let Some(ref bios) = config.bios_path {
+        let _ = writeln!(
+            xml,
+            "    <!-- Custom BIOS/ROM (-bios {}) has no direct libvirt equivalent -->",
+            escape_comment(&bios.display().to_string())
+        );
+    }
+    let _ = writeln!(xml, "  </os>");
+
+    if config.enable_kvm {
+        let _ = writeln!(xml, "  <features>");
+        let _ = writeln!(xml, "    <acpi/>");
+        let _ = writeln!(xml, "    <apic/>");
+        let _ = writeln!(xml, "  </features>");
+    }
+
+    // <cpu>
+    match config.cpu_model.as_deref() {
+        Some(model) => {
+            let (base, flags) = model.split_once(',').unwrap_or((model, ""));
+            if base == "host" {
+                let _ = writeln!(xml, "  <cpu mode='host-passthrough'/>");
+            } else {
+                let _ = writeln!(xml, "  <cpu mode='custom'>");
+                let _ = writeln!(xml, "    <model>{}</model>", escape(base));
+                let _ = writeln!(xml, "  </cpu>");
+            }
+            if !flags.is_empty() {
+                let _ = writeln!(
+                    xml,
+                    "  <!-- CPU flags not translated: {} -->",
+                    escape_comment(flags)
+                );
+            }
+        }
+        None if config.enable_kvm => {
+            let _ = writeln!(xml, "  <cpu mode='host-passthrough'/>");
+        }
+        None => {}
+    }
+
+    let _ = writeln!(xml, "  <devices>");
+    let _ = writeln!(
+        xml,
+        "    <emulator>/usr/bin/{}</emulator>",
+        escape(config.emulator.command())
+    );
+
+    // Disks, numbered per bus (vda, vdb, hda, ...)
+    let mut bus_counts: Vec<(&str, usize)> = Vec::new();
+    for disk in disks {
+        let (bus, prefix) = libvirt_disk_bus(&disk.interface);
+        let index = match bus_counts.iter_mut().find(|(b, _)| *b == bus) {
+            Some((_, n)) => {
+                *n += 1;
+                *n - 1
+            }
+            None => {
+                bus_counts.push((bus, 1));
+                0
+            }
+        };
+        let is_iso = disk
+            .path
+            .extension()
+            .is_some_and(|e| e.eq_ignore_ascii_case("iso"));
+        let device = if is_iso { "cdrom" } else { "disk" };
+        let _ = writeln!(xml, "    <disk type='file' device='{}'>", device);
+        let _ = writeln!(
+            xml,
+            "      <driver name='qemu' type='{}'/>",
+            libvirt_disk_format(&disk.format)
+        );
+        let _ = writeln!(
+            xml,
+            "      <source file='{}'/>",
+            escape(&disk.path.display().to_string())
+        );
+        let _ = writeln!(
+            xml,
+            "      <target dev='{}{}' bus='{}'/>",
+            prefix,
+            (b'a' + index.min(25) as u8) as char,
+            bus
+        );
+        if is_iso {
+            let _ = writeln!(xml, "      <readonly/>");
+        }
+        let _ = writeln!(xml, "    </disk>");
+    }
+
+    // Network
+    if let Some(ref net) = config.network {
+        let model = libvirt_nic_model(&net.model);
+        let (net_type, source, backend) = match &net.backend {
+            NetworkBackend::User => ("user", None, None),
+            NetworkBackend::Passt => ("user", None, Some("passt")),
+            NetworkBackend::Bridge(name) => ("bridge", Some(name.as_str()), None),
+            NetworkBackend::None => ("", None, None),
+        };
+        if !net_type.is_empty() {
+            let _ = writeln!(xml, "    <interface type='{}'>", net_type);
+            if let Some(ref mac) = net.mac_address {
+                let _ = writeln!(xml, "      <mac address='{}'/>", escape(mac));
+            }
+            if let Some(bridge) = source {
+                let _ = writeln!(xml, "      <source bridge='{}'/>", escape(bridge));
+            }
+            if let Some(backend) = backend {
+                let _ = writeln!(xml, "      <backend type='{}'/>", backend);
+            }
+            let _ = writeln!(xml, "      <model type='{}'/>", model);
+            if !net.port_forwards.is_empty() {
+                let rules: Vec<String> =
+                    net.port_forwards.iter().map(|pf| pf.to_string()).collect();
+                let _ = writeln!(
+                    xml,
+                    "      <!-- Port forwards not translated: {} -->",
+                    escape_comment(&rules.join(", "))
+                );
+            }
+            let _ = writeln!(xml, "    </interface>");
+        }
+    }
+
+    // Display and video
+    let display = display_backend(config);
+    match display.as_deref() {
+        Some("vnc") => {
+            let _ = writeln!(xml, "    <graphics type='vnc' autoport='yes'/>");
+        }
+        Some("sdl") => {
+            let _ = writeln!(xml, "    <graphics type='sdl'/>");
+        }
+        Some("none") => {}
+        other => {
+            if let Some(d) = other.filter(|d| !d.starts_with("spice")) {
+                let _ = writeln!(
+                    xml,
+                    "    <!-- Display '{}' has no libvirt equivalent; using SPICE -->",
+                    escape_comment(d)
+                );
+            }
+            let _ = writeln!(xml, "    <graphics type='spice' autoport='yes'/>");
+        }
+    }
+    if config.vga != VgaType::None {
+        let _ = writeln!(xml, "    <video>");
+        let _ = writeln!(
+            xml,
+            "      <model type='{}'/>",
+            libvirt_video_model(&config.vga)
+        );
+        let _ = writeln!(xml, "    </video>");
+    }
+
+    // Audio
+    for audio in &config.audio_devices {
+        match audio {
+            AudioDevice::Hda => {
+                let _ = writeln!(xml, "    <sound model='ich9'/>");
+            }
+            AudioDevice::Ac97 => {
+                let _ = writeln!(xml, "    <sound model='ac97'/>");
+            }
+            AudioDevice::Sb16 => {
+                let _ = writeln!(xml, "    <sound model='sb16'/>");
+            }
+            AudioDevice::Es1370 => {
+                let _ = writeln!(xml, "    <sound model='es1370'/>");
+            }
+            AudioDevice::PcSpk => {
+                let _ = writeln!(xml, "    <!-- PC speaker has no libvirt device -->");
+            }
+            AudioDevice::Other(name) => {
+                let _ = writeln!(
+                    xml,
+                    "    <!-- Audio device '{}' not translated -->",
+                    escape_comment(name)
+                );
+            }
+        }
+    }
+
+    if config.tpm {
+        let _ = writeln!(xml, "    <tpm model='tpm-crb'>");
+        let _ = writeln!(xml, "      <backend type='emulator' version='2.0'/>");
+        let _ = writeln!(xml, "    </tpm>");
+    }
+
+    let untranslated: Vec<&String> = config
+        .extra_args
+        .iter()
+        .filter(|a| !a.starts_with("-display "))
+        .collect();
+    if !untranslated.is_empty() {
+        let _ = writeln!(xml, "    <!-- QEMU arguments not translated:");
+        for arg in untranslated {
+            let _ = writeln!(xml, "         {}", escape_comment(arg));
+        }
+        let _ = writeln!(xml, "    -->");
+    }
+
+    let _ = writeln!(xml, "  </devices>");
+    let _ = writeln!(xml, "</domain>");
+    xml
+}
+
+/// OVMF code/vars images passed as pflash drives are firmware, not disks
+fn is_firmware_file(path: &Path) -> bool {
+    let name = path
+        .file_name()
+        .and_then(|n| n.to_str())
+        .unwrap_or("")
+        .to_uppercase();
+    name.contains("OVMF") || name.contains("EDK2") || name.ends_with(".FD")
+}
+
+/// The writable UEFI variable store (as opposed to the read-only code image)
+fn is_vars_file(path: &Path) -> bool {
+    path.file_name()
+        .and_then(|n| n.to_str())
+        .is_some_and(|n| n.to_uppercase().contains("VARS"))
+}
+
+/// libvirt `arch` attribute for an emulator
+fn libvirt_arch(emulator: &QemuEmulator) -> &str {
+    match emulator {
+        QemuEmulator::X86_64 => "x86_64",
+        QemuEmulator::I386 => "i686",
+        QemuEmulator::Ppc => "ppc",
+        QemuEmulator::M68k => "m68k",
+        QemuEmulator::Arm => "armv7l",
+        QemuEmulator::Aarch64 => "aarch64",
+        QemuEmulator::Other(cmd) => cmd.strip_prefix("qemu-system-").unwrap_or(cmd),
+    }
+}
+
+/// libvirt disk bus and device-name prefix for a QEMU drive interface
+fn libvirt_disk_bus(interface: &str) -> (&'static str, &'static str) {
+    match interface {
+        "virtio" => ("virtio", "vd"),
+        "scsi" => ("scsi", "sd"),
+        "sata" | "ahci" => ("sata", "sd"),
+        "usb" => ("usb", "sd"),
+        _ => ("ide", "hd"),
+    }
+}
+
+fn libvirt_disk_format(format: &DiskFormat) -> &str {
+    match format {
+        DiskFormat::Qcow2 => "qcow2",
+        DiskFormat::Raw => "raw",
+        DiskFormat::Vmdk => "vmdk",
+        DiskFormat::Vdi => "vdi",
+        DiskFormat::Other(other) if other == "iso" => "raw",
+        DiskFormat::Other(other) => other,
+    }
+}
+
+fn libvirt_nic_model(model: &str) -> &str {
+    match model {
+        "virtio-net-pci" | "virtio-net" | "virtio" => "virtio",
+        "" => "e1000",
+        other => other,
+    }
+}
+
+fn libvirt_video_model(vga: &VgaType) -> &str {
+    match vga {
+        VgaType::Std => "vga",
+        VgaType::Cirrus => "cirrus",
+        VgaType::Vmware => "vmvga",
+        VgaType::Qxl => "qxl",
+        VgaType::Virtio => "virtio",
+        VgaType::None => "none",
+        VgaType::Other(other) => other,
+    }
+}
+
+/// Display backend from the launch script (`-display X`, `-vnc`, `-spice`)
+fn display_backend(config: &QemuConfig) -> Option<String> {
+    if let Some(arg) = config
+        .extra_args
+        .iter()
+        .find_map(|a| a.strip_prefix("-display "))
+    {
+        return Some(arg.to_string());
+    }
+    let script = &config.raw_script;
+    if script.contains("-nographic") {
+        Some("none".to_string())
+    } else if script.contains("-vnc ") {
+        Some("vnc".to_string())
+    } else if script.contains("-spice ") {
+        Some("spice".to_string())
+    } else {
+        None
+    }
+}
+
+/// Escape text for XML element content and single-quoted attributes
+fn escape(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('\'', "&apos;")
+        .replace('"', "&quot;")
+}
+
+/// XML comments may not contain `--`
+fn escape_comment(s: &str) -> String {
+    s.replace("--", "- -")
+}
+
+#[cfg(test)]
+#[path = "tests/export.rs"]
+mod tests;
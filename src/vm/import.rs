@@ -1,7 +1,8 @@
 //! VM Import Logic
 //!
-//! Parses libvirt XML and quickemu .conf files, discovers importable VMs,
-//! and executes the import (directory creation, disk handling, launch script generation).
+//! Parses libvirt XML, quickemu .conf and OVA/OVF files and the command lines of
+//! QEMU processes started by hand, discovers importable VMs, and executes the
+//! import (directory creation, disk handling, launch script generation).
 
 use anyhow::{bail, Context, Result};
 use log::warn;
@@ -9,6 +10,8 @@ use std::fs;
 use std::os::unix::fs as unix_fs;
 use std::path::{Path, PathBuf};
 
+use crate::vm::qemu_config::{InputDevices, RtcConfig};
+use crate::vm::QemuProcess;
 use crate::wizard_types::{ImportDiskAction, ImportSource, ImportableVm, WizardQemuConfig};
 
 // =========================================================================
@@ -319,10 +322,13 @@ impl LibvirtParse {
         let enable_kvm = self.domain_type == "kvm";
         let detected_os_profile = detect_os_profile(&self.vm_name);
 
+        let audio_backend = WizardQemuConfig::default_audio_backend(&emulator, &display);
         let qemu_config = WizardQemuConfig {
             emulator,
             memory_mb: (self.memory_kb / 1024) as u32,
+            max_memory_mb: None,
             cpu_cores: if self.vcpu == 0 { 1 } else { self.vcpu },
+            cpu_topology: None,
             cpu_model: if enable_kvm {
                 Some("host".to_string())
             } else {
@@ -335,21 +341,31 @@ impl LibvirtParse {
             },
             vga,
             audio: vec!["intel-hda".to_string(), "hda-duplex".to_string()],
+            audio_backend,
             network_model,
             disk_interface,
             enable_kvm,
             gl_acceleration: false,
             uefi: self.has_uefi,
             tpm: self.has_tpm,
+            secure_boot: false,
             rtc_localtime: false,
-            usb_tablet: true,
+            rtc: RtcConfig::default(),
+            input: InputDevices::UsbTablet,
+            apple_smc: false,
+            guest_agent: false,
+            balloon: false,
+            clipboard_sharing: true,
+            nested_virt: None,
             display,
             network_backend,
             port_forwards: Vec::new(),
             bridge_name,
             mac_address: None,
+            keymap: None,
             extra_args: Vec::new(),
             bios_path: None,
+            ovmf_paths: None,
         };
 
         Ok(ImportableVm {
@@ -366,7 +382,7 @@ impl LibvirtParse {
 }
 
 /// Parse libvirt XML from a string (separated from file IO so it can be tested).
-fn parse_libvirt_xml_str(xml: &str, config_path: &Path) -> Result<ImportableVm> {
+pub(crate) fn parse_libvirt_xml_str(xml: &str, config_path: &Path) -> Result<ImportableVm> {
     use quick_xml::events::Event;
     use quick_xml::Reader;
 
@@ -509,11 +525,11 @@ fn parse_quickemu_conf_str(content: &str, config_path: &Path) -> Result<Importab
 
     // macOS note
     if guest_os == "macos" {
-        import_notes.push(
+        import_notes.push(format!(
             "macOS: quickemu's OpenCore bootloader setup is not replicated. \
-             The QEMU config is imported as-is."
-                .to_string(),
-        );
+                 The QEMU config is imported as-is. {}",
+            crate::metadata::qemu_profiles::MACOS_LICENSE_NOTE
+        ));
     }
 
     // Check disk readability
@@ -536,26 +552,38 @@ fn parse_quickemu_conf_str(content: &str, config_path: &Path) -> Result<Importab
     let qemu_config = WizardQemuConfig {
         emulator: "qemu-system-x86_64".to_string(),
         memory_mb: if memory_mb == 0 { 2048 } else { memory_mb },
+        max_memory_mb: None,
         cpu_cores,
+        cpu_topology: None,
         cpu_model: Some("host".to_string()),
         machine: Some("q35".to_string()),
         vga: "virtio".to_string(),
         audio: vec!["intel-hda".to_string(), "hda-duplex".to_string()],
+        audio_backend: WizardQemuConfig::default_audio_backend("qemu-system-x86_64", &display),
         network_model: "virtio-net-pci".to_string(),
         disk_interface: "virtio".to_string(),
         enable_kvm: true,
         gl_acceleration: false,
         uefi,
         tpm,
+        secure_boot: false,
         rtc_localtime: guest_os == "windows",
-        usb_tablet: true,
+        rtc: RtcConfig::default(),
+        input: InputDevices::UsbTablet,
+        apple_smc: guest_os == "macos",
+        guest_agent: false,
+        balloon: false,
+        clipboard_sharing: true,
+        nested_virt: None,
         display,
         network_backend: "user".to_string(),
         port_forwards: Vec::new(),
         bridge_name: None,
         mac_address: None,
+        keymap: None,
         extra_args: Vec::new(),
         bios_path: None,
+        ovmf_paths: None,
     };
 
     let detected_os_profile = if !guest_os.is_empty() {
@@ -592,6 +620,698 @@ fn parse_quickemu_ram(ram: &str) -> u32 {
     }
 }
 
+// =========================================================================
+// OVA/OVF Parsing
+// =========================================================================
+
+/// Parse an OVA archive (tar containing an .ovf descriptor and its disks).
+///
+/// Only the descriptor is read here; disks stay in the archive until
+/// [`execute_import`] extracts them. Their paths are recorded as
+/// `<archive>/<member>` so the wizard can show where they come from.
+pub fn parse_ova(path: &Path) -> Result<ImportableVm> {
+    let members = list_tar_members(path)?;
+    // Manifests (.mf) and signatures (.cert) are ignored; only the descriptor matters
+    let ovf_member = members
+        .iter()
+        .find(|m| m.to_lowercase().ends_with(".ovf"))
+        .with_context(|| format!("No .ovf descriptor in {}", path.display()))?;
+
+    let output = std::process::Command::new("tar")
+        .args(["-xOf"])
+        .arg(path)
+        .arg(ovf_member)
+        .output()
+        .context("Failed to run tar")?;
+    if !output.status.success() {
+        bail!(
+            "Failed to read {} from {}: {}",
+            ovf_member,
+            path.display(),
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+    let xml = String::from_utf8_lossy(&output.stdout);
+
+    let mut vm = parse_ovf_str(&xml, path, path)?;
+    vm.disks_readable = vm
+        .disk_paths
+        .iter()
+        .map(|p| {
+            p.strip_prefix(path)
+                .ok()
+                .and_then(|m| m.to_str())
+                .is_some_and(|m| members.iter().any(|member| member == m))
+        })
+        .collect();
+    for (i, readable) in vm.disks_readable.iter().enumerate() {
+        if !readable {
+            vm.import_notes.push(format!(
+                "Disk {}: {} is missing from the archive",
+                i + 1,
+                vm.disk_paths[i].display()
+            ));
+        }
+    }
+    Ok(vm)
+}
+
+/// Parse an unpacked .ovf descriptor; disks are resolved next to it.
+pub fn parse_ovf(path: &Path) -> Result<ImportableVm> {
+    let content = fs::read_to_string(path)
+        .with_context(|| format!("Failed to read OVF descriptor: {}", path.display()))?;
+    let disk_dir = path.parent().unwrap_or(Path::new("."));
+
+    let mut vm = parse_ovf_str(&content, path, disk_dir)?;
+    vm.disks_readable = vm
+        .disk_paths
+        .iter()
+        .map(|p| p.exists() && fs::File::open(p).is_ok())
+        .collect();
+    for (i, (p, readable)) in vm.disk_paths.iter().zip(&vm.disks_readable).enumerate() {
+        if !readable {
+            vm.import_notes.push(format!(
+                "Disk {}: {} does not exist or is not readable",
+                i + 1,
+                p.display()
+            ));
+        }
+    }
+    Ok(vm)
+}
+
+/// Names of the files inside a tar archive
+fn list_tar_members(path: &Path) -> Result<Vec<String>> {
+    let output = std::process::Command::new("tar")
+        .arg("-tf")
+        .arg(path)
+        .output()
+        .context("Failed to run tar")?;
+    if !output.status.success() {
+        bail!(
+            "{} is not a readable OVA archive: {}",
+            path.display(),
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+    Ok(String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .map(|l| l.trim_start_matches("./").to_string())
+        .filter(|l| !l.is_empty())
+        .collect())
+}
+
+/// CIM resource types used in OVF `<Item>` elements
+mod rasd {
+    pub const CPU: &str = "3";
+    pub const MEMORY: &str = "4";
+    pub const IDE_CONTROLLER: &str = "5";
+    pub const SCSI_CONTROLLER: &str = "6";
+    pub const ETHERNET: &str = "10";
+    pub const FLOPPY: &str = "14";
+    pub const CD_DRIVE: &str = "15";
+    pub const DVD_DRIVE: &str = "16";
+    pub const DISK: &str = "17";
+    pub const SATA_CONTROLLER: &str = "20";
+    pub const SERIAL_PORT: &str = "21";
+    pub const PARALLEL_PORT: &str = "22";
+    pub const USB_CONTROLLER: &str = "23";
+    pub const GRAPHICS: &str = "24";
+    pub const SOUND: &str = "35";
+}
+
+/// Mutable accumulator for streaming an OVF descriptor (see [`LibvirtParse`]).
+#[derive(Default)]
+struct OvfParse {
+    vm_name: String,
+    os_description: String,
+    /// File id -> href from `<References>`
+    files: Vec<(String, String)>,
+    /// Disk id -> file id from `<DiskSection>`
+    disks: Vec<(String, String)>,
+    /// Hardware items as (child element, text) pairs
+    items: Vec<Vec<(String, String)>>,
+    uefi: bool,
+
+    element_stack: Vec<String>,
+    current_item: Option<Vec<(String, String)>>,
+    text: String,
+}
+
+impl OvfParse {
+    fn handle_start(&mut self, e: &quick_xml::events::BytesStart) {
+        let tag = String::from_utf8_lossy(e.local_name().as_ref()).to_string();
+        self.handle_attributes(&tag, e);
+        if matches!(tag.as_str(), "Item" | "StorageItem" | "EthernetPortItem") {
+            self.current_item = Some(Vec::new());
+        }
+        self.text.clear();
+        self.element_stack.push(tag);
+    }
+
+    /// Attributes carry the file/disk references and VMware's firmware setting
+    fn handle_attributes(&mut self, tag: &str, e: &quick_xml::events::BytesStart) {
+        match tag {
+            "File" => {
+                if let (Some(id), Some(href)) =
+                    (find_attr_local(e, "id"), find_attr_local(e, "href"))
+                {
+                    self.files.push((id, href));
+                }
+            }
+            "Disk" => {
+                if let (Some(id), Some(file)) =
+                    (find_attr_local(e, "diskId"), find_attr_local(e, "fileRef"))
+                {
+                    self.disks.push((id, file));
+                }
+            }
+            "VirtualSystem" if self.vm_name.is_empty() => {
+                if let Some(id) = find_attr_local(e, "id") {
+                    self.vm_name = id;
+                }
+            }
+            "Config" => {
+                let is_firmware = find_attr_local(e, "key").as_deref() == Some("firmware");
+                if is_firmware && find_attr_local(e, "value").as_deref() == Some("efi") {
+                    self.uefi = true;
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn handle_empty(&mut self, e: &quick_xml::events::BytesStart) {
+        let tag = String::from_utf8_lossy(e.local_name().as_ref()).to_string();
+        self.handle_attributes(&tag, e);
+    }
+
+    fn handle_text(&mut self, raw: &str) {
+        self.text.push_str(raw);
+    }
+
+    fn handle_end(&mut self, tag: &str) {
+        let text = self.text.trim().to_string();
+        let parent = self
+            .element_stack
+            .iter()
+            .rev()
+            .nth(1)
+            .cloned()
+            .unwrap_or_default();
+
+        match tag {
+            "Item" | "StorageItem" | "EthernetPortItem" => {
+                if let Some(item) = self.current_item.take() {
+                    self.items.push(item);
+                }
+            }
+            "Name" if parent == "VirtualSystem" && !text.is_empty() => {
+                self.vm_name = text.clone();
+            }
+            "Description" if parent == "OperatingSystemSection" => {
+                self.os_description = text.clone();
+            }
+            _ => {
+                if let Some(ref mut item) = self.current_item {
+                    if !text.is_empty() {
+                        item.push((tag.to_string(), text.clone()));
+                    }
+                }
+            }
+        }
+
+        if self.element_stack.last().map(|s| s.as_str()) == Some(tag) {
+            self.element_stack.pop();
+        }
+        self.text.clear();
+    }
+
+    /// Map the collected hardware onto an [`ImportableVm`]
+    fn into_importable_vm(self, config_path: &Path, disk_dir: &Path) -> Result<ImportableVm> {
+        if self.items.is_empty() {
+            bail!("No VirtualHardwareSection found in OVF descriptor");
+        }
+
+        let mut import_notes = Vec::new();
+        let mut cpu_cores = 1;
+        let mut memory_mb = 0;
+        let mut network_model: Option<String> = None;
+        // Controller InstanceID -> QEMU interface, and the first disk's controller
+        let mut controllers: Vec<(&str, &str)> = Vec::new();
+        let mut first_disk_parent: Option<&str> = None;
+        let mut disk_ids: Vec<String> = Vec::new();
+        let mut unsupported: Vec<String> = Vec::new();
+
+        for item in &self.items {
+            let get = |key: &str| {
+                item.iter()
+                    .find(|(k, _)| k == key)
+                    .map(|(_, v)| v.as_str())
+                    .unwrap_or("")
+            };
+            let quantity: u64 = get("VirtualQuantity").parse().unwrap_or(0);
+            match get("ResourceType") {
+                rasd::CPU => cpu_cores = quantity.max(1) as u32,
+                rasd::MEMORY => {
+                    memory_mb = ovf_memory_mb(quantity, get("AllocationUnits"));
+                }
+                rasd::ETHERNET if network_model.is_none() => {
+                    let subtype = get("ResourceSubType");
+                    let model = map_ovf_nic(subtype);
+                    if !subtype.is_empty()
+                        && model == "e1000"
+                        && !subtype.eq_ignore_ascii_case("e1000")
+                    {
+                        import_notes.push(format!(
+                            "Network: {} adapter changed to e1000 (not emulated by QEMU)",
+                            subtype
+                        ));
+                    }
+                    network_model = Some(model.to_string());
+                }
+                rasd::ETHERNET => unsupported.push("additional network adapters".to_string()),
+                rasd::DISK => {
+                    let resource = get("HostResource");
+                    if let Some(id) = resource.rsplit('/').next().filter(|s| !s.is_empty()) {
+                        disk_ids.push(id.to_string());
+                    }
+                    first_disk_parent.get_or_insert(get("Parent"));
+                }
+                rasd::SCSI_CONTROLLER => controllers.push((get("InstanceID"), "scsi")),
+                // QEMU's default IDE/AHCI emulation covers both
+                rasd::IDE_CONTROLLER | rasd::SATA_CONTROLLER => {
+                    controllers.push((get("InstanceID"), "ide"))
+                }
+                rasd::SERIAL_PORT => unsupported.push("serial ports".to_string()),
+                rasd::PARALLEL_PORT => unsupported.push("parallel ports".to_string()),
+                rasd::FLOPPY => unsupported.push("floppy drive".to_string()),
+                rasd::SOUND => {
+                    let subtype = get("ResourceSubType");
+                    if !subtype.is_empty() {
+                        import_notes.push(format!(
+                            "Audio: {} sound card replaced with Intel HDA",
+                            subtype
+                        ));
+                    }
+                }
+                rasd::CD_DRIVE | rasd::DVD_DRIVE | rasd::USB_CONTROLLER | rasd::GRAPHICS => {}
+                other if !other.is_empty() => {
+                    let label = get("ElementName");
+                    unsupported.push(if label.is_empty() {
+                        format!("resource type {}", other)
+                    } else {
+                        label.to_string()
+                    });
+                }
+                _ => {}
+            }
+        }
+
+        let disk_interface = first_disk_parent
+            .and_then(|parent| controllers.iter().find(|(id, _)| *id == parent))
+            .map(|(_, interface)| *interface)
+            .unwrap_or("ide");
+
+        // Listed once each, in hardware order
+        let mut seen = std::collections::HashSet::new();
+        unsupported.retain(|item| seen.insert(item.clone()));
+        if !unsupported.is_empty() {
+            import_notes.push(format!("Hardware not imported: {}", unsupported.join(", ")));
+        }
+
+        // Disks in hardware order, falling back to DiskSection order
+        if disk_ids.is_empty() {
+            disk_ids = self.disks.iter().map(|(id, _)| id.clone()).collect();
+        }
+        let mut disk_paths = Vec::new();
+        for id in &disk_ids {
+            let file_ref = self
+                .disks
+                .iter()
+                .find(|(disk_id, _)| disk_id == id)
+                .map(|(_, f)| f.as_str());
+            let href = file_ref
+                .and_then(|f| self.files.iter().find(|(file_id, _)| file_id == f))
+                .map(|(_, href)| href.as_str());
+            match href {
+                Some(href) => disk_paths.push(disk_dir.join(href)),
+                None => import_notes.push(format!("Disk {} has no backing file (skipped)", id)),
+            }
+        }
+        for (i, path) in disk_paths.iter().enumerate() {
+            if is_vmdk(path) {
+                import_notes.push(format!(
+                    "Disk {}: {} is VMDK and will be converted to qcow2 during import",
+                    i + 1,
+                    path.file_name().unwrap_or_default().to_string_lossy()
+                ));
+            }
+        }
+
+        let vm_name = if self.vm_name.is_empty() {
+            config_path
+                .file_stem()
+                .and_then(|s| s.to_str())
+                .unwrap_or("imported-vm")
+                .to_string()
+        } else {
+            self.vm_name
+        };
+        let detected_os_profile =
+            detect_os_profile(&self.os_description).or_else(|| detect_os_profile(&vm_name));
+
+        let qemu_config = WizardQemuConfig {
+            emulator: "qemu-system-x86_64".to_string(),
+            memory_mb: if memory_mb == 0 { 2048 } else { memory_mb },
+            max_memory_mb: None,
+            cpu_cores,
+            cpu_topology: None,
+            cpu_model: Some("host".to_string()),
+            machine: Some("q35".to_string()),
+            vga: "std".to_string(),
+            audio: vec!["intel-hda".to_string(), "hda-duplex".to_string()],
+            audio_backend: WizardQemuConfig::default_audio_backend("qemu-system-x86_64", "gtk"),
+            network_model: network_model.unwrap_or_else(|| "e1000".to_string()),
+            disk_interface: disk_interface.to_string(),
+            enable_kvm: true,
+            gl_acceleration: false,
+            uefi: self.uefi,
+            tpm: false,
+            secure_boot: false,
+            rtc_localtime: detected_os_profile
+                .as_deref()
+                .is_some_and(|p| p.starts_with("windows")),
+            rtc: RtcConfig::default(),
+            input: InputDevices::UsbTablet,
+            apple_smc: false,
+            guest_agent: false,
+            balloon: false,
+            clipboard_sharing: true,
+            nested_virt: None,
+            display: "gtk".to_string(),
+            network_backend: "user".to_string(),
+            port_forwards: Vec::new(),
+            bridge_name: None,
+            mac_address: None,
+            keymap: None,
+            extra_args: Vec::new(),
+            bios_path: None,
+            ovmf_paths: None,
+        };
+
+        let disks_readable = vec![true; disk_paths.len()];
+        Ok(ImportableVm {
+            name: vm_name,
+            config_path: config_path.to_path_buf(),
+            source: ImportSource::Ova,
+            qemu_config,
+            disk_paths,
+            detected_os_profile,
+            import_notes,
+            disks_readable,
+        })
+    }
+}
+
+/// Return the value of the first attribute whose local name (ignoring any
+/// `ovf:`/`vmw:` prefix) is `key`.
+fn find_attr_local(e: &quick_xml::events::BytesStart, key: &str) -> Option<String> {
+    e.attributes()
+        .flatten()
+        .find(|attr| attr.key.local_name().as_ref() == key.as_bytes())
+        .map(|attr| attr_value(&attr))
+}
+
+/// Parse an OVF descriptor from a string. Disk hrefs resolve against `disk_dir`.
+fn parse_ovf_str(xml: &str, config_path: &Path, disk_dir: &Path) -> Result<ImportableVm> {
+    use quick_xml::events::Event;
+    use quick_xml::Reader;
+
+    let mut reader = Reader::from_str(xml);
+    let mut state = OvfParse::default();
+    let mut buf = Vec::new();
+
+    loop {
+        match reader.read_event_into(&mut buf) {
+            Ok(Event::Start(ref e)) => state.handle_start(e),
+            Ok(Event::Empty(ref e)) => state.handle_empty(e),
+            Ok(Event::Text(ref t)) => {
+                let text = String::from_utf8_lossy(t.as_ref());
+                state.handle_text(&text);
+            }
+            Ok(Event::End(ref e)) => {
+                let tag = String::from_utf8_lossy(e.local_name().as_ref()).to_string();
+                state.handle_end(&tag);
+            }
+            Ok(Event::Eof) => break,
+            Err(e) => bail!("Error parsing OVF descriptor: {}", e),
+            _ => {}
+        }
+        buf.clear();
+    }
+
+    state.into_importable_vm(config_path, disk_dir)
+}
+
+/// Convert an OVF memory quantity to MB. `AllocationUnits` is usually
+/// `byte * 2^20`; older descriptors spell it out as `MegaBytes`.
+fn ovf_memory_mb(quantity: u64, units: &str) -> u32 {
+    let units = units.replace(' ', "").to_lowercase();
+    let mb = if let Some(exp) = units.strip_prefix("byte*2^") {
+        match exp.parse::<u32>() {
+            Ok(e) if e >= 20 => quantity << (e - 20),
+            Ok(e) => quantity >> (20 - e),
+            Err(_) => quantity,
+        }
+    } else if units.starts_with("gigabyte") || units == "gb" {
+        quantity * 1024
+    } else if units.starts_with("kilobyte") || units == "kb" {
+        quantity / 1024
+    } else {
+        quantity
+    };
+    mb as u32
+}
+
+/// Map an OVF NIC subtype to a QEMU model
+fn map_ovf_nic(subtype: &str) -> &'static str {
+    match subtype.to_lowercase().as_str() {
+        "virtio" | "virtio-net" => "virtio-net-pci",
+        "rtl8139" => "rtl8139",
+        "pcnet32" | "pcnet" | "pcnetfast" | "am79c973" | "am79c970a" => "pcnet",
+        // E1000, E1000e, VmxNet3 and anything else
+        _ => "e1000",
+    }
+}
+
+fn is_vmdk(path: &Path) -> bool {
+    path.extension()
+        .and_then(|e| e.to_str())
+        .is_some_and(|e| e.eq_ignore_ascii_case("vmdk"))
+}
+
+/// Disk formats from other hypervisors as (QEMU format name, file extension).
+/// QEMU runs these directly but can only snapshot qcow2.
+const FOREIGN_DISK_FORMATS: &[(&str, &str)] = &[
+    ("vmdk", "vmdk"),
+    ("vdi", "vdi"),
+    ("vpc", "vhd"),
+    ("vhdx", "vhdx"),
+];
+
+/// QEMU format name of a VMware/VirtualBox/Hyper-V disk, or `None` for
+/// qcow2, raw and anything unrecognized.
+///
+/// Trusts `qemu-img info` when it recognizes the image; a `raw` result or a
+/// missing qemu-img falls back to the file extension.
+pub fn foreign_disk_format(path: &Path) -> Option<&'static str> {
+    let detected = crate::commands::qemu_img::detect_disk_format(path);
+    match detected.as_deref() {
+        Some("raw") | None => foreign_format_from_extension(path),
+        Some(format) => FOREIGN_DISK_FORMATS
+            .iter()
+            .find(|(name, _)| *name == format)
+            .map(|(name, _)| *name),
+    }
+}
+
+/// Foreign QEMU format implied by a disk's file extension
+pub(crate) fn foreign_format_from_extension(path: &Path) -> Option<&'static str> {
+    let ext = path.extension()?.to_str()?;
+    FOREIGN_DISK_FORMATS
+        .iter()
+        .find(|(_, e)| e.eq_ignore_ascii_case(ext))
+        .map(|(name, _)| *name)
+}
+
+fn foreign_format_extension(format: &str) -> &'static str {
+    FOREIGN_DISK_FORMATS
+        .iter()
+        .find(|(name, _)| *name == format)
+        .map_or("img", |(_, ext)| *ext)
+}
+
+// =========================================================================
+// Running QEMU Processes
+// =========================================================================
+
+/// Parse the command line of a QEMU process started outside vm-curator.
+/// Relative paths resolve against the process's working directory.
+pub fn parse_qemu_process(process: &QemuProcess, library_path: &Path) -> Result<ImportableVm> {
+    let argv = if process.args.is_empty() {
+        process
+            .cmdline
+            .split_whitespace()
+            .map(str::to_string)
+            .collect()
+    } else {
+        process.args.clone()
+    };
+    let cwd = process.cwd.as_deref().unwrap_or(Path::new("/"));
+    parse_qemu_argv(&argv, cwd, process.pid, library_path)
+}
+
+/// Parse a QEMU argument vector (emulator first) run from `cwd`
+fn parse_qemu_argv(
+    argv: &[String],
+    cwd: &Path,
+    pid: u32,
+    library_path: &Path,
+) -> Result<ImportableVm> {
+    use crate::vm::create::{is_guest_disk, wizard_config_from_parsed};
+
+    if !argv.first().is_some_and(|arg| {
+        arg.rsplit('/')
+            .next()
+            .unwrap_or(arg)
+            .starts_with("qemu-system-")
+    }) {
+        bail!("Process {} is not a qemu-system emulator", pid);
+    }
+
+    let script = qemu_argv_script(argv);
+    let parsed = crate::vm::launch_parser::parse_launch_script(&cwd.join("launch.sh"), &script)?;
+    let qemu_config = wizard_config_from_parsed(&parsed);
+    let disk_paths: Vec<PathBuf> = parsed
+        .disks
+        .iter()
+        .filter(|d| is_guest_disk(d, &parsed))
+        .map(|d| d.path.clone())
+        .collect();
+
+    let name = argv
+        .iter()
+        .position(|arg| arg == "-name")
+        .and_then(|i| argv.get(i + 1))
+        .and_then(|value| value.split(',').next())
+        .map(|value| value.trim_start_matches("guest=").to_string())
+        .filter(|name| !name.is_empty())
+        .or_else(|| {
+            disk_paths
+                .first()
+                .and_then(|d| d.file_stem())
+                .map(|stem| stem.to_string_lossy().to_string())
+        })
+        .unwrap_or_else(|| format!("qemu-{}", pid));
+
+    let mut import_notes = vec![format!(
+        "Running: this VM is still running (PID {}). Symlinking its disk is safe now; \
+         shut it down before copying, moving or converting it, and before starting the \
+         imported VM.",
+        pid
+    )];
+    for disk in &disk_paths {
+        if !disk.starts_with(library_path) {
+            import_notes.push(format!(
+                "Disk: {} is outside the VM library. Symlink leaves it where it is; \
+                 Copy or Move brings it into the new VM folder.",
+                disk.display()
+            ));
+        }
+    }
+    let dropped = crate::vm::launch_parser::unrecognized_args(&script);
+    if !dropped.is_empty() {
+        import_notes.push(format!(
+            "Options not carried over (add them in the raw script editor if needed): {}",
+            dropped.join(" ")
+        ));
+    }
+
+    let disks_readable = disk_paths
+        .iter()
+        .map(|p| p.exists() && fs::File::open(p).is_ok())
+        .collect();
+
+    Ok(ImportableVm {
+        detected_os_profile: detect_os_profile(&name),
+        name,
+        config_path: PathBuf::from(format!("/proc/{}", pid)),
+        source: ImportSource::Process,
+        qemu_config,
+        disk_paths,
+        import_notes,
+        disks_readable,
+    })
+}
+
+/// A launch script running `argv`, one option and its value per line as
+/// in generated scripts, so the line-based launch parser can read it
+fn qemu_argv_script(argv: &[String]) -> String {
+    let emulator = argv
+        .first()
+        .map(|arg| arg.rsplit('/').next().unwrap_or(arg))
+        .unwrap_or("qemu-system-x86_64");
+    let mut lines = vec![emulator.to_string()];
+    let mut args = argv.iter().skip(1).peekable();
+    while let Some(arg) = args.next() {
+        let mut line = quote_arg(arg);
+        if arg.starts_with('-') {
+            if let Some(value) = args.next_if(|v| !v.starts_with('-')) {
+                line.push(' ');
+                line.push_str(&quote_arg(value));
+            }
+        }
+        lines.push(line);
+    }
+    format!("#!/bin/bash\n\n{}\n", lines.join(" \\\n    "))
+}
+
+/// Double-quote an argument if the shell would split or expand it
+fn quote_arg(arg: &str) -> String {
+    if !arg.is_empty()
+        && arg
+            .chars()
+            .all(|c| c.is_alphanumeric() || "-_./,=:+@%".contains(c))
+    {
+        return arg.to_string();
+    }
+    let escaped: String = arg
+        .chars()
+        .flat_map(|c| {
+            let escape = matches!(c, '"' | '\\' | '$' | '`').then_some('\\');
+            escape.into_iter().chain(std::iter::once(c))
+        })
+        .collect();
+    format!("\"{}\"", escaped)
+}
+
+/// QEMU processes that don't belong to a library VM, parsed for import.
+/// `managed_pids` are the processes already matched to library VMs.
+pub fn discover_qemu_processes(managed_pids: &[u32], library_path: &Path) -> Vec<ImportableVm> {
+    crate::vm::detect_qemu_processes()
+        .iter()
+        .filter(|p| !managed_pids.contains(&p.pid))
+        .filter_map(|p| match parse_qemu_process(p, library_path) {
+            Ok(vm) => Some(vm),
+            Err(e) => {
+                warn!("Failed to parse QEMU process {}: {}", p.pid, e);
+                None
+            }
+        })
+        .collect()
+}
+
 // =========================================================================
 // Auto-Discovery
 // =========================================================================
@@ -693,6 +1413,8 @@ pub fn parse_config_file(path: &Path) -> Result<ImportableVm> {
     match path.extension().and_then(|e| e.to_str()) {
         Some("xml") => parse_libvirt_xml(path),
         Some("conf") => parse_quickemu_conf(path),
+        Some(ext) if ext.eq_ignore_ascii_case("ova") => parse_ova(path),
+        Some(ext) if ext.eq_ignore_ascii_case("ovf") => parse_ovf(path),
         Some(ext) => bail!("Unsupported config file format: .{}", ext),
         None => bail!("Config file has no extension"),
     }
@@ -709,30 +1431,110 @@ pub fn execute_import(
     vm_name: &str,
     folder_name: &str,
     disk_action: ImportDiskAction,
+    mut on_progress: impl FnMut(&str, f64),
 ) -> Result<PathBuf> {
     use crate::vm::create::{
-        create_vm_directory, detect_existing_disk_image_format, generate_launch_script_with_os,
-        write_launch_script, write_vm_metadata,
+        copy_ovmf_vars, create_vm_directory, detect_existing_disk_image_format,
+        generate_launch_script_with_os, uefi_firmware, write_launch_script, write_vm_metadata,
     };
 
+    // A running VM's disk is still being written to
+    if vm.source == ImportSource::Process
+        && disk_action != ImportDiskAction::Symlink
+        && vm.config_path.exists()
+    {
+        bail!(
+            "{} is still running; shut it down before copying or moving its disk, or choose Symlink",
+            vm.name
+        );
+    }
+
+    let firmware = uefi_firmware(&vm.qemu_config, vm.detected_os_profile.as_deref())?;
     let vm_dir = create_vm_directory(library_path, folder_name)?;
+    if let Some(ref firmware) = firmware {
+        copy_ovmf_vars(&vm_dir, firmware)?;
+    }
 
     // Handle each disk
+    let disk_count = vm.disk_paths.len();
     let mut disk_filenames: Vec<String> = Vec::new();
     for (i, disk_path) in vm.disk_paths.iter().enumerate() {
-        if !disk_path.exists() {
+        let disk_name = |ext: &str| {
+            if i == 0 {
+                format!("{}.{}", folder_name, ext)
+            } else {
+                format!("{}-disk{}.{}", folder_name, i + 1, ext)
+            }
+        };
+
+        // Disks inside an OVA are extracted into the VM directory first
+        let archive_member = ova_archive_member(vm, disk_path);
+        let staged = match archive_member {
+            Some((archive, member)) => Some(extract_tar_member(archive, member, &vm_dir)?),
+            None if !disk_path.exists() => continue,
+            None => None,
+        };
+        let source = staged.as_deref().unwrap_or(disk_path);
+        let foreign_format = foreign_disk_format(source);
+
+        // QEMU can't write OVF-style (streamOptimized) VMDKs, so those are
+        // always converted; other foreign formats only when asked to
+        let convert = foreign_format.is_some()
+            && (disk_action == ImportDiskAction::Convert
+                || (vm.source == ImportSource::Ova && is_vmdk(source)));
+        if let (true, Some(format)) = (convert, foreign_format) {
+            let disk_filename = disk_name("qcow2");
+            let dest = vm_dir.join(&disk_filename);
+            let label = format!(
+                "Converting disk {}/{} ({} to qcow2)",
+                i + 1,
+                disk_count,
+                format
+            );
+            on_progress(&label, 0.0);
+            let result = crate::commands::qemu_img::convert_disk(source, &dest, "qcow2", |p| {
+                on_progress(&label, p)
+            });
+            if let Err(e) = result {
+                // Nothing in the folder is an original unless disks were moved,
+                // so clear it out and let the user retry under the same name
+                if disk_action == ImportDiskAction::Move {
+                    let _ = fs::remove_file(&dest);
+                } else {
+                    let _ = fs::remove_dir_all(&vm_dir);
+                }
+                return Err(e).with_context(|| {
+                    format!(
+                        "Failed to convert {} ({}) to qcow2",
+                        source.display(),
+                        format
+                    )
+                });
+            }
+            if let Some(ref staged) = staged {
+                let _ = fs::remove_file(staged);
+            }
+            disk_filenames.push(disk_filename);
             continue;
         }
 
-        let disk_format = detect_existing_disk_image_format(disk_path);
-        let disk_filename = if i == 0 {
-            format!("{}.{}", folder_name, disk_format.extension())
-        } else {
-            format!("{}-disk{}.{}", folder_name, i + 1, disk_format.extension())
+        // Disks kept in a foreign format keep its extension so the launch
+        // script passes the right `format=`
+        let extension = match foreign_format {
+            Some(format) => foreign_format_extension(format),
+            None => detect_existing_disk_image_format(source).extension(),
         };
-
+        let disk_filename = disk_name(extension);
         let dest = vm_dir.join(&disk_filename);
 
+        if let Some(ref staged) = staged {
+            fs::rename(staged, &dest)
+                .with_context(|| format!("Failed to move extracted disk {}", staged.display()))?;
+            disk_filenames.push(disk_filename);
+            continue;
+        }
+
+        let label = format!("Copying disk {}/{}", i + 1, disk_count);
         match disk_action {
             ImportDiskAction::Symlink => {
                 let abs_source = fs::canonicalize(disk_path)
@@ -745,24 +1547,12 @@ pub fn execute_import(
                     )
                 })?;
             }
-            ImportDiskAction::Copy => {
-                fs::copy(disk_path, &dest).with_context(|| {
-                    format!(
-                        "Failed to copy disk from {} to {}",
-                        disk_path.display(),
-                        dest.display()
-                    )
-                })?;
+            ImportDiskAction::Copy | ImportDiskAction::Convert => {
+                crate::fs::copy_with_progress(disk_path, &dest, |p| on_progress(&label, p))?;
             }
             ImportDiskAction::Move => {
                 if fs::rename(disk_path, &dest).is_err() {
-                    fs::copy(disk_path, &dest).with_context(|| {
-                        format!(
-                            "Failed to copy disk from {} to {}",
-                            disk_path.display(),
-                            dest.display()
-                        )
-                    })?;
+                    crate::fs::copy_with_progress(disk_path, &dest, |p| on_progress(&label, p))?;
                     fs::remove_file(disk_path).with_context(|| {
                         format!("Failed to remove original disk: {}", disk_path.display())
                     })?;
@@ -793,6 +1583,47 @@ pub fn execute_import(
     Ok(vm_dir)
 }
 
+/// For a disk that lives inside the VM's OVA archive, the archive and member name
+fn ova_archive_member<'a>(
+    vm: &'a ImportableVm,
+    disk_path: &'a Path,
+) -> Option<(&'a Path, &'a str)> {
+    if vm.source != ImportSource::Ova {
+        return None;
+    }
+    let archive = vm.config_path.as_path();
+    let is_ova = archive
+        .extension()
+        .and_then(|e| e.to_str())
+        .is_some_and(|e| e.eq_ignore_ascii_case("ova"));
+    if !is_ova {
+        return None;
+    }
+    let member = disk_path.strip_prefix(archive).ok()?.to_str()?;
+    Some((archive, member))
+}
+
+/// Extract one file from a tar archive into `dir`, returning its path
+fn extract_tar_member(archive: &Path, member: &str, dir: &Path) -> Result<PathBuf> {
+    let output = std::process::Command::new("tar")
+        .arg("-xf")
+        .arg(archive)
+        .arg("-C")
+        .arg(dir)
+        .arg(member)
+        .output()
+        .context("Failed to run tar")?;
+    if !output.status.success() {
+        bail!(
+            "Failed to extract {} from {}: {}",
+            member,
+            archive.display(),
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+    Ok(dir.join(member))
+}
+
 // =========================================================================
 // Mapping Helpers
 // =========================================================================
@@ -0,0 +1,250 @@
+//! Guess the guest OS on an installer ISO
+//!
+//! Reads the ISO9660 primary volume label and a few well-known files
+//! (`.disk/info` on Ubuntu-family media, `sources/` on Windows media) so the
+//! create wizard can suggest a matching QEMU profile. Anything ambiguous
+//! returns `None` and the user's own choice stands.
+
+use std::fs::File;
+use std::io::{Read, Seek, SeekFrom};
+use std::path::Path;
+
+const SECTOR_SIZE: u64 = 2048;
+/// The primary volume descriptor lives in sector 16
+const PVD_SECTOR: u64 = 16;
+/// Never read more than this much of a signature file
+const MAX_FILE_READ: u32 = 4096;
+/// Guard against corrupt directory extents
+const MAX_DIR_SIZE: u32 = 1024 * 1024;
+
+/// A confident guess at the OS on an ISO
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IsoDetection {
+    /// Profile id from `qemu_profiles.toml`
+    pub profile_id: String,
+    /// What was recognized, e.g. "Ubuntu 24.04 LTS" or the volume label
+    pub description: String,
+}
+
+/// Volume label / release name keywords, most specific first
+const LABEL_PATTERNS: &[(&[&str], &str)] = &[
+    (&["linux mint", "linuxmint"], "linux-mint"),
+    (&["pop_os", "pop!_os", "pop-os"], "linux-pop"),
+    (&["zorin"], "linux-zorin"),
+    (&["elementary"], "linux-elementary"),
+    (&["ubuntu"], "linux-ubuntu"),
+    (&["fedora"], "linux-fedora"),
+    (&["debian"], "linux-debian"),
+    (&["kali"], "linux-kali"),
+    (&["manjaro"], "linux-manjaro"),
+    (&["endeavour"], "linux-endeavouros"),
+    (&["garuda"], "linux-garuda"),
+    (&["cachyos"], "linux-cachyos"),
+    (&["arch_", "archlinux"], "linux-arch"),
+    (&["opensuse-leap", "opensuse leap"], "linux-opensuse-leap"),
+    (&["opensuse"], "linux-suse"),
+    (&["almalinux"], "linux-alma"),
+    (&["rocky"], "linux-rocky"),
+    (&["centos"], "linux-centos"),
+    (&["nixos"], "linux-nixos"),
+    (&["alpine"], "linux-alpine"),
+    (&["void"], "linux-void"),
+    (&["gentoo"], "linux-gentoo"),
+    (&["bazzite"], "linux-bazzite"),
+    (&["proxmox", "pve"], "proxmox"),
+    (&["truenas"], "truenas-scale"),
+    (&["gparted"], "gparted-live"),
+    (&["clonezilla"], "clonezilla"),
+    (&["openbsd"], "openbsd"),
+    (&["netbsd"], "netbsd"),
+    (&["freebsd"], "freebsd"),
+    (&["haiku"], "haiku"),
+    (&["reactos"], "reactos"),
+    (&["freedos"], "freedos"),
+    (&["kolibri"], "kolibrios"),
+];
+
+/// Windows media labels that pin down a release. Windows 10 and 11 share
+/// `CCCOMA_*` labels and layouts, so those are left to the user.
+const WINDOWS_LABEL_PATTERNS: &[(&str, &str)] = &[
+    ("grmc", "windows-7"),
+    ("gsp1rm", "windows-7"),
+    ("ir5_", "windows-81"),
+    ("ir3_", "windows-81"),
+    ("hrm_", "windows-8"),
+    ("wxp", "windows-xp"),
+];
+
+/// Guess the OS on an ISO image, or `None` if detection is inconclusive
+pub fn detect_iso_os(path: &Path) -> Option<IsoDetection> {
+    let mut file = File::open(path).ok()?;
+    detect_iso_os_from(&mut file)
+}
+
+pub(crate) fn detect_iso_os_from<R: Read + Seek>(iso: &mut R) -> Option<IsoDetection> {
+    let pvd = read_extent(iso, PVD_SECTOR, SECTOR_SIZE as u32)?;
+    if pvd[0] != 1 || &pvd[1..6] != b"CD001" {
+        return None;
+    }
+    let label = String::from_utf8_lossy(&pvd[40..72]).trim().to_string();
+    let root = DirEntry::parse(&pvd[156..190])?;
+    let entries = read_dir(iso, &root);
+
+    // Ubuntu-family media name the exact release in .disk/info
+    let disk_info = find_entry(&entries, ".disk")
+        .filter(|d| d.is_dir)
+        .and_then(|d| find_entry(&read_dir(iso, d), "info").cloned())
+        .and_then(|f| read_file(iso, &f));
+    if let Some(info) = disk_info {
+        let release = release_name(info.lines().next().unwrap_or(""));
+        if let Some(profile_id) = match_label(&release) {
+            return Some(IsoDetection {
+                profile_id: profile_id.to_string(),
+                description: release,
+            });
+        }
+    }
+
+    let label_lower = label.to_lowercase();
+    let has_dir = |name: &str| find_entry(&entries, name).is_some_and(|e| e.is_dir);
+    if has_dir("sources") || has_dir("i386") {
+        return WINDOWS_LABEL_PATTERNS
+            .iter()
+            .find(|(prefix, _)| label_lower.starts_with(prefix))
+            .map(|(_, profile_id)| IsoDetection {
+                profile_id: profile_id.to_string(),
+                description: label.clone(),
+            });
+    }
+
+    match_label(&label).map(|profile_id| IsoDetection {
+        profile_id: profile_id.to_string(),
+        description: label,
+    })
+}
+
+fn match_label(label: &str) -> Option<&'static str> {
+    let lower = label.to_lowercase();
+    LABEL_PATTERNS
+        .iter()
+        .find(|(keywords, _)| keywords.iter().any(|k| lower.contains(k)))
+        .map(|(_, profile_id)| *profile_id)
+}
+
+/// `Ubuntu 24.04 LTS "Noble Numbat" - Release amd64 (20240424)` -> `Ubuntu 24.04 LTS`
+fn release_name(info: &str) -> String {
+    info.split(['"', '('])
+        .next()
+        .unwrap_or("")
+        .split(" - ")
+        .next()
+        .unwrap_or("")
+        .trim()
+        .to_string()
+}
+
+#[derive(Debug, Clone)]
+struct DirEntry {
+    name: String,
+    lba: u32,
+    size: u32,
+    is_dir: bool,
+}
+
+impl DirEntry {
+    /// Parse one ISO9660 directory record, preferring a Rock Ridge name
+    fn parse(record: &[u8]) -> Option<Self> {
+        let len = *record.first()? as usize;
+        if len < 34 || record.len() < len {
+            return None;
+        }
+        let lba = u32::from_le_bytes(record[2..6].try_into().ok()?);
+        let size = u32::from_le_bytes(record[10..14].try_into().ok()?);
+        let is_dir = record[25] & 0x02 != 0;
+        let name_len = record[32] as usize;
+        let raw_name = record.get(33..33 + name_len)?;
+
+        // System use area follows the name, padded to an even offset
+        let su_start = 33 + name_len + (name_len + 1) % 2;
+        let name = record
+            .get(su_start..len)
+            .and_then(rock_ridge_name)
+            .unwrap_or_else(|| {
+                let name = String::from_utf8_lossy(raw_name);
+                let name = name.split(';').next().unwrap_or("");
+                name.trim_end_matches('.').to_string()
+            });
+
+        Some(Self {
+            name,
+            lba,
+            size,
+            is_dir,
+        })
+    }
+}
+
+/// The `NM` (alternate name) entry from a Rock Ridge system use area
+fn rock_ridge_name(mut su: &[u8]) -> Option<String> {
+    while su.len() >= 4 {
+        let len = su[2] as usize;
+        if len < 4 || len > su.len() {
+            return None;
+        }
+        if &su[0..2] == b"NM" && len > 5 {
+            return Some(String::from_utf8_lossy(&su[5..len]).into_owned());
+        }
+        su = &su[len..];
+    }
+    None
+}
+
+fn read_extent<R: Read + Seek>(iso: &mut R, lba: u64, size: u32) -> Option<Vec<u8>> {
+    iso.seek(SeekFrom::Start(lba * SECTOR_SIZE)).ok()?;
+    let mut buf = vec![0u8; size as usize];
+    iso.read_exact(&mut buf).ok()?;
+    Some(buf)
+}
+
+fn read_dir<R: Read + Seek>(iso: &mut R, dir: &DirEntry) -> Vec<DirEntry> {
+    let Some(data) = read_extent(iso, dir.lba as u64, dir.size.min(MAX_DIR_SIZE)) else {
+        return Vec::new();
+    };
+    let mut entries = Vec::new();
+    let mut offset = 0;
+    while offset < data.len() {
+        let len = data[offset] as usize;
+        if len == 0 {
+            // Records never span sectors; skip the padding to the next one
+            offset = (offset / SECTOR_SIZE as usize + 1) * SECTOR_SIZE as usize;
+            continue;
+        }
+        let Some(entry) = data.get(offset..).and_then(DirEntry::parse) else {
+            break;
+        };
+        // Skip the "." and ".." records (names 0x00 and 0x01)
+        let name_byte = data.get(offset + 33).copied().unwrap_or(0);
+        if data[offset + 32] != 1 || name_byte > 1 {
+            entries.push(entry);
+        }
+        offset += len;
+    }
+    entries
+}
+
+fn read_file<R: Read + Seek>(iso: &mut R, file: &DirEntry) -> Option<String> {
+    let data = read_extent(iso, file.lba as u64, file.size.min(MAX_FILE_READ))?;
+    Some(String::from_utf8_lossy(&data).into_owned())
+}
+
+/// Case-insensitive lookup; plain ISO9660 names spell a leading `.` as `_`
+fn find_entry<'a>(entries: &'a [DirEntry], name: &str) -> Option<&'a DirEntry> {
+    let mangled = name.replacen('.', "_", 1);
+    entries
+        .iter()
+        .find(|e| e.name.eq_ignore_ascii_case(name) || e.name.eq_ignore_ascii_case(&mangled))
+}
+
+#[cfg(test)]
+#[path = "tests/iso_detect.rs"]
+mod tests;
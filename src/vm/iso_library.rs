@@ -0,0 +1,115 @@
+//! ISO Library
+//!
+//! Install media kept in one directory (`isos/` in the VM library unless
+//! configured otherwise). The wizard's downloads land there, and the ISO
+//! Library screen lists each image with its size and detected OS so unused
+//! ones can be deleted.
+
+use anyhow::{bail, Context, Result};
+use std::path::{Path, PathBuf};
+
+use super::discovery::DiscoveredVm;
+use super::iso_detect::{detect_iso_os, IsoDetection};
+
+/// Suffix of a download that hasn't finished yet
+const PARTIAL_SUFFIX: &str = ".part";
+
+/// An ISO in the library directory
+#[derive(Debug, Clone)]
+pub struct LibraryIso {
+    pub path: PathBuf,
+    /// File name, without the `.part` of an unfinished download
+    pub name: String,
+    pub size_bytes: u64,
+    pub modified: Option<chrono::DateTime<chrono::Local>>,
+    /// An interrupted download that resumes the next time it is fetched
+    pub partial: bool,
+    /// The OS on the image, if it could be recognized
+    pub os: Option<IsoDetection>,
+}
+
+fn is_iso_name(name: &str) -> bool {
+    name.to_lowercase().ends_with(".iso")
+}
+
+/// ISOs (and unfinished ISO downloads) in `dir`, sorted by name. A missing
+/// directory is an empty library.
+pub fn list_isos(dir: &Path) -> Vec<LibraryIso> {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return Vec::new();
+    };
+    let mut isos: Vec<LibraryIso> = entries
+        .flatten()
+        .filter_map(|entry| {
+            let file_name = entry.file_name().into_string().ok()?;
+            let (name, partial) = match file_name.strip_suffix(PARTIAL_SUFFIX) {
+                Some(name) => (name.to_string(), true),
+                None => (file_name, false),
+            };
+            if !is_iso_name(&name) {
+                return None;
+            }
+            let path = entry.path();
+            let meta = std::fs::metadata(&path).ok().filter(|m| m.is_file())?;
+            Some(LibraryIso {
+                os: if partial { None } else { detect_iso_os(&path) },
+                size_bytes: meta.len(),
+                modified: meta.modified().ok().map(chrono::DateTime::from),
+                path,
+                name,
+                partial,
+            })
+        })
+        .collect();
+    isos.sort_by(|a, b| {
+        a.name
+            .to_lowercase()
+            .cmp(&b.name.to_lowercase())
+            .then(a.partial.cmp(&b.partial))
+    });
+    isos
+}
+
+/// Whether `iso` is the file a download to `dest` is writing (its `.part`)
+/// or will finish as
+pub fn is_download_target(iso: &LibraryIso, dest: &Path) -> bool {
+    iso.path.with_file_name(&iso.name) == dest
+}
+
+/// Names of the VMs whose launch.sh refers to the ISO at `path`
+pub fn vms_using_iso(vms: &[DiscoveredVm], path: &Path) -> Vec<String> {
+    let needle = path.display().to_string();
+    // `/isos/a.iso` shouldn't match `/isos/a.iso.bak`
+    let ends_path = |rest: &str| {
+        rest.chars()
+            .next()
+            .is_none_or(|c| !(c.is_alphanumeric() || "._-".contains(c)))
+    };
+    vms.iter()
+        .filter(|vm| {
+            std::fs::read_to_string(&vm.launch_script).is_ok_and(|script| {
+                script
+                    .match_indices(&needle)
+                    .any(|(i, _)| ends_path(&script[i + needle.len()..]))
+            })
+        })
+        .map(|vm| vm.display_name())
+        .collect()
+}
+
+/// Permanently delete an ISO from the library. `downloading` is the target
+/// of the running ISO download, which is never deleted out from under curl.
+pub fn delete_iso(iso: &LibraryIso, downloading: Option<&Path>) -> Result<()> {
+    if downloading.is_some_and(|dest| is_download_target(iso, dest)) {
+        bail!(
+            "{} is still downloading. Cancel the download before deleting it.",
+            iso.name
+        );
+    }
+    std::fs::remove_file(&iso.path)
+        .with_context(|| format!("Failed to delete {}", iso.path.display()))
+}
+
+#[cfg(test)]
+#[path = "tests/iso_library.rs"]
+mod tests;
@@ -0,0 +1,212 @@
+//! Guest keyboard layout (`-k`)
+//!
+//! QEMU sends the guest raw PC scancodes from GTK and SDL windows, but a VNC
+//! display only gets keysyms and has to translate them with a keymap. Without
+//! `-k` that is en-us, so non-US keyboards type the wrong characters.
+
+use anyhow::{bail, Context, Result};
+use std::path::Path;
+
+use super::discovery::DiscoveredVm;
+
+/// Keymaps shipped with QEMU (`pc-bios/keymaps`), used when the installed
+/// keymap directory can't be read
+pub const QEMU_KEYMAPS: &[&str] = &[
+    "ar", "bepo", "cz", "da", "de", "de-ch", "en-gb", "en-us", "es", "et", "fi", "fo", "fr",
+    "fr-be", "fr-ca", "fr-ch", "hr", "hu", "is", "it", "ja", "lt", "lv", "mk", "nl", "no", "pl",
+    "pt", "pt-br", "ru", "sl", "sv", "th", "tr",
+];
+
+/// Where distributions install QEMU's keymaps
+const KEYMAP_DIRS: &[&str] = &["/usr/share/qemu/keymaps", "/usr/local/share/qemu/keymaps"];
+
+/// Keymaps the installed QEMU accepts for `-k`, sorted
+pub fn available_keymaps() -> Vec<String> {
+    for dir in KEYMAP_DIRS {
+        let Ok(entries) = std::fs::read_dir(dir) else {
+            continue;
+        };
+        // Keymaps are extension-less files; skip build files like meson.build
+        let mut keymaps: Vec<String> = entries
+            .flatten()
+            .filter(|e| e.file_type().is_ok_and(|t| t.is_file()))
+            .filter_map(|e| e.file_name().into_string().ok())
+            .filter(|name| !name.contains('.') && name != "common" && name != "modifiers")
+            .collect();
+        if !keymaps.is_empty() {
+            keymaps.sort();
+            return keymaps;
+        }
+    }
+    QEMU_KEYMAPS.iter().map(|k| k.to_string()).collect()
+}
+
+/// Whether QEMU accepts `keymap` for `-k`
+pub fn is_valid_keymap(keymap: &str) -> bool {
+    available_keymaps().iter().any(|k| k == keymap)
+}
+
+/// QEMU keymap for an XKB layout and variant (`de`, `ch` + `fr`), or a
+/// console keymap name (`de-latin1`, `uk`, `jp106`)
+pub(crate) fn keymap_for_layout(layout: &str, variant: &str) -> Option<&'static str> {
+    let layout = layout.split(',').next()?.trim().to_lowercase();
+    let variant = variant.split(',').next().unwrap_or("").trim();
+    // Console keymaps add a suffix (de-latin1-nodeadkeys, jp106, fr_CH)
+    let (base, console_variant) = match layout.split_once(['-', '_']) {
+        Some((base, rest)) => (base.to_string(), rest.to_string()),
+        None => (layout.clone(), String::new()),
+    };
+    let base = base.trim_end_matches(|c: char| c.is_ascii_digit());
+    let variant = if variant.is_empty() {
+        console_variant.as_str()
+    } else {
+        variant
+    };
+    let keymap = match base {
+        "us" => "en-us",
+        "gb" | "uk" => "en-gb",
+        "ch" if variant.starts_with("fr") => "fr-ch",
+        "ch" | "sg" => "de-ch",
+        "fr" if variant == "bepo" => "bepo",
+        "fr" if variant.eq_ignore_ascii_case("ch") => "fr-ch",
+        "be" => "fr-be",
+        "ca" => "fr-ca",
+        "br" => "pt-br",
+        "dk" => "da",
+        "se" => "sv",
+        "ee" => "et",
+        "si" => "sl",
+        "jp" => "ja",
+        "ara" => "ar",
+        other => QEMU_KEYMAPS.iter().find(|k| **k == other)?,
+    };
+    Some(keymap)
+}
+
+/// The host's keyboard layout as a QEMU keymap, from `XKB_DEFAULT_LAYOUT`,
+/// the X11 keyboard config written by `localectl`, `/etc/vconsole.conf` or
+/// Debian's `/etc/default/keyboard`
+pub fn host_keymap() -> Option<&'static str> {
+    if let Ok(layout) = std::env::var("XKB_DEFAULT_LAYOUT") {
+        let variant = std::env::var("XKB_DEFAULT_VARIANT").unwrap_or_default();
+        if let Some(keymap) = keymap_for_layout(&layout, &variant) {
+            return Some(keymap);
+        }
+    }
+    if let Ok(conf) = std::fs::read_to_string("/etc/X11/xorg.conf.d/00-keyboard.conf") {
+        if let Some(keymap) = keymap_from_xorg_conf(&conf) {
+            return Some(keymap);
+        }
+    }
+    ["/etc/vconsole.conf", "/etc/default/keyboard"]
+        .iter()
+        .filter_map(|path| std::fs::read_to_string(path).ok())
+        .find_map(|conf| keymap_from_shell_conf(&conf))
+}
+
+/// `Option "XkbLayout" "de"` (and `XkbVariant`) from an xorg.conf snippet
+pub(crate) fn keymap_from_xorg_conf(conf: &str) -> Option<&'static str> {
+    let option = |name: &str| {
+        conf.lines().find_map(|line| {
+            let rest = line.trim().strip_prefix("Option")?.trim();
+            let rest = rest.strip_prefix(&format!("\"{}\"", name))?;
+            Some(rest.trim().trim_matches('"').to_string())
+        })
+    };
+    keymap_for_layout(
+        &option("XkbLayout")?,
+        &option("XkbVariant").unwrap_or_default(),
+    )
+}
+
+/// `XKBLAYOUT=`/`XKBVARIANT=`, or else the console `KEYMAP=`, from a
+/// vconsole.conf-style file
+pub(crate) fn keymap_from_shell_conf(conf: &str) -> Option<&'static str> {
+    let value = |key: &str| {
+        conf.lines().find_map(|line| {
+            let value = line.trim().strip_prefix(key)?.strip_prefix('=')?;
+            Some(value.trim().trim_matches('"').to_string())
+        })
+    };
+    value("XKBLAYOUT")
+        .and_then(|layout| keymap_for_layout(&layout, &value("XKBVARIANT").unwrap_or_default()))
+        .or_else(|| keymap_for_layout(&value("KEYMAP")?, ""))
+}
+
+/// The keymap after `current` in `keymaps`, for stepping through them in the
+/// UI. Unset starts at `host` (or the first keymap); the last one wraps
+/// back to unset. `backward` steps the other way.
+pub fn next_keymap(
+    keymaps: &[String],
+    current: Option<&str>,
+    host: Option<&str>,
+    backward: bool,
+) -> Option<String> {
+    let pos = current.and_then(|c| keymaps.iter().position(|k| k == c));
+    let next = match (pos, backward) {
+        (None, false) => host
+            .and_then(|h| keymaps.iter().position(|k| k == h))
+            .or((!keymaps.is_empty()).then_some(0)),
+        (None, true) => keymaps.len().checked_sub(1),
+        (Some(i), false) => (i + 1 < keymaps.len()).then_some(i + 1),
+        (Some(i), true) => i.checked_sub(1),
+    };
+    next.map(|i| keymaps[i].clone())
+}
+
+/// The `-k` keymap a launch script sets, if any
+pub fn script_keymap(content: &str) -> Option<String> {
+    content
+        .lines()
+        .filter(|line| !line.trim_start().starts_with('#'))
+        .find_map(|line| {
+            let mut words = line.split_whitespace();
+            words.find(|w| *w == "-k")?;
+            let value = words.next()?.trim_matches(|c| c == '"' || c == '\'');
+            (!value.is_empty() && value != "\\").then(|| value.to_string())
+        })
+}
+
+/// Set (or with `None` remove) `-k` on every QEMU command in a launch script
+pub fn set_script_keymap(content: &str, keymap: Option<&str>) -> String {
+    let existing = regex::Regex::new(r#"\s-k\s+["']?[\w-]+["']?"#).expect("valid regex");
+    let stripped: Vec<String> = content
+        .lines()
+        .map(|line| {
+            if line.trim_start().starts_with('#') {
+                line.to_string()
+            } else {
+                existing.replace_all(line, "").to_string()
+            }
+        })
+        .collect();
+    let mut stripped = stripped.join("\n");
+    if content.ends_with('\n') {
+        stripped.push('\n');
+    }
+    match keymap {
+        Some(keymap) => {
+            super::lifecycle::insert_after_qemu_command(&stripped, &format!("-k {}", keymap))
+                .unwrap_or(stripped)
+        }
+        None => stripped,
+    }
+}
+
+/// Save a VM's keyboard layout to its launch.sh (`None` = QEMU's en-us)
+pub fn save_keymap(vm: &DiscoveredVm, keymap: Option<&str>) -> Result<()> {
+    if let Some(keymap) = keymap {
+        if !is_valid_keymap(keymap) {
+            bail!("QEMU has no keymap named '{}'", keymap);
+        }
+    }
+    let script_path: &Path = &vm.launch_script;
+    let content = std::fs::read_to_string(script_path).context("Failed to read launch.sh")?;
+    std::fs::write(script_path, set_script_keymap(&content, keymap))
+        .context("Failed to write launch.sh")?;
+    Ok(())
+}
+
+#[cfg(test)]
+#[path = "tests/keymap.rs"]
+mod tests;
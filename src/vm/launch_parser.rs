@@ -51,6 +51,7 @@ pub fn parse_launch_script(script_path: &Path, content: &str) -> Result<QemuConf
 
     // Check for TPM
     config.tpm = content.contains("-tpmdev") || content.contains("swtpm");
+    config.secure_boot = content.contains("cfi.pflash01,property=secure,value=on");
 
     // Extract BIOS/ROM path (for classic Mac and other custom firmware)
     config.bios_path = extract_bios_path(content, vm_dir);
@@ -58,9 +59,18 @@ pub fn parse_launch_script(script_path: &Path, content: &str) -> Result<QemuConf
     // Extract disks
     config.disks = extract_disks(content, vm_dir);
 
+    // Extract direct kernel boot
+    config.direct_kernel = extract_direct_kernel(content, vm_dir);
+
+    // Extract serial console socket
+    config.serial_socket = extract_serial_socket(content, vm_dir);
+
     // Extract network config
     config.network = extract_network(content);
 
+    // Extract RTC settings
+    config.rtc = extract_rtc(content);
+
     // Extract extra arguments we don't specifically parse
     config.extra_args = extract_extra_args(content);
 
@@ -124,6 +134,8 @@ fn extract_cpu_cores(content: &str) -> Option<u32> {
         // Look for -smp
         if let Some(idx) = line.find("-smp ") {
             let rest = &line[idx + 5..];
+            // `-smp cpus=N,sockets=...` names the count explicitly
+            let rest = rest.strip_prefix("cpus=").unwrap_or(rest);
             let value: String = rest.chars().take_while(|c| c.is_ascii_digit()).collect();
             if let Ok(cores) = value.parse::<u32>() {
                 return Some(cores);
@@ -146,6 +158,8 @@ fn extract_cpu_model(content: &str) -> Option<String> {
                 .chars()
                 .take_while(|c| !c.is_whitespace() && *c != '\\')
                 .collect();
+            // Models with flags are single-quoted by the generator ('host,kvm=off')
+            let model = model.trim_matches('\'').to_string();
             if !model.is_empty() {
                 return Some(model);
             }
@@ -393,13 +407,14 @@ fn extract_disks(content: &str, vm_dir: &Path) -> Vec<DiskConfig> {
                         path: full_path,
                         format,
                         interface: "ide".to_string(),
+                        throttle: Default::default(),
                     });
                 }
             }
         }
 
-        // Look for -drive file=
-        if line.contains("-drive") && line.contains("file=") {
+        // Look for -drive file= (floppies are media, like -fda)
+        if line.contains("-drive") && line.contains("file=") && !line.contains("if=floppy") {
             if let Some(path) = extract_drive_file(line) {
                 let expanded = expand_variables(&path, &vars, vm_dir);
                 let full_path = resolve_path(&expanded, vm_dir);
@@ -415,6 +430,7 @@ fn extract_disks(content: &str, vm_dir: &Path) -> Vec<DiskConfig> {
                     path: full_path,
                     format,
                     interface: interface.to_string(),
+                    throttle: DiskThrottle::parse(line),
                 });
             }
         }
@@ -423,6 +439,102 @@ fn extract_disks(content: &str, vm_dir: &Path) -> Vec<DiskConfig> {
     disks
 }
 
+/// Indices of the lines whose `-drive` attaches `disk`, matched the way
+/// [`extract_disks`] resolves paths
+pub(crate) fn drive_lines_of(content: &str, vm_dir: &Path, disk: &Path) -> Vec<usize> {
+    let vars = extract_shell_variables(content, vm_dir);
+    content
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| {
+            !line.trim_start().starts_with('#') && line.contains("-drive") && line.contains("file=")
+        })
+        .filter(|(_, line)| {
+            extract_drive_file(line).is_some_and(|path| {
+                resolve_path(&expand_variables(&path, &vars, vm_dir), vm_dir) == disk
+            })
+        })
+        .map(|(i, _)| i)
+        .collect()
+}
+
+/// Extract direct kernel boot (-kernel/-initrd/-append), written either
+/// straight into a QEMU command or in the managed `DIRECT_KERNEL_ARGS` array
+fn extract_direct_kernel(content: &str, vm_dir: &Path) -> Option<DirectKernelBoot> {
+    let vars = extract_shell_variables(content, vm_dir);
+    let arrays = content
+        .lines()
+        .filter_map(|line| {
+            line.trim()
+                .strip_prefix("DIRECT_KERNEL_ARGS=(")?
+                .strip_suffix(')')
+        })
+        .map(str::to_string);
+
+    for words in arrays.chain(qemu_commands(content)) {
+        let tokens = split_shell_words(&words);
+        let mut boot = DirectKernelBoot::default();
+        for pair in tokens.windows(2) {
+            let path = || resolve_path(&expand_variables(&pair[1], &vars, vm_dir), vm_dir);
+            match pair[0].as_str() {
+                "-kernel" => boot.kernel = path(),
+                "-initrd" => boot.initrd = Some(path()),
+                "-append" => boot.append = pair[1].clone(),
+                _ => {}
+            }
+        }
+        if boot.is_enabled() {
+            return Some(boot);
+        }
+    }
+    None
+}
+
+/// Extract the Unix socket backing the first serial port: `-serial unix:PATH`,
+/// or a `-chardev socket` used by `-serial chardev:ID` or a serial device
+fn extract_serial_socket(content: &str, vm_dir: &Path) -> Option<PathBuf> {
+    let vars = extract_shell_variables(content, vm_dir);
+    let resolve = |path: &str| resolve_path(&expand_variables(path, &vars, vm_dir), vm_dir);
+
+    for command in qemu_commands(content) {
+        let tokens = split_shell_words(&command);
+        let mut chardevs: HashMap<&str, &str> = HashMap::new();
+        let mut serial_ids: Vec<&str> = Vec::new();
+
+        for pair in tokens.windows(2) {
+            let opts = || pair[1].split(',');
+            let opt = |key: &str| opts().find_map(|o| o.strip_prefix(key));
+            match pair[0].as_str() {
+                "-serial" => {
+                    if let Some(path) = pair[1].strip_prefix("unix:") {
+                        return Some(resolve(path.split(',').next().unwrap_or_default()));
+                    }
+                    if let Some(id) = pair[1].strip_prefix("chardev:") {
+                        serial_ids.push(id);
+                    }
+                }
+                "-chardev" if pair[1].starts_with("socket,") => {
+                    if let (Some(id), Some(path)) = (opt("id="), opt("path=")) {
+                        chardevs.insert(id, path);
+                    }
+                }
+                "-device" if SERIAL_DEVICES.iter().any(|d| pair[1].starts_with(d)) => {
+                    serial_ids.extend(opt("chardev="));
+                }
+                _ => {}
+            }
+        }
+
+        if let Some(path) = serial_ids.iter().find_map(|id| chardevs.get(id)) {
+            return Some(resolve(path));
+        }
+    }
+    None
+}
+
+/// Device models that put a chardev on a guest serial console
+const SERIAL_DEVICES: &[&str] = &["isa-serial,", "pci-serial,", "virtconsole,"];
+
 /// Extract file path from -drive file= argument
 fn extract_drive_file(line: &str) -> Option<String> {
     if let Some(idx) = line.find("file=") {
@@ -612,6 +724,14 @@ fn extract_network(content: &str) -> Option<NetworkConfig> {
         }
     }
 
+    // Written by the network shaping section
+    if let Some(args) = content
+        .lines()
+        .find_map(|line| line.trim().strip_prefix("NET_SHAPING="))
+    {
+        config.shaping = NetworkShaping::parse_netem(args.trim_matches('"'));
+    }
+
     if has_network || content.contains("-net") || content.contains("-nic") {
         Some(config)
     } else {
@@ -722,6 +842,24 @@ fn extract_bios_path(content: &str, vm_dir: &Path) -> Option<PathBuf> {
     None
 }
 
+/// Extract the `-rtc` settings (the first `-rtc` outside comments)
+fn extract_rtc(content: &str) -> RtcConfig {
+    content
+        .lines()
+        .filter(|line| !line.trim_start().starts_with('#'))
+        .find_map(|line| {
+            let idx = line.find("-rtc ")?;
+            let value: String = line[idx + 5..]
+                .trim_start()
+                .chars()
+                .take_while(|c| !c.is_whitespace() && *c != '\\')
+                .filter(|c| *c != '"' && *c != '\'')
+                .collect();
+            Some(RtcConfig::parse(&value))
+        })
+        .unwrap_or_default()
+}
+
 /// Extract extra arguments we don't specifically handle
 fn extract_extra_args(content: &str) -> Vec<String> {
     let mut args = Vec::new();
@@ -751,13 +889,269 @@ fn extract_extra_args(content: &str) -> Vec<String> {
     }
 
     // Look for RTC settings
-    if content.contains("-rtc base=localtime") {
-        args.push("-rtc base=localtime".to_string());
+    if let Some(rtc) = extract_rtc(content).to_arg() {
+        args.push(rtc);
     }
 
     args
 }
 
+/// QEMU options `parse_launch_script` understands, or that vm-curator
+/// generates and manages itself, and which take a value.
+const KNOWN_VALUE_OPTIONS: &[&str] = &[
+    "-accel",
+    "-append",
+    "-audiodev",
+    "-bios",
+    "-boot",
+    "-cdrom",
+    "-chardev",
+    "-cpu",
+    "-device",
+    "-display",
+    "-drive",
+    "-fda",
+    "-fsdev",
+    "-global",
+    "-hda",
+    "-hdb",
+    "-hdc",
+    "-hdd",
+    "-initrd",
+    "-k",
+    "-kernel",
+    "-M",
+    "-m",
+    "-machine",
+    "-name",
+    "-net",
+    "-netdev",
+    "-nic",
+    "-qmp",
+    "-rtc",
+    "-smbios",
+    "-smp",
+    "-tpmdev",
+    "-vga",
+];
+
+/// Value-less QEMU options understood by the parser.
+const KNOWN_FLAGS: &[&str] = &["-enable-kvm", "-usb"];
+
+/// `-device` models the parser understands or vm-curator adds itself
+/// (matched as prefixes of the model name).
+const KNOWN_DEVICES: &[&str] = &[
+    // Graphics
+    "VGA",
+    "cirrus-vga",
+    "qxl",
+    "virtio-vga",
+    "virtio-gpu",
+    "vmware-svga",
+    // Storage and install media
+    "ich9-ahci",
+    "ide-cd",
+    "ide-hd",
+    "scsi-cd",
+    "scsi-hd",
+    // Audio
+    "AC97",
+    "ac97",
+    "es1370",
+    "hda-",
+    "intel-hda",
+    "sb16",
+    // Network
+    "e1000",
+    "rtl8139",
+    "virtio-net",
+    // Input, guest channels, TPM and passthrough
+    "isa-applesmc",
+    "tpm-tis",
+    "usb-host",
+    "usb-kbd",
+    "usb-tablet",
+    "vfio-pci",
+    "virtio-9p",
+    "virtio-balloon",
+    "virtio-keyboard",
+    "virtio-mouse",
+    "virtio-serial",
+    "virtio-tablet",
+    "virtserialport",
+];
+
+/// `-chardev` ids the generator gives its own channels (SPICE agent, guest
+/// agent, TPM); any other chardev belongs to a hand-added device
+const GENERATED_CHARDEV_IDS: &[&str] = &["spicechannel0", "qga0", "chrtpm"];
+
+/// QEMU options in the script's QEMU commands that the structured config
+/// does not capture, as `-option value` strings in first-seen order.
+///
+/// Shell variable expansions (vm-curator's managed sections) are skipped;
+/// they are accounted for separately.
+pub fn unrecognized_args(content: &str) -> Vec<String> {
+    scan_unmodeled_args(content, false)
+        .into_iter()
+        .map(|(arg, _)| arg)
+        .collect()
+}
+
+/// The options a regenerated script has to carry over verbatim: those in
+/// [`unrecognized_args`] plus a socket-backed serial console, as written in
+/// the script (quotes and `$VM_DIR` kept).
+pub fn carried_over_args(content: &str) -> Vec<String> {
+    scan_unmodeled_args(content, true)
+        .into_iter()
+        .map(|(_, raw)| raw)
+        .collect()
+}
+
+/// Unmodeled options as (unquoted, as written) pairs
+fn scan_unmodeled_args(content: &str, with_serial_socket: bool) -> Vec<(String, String)> {
+    let mut found: Vec<(String, String)> = Vec::new();
+
+    for command in qemu_commands(content) {
+        let words = shell_words(&command);
+        let mut i = 1; // words[0] is the emulator
+        while i < words.len() {
+            let (token, raw_token) = &words[i];
+            i += 1;
+            if !token.starts_with('-') {
+                continue;
+            }
+            let value = words
+                .get(i)
+                .filter(|(v, _)| !v.starts_with('-') && !v.starts_with('$'));
+            let with_value = |(v, raw): &(String, String)| {
+                (format!("{} {}", token, v), format!("{} {}", raw_token, raw))
+            };
+
+            let arg = if KNOWN_FLAGS.contains(&token.as_str()) {
+                None
+            } else if token == "-serial"
+                && value.is_some_and(|(v, _)| v.starts_with("unix:") || v.starts_with("chardev:"))
+            {
+                // Socket-backed serial consoles are what Attach Console uses
+                i += 1;
+                value.filter(|_| with_serial_socket).map(with_value)
+            } else if token == "-chardev" {
+                i += 1;
+                let id = value.and_then(|(v, _)| v.split(',').find_map(|o| o.strip_prefix("id=")));
+                id.filter(|id| !GENERATED_CHARDEV_IDS.contains(id))
+                    .and(value)
+                    .map(with_value)
+            } else if KNOWN_VALUE_OPTIONS.contains(&token.as_str()) {
+                i += 1;
+                let model = value
+                    .and_then(|(v, _)| v.split(',').next())
+                    .unwrap_or_default();
+                if token == "-device" && !KNOWN_DEVICES.iter().any(|d| model.starts_with(d)) {
+                    Some(
+                        value
+                            .map(with_value)
+                            .unwrap_or_else(|| ("-device ".to_string(), format!("{} ", raw_token))),
+                    )
+                } else {
+                    None
+                }
+            } else {
+                match value {
+                    Some(v) => {
+                        i += 1;
+                        Some(with_value(v))
+                    }
+                    None => Some((token.clone(), raw_token.clone())),
+                }
+            };
+
+            if let Some(arg) = arg {
+                if !found.iter().any(|(seen, _)| *seen == arg.0) {
+                    found.push(arg);
+                }
+            }
+        }
+    }
+
+    found
+}
+
+/// Each QEMU invocation in a script, with line continuations joined.
+fn qemu_commands(content: &str) -> Vec<String> {
+    let mut commands = Vec::new();
+    let mut lines = content.lines();
+    while let Some(line) = lines.next() {
+        let trimmed = line.trim();
+        let is_qemu = trimmed.starts_with("qemu-system-")
+            || trimmed.starts_with("exec qemu-system-")
+            || trimmed.starts_with("\"$QEMU\"")
+            || trimmed.starts_with("$QEMU ");
+        if !is_qemu {
+            continue;
+        }
+        let mut command = trimmed.trim_start_matches("exec ").to_string();
+        while command.ends_with('\\') {
+            command.pop();
+            match lines.next() {
+                Some(next) => {
+                    command.push(' ');
+                    command.push_str(next.trim());
+                }
+                None => break,
+            }
+        }
+        commands.push(command);
+    }
+    commands
+}
+
+/// Split a command line into words the way bash would for literal text:
+/// whitespace separates words, quotes group and are removed.
+fn split_shell_words(command: &str) -> Vec<String> {
+    shell_words(command)
+        .into_iter()
+        .map(|(word, _)| word)
+        .collect()
+}
+
+/// Words of a command line, each unquoted and as written
+fn shell_words(command: &str) -> Vec<(String, String)> {
+    let mut words = Vec::new();
+    let mut word = String::new();
+    let mut raw = String::new();
+    let mut in_word = false;
+    let mut quote: Option<char> = None;
+
+    for c in command.chars() {
+        if in_word || quote.is_some() || !c.is_whitespace() {
+            raw.push(c);
+        }
+        match quote {
+            Some(q) if c == q => quote = None,
+            Some(_) => word.push(c),
+            None if c == '\'' || c == '"' => {
+                quote = Some(c);
+                in_word = true;
+            }
+            None if c.is_whitespace() => {
+                if in_word {
+                    raw.pop();
+                    words.push((std::mem::take(&mut word), std::mem::take(&mut raw)));
+                    in_word = false;
+                }
+            }
+            None => {
+                word.push(c);
+                in_word = true;
+            }
+        }
+    }
+    if in_word {
+        words.push((word, raw));
+    }
+    words
+}
+
 #[cfg(test)]
 #[path = "tests/launch_parser.rs"]
 mod tests;
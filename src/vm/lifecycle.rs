@@ -1,13 +1,19 @@
 use anyhow::{bail, Context, Result};
 use std::io::{BufRead, BufReader};
-use std::path::Path;
+use std::os::unix::process::CommandExt;
+use std::path::{Path, PathBuf};
 use std::process::{Command, Stdio};
-use std::sync::mpsc;
+use std::sync::{mpsc, Arc, Mutex};
 use std::thread;
-use std::time::Duration;
+use std::time::{Duration, Instant};
 
 use super::discovery::DiscoveredVm;
-use super::qemu_config::BootMode;
+use super::qemu_config::{
+    BootMode, BootOrder, CpuConfig, DirectKernelBoot, DiskThrottle, DisplayWindow, NetworkShaping,
+    RtcConfig, VgaType, FLOPPY_DRIVE_INDEX, TOOLS_ISO_DRIVE_INDEX, WINDOW_NAME_ARG,
+    WINDOW_TITLE_VAR,
+};
+use crate::commands::qemu_compat::{self, QemuVersion};
 use crate::hardware::UsbVersion;
 
 /// Result of a VM launch attempt
@@ -19,6 +25,12 @@ pub struct LaunchResult {
     pub error: Option<String>,
     /// VM display name for status messages
     pub vm_name: String,
+    /// Startup health check for a launch that got past the initial check
+    pub watch: Option<StartupWatch>,
+    /// Where the SPICE server listens, for VMs using the `spice-app` display
+    pub spice: Option<SpiceEndpoint>,
+    /// Stderr of a launch that got past the initial check, still being read
+    pub stderr: Option<LaunchStderr>,
 }
 
 /// Convert a path to a string, returning an error if the path contains invalid UTF-8
@@ -33,6 +45,15 @@ pub struct LaunchOptions {
     pub boot_mode: BootMode,
     pub extra_args: Vec<String>,
     pub usb_devices: Vec<UsbPassthrough>,
+    /// One-shot software emulation: run this launch with TCG instead of KVM
+    /// without touching launch.sh (used when /dev/kvm is unusable).
+    pub force_tcg: bool,
+    /// Restore the state written by [`suspend_vm`] instead of booting. Call
+    /// [`finish_resume`] after a successful launch to clear the slot.
+    pub resume_suspended: bool,
+    /// Serve a `spice-app` display over TCP instead of a socket in the VM
+    /// directory, so viewers on other hosts can connect
+    pub spice_tcp: bool,
 }
 
 /// USB device for passthrough
@@ -41,24 +62,40 @@ pub struct UsbPassthrough {
     pub vendor_id: u16,
     pub product_id: u16,
     pub usb_version: UsbVersion,
+    /// Also pin the device to a physical port, so one of several identical
+    /// devices can be told apart. `None` matches by vendor:product alone.
+    pub port: Option<UsbPort>,
+}
+
+/// A physical USB port: the host bus and the port path on it (`hostport`,
+/// e.g. "2.3" for port 3 of a hub on root port 2)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UsbPort {
+    pub bus: u8,
+    pub path: String,
 }
 
 impl UsbPassthrough {
+    /// The `usb-host` device spec, attached to `bus` (e.g. "xhci.0") if given
+    pub fn device_spec(&self, bus: Option<&str>) -> String {
+        let mut spec = "usb-host".to_string();
+        if let Some(bus_name) = bus {
+            spec.push_str(&format!(",bus={}", bus_name));
+        }
+        if let Some(ref port) = self.port {
+            spec.push_str(&format!(",hostbus={},hostport={}", port.bus, port.path));
+        }
+        spec.push_str(&format!(
+            ",vendorid=0x{:04x},productid=0x{:04x}",
+            self.vendor_id, self.product_id
+        ));
+        spec
+    }
+
     /// Generate QEMU device arguments for this USB device
     /// If `bus` is provided, attach to that specific bus (e.g., "xhci.0" for USB 3.0)
     pub fn to_qemu_args(&self, bus: Option<&str>) -> Vec<String> {
-        let device_spec = if let Some(bus_name) = bus {
-            format!(
-                "usb-host,bus={},vendorid=0x{:04x},productid=0x{:04x}",
-                bus_name, self.vendor_id, self.product_id
-            )
-        } else {
-            format!(
-                "usb-host,vendorid=0x{:04x},productid=0x{:04x}",
-                self.vendor_id, self.product_id
-            )
-        };
-        vec!["-device".to_string(), device_spec]
+        vec!["-device".to_string(), self.device_spec(bus)]
     }
 
     /// Check if this device is USB 3.0 or higher
@@ -78,14 +115,93 @@ pub fn launch_vm_with_error_check(vm: &DiscoveredVm, options: &LaunchOptions) ->
     if let Err(e) = ensure_qmp_in_script(&vm.path) {
         log::warn!("launch_vm_with_error_check: could not patch QMP into launch.sh: {e}");
     }
+    if let Err(e) = ensure_cached_sudo_in_script(&vm.path) {
+        log::warn!("launch_vm_with_error_check: could not patch VFIO auth in launch.sh: {e}");
+    }
+    if let Err(e) = check_disks_not_open(vm, &detect_qemu_processes(), Path::new(SYS_BLOCK))
+        .and_then(|()| check_boot_keeps_suspend_state(&vm.path, &options.boot_mode))
+    {
+        return LaunchResult {
+            success: false,
+            error: Some(format!("{:#}", e)),
+            vm_name,
+            watch: None,
+            spice: None,
+            stderr: None,
+        };
+    }
 
     let mut cmd = Command::new("bash");
     cmd.current_dir(&vm.path);
 
-    let mut args = vec![vm.launch_script.to_string_lossy().to_string()];
+    // Once the first boot is done, a normal launch no longer boots the
+    // install ISO even if the script still attaches it
+    let skip_install_media = matches!(options.boot_mode, BootMode::Normal | BootMode::Ephemeral)
+        && settle_first_boot(vm)
+        && skip_install_media_boot(&vm.config.raw_script) != vm.config.raw_script;
+
+    let spice = if vm.config.has_spice_display() {
+        match SpiceEndpoint::for_launch(&vm.path, options.spice_tcp) {
+            Ok(endpoint) => Some(endpoint),
+            Err(e) => {
+                return LaunchResult {
+                    success: false,
+                    error: Some(format!("{:#}", e)),
+                    vm_name,
+                    watch: None,
+                    spice: None,
+                    stderr: None,
+                };
+            }
+        }
+    } else {
+        None
+    };
+
+    // Options the installed QEMU is too old (or too new) for: those with an
+    // older spelling are rewritten, the rest explain a failed launch
+    let qemu_version = qemu_compat::installed_version(vm.config.emulator.command());
+    let adapt_to = qemu_version
+        .filter(|v| qemu_compat::adapt_script(&vm.config.raw_script, *v) != vm.config.raw_script);
+    let compat_issues = qemu_version
+        .map(|v| qemu_compat::check_script(&qemu_compat::adapt_script(&vm.config.raw_script, v), v))
+        .unwrap_or_default();
+    for issue in &compat_issues {
+        log::warn!("{}: {}", vm_name, issue);
+    }
+
+    // TCG fallback, resume, ephemeral boots, skipping the install ISO, SPICE
+    // and QEMU version fixes run a rewritten copy of the script; launch.sh
+    // is untouched
+    let ephemeral = options.boot_mode == BootMode::Ephemeral;
+    let script = if options.force_tcg
+        || options.resume_suspended
+        || ephemeral
+        || skip_install_media
+        || spice.is_some()
+        || adapt_to.is_some()
+    {
+        match write_temp_launch_script(vm, options, skip_install_media, spice.as_ref(), adapt_to) {
+            Ok(path) => path,
+            Err(e) => {
+                return LaunchResult {
+                    success: false,
+                    error: Some(format!("Failed to prepare launch script: {}", e)),
+                    vm_name,
+                    watch: None,
+                    spice: None,
+                    stderr: None,
+                };
+            }
+        }
+    } else {
+        vm.launch_script.clone()
+    };
+
+    let mut args = vec![script.to_string_lossy().to_string()];
 
     match &options.boot_mode {
-        BootMode::Normal => {}
+        BootMode::Normal | BootMode::Ephemeral => {}
         BootMode::Install => {
             args.push("--install".to_string());
         }
@@ -96,6 +212,9 @@ pub fn launch_vm_with_error_check(vm: &DiscoveredVm, options: &LaunchOptions) ->
                     success: false,
                     error: Some(format!("ISO file not found: {}", iso_path.display())),
                     vm_name,
+                    watch: None,
+                    spice: None,
+                    stderr: None,
                 };
             }
             if !iso_path.is_file() {
@@ -103,6 +222,9 @@ pub fn launch_vm_with_error_check(vm: &DiscoveredVm, options: &LaunchOptions) ->
                     success: false,
                     error: Some(format!("ISO path is not a file: {}", iso_path.display())),
                     vm_name,
+                    watch: None,
+                    spice: None,
+                    stderr: None,
                 };
             }
             args.push("--cdrom".to_string());
@@ -115,6 +237,9 @@ pub fn launch_vm_with_error_check(vm: &DiscoveredVm, options: &LaunchOptions) ->
                     success: false,
                     error: Some(format!("Recovery image not found: {}", dmg_path.display())),
                     vm_name,
+                    watch: None,
+                    spice: None,
+                    stderr: None,
                 };
             }
             if !dmg_path.is_file() {
@@ -125,6 +250,9 @@ pub fn launch_vm_with_error_check(vm: &DiscoveredVm, options: &LaunchOptions) ->
                         dmg_path.display()
                     )),
                     vm_name,
+                    watch: None,
+                    spice: None,
+                    stderr: None,
                 };
             }
             args.push("--recovery".to_string());
@@ -136,6 +264,9 @@ pub fn launch_vm_with_error_check(vm: &DiscoveredVm, options: &LaunchOptions) ->
                     success: false,
                     error: Some(format!("Floppy image not found: {}", floppy_path.display())),
                     vm_name,
+                    watch: None,
+                    spice: None,
+                    stderr: None,
                 };
             }
             if !floppy_path.is_file() {
@@ -146,6 +277,9 @@ pub fn launch_vm_with_error_check(vm: &DiscoveredVm, options: &LaunchOptions) ->
                         floppy_path.display()
                     )),
                     vm_name,
+                    watch: None,
+                    spice: None,
+                    stderr: None,
                 };
             }
             args.push("--floppy".to_string());
@@ -178,6 +312,11 @@ pub fn launch_vm_with_error_check(vm: &DiscoveredVm, options: &LaunchOptions) ->
         }
     }
 
+    // Run through the hook wrapper when the VM has pre-launch/post-stop hooks
+    if has_hooks(&vm.path) {
+        cmd.arg("-c").arg(HOOK_WRAPPER).arg("vm-curator-hooks");
+        cmd.envs(hook_env(vm));
+    }
     cmd.args(&args);
 
     // Capture stderr to detect errors, but let stdout go to null
@@ -192,6 +331,9 @@ pub fn launch_vm_with_error_check(vm: &DiscoveredVm, options: &LaunchOptions) ->
                 success: false,
                 error: Some(format!("Failed to start VM process: {}", e)),
                 vm_name,
+                watch: None,
+                spice: None,
+                stderr: None,
             };
         }
     };
@@ -204,27 +346,14 @@ pub fn launch_vm_with_error_check(vm: &DiscoveredVm, options: &LaunchOptions) ->
                 success: true,
                 error: None,
                 vm_name,
+                watch: None,
+                spice: None,
+                stderr: None,
             };
         }
     };
 
-    // Create a channel to receive error output
-    let (tx, rx) = mpsc::channel();
-
-    // Spawn a thread to read ALL stderr output
-    thread::spawn(move || {
-        let reader = BufReader::new(stderr);
-        let mut all_lines = Vec::new();
-
-        for line in reader.lines().map_while(Result::ok) {
-            // Capture all stderr output - we'll filter later if needed
-            if !line.trim().is_empty() {
-                all_lines.push(line);
-            }
-        }
-
-        let _ = tx.send(all_lines);
-    });
+    let (output, rx) = collect_stderr(stderr);
 
     // Wait for QEMU to either start successfully or fail
     // QEMU typically fails fast if there's a configuration error
@@ -238,48 +367,23 @@ pub fn launch_vm_with_error_check(vm: &DiscoveredVm, options: &LaunchOptions) ->
 
             // Wait a bit more for stderr to be fully captured
             thread::sleep(Duration::from_millis(300));
-
-            // Try to get error output
-            let stderr_lines = rx
-                .recv_timeout(Duration::from_millis(500))
-                .unwrap_or_default();
-
-            // Filter for error-related lines for display
-            let error_lines: Vec<&String> = stderr_lines
-                .iter()
-                .filter(|line| {
-                    let lower = line.to_lowercase();
-                    lower.contains("error")
-                        || lower.contains("failed")
-                        || lower.contains("cannot")
-                        || lower.contains("unable")
-                        || lower.contains("not found")
-                        || lower.contains("no such")
-                        || lower.contains("invalid")
-                        || lower.contains("is not a valid")
-                        || lower.contains("could not")
-                        || lower.contains("qemu-system")
-                        || lower.contains("permission denied")
-                })
-                .collect();
-
-            let error_msg = if !error_lines.is_empty() {
-                error_lines
-                    .iter()
-                    .map(|s| s.as_str())
-                    .collect::<Vec<_>>()
-                    .join("\n")
-            } else if !stderr_lines.is_empty() {
-                // Show all stderr if no specific errors found
-                stderr_lines.join("\n")
-            } else {
-                format!("VM process exited with code: {}", status)
-            };
-
+            let _ = rx.recv_timeout(Duration::from_millis(500));
+
+            let stderr_lines = output.lock().map(|l| l.clone()).unwrap_or_default();
+            let mut error = summarize_launch_errors(&stderr_lines, &status.to_string());
+            if !compat_issues.is_empty() {
+                error.push_str("\n\nlaunch.sh uses options the installed QEMU doesn't accept:");
+                for issue in &compat_issues {
+                    error.push_str(&format!("\n  {}", issue));
+                }
+            }
             return LaunchResult {
                 success: false,
-                error: Some(error_msg),
+                error: Some(error),
                 vm_name,
+                watch: None,
+                spice: None,
+                stderr: None,
             };
         }
         Ok(None) => {
@@ -290,147 +394,1076 @@ pub fn launch_vm_with_error_check(vm: &DiscoveredVm, options: &LaunchOptions) ->
                 success: false,
                 error: Some(format!("Failed to check VM status: {}", e)),
                 vm_name,
+                watch: None,
+                spice: None,
+                stderr: None,
             };
         }
     }
 
+    if let Err(e) = record_launch(&vm.path) {
+        log::warn!(
+            "could not record launch time for {}: {e}",
+            vm.path.display()
+        );
+    }
+
+    // Only scripts with a QMP socket can be health-checked
+    let has_qmp = std::fs::read_to_string(&vm.launch_script)
+        .map(|content| content.contains("qemu.sock"))
+        .unwrap_or(false);
+
     LaunchResult {
         success: true,
         error: None,
         vm_name,
+        stderr: Some(LaunchStderr(Arc::clone(&output))),
+        watch: has_qmp.then(|| StartupWatch {
+            child,
+            output,
+            vm_path: vm.path.clone(),
+        }),
+        spice,
     }
 }
 
-/// Launch a VM synchronously (legacy function for compatibility)
-pub fn launch_vm_sync(vm: &DiscoveredVm, options: &LaunchOptions) -> Result<()> {
-    let result = launch_vm_with_error_check(vm, options);
+/// Read a child's stderr on a background thread. Returns the non-empty lines
+/// collected so far and a channel that fires once stderr closes.
+fn collect_stderr(
+    stderr: std::process::ChildStderr,
+) -> (Arc<Mutex<Vec<String>>>, mpsc::Receiver<()>) {
+    let output = Arc::new(Mutex::new(Vec::new()));
+    let (tx, rx) = mpsc::channel();
 
-    if result.success {
-        Ok(())
+    let lines = Arc::clone(&output);
+    thread::spawn(move || {
+        let reader = BufReader::new(stderr);
+
+        for line in reader.lines().map_while(Result::ok) {
+            // Capture all stderr output - we'll filter later if needed
+            if !line.trim().is_empty() {
+                if let Ok(mut lines) = lines.lock() {
+                    lines.push(line);
+                }
+            }
+        }
+
+        let _ = tx.send(());
+    });
+
+    (output, rx)
+}
+
+/// Pick the error-looking lines out of a failed launch's stderr, falling back
+/// to all of it, or to the exit status when there was no output.
+pub(crate) fn summarize_launch_errors(stderr_lines: &[String], status: &str) -> String {
+    let error_lines: Vec<&str> = stderr_lines
+        .iter()
+        .filter(|line| {
+            let lower = line.to_lowercase();
+            lower.contains("error")
+                || lower.contains("failed")
+                || lower.contains("cannot")
+                || lower.contains("unable")
+                || lower.contains("not found")
+                || lower.contains("no such")
+                || lower.contains("invalid")
+                || lower.contains("is not a valid")
+                || lower.contains("could not")
+                || lower.contains("qemu-system")
+                || lower.contains("permission denied")
+        })
+        .map(|s| s.as_str())
+        .collect();
+
+    if !error_lines.is_empty() {
+        error_lines.join("\n")
+    } else if !stderr_lines.is_empty() {
+        // Show all stderr if no specific errors found
+        stderr_lines.join("\n")
     } else {
-        bail!(
-            "{}",
-            result.error.unwrap_or_else(|| "Unknown error".to_string())
-        )
+        format!("VM process exited with code: {}", status)
     }
 }
 
-/// Launch VM with QEMU D-Bus display for GUI embedding.
-/// Rewrites the launch script in memory to replace any existing -display flag
-/// with -display dbus (session bus). Returns the child process PID on success.
-#[allow(dead_code)]
-pub fn launch_vm_dbus(vm: &DiscoveredVm) -> Result<u32> {
-    let tmp = vm.path.join(".launch_dbus_tmp.sh");
-    let _ = std::fs::remove_file(&tmp); // stale temp script from prior crash
-    let _ = std::fs::remove_file(vm.path.join("qemu.sock")); // stale socket from unclean shutdown
+/// How long after a launch QEMU's stderr is checked for warnings
+pub const LAUNCH_WARNING_WINDOW: Duration = Duration::from_secs(4);
+
+/// Stderr lines that say "warning" but aren't worth surfacing: GTK/GLib
+/// chatter from the display window and accessibility bus noise.
+const BENIGN_STDERR: &[&str] = &[
+    "Gtk-Message",
+    "Gtk-WARNING",
+    "GLib-GIO-WARNING",
+    "dbind-WARNING",
+    "libdecor-gtk-WARNING",
+    "Fontconfig warning",
+    "atk-bridge",
+];
+
+/// Shared view of a launched VM's stderr, filled by a background reader
+#[derive(Debug, Clone)]
+pub struct LaunchStderr(Arc<Mutex<Vec<String>>>);
 
-    if let Err(e) = ensure_qmp_in_script(&vm.path) {
-        log::warn!("launch_vm_dbus: could not patch QMP into launch.sh: {e}");
+impl LaunchStderr {
+    /// Warnings printed so far (see [`launch_warnings`])
+    pub fn warnings(&self) -> Vec<String> {
+        let lines = self.0.lock().map(|l| l.clone()).unwrap_or_default();
+        launch_warnings(&lines)
+    }
+}
+
+/// The actionable warnings in a running VM's stderr: QEMU's own `warning:`
+/// lines and deprecation notices, minus known-benign display noise
+pub(crate) fn launch_warnings(stderr_lines: &[String]) -> Vec<String> {
+    stderr_lines
+        .iter()
+        .filter(|line| launch_stage(line).is_none())
+        .filter(|line| !BENIGN_STDERR.iter().any(|b| line.contains(b)))
+        .filter(|line| {
+            let lower = line.to_lowercase();
+            lower.contains("warning") || lower.contains("deprecated")
+        })
+        .map(|line| line.trim().to_string())
+        .collect()
+}
+
+// ── Launch hooks ────────────────────────────────────────────────────────────
+
+/// Per-VM hook run before launch.sh; a non-zero exit aborts the launch.
+pub const PRE_LAUNCH_HOOK: &str = "pre_launch.sh";
+/// Per-VM hook run once the VM's launch script has exited.
+pub const POST_STOP_HOOK: &str = "post_stop.sh";
+/// Log in the VM directory that hook output is appended to.
+pub const LAUNCH_LOG: &str = "launch.log";
+
+/// Environment variables exported to hooks, with a short description each.
+pub const HOOK_ENV_VARS: &[(&str, &str)] = &[
+    ("VM_NAME", "display name of the VM"),
+    ("VM_DIR", "VM directory (also the working directory)"),
+    ("VM_DISK", "path of the first disk image, if any"),
+    ("VM_PID", "PID of the exited launch.sh (post_stop.sh only)"),
+    (
+        "VM_EXIT_CODE",
+        "exit status of launch.sh (post_stop.sh only)",
+    ),
+];
+
+/// Runs in place of launch.sh when a hook exists: `$@` is launch.sh and its
+/// arguments. Hook output goes to the launch log; launch.sh keeps its stderr
+/// so the usual error checks still see QEMU failures.
+const HOOK_WRAPPER: &str = r#"log="$VM_DIR/launch.log"
+if [[ -f "$VM_DIR/pre_launch.sh" ]]; then
+    echo "vm-curator: running pre_launch.sh" >&2
+    echo "=== pre_launch.sh $(date '+%F %T') ===" >> "$log"
+    bash "$VM_DIR/pre_launch.sh" >> "$log" 2>&1
+    status=$?
+    if [[ $status -ne 0 ]]; then
+        echo "pre_launch.sh failed with exit code $status (see $log)" >&2
+        exit "$status"
+    fi
+fi
+bash "$@" &
+export VM_PID=$!
+wait "$VM_PID"
+status=$?
+if [[ -f "$VM_DIR/post_stop.sh" ]]; then
+    echo "=== post_stop.sh $(date '+%F %T') ===" >> "$log"
+    VM_EXIT_CODE=$status bash "$VM_DIR/post_stop.sh" >> "$log" 2>&1
+fi
+exit "$status"
+"#;
+
+/// Whether the VM directory has a pre-launch or post-stop hook.
+pub fn has_hooks(vm_path: &Path) -> bool {
+    vm_path.join(PRE_LAUNCH_HOOK).is_file() || vm_path.join(POST_STOP_HOOK).is_file()
+}
+
+/// The environment hooks run with, on top of the user's own.
+fn hook_env(vm: &DiscoveredVm) -> Vec<(&'static str, String)> {
+    let disk = vm
+        .config
+        .disks
+        .first()
+        .map(|d| vm.path.join(&d.path).to_string_lossy().to_string())
+        .unwrap_or_default();
+    vec![
+        ("VM_NAME", vm.display_name()),
+        ("VM_DIR", vm.path.to_string_lossy().to_string()),
+        ("VM_DISK", disk),
+    ]
+}
+
+/// Starting content for a hook that doesn't exist yet.
+pub fn hook_template(hook: &str) -> String {
+    let when = if hook == PRE_LAUNCH_HOOK {
+        "before the VM starts. A non-zero exit aborts the launch."
+    } else {
+        "after the VM has stopped."
+    };
+    let mut text = format!(
+        "#!/bin/bash\n# {} - run by vm-curator {}\n# Output is appended to {} in the VM directory.\n#\n# Available variables:\n",
+        hook, when, LAUNCH_LOG
+    );
+    for (name, desc) in HOOK_ENV_VARS {
+        text.push_str(&format!("#   {:<13}{}\n", name, desc));
     }
+    text.push_str("\nset -e\n\n");
+    text
+}
 
-    let script_path = vm.path.join("launch.sh");
-    let content = std::fs::read_to_string(&script_path).context("Failed to read launch.sh")?;
+// ── Startup health check ────────────────────────────────────────────────────
 
-    let modified = replace_display_for_dbus(&content, "-display dbus");
+/// Prefix of the progress lines generated launch scripts write to stderr
+/// (e.g. while waiting on pkexec/sudo to bind VFIO devices), so a hung
+/// elevation prompt can be told apart from QEMU itself hanging.
+pub const LAUNCH_STAGE_PREFIX: &str = "vm-curator: ";
 
-    std::fs::write(&tmp, &modified).context("Failed to write temp launch script")?;
-    use std::os::unix::fs::PermissionsExt;
-    let _ = std::fs::set_permissions(&tmp, std::fs::Permissions::from_mode(0o755));
+/// The stage text of a launch script progress line, if `line` is one.
+pub fn launch_stage(line: &str) -> Option<&str> {
+    line.strip_prefix(LAUNCH_STAGE_PREFIX).map(str::trim)
+}
+
+/// How a freshly launched VM's startup turned out.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum StartupHealth {
+    /// QEMU answered on its QMP socket (or the script finished cleanly)
+    Ready,
+    /// The launch script failed after the initial error check
+    Exited(String),
+    /// Still running after the timeout without QEMU answering. `stage` is
+    /// the last progress line the script printed.
+    Stuck {
+        stage: Option<String>,
+        output: String,
+    },
+}
+
+/// A launched VM whose startup hasn't been confirmed yet. Returned by
+/// [`launch_vm_with_error_check`] when the script has a QMP socket; call
+/// [`StartupWatch::wait`] off the UI thread.
+#[derive(Debug)]
+pub struct StartupWatch {
+    child: std::process::Child,
+    output: Arc<Mutex<Vec<String>>>,
+    vm_path: PathBuf,
+}
+
+impl StartupWatch {
+    /// Wait up to `timeout` for QEMU's QMP greeting or the script exiting,
+    /// calling `on_stage` for each progress line the script prints.
+    pub fn wait(&mut self, timeout: Duration, mut on_stage: impl FnMut(&str)) -> StartupHealth {
+        let deadline = Instant::now() + timeout;
+        let mut seen = 0;
+        let mut stage = None;
+
+        loop {
+            let lines = self.output.lock().map(|l| l.clone()).unwrap_or_default();
+            for line in lines.iter().skip(seen) {
+                if let Some(s) = launch_stage(line) {
+                    on_stage(s);
+                    stage = Some(s.to_string());
+                }
+            }
+            seen = lines.len();
+
+            match self.child.try_wait() {
+                Ok(Some(status)) if status.success() => return StartupHealth::Ready,
+                Ok(Some(status)) => {
+                    // Give the reader thread a moment to collect the tail
+                    thread::sleep(Duration::from_millis(300));
+                    let lines = self.output.lock().map(|l| l.clone()).unwrap_or_default();
+                    return StartupHealth::Exited(summarize_launch_errors(
+                        &lines,
+                        &status.to_string(),
+                    ));
+                }
+                Ok(None) => {}
+                Err(e) => {
+                    return StartupHealth::Exited(format!("Failed to check VM status: {}", e))
+                }
+            }
+
+            if qmp_greeting(&self.vm_path) {
+                return StartupHealth::Ready;
+            }
+
+            if Instant::now() >= deadline {
+                let tail = lines.len().saturating_sub(20);
+                return StartupHealth::Stuck {
+                    stage,
+                    output: lines[tail..].join("\n"),
+                };
+            }
+            thread::sleep(Duration::from_millis(500));
+        }
+    }
+}
+
+/// Whether QEMU is answering on the VM's QMP socket (reads the greeting only).
+fn qmp_greeting(vm_path: &Path) -> bool {
+    use std::os::unix::net::UnixStream;
+
+    let Ok(stream) = UnixStream::connect(vm_path.join("qemu.sock")) else {
+        return false;
+    };
+    stream.set_read_timeout(Some(Duration::from_secs(1))).ok();
+    let mut line = String::new();
+    BufReader::new(stream).read_line(&mut line).is_ok() && line.contains("\"QMP\"")
+}
+
+// ── Test launch ─────────────────────────────────────────────────────────────
+
+/// Script a test launch runs, next to launch.sh
+const TEST_LAUNCH_SCRIPT: &str = ".test_launch.sh";
+
+/// How long a test launch waits for QEMU; long enough for a pkexec prompt
+pub const TEST_LAUNCH_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// How a test launch went
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TestLaunch {
+    /// QEMU set up every device and answered on QMP, then was told to quit.
+    /// `warnings` are the non-fatal ones it printed on the way.
+    Passed { warnings: Vec<String> },
+    /// QEMU exited during setup. `option` is the QEMU argument its error
+    /// names (usually the `-device` that failed to initialize).
+    Failed {
+        option: Option<String>,
+        error: String,
+    },
+    /// Neither answered nor exited within the timeout
+    TimedOut { output: String },
+}
 
+/// The QEMU argument an error line blames, e.g. `-device vfio-pci,host=01:00.0`
+/// from `qemu-system-x86_64: -device vfio-pci,host=01:00.0: vfio ...: failed`
+pub(crate) fn failing_option(stderr_lines: &[String]) -> Option<String> {
+    stderr_lines.iter().find_map(|line| {
+        let (_, rest) = line.split_once("qemu-system-")?;
+        let (_, rest) = rest.split_once(": ")?;
+        if !rest.starts_with('-') {
+            return None;
+        }
+        let (option, _) = rest.split_once(": ")?;
+        Some(option.to_string())
+    })
+}
+
+/// Start the VM with its CPUs paused (`-S`) to check that QEMU accepts the
+/// configuration and initializes every device (passthrough, firmware, TPM),
+/// then quit it over QMP. The guest never runs, so its disks aren't touched.
+/// The caller makes sure the VM isn't already running.
+pub fn test_launch(vm: &DiscoveredVm, timeout: Duration) -> Result<TestLaunch> {
+    ensure_qmp_in_script(&vm.path)?;
+    if let Err(e) = ensure_cached_sudo_in_script(&vm.path) {
+        log::warn!("test_launch: could not patch VFIO auth in launch.sh: {e}");
+    }
+    let mut content =
+        std::fs::read_to_string(&vm.launch_script).context("Failed to read launch.sh")?;
+    if let Some(version) = qemu_compat::installed_version(vm.config.emulator.command()) {
+        content = qemu_compat::adapt_script(&content, version);
+    }
+    let content = insert_after_qemu_command(&content, "-S")
+        .context("launch.sh has no QEMU command to test")?;
+
+    let script = vm.path.join(TEST_LAUNCH_SCRIPT);
+    std::fs::write(&script, content).context("Failed to write test launch script")?;
+    // A socket left behind by a crashed QEMU would look like a greeting
+    let _ = std::fs::remove_file(vm.path.join("qemu.sock"));
+
+    // Its own process group, so QEMU and the script's helpers can be stopped
+    // together if it hangs
     let mut child = Command::new("bash")
-        .arg(&tmp)
+        .arg(&script)
         .current_dir(&vm.path)
-        .stderr(std::process::Stdio::piped())
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::piped())
+        .process_group(0)
         .spawn()
-        .context("Failed to spawn QEMU")?;
-    let pid = child.id();
+        .context("Failed to start the test launch")?;
+    let stderr = child.stderr.take().context("No stderr from test launch")?;
+    let (output, _) = collect_stderr(stderr);
+    let lines = || output.lock().map(|l| l.clone()).unwrap_or_default();
+
+    let deadline = Instant::now() + timeout;
+    let result = loop {
+        if let Ok(Some(status)) = child.try_wait() {
+            // Give the reader thread a moment to collect the tail
+            thread::sleep(Duration::from_millis(300));
+            let lines = lines();
+            break TestLaunch::Failed {
+                option: failing_option(&lines),
+                error: summarize_launch_errors(&lines, &status.to_string()),
+            };
+        }
+        if qmp_greeting(&vm.path) {
+            let _ = qmp_send(&vm.path, "quit");
+            let quit_by = Instant::now() + Duration::from_secs(5);
+            while matches!(child.try_wait(), Ok(None)) && Instant::now() < quit_by {
+                thread::sleep(Duration::from_millis(100));
+            }
+            stop_process_group(&mut child);
+            break TestLaunch::Passed {
+                warnings: launch_warnings(&lines()),
+            };
+        }
+        if Instant::now() >= deadline {
+            stop_process_group(&mut child);
+            let lines = lines();
+            let tail = lines.len().saturating_sub(20);
+            break TestLaunch::TimedOut {
+                output: lines[tail..].join("\n"),
+            };
+        }
+        thread::sleep(Duration::from_millis(200));
+    };
+    let _ = std::fs::remove_file(&script);
+    Ok(result)
+}
 
-    // Brief poll to catch immediate QEMU startup failures (missing session D-Bus,
-    // bad flag, missing library) before returning Ok(pid) to the caller.
-    thread::sleep(Duration::from_millis(300));
-    if let Ok(Some(status)) = child.try_wait() {
-        use std::io::Read;
-        let stderr = child
-            .stderr
-            .take()
-            .map(|mut s| {
-                let mut b = String::new();
-                s.read_to_string(&mut b).ok();
-                b
-            })
-            .unwrap_or_default();
-        bail!("QEMU exited immediately ({}): {}", status, stderr.trim());
+/// Stop a script started in its own process group along with everything it
+/// spawned, then reap it. SIGTERM first so the script's exit trap still runs
+/// (it stops swtpm, which daemonizes out of the group); SIGKILL whatever is
+/// left after a few seconds.
+fn stop_process_group(child: &mut std::process::Child) {
+    if !matches!(child.try_wait(), Ok(None)) {
+        return;
     }
-    // child intentionally dropped — QEMU process continues running
+    let group = format!("-{}", child.id());
+    let signal_group = |signal: &str| {
+        let _ = Command::new("kill")
+            .args([signal, "--", &group])
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .status();
+    };
+    signal_group("-TERM");
+    let killed_by = Instant::now() + Duration::from_secs(3);
+    while matches!(child.try_wait(), Ok(None)) && Instant::now() < killed_by {
+        thread::sleep(Duration::from_millis(100));
+    }
+    // QEMU can outlive the script when the script exits first
+    signal_group("-KILL");
+    let _ = child.wait();
+}
 
-    let t = tmp.to_owned();
+/// Write `.launch_tmp.sh` next to launch.sh with the one-shot changes from
+/// `options` applied (KVM swapped for TCG, suspend state loaded with
+/// `-incoming`), and schedule its removal once QEMU has had time to start.
+fn write_temp_launch_script(
+    vm: &DiscoveredVm,
+    options: &LaunchOptions,
+    skip_install_media: bool,
+    spice: Option<&SpiceEndpoint>,
+    adapt_to: Option<QemuVersion>,
+) -> Result<std::path::PathBuf> {
+    let mut content =
+        std::fs::read_to_string(&vm.launch_script).context("Failed to read launch.sh")?;
+    if let Some(version) = adapt_to {
+        content = qemu_compat::adapt_script(&content, version);
+    }
+    if options.force_tcg {
+        content = replace_kvm_with_tcg(&content);
+    }
+    if skip_install_media {
+        content = skip_install_media_boot(&content);
+    }
+    if options.resume_suspended {
+        content = append_qemu_arg(&content, INCOMING_ARG);
+    }
+    if options.boot_mode == BootMode::Ephemeral {
+        // Launching without -snapshot would quietly keep every change
+        content = add_snapshot_arg(&content)
+            .context("launch.sh has no QEMU command to add -snapshot to")?;
+    }
+    if let Some(endpoint) = spice {
+        let viewer = crate::commands::qemu_system::spice_uri_viewer();
+        if let Some(served) = serve_spice(&content, endpoint, viewer) {
+            content = served;
+        }
+    }
+    let tmp = vm.path.join(".launch_tmp.sh");
+    std::fs::write(&tmp, content).context("Failed to write temp launch script")?;
+
+    let t = tmp.clone();
     thread::spawn(move || {
         thread::sleep(Duration::from_secs(4));
         let _ = std::fs::remove_file(t);
     });
 
-    Ok(pid)
+    Ok(tmp)
 }
 
-/// Replace every `-display <backend>` argument in a bash launch script with `replacement`.
-/// Scripts with multiple case branches each get their own replacement.
-/// Also strips SPICE-specific lines that are incompatible with dbus display.
-#[allow(dead_code)]
-fn replace_display_for_dbus(content: &str, replacement: &str) -> String {
+/// Add `-snapshot` to each QEMU command in a launch script, so disk writes go
+/// to temporary overlays dropped when QEMU exits. `None` if the script has
+/// no line starting a QEMU command.
+pub(crate) fn add_snapshot_arg(content: &str) -> Option<String> {
+    insert_after_qemu_command(content, SNAPSHOT_ARG)
+}
+
+/// Insert `arg` right after the emulator on each line starting a QEMU
+/// command. `None` if the script has no such line.
+pub(crate) fn insert_after_qemu_command(content: &str, arg: &str) -> Option<String> {
+    let command =
+        regex::Regex::new(r"^(\s*(?:exec\s+)?\S*qemu-system-[\w-]+)(\s|$)").expect("valid regex");
+    let mut patched = 0;
     let mut out: Vec<String> = Vec::new();
     for line in content.lines() {
-        let t = line.trim();
-        let is_display =
-            !t.starts_with('#') && (t.starts_with("-display ") || t.contains(" -display "));
-        let bare = t.trim_end_matches('\\').trim_end();
-        let is_spice = t.starts_with("-spice ")
-            || (t.starts_with("-device virtio-serial") && t.contains("spice"))
-            || (t.starts_with("-device virtserialport") && t.contains("com.redhat.spice"))
-            || t.starts_with("-chardev spice")
-            // SPICE clipboard channel (e.g. `-device virtio-serial-pci`) — incompatible
-            // with the dbus display used for single-GPU passthrough.
-            || crate::vm::create::SPICE_AGENT_ARGS.contains(&bare);
-        if is_display {
-            let indent_len = line.len() - line.trim_start().len();
-            let indent = &line[..indent_len];
-            if line.trim_end().ends_with('\\') {
-                out.push(format!("{}{} \\", indent, replacement));
-            } else {
-                out.push(format!("{}{}", indent, replacement));
+        match command.captures(line) {
+            Some(caps) if !line.trim_start().starts_with('#') => {
+                patched += 1;
+                out.push(format!("{} {}{}", &caps[1], arg, &line[caps[1].len()..]));
             }
-        } else if !is_spice {
-            out.push(line.to_string());
+            _ => out.push(line.to_string()),
         }
     }
+    if patched == 0 {
+        return None;
+    }
     let mut s = out.join("\n");
     if content.ends_with('\n') {
         s.push('\n');
     }
-    s
+    Some(s)
 }
 
-/// Reset a VM by recreating its disk from a backing file or template
-pub fn reset_vm(vm: &DiscoveredVm) -> Result<()> {
-    // Find the primary disk
-    let disk = vm
-        .config
-        .primary_disk()
-        .context("VM has no disk configured")?;
+/// SPICE socket in the VM directory, served in place of `-display spice-app`
+pub const SPICE_SOCKET: &str = "spice.sock";
 
-    let disk_path = &disk.path;
+/// TCP ports tried for SPICE over TCP; VNC displays take 5900 and up
+const SPICE_TCP_PORTS: std::ops::Range<u16> = 5930..6000;
 
-    // Check for a backing file or template
-    let info = super::snapshot::get_disk_info(disk_path).context("Failed to get disk info")?;
+/// Where a launch's SPICE server listens
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SpiceEndpoint {
+    /// Unix socket, reachable only from this host
+    Socket(PathBuf),
+    /// TCP port on all interfaces, for viewers on other hosts
+    Tcp(u16),
+}
 
-    if let Some(backing) = &info.backing_file {
-        // Recreate from backing file
-        let backing_path = Path::new(backing);
-        if !backing_path.exists() {
-            bail!("Backing file not found: {}", backing);
+impl SpiceEndpoint {
+    /// The socket in `vm_dir`, or with `tcp` the first free port from 5930
+    pub fn for_launch(vm_dir: &Path, tcp: bool) -> Result<Self> {
+        if !tcp {
+            return Ok(Self::Socket(vm_dir.join(SPICE_SOCKET)));
         }
+        SPICE_TCP_PORTS
+            .clone()
+            .find(|port| std::net::TcpListener::bind(("0.0.0.0", *port)).is_ok())
+            .map(Self::Tcp)
+            .with_context(|| {
+                format!(
+                    "No free TCP port for SPICE between {} and {}",
+                    SPICE_TCP_PORTS.start,
+                    SPICE_TCP_PORTS.end - 1
+                )
+            })
+    }
 
-        // Remove old disk
-        std::fs::remove_file(disk_path).context("Failed to remove old disk")?;
+    /// URI for `remote-viewer`
+    pub fn uri(&self) -> String {
+        match self {
+            Self::Socket(path) => format!("spice+unix://{}", path.display()),
+            Self::Tcp(port) => format!("spice://127.0.0.1:{}", port),
+        }
+    }
+
+    /// The `-spice` option. SPICE GL only works over a local socket, so
+    /// `gl` is ignored for TCP.
+    fn spice_arg(&self, gl: bool) -> String {
+        match self {
+            Self::Socket(path) => {
+                // Commas in QEMU option values are doubled
+                let addr = path.display().to_string().replace(',', ",,");
+                let gl = if gl { ",gl=on" } else { "" };
+                format!(
+                    "-spice {}",
+                    shell_escape(&format!("unix=on,addr={},disable-ticketing=on{}", addr, gl))
+                )
+            }
+            Self::Tcp(port) => format!("-spice port={},disable-ticketing=on", port),
+        }
+    }
+}
+
+/// Rewrite a launch script to serve SPICE at `endpoint` in place of
+/// `-display spice-app`, which always makes its own socket in a temporary
+/// directory. Like spice-app, the script opens `viewer` once the server is
+/// up; a socket is removed before QEMU starts and after the script exits.
+/// `None` if the script has no `-display spice-app`.
+pub(crate) fn serve_spice(
+    content: &str,
+    endpoint: &SpiceEndpoint,
+    viewer: Option<&str>,
+) -> Option<String> {
+    let display = regex::Regex::new(r"-display\s+spice-app((?:,[\w=.-]+)*)").expect("valid regex");
+    if !display.is_match(content) {
+        return None;
+    }
+    let body = display.replace_all(content, |caps: &regex::Captures| {
+        endpoint.spice_arg(caps[1].split(',').any(|opt| opt == "gl=on"))
+    });
+
+    let mut prelude = vec!["# SPICE server for this launch (vm-curator)".to_string()];
+    let ready = match endpoint {
+        SpiceEndpoint::Socket(path) => {
+            let path = shell_escape(&path.display().to_string());
+            prelude.push(format!("rm -f {}", path));
+            prelude.push(format!("trap \"rm -f {}\" EXIT", path));
+            format!("[ -S {} ]", path)
+        }
+        SpiceEndpoint::Tcp(port) => format!("(: </dev/tcp/127.0.0.1/{}) 2>/dev/null", port),
+    };
+    if let Some(viewer) = viewer {
+        prelude.push(format!(
+            "(for _ in $(seq 100); do {} && exec {} {}; sleep 0.3; done) >/dev/null 2>&1 &",
+            ready,
+            viewer,
+            shell_escape(&endpoint.uri())
+        ));
+    }
+
+    // After the shebang, before anything that may start QEMU
+    let (head, rest) = match body.split_once('\n') {
+        Some((first, rest)) if first.starts_with("#!") => (format!("{}\n", first), rest),
+        _ => (String::new(), body.as_ref()),
+    };
+    Some(format!("{}{}\n{}", head, prelude.join("\n"), rest))
+}
+
+/// QEMU option that keeps disk writes in temporary overlays
+const SNAPSHOT_ARG: &str = "-snapshot";
+
+/// Whether the QEMU process `pid` runs with `-snapshot`, so nothing it
+/// writes to disk will be kept
+pub fn is_ephemeral_process(pid: u32) -> bool {
+    std::fs::read(format!("/proc/{}/cmdline", pid)).is_ok_and(|cmdline| {
+        cmdline
+            .split(|&b| b == 0)
+            .any(|arg| arg == SNAPSHOT_ARG.as_bytes())
+    })
+}
+
+/// Rewrite a launch script so the CD-ROM can't be booted: `d` is dropped from
+/// `-boot` orders (an emptied order falls back to the disk) and CD drives lose
+/// their `bootindex`. The drives themselves stay attached.
+pub(crate) fn skip_install_media_boot(content: &str) -> String {
+    // Stops at quotes so `BOOT_ORDER_ARGS="-boot order=dc"` keeps its closing `"`
+    let boot = regex::Regex::new(r#"-boot\s+([^\s"']+)"#).expect("valid regex");
+    let device = regex::Regex::new(r"-(?:device|drive)\s+(\S+)").expect("valid regex");
+    let bootindex = regex::Regex::new(r",bootindex=\d+").expect("valid regex");
+
+    let mut out: Vec<String> = Vec::new();
+    for line in content.lines() {
+        if line.trim_start().starts_with('#') {
+            out.push(line.to_string());
+            continue;
+        }
+        let l = boot.replace_all(line, |caps: &regex::Captures| {
+            format!("-boot {}", boot_value_without_cdrom(&caps[1]))
+        });
+        let l = device.replace_all(&l, |caps: &regex::Captures| {
+            let value = &caps[1];
+            if ["ide-cd", "scsi-cd", "media=cdrom"]
+                .iter()
+                .any(|cd| value.contains(cd))
+            {
+                caps[0].replace(value, &bootindex.replace_all(value, ""))
+            } else {
+                caps[0].to_string()
+            }
+        });
+        out.push(l.into_owned());
+    }
+    let mut s = out.join("\n");
+    if content.ends_with('\n') {
+        s.push('\n');
+    }
+    s
+}
+
+/// A `-boot` value with the CD-ROM (`d`) taken out of its `order`/`once`
+/// (or legacy bare) drive list
+fn boot_value_without_cdrom(value: &str) -> String {
+    let is_drives = |s: &str| s.chars().all(|c| "abcdnop".contains(c));
+    let opts: Vec<String> = value
+        .split(',')
+        .enumerate()
+        .filter_map(|(i, opt)| {
+            let (key, drives) = match opt.split_once('=') {
+                Some((key @ ("order" | "once"), drives)) if is_drives(drives) => {
+                    (Some(key), drives)
+                }
+                None if i == 0 && is_drives(opt) => (None, opt),
+                _ => return Some(opt.to_string()),
+            };
+            let kept: String = drives.chars().filter(|&c| c != 'd').collect();
+            match (key, kept.is_empty()) {
+                (Some("once"), true) => None,
+                (Some(key), true) => Some(format!("{}=c", key)),
+                (Some(key), false) => Some(format!("{}={}", key, kept)),
+                (None, true) => Some("c".to_string()),
+                (None, false) => Some(kept),
+            }
+        })
+        .collect();
+    if opts.is_empty() {
+        "order=c".to_string()
+    } else {
+        opts.join(",")
+    }
+}
+
+/// Rewrite a launch script to use TCG software emulation instead of KVM.
+///
+/// Handles `-enable-kvm`, `-accel kvm` and `-machine ...,accel=kvm`, and swaps
+/// `-cpu host` (KVM-only) for `-cpu max`, the closest TCG equivalent.
+pub(crate) fn replace_kvm_with_tcg(content: &str) -> String {
+    let mut out: Vec<String> = Vec::new();
+    for line in content.lines() {
+        if line.trim_start().starts_with('#') {
+            out.push(line.to_string());
+            continue;
+        }
+        // `-enable-kvm` is just dropped: TCG is QEMU's default accelerator, and
+        // adding `-accel tcg` would clash with a `-machine accel=` option.
+        let mut l = line
+            .replace("-enable-kvm ", "")
+            .replace("-enable-kvm", "")
+            .replace("-accel kvm", "-accel tcg")
+            .replace("accel=kvm", "accel=tcg");
+        if let Some(swapped) = cpu_host_to_max(&l) {
+            l = swapped;
+        }
+        out.push(l);
+    }
+    let mut s = out.join("\n");
+    if content.ends_with('\n') {
+        s.push('\n');
+    }
+    s
+}
+
+/// CPU flags QEMU only accepts under KVM
+const KVM_ONLY_CPU_FLAGS: &[&str] = &["hv-passthrough", "hv_passthrough"];
+
+/// `line` with its `-cpu host` value, quoted (`-cpu 'host,kvm=off'`) or not,
+/// switched to the `max` model. The flags are kept, except those only KVM
+/// accepts. `None` if the line has no `-cpu host`.
+fn cpu_host_to_max(line: &str) -> Option<String> {
+    let pos = line
+        .match_indices("-cpu")
+        .map(|(i, _)| i)
+        .find(|&i| i == 0 || line[..i].ends_with(char::is_whitespace))?;
+    let after = &line[pos + "-cpu".len()..];
+    let value = after.trim_start();
+    if value.len() == after.len() {
+        return None;
+    }
+    let quote = value.chars().next().filter(|c| *c == '\'' || *c == '"');
+    let start = line.len() - value.len() + quote.map_or(0, char::len_utf8);
+    let body = &line[start..];
+    let end = match quote {
+        Some(q) => body.find(q)?,
+        None => body
+            .find(|c: char| c.is_whitespace() || c == '\\')
+            .unwrap_or(body.len()),
+    };
+    let cpu = CpuConfig::parse(&body[..end]);
+    if cpu.model != "host" {
+        return None;
+    }
+    let tcg = CpuConfig {
+        model: "max".to_string(),
+        flags: cpu
+            .flags
+            .into_iter()
+            .filter(|f| !KVM_ONLY_CPU_FLAGS.contains(&f.split('=').next().unwrap_or(f)))
+            .collect(),
+    };
+    Some(format!(
+        "{}{}{}",
+        &line[..start],
+        tcg.to_arg(),
+        &body[end..]
+    ))
+}
+
+/// Metadata key in vm-curator.toml holding when a VM was last launched
+const LAST_LAUNCHED_KEY: &str = "last_launched";
+/// Metadata key in vm-curator.toml counting a VM's launches
+const LAUNCH_COUNT_KEY: &str = "launch_count";
+
+/// When the VM was last launched from vm-curator, if ever
+pub fn load_last_launched(vm_path: &Path) -> Option<chrono::DateTime<chrono::Local>> {
+    let value = super::discovery::read_vm_metadata_value(vm_path, LAST_LAUNCHED_KEY)?;
+    chrono::DateTime::parse_from_rfc3339(&value)
+        .ok()
+        .map(|t| t.with_timezone(&chrono::Local))
+}
+
+/// How many times the VM has been launched from vm-curator
+pub fn load_launch_count(vm_path: &Path) -> u32 {
+    super::discovery::read_vm_metadata_value(vm_path, LAUNCH_COUNT_KEY)
+        .and_then(|v| v.parse().ok())
+        .unwrap_or(0)
+}
+
+/// Store the current time as the VM's last launch (RFC 3339) and bump its
+/// launch count
+pub fn record_launch(vm_path: &Path) -> Result<()> {
+    let now = chrono::Local::now().to_rfc3339_opts(chrono::SecondsFormat::Secs, false);
+    super::create::set_vm_metadata_value(
+        vm_path,
+        LAST_LAUNCHED_KEY,
+        Some(&format!("\"{}\"", now)),
+    )?;
+    let count = load_launch_count(vm_path).saturating_add(1);
+    super::create::set_vm_metadata_value(vm_path, LAUNCH_COUNT_KEY, Some(&count.to_string()))
+}
+
+/// Key of the per-VM flag that starts the VM when vm-curator opens
+const AUTOSTART_KEY: &str = "autostart";
+
+/// Whether the VM is started when vm-curator opens
+pub fn load_autostart(vm_path: &Path) -> bool {
+    super::discovery::read_vm_metadata_value(vm_path, AUTOSTART_KEY).as_deref() == Some("true")
+}
+
+/// Turn autostart on or off; off removes the key
+pub fn set_autostart(vm_path: &Path, enabled: bool) -> Result<()> {
+    super::create::set_vm_metadata_value(vm_path, AUTOSTART_KEY, enabled.then_some("true"))
+}
+
+/// Key of the per-VM flag recording that the first boot (the OS install) is
+/// done; `false` means the install ISO was deliberately made bootable again
+const FIRST_BOOT_DONE_KEY: &str = "first_boot_done";
+
+/// Whether the VM's first boot is done, or `None` if that isn't known yet
+pub fn load_first_boot_done(vm_path: &Path) -> Option<bool> {
+    super::discovery::read_vm_metadata_value(vm_path, FIRST_BOOT_DONE_KEY)?
+        .parse()
+        .ok()
+}
+
+/// Record whether the VM's first boot is done; while it is, normal launches
+/// don't boot the install ISO
+pub fn set_first_boot_done(vm_path: &Path, done: bool) -> Result<()> {
+    super::create::set_vm_metadata_value(vm_path, FIRST_BOOT_DONE_KEY, Some(&done.to_string()))
+}
+
+/// Whether `vm`'s first boot is done. A VM that has launched before counts as
+/// done from its second launch on, which is recorded here.
+fn settle_first_boot(vm: &DiscoveredVm) -> bool {
+    match vm.first_boot_done {
+        Some(done) => done,
+        None if vm.launch_count >= 1 => {
+            if let Err(e) = set_first_boot_done(&vm.path, true) {
+                log::warn!("could not record first boot of {}: {e}", vm.path.display());
+            }
+            true
+        }
+        None => false,
+    }
+}
+
+/// File in a VM folder holding ASCII art shown instead of the OS logo
+pub const CUSTOM_ASCII_FILE: &str = "ascii.txt";
+
+/// Largest ASCII art file accepted; logos are a few KB at most
+const MAX_ASCII_BYTES: u64 = 64 * 1024;
+
+/// The VM's own ASCII art, if it has any
+pub fn load_custom_ascii(vm_path: &Path) -> Option<String> {
+    std::fs::read_to_string(vm_path.join(CUSTOM_ASCII_FILE))
+        .ok()
+        .filter(|art| !art.trim().is_empty())
+}
+
+/// Copy a text file into the VM folder as its ASCII art, or remove the
+/// VM's art with `None`. Returns the art now in use.
+pub fn set_custom_ascii(vm_path: &Path, source: Option<&Path>) -> Result<Option<String>> {
+    let dest = vm_path.join(CUSTOM_ASCII_FILE);
+    let Some(source) = source else {
+        match std::fs::remove_file(&dest) {
+            Ok(()) => {}
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {}
+            Err(e) => return Err(e).context("Failed to remove ASCII art"),
+        }
+        return Ok(None);
+    };
+
+    let size = std::fs::metadata(source)
+        .with_context(|| format!("Failed to read {}", source.display()))?
+        .len();
+    if size > MAX_ASCII_BYTES {
+        bail!(
+            "{} is {} KB; ASCII art is limited to {} KB",
+            source.display(),
+            size / 1024,
+            MAX_ASCII_BYTES / 1024
+        );
+    }
+    let art = std::fs::read_to_string(source)
+        .with_context(|| format!("{} is not a UTF-8 text file", source.display()))?;
+    if art.trim().is_empty() {
+        bail!("{} is empty", source.display());
+    }
+    // Escape sequences would restyle or move the cursor in the terminal
+    if art
+        .chars()
+        .any(|c| c.is_control() && !matches!(c, '\n' | '\r' | '\t'))
+    {
+        bail!(
+            "{} contains terminal control characters; use plain text",
+            source.display()
+        );
+    }
+    std::fs::write(&dest, &art).context("Failed to save ASCII art")?;
+    Ok(Some(art))
+}
+
+/// Launch a VM synchronously (legacy function for compatibility).
+/// Returns where its SPICE server listens, if it has one.
+pub fn launch_vm_sync(vm: &DiscoveredVm, options: &LaunchOptions) -> Result<Option<SpiceEndpoint>> {
+    let result = launch_vm_with_error_check(vm, options);
+
+    if result.success {
+        Ok(result.spice)
+    } else {
+        bail!(
+            "{}",
+            result.error.unwrap_or_else(|| "Unknown error".to_string())
+        )
+    }
+}
+
+/// Launch VM with QEMU D-Bus display for GUI embedding.
+/// Rewrites the launch script in memory to replace any existing -display flag
+/// with -display dbus (session bus). Returns the child process PID on success.
+#[allow(dead_code)]
+pub fn launch_vm_dbus(vm: &DiscoveredVm) -> Result<u32> {
+    let tmp = vm.path.join(".launch_dbus_tmp.sh");
+    let _ = std::fs::remove_file(&tmp); // stale temp script from prior crash
+    let _ = std::fs::remove_file(vm.path.join("qemu.sock")); // stale socket from unclean shutdown
+
+    if let Err(e) = ensure_qmp_in_script(&vm.path) {
+        log::warn!("launch_vm_dbus: could not patch QMP into launch.sh: {e}");
+    }
+
+    let script_path = vm.path.join("launch.sh");
+    let content = std::fs::read_to_string(&script_path).context("Failed to read launch.sh")?;
+
+    let modified = replace_display_for_dbus(&content, "-display dbus");
+
+    std::fs::write(&tmp, &modified).context("Failed to write temp launch script")?;
+    use std::os::unix::fs::PermissionsExt;
+    let _ = std::fs::set_permissions(&tmp, std::fs::Permissions::from_mode(0o755));
+
+    let mut child = Command::new("bash")
+        .arg(&tmp)
+        .current_dir(&vm.path)
+        .stderr(std::process::Stdio::piped())
+        .spawn()
+        .context("Failed to spawn QEMU")?;
+    let pid = child.id();
+
+    // Brief poll to catch immediate QEMU startup failures (missing session D-Bus,
+    // bad flag, missing library) before returning Ok(pid) to the caller.
+    thread::sleep(Duration::from_millis(300));
+    if let Ok(Some(status)) = child.try_wait() {
+        use std::io::Read;
+        let stderr = child
+            .stderr
+            .take()
+            .map(|mut s| {
+                let mut b = String::new();
+                s.read_to_string(&mut b).ok();
+                b
+            })
+            .unwrap_or_default();
+        bail!("QEMU exited immediately ({}): {}", status, stderr.trim());
+    }
+    // child intentionally dropped — QEMU process continues running
+
+    if let Err(e) = record_launch(&vm.path) {
+        log::warn!(
+            "could not record launch time for {}: {e}",
+            vm.path.display()
+        );
+    }
+
+    let t = tmp.to_owned();
+    thread::spawn(move || {
+        thread::sleep(Duration::from_secs(4));
+        let _ = std::fs::remove_file(t);
+    });
+
+    Ok(pid)
+}
+
+/// Replace every `-display <backend>` argument in a bash launch script with `replacement`.
+/// Scripts with multiple case branches each get their own replacement.
+/// Also strips SPICE-specific lines that are incompatible with dbus display.
+#[allow(dead_code)]
+fn replace_display_for_dbus(content: &str, replacement: &str) -> String {
+    let mut out: Vec<String> = Vec::new();
+    for line in content.lines() {
+        let t = line.trim();
+        let is_display =
+            !t.starts_with('#') && (t.starts_with("-display ") || t.contains(" -display "));
+        let bare = t.trim_end_matches('\\').trim_end();
+        let is_spice = t.starts_with("-spice ")
+            || (t.starts_with("-device virtio-serial") && t.contains("spice"))
+            || (t.starts_with("-device virtserialport") && t.contains("com.redhat.spice"))
+            || t.starts_with("-chardev spice")
+            // SPICE clipboard channel (e.g. `-device virtio-serial-pci`) — incompatible
+            // with the dbus display used for single-GPU passthrough.
+            || crate::vm::create::SPICE_AGENT_ARGS.contains(&bare);
+        if is_display {
+            let indent_len = line.len() - line.trim_start().len();
+            let indent = &line[..indent_len];
+            if line.trim_end().ends_with('\\') {
+                out.push(format!("{}{} \\", indent, replacement));
+            } else {
+                out.push(format!("{}{}", indent, replacement));
+            }
+        } else if !is_spice {
+            out.push(line.to_string());
+        }
+    }
+    let mut s = out.join("\n");
+    if content.ends_with('\n') {
+        s.push('\n');
+    }
+    s
+}
+
+/// Reset a VM by recreating its disk from a backing file or template
+pub fn reset_vm(vm: &DiscoveredVm) -> Result<()> {
+    // Find the primary disk
+    let disk = vm
+        .config
+        .primary_disk()
+        .context("VM has no disk configured")?;
+
+    let disk_path = &disk.path;
+
+    // Check for a backing file or template
+    let info = super::snapshot::get_disk_info(disk_path).context("Failed to get disk info")?;
+
+    if let Some(backing) = &info.backing_file {
+        // Recreate from backing file
+        let backing_path = Path::new(backing);
+        if !backing_path.exists() {
+            bail!("Backing file not found: {}", backing);
+        }
+
+        // Remove old disk
+        std::fs::remove_file(disk_path).context("Failed to remove old disk")?;
 
         // Create new disk with backing file
         let disk_str = path_to_str(disk_path)?;
@@ -465,36 +1498,23 @@ pub fn reset_vm(vm: &DiscoveredVm) -> Result<()> {
     Ok(())
 }
 
-/// Delete a VM (move to trash or permanently delete)
-pub fn delete_vm(vm: &DiscoveredVm, permanent: bool) -> Result<()> {
-    if permanent {
-        std::fs::remove_dir_all(&vm.path).context("Failed to delete VM directory")?;
-    } else {
-        // Move to trash using trash-cli if available
-        let result = Command::new("trash-put").arg(&vm.path).output();
-
-        match result {
-            Ok(output) if output.status.success() => {}
-            _ => {
-                // Fall back to moving to a .trash directory
-                let trash_dir = vm.path.parent().unwrap_or(Path::new(".")).join(".trash");
-                std::fs::create_dir_all(&trash_dir).context("Failed to create trash directory")?;
-
-                // Find a unique name in trash (append timestamp if needed)
-                let mut trash_path = trash_dir.join(&vm.id);
-                if trash_path.exists() {
-                    let timestamp = std::time::SystemTime::now()
-                        .duration_since(std::time::UNIX_EPOCH)
-                        .map(|d| d.as_secs())
-                        .unwrap_or(0);
-                    trash_path = trash_dir.join(format!("{}-{}", vm.id, timestamp));
-                }
-
-                std::fs::rename(&vm.path, &trash_path).context("Failed to move VM to trash")?;
-            }
+/// Delete a VM: move its folder into `trash_dir` (restorable from the Trash
+/// screen), or remove it permanently when `trash_dir` is None. A move to
+/// another filesystem reports the percent copied to `on_progress`.
+pub fn delete_vm(
+    vm: &DiscoveredVm,
+    trash_dir: Option<&Path>,
+    on_progress: impl FnMut(f64),
+) -> Result<()> {
+    match trash_dir {
+        Some(trash_dir) => {
+            super::trash::move_to_trash(&vm.path, trash_dir, on_progress)
+                .context("Failed to move VM to trash")?;
+        }
+        None => {
+            std::fs::remove_dir_all(&vm.path).context("Failed to delete VM directory")?;
         }
     }
-
     Ok(())
 }
 
@@ -507,31 +1527,400 @@ pub fn rename_vm(vm: &DiscoveredVm, new_name: &str) -> Result<()> {
     crate::vm::create::write_vm_metadata(&vm.path, new_name, os_profile, notes)
         .context("Failed to write VM metadata")?;
 
+    // A window title that was just the old name follows the rename
+    if load_window_title(vm).is_some_and(|title| title == vm.display_name()) {
+        save_window_title(vm, new_name)?;
+    }
+
     Ok(())
 }
 
-/// Save (or clear) the notes for a VM, preserving display_name and os_profile.
-#[allow(dead_code)]
-pub fn save_notes(vm: &DiscoveredVm, notes: Option<&str>) -> Result<()> {
-    let display_name = vm.display_name();
-    let os_profile = vm.os_profile.as_deref().or(Some(&vm.id));
-    crate::vm::create::write_vm_metadata(&vm.path, &display_name, os_profile, notes)
-        .context("Failed to write VM notes")?;
-    Ok(())
+/// How generated launch scripts find their own directory
+const VM_DIR_LINE: &str = r#"VM_DIR="$(dirname "$(readlink -f "$0")")""#;
+
+/// The QEMU window title a launch script sets in [`WINDOW_TITLE_VAR`]
+pub fn script_window_title(content: &str) -> Option<String> {
+    let prefix = format!("{}=", WINDOW_TITLE_VAR);
+    let value = content
+        .lines()
+        .find_map(|line| line.strip_prefix(&prefix))?;
+    let value = value.trim();
+    let title = if let Some(quoted) = value.strip_prefix('\'').and_then(|v| v.strip_suffix('\'')) {
+        quoted.replace("'\\''", "'")
+    } else {
+        value.trim_matches('"').to_string()
+    };
+    Some(title)
 }
 
-/// A running QEMU process with its PID, command line, and working directory.
-pub struct QemuProcess {
-    pub pid: u32,
-    pub cmdline: String,
+/// Set a launch script's window title. Scripts without one (made before
+/// titles, or by hand) get [`WINDOW_TITLE_VAR`] and [`WINDOW_NAME_ARG`] in
+/// place of any `-name` they had; `None` if there is no QEMU command.
+pub(crate) fn set_script_window_title(content: &str, title: &str) -> Option<String> {
+    let prefix = format!("{}=", WINDOW_TITLE_VAR);
+    let line = format!("{}{}", prefix, shell_escape(title));
+    if content.lines().any(|l| l.starts_with(&prefix)) {
+        let mut out: Vec<String> = content
+            .lines()
+            .map(|l| {
+                if l.starts_with(&prefix) {
+                    line.clone()
+                } else {
+                    l.to_string()
+                }
+            })
+            .collect();
+        if content.ends_with('\n') {
+            out.push(String::new());
+        }
+        return Some(out.join("\n"));
+    }
+
+    let name = regex::Regex::new(r#"\s-name\s+(?:"[^"]*"|'[^']*'|\S+)"#).expect("valid regex");
+    let stripped: Vec<String> = content
+        .lines()
+        .map(|l| {
+            if l.trim_start().starts_with('#') {
+                l.to_string()
+            } else {
+                name.replace_all(l, "").to_string()
+            }
+        })
+        .collect();
+    let mut stripped = stripped.join("\n");
+    if content.ends_with('\n') {
+        stripped.push('\n');
+    }
+    let body = insert_after_qemu_command(&stripped, WINDOW_NAME_ARG)?;
+
+    // The variables go after the shebang, or after VM_DIR if the script has it
+    let mut vars = vec![line];
+    let has_vm_dir = body.lines().any(|l| l.starts_with("VM_DIR="));
+    if !has_vm_dir {
+        vars.insert(0, VM_DIR_LINE.to_string());
+    }
+    let mut out: Vec<&str> = Vec::new();
+    let mut inserted = false;
+    for (i, l) in body.lines().enumerate() {
+        out.push(l);
+        let anchor = if has_vm_dir {
+            l.starts_with("VM_DIR=")
+        } else {
+            i == 0 && l.starts_with("#!")
+        };
+        if anchor && !inserted {
+            out.extend(vars.iter().map(String::as_str));
+            inserted = true;
+        }
+    }
+    if !inserted {
+        let mut all: Vec<&str> = vars.iter().map(String::as_str).collect();
+        all.extend(out);
+        out = all;
+    }
+    let mut s = out.join("\n");
+    if body.ends_with('\n') {
+        s.push('\n');
+    }
+    Some(s)
+}
+
+/// The window title in `vm`'s launch.sh, if it sets one
+pub fn load_window_title(vm: &DiscoveredVm) -> Option<String> {
+    let content = std::fs::read_to_string(&vm.launch_script).ok()?;
+    script_window_title(&content)
+}
+
+/// Set the QEMU window title in `vm`'s launch.sh; takes effect on next launch
+pub fn save_window_title(vm: &DiscoveredVm, title: &str) -> Result<()> {
+    let content = std::fs::read_to_string(&vm.launch_script).context("Failed to read launch.sh")?;
+    let updated =
+        set_script_window_title(&content, title).context("No QEMU command found in launch.sh")?;
+    std::fs::write(&vm.launch_script, updated).context("Failed to write launch.sh")?;
+    Ok(())
+}
+
+/// Save (or clear) the notes for a VM, preserving display_name and os_profile.
+#[allow(dead_code)]
+pub fn save_notes(vm: &DiscoveredVm, notes: Option<&str>) -> Result<()> {
+    let display_name = vm.display_name();
+    let os_profile = vm.os_profile.as_deref().or(Some(&vm.id));
+    crate::vm::create::write_vm_metadata(&vm.path, &display_name, os_profile, notes)
+        .context("Failed to write VM notes")?;
+    Ok(())
+}
+
+/// A running QEMU process with its PID, command line, and working directory.
+pub struct QemuProcess {
+    pub pid: u32,
+    pub cmdline: String,
+    /// Arguments from `/proc/<pid>/cmdline`, which keeps arguments with
+    /// spaces intact (unlike `cmdline`); empty if it couldn't be read
+    pub args: Vec<String>,
     /// The working directory of the process (from `/proc/<pid>/cwd`)
     pub cwd: Option<std::path::PathBuf>,
+    /// `process=` from the process's `-name`: the VM directory name for
+    /// scripts vm-curator generated
+    pub process_tag: Option<String>,
+    /// Whether the guest agent answered a `guest-ping` (only probed when the
+    /// VM directory has a `qga.sock`; filled in by the caller, not by detection)
+    pub guest_agent_ready: bool,
+    /// Current guest RAM in MB from `query-balloon` (only probed when the
+    /// command line has a virtio-balloon device; filled in by the caller)
+    pub balloon_mb: Option<u64>,
+}
+
+impl QemuProcess {
+    /// Image files the process opened (`-drive file=`, `-blockdev
+    /// filename=`, `-hda`, `-cdrom`, ...). Relative paths resolve against
+    /// the working directory and are skipped when it is unknown.
+    pub fn drive_files(&self) -> Vec<PathBuf> {
+        let args: Vec<String> = if self.args.is_empty() {
+            self.cmdline
+                .split_whitespace()
+                .map(str::to_string)
+                .collect()
+        } else {
+            self.args.clone()
+        };
+        // -daemonize moves QEMU to /, after it resolved relative paths
+        let cwd = self.cwd.as_deref().filter(|cwd| *cwd != Path::new("/"));
+        args.windows(2)
+            .filter_map(|pair| match pair[0].as_str() {
+                "-drive" => qemu_opt_value(&pair[1], "file"),
+                "-blockdev" => qemu_opt_value(&pair[1], "filename"),
+                "-hda" | "-hdb" | "-hdc" | "-hdd" | "-cdrom" | "-fda" | "-fdb" => {
+                    Some(pair[1].clone())
+                }
+                _ => None,
+            })
+            .filter_map(|file| {
+                let path = PathBuf::from(file);
+                if path.is_absolute() {
+                    Some(path)
+                } else {
+                    cwd.map(|cwd| cwd.join(path))
+                }
+            })
+            .collect()
+    }
+}
+
+/// The value of `key` in a QEMU option string (`file=a,,b.img,if=ide`),
+/// with doubled commas turned back into commas
+fn qemu_opt_value(opts: &str, key: &str) -> Option<String> {
+    let prefix = format!("{}=", key);
+    opts.replace(",,", "\u{0}")
+        .split(',')
+        .find_map(|opt| opt.strip_prefix(&prefix).map(|v| v.replace('\u{0}', ",")))
+}
+
+/// `path` with symlinks resolved, or as given if it doesn't exist
+fn canonical(path: &Path) -> PathBuf {
+    std::fs::canonicalize(path).unwrap_or_else(|_| path.to_path_buf())
+}
+
+/// How a QEMU process was tied to a VM, weakest first
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+enum ProcessMatch {
+    /// Opens one of the VM's disk images
+    Disk,
+    /// Runs in the VM's directory
+    WorkingDir,
+    /// `-name ...,process=<VM id>`
+    Tag,
+}
+
+/// How `proc` is tied to `vm`, if at all.
+///
+/// Generated launch scripts tag QEMU with `process=<VM id>`. Launch scripts
+/// also run QEMU from the VM's directory, so /proc/<pid>/cwd reliably
+/// identifies a VM — unlike disk filenames, which are often generic
+/// (e.g., "disk.qcow2"). Disk paths are compared whole, and only when the
+/// working directory is unreadable or `/` (a `-daemonize`d QEMU).
+fn process_match(vm: &DiscoveredVm, proc: &QemuProcess) -> Option<ProcessMatch> {
+    if proc.process_tag.as_deref() == Some(vm.id.as_str()) {
+        return Some(ProcessMatch::Tag);
+    }
+    match proc.cwd.as_deref().filter(|cwd| *cwd != Path::new("/")) {
+        Some(cwd) => (canonical(cwd) == canonical(&vm.path)).then_some(ProcessMatch::WorkingDir),
+        None => {
+            let disks: Vec<PathBuf> = vm
+                .config
+                .disks
+                .iter()
+                .map(|d| canonical(&vm.path.join(&d.path)))
+                .collect();
+            proc.drive_files()
+                .iter()
+                .any(|file| disks.contains(&canonical(file)))
+                .then_some(ProcessMatch::Disk)
+        }
+    }
+}
+
+/// PID of the QEMU process running `vm`, if any (see [`process_match`])
+pub fn vm_process_pid(vm: &DiscoveredVm, processes: &[QemuProcess]) -> Option<u32> {
+    processes
+        .iter()
+        .filter_map(|proc| process_match(vm, proc).map(|m| (m, proc.pid)))
+        .max_by_key(|(m, _)| *m)
+        .map(|(_, pid)| pid)
+}
+
+/// Running VMs by id, each process given to the one VM it is most strongly
+/// tied to. A process that opens disks of several VMs (a shared disk) and
+/// has nothing stronger to go on counts for none of them.
+pub fn running_vm_pids(
+    vms: &[DiscoveredVm],
+    processes: &[QemuProcess],
+) -> std::collections::HashMap<String, u32> {
+    let mut pids = std::collections::HashMap::new();
+    for proc in processes {
+        let mut matches: Vec<(ProcessMatch, &DiscoveredVm)> = vms
+            .iter()
+            .filter_map(|vm| process_match(vm, proc).map(|m| (m, vm)))
+            .collect();
+        let Some(strongest) = matches.iter().map(|(m, _)| *m).max() else {
+            continue;
+        };
+        matches.retain(|(m, _)| *m == strongest);
+        if let [(_, vm)] = matches.as_slice() {
+            pids.entry(vm.id.clone()).or_insert(proc.pid);
+        }
+    }
+    pids
+}
+
+/// Refuse to launch `vm` while another VM using one of its disks is
+/// running; two QEMU processes writing one image corrupt it. `is_running`
+/// says whether the VM with a given id is running.
+pub fn check_shared_disks(
+    vm: &DiscoveredVm,
+    vms: &[DiscoveredVm],
+    is_running: impl Fn(&str) -> bool,
+) -> Result<()> {
+    for shared in &vm.shared_disks {
+        if let Some(id) = shared.vm_ids.iter().find(|id| is_running(id)) {
+            let other = vms
+                .iter()
+                .find(|v| &v.id == id)
+                .map(|v| v.display_name())
+                .unwrap_or_else(|| id.clone());
+            bail!(
+                "{} is also used by {}, which is running. Stop it before launching {}; \
+                 two VMs writing one disk image corrupt it.",
+                shared.path.display(),
+                other,
+                vm.display_name()
+            );
+        }
+    }
+    Ok(())
+}
+
+/// Where the kernel lists block devices, including loop devices' backing files
+const SYS_BLOCK: &str = "/sys/block";
+
+/// Whether `cmdline` names `word` as a whole path or path component, so
+/// `disk.qcow2` doesn't match `old-disk.qcow2`
+fn mentions_path(cmdline: &str, word: &str) -> bool {
+    cmdline.match_indices(word).any(|(i, _)| {
+        let before = cmdline[..i].chars().next_back();
+        let after = cmdline[i + word.len()..].chars().next();
+        !before.is_some_and(|c| c.is_alphanumeric() || matches!(c, '-' | '_' | '.'))
+            && !after.is_some_and(|c| c.is_alphanumeric() || matches!(c, '-' | '_' | '.' | '/'))
+    })
+}
+
+/// PID of a QEMU process with `disk` on its command line: by full path, or
+/// by file name when the process runs in the disk's directory
+fn process_using_disk(disk: &Path, processes: &[QemuProcess]) -> Option<u32> {
+    let full = disk.to_str()?;
+    let name = disk.file_name()?.to_str()?;
+    processes
+        .iter()
+        .find(|proc| {
+            mentions_path(&proc.cmdline, full)
+                || (proc.cwd.as_deref() == disk.parent() && mentions_path(&proc.cmdline, name))
+        })
+        .map(|proc| proc.pid)
+}
+
+/// Loop device (e.g. `/dev/loop0`) backed by `disk`, from `sys_block`
+fn loop_device_for(disk: &Path, sys_block: &Path) -> Option<String> {
+    let disk = std::fs::canonicalize(disk).ok()?;
+    std::fs::read_dir(sys_block)
+        .ok()?
+        .flatten()
+        .filter(|entry| entry.file_name().to_string_lossy().starts_with("loop"))
+        .find(|entry| {
+            std::fs::read_to_string(entry.path().join("loop/backing_file"))
+                .is_ok_and(|backing| Path::new(backing.trim()) == disk)
+        })
+        .map(|entry| format!("/dev/{}", entry.file_name().to_string_lossy()))
+}
+
+/// Refuse to launch `vm` while one of its disk images is already open: by a
+/// QEMU process (including one started by hand), a loop device, or anything
+/// else holding QEMU's image lock. QEMU itself would only fail with a terse
+/// "Failed to get write lock".
+pub fn check_disks_not_open(
+    vm: &DiscoveredVm,
+    processes: &[QemuProcess],
+    sys_block: &Path,
+) -> Result<()> {
+    let disks = vm
+        .config
+        .disks
+        .iter()
+        .filter(|d| super::discovery::is_disk_image(&d.path) && d.path.is_file());
+    for disk in disks {
+        let path = disk.path.display();
+        if let Some(pid) = process_using_disk(&disk.path, processes) {
+            bail!(
+                "{} is already open in QEMU process {}. Stop that VM before launching {}.",
+                path,
+                pid,
+                vm.display_name()
+            );
+        }
+        if let Some(device) = loop_device_for(&disk.path, sys_block) {
+            bail!(
+                "{} is attached to loop device {}. Unmount it and run `losetup -d {}` first.",
+                path,
+                device,
+                device
+            );
+        }
+        if let Some(error) = crate::commands::qemu_img::image_lock_error(&disk.path) {
+            bail!("{} is in use by another process:\n{}", path, error);
+        }
+    }
+    Ok(())
+}
+
+/// [`check_shared_disks`] against the QEMU processes running right now
+pub fn check_shared_disks_live(vm: &DiscoveredVm, vms: &[DiscoveredVm]) -> Result<()> {
+    if vm.shared_disks.is_empty() {
+        return Ok(());
+    }
+    let processes = detect_qemu_processes();
+    check_shared_disks(vm, vms, |id| {
+        vms.iter()
+            .any(|other| other.id == id && vm_process_pid(other, &processes).is_some())
+    })
 }
 
 /// Detect all running QEMU processes.
 /// Returns process info including the working directory read from /proc.
 pub fn detect_qemu_processes() -> Vec<QemuProcess> {
-    let output = match Command::new("pgrep").args(["-a", "qemu-system"]).output() {
+    // `-name ...,process=` renames the process, so match the command line
+    // rather than the process name
+    let output = match Command::new("pgrep")
+        .args(["-af", "^([^ ]*/)?qemu-system-"])
+        .output()
+    {
         Ok(o) => o,
         Err(_) => return Vec::new(),
     };
@@ -553,13 +1942,45 @@ pub fn detect_qemu_processes() -> Vec<QemuProcess> {
                 let cmdline = line[space_pos + 1..].to_string();
                 // Read the process working directory from /proc
                 let cwd = std::fs::read_link(format!("/proc/{}/cwd", pid)).ok();
-                processes.push(QemuProcess { pid, cmdline, cwd });
+                let args = read_process_args(pid);
+                let process_tag = process_tag(&args);
+                processes.push(QemuProcess {
+                    pid,
+                    cmdline,
+                    args,
+                    cwd,
+                    process_tag,
+                    guest_agent_ready: false,
+                    balloon_mb: None,
+                });
             }
         }
     }
     processes
 }
 
+/// A process's arguments from `/proc/<pid>/cmdline`; empty if unreadable
+fn read_process_args(pid: u32) -> Vec<String> {
+    std::fs::read(format!("/proc/{}/cmdline", pid))
+        .map(|raw| {
+            raw.split(|&b| b == 0)
+                .filter(|arg| !arg.is_empty())
+                .map(|arg| String::from_utf8_lossy(arg).into_owned())
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// `process=` of the `-name` option in a QEMU argv
+pub(crate) fn process_tag(args: &[String]) -> Option<String> {
+    let value = args
+        .iter()
+        .position(|arg| arg == "-name")
+        .and_then(|i| args.get(i + 1))?;
+    // Doubled commas are literal commas inside the title
+    qemu_opt_value(value, "process").filter(|tag| !tag.is_empty())
+}
+
 /// Send SIGTERM to a QEMU process (triggers ACPI shutdown in modern QEMU).
 pub fn stop_vm_by_pid(pid: u32) -> Result<()> {
     let status = Command::new("kill")
@@ -569,79 +1990,631 @@ pub fn stop_vm_by_pid(pid: u32) -> Result<()> {
     if !status.success() {
         bail!("kill returned non-zero exit code");
     }
-    Ok(())
+    Ok(())
+}
+
+/// Force-kill a QEMU process with SIGKILL.
+pub fn force_stop_vm(pid: u32) -> Result<()> {
+    let status = Command::new("kill")
+        .args(["-9", &pid.to_string()])
+        .status()
+        .context("Failed to send SIGKILL")?;
+    if !status.success() {
+        bail!("kill -9 returned non-zero exit code");
+    }
+    Ok(())
+}
+
+// USB Passthrough configuration markers
+const USB_MARKER_START: &str = "# >>> USB Passthrough (managed by vm-curator) >>>";
+const USB_MARKER_END: &str = "# <<< USB Passthrough <<<";
+
+/// Save USB passthrough configuration to the VM's launch.sh
+pub fn save_usb_passthrough(vm: &DiscoveredVm, devices: &[UsbPassthrough]) -> Result<()> {
+    let script_path = &vm.launch_script;
+    let content = std::fs::read_to_string(script_path).context("Failed to read launch.sh")?;
+
+    // Remove existing USB passthrough section if present
+    let content = remove_usb_section(&content);
+
+    // Generate new USB passthrough section
+    let usb_section = generate_usb_section(devices);
+
+    // Find where to insert the USB section
+    // We want to add it before the final qemu-system command or at the end
+    let new_content = insert_usb_section(&content, &usb_section);
+
+    // Write back
+    std::fs::write(script_path, new_content).context("Failed to write launch.sh")?;
+
+    Ok(())
+}
+
+/// Load USB passthrough configuration from the VM's launch.sh
+pub fn load_usb_passthrough(vm: &DiscoveredVm) -> Vec<UsbPassthrough> {
+    let script_path = &vm.launch_script;
+    let content = match std::fs::read_to_string(script_path) {
+        Ok(c) => c,
+        Err(_) => return Vec::new(),
+    };
+
+    parse_usb_section(&content)
+}
+
+fn remove_usb_section(content: &str) -> String {
+    let mut result = String::new();
+    let mut in_usb_section = false;
+
+    for line in content.lines() {
+        if line.trim() == USB_MARKER_START {
+            in_usb_section = true;
+            continue;
+        }
+        if line.trim() == USB_MARKER_END {
+            in_usb_section = false;
+            continue;
+        }
+        if !in_usb_section {
+            // Also remove any $USB_PASSTHROUGH_ARGS references from qemu command lines
+            let cleaned_line = remove_usb_args_from_line(line);
+            result.push_str(&cleaned_line);
+            result.push('\n');
+        }
+    }
+
+    // Remove trailing empty lines that may have accumulated
+    while result.ends_with("\n\n") {
+        result.pop();
+    }
+
+    result
+}
+
+fn remove_usb_args_from_line(line: &str) -> String {
+    // Remove $USB_PASSTHROUGH_ARGS from a line (with various formats)
+    line.replace(" $USB_PASSTHROUGH_ARGS", "")
+        .replace("$USB_PASSTHROUGH_ARGS ", "")
+        .replace("$USB_PASSTHROUGH_ARGS", "")
+}
+
+fn generate_usb_section(devices: &[UsbPassthrough]) -> String {
+    if devices.is_empty() {
+        return String::new();
+    }
+
+    let mut section = String::new();
+    section.push_str(USB_MARKER_START);
+    section.push('\n');
+    section.push_str("USB_PASSTHROUGH_ARGS=\"-usb");
+
+    // Check if any USB 3.0 devices are present
+    let has_usb3 = devices.iter().any(|d| d.is_usb3());
+
+    // Add xHCI controller if USB 3.0 devices are present
+    if has_usb3 {
+        section.push_str(" -device qemu-xhci,id=xhci,p2=8,p3=8");
+    }
+
+    // Add each USB device, attaching USB 3.0 devices to xHCI controller
+    for device in devices {
+        let bus = device.is_usb3().then_some("xhci.0");
+        section.push_str(&format!(" -device {}", device.device_spec(bus)));
+    }
+
+    section.push_str("\"\n");
+    section.push_str(USB_MARKER_END);
+    section.push('\n');
+
+    section
+}
+
+fn insert_usb_section(content: &str, usb_section: &str) -> String {
+    insert_args_section(content, usb_section, "$USB_PASSTHROUGH_ARGS")
+}
+
+/// Generic function to insert a variable-definition section into a launch script
+/// and append `$VAR_NAME` to all QEMU command endings.
+///
+/// The section is inserted at the top-level scope: before a `case` statement if
+/// one exists, otherwise before the first QEMU command. This ensures the variable
+/// is visible to all branches.
+pub fn insert_args_section(content: &str, section: &str, var_ref: &str) -> String {
+    if section.is_empty() {
+        return content.to_string();
+    }
+
+    let lines: Vec<&str> = content.lines().collect();
+    let mut result = String::new();
+    let mut inserted_section = false;
+
+    // Find ALL QEMU commands in the script (there may be multiple in a case statement)
+    // Each entry is (start_idx, end_idx) for a QEMU command
+    let mut qemu_commands: Vec<(usize, usize)> = Vec::new();
+    let mut i = 0;
+    while i < lines.len() {
+        let trimmed = lines[i].trim();
+        let is_qemu_line = (trimmed.starts_with("qemu-system-")
+            || trimmed.starts_with("exec qemu-system-")
+            || trimmed.starts_with("\"$QEMU\"")
+            || trimmed.starts_with("$QEMU "))
+            && !trimmed.starts_with('#');
+
+        if is_qemu_line {
+            let start_idx = i;
+            // Find the end of this QEMU command (the line without trailing \)
+            while i < lines.len() {
+                let line_trimmed = lines[i].trim();
+                if !line_trimmed.ends_with('\\') {
+                    break;
+                }
+                i += 1;
+            }
+            let end_idx = i;
+            qemu_commands.push((start_idx, end_idx));
+        }
+        i += 1;
+    }
+
+    // Track which end lines we need to modify
+    let qemu_end_indices: std::collections::HashSet<usize> =
+        qemu_commands.iter().map(|(_, end)| *end).collect();
+
+    // Determine where to insert the section: before a `case` statement if present
+    // (so the variable is in top-level scope), otherwise before the first QEMU command.
+    let case_line = lines.iter().position(|l| {
+        let trimmed = l.trim();
+        trimmed.starts_with("case ") && !trimmed.starts_with('#')
+    });
+    let first_qemu_start = qemu_commands.first().map(|(start, _)| *start);
+    let insert_before = case_line.or(first_qemu_start);
+
+    for (i, line) in lines.iter().enumerate() {
+        // Insert section at the chosen insertion point
+        if Some(i) == insert_before && !inserted_section {
+            result.push_str(section);
+            result.push('\n');
+            inserted_section = true;
+        }
+
+        // Modify ALL QEMU command endings to include the variable reference
+        if qemu_end_indices.contains(&i) && !var_ref.is_empty() {
+            let trimmed = line.trim_end();
+            if let Some(comment_pos) = trimmed.find(" #") {
+                let (cmd, comment) = trimmed.split_at(comment_pos);
+                result.push_str(cmd);
+                result.push(' ');
+                result.push_str(var_ref);
+                result.push_str(comment);
+            } else {
+                result.push_str(trimmed);
+                result.push(' ');
+                result.push_str(var_ref);
+            }
+            result.push('\n');
+            continue;
+        }
+
+        result.push_str(line);
+        if i < lines.len() - 1 {
+            result.push('\n');
+        }
+    }
+
+    // If we didn't find a suitable insertion point, append at the end
+    if !inserted_section {
+        if !result.ends_with('\n') {
+            result.push('\n');
+        }
+        result.push('\n');
+        result.push_str(section);
+    }
+
+    // Ensure file ends with newline
+    if !result.ends_with('\n') {
+        result.push('\n');
+    }
+
+    result
+}
+
+fn parse_usb_section(content: &str) -> Vec<UsbPassthrough> {
+    let mut devices = Vec::new();
+    let mut in_usb_section = false;
+
+    for line in content.lines() {
+        if line.trim() == USB_MARKER_START {
+            in_usb_section = true;
+            continue;
+        }
+        if line.trim() == USB_MARKER_END {
+            in_usb_section = false;
+            continue;
+        }
+        if in_usb_section && line.contains("USB_PASSTHROUGH_ARGS=") {
+            // Parse the USB args line
+            // Format: USB_PASSTHROUGH_ARGS="-usb -device usb-host,vendorid=0x1234,productid=0x5678 ..."
+            // Or with xHCI: USB_PASSTHROUGH_ARGS="-usb -device qemu-xhci,id=xhci -device usb-host,bus=xhci.0,vendorid=0x1234,productid=0x5678 ..."
+            // Port-pinned devices add hostbus=N,hostport=P before the IDs
+            for part in line.split("-device usb-host,") {
+                if part.contains("vendorid=") && part.contains("productid=") {
+                    if let (Some(vid), Some(pid)) = (
+                        extract_hex_value(part, "vendorid="),
+                        extract_hex_value(part, "productid="),
+                    ) {
+                        // Detect USB version from bus assignment
+                        // If attached to xhci.0, it's USB 3.0; otherwise default to USB 2.0
+                        let usb_version = if part.contains("bus=xhci") {
+                            UsbVersion::Usb3
+                        } else {
+                            UsbVersion::Usb2
+                        };
+                        let port = extract_usb_option(part, "hostbus=")
+                            .and_then(|bus| bus.parse().ok())
+                            .zip(extract_usb_option(part, "hostport="))
+                            .map(|(bus, path)| UsbPort {
+                                bus,
+                                path: path.to_string(),
+                            });
+                        devices.push(UsbPassthrough {
+                            vendor_id: vid,
+                            product_id: pid,
+                            usb_version,
+                            port,
+                        });
+                    }
+                }
+            }
+        }
+    }
+
+    devices
+}
+
+/// The raw value of a `key=value` option in a `usb-host` device spec
+fn extract_usb_option<'a>(s: &'a str, prefix: &str) -> Option<&'a str> {
+    let start = s.find(prefix)? + prefix.len();
+    let rest = &s[start..];
+    let end = rest.find([',', ' ', '"', '\'']).unwrap_or(rest.len());
+    Some(&rest[..end]).filter(|v| !v.is_empty())
+}
+
+fn extract_hex_value(s: &str, prefix: &str) -> Option<u16> {
+    let value = extract_usb_option(s, prefix)?;
+    // Handle 0x prefix
+    u16::from_str_radix(value.strip_prefix("0x").unwrap_or(value), 16).ok()
+}
+
+// Shared Folders section markers
+const SHARED_FOLDERS_MARKER_START: &str = "# >>> Shared Folders (managed by vm-curator) >>>";
+const SHARED_FOLDERS_MARKER_END: &str = "# <<< Shared Folders <<<";
+
+/// A shared folder configuration for virtio-9p host-to-guest file sharing
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SharedFolder {
+    pub host_path: String,
+    pub mount_tag: String,
+}
+
+/// Escape a string for safe use in shell scripts
+fn shell_escape(s: &str) -> String {
+    if s.chars()
+        .all(|c| c.is_alphanumeric() || c == '-' || c == '_' || c == '.' || c == '/')
+    {
+        return s.to_string();
+    }
+    let escaped = s.replace('\'', "'\\''");
+    format!("'{}'", escaped)
+}
+
+/// Save shared folders configuration to the VM's launch.sh
+pub fn save_shared_folders(vm: &DiscoveredVm, folders: &[SharedFolder]) -> Result<()> {
+    let script_path = &vm.launch_script;
+    let content = std::fs::read_to_string(script_path).context("Failed to read launch.sh")?;
+
+    // Remove existing shared folders section if present
+    let content = remove_shared_folders_section(&content);
+
+    // Determine device name based on architecture
+    let device_name = if vm.config.emulator.command().contains("aarch64")
+        || vm.config.emulator.command().contains("arm")
+    {
+        "virtio-9p-device"
+    } else {
+        "virtio-9p-pci"
+    };
+
+    // Generate new shared folders section
+    let section = generate_shared_folders_section(folders, device_name);
+
+    // Insert into script
+    let new_content = insert_shared_folders_section(&content, &section);
+
+    std::fs::write(script_path, new_content).context("Failed to write launch.sh")?;
+
+    Ok(())
+}
+
+/// Load shared folders configuration from the VM's launch.sh
+pub fn load_shared_folders(vm: &DiscoveredVm) -> Vec<SharedFolder> {
+    let content = match std::fs::read_to_string(&vm.launch_script) {
+        Ok(c) => c,
+        Err(_) => return Vec::new(),
+    };
+    parse_shared_folders_section(&content)
+}
+
+fn remove_shared_folders_section(content: &str) -> String {
+    let mut result = String::new();
+    let mut in_section = false;
+
+    for line in content.lines() {
+        if line.trim() == SHARED_FOLDERS_MARKER_START {
+            in_section = true;
+            continue;
+        }
+        if line.trim() == SHARED_FOLDERS_MARKER_END {
+            in_section = false;
+            continue;
+        }
+        if !in_section {
+            let cleaned_line = line
+                .replace(" $SHARED_FOLDERS_ARGS", "")
+                .replace("$SHARED_FOLDERS_ARGS ", "")
+                .replace("$SHARED_FOLDERS_ARGS", "");
+            result.push_str(&cleaned_line);
+            result.push('\n');
+        }
+    }
+
+    // Remove trailing empty lines that may have accumulated
+    while result.ends_with("\n\n") {
+        result.pop();
+    }
+
+    result
+}
+
+fn generate_shared_folders_section(folders: &[SharedFolder], device_name: &str) -> String {
+    if folders.is_empty() {
+        return String::new();
+    }
+
+    let mut section = String::new();
+    section.push_str(SHARED_FOLDERS_MARKER_START);
+    section.push('\n');
+    section.push_str("SHARED_FOLDERS_ARGS=\"");
+
+    for (i, folder) in folders.iter().enumerate() {
+        let id = format!("fsdev{}", i);
+        let escaped_path = shell_escape(&folder.host_path);
+
+        if i > 0 {
+            section.push(' ');
+        }
+        section.push_str(&format!(
+            "-fsdev local,id={},path={},security_model=mapped-xattr -device {},fsdev={},mount_tag={}",
+            id, escaped_path, device_name, id, folder.mount_tag
+        ));
+    }
+
+    section.push_str("\"\n");
+    section.push_str(SHARED_FOLDERS_MARKER_END);
+    section.push('\n');
+
+    section
+}
+
+fn insert_shared_folders_section(content: &str, section: &str) -> String {
+    insert_args_section(content, section, "$SHARED_FOLDERS_ARGS")
+}
+
+fn parse_shared_folders_section(content: &str) -> Vec<SharedFolder> {
+    let mut folders = Vec::new();
+    let mut in_section = false;
+
+    for line in content.lines() {
+        if line.trim() == SHARED_FOLDERS_MARKER_START {
+            in_section = true;
+            continue;
+        }
+        if line.trim() == SHARED_FOLDERS_MARKER_END {
+            in_section = false;
+            continue;
+        }
+        if in_section && line.contains("SHARED_FOLDERS_ARGS=") {
+            // Parse -fsdev local,id=...,path=...,security_model=... -device ...,mount_tag=...
+            // Split on "-fsdev " to get each folder pair
+            for part in line.split("-fsdev ") {
+                if !part.contains("path=") {
+                    continue;
+                }
+                let host_path = extract_path_value(part);
+                let mount_tag = extract_simple_value(part, "mount_tag=");
+
+                if let (Some(path), Some(tag)) = (host_path, mount_tag) {
+                    folders.push(SharedFolder {
+                        host_path: path,
+                        mount_tag: tag,
+                    });
+                }
+            }
+        }
+    }
+
+    folders
+}
+
+/// Extract a path value from a -fsdev argument, handling shell quoting
+fn extract_path_value(s: &str) -> Option<String> {
+    let start = s.find("path=")? + 5;
+    let rest = &s[start..];
+
+    if let Some(inner) = rest.strip_prefix('\'') {
+        // Single-quoted path: find matching closing quote (handle escaped quotes)
+        let mut result = String::new();
+        let mut chars = inner.chars();
+        while let Some(c) = chars.next() {
+            if c == '\'' {
+                // Check for escaped quote pattern: '\''
+                if chars.as_str().starts_with("\\''") {
+                    result.push('\'');
+                    chars.next(); // skip backslash
+                    chars.next(); // skip first quote
+                } else {
+                    break;
+                }
+            } else {
+                result.push(c);
+            }
+        }
+        Some(result)
+    } else {
+        // Unquoted path: ends at comma or space
+        let end = rest.find([',', ' ', '"']).unwrap_or(rest.len());
+        Some(rest[..end].to_string())
+    }
 }
 
-/// Force-kill a QEMU process with SIGKILL.
-pub fn force_stop_vm(pid: u32) -> Result<()> {
-    let status = Command::new("kill")
-        .args(["-9", &pid.to_string()])
-        .status()
-        .context("Failed to send SIGKILL")?;
-    if !status.success() {
-        bail!("kill -9 returned non-zero exit code");
+/// Extract a simple key=value from a string
+fn extract_simple_value(s: &str, prefix: &str) -> Option<String> {
+    let start = s.find(prefix)? + prefix.len();
+    let rest = &s[start..];
+    let end = rest.find([',', ' ', '"', '\'']).unwrap_or(rest.len());
+    let value = rest[..end].trim();
+    if value.is_empty() {
+        None
+    } else {
+        Some(value.to_string())
     }
-    Ok(())
 }
 
-// USB Passthrough configuration markers
-const USB_MARKER_START: &str = "# >>> USB Passthrough (managed by vm-curator) >>>";
-const USB_MARKER_END: &str = "# <<< USB Passthrough <<<";
+// Boot order section markers
+const BOOT_ORDER_MARKER_START: &str = "# >>> Boot Order (managed by vm-curator) >>>";
+const BOOT_ORDER_MARKER_END: &str = "# <<< Boot Order <<<";
 
-/// Save USB passthrough configuration to the VM's launch.sh
-pub fn save_usb_passthrough(vm: &DiscoveredVm, devices: &[UsbPassthrough]) -> Result<()> {
+/// Save the boot order / boot menu setting to the VM's launch.sh
+pub fn save_boot_order(vm: &DiscoveredVm, boot: &BootOrder) -> Result<()> {
     let script_path = &vm.launch_script;
     let content = std::fs::read_to_string(script_path).context("Failed to read launch.sh")?;
 
-    // Remove existing USB passthrough section if present
-    let content = remove_usb_section(&content);
-
-    // Generate new USB passthrough section
-    let usb_section = generate_usb_section(devices);
-
-    // Find where to insert the USB section
-    // We want to add it before the final qemu-system command or at the end
-    let new_content = insert_usb_section(&content, &usb_section);
+    let content = remove_boot_order_section(&content);
+    let section = generate_boot_order_section(boot);
+    let new_content = insert_args_section(&content, &section, "$BOOT_ORDER_ARGS");
 
-    // Write back
     std::fs::write(script_path, new_content).context("Failed to write launch.sh")?;
 
     Ok(())
 }
 
-/// Load USB passthrough configuration from the VM's launch.sh
-pub fn load_usb_passthrough(vm: &DiscoveredVm) -> Vec<UsbPassthrough> {
+/// Save a CPU model/flags configuration by rewriting the `-cpu` argument of
+/// every QEMU command in the VM's launch.sh.
+pub fn save_cpu_config(vm: &DiscoveredVm, cpu: &CpuConfig) -> Result<()> {
     let script_path = &vm.launch_script;
-    let content = match std::fs::read_to_string(script_path) {
-        Ok(c) => c,
-        Err(_) => return Vec::new(),
-    };
+    let content = std::fs::read_to_string(script_path).context("Failed to read launch.sh")?;
+    let new_content = set_cpu_arg(&content, &cpu.to_arg());
+    std::fs::write(script_path, new_content).context("Failed to write launch.sh")?;
+    Ok(())
+}
 
-    parse_usb_section(&content)
+/// Load the CPU model/flags from the VM's launch.sh (`host` if none is set)
+pub fn load_cpu_config(vm: &DiscoveredVm) -> CpuConfig {
+    std::fs::read_to_string(&vm.launch_script)
+        .ok()
+        .and_then(|content| {
+            crate::vm::launch_parser::parse_launch_script(&vm.launch_script, &content).ok()
+        })
+        .and_then(|config| config.cpu_model)
+        .map(|model| CpuConfig::parse(&model))
+        .unwrap_or_else(|| CpuConfig::parse("host"))
 }
 
-fn remove_usb_section(content: &str) -> String {
+/// Replace the value of every `-cpu` argument with `value`. Scripts without
+/// one get `-cpu <value>` added right after each QEMU binary.
+pub(crate) fn set_cpu_arg(content: &str, value: &str) -> String {
+    let escaped = shell_escape(value);
+    let mut found = false;
+    let mut lines: Vec<String> = Vec::new();
+
+    for line in content.lines() {
+        if line.trim_start().starts_with('#') {
+            lines.push(line.to_string());
+            continue;
+        }
+        let pos = line
+            .match_indices("-cpu ")
+            .map(|(i, _)| i)
+            .find(|&i| i == 0 || line[..i].ends_with(char::is_whitespace));
+        match pos {
+            Some(i) => {
+                found = true;
+                let rest = line[i + 5..].trim_start();
+                let end = rest
+                    .find(|c: char| c.is_whitespace() || c == '\\')
+                    .unwrap_or(rest.len());
+                lines.push(format!("{}-cpu {}{}", &line[..i], escaped, &rest[end..]));
+            }
+            None => lines.push(line.to_string()),
+        }
+    }
+
+    if !found {
+        lines = lines
+            .into_iter()
+            .map(|line| {
+                let t = line.trim_start();
+                if t.starts_with('#') || !t.starts_with("qemu-system-") {
+                    return line;
+                }
+                match t.find(char::is_whitespace) {
+                    Some(i) => {
+                        let indent = line.len() - t.len();
+                        let (bin, rest) = line.split_at(indent + i);
+                        format!("{} -cpu {}{}", bin, escaped, rest)
+                    }
+                    None => format!("{} -cpu {}", line, escaped),
+                }
+            })
+            .collect();
+    }
+
+    let mut result = lines.join("\n");
+    if content.ends_with('\n') {
+        result.push('\n');
+    }
+    result
+}
+
+/// Load the boot order / boot menu setting from the VM's launch.sh
+pub fn load_boot_order(vm: &DiscoveredVm) -> BootOrder {
+    match std::fs::read_to_string(&vm.launch_script) {
+        Ok(content) => parse_boot_order_section(&content),
+        Err(_) => BootOrder::default(),
+    }
+}
+
+fn remove_boot_order_section(content: &str) -> String {
     let mut result = String::new();
-    let mut in_usb_section = false;
+    let mut in_section = false;
 
     for line in content.lines() {
-        if line.trim() == USB_MARKER_START {
-            in_usb_section = true;
+        if line.trim() == BOOT_ORDER_MARKER_START {
+            in_section = true;
             continue;
         }
-        if line.trim() == USB_MARKER_END {
-            in_usb_section = false;
+        if line.trim() == BOOT_ORDER_MARKER_END {
+            in_section = false;
             continue;
         }
-        if !in_usb_section {
-            // Also remove any $USB_PASSTHROUGH_ARGS references from qemu command lines
-            let cleaned_line = remove_usb_args_from_line(line);
+        if !in_section {
+            let cleaned_line = line
+                .replace(" $BOOT_ORDER_ARGS", "")
+                .replace("$BOOT_ORDER_ARGS ", "")
+                .replace("$BOOT_ORDER_ARGS", "");
             result.push_str(&cleaned_line);
             result.push('\n');
         }
     }
 
-    // Remove trailing empty lines that may have accumulated
     while result.ends_with("\n\n") {
         result.pop();
     }
@@ -649,304 +2622,311 @@ fn remove_usb_section(content: &str) -> String {
     result
 }
 
-fn remove_usb_args_from_line(line: &str) -> String {
-    // Remove $USB_PASSTHROUGH_ARGS from a line (with various formats)
-    line.replace(" $USB_PASSTHROUGH_ARGS", "")
-        .replace("$USB_PASSTHROUGH_ARGS ", "")
-        .replace("$USB_PASSTHROUGH_ARGS", "")
-}
-
-fn generate_usb_section(devices: &[UsbPassthrough]) -> String {
-    if devices.is_empty() {
+/// Generate the boot order section. The normal-boot value applies when the
+/// script is run without arguments; install/CD-ROM/recovery/floppy branches
+/// already pick their boot device (`-boot d`, `-boot a`), and QEMU merges
+/// repeated `-boot` options, so those branches only get the menu toggle.
+fn generate_boot_order_section(boot: &BootOrder) -> String {
+    if boot.is_default() {
         return String::new();
     }
+    let normal_arg = boot.normal_boot_arg().unwrap_or_default();
+    let media_arg = boot.media_boot_arg().unwrap_or_default();
 
     let mut section = String::new();
-    section.push_str(USB_MARKER_START);
+    section.push_str(BOOT_ORDER_MARKER_START);
     section.push('\n');
-    section.push_str("USB_PASSTHROUGH_ARGS=\"-usb");
-
-    // Check if any USB 3.0 devices are present
-    let has_usb3 = devices.iter().any(|d| d.is_usb3());
-
-    // Add xHCI controller if USB 3.0 devices are present
-    if has_usb3 {
-        section.push_str(" -device qemu-xhci,id=xhci,p2=8,p3=8");
-    }
-
-    // Add each USB device, attaching USB 3.0 devices to xHCI controller
-    for device in devices {
-        if device.is_usb3() {
-            section.push_str(&format!(
-                " -device usb-host,bus=xhci.0,vendorid=0x{:04x},productid=0x{:04x}",
-                device.vendor_id, device.product_id
-            ));
-        } else {
-            section.push_str(&format!(
-                " -device usb-host,vendorid=0x{:04x},productid=0x{:04x}",
-                device.vendor_id, device.product_id
-            ));
-        }
+    section.push_str(&format!("BOOT_ORDER_ARGS=\"{}\"\n", normal_arg));
+    if media_arg != normal_arg {
+        section.push_str("if [[ -n \"$1\" ]]; then\n");
+        section.push_str(&format!("    BOOT_ORDER_ARGS=\"{}\"\n", media_arg));
+        section.push_str("fi\n");
     }
-
-    section.push_str("\"\n");
-    section.push_str(USB_MARKER_END);
+    section.push_str(BOOT_ORDER_MARKER_END);
     section.push('\n');
 
     section
 }
 
-fn insert_usb_section(content: &str, usb_section: &str) -> String {
-    insert_args_section(content, usb_section, "$USB_PASSTHROUGH_ARGS")
-}
-
-/// Generic function to insert a variable-definition section into a launch script
-/// and append `$VAR_NAME` to all QEMU command endings.
-///
-/// The section is inserted at the top-level scope: before a `case` statement if
-/// one exists, otherwise before the first QEMU command. This ensures the variable
-/// is visible to all branches.
-pub fn insert_args_section(content: &str, section: &str, var_ref: &str) -> String {
-    if section.is_empty() {
-        return content.to_string();
-    }
-
-    let lines: Vec<&str> = content.lines().collect();
-    let mut result = String::new();
-    let mut inserted_section = false;
-
-    // Find ALL QEMU commands in the script (there may be multiple in a case statement)
-    // Each entry is (start_idx, end_idx) for a QEMU command
-    let mut qemu_commands: Vec<(usize, usize)> = Vec::new();
-    let mut i = 0;
-    while i < lines.len() {
-        let trimmed = lines[i].trim();
-        let is_qemu_line = (trimmed.starts_with("qemu-system-")
-            || trimmed.starts_with("exec qemu-system-")
-            || trimmed.starts_with("\"$QEMU\"")
-            || trimmed.starts_with("$QEMU "))
-            && !trimmed.starts_with('#');
+fn parse_boot_order_section(content: &str) -> BootOrder {
+    let mut boot = BootOrder::default();
+    let mut in_section = false;
 
-        if is_qemu_line {
-            let start_idx = i;
-            // Find the end of this QEMU command (the line without trailing \)
-            while i < lines.len() {
-                let line_trimmed = lines[i].trim();
-                if !line_trimmed.ends_with('\\') {
-                    break;
-                }
-                i += 1;
-            }
-            let end_idx = i;
-            qemu_commands.push((start_idx, end_idx));
+    for line in content.lines() {
+        let trimmed = line.trim();
+        if trimmed == BOOT_ORDER_MARKER_START {
+            in_section = true;
+            continue;
         }
-        i += 1;
-    }
-
-    // Track which end lines we need to modify
-    let qemu_end_indices: std::collections::HashSet<usize> =
-        qemu_commands.iter().map(|(_, end)| *end).collect();
-
-    // Determine where to insert the section: before a `case` statement if present
-    // (so the variable is in top-level scope), otherwise before the first QEMU command.
-    let case_line = lines.iter().position(|l| {
-        let trimmed = l.trim();
-        trimmed.starts_with("case ") && !trimmed.starts_with('#')
-    });
-    let first_qemu_start = qemu_commands.first().map(|(start, _)| *start);
-    let insert_before = case_line.or(first_qemu_start);
-
-    for (i, line) in lines.iter().enumerate() {
-        // Insert section at the chosen insertion point
-        if Some(i) == insert_before && !inserted_section {
-            result.push_str(section);
-            result.push('\n');
-            inserted_section = true;
+        if trimmed == BOOT_ORDER_MARKER_END {
+            break;
         }
-
-        // Modify ALL QEMU command endings to include the variable reference
-        if qemu_end_indices.contains(&i) {
-            let trimmed = line.trim_end();
-            if let Some(comment_pos) = trimmed.find(" #") {
-                let (cmd, comment) = trimmed.split_at(comment_pos);
-                result.push_str(cmd);
-                result.push(' ');
-                result.push_str(var_ref);
-                result.push_str(comment);
-            } else {
-                result.push_str(trimmed);
-                result.push(' ');
-                result.push_str(var_ref);
+        // The first assignment is the normal-boot value, which carries both settings
+        if in_section && trimmed.starts_with("BOOT_ORDER_ARGS=") {
+            let value = trimmed
+                .trim_start_matches("BOOT_ORDER_ARGS=")
+                .trim_matches('"');
+            let opts = value.trim_start_matches("-boot").trim();
+            for opt in opts.split(',') {
+                if let Some(order) = opt.strip_prefix("order=") {
+                    if BootOrder::is_valid_order(order) {
+                        boot.order = Some(order.to_string());
+                    }
+                } else if opt == "menu=on" {
+                    boot.menu = true;
+                }
             }
-            result.push('\n');
-            continue;
-        }
-
-        result.push_str(line);
-        if i < lines.len() - 1 {
-            result.push('\n');
+            break;
         }
     }
 
-    // If we didn't find a suitable insertion point, append at the end
-    if !inserted_section {
-        if !result.ends_with('\n') {
-            result.push('\n');
-        }
-        result.push('\n');
-        result.push_str(section);
-    }
+    boot
+}
 
-    // Ensure file ends with newline
-    if !result.ends_with('\n') {
-        result.push('\n');
-    }
+// Display window section markers
+const DISPLAY_WINDOW_MARKER_START: &str = "# >>> Display Window (managed by vm-curator) >>>";
+const DISPLAY_WINDOW_MARKER_END: &str = "# <<< Display Window <<<";
 
-    result
+/// Save the window scaling, full-screen and guest resolution options to the
+/// VM's launch.sh. Options the current display backend can't use are dropped.
+pub fn save_display_window(vm: &DiscoveredVm, window: &DisplayWindow) -> Result<()> {
+    let script_path = &vm.launch_script;
+    let content = std::fs::read_to_string(script_path).context("Failed to read launch.sh")?;
+    let new_content = apply_display_window(&content, window);
+    std::fs::write(script_path, new_content).context("Failed to write launch.sh")?;
+    Ok(())
 }
 
-fn parse_usb_section(content: &str) -> Vec<UsbPassthrough> {
-    let mut devices = Vec::new();
-    let mut in_usb_section = false;
-
+/// Read the display window options from a launch script
+pub fn parse_display_window(content: &str) -> DisplayWindow {
+    let mut window = DisplayWindow {
+        zoom_to_fit: display_args(content).any(|opts| opts.contains(&"zoom-to-fit=on")),
+        ..Default::default()
+    };
+    let mut in_section = false;
+    let (mut xres, mut yres) = (None, None);
+    let mut qxl_heads = 0;
     for line in content.lines() {
-        if line.trim() == USB_MARKER_START {
-            in_usb_section = true;
-            continue;
-        }
-        if line.trim() == USB_MARKER_END {
-            in_usb_section = false;
+        let trimmed = line.trim();
+        if trimmed == DISPLAY_WINDOW_MARKER_START {
+            in_section = true;
             continue;
-        }
-        if in_usb_section && line.contains("USB_PASSTHROUGH_ARGS=") {
-            // Parse the USB args line
-            // Format: USB_PASSTHROUGH_ARGS="-usb -device usb-host,vendorid=0x1234,productid=0x5678 ..."
-            // Or with xHCI: USB_PASSTHROUGH_ARGS="-usb -device qemu-xhci,id=xhci -device usb-host,bus=xhci.0,vendorid=0x1234,productid=0x5678 ..."
-            for part in line.split("-device usb-host,") {
-                if part.contains("vendorid=") && part.contains("productid=") {
-                    if let (Some(vid), Some(pid)) = (
-                        extract_hex_value(part, "vendorid="),
-                        extract_hex_value(part, "productid="),
-                    ) {
-                        // Detect USB version from bus assignment
-                        // If attached to xhci.0, it's USB 3.0; otherwise default to USB 2.0
-                        let usb_version = if part.contains("bus=xhci") {
-                            UsbVersion::Usb3
-                        } else {
-                            UsbVersion::Usb2
-                        };
-                        devices.push(UsbPassthrough {
-                            vendor_id: vid,
-                            product_id: pid,
-                            usb_version,
-                        });
-                    }
+        }
+        if trimmed == DISPLAY_WINDOW_MARKER_END {
+            break;
+        }
+        if in_section && trimmed.starts_with("DISPLAY_WINDOW_ARGS=") {
+            for word in trimmed
+                .trim_start_matches("DISPLAY_WINDOW_ARGS=")
+                .trim_matches('"')
+                .split_whitespace()
+            {
+                if word == "-full-screen" {
+                    window.full_screen = true;
+                } else if let Some(v) = word.strip_prefix("virtio-gpu-base.xres=") {
+                    xres = v.parse().ok();
+                } else if let Some(v) = word.strip_prefix("virtio-gpu-base.yres=") {
+                    yres = v.parse().ok();
+                } else if let Some(v) = word.strip_prefix("virtio-gpu-base.max_outputs=") {
+                    window.num_displays = v.parse().unwrap_or(1);
+                } else if word == "qxl" {
+                    qxl_heads += 1;
                 }
             }
         }
     }
-
-    devices
+    window.resolution = xres.zip(yres);
+    if qxl_heads > 0 {
+        window.num_displays = qxl_heads + 1;
+    }
+    window
 }
 
-fn extract_hex_value(s: &str, prefix: &str) -> Option<u16> {
-    let start = s.find(prefix)? + prefix.len();
-    let rest = &s[start..];
-
-    // Find end of hex value (comma, space, quote, or end of string)
-    let end = rest.find([',', ' ', '"', '\'']).unwrap_or(rest.len());
-
-    let hex_str = &rest[..end];
-
-    // Handle 0x prefix
-    let hex_str = hex_str.strip_prefix("0x").unwrap_or(hex_str);
+/// The graphics adapter a launch script gives the guest
+pub fn script_graphics(content: &str) -> VgaType {
+    // The display window section's `-global virtio-gpu-base...` isn't an adapter
+    let code = content
+        .lines()
+        .map(str::trim_start)
+        .filter(|line| !line.starts_with('#') && !line.starts_with("DISPLAY_WINDOW_ARGS="))
+        .collect::<Vec<_>>()
+        .join("\n");
+    if code.contains("virtio-vga") || code.contains("virtio-gpu") {
+        return VgaType::Virtio;
+    }
+    code.split_whitespace()
+        .skip_while(|word| *word != "-vga")
+        .nth(1)
+        .map(VgaType::from_str)
+        .unwrap_or_default()
+}
 
-    u16::from_str_radix(hex_str, 16).ok()
+/// Rewrite a launch script's display window options: `zoom-to-fit` on the
+/// `-display` arguments, the rest in a managed section. Everything is
+/// filtered through what the script's display backend supports.
+pub fn apply_display_window(content: &str, window: &DisplayWindow) -> String {
+    let backend = display_args(content)
+        .next()
+        .and_then(|opts| opts.first().map(|b| b.to_string()))
+        .unwrap_or_default();
+    let window = window.for_display(&backend);
+
+    let content = set_display_option(content, "zoom-to-fit", window.zoom_to_fit);
+    let content = remove_args_section(
+        &content,
+        DISPLAY_WINDOW_MARKER_START,
+        DISPLAY_WINDOW_MARKER_END,
+        "$DISPLAY_WINDOW_ARGS",
+    );
+    let args = window.to_args(&script_graphics(&content));
+    if args.is_empty() {
+        return content;
+    }
+    let section = format!(
+        "{}\nDISPLAY_WINDOW_ARGS=\"{}\"\n{}\n",
+        DISPLAY_WINDOW_MARKER_START,
+        args.join(" "),
+        DISPLAY_WINDOW_MARKER_END
+    );
+    insert_args_section(&content, &section, "$DISPLAY_WINDOW_ARGS")
 }
 
-// Shared Folders section markers
-const SHARED_FOLDERS_MARKER_START: &str = "# >>> Shared Folders (managed by vm-curator) >>>";
-const SHARED_FOLDERS_MARKER_END: &str = "# <<< Shared Folders <<<";
+/// Re-apply the script's own display window options, dropping any its
+/// (possibly just changed) display backend can't use
+pub fn fit_display_window_to_backend(content: &str) -> String {
+    apply_display_window(content, &parse_display_window(content))
+}
 
-/// A shared folder configuration for virtio-9p host-to-guest file sharing
-#[derive(Debug, Clone, PartialEq, Eq)]
-pub struct SharedFolder {
-    pub host_path: String,
-    pub mount_tag: String,
+/// The comma-separated values of each uncommented `-display` argument
+fn display_args(content: &str) -> impl Iterator<Item = Vec<&str>> {
+    content
+        .lines()
+        .filter(|line| !line.trim_start().starts_with('#'))
+        .filter_map(|line| {
+            let i = line
+                .match_indices("-display ")
+                .map(|(i, _)| i)
+                .find(|&i| i == 0 || line[..i].ends_with(char::is_whitespace))?;
+            let rest = line[i + 9..].trim_start();
+            let end = rest
+                .find(|c: char| c.is_whitespace() || c == '\\')
+                .unwrap_or(rest.len());
+            Some(rest[..end].split(',').collect())
+        })
 }
 
-/// Escape a string for safe use in shell scripts
-fn shell_escape(s: &str) -> String {
-    if s.chars()
-        .all(|c| c.is_alphanumeric() || c == '-' || c == '_' || c == '.' || c == '/')
-    {
-        return s.to_string();
+/// Add (`key=on`) or remove a sub-option on every `-display` argument,
+/// keeping it after the backend and `gl=on`
+fn set_display_option(content: &str, key: &str, on: bool) -> String {
+    let prefix = format!("{}=", key);
+    let mut lines: Vec<String> = Vec::new();
+    for line in content.lines() {
+        let pos = (!line.trim_start().starts_with('#'))
+            .then(|| {
+                line.match_indices("-display ")
+                    .map(|(i, _)| i)
+                    .find(|&i| i == 0 || line[..i].ends_with(char::is_whitespace))
+            })
+            .flatten();
+        let Some(i) = pos else {
+            lines.push(line.to_string());
+            continue;
+        };
+        let rest = line[i + 9..].trim_start();
+        let end = rest
+            .find(|c: char| c.is_whitespace() || c == '\\')
+            .unwrap_or(rest.len());
+        let mut opts: Vec<String> = rest[..end]
+            .split(',')
+            .filter(|opt| !opt.starts_with(&prefix))
+            .map(str::to_string)
+            .collect();
+        if on {
+            opts.push(format!("{}on", prefix));
+        }
+        lines.push(format!(
+            "{}-display {}{}",
+            &line[..i],
+            opts.join(","),
+            &rest[end..]
+        ));
     }
-    let escaped = s.replace('\'', "'\\''");
-    format!("'{}'", escaped)
+    let mut s = lines.join("\n");
+    if content.ends_with('\n') {
+        s.push('\n');
+    }
+    s
 }
 
-/// Save shared folders configuration to the VM's launch.sh
-pub fn save_shared_folders(vm: &DiscoveredVm, folders: &[SharedFolder]) -> Result<()> {
+// Tools ISO section markers
+const TOOLS_ISO_MARKER_START: &str = "# >>> Tools ISO (managed by vm-curator) >>>";
+const TOOLS_ISO_MARKER_END: &str = "# <<< Tools ISO <<<";
+const TOOLS_ISO_VAR_REF: &str = "\"${TOOLS_ISO_ARGS[@]}\"";
+
+/// Attach (or with `None`, detach) a persistent secondary CD-ROM, such as the
+/// virtio-win driver ISO, in the VM's launch.sh. It is added to every boot
+/// branch so it is available next to the install media.
+pub fn save_tools_iso(vm: &DiscoveredVm, iso: Option<&Path>) -> Result<()> {
     let script_path = &vm.launch_script;
     let content = std::fs::read_to_string(script_path).context("Failed to read launch.sh")?;
 
-    // Remove existing shared folders section if present
-    let content = remove_shared_folders_section(&content);
-
-    // Determine device name based on architecture
-    let device_name = if vm.config.emulator.command().contains("aarch64")
-        || vm.config.emulator.command().contains("arm")
-    {
-        "virtio-9p-device"
-    } else {
-        "virtio-9p-pci"
+    let content = remove_tools_iso_section(&content);
+    let new_content = match iso {
+        Some(iso) => {
+            let section = generate_tools_iso_section(iso);
+            insert_args_section(&content, &section, TOOLS_ISO_VAR_REF)
+        }
+        None => content,
     };
 
-    // Generate new shared folders section
-    let section = generate_shared_folders_section(folders, device_name);
-
-    // Insert into script
-    let new_content = insert_shared_folders_section(&content, &section);
-
     std::fs::write(script_path, new_content).context("Failed to write launch.sh")?;
 
     Ok(())
 }
 
-/// Load shared folders configuration from the VM's launch.sh
-pub fn load_shared_folders(vm: &DiscoveredVm) -> Vec<SharedFolder> {
-    let content = match std::fs::read_to_string(&vm.launch_script) {
-        Ok(c) => c,
-        Err(_) => return Vec::new(),
-    };
-    parse_shared_folders_section(&content)
+/// Load the tools ISO path from the VM's launch.sh
+pub fn load_tools_iso(vm: &DiscoveredVm) -> Option<std::path::PathBuf> {
+    let content = std::fs::read_to_string(&vm.launch_script).ok()?;
+    parse_tools_iso_section(&content)
 }
 
-fn remove_shared_folders_section(content: &str) -> String {
+fn remove_tools_iso_section(content: &str) -> String {
+    remove_args_section(
+        content,
+        TOOLS_ISO_MARKER_START,
+        TOOLS_ISO_MARKER_END,
+        TOOLS_ISO_VAR_REF,
+    )
+}
+
+/// Strip a managed section and every reference to its variable from the
+/// QEMU commands; the inverse of `insert_args_section`.
+fn remove_args_section(content: &str, start: &str, end: &str, var_ref: &str) -> String {
     let mut result = String::new();
     let mut in_section = false;
 
     for line in content.lines() {
-        if line.trim() == SHARED_FOLDERS_MARKER_START {
+        if line.trim() == start {
             in_section = true;
             continue;
         }
-        if line.trim() == SHARED_FOLDERS_MARKER_END {
+        if line.trim() == end {
             in_section = false;
             continue;
         }
-        if !in_section {
+        if !in_section && var_ref.is_empty() {
+            result.push_str(line);
+            result.push('\n');
+        } else if !in_section {
             let cleaned_line = line
-                .replace(" $SHARED_FOLDERS_ARGS", "")
-                .replace("$SHARED_FOLDERS_ARGS ", "")
-                .replace("$SHARED_FOLDERS_ARGS", "");
+                .replace(&format!(" {}", var_ref), "")
+                .replace(&format!("{} ", var_ref), "")
+                .replace(var_ref, "");
             result.push_str(&cleaned_line);
             result.push('\n');
         }
     }
 
-    // Remove trailing empty lines that may have accumulated
     while result.ends_with("\n\n") {
         result.pop();
     }
@@ -954,118 +2934,189 @@ fn remove_shared_folders_section(content: &str) -> String {
     result
 }
 
-fn generate_shared_folders_section(folders: &[SharedFolder], device_name: &str) -> String {
-    if folders.is_empty() {
-        return String::new();
-    }
-
+/// The path goes in its own variable and the args in an array, so paths with
+/// spaces survive word splitting; commas are doubled as QEMU's option parser
+/// requires.
+fn generate_tools_iso_section(iso: &Path) -> String {
     let mut section = String::new();
-    section.push_str(SHARED_FOLDERS_MARKER_START);
+    section.push_str(TOOLS_ISO_MARKER_START);
     section.push('\n');
-    section.push_str("SHARED_FOLDERS_ARGS=\"");
-
-    for (i, folder) in folders.iter().enumerate() {
-        let id = format!("fsdev{}", i);
-        let escaped_path = shell_escape(&folder.host_path);
-
-        if i > 0 {
-            section.push(' ');
-        }
-        section.push_str(&format!(
-            "-fsdev local,id={},path={},security_model=mapped-xattr -device {},fsdev={},mount_tag={}",
-            id, escaped_path, device_name, id, folder.mount_tag
-        ));
-    }
-
-    section.push_str("\"\n");
-    section.push_str(SHARED_FOLDERS_MARKER_END);
+    section.push_str(&format!(
+        "TOOLS_ISO={}\n",
+        shell_escape(&iso.display().to_string())
+    ));
+    section.push_str(&format!(
+        "TOOLS_ISO_ARGS=(-drive \"file=${{TOOLS_ISO//,/,,}},media=cdrom,index={}\")\n",
+        TOOLS_ISO_DRIVE_INDEX
+    ));
+    section.push_str(TOOLS_ISO_MARKER_END);
     section.push('\n');
 
     section
 }
 
-fn insert_shared_folders_section(content: &str, section: &str) -> String {
-    insert_args_section(content, section, "$SHARED_FOLDERS_ARGS")
+fn parse_tools_iso_section(content: &str) -> Option<std::path::PathBuf> {
+    parse_section_path(
+        content,
+        TOOLS_ISO_MARKER_START,
+        TOOLS_ISO_MARKER_END,
+        "TOOLS_ISO=",
+    )
 }
 
-fn parse_shared_folders_section(content: &str) -> Vec<SharedFolder> {
-    let mut folders = Vec::new();
+/// The shell-quoted path assigned with `assignment` (`VAR=`) inside a
+/// managed section
+fn parse_section_path(
+    content: &str,
+    start: &str,
+    end: &str,
+    assignment: &str,
+) -> Option<std::path::PathBuf> {
     let mut in_section = false;
 
     for line in content.lines() {
-        if line.trim() == SHARED_FOLDERS_MARKER_START {
+        let trimmed = line.trim();
+        if trimmed == start {
             in_section = true;
             continue;
         }
-        if line.trim() == SHARED_FOLDERS_MARKER_END {
-            in_section = false;
-            continue;
+        if trimmed == end {
+            break;
         }
-        if in_section && line.contains("SHARED_FOLDERS_ARGS=") {
-            // Parse -fsdev local,id=...,path=...,security_model=... -device ...,mount_tag=...
-            // Split on "-fsdev " to get each folder pair
-            for part in line.split("-fsdev ") {
-                if !part.contains("path=") {
-                    continue;
-                }
-                let host_path = extract_path_value(part);
-                let mount_tag = extract_simple_value(part, "mount_tag=");
-
-                if let (Some(path), Some(tag)) = (host_path, mount_tag) {
-                    folders.push(SharedFolder {
-                        host_path: path,
-                        mount_tag: tag,
-                    });
+        if in_section {
+            if let Some(value) = trimmed.strip_prefix(assignment) {
+                let path = match value.strip_prefix('\'').and_then(|v| v.strip_suffix('\'')) {
+                    Some(quoted) => quoted.replace("'\\''", "'"),
+                    None => value.to_string(),
+                };
+                if !path.is_empty() {
+                    return Some(std::path::PathBuf::from(path));
                 }
             }
         }
     }
 
-    folders
+    None
 }
 
-/// Extract a path value from a -fsdev argument, handling shell quoting
-fn extract_path_value(s: &str) -> Option<String> {
-    let start = s.find("path=")? + 5;
-    let rest = &s[start..];
+// Floppy drive section markers
+const FLOPPY_DRIVE_MARKER_START: &str = "# >>> Floppy Drive (managed by vm-curator) >>>";
+const FLOPPY_DRIVE_MARKER_END: &str = "# <<< Floppy Drive <<<";
+const FLOPPY_DRIVE_VAR_REF: &str = "\"${FLOPPY_DRIVE_ARGS[@]}\"";
 
-    if let Some(inner) = rest.strip_prefix('\'') {
-        // Single-quoted path: find matching closing quote (handle escaped quotes)
-        let mut result = String::new();
-        let mut chars = inner.chars();
-        while let Some(c) = chars.next() {
-            if c == '\'' {
-                // Check for escaped quote pattern: '\''
-                if chars.as_str().starts_with("\\''") {
-                    result.push('\'');
-                    chars.next(); // skip backslash
-                    chars.next(); // skip first quote
-                } else {
-                    break;
-                }
-            } else {
-                result.push(c);
-            }
+/// Insert (or with `None`, eject) a floppy image that stays in drive B: on
+/// every boot, for DOS-era games and utilities that ship on floppies
+pub fn save_floppy_drive(vm: &DiscoveredVm, image: Option<&Path>) -> Result<()> {
+    let script_path = &vm.launch_script;
+    let content = std::fs::read_to_string(script_path).context("Failed to read launch.sh")?;
+
+    let content = remove_floppy_drive_section(&content);
+    let new_content = match image {
+        Some(image) => {
+            let section = generate_floppy_drive_section(image);
+            insert_args_section(&content, &section, FLOPPY_DRIVE_VAR_REF)
         }
-        Some(result)
-    } else {
-        // Unquoted path: ends at comma or space
-        let end = rest.find([',', ' ', '"']).unwrap_or(rest.len());
-        Some(rest[..end].to_string())
-    }
+        None => content,
+    };
+
+    std::fs::write(script_path, new_content).context("Failed to write launch.sh")?;
+
+    Ok(())
 }
 
-/// Extract a simple key=value from a string
-fn extract_simple_value(s: &str, prefix: &str) -> Option<String> {
-    let start = s.find(prefix)? + prefix.len();
-    let rest = &s[start..];
-    let end = rest.find([',', ' ', '"', '\'']).unwrap_or(rest.len());
-    let value = rest[..end].trim();
-    if value.is_empty() {
-        None
-    } else {
-        Some(value.to_string())
-    }
+/// Load the floppy image kept in drive B: from the VM's launch.sh
+pub fn load_floppy_drive(vm: &DiscoveredVm) -> Option<std::path::PathBuf> {
+    let content = std::fs::read_to_string(&vm.launch_script).ok()?;
+    parse_floppy_drive_section(&content)
+}
+
+fn remove_floppy_drive_section(content: &str) -> String {
+    remove_args_section(
+        content,
+        FLOPPY_DRIVE_MARKER_START,
+        FLOPPY_DRIVE_MARKER_END,
+        FLOPPY_DRIVE_VAR_REF,
+    )
+}
+
+/// Same layout as the tools ISO section; the image is raw, and saying so
+/// stops QEMU from probing (and warning about) the format
+fn generate_floppy_drive_section(image: &Path) -> String {
+    let mut section = String::new();
+    section.push_str(FLOPPY_DRIVE_MARKER_START);
+    section.push('\n');
+    section.push_str(&format!(
+        "FLOPPY_DRIVE={}\n",
+        shell_escape(&image.display().to_string())
+    ));
+    section.push_str(&format!(
+        "FLOPPY_DRIVE_ARGS=(-drive \"file=${{FLOPPY_DRIVE//,/,,}},if=floppy,index={},format=raw\")\n",
+        FLOPPY_DRIVE_INDEX
+    ));
+    section.push_str(FLOPPY_DRIVE_MARKER_END);
+    section.push('\n');
+
+    section
+}
+
+fn parse_floppy_drive_section(content: &str) -> Option<std::path::PathBuf> {
+    parse_section_path(
+        content,
+        FLOPPY_DRIVE_MARKER_START,
+        FLOPPY_DRIVE_MARKER_END,
+        "FLOPPY_DRIVE=",
+    )
+}
+
+// Direct kernel boot section markers
+const DIRECT_KERNEL_MARKER_START: &str = "# >>> Direct Kernel Boot (managed by vm-curator) >>>";
+const DIRECT_KERNEL_MARKER_END: &str = "# <<< Direct Kernel Boot <<<";
+const DIRECT_KERNEL_VAR_REF: &str = "\"${DIRECT_KERNEL_ARGS[@]}\"";
+
+/// Boot the VM straight into a kernel/initrd (or with `None`, go back to the
+/// disk's bootloader) by managing a `-kernel`/`-initrd`/`-append` section in
+/// launch.sh. Applies to every boot branch.
+pub fn save_direct_kernel(vm: &DiscoveredVm, boot: Option<&DirectKernelBoot>) -> Result<()> {
+    let script_path = &vm.launch_script;
+    let content = std::fs::read_to_string(script_path).context("Failed to read launch.sh")?;
+
+    let content = remove_args_section(
+        &content,
+        DIRECT_KERNEL_MARKER_START,
+        DIRECT_KERNEL_MARKER_END,
+        DIRECT_KERNEL_VAR_REF,
+    );
+    let new_content = match boot.filter(|b| b.is_enabled()) {
+        Some(boot) => {
+            let section = generate_direct_kernel_section(boot);
+            insert_args_section(&content, &section, DIRECT_KERNEL_VAR_REF)
+        }
+        None => content,
+    };
+
+    std::fs::write(script_path, new_content).context("Failed to write launch.sh")?;
+
+    Ok(())
+}
+
+/// Load the direct kernel boot settings from the VM's launch.sh
+pub fn load_direct_kernel(vm: &DiscoveredVm) -> Option<DirectKernelBoot> {
+    let content = std::fs::read_to_string(&vm.launch_script).ok()?;
+    crate::vm::launch_parser::parse_launch_script(&vm.launch_script, &content)
+        .ok()?
+        .direct_kernel
+}
+
+/// The args go in an array of individually quoted words, so paths and the
+/// kernel command line keep their spaces.
+fn generate_direct_kernel_section(boot: &DirectKernelBoot) -> String {
+    let words: Vec<String> = boot.to_args().iter().map(|a| shell_escape(a)).collect();
+    format!(
+        "{}\nDIRECT_KERNEL_ARGS=({})\n{}\n",
+        DIRECT_KERNEL_MARKER_START,
+        words.join(" "),
+        DIRECT_KERNEL_MARKER_END
+    )
 }
 
 // PCI Passthrough section markers
@@ -1182,8 +3233,10 @@ pub fn save_pci_passthrough(
     section.push_str(")\n");
     section.push_str("declare -A PCI_ORIG_DRIVERS\n\n");
 
-    section.push_str(r#"_pci_elevated() {
+    section.push_str(
+        r#"_pci_elevated() {
     if [[ $EUID -eq 0 ]]; then sh -c "$1"
+    elif sudo -n true 2>/dev/null; then sudo sh -c "$1"
     elif command -v pkexec >/dev/null 2>&1; then pkexec sh -c "$1"
     elif command -v sudo >/dev/null 2>&1; then sudo sh -c "$1"
     else echo "Error: root required to bind PCI devices"; return 1; fi
@@ -1203,7 +3256,11 @@ bind_vfio() {
         bind_cmds+="echo 'vfio-pci' > '$dev_path/driver_override'; "
         bind_cmds+="echo '$dev' > /sys/bus/pci/drivers_probe; "
     done
-    [[ -n "$bind_cmds" ]] && { echo "Binding PCI devices to vfio-pci..."; _pci_elevated "$bind_cmds" || return 1; }
+    if [[ -n "$bind_cmds" ]]; then
+        echo "vm-curator: binding PCI devices to vfio-pci (waiting for pkexec/sudo)" >&2
+        _pci_elevated "$bind_cmds" || return 1
+        echo "vm-curator: PCI devices bound, starting QEMU" >&2
+    fi
     sleep 0.5
 }
 restore_pci() {
@@ -1224,23 +3281,257 @@ restore_pci() {
         else echo "Warning: could not restore PCI devices (reboot to restore)"; fi
     fi
 }
-if declare -f cleanup >/dev/null 2>&1; then
-    eval "$(declare -f cleanup | sed '1s/cleanup/_pci_pre_cleanup/')"
-    cleanup() { restore_pci; _pci_pre_cleanup; }
-else
-    trap 'restore_pci' EXIT
-fi
-bind_vfio || exit 1
-"#);
+if declare -f cleanup >/dev/null 2>&1; then
+    eval "$(declare -f cleanup | sed '1s/cleanup/_pci_pre_cleanup/')"
+    cleanup() { restore_pci; _pci_pre_cleanup; }
+else
+    trap 'restore_pci' EXIT
+fi
+bind_vfio || exit 1
+"#,
+    );
+
+    section.push_str(PCI_MARKER_END);
+    section.push('\n');
+
+    let new_content = insert_args_section(&cleaned, &section, "$PCI_PASSTHROUGH_ARGS");
+    std::fs::write(&vm.launch_script, new_content).context("Failed to write launch script")?;
+    Ok(())
+}
+
+/// vm-curator managed launch.sh sections, as (start marker, end marker,
+/// variable reference appended to each QEMU command).
+const MANAGED_SECTIONS: &[(&str, &str, &str)] = &[
+    (USB_MARKER_START, USB_MARKER_END, "$USB_PASSTHROUGH_ARGS"),
+    (
+        SHARED_FOLDERS_MARKER_START,
+        SHARED_FOLDERS_MARKER_END,
+        "$SHARED_FOLDERS_ARGS",
+    ),
+    (
+        BOOT_ORDER_MARKER_START,
+        BOOT_ORDER_MARKER_END,
+        "$BOOT_ORDER_ARGS",
+    ),
+    (
+        TOOLS_ISO_MARKER_START,
+        TOOLS_ISO_MARKER_END,
+        TOOLS_ISO_VAR_REF,
+    ),
+    (
+        FLOPPY_DRIVE_MARKER_START,
+        FLOPPY_DRIVE_MARKER_END,
+        FLOPPY_DRIVE_VAR_REF,
+    ),
+    (
+        DISPLAY_WINDOW_MARKER_START,
+        DISPLAY_WINDOW_MARKER_END,
+        "$DISPLAY_WINDOW_ARGS",
+    ),
+    (
+        DIRECT_KERNEL_MARKER_START,
+        DIRECT_KERNEL_MARKER_END,
+        DIRECT_KERNEL_VAR_REF,
+    ),
+    (PCI_MARKER_START, PCI_MARKER_END, "$PCI_PASSTHROUGH_ARGS"),
+    // Adds no QEMU arguments, only a background job
+    (NET_SHAPING_MARKER_START, NET_SHAPING_MARKER_END, ""),
+];
+
+/// Copy every managed section of `old` into the freshly generated `new`
+/// script, wiring each one into the QEMU commands as its save path does.
+pub(crate) fn carry_over_managed_sections(old: &str, new: &str) -> String {
+    let mut content = new.to_string();
+    for (start, end, var_ref) in MANAGED_SECTIONS {
+        let mut section = String::new();
+        let mut in_section = false;
+        for line in old.lines() {
+            if line.trim() == *start {
+                in_section = true;
+            }
+            if in_section {
+                section.push_str(line);
+                section.push('\n');
+                if line.trim() == *end {
+                    break;
+                }
+            }
+        }
+        content = insert_args_section(&content, &section, var_ref);
+    }
+    content
+}
+
+// ── VFIO bind authentication ────────────────────────────────────────────────
+//
+// The PCI section's `bind_vfio` needs root. Launched from the TUI, a terminal
+// sudo prompt would be drawn underneath the alternate screen and read keys the
+// TUI never sees, hanging the launch. So before launching, vm-curator works out
+// who will answer the prompt and, for sudo, asks for the password itself
+// outside the TUI; the script then reuses the cached credentials.
+
+/// How a VM's VFIO bind step will get root at launch.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum VfioAuth {
+    /// Nothing to rebind, already root, or sudo credentials are cached
+    NotNeeded,
+    /// pkexec will ask through the desktop's polkit agent
+    PolkitAgent,
+    /// sudo must prompt on the terminal, outside the TUI
+    SudoPrompt,
+    /// Neither a polkit agent nor sudo is available to ask
+    Unavailable,
+}
+
+/// PCI addresses listed in the launch script's managed PCI section.
+pub(crate) fn pci_devices_in_script(content: &str) -> Vec<String> {
+    let mut in_section = false;
+    for line in content.lines() {
+        let t = line.trim();
+        if t == PCI_MARKER_START {
+            in_section = true;
+        } else if t == PCI_MARKER_END {
+            in_section = false;
+        } else if in_section {
+            if let Some(list) = t
+                .strip_prefix("PCI_DEVICES=(")
+                .and_then(|l| l.strip_suffix(')'))
+            {
+                return list
+                    .split_whitespace()
+                    .map(|a| a.trim_matches('"').to_string())
+                    .filter(|a| !a.is_empty())
+                    .collect();
+            }
+        }
+    }
+    Vec::new()
+}
+
+/// Work out how the VFIO bind step of `vm`'s launch script will authenticate.
+pub fn vfio_auth_for_launch(vm: &DiscoveredVm) -> VfioAuth {
+    use std::os::unix::fs::MetadataExt;
+
+    let Ok(content) = std::fs::read_to_string(&vm.launch_script) else {
+        return VfioAuth::NotNeeded;
+    };
+    let needs_rebind = pci_devices_in_script(&content).iter().any(|addr| {
+        let dev = Path::new("/sys/bus/pci/devices").join(addr);
+        let driver = std::fs::read_link(dev.join("driver"))
+            .ok()
+            .and_then(|l| l.file_name().map(|n| n.to_string_lossy().into_owned()));
+        dev.exists() && driver.as_deref() != Some("vfio-pci")
+    });
+    let is_root = std::fs::metadata("/proc/self").is_ok_and(|m| m.uid() == 0);
+    if !needs_rebind || is_root || command_succeeds("sudo", &["-n", "true"]) {
+        return VfioAuth::NotNeeded;
+    }
+
+    let graphical =
+        std::env::var_os("WAYLAND_DISPLAY").is_some() || std::env::var_os("DISPLAY").is_some();
+    if graphical && command_succeeds("which", &["pkexec"]) && polkit_agent_running() {
+        VfioAuth::PolkitAgent
+    } else if command_succeeds("which", &["sudo"]) {
+        VfioAuth::SudoPrompt
+    } else {
+        VfioAuth::Unavailable
+    }
+}
+
+fn command_succeeds(program: &str, args: &[&str]) -> bool {
+    Command::new(program)
+        .args(args)
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .status()
+        .is_ok_and(|s| s.success())
+}
+
+/// Whether a process name (`/proc/<pid>/comm`, truncated to 15 chars by the
+/// kernel) belongs to a graphical polkit authentication agent.
+pub(crate) fn is_polkit_agent(comm: &str) -> bool {
+    let comm = comm.trim();
+    // GNOME Shell and Cinnamon carry their own agent; the standalone ones are
+    // polkit-gnome/-kde/-mate-authentication-agent-1, lxpolkit, xfce-polkit
+    // and hyprpolkitagent (the daemon itself is `polkitd`)
+    matches!(
+        comm,
+        "gnome-shell" | "cinnamon" | "lxpolkit" | "xfce-polkit"
+    ) || comm.starts_with("polkit-")
+        || comm.contains("polkitagent")
+}
+
+/// Whether any running process is a graphical polkit agent.
+fn polkit_agent_running() -> bool {
+    let Ok(entries) = std::fs::read_dir("/proc") else {
+        return false;
+    };
+    entries
+        .flatten()
+        .any(|e| std::fs::read_to_string(e.path().join("comm")).is_ok_and(|c| is_polkit_agent(&c)))
+}
 
-    section.push_str(PCI_MARKER_END);
-    section.push('\n');
+/// Ask for the sudo password on the current terminal and cache it, killing
+/// the prompt if nobody answers within `timeout`. The caller must have left
+/// the TUI's alternate screen first.
+pub fn authenticate_sudo(timeout: Duration) -> Result<()> {
+    let mut child = Command::new("sudo")
+        .args([
+            "-v",
+            "-p",
+            "[vm-curator] password for %u to bind PCI devices to vfio-pci: ",
+        ])
+        .spawn()
+        .context("Failed to run sudo")?;
 
-    let new_content = insert_args_section(&cleaned, &section, "$PCI_PASSTHROUGH_ARGS");
-    std::fs::write(&vm.launch_script, new_content).context("Failed to write launch script")?;
+    let deadline = Instant::now() + timeout;
+    loop {
+        if let Some(status) = child.try_wait()? {
+            if status.success() {
+                return Ok(());
+            }
+            bail!("Authentication failed or was cancelled");
+        }
+        if Instant::now() >= deadline {
+            let _ = child.kill();
+            let _ = child.wait();
+            bail!(
+                "Timed out after {}s waiting for the sudo password",
+                timeout.as_secs()
+            );
+        }
+        thread::sleep(Duration::from_millis(100));
+    }
+}
+
+/// Let the VFIO bind step of scripts written before cached sudo credentials
+/// were tried reuse them instead of going straight to pkexec.
+/// Idempotent — safe to call before every launch.
+pub fn ensure_cached_sudo_in_script(vm_path: &Path) -> Result<()> {
+    let script_path = vm_path.join("launch.sh");
+    let content = std::fs::read_to_string(&script_path)
+        .context("Failed to read launch.sh for VFIO auth patch")?;
+    let patched = add_cached_sudo_branch(&content);
+    if patched != content {
+        std::fs::write(&script_path, patched).context("Failed to write patched launch.sh")?;
+    }
     Ok(())
 }
 
+/// Insert the `sudo -n` branch into an old-style `_pci_elevated` helper.
+pub(crate) fn add_cached_sudo_branch(content: &str) -> String {
+    content
+        .replace(
+            "    if [[ $EUID -eq 0 ]]; then\n        sh -c \"$1\"\n    elif command -v pkexec",
+            "    if [[ $EUID -eq 0 ]]; then\n        sh -c \"$1\"\n    elif sudo -n true 2>/dev/null; then\n        sudo sh -c \"$1\"\n    elif command -v pkexec",
+        )
+        .replace(
+            "    if [[ $EUID -eq 0 ]]; then sh -c \"$1\"\n    elif command -v pkexec",
+            "    if [[ $EUID -eq 0 ]]; then sh -c \"$1\"\n    elif sudo -n true 2>/dev/null; then sudo sh -c \"$1\"\n    elif command -v pkexec",
+        )
+}
+
 // ── QMP (QEMU Machine Protocol) ─────────────────────────────────────────────
 //
 // The items below are `#[allow(dead_code)]` infrastructure for planned
@@ -1270,9 +3561,16 @@ pub fn ensure_qmp_in_script(vm_path: &Path) -> Result<()> {
         return Ok(());
     }
 
+    std::fs::write(&script_path, append_qemu_arg(&content, QMP_ARG))
+        .context("Failed to write patched launch.sh")?;
+    Ok(())
+}
+
+/// Append an argument line to every QEMU invocation in a launch script.
+fn append_qemu_arg(content: &str, arg_line: &str) -> String {
     // Generated scripts always end each QEMU invocation block with a line containing
     // no trailing `\` followed immediately by `        ;;`. Walk line-by-line and insert
-    // the QMP arg (with a continuation `\`) before each closing `;;` that follows a
+    // the arg (with a continuation `\`) before each closing `;;` that follows a
     // non-continuation QEMU arg line.
     let lines: Vec<&str> = content.lines().collect();
     let mut result = String::with_capacity(content.len() + 256);
@@ -1293,7 +3591,7 @@ pub fn ensure_qmp_in_script(vm_path: &Path) -> Result<()> {
         {
             result.push_str(line);
             result.push_str(" \\\n");
-            result.push_str(QMP_ARG);
+            result.push_str(arg_line);
             result.push('\n');
             in_qemu_block = false;
             continue;
@@ -1302,21 +3600,29 @@ pub fn ensure_qmp_in_script(vm_path: &Path) -> Result<()> {
         result.push_str(line);
         result.push('\n');
     }
-
-    std::fs::write(&script_path, result).context("Failed to write patched launch.sh")?;
-    Ok(())
+    result
 }
 
 /// Send a raw QMP command to a running VM's monitor socket.
 #[allow(dead_code)]
 fn qmp_send(vm_path: &Path, command: &str) -> Result<String> {
+    qmp_execute(
+        vm_path,
+        &format!("{{\"execute\":\"{}\"}}", command),
+        Duration::from_secs(2),
+    )
+}
+
+/// Send a QMP request (a full JSON object) and return the reply line.
+/// Asynchronous events (STOP, RESUME, ...) arriving before the reply are skipped.
+fn qmp_execute(vm_path: &Path, request: &str, timeout: Duration) -> Result<String> {
     use std::io::{BufRead, BufReader, Write};
     use std::os::unix::net::UnixStream;
 
     let sock = vm_path.join("qemu.sock");
     let stream = UnixStream::connect(&sock)
         .with_context(|| format!("QMP socket not available: {}", sock.display()))?;
-    stream.set_read_timeout(Some(Duration::from_secs(2))).ok();
+    stream.set_read_timeout(Some(timeout)).ok();
     let mut reader = BufReader::new(stream.try_clone()?);
     let mut writer = stream;
 
@@ -1330,10 +3636,107 @@ fn qmp_send(vm_path: &Path, command: &str) -> Result<String> {
     reader.read_line(&mut line)?;
 
     // Send the actual command
-    writer.write_all(format!("{{\"execute\":\"{}\"}}\n", command).as_bytes())?;
-    line.clear();
-    reader.read_line(&mut line)?;
-    Ok(line)
+    writer.write_all(format!("{}\n", request).as_bytes())?;
+    loop {
+        line.clear();
+        if reader.read_line(&mut line)? == 0 {
+            bail!("QMP connection closed before a reply");
+        }
+        if line.contains("\"return\"") || line.contains("\"error\"") {
+            return Ok(line);
+        }
+    }
+}
+
+/// Run a human monitor (HMP) command such as `savevm name` over QMP and
+/// return its text output. HMP reports failures as text, so output
+/// mentioning an error is returned as `Err`.
+pub fn qmp_human_command(vm_path: &Path, command_line: &str, timeout: Duration) -> Result<String> {
+    let request = serde_json::json!({
+        "execute": "human-monitor-command",
+        "arguments": { "command-line": command_line },
+    });
+    let reply = qmp_execute(vm_path, &request.to_string(), timeout)?;
+    let reply: serde_json::Value =
+        serde_json::from_str(reply.trim()).context("Invalid QMP reply")?;
+    if let Some(desc) = reply.pointer("/error/desc").and_then(|d| d.as_str()) {
+        bail!("{}", desc);
+    }
+    let output = reply
+        .get("return")
+        .and_then(|r| r.as_str())
+        .unwrap_or_default()
+        .trim()
+        .to_string();
+    if output.to_lowercase().contains("error") {
+        bail!("{}", output);
+    }
+    Ok(output)
+}
+
+/// Returns true if the QEMU guest agent inside the VM answers `guest-ping` on
+/// the VM's `qga.sock`. Returns false if no agent channel is configured, the
+/// agent isn't running in the guest yet, or it doesn't answer within a second.
+pub fn guest_agent_ping(vm_path: &Path) -> bool {
+    guest_agent_execute(vm_path, "guest-ping").is_some()
+}
+
+/// The guest's IP addresses as reported by its guest agent, e.g.
+/// `eth0 192.168.1.50/24`. `None` if the agent doesn't answer.
+pub fn guest_agent_addresses(vm_path: &Path) -> Option<Vec<String>> {
+    guest_agent_execute(vm_path, "guest-network-get-interfaces")
+        .map(|interfaces| guest_addresses(&interfaces))
+}
+
+/// Addresses from a `guest-network-get-interfaces` reply, without loopback
+/// and IPv6 link-local addresses
+pub(crate) fn guest_addresses(interfaces: &serde_json::Value) -> Vec<String> {
+    let mut addresses = Vec::new();
+    for iface in interfaces.as_array().into_iter().flatten() {
+        let name = iface["name"].as_str().unwrap_or("?");
+        if name == "lo" || name.starts_with("Loopback") {
+            continue;
+        }
+        for addr in iface["ip-addresses"].as_array().into_iter().flatten() {
+            let Some(ip) = addr["ip-address"].as_str() else {
+                continue;
+            };
+            if ip.starts_with("fe80:") || ip.starts_with("127.") || ip == "::1" {
+                continue;
+            }
+            match addr["prefix"].as_u64() {
+                Some(prefix) => addresses.push(format!("{} {}/{}", name, ip, prefix)),
+                None => addresses.push(format!("{} {}", name, ip)),
+            }
+        }
+    }
+    addresses
+}
+
+/// Run a guest agent command over `qga.sock`, returning its `return` value
+fn guest_agent_execute(vm_path: &Path, command: &str) -> Option<serde_json::Value> {
+    use std::io::{BufRead, BufReader, Write};
+    use std::os::unix::net::UnixStream;
+
+    let sock = vm_path.join("qga.sock");
+    let stream = UnixStream::connect(&sock).ok()?;
+    stream.set_read_timeout(Some(Duration::from_secs(1))).ok();
+    stream.set_write_timeout(Some(Duration::from_secs(1))).ok();
+    let mut reader = BufReader::new(stream.try_clone().ok()?);
+    let mut writer = stream;
+
+    // Unlike QMP there is no greeting or capability negotiation
+    writer
+        .write_all(format!("{{\"execute\":\"{}\"}}\n", command).as_bytes())
+        .ok()?;
+    let mut line = String::new();
+    match reader.read_line(&mut line) {
+        Ok(n) if n > 0 => {
+            let mut reply: serde_json::Value = serde_json::from_str(&line).ok()?;
+            Some(reply.get_mut("return")?.take())
+        }
+        _ => None,
+    }
 }
 
 /// Pause a running VM (suspends guest execution, state preserved in memory).
@@ -1357,6 +3760,678 @@ pub fn is_vm_paused(vm_path: &Path) -> bool {
         .unwrap_or(false)
 }
 
+// ── Serial console ──────────────────────────────────────────────────────────
+//
+// Headless guests are reached through their first serial port, exposed on a
+// Unix socket in the VM directory. Attaching relays raw bytes between the
+// terminal and that socket.
+
+/// Serial console socket added by [`add_serial_socket_to_script`]
+pub const SERIAL_SOCKET: &str = "serial.sock";
+
+const SERIAL_SOCKET_ARG: &str = "-serial unix:\"$VM_DIR/serial.sock\",server=on,wait=off";
+
+/// Byte that detaches from the serial console (Ctrl+], as in telnet and virsh)
+pub const CONSOLE_DETACH_KEY: u8 = 0x1d;
+
+/// Expose the VM's first serial port on [`SERIAL_SOCKET`]. A `-serial` aimed
+/// at the terminal (stdio, pty, ...) is switched over, since launch.sh runs
+/// detached from one; otherwise the arg is added to every QEMU command.
+pub fn add_serial_socket_to_script(vm_path: &Path) -> Result<()> {
+    let script_path = vm_path.join("launch.sh");
+    let content = std::fs::read_to_string(&script_path).context("Failed to read launch.sh")?;
+    std::fs::write(&script_path, with_serial_socket(&content)?)
+        .context("Failed to write launch.sh")?;
+    Ok(())
+}
+
+/// Save the RTC base and clock to the VM's launch.sh
+pub fn save_rtc(vm: &DiscoveredVm, rtc: &RtcConfig) -> Result<()> {
+    let script_path = &vm.launch_script;
+    let content = std::fs::read_to_string(script_path).context("Failed to read launch.sh")?;
+    std::fs::write(script_path, apply_rtc(&content, rtc)?).context("Failed to write launch.sh")?;
+    Ok(())
+}
+
+/// Rewrite every `-rtc` option in a launch script for `rtc`, appending one
+/// when there is none; the default RTC removes them.
+pub(crate) fn apply_rtc(content: &str, rtc: &RtcConfig) -> Result<String> {
+    let rtc_opt = regex::Regex::new(r"\s*-rtc\s+\S+").expect("valid regex");
+    let arg = rtc.to_arg();
+    let has_rtc = content
+        .lines()
+        .any(|l| !l.trim_start().starts_with('#') && rtc_opt.is_match(l));
+
+    if !has_rtc {
+        let Some(arg) = arg else {
+            return Ok(content.to_string());
+        };
+        let patched = append_qemu_arg(content, &format!("        {}", arg));
+        if !patched.contains(&arg) {
+            bail!(
+                "Could not find the end of the QEMU command in launch.sh; add `{}` to it by hand",
+                arg
+            );
+        }
+        return Ok(patched);
+    }
+
+    let mut out: Vec<String> = Vec::new();
+    for line in content.lines() {
+        if line.trim_start().starts_with('#') || !rtc_opt.is_match(line) {
+            out.push(line.to_string());
+            continue;
+        }
+        match &arg {
+            Some(arg) => out.push(
+                rtc_opt
+                    .replace_all(line, |caps: &regex::Captures| {
+                        let indent = caps[0].len() - caps[0].trim_start().len();
+                        format!("{}{}", &caps[0][..indent], arg)
+                    })
+                    .into_owned(),
+            ),
+            None => {
+                let rest = rtc_opt.replace_all(line, "");
+                if !matches!(rest.trim(), "" | "\\") {
+                    out.push(rest.into_owned());
+                } else if !line.trim_end().ends_with('\\') {
+                    // The option ended the command: end it on the line before
+                    if let Some(prev) = out.last_mut() {
+                        if let Some(stripped) = prev.trim_end().strip_suffix('\\') {
+                            *prev = stripped.trim_end().to_string();
+                        }
+                    }
+                }
+            }
+        }
+    }
+    let mut s = out.join("\n");
+    if content.ends_with('\n') {
+        s.push('\n');
+    }
+    Ok(s)
+}
+
+/// Save IO limits for one of the VM's disks to its launch.sh
+pub fn save_disk_throttle(vm: &DiscoveredVm, disk: &Path, throttle: &DiskThrottle) -> Result<()> {
+    let script_path = &vm.launch_script;
+    let content = std::fs::read_to_string(script_path).context("Failed to read launch.sh")?;
+    let patched = apply_disk_throttle(&content, &vm.path, disk, throttle)?;
+    std::fs::write(script_path, patched).context("Failed to write launch.sh")?;
+    Ok(())
+}
+
+/// Rewrite the limits on every `-drive` attaching `disk` (one per boot
+/// branch of a generated script). Other `-drive` options are kept.
+pub(crate) fn apply_disk_throttle(
+    content: &str,
+    vm_dir: &Path,
+    disk: &Path,
+    throttle: &DiskThrottle,
+) -> Result<String> {
+    let lines = super::launch_parser::drive_lines_of(content, vm_dir, disk);
+    if lines.is_empty() {
+        bail!(
+            "No -drive option attaches {} in launch.sh; limits need one (not -hda)",
+            disk.display()
+        );
+    }
+    let mut out: Vec<String> = content.lines().map(str::to_string).collect();
+    for i in lines {
+        out[i] = set_drive_throttle(&out[i], throttle);
+    }
+    let mut s = out.join("\n");
+    if content.ends_with('\n') {
+        s.push('\n');
+    }
+    Ok(s)
+}
+
+/// Replace the limits in the options of a line's `-drive`
+fn set_drive_throttle(line: &str, throttle: &DiskThrottle) -> String {
+    let Some(idx) = line.find("-drive ") else {
+        return line.to_string();
+    };
+    let start = idx + "-drive ".len();
+    let start = start + (line[start..].len() - line[start..].trim_start().len());
+    // The options end at the first space outside double quotes
+    let mut quoted = false;
+    let end = line[start..]
+        .char_indices()
+        .find(|&(_, c)| {
+            if c == '"' {
+                quoted = !quoted;
+            }
+            c.is_whitespace() && !quoted
+        })
+        .map_or(line.len(), |(i, _)| start + i);
+
+    let opts: Vec<&str> = line[start..end]
+        .split(',')
+        .filter(|opt| !DiskThrottle::is_throttle_opt(opt))
+        .collect();
+    format!(
+        "{}{}{}{}",
+        &line[..start],
+        opts.join(","),
+        throttle.drive_opts(),
+        &line[end..]
+    )
+}
+
+// Network shaping section markers
+const NET_SHAPING_MARKER_START: &str = "# >>> Network Shaping (managed by vm-curator) >>>";
+const NET_SHAPING_MARKER_END: &str = "# <<< Network Shaping <<<";
+
+/// Save poor network emulation for the VM's NIC to its launch.sh
+pub fn save_network_shaping(vm: &DiscoveredVm, shaping: &NetworkShaping) -> Result<()> {
+    let script_path = &vm.launch_script;
+    let content = std::fs::read_to_string(script_path).context("Failed to read launch.sh")?;
+    let new_content = apply_network_shaping(&content, shaping);
+    std::fs::write(script_path, new_content).context("Failed to write launch.sh")?;
+    Ok(())
+}
+
+/// Rewrite a launch script's network shaping section. The section starts a
+/// background job that waits for QEMU to open its tap device, adds a netem
+/// qdisc to it (as root, like the VFIO bind), and deletes the qdisc again
+/// once QEMU exits if the device outlives it. Its output goes to launch.log.
+pub(crate) fn apply_network_shaping(content: &str, shaping: &NetworkShaping) -> String {
+    let content = remove_args_section(
+        content,
+        NET_SHAPING_MARKER_START,
+        NET_SHAPING_MARKER_END,
+        "",
+    );
+    if shaping.is_off() {
+        return content;
+    }
+    let section = format!(
+        "{}\nNET_SHAPING=\"{}\"\n{}{}\n",
+        NET_SHAPING_MARKER_START,
+        shaping.netem_args(),
+        NET_SHAPING_JOB,
+        NET_SHAPING_MARKER_END
+    );
+    insert_args_section(&content, &section, "")
+}
+
+/// Body of the network shaping section, after `NET_SHAPING=`
+const NET_SHAPING_JOB: &str = r#"_net_elevated() {
+    if [[ $EUID -eq 0 ]]; then sh -c "$1"
+    elif sudo -n true 2>/dev/null; then sudo sh -c "$1"
+    elif command -v pkexec >/dev/null 2>&1; then pkexec sh -c "$1"
+    else echo "Error: root required to run tc"; return 1; fi
+}
+shape_network() {
+    local pid="" tap="" fdinfo
+    # QEMU reports its tap device in the fdinfo of its /dev/net/tun fd
+    for _ in $(seq 60); do
+        for pid in $$ $(pgrep -P $$); do
+            for fdinfo in /proc/"$pid"/fdinfo/*; do
+                tap=$(awk '/^iff:/ { print $2 }' "$fdinfo" 2>/dev/null)
+                [[ -n "$tap" ]] && break 3
+            done
+        done
+        sleep 0.5
+    done
+    if [[ -z "$tap" ]]; then
+        echo "Network shaping: no tap device found (bridge backend only)"
+        return 1
+    fi
+    echo "Network shaping $tap: $NET_SHAPING"
+    _net_elevated "tc qdisc replace dev '$tap' root netem $NET_SHAPING" || return 1
+    while kill -0 "$pid" 2>/dev/null; do sleep 2; done
+    if [[ -e "/sys/class/net/$tap" ]]; then
+        _net_elevated "tc qdisc del dev '$tap' root"
+    fi
+}
+shape_network </dev/null >> "$VM_DIR/launch.log" 2>&1 &
+"#;
+
+fn with_serial_socket(content: &str) -> Result<String> {
+    let terminal_serial =
+        regex::Regex::new(r"-serial\s+(?:mon:)?(?:stdio|pty|vc|null|none)\b").expect("valid regex");
+    if terminal_serial.is_match(content) {
+        return Ok(terminal_serial
+            .replace_all(content, regex::NoExpand(SERIAL_SOCKET_ARG))
+            .into_owned());
+    }
+
+    let patched = append_qemu_arg(content, &format!("        {}", SERIAL_SOCKET_ARG));
+    if !patched.contains(SERIAL_SOCKET_ARG) {
+        bail!(
+            "Could not find the end of the QEMU command in launch.sh; add `{}` to it by hand",
+            SERIAL_SOCKET_ARG
+        );
+    }
+    Ok(patched)
+}
+
+/// Connect to a running VM's serial console socket.
+pub fn connect_serial_console(socket: &Path) -> Result<std::os::unix::net::UnixStream> {
+    std::os::unix::net::UnixStream::connect(socket).with_context(|| {
+        format!(
+            "Serial console socket not available: {} (is the VM running?)",
+            socket.display()
+        )
+    })
+}
+
+/// Relay the terminal to a connected serial console until the user presses
+/// [`CONSOLE_DETACH_KEY`]. The terminal should be in raw mode so keys reach
+/// the guest unprocessed.
+pub fn run_serial_console(stream: std::os::unix::net::UnixStream) -> Result<()> {
+    relay_console(stream, std::io::stdin(), std::io::stdout())
+}
+
+/// Copy guest output to `output` on a helper thread while forwarding `input`
+/// to the guest, up to the detach key or the end of input. If the guest side
+/// closes first, the next key returns.
+fn relay_console(
+    stream: std::os::unix::net::UnixStream,
+    mut input: impl std::io::Read,
+    output: impl std::io::Write + Send + 'static,
+) -> Result<()> {
+    use std::io::{Read, Write};
+    use std::sync::atomic::{AtomicBool, Ordering};
+
+    let detached = Arc::new(AtomicBool::new(false));
+    let mut reader = stream
+        .try_clone()
+        .context("Failed to clone console socket")?;
+    let printer = {
+        let detached = Arc::clone(&detached);
+        let mut output = output;
+        thread::spawn(move || {
+            let mut buf = [0u8; 4096];
+            while let Ok(n) = reader.read(&mut buf) {
+                if n == 0
+                    || output
+                        .write_all(&buf[..n])
+                        .and_then(|_| output.flush())
+                        .is_err()
+                {
+                    break;
+                }
+            }
+            if !detached.load(Ordering::SeqCst) {
+                let _ = output.write_all(b"\r\n[console closed - press any key]\r\n");
+                let _ = output.flush();
+            }
+        })
+    };
+
+    let mut writer = stream;
+    let mut buf = [0u8; 1024];
+    loop {
+        let n = input
+            .read(&mut buf)
+            .context("Failed to read from the terminal")?;
+        if n == 0 || printer.is_finished() {
+            break;
+        }
+        let chunk = &buf[..n];
+        let detach_at = chunk.iter().position(|&b| b == CONSOLE_DETACH_KEY);
+        if writer.write_all(&chunk[..detach_at.unwrap_or(n)]).is_err() || detach_at.is_some() {
+            break;
+        }
+    }
+
+    detached.store(true, std::sync::atomic::Ordering::SeqCst);
+    let _ = writer.shutdown(std::net::Shutdown::Both);
+    let _ = printer.join();
+    Ok(())
+}
+
+// ── Suspend to disk ─────────────────────────────────────────────────────────
+//
+// A single resumable slot per VM, separate from qcow2 snapshots: the guest's
+// RAM and device state are migrated into a gzip file in the VM folder and the
+// VM quits. The next normal launch loads that file with `-incoming`.
+
+/// Saved-state file written by [`suspend_vm`]
+pub const SUSPEND_STATE_FILE: &str = "suspend.state.gz";
+
+const INCOMING_ARG: &str = "        -incoming \"exec:gzip -c -d '$VM_DIR/suspend.state.gz'\"";
+const MIGRATE_TIMEOUT: Duration = Duration::from_secs(600);
+
+/// Whether the VM has a suspended state waiting to be resumed
+pub fn has_suspend_state(vm_path: &Path) -> bool {
+    vm_path.join(SUSPEND_STATE_FILE).is_file()
+}
+
+/// Refuse boots that would write to the disks under a suspended state: the
+/// state would later restore stale RAM over a disk that has moved on. Normal
+/// boots resume it and ephemeral boots discard their writes.
+pub(crate) fn check_boot_keeps_suspend_state(vm_path: &Path, boot_mode: &BootMode) -> Result<()> {
+    if !matches!(boot_mode, BootMode::Normal | BootMode::Ephemeral) && has_suspend_state(vm_path) {
+        bail!(
+            "This VM is suspended. Resume it, or use \"Discard Suspended State\" in the \
+             management menu, before booting from other media."
+        );
+    }
+    Ok(())
+}
+
+/// Save a running VM's memory state to its folder and shut it down.
+///
+/// The guest is paused, migrated into `suspend.state.gz` and QEMU quits once
+/// the file is complete. On failure the partial file is removed and the
+/// guest continues running.
+pub fn suspend_vm(vm_path: &Path) -> Result<()> {
+    let state = vm_path.join(SUSPEND_STATE_FILE);
+    let partial = vm_path.join(format!("{}.part", SUSPEND_STATE_FILE));
+    let _ = std::fs::remove_file(&partial);
+
+    qmp_send(vm_path, "stop").context("Failed to pause the VM")?;
+    let request = serde_json::json!({
+        "execute": "migrate",
+        "arguments": { "uri": format!("exec:gzip -c > {}", shell_escape(path_to_str(&partial)?)) },
+    });
+    let result = qmp_execute(vm_path, &request.to_string(), Duration::from_secs(5))
+        .and_then(|reply| qmp_reply_error(&reply))
+        .and_then(|()| wait_for_migration(vm_path));
+
+    if let Err(e) = result {
+        let _ = std::fs::remove_file(&partial);
+        let _ = qmp_send(vm_path, "cont");
+        return Err(e);
+    }
+    std::fs::rename(&partial, &state).context("Failed to save the suspend state")?;
+    // QEMU closes the socket as it exits, which may cut off the reply
+    let _ = qmp_send(vm_path, "quit");
+    Ok(())
+}
+
+/// Delete a VM's suspended state so its next launch boots normally
+pub fn discard_suspend_state(vm_path: &Path) -> Result<()> {
+    std::fs::remove_file(vm_path.join(SUSPEND_STATE_FILE))
+        .context("Failed to delete the suspend state")
+}
+
+/// Poll `query-migrate` until the outgoing migration finishes
+fn wait_for_migration(vm_path: &Path) -> Result<()> {
+    let started = std::time::Instant::now();
+    while started.elapsed() < MIGRATE_TIMEOUT {
+        let reply = qmp_send(vm_path, "query-migrate")?;
+        match migration_status(&reply).as_deref() {
+            Some("completed") => return Ok(()),
+            Some("failed") | Some("cancelled") => {
+                let reply: serde_json::Value = serde_json::from_str(reply.trim())?;
+                let desc = reply
+                    .pointer("/return/error-desc")
+                    .and_then(|d| d.as_str())
+                    .unwrap_or("migration failed");
+                bail!("Saving VM state failed: {}", desc);
+            }
+            _ => thread::sleep(Duration::from_millis(250)),
+        }
+    }
+    bail!("Saving VM state timed out")
+}
+
+/// Wait for a VM launched with `resume_suspended` to finish loading its
+/// state, continue the guest (it was paused when suspended, and arrives
+/// paused), then remove the state file so the slot can't be restored twice
+/// over a disk that has moved on. Blocks until then (or a timeout).
+pub fn finish_resume(vm_path: &Path) -> Result<()> {
+    wait_for_incoming_migration(vm_path, Duration::from_millis(500))?;
+    qmp_send(vm_path, "cont")
+        .and_then(|reply| qmp_reply_error(&reply))
+        .context("Failed to continue the resumed VM")?;
+    discard_suspend_state(vm_path)
+}
+
+/// Poll `query-migrate` until the incoming migration has loaded. QEMUs that
+/// don't report incoming migrations are done once they leave `inmigrate`.
+fn wait_for_incoming_migration(vm_path: &Path, poll: Duration) -> Result<()> {
+    let started = std::time::Instant::now();
+    while started.elapsed() < MIGRATE_TIMEOUT {
+        if let Ok(reply) = qmp_send(vm_path, "query-migrate") {
+            match migration_status(&reply).as_deref() {
+                Some("completed") => return Ok(()),
+                Some("failed") | Some("cancelled") => {
+                    bail!("Loading the suspend state failed")
+                }
+                Some(_) => {}
+                None => {
+                    let loaded = qmp_send(vm_path, "query-status").is_ok_and(|status| {
+                        !status.contains("\"inmigrate\"") && !status.contains("\"prelaunch\"")
+                    });
+                    if loaded {
+                        return Ok(());
+                    }
+                }
+            }
+        }
+        thread::sleep(poll);
+    }
+    bail!("VM did not finish loading its suspend state")
+}
+
+/// The `status` of a `query-migrate` reply
+fn migration_status(reply: &str) -> Option<String> {
+    let reply: serde_json::Value = serde_json::from_str(reply.trim()).ok()?;
+    reply
+        .pointer("/return/status")
+        .and_then(|s| s.as_str())
+        .map(str::to_string)
+}
+
+/// Turn a QMP error reply into `Err`
+fn qmp_reply_error(reply: &str) -> Result<()> {
+    let reply: serde_json::Value =
+        serde_json::from_str(reply.trim()).context("Invalid QMP reply")?;
+    match reply.pointer("/error/desc").and_then(|d| d.as_str()) {
+        Some(desc) => bail!("{}", desc),
+        None => Ok(()),
+    }
+}
+
+// ── Memory balloon ──────────────────────────────────────────────────────────
+
+/// Current guest RAM in MB as reported by the virtio-balloon device
+/// (QMP `query-balloon`). Fails if the VM has no balloon device.
+pub fn query_balloon_mb(vm_path: &Path) -> Result<u64> {
+    let reply = qmp_send(vm_path, "query-balloon")?;
+    qmp_reply_error(&reply)?;
+    balloon_actual_mb(&reply).context("Invalid query-balloon reply")
+}
+
+/// Ask the guest's balloon driver to grow or shrink its RAM to `target_mb`.
+/// The guest gets there on its own time; poll [`query_balloon_mb`] to follow.
+pub fn set_balloon_target(vm_path: &Path, target_mb: u64) -> Result<()> {
+    let request = serde_json::json!({
+        "execute": "balloon",
+        "arguments": { "value": target_mb * 1024 * 1024 },
+    });
+    let reply = qmp_execute(vm_path, &request.to_string(), Duration::from_secs(2))?;
+    qmp_reply_error(&reply)
+}
+
+/// The `actual` size (bytes) of a `query-balloon` reply, in MB
+fn balloon_actual_mb(reply: &str) -> Option<u64> {
+    let reply: serde_json::Value = serde_json::from_str(reply.trim()).ok()?;
+    reply
+        .pointer("/return/actual")
+        .and_then(|a| a.as_u64())
+        .map(|bytes| bytes / (1024 * 1024))
+}
+
+// ── USB hot-plug ────────────────────────────────────────────────────────────
+
+/// QOM id given to a host USB device hot-added at `bus`/`addr`
+fn usb_hotplug_id(bus: u8, addr: u8) -> String {
+    format!("usbhot-{}-{}", bus, addr)
+}
+
+/// Attach the host USB device at `bus`/`addr` to a running VM (QMP
+/// `device_add usb-host`). QEMU detaches the host's kernel driver itself;
+/// the VM needs a USB controller and the user write access to the device node.
+pub fn usb_hot_add(vm_path: &Path, bus: u8, addr: u8) -> Result<()> {
+    let request = serde_json::json!({
+        "execute": "device_add",
+        "arguments": {
+            "driver": "usb-host",
+            "id": usb_hotplug_id(bus, addr),
+            "hostbus": bus,
+            "hostaddr": addr,
+        },
+    });
+    let reply = qmp_execute(vm_path, &request.to_string(), Duration::from_secs(5))?;
+    qmp_reply_error(&reply)
+}
+
+/// Detach a device added with [`usb_hot_add`]; it returns to the host
+pub fn usb_hot_remove(vm_path: &Path, bus: u8, addr: u8) -> Result<()> {
+    let request = serde_json::json!({
+        "execute": "device_del",
+        "arguments": { "id": usb_hotplug_id(bus, addr) },
+    });
+    let reply = qmp_execute(vm_path, &request.to_string(), Duration::from_secs(5))?;
+    qmp_reply_error(&reply)
+}
+
+/// Bus/address pairs of the USB devices hot-added to a running VM
+pub fn list_hotplugged_usb(vm_path: &Path) -> Result<Vec<(u8, u8)>> {
+    let request = serde_json::json!({
+        "execute": "qom-list",
+        "arguments": { "path": "/machine/peripheral" },
+    });
+    let reply = qmp_execute(vm_path, &request.to_string(), Duration::from_secs(2))?;
+    qmp_reply_error(&reply)?;
+    Ok(hotplugged_usb_from_qom_list(&reply))
+}
+
+/// Pick our `usbhot-<bus>-<addr>` ids out of a `qom-list` reply
+fn hotplugged_usb_from_qom_list(reply: &str) -> Vec<(u8, u8)> {
+    let Ok(reply) = serde_json::from_str::<serde_json::Value>(reply.trim()) else {
+        return Vec::new();
+    };
+    reply["return"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter_map(|prop| {
+            let (bus, addr) = prop["name"]
+                .as_str()?
+                .strip_prefix("usbhot-")?
+                .split_once('-')?;
+            Some((bus.parse().ok()?, addr.parse().ok()?))
+        })
+        .collect()
+}
+
+// ── Removable media swapping ────────────────────────────────────────────────
+
+/// Kind of removable-media drive
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum MediaKind {
+    #[default]
+    Floppy,
+    Cdrom,
+}
+
+impl MediaKind {
+    /// Whether a `query-block` device name belongs to this kind. CD-ROMs are
+    /// `ide1-cd0` for `-cdrom`/`media=cdrom` and `cd0` for the wizard's
+    /// `if=none` drive.
+    fn matches_device(self, device: &str) -> bool {
+        match self {
+            MediaKind::Floppy => device.starts_with("floppy"),
+            MediaKind::Cdrom => device.contains("cd"),
+        }
+    }
+}
+
+/// A floppy or CD-ROM drive of a running VM
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RemovableDrive {
+    /// QEMU block device (`floppy0` is A:, `floppy1` B:; `ide1-cd0`, `cd0`)
+    pub device: String,
+    pub kind: MediaKind,
+    /// Image in the drive; `None` when empty
+    pub image: Option<PathBuf>,
+}
+
+impl RemovableDrive {
+    /// Name to show: the DOS drive letter of a floppy, or the CD-ROM's
+    /// QEMU device
+    pub fn label(&self) -> String {
+        match self.kind {
+            MediaKind::Floppy if self.device == "floppy1" => "Drive B:".to_string(),
+            MediaKind::Floppy => "Drive A:".to_string(),
+            MediaKind::Cdrom => format!("CD-ROM {}", self.device),
+        }
+    }
+}
+
+/// Floppy or CD-ROM drives of a running VM (QMP `query-block`). PC machines
+/// have drive A: even when no floppy was given at launch (q35 has none);
+/// CD-ROM drives exist only if the VM was started with one, and QEMU can't
+/// hot-add them.
+pub fn list_removable_drives(vm_path: &Path, kind: MediaKind) -> Result<Vec<RemovableDrive>> {
+    let request = serde_json::json!({ "execute": "query-block" });
+    let reply = qmp_execute(vm_path, &request.to_string(), Duration::from_secs(2))?;
+    qmp_reply_error(&reply)?;
+    Ok(removable_drives_from_query_block(&reply, kind))
+}
+
+/// Pick the drives of `kind` out of a `query-block` reply
+fn removable_drives_from_query_block(reply: &str, kind: MediaKind) -> Vec<RemovableDrive> {
+    let Ok(reply) = serde_json::from_str::<serde_json::Value>(reply.trim()) else {
+        return Vec::new();
+    };
+    let mut drives: Vec<RemovableDrive> = reply["return"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter(|block| block["removable"].as_bool() == Some(true))
+        .filter_map(|block| {
+            let device = block["device"].as_str()?;
+            kind.matches_device(device).then(|| RemovableDrive {
+                device: device.to_string(),
+                kind,
+                image: block
+                    .pointer("/inserted/file")
+                    .and_then(|f| f.as_str())
+                    .map(PathBuf::from),
+            })
+        })
+        .collect();
+    drives.sort_by(|a, b| a.device.cmp(&b.device));
+    drives
+}
+
+/// Put `image` in a running VM's floppy or CD-ROM drive, replacing any disk
+/// in it (QMP `blockdev-change-medium`), e.g. to move on to an installer's
+/// disk 2 or mount guest tools after boot
+pub fn change_medium(vm_path: &Path, device: &str, image: &Path) -> Result<()> {
+    let request = serde_json::json!({
+        "execute": "blockdev-change-medium",
+        "arguments": {
+            "device": device,
+            "filename": image.to_string_lossy(),
+            "format": "raw",
+        },
+    });
+    let reply = qmp_execute(vm_path, &request.to_string(), Duration::from_secs(5))?;
+    qmp_reply_error(&reply)
+}
+
+/// Take the disk out of a running VM's floppy or CD-ROM drive, even if the
+/// guest has locked the tray
+pub fn eject_medium(vm_path: &Path, device: &str) -> Result<()> {
+    let request = serde_json::json!({
+        "execute": "eject",
+        "arguments": { "device": device, "force": true },
+    });
+    let reply = qmp_execute(vm_path, &request.to_string(), Duration::from_secs(5))?;
+    qmp_reply_error(&reply)
+}
+
 #[cfg(test)]
 #[path = "tests/lifecycle.rs"]
 mod tests;
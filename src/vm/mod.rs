@@ -1,22 +1,45 @@
 pub mod create;
 pub mod discovery;
+pub mod export;
 pub mod import;
+pub mod iso_detect;
+pub mod iso_library;
+pub mod keymap;
 pub mod launch_parser;
 pub mod lifecycle;
 pub mod mac;
+pub mod proc_stats;
 pub mod qemu_config;
+pub mod report;
 pub mod single_gpu_scripts;
 pub mod snapshot;
+pub mod ssh;
+pub mod trash;
+pub mod uefi;
 
 #[allow(unused_imports)]
 pub use create::create_vm;
-pub use discovery::{discover_vms, group_vms_by_category, DiscoveredVm};
+pub use discovery::{
+    affects_discovery, discover_vms, find_by_display_name, group_vms_by_category, library_issue,
+    DiscoveredVm, LibraryIssue,
+};
+pub use iso_detect::{detect_iso_os, IsoDetection};
 pub use lifecycle::{
-    detect_qemu_processes, force_stop_vm, launch_vm_sync, launch_vm_with_error_check,
-    load_pci_passthrough, load_shared_folders, load_usb_passthrough, save_shared_folders,
-    save_usb_passthrough, stop_vm_by_pid, LaunchOptions, QemuProcess, SharedFolder, UsbPassthrough,
+    change_medium, detect_qemu_processes, discard_suspend_state, eject_medium, finish_resume,
+    force_stop_vm, guest_agent_ping, has_suspend_state, hook_template, launch_vm_sync,
+    launch_vm_with_error_check, list_hotplugged_usb, list_removable_drives, load_boot_order,
+    load_cpu_config, load_direct_kernel, load_floppy_drive, load_pci_passthrough,
+    load_shared_folders, load_tools_iso, load_usb_passthrough, load_window_title, query_balloon_mb,
+    save_boot_order, save_cpu_config, save_direct_kernel, save_disk_throttle, save_display_window,
+    save_floppy_drive, save_network_shaping, save_rtc, save_shared_folders, save_tools_iso,
+    save_usb_passthrough, save_window_title, set_balloon_target, stop_vm_by_pid, suspend_vm,
+    test_launch, usb_hot_add, usb_hot_remove, vfio_auth_for_launch, LaunchOptions, MediaKind,
+    QemuProcess, RemovableDrive, SharedFolder, StartupHealth, TestLaunch, UsbPassthrough, UsbPort,
+    VfioAuth, HOOK_ENV_VARS, LAUNCH_LOG, POST_STOP_HOOK, PRE_LAUNCH_HOOK,
+};
+pub use qemu_config::{
+    BootMode, BootOrder, CpuConfig, DirectKernelBoot, DisplayWindow, QemuConfig, RtcBase, RtcConfig,
 };
-pub use qemu_config::{BootMode, QemuConfig};
 pub use single_gpu_scripts::generate_single_gpu_scripts;
 pub use snapshot::{create_snapshot, delete_snapshot, list_snapshots, restore_snapshot, Snapshot};
 
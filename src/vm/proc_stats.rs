@@ -0,0 +1,112 @@
+//! QEMU process resource usage
+//!
+//! Samples CPU time, resident memory and start time of a process from
+//! `/proc`. CPU usage is a rate, so it takes two samples of the same
+//! process: the share of one host CPU used between them.
+
+use std::path::Path;
+use std::time::Duration;
+
+/// Kernel clock ticks per second (`USER_HZ`) used by `/proc/<pid>/stat`.
+/// Fixed at 100 on every architecture Linux exposes to user space.
+const CLOCK_TICKS_PER_SEC: u64 = 100;
+
+/// One reading of a process's resource usage
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ProcSample {
+    /// User plus system CPU time, in clock ticks
+    pub cpu_ticks: u64,
+    /// Resident memory in bytes
+    pub rss_bytes: u64,
+    /// How long the process has been running
+    pub uptime: Duration,
+}
+
+/// Sample a running process, or `None` if it has exited
+pub fn sample(pid: u32) -> Option<ProcSample> {
+    sample_in(Path::new("/proc"), pid)
+}
+
+/// [`sample`] against another proc root (for tests)
+pub fn sample_in(proc_root: &Path, pid: u32) -> Option<ProcSample> {
+    let dir = proc_root.join(pid.to_string());
+    let stat = parse_stat(&std::fs::read_to_string(dir.join("stat")).ok()?)?;
+    let rss_kb = parse_vm_rss_kb(&std::fs::read_to_string(dir.join("status")).ok()?).unwrap_or(0);
+    let system_uptime = std::fs::read_to_string(proc_root.join("uptime"))
+        .ok()
+        .and_then(|s| s.split_whitespace().next()?.parse::<f64>().ok())
+        .unwrap_or(0.0);
+    let started = stat.start_ticks as f64 / CLOCK_TICKS_PER_SEC as f64;
+
+    Some(ProcSample {
+        cpu_ticks: stat.cpu_ticks,
+        rss_bytes: rss_kb * 1024,
+        uptime: Duration::from_secs_f64((system_uptime - started).max(0.0)),
+    })
+}
+
+/// Fields used from `/proc/<pid>/stat`
+#[derive(Debug, PartialEq, Eq)]
+pub(crate) struct StatFields {
+    /// utime + stime
+    pub cpu_ticks: u64,
+    /// Start time after boot
+    pub start_ticks: u64,
+}
+
+/// Parse `/proc/<pid>/stat`. The command name (field 2) is in parentheses
+/// and may itself contain spaces or parentheses, so fields are counted from
+/// the last `)`.
+pub(crate) fn parse_stat(content: &str) -> Option<StatFields> {
+    let rest = &content[content.rfind(')')? + 1..];
+    // rest starts at field 3 (state)
+    let fields: Vec<&str> = rest.split_whitespace().collect();
+    let field = |n: usize| fields.get(n - 3)?.parse::<u64>().ok();
+    Some(StatFields {
+        cpu_ticks: field(14)? + field(15)?,
+        start_ticks: field(22)?,
+    })
+}
+
+/// `VmRSS` from `/proc/<pid>/status`, in kB
+pub(crate) fn parse_vm_rss_kb(content: &str) -> Option<u64> {
+    content
+        .lines()
+        .find_map(|line| line.strip_prefix("VmRSS:"))?
+        .split_whitespace()
+        .next()?
+        .parse()
+        .ok()
+}
+
+/// CPU use between two samples of one process, in percent of one host CPU
+/// (a busy 4-vCPU guest can show up to 400%)
+pub fn cpu_percent(previous: &ProcSample, current: &ProcSample, elapsed: Duration) -> f64 {
+    let secs = elapsed.as_secs_f64();
+    if secs <= 0.0 {
+        return 0.0;
+    }
+    let ticks = current.cpu_ticks.saturating_sub(previous.cpu_ticks);
+    ticks as f64 / CLOCK_TICKS_PER_SEC as f64 / secs * 100.0
+}
+
+/// Uptime as `HH:MM:SS`, with a day count in front past 24 hours
+pub fn format_uptime(uptime: Duration) -> String {
+    let secs = uptime.as_secs();
+    let (days, secs) = (secs / 86400, secs % 86400);
+    let clock = format!(
+        "{:02}:{:02}:{:02}",
+        secs / 3600,
+        secs % 3600 / 60,
+        secs % 60
+    );
+    if days > 0 {
+        format!("{}d {}", days, clock)
+    } else {
+        clock
+    }
+}
+
+#[cfg(test)]
+#[path = "tests/proc_stats.rs"]
+mod tests;
@@ -152,6 +152,37 @@ impl fmt::Display for PortForward {
     }
 }
 
+impl PortForward {
+    /// Service usually on the guest port, e.g. "ssh" for 22
+    pub fn service(&self) -> Option<&'static str> {
+        let service = match (self.protocol, self.guest_port) {
+            (PortProtocol::Tcp, 21) => "ftp",
+            (PortProtocol::Tcp, 22) => "ssh",
+            (PortProtocol::Tcp, 80) => "http",
+            (PortProtocol::Tcp, 443) => "https",
+            (PortProtocol::Tcp, 445) => "smb",
+            (PortProtocol::Tcp, 3389) => "rdp",
+            (PortProtocol::Tcp, 5900) => "vnc",
+            (PortProtocol::Tcp, 8080) => "http-alt",
+            (PortProtocol::Udp, 53) => "dns",
+            _ => return None,
+        };
+        Some(service)
+    }
+
+    /// e.g. "ssh: host 2222 → guest 22", with the protocol for UDP rules
+    pub fn describe(&self) -> String {
+        let mut text = format!("host {} → guest {}", self.host_port, self.guest_port);
+        if self.protocol == PortProtocol::Udp {
+            text.push_str(" (UDP)");
+        }
+        match self.service() {
+            Some(service) => format!("{}: {}", service, text),
+            None => text,
+        }
+    }
+}
+
 /// Port forwarding protocol
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum PortProtocol {
@@ -182,12 +213,28 @@ pub struct NetworkConfig {
     /// `None` lets QEMU pick its own.
     #[serde(default)]
     pub mac_address: Option<String>,
+    /// Poor network emulation on the NIC's tap device (bridge backend only)
+    #[serde(default)]
+    pub shaping: NetworkShaping,
 }
 
 fn default_true() -> bool {
     true
 }
 
+impl NetworkConfig {
+    /// How the guest gets its address with this backend, for the info screen
+    pub fn addressing(&self) -> Option<&'static str> {
+        match self.backend {
+            // SLIRP's fixed 10.0.2.0/24 network
+            NetworkBackend::User => Some("NAT: guest 10.0.2.15, gateway 10.0.2.2, DNS 10.0.2.3"),
+            NetworkBackend::Passt => Some("passt: guest gets the host's address and gateway"),
+            NetworkBackend::Bridge(_) => Some("Bridged: address assigned by the LAN's DHCP server"),
+            NetworkBackend::None => None,
+        }
+    }
+}
+
 impl Default for NetworkConfig {
     fn default() -> Self {
         Self {
@@ -197,10 +244,152 @@ impl Default for NetworkConfig {
             user_net: true,
             bridge: None,
             mac_address: None,
+            shaping: NetworkShaping::default(),
         }
     }
 }
 
+/// One setting of [`NetworkShaping`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ShapingField {
+    Rate,
+    Delay,
+    Loss,
+}
+
+impl ShapingField {
+    pub const ALL: [ShapingField; 3] =
+        [ShapingField::Rate, ShapingField::Delay, ShapingField::Loss];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            ShapingField::Rate => "Rate",
+            ShapingField::Delay => "Delay",
+            ShapingField::Loss => "Loss",
+        }
+    }
+
+    /// Unit the setting is shown and typed in
+    pub fn unit(self) -> &'static str {
+        match self {
+            ShapingField::Rate => "kbit/s",
+            ShapingField::Delay => "ms",
+            ShapingField::Loss => "%",
+        }
+    }
+}
+
+/// Poor network emulation: a `tc` netem qdisc on the tap device QEMU opens
+/// for a bridge backend. SLIRP and passt traffic never crosses a host
+/// interface of its own, so those backends can't be shaped.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct NetworkShaping {
+    /// Bandwidth cap in kbit/s
+    pub rate_kbit: Option<u32>,
+    /// Added latency in milliseconds
+    pub delay_ms: Option<u32>,
+    /// Share of packets dropped, in percent
+    pub loss_percent: Option<f32>,
+}
+
+impl NetworkShaping {
+    pub fn is_off(&self) -> bool {
+        *self == NetworkShaping::default()
+    }
+
+    /// Arguments after `tc qdisc ... netem`, e.g. `delay 100ms loss 1% rate 512kbit`
+    pub fn netem_args(&self) -> String {
+        let mut args = Vec::new();
+        if let Some(ms) = self.delay_ms {
+            args.push(format!("delay {}ms", ms));
+        }
+        if let Some(loss) = self.loss_percent {
+            args.push(format!("loss {}%", loss));
+        }
+        if let Some(kbit) = self.rate_kbit {
+            args.push(format!("rate {}kbit", kbit));
+        }
+        args.join(" ")
+    }
+
+    /// Read back what [`Self::netem_args`] wrote
+    pub fn parse_netem(args: &str) -> Self {
+        let mut shaping = NetworkShaping::default();
+        let words: Vec<&str> = args.split_whitespace().collect();
+        for pair in words.windows(2) {
+            match pair[0] {
+                "delay" => shaping.delay_ms = pair[1].trim_end_matches("ms").parse().ok(),
+                "loss" => shaping.loss_percent = pair[1].trim_end_matches('%').parse().ok(),
+                "rate" => shaping.rate_kbit = pair[1].trim_end_matches("kbit").parse().ok(),
+                _ => {}
+            }
+        }
+        shaping
+    }
+
+    /// A setting for display, e.g. `512 kbit/s` or `off`
+    pub fn describe(&self, field: ShapingField) -> String {
+        let value = self.input_value(field);
+        if value.is_empty() {
+            "off".to_string()
+        } else {
+            format!("{} {}", value, field.unit())
+        }
+    }
+
+    /// A setting in its unit, as typed in (blank for off)
+    pub fn input_value(&self, field: ShapingField) -> String {
+        match field {
+            ShapingField::Rate => self.rate_kbit.map(|v| v.to_string()),
+            ShapingField::Delay => self.delay_ms.map(|v| v.to_string()),
+            ShapingField::Loss => self.loss_percent.map(|v| v.to_string()),
+        }
+        .unwrap_or_default()
+    }
+
+    /// Set a setting from typed input: a whole number (loss may have
+    /// decimals, up to 100), with blank or 0 turning it off
+    pub fn set_input(&mut self, field: ShapingField, input: &str) -> Result<(), String> {
+        let input = input.trim();
+        let invalid = || {
+            format!(
+                "'{}' is not a valid {} in {}",
+                input,
+                field.label(),
+                field.unit()
+            )
+        };
+        match field {
+            ShapingField::Rate | ShapingField::Delay => {
+                let value = if input.is_empty() {
+                    None
+                } else {
+                    Some(input.parse::<u32>().map_err(|_| invalid())?)
+                }
+                .filter(|v| *v > 0);
+                if field == ShapingField::Rate {
+                    self.rate_kbit = value;
+                } else {
+                    self.delay_ms = value;
+                }
+            }
+            ShapingField::Loss => {
+                let value = if input.is_empty() {
+                    None
+                } else {
+                    let loss = input.parse::<f32>().map_err(|_| invalid())?;
+                    if !(0.0..=100.0).contains(&loss) {
+                        return Err(invalid());
+                    }
+                    Some(loss)
+                };
+                self.loss_percent = value.filter(|v| *v > 0.0);
+            }
+        }
+        Ok(())
+    }
+}
+
 /// Disk image format
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub enum DiskFormat {
@@ -233,6 +422,186 @@ pub struct DiskConfig {
     pub path: PathBuf,
     pub format: DiskFormat,
     pub interface: String,
+    /// IO limits from the disk's `-drive` options
+    #[serde(default)]
+    pub throttle: DiskThrottle,
+}
+
+/// One IO limit of a disk
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ThrottleField {
+    IopsRead,
+    IopsWrite,
+    BpsRead,
+    BpsWrite,
+}
+
+impl ThrottleField {
+    pub const ALL: [ThrottleField; 4] = [
+        ThrottleField::IopsRead,
+        ThrottleField::IopsWrite,
+        ThrottleField::BpsRead,
+        ThrottleField::BpsWrite,
+    ];
+
+    /// `-drive` option setting this limit
+    pub fn option(self) -> &'static str {
+        match self {
+            ThrottleField::IopsRead => "throttling.iops-read",
+            ThrottleField::IopsWrite => "throttling.iops-write",
+            ThrottleField::BpsRead => "throttling.bps-read",
+            ThrottleField::BpsWrite => "throttling.bps-write",
+        }
+    }
+
+    /// Older `-drive` spelling of the same limit
+    fn legacy_option(self) -> &'static str {
+        match self {
+            ThrottleField::IopsRead => "iops_rd",
+            ThrottleField::IopsWrite => "iops_wr",
+            ThrottleField::BpsRead => "bps_rd",
+            ThrottleField::BpsWrite => "bps_wr",
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            ThrottleField::IopsRead => "Read operations",
+            ThrottleField::IopsWrite => "Write operations",
+            ThrottleField::BpsRead => "Read bandwidth",
+            ThrottleField::BpsWrite => "Write bandwidth",
+        }
+    }
+
+    /// Unit the limit is shown and typed in
+    pub fn unit(self) -> &'static str {
+        match self {
+            ThrottleField::IopsRead | ThrottleField::IopsWrite => "IOPS",
+            ThrottleField::BpsRead | ThrottleField::BpsWrite => "MiB/s",
+        }
+    }
+
+    /// QEMU's value for one typed unit (bandwidth is set in bytes/s)
+    fn scale(self) -> u64 {
+        match self {
+            ThrottleField::IopsRead | ThrottleField::IopsWrite => 1,
+            ThrottleField::BpsRead | ThrottleField::BpsWrite => 1024 * 1024,
+        }
+    }
+}
+
+/// IO limits on one disk, written as `throttling.*` options on its
+/// `-drive`. `None` leaves a limit off (QEMU also treats 0 as no limit).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DiskThrottle {
+    pub iops_read: Option<u64>,
+    pub iops_write: Option<u64>,
+    /// Bytes per second
+    pub bps_read: Option<u64>,
+    /// Bytes per second
+    pub bps_write: Option<u64>,
+}
+
+impl DiskThrottle {
+    /// Limits in a `-drive` line, in the `throttling.*` or older `iops_rd`
+    /// style spelling
+    pub fn parse(drive: &str) -> Self {
+        let mut throttle = DiskThrottle::default();
+        for opt in drive.split(|c: char| c == ',' || c.is_whitespace()) {
+            let Some((key, value)) = opt.split_once('=') else {
+                continue;
+            };
+            let field = ThrottleField::ALL
+                .into_iter()
+                .find(|f| key == f.option() || key == f.legacy_option());
+            if let (Some(field), Ok(value)) = (field, value.parse()) {
+                throttle.set(field, Some(value));
+            }
+        }
+        throttle
+    }
+
+    pub fn get(&self, field: ThrottleField) -> Option<u64> {
+        match field {
+            ThrottleField::IopsRead => self.iops_read,
+            ThrottleField::IopsWrite => self.iops_write,
+            ThrottleField::BpsRead => self.bps_read,
+            ThrottleField::BpsWrite => self.bps_write,
+        }
+    }
+
+    /// Set a limit in QEMU's units; 0 clears it
+    pub fn set(&mut self, field: ThrottleField, value: Option<u64>) {
+        let value = value.filter(|v| *v > 0);
+        match field {
+            ThrottleField::IopsRead => self.iops_read = value,
+            ThrottleField::IopsWrite => self.iops_write = value,
+            ThrottleField::BpsRead => self.bps_read = value,
+            ThrottleField::BpsWrite => self.bps_write = value,
+        }
+    }
+
+    pub fn is_unlimited(&self) -> bool {
+        *self == DiskThrottle::default()
+    }
+
+    /// `-drive` options for the limits that are set, each led by a comma
+    pub fn drive_opts(&self) -> String {
+        ThrottleField::ALL
+            .into_iter()
+            .filter_map(|f| Some(format!(",{}={}", f.option(), self.get(f)?)))
+            .collect()
+    }
+
+    /// Whether a `-drive` option (`key=value`) sets one of these limits
+    pub fn is_throttle_opt(opt: &str) -> bool {
+        let key = opt.split_once('=').map_or(opt, |(key, _)| key);
+        ThrottleField::ALL
+            .into_iter()
+            .any(|f| key == f.option() || key == f.legacy_option())
+    }
+
+    /// A limit as typed, in the field's unit: a whole number, with blank or
+    /// 0 for no limit. Bandwidth may also be given in bytes/s with a `B`
+    /// suffix. Returns QEMU's value.
+    pub fn parse_input(field: ThrottleField, input: &str) -> Result<Option<u64>, String> {
+        let input = input.trim();
+        if input.is_empty() {
+            return Ok(None);
+        }
+        let (number, scale, unit) = match input.strip_suffix(['B', 'b']) {
+            Some(bytes) if field.scale() > 1 => (bytes.trim(), 1, "bytes/s"),
+            _ => (input, field.scale(), field.unit()),
+        };
+        let value: u64 = number
+            .parse()
+            .map_err(|_| format!("'{}' is not a whole number of {}", number, unit))?;
+        let value = value
+            .checked_mul(scale)
+            .ok_or_else(|| format!("{} {} is too large", number, unit))?;
+        Ok(Some(value).filter(|v| *v > 0))
+    }
+
+    /// A limit in the field's unit, as typed in (blank for none). Bandwidth
+    /// that isn't a whole MiB/s is shown in bytes, so saving it unchanged
+    /// keeps it exact.
+    pub fn input_value(&self, field: ThrottleField) -> String {
+        match self.get(field) {
+            None => String::new(),
+            Some(v) if v % field.scale() == 0 => (v / field.scale()).to_string(),
+            Some(v) => format!("{}B", v),
+        }
+    }
+
+    /// A limit for display, e.g. `500 IOPS`, `40 MiB/s` or `unlimited`
+    pub fn describe(&self, field: ThrottleField) -> String {
+        match self.get(field) {
+            None => "unlimited".to_string(),
+            Some(v) if v % field.scale() == 0 => format!("{} {}", v / field.scale(), field.unit()),
+            // Set by hand in bytes/s
+            Some(v) => format!("{} bytes/s", v),
+        }
+    }
 }
 
 /// Boot mode
@@ -245,6 +614,785 @@ pub enum BootMode {
     Recovery(PathBuf),
     Floppy(PathBuf),
     Network,
+    /// Normal boot with `-snapshot`: disk writes go to temporary overlays
+    /// that QEMU drops on exit
+    Ephemeral,
+}
+
+/// Direct kernel boot: QEMU loads the kernel (and optional initrd) itself,
+/// skipping the disk's bootloader.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DirectKernelBoot {
+    /// Kernel image (`-kernel`); empty while no kernel is chosen
+    pub kernel: PathBuf,
+    /// Initial ramdisk (`-initrd`)
+    pub initrd: Option<PathBuf>,
+    /// Kernel command line (`-append`)
+    pub append: String,
+}
+
+impl DirectKernelBoot {
+    /// Whether a kernel is set, i.e. there is anything to boot.
+    pub fn is_enabled(&self) -> bool {
+        !self.kernel.as_os_str().is_empty()
+    }
+
+    /// QEMU arguments as separate, unescaped words (`-kernel`, path, ...).
+    pub fn to_args(&self) -> Vec<String> {
+        if !self.is_enabled() {
+            return Vec::new();
+        }
+        let mut args = vec!["-kernel".to_string(), self.kernel.display().to_string()];
+        if let Some(ref initrd) = self.initrd {
+            args.push("-initrd".to_string());
+            args.push(initrd.display().to_string());
+        }
+        if !self.append.is_empty() {
+            args.push("-append".to_string());
+            args.push(self.append.clone());
+        }
+        args
+    }
+}
+
+/// Window options for the GTK and SDL display backends, plus a preferred
+/// guest resolution for virtio-gpu and the number of guest monitors.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DisplayWindow {
+    /// Scale the guest display to the window (GTK `zoom-to-fit`)
+    pub zoom_to_fit: bool,
+    /// Open the window full screen (`-full-screen`)
+    pub full_screen: bool,
+    /// Resolution virtio-gpu reports to the guest as preferred, (width, height)
+    pub resolution: Option<(u32, u32)>,
+    /// Monitors (heads) the guest sees: virtio-gpu `max_outputs`, or one
+    /// extra `-device qxl` per head beyond the first
+    pub num_displays: u32,
+}
+
+impl Default for DisplayWindow {
+    fn default() -> Self {
+        Self {
+            zoom_to_fit: false,
+            full_screen: false,
+            resolution: None,
+            num_displays: 1,
+        }
+    }
+}
+
+/// Most guest monitors offered in the display options screen
+pub const MAX_DISPLAYS: u32 = 4;
+
+/// Resolutions offered in the display options screen
+pub const RESOLUTION_PRESETS: &[(u32, u32)] = &[
+    (1280, 720),
+    (1600, 900),
+    (1920, 1080),
+    (2560, 1440),
+    (3840, 2160),
+];
+
+impl DisplayWindow {
+    /// Whether the display backend has `zoom-to-fit` (only GTK here; SDL
+    /// always scales to its window)
+    pub fn supports_zoom_to_fit(display: &str) -> bool {
+        display == "gtk"
+    }
+
+    /// Whether the display backend opens a local window that can start full screen
+    pub fn supports_full_screen(display: &str) -> bool {
+        matches!(display, "gtk" | "sdl")
+    }
+
+    /// Whether the display backend shows every guest head (VNC only shows
+    /// the first)
+    pub fn supports_multiple_displays(display: &str) -> bool {
+        matches!(display, "gtk" | "sdl" | "spice-app")
+    }
+
+    /// Whether the graphics adapter can drive more than one head
+    pub fn adapter_supports_multiple_displays(vga: &VgaType) -> bool {
+        matches!(vga, VgaType::Virtio | VgaType::Qxl)
+    }
+
+    /// These options with anything `display` can't use turned off
+    pub fn for_display(self, display: &str) -> Self {
+        Self {
+            zoom_to_fit: self.zoom_to_fit && Self::supports_zoom_to_fit(display),
+            full_screen: self.full_screen && Self::supports_full_screen(display),
+            resolution: self.resolution,
+            num_displays: if Self::supports_multiple_displays(display) {
+                self.num_displays
+            } else {
+                1
+            },
+        }
+    }
+
+    /// One more monitor, wrapping back to one after [`MAX_DISPLAYS`]
+    pub fn next_num_displays(&self) -> u32 {
+        if self.num_displays >= MAX_DISPLAYS {
+            1
+        } else {
+            self.num_displays + 1
+        }
+    }
+
+    /// The preset after the current resolution, wrapping back to `None`
+    /// (the guest's own default)
+    pub fn next_resolution(&self) -> Option<(u32, u32)> {
+        match self.resolution {
+            None => RESOLUTION_PRESETS.first().copied(),
+            Some(current) => RESOLUTION_PRESETS
+                .iter()
+                .position(|&r| r == current)
+                .and_then(|i| RESOLUTION_PRESETS.get(i + 1))
+                .copied(),
+        }
+    }
+
+    /// QEMU arguments other than `zoom-to-fit`, which is a `-display` option.
+    /// Extra monitors depend on the VM's graphics adapter `vga`.
+    pub fn to_args(self, vga: &VgaType) -> Vec<String> {
+        let mut args = Vec::new();
+        if self.full_screen {
+            args.push("-full-screen".to_string());
+        }
+        // virtio-gpu-base covers virtio-vga, virtio-gpu-pci and their -gl variants
+        if let Some((width, height)) = self.resolution {
+            args.push(format!("-global virtio-gpu-base.xres={}", width));
+            args.push(format!("-global virtio-gpu-base.yres={}", height));
+        }
+        if self.num_displays > 1 {
+            match vga {
+                VgaType::Virtio => args.push(format!(
+                    "-global virtio-gpu-base.max_outputs={}",
+                    self.num_displays
+                )),
+                // -vga qxl is the first head; each secondary head is its own device
+                VgaType::Qxl => {
+                    for _ in 1..self.num_displays {
+                        args.push("-device qxl".to_string());
+                    }
+                }
+                _ => {}
+            }
+        }
+        args
+    }
+}
+
+/// IDE index of the secondary "tools" CD-ROM (e.g. the virtio-win driver ISO).
+/// Index 0 is the main disk, 1 the install ISO and 2 a recovery image, so the
+/// tools ISO stays attached alongside any of them.
+pub const TOOLS_ISO_DRIVE_INDEX: u8 = 3;
+
+/// Floppy unit of the floppy kept attached from boot options: drive B:, since
+/// A: belongs to boot floppies (`--floppy` and the wizard's boot floppy)
+pub const FLOPPY_DRIVE_INDEX: u8 = 1;
+
+/// Launch script variable holding the QEMU window title
+pub const WINDOW_TITLE_VAR: &str = "VM_TITLE";
+
+/// `-name` for launch scripts: the window title from [`WINDOW_TITLE_VAR`]
+/// (commas doubled for QEMU) and `process=` set to the VM directory name,
+/// so `ps` shows which VM a QEMU is and vm-curator can match it reliably
+pub const WINDOW_NAME_ARG: &str = r#"-name "${VM_TITLE//,/,,},process=${VM_DIR##*/}""#;
+
+/// Boot order presets offered in the boot options screen, as
+/// (QEMU drive letters, label). `None` leaves the choice to the firmware.
+pub const BOOT_ORDER_PRESETS: &[(Option<&str>, &str)] = &[
+    (None, "Firmware default"),
+    (Some("cd"), "Disk, then CD-ROM"),
+    (Some("dc"), "CD-ROM, then disk"),
+    (Some("cdn"), "Disk, CD-ROM, then network"),
+    (Some("ncd"), "Network, disk, then CD-ROM"),
+    (Some("acd"), "Floppy, disk, then CD-ROM"),
+];
+
+/// Persistent boot device order and interactive boot menu setting.
+///
+/// Applied on top of `BootMode`: a normal boot uses `order`, while install,
+/// CD-ROM, recovery and floppy boots keep their own boot device and only pick
+/// up the boot menu toggle.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BootOrder {
+    /// QEMU drive letters in priority order (a=floppy, c=disk, d=CD-ROM, n=network)
+    pub order: Option<String>,
+    /// Show the firmware's interactive boot menu (`-boot menu=on`)
+    pub menu: bool,
+}
+
+impl BootOrder {
+    /// Whether `order` is a valid `-boot order=` value: floppy, disk, CD-ROM
+    /// or network drive letters, each given at most once.
+    pub fn is_valid_order(order: &str) -> bool {
+        !order.is_empty()
+            && order.chars().enumerate().all(|(i, c)| {
+                matches!(c, 'a' | 'b' | 'c' | 'd' | 'n' | 'o' | 'p') && !order[..i].contains(c)
+            })
+    }
+
+    /// True when nothing needs to be emitted (firmware default, no menu).
+    pub fn is_default(&self) -> bool {
+        self.order.is_none() && !self.menu
+    }
+
+    /// Human-readable label for the current order.
+    pub fn order_label(&self) -> String {
+        match self.order.as_deref() {
+            None => BOOT_ORDER_PRESETS[0].1.to_string(),
+            Some(order) => BOOT_ORDER_PRESETS
+                .iter()
+                .find(|(o, _)| *o == Some(order))
+                .map(|(_, label)| label.to_string())
+                .unwrap_or_else(|| format!("Custom ({})", order)),
+        }
+    }
+
+    /// Advance to the next preset order, wrapping around. Custom orders
+    /// restart at the first preset.
+    pub fn cycle_order(&mut self) {
+        let current = BOOT_ORDER_PRESETS
+            .iter()
+            .position(|(o, _)| *o == self.order.as_deref());
+        let next = current
+            .map(|i| (i + 1) % BOOT_ORDER_PRESETS.len())
+            .unwrap_or(0);
+        self.order = BOOT_ORDER_PRESETS[next].0.map(|o| o.to_string());
+    }
+
+    /// `-boot` argument for a normal boot, or `None` if nothing needs setting.
+    pub fn normal_boot_arg(&self) -> Option<String> {
+        let mut opts = Vec::new();
+        if let Some(order) = self.order.as_deref().filter(|o| Self::is_valid_order(o)) {
+            opts.push(format!("order={}", order));
+        }
+        if self.menu {
+            opts.push("menu=on".to_string());
+        }
+        if opts.is_empty() {
+            None
+        } else {
+            Some(format!("-boot {}", opts.join(",")))
+        }
+    }
+
+    /// `-boot` argument for install/CD-ROM/recovery/floppy boots. These keep
+    /// the boot device their branch already selects, so only the menu applies.
+    pub fn media_boot_arg(&self) -> Option<String> {
+        if self.menu {
+            Some("-boot menu=on".to_string())
+        } else {
+            None
+        }
+    }
+}
+
+/// CPU models offered by the CPU editor. Anything else found in a script is
+/// kept as-is and shown alongside these.
+pub const CPU_MODELS: &[&str] = &["host", "max", "qemu64", "kvm64", "EPYC", "Skylake-Client"];
+
+/// Common `-cpu` flags the CPU editor can toggle, as (flag, description).
+/// Flags with a value (`hv_spinlocks=0x1fff`) are matched by their key.
+pub const CPU_FLAG_OPTIONS: &[(&str, &str)] = &[
+    ("kvm=off", "Hide the KVM signature (NVIDIA passthrough)"),
+    (
+        "hv_relaxed",
+        "Hyper-V: relaxed timing, avoids watchdog BSODs",
+    ),
+    ("hv_vapic", "Hyper-V: virtual APIC"),
+    ("hv_spinlocks=0x1fff", "Hyper-V: paravirtual spinlocks"),
+    ("hv_time", "Hyper-V: reference TSC clock"),
+    ("hv_vpindex", "Hyper-V: virtual processor index"),
+    ("hv_synic", "Hyper-V: synthetic interrupt controller"),
+    ("hv_stimer", "Hyper-V: synthetic timers (needs synic, time)"),
+    ("hv_frequencies", "Hyper-V: expose TSC/APIC frequencies"),
+    ("topoext", "AMD: expose SMT topology"),
+    ("+vmx", "Intel: nested virtualization (VT-x in guest)"),
+    ("+svm", "AMD: nested virtualization (AMD-V in guest)"),
+];
+
+/// Hyper-V vendor ID used by the NVIDIA workaround. Any 12-character string
+/// other than the default "Microsoft Hv" works.
+pub const NVIDIA_HV_VENDOR_ID: &str = "hv_vendor_id=vmcurator123";
+
+/// Named `-cpu` presets, as (label, composed `-cpu` value).
+pub const CPU_PRESETS: &[(&str, &str)] = &[
+    (
+        "Windows gaming passthrough",
+        "host,kvm=off,hv_relaxed,hv_vapic,hv_spinlocks=0x1fff,hv_time,hv_vpindex,hv_synic,hv_stimer,hv_frequencies",
+    ),
+    (
+        "Windows (Hyper-V enlightenments)",
+        "host,hv_relaxed,hv_vapic,hv_spinlocks=0x1fff,hv_time",
+    ),
+    ("Linux native", "host"),
+    ("Portable (no host CPU features)", "qemu64"),
+];
+
+/// A `-cpu` value split into its model and comma-separated flags, so the CPU
+/// editor can toggle individual flags and re-emit the same string.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CpuConfig {
+    /// CPU model (`host`, `qemu64`, ...)
+    pub model: String,
+    /// Flags in script order (`hv_relaxed`, `kvm=off`, `+invtsc`, ...)
+    pub flags: Vec<String>,
+}
+
+impl CpuConfig {
+    /// Parse a `-cpu` value such as `host,kvm=off,hv_relaxed`.
+    pub fn parse(value: &str) -> Self {
+        let mut parts = value.split(',').map(str::trim).filter(|p| !p.is_empty());
+        Self {
+            model: parts.next().unwrap_or("host").to_string(),
+            flags: parts.map(str::to_string).collect(),
+        }
+    }
+
+    /// The composed `-cpu` value (model followed by its flags).
+    pub fn to_arg(&self) -> String {
+        std::iter::once(self.model.as_str())
+            .chain(self.flags.iter().map(String::as_str))
+            .collect::<Vec<_>>()
+            .join(",")
+    }
+
+    fn flag_key(flag: &str) -> &str {
+        flag.split('=').next().unwrap_or(flag)
+    }
+
+    /// Whether a flag with the same key is set (`hv_spinlocks=0xfff` counts
+    /// as `hv_spinlocks=0x1fff`).
+    pub fn has_flag(&self, flag: &str) -> bool {
+        let key = Self::flag_key(flag);
+        self.flags.iter().any(|f| Self::flag_key(f) == key)
+    }
+
+    /// Add `flag` if no flag with its key is set, otherwise remove it.
+    pub fn toggle_flag(&mut self, flag: &str) {
+        if self.has_flag(flag) {
+            let key = Self::flag_key(flag);
+            self.flags.retain(|f| Self::flag_key(f) != key);
+        } else {
+            self.flags.push(flag.to_string());
+        }
+    }
+
+    /// Step through `CPU_MODELS`; a custom model restarts at the first one.
+    pub fn cycle_model(&mut self, delta: i32) {
+        let len = CPU_MODELS.len() as i32;
+        let next = match CPU_MODELS.iter().position(|m| *m == self.model) {
+            Some(i) => (i as i32 + delta).rem_euclid(len) as usize,
+            None => 0,
+        };
+        self.model = CPU_MODELS[next].to_string();
+    }
+
+    /// Whether the guest can't see it's virtualized: the KVM signature is
+    /// hidden and either the Hyper-V vendor ID is spoofed or the CPUID
+    /// hypervisor bit is cleared. NVIDIA drivers before R465 refuse to run
+    /// (Code 43) when they detect a hypervisor.
+    pub fn hides_hypervisor(&self) -> bool {
+        // `has_flag` compares keys only, so `kvm=on` would count as `kvm=off`
+        let set = |flag: &str| self.flags.iter().any(|f| f == flag);
+        set("kvm=off")
+            && (self.has_flag("hv_vendor_id") || set("hypervisor=off") || set("-hypervisor"))
+    }
+
+    /// Apply the NVIDIA Code 43 workaround: host CPU model, hidden KVM
+    /// signature, spoofed Hyper-V vendor ID and a cleared hypervisor bit.
+    /// Existing flags are kept, except `kvm=on`/`hypervisor=on`.
+    pub fn apply_nvidia_workaround(&mut self) {
+        self.model = "host".to_string();
+        for flag in ["kvm=off", NVIDIA_HV_VENDOR_ID, "hypervisor=off"] {
+            if flag.ends_with("=off") && !self.flags.iter().any(|f| f == flag) {
+                let key = Self::flag_key(flag);
+                self.flags.retain(|f| Self::flag_key(f) != key);
+            }
+            if !self.has_flag(flag) {
+                self.flags.push(flag.to_string());
+            }
+        }
+    }
+
+    /// Expose the host's virtualization extension (`vmx` or `svm`) to the
+    /// guest for nested virtualization. Emulated models don't carry it, so
+    /// anything other than `host`/`max` is switched to `host`.
+    pub fn enable_nested(&mut self, virt_flag: &str) {
+        if self.model != "host" && self.model != "max" {
+            self.model = "host".to_string();
+        }
+        let disabled = [format!("-{}", virt_flag), format!("{}=off", virt_flag)];
+        self.flags.retain(|f| !disabled.contains(f));
+        let enabled = [
+            format!("+{}", virt_flag),
+            virt_flag.to_string(),
+            format!("{}=on", virt_flag),
+        ];
+        if !self.flags.iter().any(|f| enabled.contains(f)) {
+            self.flags.push(format!("+{}", virt_flag));
+        }
+    }
+
+    /// Whether `vmx` or `svm` is exposed to the guest for nested
+    /// virtualization (`+vmx`, `vmx` or `vmx=on`)
+    pub fn nested_enabled(&self) -> bool {
+        self.nested_flag().is_some()
+    }
+
+    /// The virtualization extension (`vmx` or `svm`) exposed to the guest
+    /// for nested virtualization, if any
+    pub fn nested_flag(&self) -> Option<&'static str> {
+        ["vmx", "svm"].into_iter().find(|virt| {
+            self.flags
+                .iter()
+                .any(|f| f.strip_prefix('+').unwrap_or(f) == *virt || *f == format!("{}=on", virt))
+        })
+    }
+
+    /// Label of the preset this config matches exactly, if any.
+    pub fn preset_label(&self) -> Option<&'static str> {
+        let arg = self.to_arg();
+        CPU_PRESETS
+            .iter()
+            .find(|(_, value)| *value == arg)
+            .map(|(label, _)| *label)
+    }
+}
+
+/// Where the guest's real-time clock starts (`-rtc base=`)
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum RtcBase {
+    #[default]
+    Utc,
+    /// Host local time (what Windows expects)
+    Localtime,
+    /// A fixed start date, `YYYY-MM-DD` or `YYYY-MM-DDTHH:MM:SS`
+    Fixed(String),
+}
+
+impl RtcBase {
+    /// Whether `value` is a start date QEMU accepts for `base=`
+    pub fn is_valid_datetime(value: &str) -> bool {
+        chrono::NaiveDateTime::parse_from_str(value, "%Y-%m-%dT%H:%M:%S").is_ok()
+            || chrono::NaiveDate::parse_from_str(value, "%Y-%m-%d").is_ok()
+    }
+
+    fn value(&self) -> &str {
+        match self {
+            RtcBase::Utc => "utc",
+            RtcBase::Localtime => "localtime",
+            RtcBase::Fixed(date) => date,
+        }
+    }
+}
+
+/// What drives the guest's real-time clock (`-rtc clock=`)
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum RtcClock {
+    /// Follows the host clock, including its adjustments
+    #[default]
+    Host,
+    /// Host clock without adjustments (monotonic)
+    Rt,
+    /// Runs only while the guest runs; isolated from the host
+    Vm,
+}
+
+impl RtcClock {
+    pub const ALL: [RtcClock; 3] = [RtcClock::Host, RtcClock::Rt, RtcClock::Vm];
+
+    pub fn value(&self) -> &'static str {
+        match self {
+            RtcClock::Host => "host",
+            RtcClock::Rt => "rt",
+            RtcClock::Vm => "vm",
+        }
+    }
+
+    pub fn description(&self) -> &'static str {
+        match self {
+            RtcClock::Host => "follows the host clock",
+            RtcClock::Rt => "host clock without NTP/date adjustments",
+            RtcClock::Vm => "only advances while the guest runs",
+        }
+    }
+
+    /// The next clock source, wrapping around
+    pub fn next(self) -> Self {
+        let i = Self::ALL.iter().position(|c| *c == self).unwrap_or(0);
+        Self::ALL[(i + 1) % Self::ALL.len()]
+    }
+}
+
+/// Keyboard and pointer devices the guest gets
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum InputDevices {
+    /// The machine's built-in PS/2 keyboard and mouse only; all DOS and
+    /// early Windows understand
+    Ps2,
+    /// A USB tablet, so the pointer follows the host cursor without grabbing
+    #[default]
+    UsbTablet,
+    /// virtio keyboard and tablet: lowest latency, needs guest drivers
+    /// (in Linux, virtio-win for Windows)
+    Virtio,
+}
+
+impl InputDevices {
+    pub const ALL: [InputDevices; 3] = [
+        InputDevices::Ps2,
+        InputDevices::UsbTablet,
+        InputDevices::Virtio,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            InputDevices::Ps2 => "PS/2",
+            InputDevices::UsbTablet => "USB tablet",
+            InputDevices::Virtio => "virtio",
+        }
+    }
+
+    /// The next choice, wrapping around (`backward` steps the other way)
+    pub fn cycle(self, backward: bool) -> Self {
+        let i = Self::ALL.iter().position(|d| *d == self).unwrap_or(0);
+        let len = Self::ALL.len();
+        Self::ALL[if backward {
+            (i + len - 1) % len
+        } else {
+            (i + 1) % len
+        }]
+    }
+
+    /// The devices a launch script adds. PS/2 is part of the machine, so it
+    /// needs no arguments.
+    pub fn to_args(self) -> Vec<String> {
+        match self {
+            InputDevices::Ps2 => Vec::new(),
+            InputDevices::UsbTablet => vec!["-usb".to_string(), "-device usb-tablet".to_string()],
+            InputDevices::Virtio => vec![
+                "-device virtio-keyboard-pci".to_string(),
+                "-device virtio-tablet-pci".to_string(),
+            ],
+        }
+    }
+
+    /// The input devices a launch script adds
+    pub fn from_script(content: &str) -> Self {
+        let code: Vec<&str> = content
+            .lines()
+            .filter(|line| !line.trim_start().starts_with('#'))
+            .collect();
+        let has = |device: &str| code.iter().any(|line| line.contains(device));
+        if has("virtio-keyboard") || has("virtio-tablet") || has("virtio-mouse") {
+            InputDevices::Virtio
+        } else if has("usb-tablet") {
+            InputDevices::UsbTablet
+        } else {
+            InputDevices::Ps2
+        }
+    }
+}
+
+/// `osk` string the Apple SMC hands to macOS. Intel macOS refuses to boot
+/// without it; it is the same on every Mac.
+pub const APPLE_SMC_OSK: &str = "ourhardworkbythesewordsguardedpleasedontsteal(c)AppleComputerInc";
+
+/// Arguments Intel macOS guests need on top of their CPU masking: the Apple
+/// SMC and an SMBIOS baseboard table
+pub fn apple_smc_args() -> Vec<String> {
+    vec![
+        format!("-device \"isa-applesmc,osk={}\"", APPLE_SMC_OSK),
+        "-smbios type=2".to_string(),
+    ]
+}
+
+/// Whether a launch script gives the guest an Apple SMC
+pub fn has_apple_smc(content: &str) -> bool {
+    content
+        .lines()
+        .filter(|line| !line.trim_start().starts_with('#'))
+        .any(|line| line.contains("isa-applesmc"))
+}
+
+/// Guest real-time clock settings (`-rtc`)
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RtcConfig {
+    pub base: RtcBase,
+    pub clock: RtcClock,
+    /// Other `-rtc` options (e.g. `driftfix=slew`), kept as written
+    pub extra: Vec<String>,
+}
+
+impl RtcConfig {
+    /// Settings from a `-rtc` value, e.g. `base=localtime,clock=vm`
+    pub fn parse(value: &str) -> Self {
+        let mut rtc = RtcConfig::default();
+        for opt in value.split(',').filter(|o| !o.is_empty()) {
+            match opt.split_once('=') {
+                Some(("base", "utc")) => rtc.base = RtcBase::Utc,
+                Some(("base", "localtime")) => rtc.base = RtcBase::Localtime,
+                Some(("base", date)) => rtc.base = RtcBase::Fixed(date.to_string()),
+                Some(("clock", clock)) => match RtcClock::ALL.iter().find(|c| c.value() == clock) {
+                    Some(c) => rtc.clock = *c,
+                    None => rtc.extra.push(opt.to_string()),
+                },
+                _ => rtc.extra.push(opt.to_string()),
+            }
+        }
+        rtc
+    }
+
+    /// The `-rtc` argument, or `None` for QEMU's default (UTC, host clock).
+    /// Local time alone gives `-rtc base=localtime`, as the old toggle did.
+    pub fn to_arg(&self) -> Option<String> {
+        if *self == RtcConfig::default() {
+            return None;
+        }
+        let mut opts = vec![format!("base={}", self.base.value())];
+        if self.clock != RtcClock::Host {
+            opts.push(format!("clock={}", self.clock.value()));
+        }
+        opts.extend(self.extra.iter().cloned());
+        Some(format!("-rtc {}", opts.join(",")))
+    }
+}
+
+/// One dimension of a [`CpuTopology`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TopologyDim {
+    Sockets,
+    Cores,
+    Threads,
+}
+
+/// Explicit `-smp` topology. Without one, the vCPUs are emitted as cores of
+/// a single socket.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CpuTopology {
+    pub sockets: u32,
+    /// Cores per socket
+    pub cores: u32,
+    /// Threads per core
+    pub threads: u32,
+}
+
+impl CpuTopology {
+    /// All vCPUs as cores of one socket.
+    pub fn flat(cpus: u32) -> Self {
+        Self {
+            sockets: 1,
+            cores: cpus.max(1),
+            threads: 1,
+        }
+    }
+
+    /// Number of vCPUs this topology describes.
+    pub fn total(&self) -> u32 {
+        self.sockets * self.cores * self.threads
+    }
+
+    /// Check that the topology adds up to `cpus` vCPUs.
+    pub fn validate(&self, cpus: u32) -> Result<(), String> {
+        if self.sockets == 0 || self.cores == 0 || self.threads == 0 {
+            return Err("Sockets, cores and threads must each be at least 1".to_string());
+        }
+        if self.total() != cpus {
+            return Err(format!(
+                "{} sockets x {} cores x {} threads is {} vCPUs, not {}",
+                self.sockets,
+                self.cores,
+                self.threads,
+                self.total(),
+                cpus
+            ));
+        }
+        Ok(())
+    }
+
+    /// Step one dimension to the next value that keeps the product at
+    /// `cpus`, rebalancing the others: editing sockets or threads adjusts
+    /// cores, editing cores adjusts sockets. Stays put at either end.
+    pub fn step(&mut self, dim: TopologyDim, delta: i32, cpus: u32) {
+        let cpus = cpus.max(1);
+        let fixed = match dim {
+            TopologyDim::Sockets | TopologyDim::Cores => self.threads,
+            TopologyDim::Threads => self.sockets,
+        };
+        let fixed = if cpus.is_multiple_of(fixed) { fixed } else { 1 };
+        let room = cpus / fixed;
+
+        // Next divisor of `room` past the current value
+        let current = self.current(dim);
+        let next = if delta > 0 {
+            (current + 1..=room).find(|v| room.is_multiple_of(*v))
+        } else {
+            (1..current.min(room + 1))
+                .rev()
+                .find(|v| room.is_multiple_of(*v))
+        };
+        let Some(value) = next else {
+            return;
+        };
+        let other = room / value;
+        (self.sockets, self.cores, self.threads) = match dim {
+            TopologyDim::Sockets => (value, other, fixed),
+            TopologyDim::Cores => (other, value, fixed),
+            TopologyDim::Threads => (fixed, other, value),
+        };
+    }
+
+    /// Fit the topology to a new vCPU count, keeping threads and sockets
+    /// while they still divide it and putting the rest in cores.
+    pub fn rebalance(&mut self, cpus: u32) {
+        let cpus = cpus.max(1);
+        if !cpus.is_multiple_of(self.threads) {
+            self.threads = 1;
+        }
+        if !(cpus / self.threads).is_multiple_of(self.sockets) {
+            self.sockets = 1;
+        }
+        self.cores = cpus / (self.sockets * self.threads);
+    }
+
+    fn current(&self, dim: TopologyDim) -> u32 {
+        match dim {
+            TopologyDim::Sockets => self.sockets,
+            TopologyDim::Cores => self.cores,
+            TopologyDim::Threads => self.threads,
+        }
+    }
+}
+
+/// `-smp` argument for `cpus` vCPUs, with an explicit topology when given.
+pub fn smp_arg(cpus: u32, topology: Option<&CpuTopology>) -> String {
+    match topology {
+        Some(t) => format!(
+            "-smp cpus={},sockets={},cores={},threads={}",
+            cpus, t.sockets, t.cores, t.threads
+        ),
+        None => format!("-smp {},sockets=1,cores={},threads=1", cpus, cpus),
+    }
+}
+
+/// Hotplug DIMM slots reserved when a VM has a memory ceiling
+pub const MEMORY_HOTPLUG_SLOTS: u32 = 4;
+
+/// `-m` argument for `memory_mb` of boot RAM, reserving room up to
+/// `max_memory_mb` for hotplug when given.
+pub fn memory_arg(memory_mb: u32, max_memory_mb: Option<u32>) -> String {
+    match max_memory_mb {
+        Some(max) if max > memory_mb => format!(
+            "-m {}M,slots={},maxmem={}M",
+            memory_mb, MEMORY_HOTPLUG_SLOTS, max
+        ),
+        _ => format!("-m {}M", memory_mb),
+    }
 }
 
 /// QEMU configuration extracted from launch.sh
@@ -263,11 +1411,23 @@ pub struct QemuConfig {
     pub enable_kvm: bool,
     pub uefi: bool,
     pub tpm: bool,
+    /// The UEFI pflash enforces Secure Boot
+    #[serde(default)]
+    pub secure_boot: bool,
     pub extra_args: Vec<String>,
     pub raw_script: String,
     /// BIOS/ROM file path (for classic Mac and other systems needing custom firmware)
     #[serde(default)]
     pub bios_path: Option<PathBuf>,
+    /// Kernel/initrd booted directly by QEMU, if configured
+    #[serde(default)]
+    pub direct_kernel: Option<DirectKernelBoot>,
+    /// Unix socket the guest's serial console is exposed on, if any
+    #[serde(default)]
+    pub serial_socket: Option<PathBuf>,
+    /// Guest real-time clock settings
+    #[serde(default)]
+    pub rtc: RtcConfig,
 }
 
 impl Default for QemuConfig {
@@ -286,9 +1446,13 @@ impl Default for QemuConfig {
             enable_kvm: false,
             uefi: false,
             tpm: false,
+            secure_boot: false,
             extra_args: Vec::new(),
             raw_script: String::new(),
             bios_path: None,
+            direct_kernel: None,
+            serial_socket: None,
+            rtc: RtcConfig::default(),
         }
     }
 }
@@ -309,6 +1473,43 @@ impl QemuConfig {
             .or_else(|| self.disks.first())
     }
 
+    /// Whether the launch script attaches a QEMU guest agent channel.
+    pub fn has_guest_agent(&self) -> bool {
+        self.raw_script.contains("org.qemu.guest_agent.0")
+            || self
+                .extra_args
+                .iter()
+                .any(|arg| arg.contains("org.qemu.guest_agent.0"))
+    }
+
+    /// Whether the launch script attaches a virtio-balloon device.
+    pub fn has_balloon(&self) -> bool {
+        self.raw_script.contains("virtio-balloon")
+            || self
+                .extra_args
+                .iter()
+                .any(|arg| arg.contains("virtio-balloon"))
+    }
+
+    /// Whether the launch script uses the SPICE (`spice-app`) display.
+    pub fn has_spice_display(&self) -> bool {
+        self.raw_script.contains("-display spice-app")
+            || self
+                .extra_args
+                .iter()
+                .any(|arg| arg.contains("-display spice-app"))
+    }
+
+    /// Whether the launch script attaches the SPICE agent channel used for
+    /// clipboard sharing.
+    pub fn has_spice_agent(&self) -> bool {
+        self.raw_script.contains("com.redhat.spice.0")
+            || self
+                .extra_args
+                .iter()
+                .any(|arg| arg.contains("com.redhat.spice.0"))
+    }
+
     /// Whether para-virtualized 3D acceleration is currently enabled.
     /// Detected from the raw script (gl=on on display, virtio-vga-gl device,
     /// or any extra_arg containing those tokens).
@@ -329,6 +1530,56 @@ impl QemuConfig {
 mod tests {
     use super::*;
 
+    #[test]
+    fn port_forward_descriptions_name_the_service() {
+        let ssh = PortForward {
+            protocol: PortProtocol::Tcp,
+            host_port: 2222,
+            guest_port: 22,
+        };
+        assert_eq!(ssh.describe(), "ssh: host 2222 → guest 22");
+        let game = PortForward {
+            protocol: PortProtocol::Udp,
+            host_port: 27015,
+            guest_port: 27015,
+        };
+        assert_eq!(game.describe(), "host 27015 → guest 27015 (UDP)");
+    }
+
+    #[test]
+    fn network_addressing_follows_backend() {
+        let net = |backend| NetworkConfig {
+            backend,
+            ..NetworkConfig::default()
+        };
+        assert!(net(NetworkBackend::User)
+            .addressing()
+            .is_some_and(|a| a.contains("10.0.2.15")));
+        assert!(net(NetworkBackend::Bridge("br0".to_string()))
+            .addressing()
+            .is_some_and(|a| a.contains("DHCP")));
+        assert_eq!(net(NetworkBackend::None).addressing(), None);
+    }
+
+    #[test]
+    fn spice_clipboard_detection() {
+        let cfg = QemuConfig {
+            raw_script: "qemu-system-x86_64 \\\n  -display spice-app,gl=on \\\n  \
+                -device virtserialport,chardev=spicechannel0,name=com.redhat.spice.0"
+                .to_string(),
+            ..Default::default()
+        };
+        assert!(cfg.has_spice_display());
+        assert!(cfg.has_spice_agent());
+
+        let gtk = QemuConfig {
+            raw_script: "qemu-system-x86_64 \\\n  -display gtk".to_string(),
+            ..Default::default()
+        };
+        assert!(!gtk.has_spice_display());
+        assert!(!gtk.has_spice_agent());
+    }
+
     #[test]
     fn has_gl_acceleration_detects_device() {
         let cfg = QemuConfig {
@@ -356,4 +1607,318 @@ mod tests {
         };
         assert!(!cfg.has_gl_acceleration());
     }
+
+    #[test]
+    fn boot_order_validation() {
+        assert!(BootOrder::is_valid_order("cd"));
+        assert!(BootOrder::is_valid_order("ncd"));
+        assert!(!BootOrder::is_valid_order(""));
+        assert!(!BootOrder::is_valid_order("cc"));
+        assert!(!BootOrder::is_valid_order("cx"));
+        assert!(!BootOrder::is_valid_order("c;rm"));
+    }
+
+    #[test]
+    fn boot_order_args() {
+        let default = BootOrder::default();
+        assert!(default.is_default());
+        assert_eq!(default.normal_boot_arg(), None);
+        assert_eq!(default.media_boot_arg(), None);
+
+        let both = BootOrder {
+            order: Some("dc".to_string()),
+            menu: true,
+        };
+        assert_eq!(
+            both.normal_boot_arg().as_deref(),
+            Some("-boot order=dc,menu=on")
+        );
+        assert_eq!(both.media_boot_arg().as_deref(), Some("-boot menu=on"));
+
+        let order_only = BootOrder {
+            order: Some("cd".to_string()),
+            menu: false,
+        };
+        assert_eq!(
+            order_only.normal_boot_arg().as_deref(),
+            Some("-boot order=cd")
+        );
+        assert_eq!(order_only.media_boot_arg(), None);
+    }
+
+    #[test]
+    fn boot_order_cycle_wraps() {
+        let mut boot = BootOrder::default();
+        for _ in 0..BOOT_ORDER_PRESETS.len() {
+            boot.cycle_order();
+        }
+        assert_eq!(boot.order, None);
+
+        boot.order = Some("dnc".to_string());
+        assert_eq!(boot.order_label(), "Custom (dnc)");
+        boot.cycle_order();
+        assert_eq!(boot.order, None);
+    }
+
+    #[test]
+    fn cpu_config_round_trips() {
+        let value = "host,kvm=off,hv_relaxed,hv_spinlocks=0x1fff,+invtsc";
+        let cpu = CpuConfig::parse(value);
+        assert_eq!(cpu.model, "host");
+        assert_eq!(cpu.flags.len(), 4);
+        assert_eq!(cpu.to_arg(), value);
+        assert_eq!(CpuConfig::parse("qemu64").to_arg(), "qemu64");
+    }
+
+    #[test]
+    fn cpu_config_toggles_flags_by_key() {
+        let mut cpu = CpuConfig::parse("host,hv_spinlocks=0xfff");
+        assert!(cpu.has_flag("hv_spinlocks=0x1fff"));
+        cpu.toggle_flag("hv_spinlocks=0x1fff");
+        assert_eq!(cpu.to_arg(), "host");
+        cpu.toggle_flag("kvm=off");
+        cpu.toggle_flag("hv_relaxed");
+        assert_eq!(cpu.to_arg(), "host,kvm=off,hv_relaxed");
+    }
+
+    #[test]
+    fn cpu_presets_are_recognised() {
+        for (label, value) in CPU_PRESETS {
+            assert_eq!(CpuConfig::parse(value).preset_label(), Some(*label));
+        }
+        let mut cpu = CpuConfig::parse("Penryn");
+        assert_eq!(cpu.preset_label(), None);
+        cpu.cycle_model(1);
+        assert_eq!(cpu.model, CPU_MODELS[0]);
+    }
+
+    #[test]
+    fn nvidia_workaround_hides_hypervisor() {
+        let mut cpu = CpuConfig::parse("qemu64,hv_relaxed,kvm=off");
+        assert!(!cpu.hides_hypervisor());
+        cpu.apply_nvidia_workaround();
+        assert!(cpu.hides_hypervisor());
+        assert_eq!(
+            cpu.to_arg(),
+            "host,hv_relaxed,kvm=off,hv_vendor_id=vmcurator123,hypervisor=off"
+        );
+        // Applying twice doesn't duplicate flags
+        cpu.apply_nvidia_workaround();
+        assert_eq!(cpu.flags.len(), 4);
+        assert!(CpuConfig::parse("host,kvm=off,-hypervisor").hides_hypervisor());
+    }
+
+    #[test]
+    fn hypervisor_or_kvm_on_is_not_hidden() {
+        assert!(!CpuConfig::parse("host,kvm=off,hypervisor=on").hides_hypervisor());
+        assert!(!CpuConfig::parse("host,kvm=on,hypervisor=off").hides_hypervisor());
+        assert!(CpuConfig::parse("host,kvm=off,hypervisor=off").hides_hypervisor());
+
+        let mut cpu = CpuConfig::parse("host,kvm=on,hypervisor=on");
+        cpu.apply_nvidia_workaround();
+        assert!(cpu.hides_hypervisor());
+        assert_eq!(
+            cpu.to_arg(),
+            "host,kvm=off,hv_vendor_id=vmcurator123,hypervisor=off"
+        );
+    }
+
+    #[test]
+    fn memory_arg_with_and_without_ceiling() {
+        assert_eq!(memory_arg(2048, None), "-m 2048M");
+        assert_eq!(
+            memory_arg(2048, Some(8192)),
+            "-m 2048M,slots=4,maxmem=8192M"
+        );
+        // A ceiling at or below boot RAM has nothing to hotplug
+        assert_eq!(memory_arg(2048, Some(2048)), "-m 2048M");
+    }
+
+    #[test]
+    fn smp_arg_flat_and_explicit() {
+        assert_eq!(smp_arg(4, None), "-smp 4,sockets=1,cores=4,threads=1");
+        let topo = CpuTopology {
+            sockets: 2,
+            cores: 2,
+            threads: 2,
+        };
+        assert_eq!(
+            smp_arg(8, Some(&topo)),
+            "-smp cpus=8,sockets=2,cores=2,threads=2"
+        );
+        assert!(topo.validate(8).is_ok());
+        assert!(topo.validate(6).is_err());
+    }
+
+    #[test]
+    fn topology_step_keeps_product() {
+        let mut topo = CpuTopology::flat(12);
+        topo.step(TopologyDim::Sockets, 1, 12);
+        assert_eq!((topo.sockets, topo.cores, topo.threads), (2, 6, 1));
+        topo.step(TopologyDim::Threads, 1, 12);
+        assert_eq!((topo.sockets, topo.cores, topo.threads), (2, 3, 2));
+        // Editing cores rebalances sockets; 3 -> 6 skips 4 and 5
+        topo.step(TopologyDim::Cores, 1, 12);
+        assert_eq!((topo.sockets, topo.cores, topo.threads), (1, 6, 2));
+        // Already at the top: stays put
+        topo.step(TopologyDim::Cores, 1, 12);
+        assert_eq!((topo.sockets, topo.cores, topo.threads), (1, 6, 2));
+        assert_eq!(topo.total(), 12);
+    }
+
+    #[test]
+    fn topology_rebalance_on_cpu_change() {
+        let mut topo = CpuTopology {
+            sockets: 2,
+            cores: 2,
+            threads: 2,
+        };
+        topo.rebalance(12);
+        assert_eq!((topo.sockets, topo.cores, topo.threads), (2, 3, 2));
+        topo.rebalance(5);
+        assert_eq!((topo.sockets, topo.cores, topo.threads), (1, 5, 1));
+    }
+
+    #[test]
+    fn enable_nested_adds_virt_flag_once() {
+        let mut cpu = CpuConfig::parse("qemu64,-vmx");
+        cpu.enable_nested("vmx");
+        assert_eq!(cpu.to_arg(), "host,+vmx");
+        cpu.enable_nested("vmx");
+        assert_eq!(cpu.to_arg(), "host,+vmx");
+
+        let mut cpu = CpuConfig::parse("max,svm=on");
+        cpu.enable_nested("svm");
+        assert_eq!(cpu.to_arg(), "max,svm=on");
+    }
+
+    #[test]
+    fn rtc_config_roundtrip() {
+        assert_eq!(RtcConfig::parse("base=utc").to_arg(), None);
+        assert_eq!(
+            RtcConfig::parse("base=localtime").to_arg().as_deref(),
+            Some("-rtc base=localtime")
+        );
+
+        let rtc = RtcConfig::parse("base=2006-06-17T16:01:21,clock=vm,driftfix=slew");
+        assert_eq!(rtc.base, RtcBase::Fixed("2006-06-17T16:01:21".to_string()));
+        assert_eq!(rtc.clock, RtcClock::Vm);
+        assert_eq!(
+            rtc.to_arg().as_deref(),
+            Some("-rtc base=2006-06-17T16:01:21,clock=vm,driftfix=slew")
+        );
+    }
+
+    #[test]
+    fn disk_throttle_roundtrip() {
+        let throttle = DiskThrottle::parse(
+            "-drive file=\"$DISK\",format=qcow2,if=virtio,throttling.iops-read=500,bps_wr=2097152 \\",
+        );
+        assert_eq!(throttle.iops_read, Some(500));
+        assert_eq!(throttle.bps_write, Some(2 * 1024 * 1024));
+        assert_eq!(throttle.iops_write, None);
+        assert_eq!(
+            throttle.drive_opts(),
+            ",throttling.iops-read=500,throttling.bps-write=2097152"
+        );
+        assert_eq!(throttle.describe(ThrottleField::BpsWrite), "2 MiB/s");
+        assert_eq!(throttle.describe(ThrottleField::IopsWrite), "unlimited");
+        assert!(DiskThrottle::is_throttle_opt("iops_rd=10"));
+        assert!(!DiskThrottle::is_throttle_opt("format=qcow2"));
+        assert!(DiskThrottle::parse("-drive file=a.qcow2,format=qcow2").is_unlimited());
+    }
+
+    #[test]
+    fn disk_throttle_input() {
+        assert_eq!(
+            DiskThrottle::parse_input(ThrottleField::BpsRead, " 40 "),
+            Ok(Some(40 * 1024 * 1024))
+        );
+        assert_eq!(
+            DiskThrottle::parse_input(ThrottleField::IopsRead, "250"),
+            Ok(Some(250))
+        );
+        assert_eq!(
+            DiskThrottle::parse_input(ThrottleField::IopsRead, ""),
+            Ok(None)
+        );
+        assert_eq!(
+            DiskThrottle::parse_input(ThrottleField::IopsRead, "0"),
+            Ok(None)
+        );
+        assert!(DiskThrottle::parse_input(ThrottleField::IopsRead, "-5").is_err());
+        assert!(DiskThrottle::parse_input(ThrottleField::BpsRead, &u64::MAX.to_string()).is_err());
+        assert_eq!(
+            DiskThrottle::parse_input(ThrottleField::BpsRead, "1572864B"),
+            Ok(Some(1572864))
+        );
+        assert!(DiskThrottle::parse_input(ThrottleField::IopsRead, "250B").is_err());
+    }
+
+    #[test]
+    fn disk_throttle_input_keeps_bandwidth_exact() {
+        let throttle = DiskThrottle::parse("-drive file=a.qcow2,bps_rd=1572864,bps_wr=2097152");
+        assert_eq!(throttle.input_value(ThrottleField::BpsRead), "1572864B");
+        assert_eq!(throttle.input_value(ThrottleField::BpsWrite), "2");
+        for field in [ThrottleField::BpsRead, ThrottleField::BpsWrite] {
+            assert_eq!(
+                DiskThrottle::parse_input(field, &throttle.input_value(field)),
+                Ok(throttle.get(field))
+            );
+        }
+    }
+
+    #[test]
+    fn network_shaping_netem_roundtrip() {
+        let shaping = NetworkShaping {
+            rate_kbit: Some(512),
+            delay_ms: Some(150),
+            loss_percent: Some(1.5),
+        };
+        assert_eq!(shaping.netem_args(), "delay 150ms loss 1.5% rate 512kbit");
+        assert_eq!(NetworkShaping::parse_netem(&shaping.netem_args()), shaping);
+        assert!(NetworkShaping::parse_netem("").is_off());
+        assert_eq!(shaping.describe(ShapingField::Rate), "512 kbit/s");
+        assert_eq!(
+            NetworkShaping::default().describe(ShapingField::Loss),
+            "off"
+        );
+    }
+
+    #[test]
+    fn network_shaping_input() {
+        let mut shaping = NetworkShaping::default();
+        shaping.set_input(ShapingField::Delay, " 200 ").unwrap();
+        shaping.set_input(ShapingField::Loss, "0.5").unwrap();
+        assert_eq!(shaping.delay_ms, Some(200));
+        assert_eq!(shaping.loss_percent, Some(0.5));
+        shaping.set_input(ShapingField::Delay, "0").unwrap();
+        assert_eq!(shaping.delay_ms, None);
+        assert!(shaping.set_input(ShapingField::Rate, "fast").is_err());
+        assert!(shaping.set_input(ShapingField::Rate, "-1").is_err());
+        assert!(shaping.set_input(ShapingField::Loss, "101").is_err());
+        assert_eq!(shaping.loss_percent, Some(0.5));
+    }
+
+    #[test]
+    fn apple_smc_args_round_trip() {
+        let script = format!(
+            "qemu-system-x86_64 \\\n    {}\n",
+            apple_smc_args().join(" \\\n    ")
+        );
+        assert!(script.contains("isa-applesmc,osk=ourhardwork"));
+        assert!(script.contains("-smbios type=2"));
+        assert!(has_apple_smc(&script));
+        assert!(!has_apple_smc(
+            "# -device isa-applesmc\nqemu-system-x86_64 -m 2G\n"
+        ));
+    }
+
+    #[test]
+    fn rtc_fixed_date_validation() {
+        assert!(RtcBase::is_valid_datetime("2006-06-17"));
+        assert!(RtcBase::is_valid_datetime("2006-06-17T16:01:21"));
+        assert!(!RtcBase::is_valid_datetime("2006-13-01"));
+        assert!(!RtcBase::is_valid_datetime("17/06/2006"));
+    }
 }
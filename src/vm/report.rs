@@ -0,0 +1,153 @@
+//! Bug report bundle for a VM
+//!
+//! Collects what issue reports ask for — the VM's launch.sh, its parsed
+//! configuration, the end of its launch log and the host hardware summary —
+//! into one text file. Home directory paths and passwords are redacted
+//! unless turned off in the config.
+
+use anyhow::{Context, Result};
+use std::path::{Path, PathBuf};
+
+use super::discovery::DiscoveredVm;
+use super::lifecycle::LAUNCH_LOG;
+
+/// Launch log lines kept in a report
+const LOG_TAIL_LINES: usize = 100;
+
+/// What [`redact`] removes
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Redaction {
+    /// Home directory written as `~` (`None` = leave paths alone)
+    pub home: Option<PathBuf>,
+    /// Blank out passwords, secrets and tokens
+    pub secrets: bool,
+}
+
+/// `text` with each whole-path use of `dir` replaced by `with`: `/home/al`
+/// matches `/home/al/disk.qcow2` but not `/home/alice` or `/mnt/home/al`
+fn replace_dir(text: &str, dir: &str, with: &str) -> String {
+    let in_path = |c: char| c.is_alphanumeric() || "._-/".contains(c);
+    let mut out = String::with_capacity(text.len());
+    let mut last = 0;
+    for (i, _) in text.match_indices(dir) {
+        let end = i + dir.len();
+        let starts_path = !text[..i].chars().next_back().is_some_and(in_path);
+        let ends_component = text[end..]
+            .chars()
+            .next()
+            .is_none_or(|c| c == '/' || !in_path(c));
+        if starts_path && ends_component {
+            out.push_str(&text[last..i]);
+            out.push_str(with);
+            last = end;
+        }
+    }
+    out.push_str(&text[last..]);
+    out
+}
+
+/// `text` with the home directory shortened to `~` and the values of
+/// password/secret/token options (`password=`, `SPICE_PASSWORD="..."`,
+/// `-object secret,...,data=`) replaced with `<redacted>`
+pub fn redact(text: &str, redaction: &Redaction) -> String {
+    let mut text = text.to_string();
+    if let Some(home) = redaction.home.as_ref().and_then(|h| h.to_str()) {
+        if !home.is_empty() && home != "/" {
+            text = replace_dir(&text, home.trim_end_matches('/'), "~");
+        }
+    }
+    if !redaction.secrets {
+        return text;
+    }
+    let secret = regex::Regex::new(
+        r#"(?i)(\b[\w-]*(?:password|passwd|secret|token)[\w-]*=)("[^"]*"|'[^']*'|[^,\s"'\\]+)"#,
+    )
+    .expect("valid regex");
+    let data = regex::Regex::new(r#"(\bdata=)("[^"]*"|'[^']*'|[^,\s"'\\]+)"#).expect("valid regex");
+    let mut out: Vec<String> = Vec::new();
+    for line in text.lines() {
+        let mut line = secret.replace_all(line, "$1<redacted>").to_string();
+        // `-object secret,id=...,data=...` carries the secret itself
+        if line.contains("secret,") {
+            line = data.replace_all(&line, "$1<redacted>").to_string();
+        }
+        out.push(line);
+    }
+    let mut result = out.join("\n");
+    if text.ends_with('\n') {
+        result.push('\n');
+    }
+    result
+}
+
+/// The last `lines` lines of a VM's launch log, if it has one
+fn launch_log_tail(vm_path: &Path, lines: usize) -> Option<String> {
+    let log = std::fs::read_to_string(vm_path.join(LAUNCH_LOG)).ok()?;
+    let all: Vec<&str> = log.lines().collect();
+    Some(all[all.len().saturating_sub(lines)..].join("\n"))
+}
+
+/// Build a redacted bug report for `vm`. `host_info` is the plain-text host
+/// summary (see `hardware::HostInfo::to_text`).
+pub fn bug_report(vm: &DiscoveredVm, host_info: &str, redaction: &Redaction) -> String {
+    let script =
+        std::fs::read_to_string(&vm.launch_script).unwrap_or_else(|_| vm.config.raw_script.clone());
+    // The script is already in the report once
+    let config = serde_json::to_value(&vm.config)
+        .map(|mut value| {
+            if let Some(map) = value.as_object_mut() {
+                map.remove("raw_script");
+            }
+            serde_json::to_string_pretty(&value).unwrap_or_default()
+        })
+        .unwrap_or_default();
+
+    let mut report = format!(
+        "# vm-curator bug report\n\nGenerated: {}\n\n## VM\n\nName:       {}\nID:         {}\nPath:       {}\nOS profile: {}\n",
+        chrono::Local::now().format("%Y-%m-%d %H:%M:%S"),
+        vm.display_name(),
+        vm.id,
+        vm.path.display(),
+        vm.os_profile.as_deref().unwrap_or("none"),
+    );
+    if !vm.unrecognized_args.is_empty() {
+        report.push_str(&format!(
+            "Unrecognized arguments: {}\n",
+            vm.unrecognized_args.join(" ")
+        ));
+    }
+    report.push_str(&format!("\n## Host\n\n{}\n", host_info.trim_end()));
+    report.push_str(&format!(
+        "\n## launch.sh\n\n```bash\n{}\n```\n",
+        script.trim_end()
+    ));
+    report.push_str(&format!(
+        "\n## Parsed configuration\n\n```json\n{}\n```\n",
+        config
+    ));
+    report.push_str(&format!(
+        "\n## {} (last {} lines)\n\n",
+        LAUNCH_LOG, LOG_TAIL_LINES
+    ));
+    match launch_log_tail(&vm.path, LOG_TAIL_LINES) {
+        Some(tail) => report.push_str(&format!("```\n{}\n```\n", tail)),
+        None => report.push_str("No launch log.\n"),
+    }
+    redact(&report, redaction)
+}
+
+/// Write a report to `dir` as `bug-report-<vm id>-<timestamp>.txt`
+pub fn save_bug_report(dir: &Path, vm_id: &str, report: &str) -> Result<PathBuf> {
+    std::fs::create_dir_all(dir).with_context(|| format!("Failed to create {}", dir.display()))?;
+    let path = dir.join(format!(
+        "bug-report-{}-{}.txt",
+        vm_id,
+        chrono::Local::now().format("%Y%m%d-%H%M%S")
+    ));
+    std::fs::write(&path, report).with_context(|| format!("Failed to write {}", path.display()))?;
+    Ok(path)
+}
+
+#[cfg(test)]
+#[path = "tests/report.rs"]
+mod tests;
@@ -91,6 +91,10 @@ pub struct GeneratedScripts {
     pub start_script: PathBuf,
     /// Path to the restore script
     pub restore_script: PathBuf,
+    /// Path to the standalone vfio-pci bind script (sourced by the start script)
+    pub bind_script: PathBuf,
+    /// Path to the standalone vfio-pci unbind script (sourced by start/restore)
+    pub unbind_script: PathBuf,
 }
 
 /// Components extracted from the VM's launch.sh script
@@ -218,6 +222,16 @@ pub fn generate_single_gpu_scripts(
     crate::hardware::save_config(vm_dir, config)
         .with_context(|| "Failed to save single-GPU config")?;
 
+    // Generate the bind/unbind helpers first; the start and restore scripts
+    // source them from the VM directory
+    let bind_content = generate_bind_script(vm, config);
+    let bind_path = vm_dir.join("bind.sh");
+    write_executable_script(&bind_path, &bind_content)?;
+
+    let unbind_content = generate_unbind_script(vm, config);
+    let unbind_path = vm_dir.join("unbind.sh");
+    write_executable_script(&unbind_path, &unbind_content)?;
+
     // Generate the start script
     let start_content = generate_start_script(vm, config)?;
     let start_path = vm_dir.join("single-gpu-start.sh");
@@ -234,6 +248,8 @@ pub fn generate_single_gpu_scripts(
     Ok(GeneratedScripts {
         start_script: start_path,
         restore_script: restore_path,
+        bind_script: bind_path,
+        unbind_script: unbind_path,
     })
 }
 
@@ -266,18 +282,6 @@ fn generate_start_script(vm: &DiscoveredVm, config: &SingleGpuConfig) -> Result<
         .with_context(|| format!("Failed to read launch script: {:?}", vm.launch_script))?;
     let components = parse_launch_script(&launch_script);
 
-    // Get dependent modules to unload
-    let modules_to_unload = config.original_driver.dependent_modules();
-    let unload_modules_cmd = if !modules_to_unload.is_empty() {
-        modules_to_unload
-            .iter()
-            .map(|m| format!("    modprobe -r {} 2>/dev/null || true", m))
-            .collect::<Vec<_>>()
-            .join("\n")
-    } else {
-        "    # No additional modules to unload".to_string()
-    };
-
     // Load USB passthrough from launch.sh
     let usb_devices = load_usb_passthrough(vm);
     let usb_passthrough_args = generate_usb_passthrough_args(&usb_devices);
@@ -285,21 +289,7 @@ fn generate_start_script(vm: &DiscoveredVm, config: &SingleGpuConfig) -> Result<
     // Load PCI passthrough from launch.sh (network cards, USB controllers, etc.)
     let pci_passthrough_args = load_pci_passthrough(vm);
 
-    // Extract extra PCI addresses for binding (NICs, USB controllers, NVMe, etc.)
-    let extra_pci_addrs =
-        filter_bindable_pci_addresses(extract_pci_addresses(&pci_passthrough_args));
-    let extra_pci_addrs_str = if extra_pci_addrs.is_empty() {
-        "EXTRA_PCI_ADDRS=()".to_string()
-    } else {
-        format!(
-            "EXTRA_PCI_ADDRS=({})",
-            extra_pci_addrs
-                .iter()
-                .map(|a| format!("\"{}\"", a))
-                .collect::<Vec<_>>()
-                .join(" ")
-        )
-    };
+    let extra_pci_addrs_str = extra_pci_addrs_line(&pci_passthrough_args);
 
     // Build QEMU command from existing launch.sh
     let qemu_command = extract_qemu_command_for_passthrough(
@@ -342,27 +332,6 @@ start_tpm
         String::new()
     };
 
-    // Generate cleanup for NVIDIA module loading
-    let nvidia_module_load = if original_driver == "nvidia" {
-        r#"
-    # Load NVIDIA modules in dependency order
-    modprobe nvidia 2>/dev/null || true
-    sleep 1
-    modprobe nvidia_modeset 2>/dev/null || true
-    sleep 0.5
-    modprobe nvidia_drm 2>/dev/null || true
-    modprobe nvidia_uvm 2>/dev/null || true
-    sleep 1"#
-            .to_string()
-    } else {
-        format!(
-            r#"
-    modprobe "{}" 2>/dev/null || true
-    sleep 1"#,
-            original_driver
-        )
-    };
-
     let script = format!(
         r#"#!/bin/bash
 # Single GPU Passthrough Start Script
@@ -420,46 +389,15 @@ if ! modinfo vfio_pci &>/dev/null; then
 fi
 {tpm_functions}
 # ============================================================================
-# Cleanup Function
+# VFIO Bind/Unbind (bind.sh and unbind.sh can also be run on their own)
 # ============================================================================
 
-# Return the GPU to its original driver via PCI remove+rescan
-rebind_gpu() {{
-    # Unbind from vfio-pci using PCI remove+rescan pattern
-    if [[ -e "/sys/bus/pci/devices/$GPU_ADDR" ]]; then
-        echo "Removing GPU from PCI bus..."
-        echo 1 > /sys/bus/pci/devices/$GPU_ADDR/remove 2>/dev/null || true
-    fi
-    if [[ -n "$AUDIO_ADDR" ]] && [[ -e "/sys/bus/pci/devices/$AUDIO_ADDR" ]]; then
-        echo 1 > /sys/bus/pci/devices/$AUDIO_ADDR/remove 2>/dev/null || true
-    fi
-
-    # Remove extra PCI devices from bus (will be re-bound on rescan)
-    for addr in "${{EXTRA_PCI_ADDRS[@]}}"; do
-        if [[ -e "/sys/bus/pci/devices/$addr" ]]; then
-            echo 1 > /sys/bus/pci/devices/$addr/remove 2>/dev/null || true
-        fi
-    done
-    sleep 2
-
-    # Rescan PCI bus
-    echo "Rescanning PCI bus..."
-    echo 1 > /sys/bus/pci/rescan
-    sleep 3
-
-    # Unload VFIO modules
-    modprobe -r vfio_pci 2>/dev/null || true
-    modprobe -r vfio_iommu_type1 2>/dev/null || true
-    modprobe -r vfio 2>/dev/null || true
-    sleep 1
-{nvidia_module_load}
+source "$VM_DIR/bind.sh"
+source "$VM_DIR/unbind.sh"
 
-    # Manual bind fallback if GPU doesn't auto-bind
-    if [[ -e "/sys/bus/pci/devices/$GPU_ADDR" ]] && [[ ! -e "/sys/bus/pci/devices/$GPU_ADDR/driver" ]]; then
-        echo "Manual bind to $ORIGINAL_DRIVER..."
-        echo "$GPU_ADDR" > /sys/bus/pci/drivers/$ORIGINAL_DRIVER/bind 2>/dev/null || true
-    fi
-}}
+# ============================================================================
+# Cleanup Function
+# ============================================================================
 
 cleanup() {{
     local exit_code=$?
@@ -480,16 +418,11 @@ cleanup() {{
     if [[ "$gpu_driver" == "$ORIGINAL_DRIVER" ]]; then
         echo "GPU is still bound to $ORIGINAL_DRIVER; skipping PCI rebind."
     else
-        rebind_gpu
+        vfio_unbind
     fi
 
     # Reattach the EFI framebuffer and virtual consoles (issue #61)
-    echo "efi-framebuffer.0" > /sys/bus/platform/drivers/efi-framebuffer/bind 2>/dev/null || true
-    for vtcon in /sys/class/vtconsole/vtcon*; do
-        if grep -q "frame buffer" "$vtcon/name" 2>/dev/null; then
-            echo 1 > "$vtcon/bind" 2>/dev/null || true
-        fi
-    done
+    reattach_consoles
 
     # Restart display manager
     echo "Starting display manager..."
@@ -527,82 +460,9 @@ for proc in Xorg Xwayland gnome-shell kwin_wayland plasmashell sway hyprland; do
 done
 sleep 2
 
-# Detach virtual consoles from the GPU framebuffer. Unloading the GPU driver
-# while fbcon still renders the active TTY through it can hard-hang or even
-# power off the machine — especially on AMD APUs (issue #61).
-echo "Detaching virtual consoles from GPU framebuffer..."
-for vtcon in /sys/class/vtconsole/vtcon*; do
-    if grep -q "frame buffer" "$vtcon/name" 2>/dev/null; then
-        echo 0 > "$vtcon/bind" 2>/dev/null || true
-    fi
-done
-
-# Unbind the generic EFI/simple framebuffer if still present
-echo "efi-framebuffer.0" > /sys/bus/platform/drivers/efi-framebuffer/unbind 2>/dev/null || true
-echo "simple-framebuffer.0" > /sys/bus/platform/drivers/simple-framebuffer/unbind 2>/dev/null || true
-sleep 1
-
-# Unload driver modules
-{unload_modules_cmd}
-
-# The GPU driver must have released the device by now. Force-unbinding a
-# driver that is still in use can hard-hang or power off the machine
-# (issue #61), so abort gracefully instead — the cleanup trap restores
-# the display.
-if [[ -e "/sys/bus/pci/devices/$GPU_ADDR/driver" ]]; then
-    current_driver=$(basename "$(readlink "/sys/bus/pci/devices/$GPU_ADDR/driver")")
-    if [[ "$current_driver" != "vfio-pci" ]]; then
-        echo "ERROR: GPU is still bound to '$current_driver' — driver did not unload."
-        echo "Something is still using the GPU. Aborting and restoring the display."
-        exit 1
-    fi
-fi
-
-if [[ -n "$AUDIO_ADDR" ]] && [[ -e "/sys/bus/pci/devices/$AUDIO_ADDR/driver" ]]; then
-    current_driver=$(basename $(readlink /sys/bus/pci/devices/$AUDIO_ADDR/driver))
-    if [[ "$current_driver" != "vfio-pci" ]]; then
-        echo "$AUDIO_ADDR" > /sys/bus/pci/drivers/$current_driver/unbind 2>/dev/null || true
-    fi
-fi
-
-# ============================================================================
-# Bind to VFIO
-# ============================================================================
-
-echo "Binding GPU to vfio-pci..."
-
-modprobe vfio_pci
-
-# Set driver override and bind
-echo "vfio-pci" > /sys/bus/pci/devices/$GPU_ADDR/driver_override
-echo "$GPU_ADDR" > /sys/bus/pci/drivers/vfio-pci/bind
-
-if [[ -n "$AUDIO_ADDR" ]]; then
-    echo "vfio-pci" > /sys/bus/pci/devices/$AUDIO_ADDR/driver_override
-    echo "$AUDIO_ADDR" > /sys/bus/pci/drivers/vfio-pci/bind
-fi
-
-# Bind extra PCI devices (network cards, USB controllers, NVMe, etc.)
-for addr in "${{EXTRA_PCI_ADDRS[@]}}"; do
-    echo "Binding $addr to vfio-pci..."
-    # Unbind from current driver if bound
-    if [[ -e "/sys/bus/pci/devices/$addr/driver" ]]; then
-        current_driver=$(basename $(readlink /sys/bus/pci/devices/$addr/driver))
-        if [[ "$current_driver" != "vfio-pci" ]]; then
-            echo "$addr" > /sys/bus/pci/drivers/$current_driver/unbind 2>/dev/null || true
-        fi
-    fi
-    echo "vfio-pci" > /sys/bus/pci/devices/$addr/driver_override
-    echo "$addr" > /sys/bus/pci/drivers/vfio-pci/bind
-done
-
-# Verify binding
-if [[ ! -e "/sys/bus/pci/drivers/vfio-pci/$GPU_ADDR" ]]; then
-    echo "ERROR: Failed to bind GPU to vfio-pci"
-    exit 1
-fi
-
-echo "GPU successfully bound to vfio-pci"
+# Detach consoles, unload the driver and bind to vfio-pci (bind.sh). A
+# failure exits via set -e and the cleanup trap restores the display.
+vfio_bind
 {tpm_start}
 # ============================================================================
 # Start VM
@@ -643,8 +503,6 @@ echo "VM has exited."
         } else {
             ""
         },
-        nvidia_module_load = nvidia_module_load,
-        unload_modules_cmd = unload_modules_cmd,
         tpm_start = tpm_start,
         qemu_command = qemu_command,
     );
@@ -746,6 +604,282 @@ start_tpm() {{
     )
 }
 
+/// Build the `EXTRA_PCI_ADDRS=(...)` shell array for the extra PCI devices
+/// (NICs, USB controllers, NVMe, etc.) passed through alongside the GPU
+fn extra_pci_addrs_line(pci_passthrough_args: &[String]) -> String {
+    let extra_pci_addrs =
+        filter_bindable_pci_addresses(extract_pci_addresses(pci_passthrough_args));
+    if extra_pci_addrs.is_empty() {
+        "EXTRA_PCI_ADDRS=()".to_string()
+    } else {
+        format!(
+            "EXTRA_PCI_ADDRS=({})",
+            extra_pci_addrs
+                .iter()
+                .map(|a| format!("\"{}\"", a))
+                .collect::<Vec<_>>()
+                .join(" ")
+        )
+    }
+}
+
+/// Generate bind.sh: detaches the host from the GPU and binds it (plus audio
+/// and extra PCI devices) to vfio-pci. The start script sources it and calls
+/// `vfio_bind`; running it directly as root performs the bind on its own.
+fn generate_bind_script(vm: &DiscoveredVm, config: &SingleGpuConfig) -> String {
+    let gpu_addr = &config.gpu.address;
+    let audio_addr = config
+        .audio
+        .as_ref()
+        .map(|a| a.address.as_str())
+        .unwrap_or("");
+    let original_driver = config.original_driver.module_name();
+    let extra_pci_addrs_str = extra_pci_addrs_line(&load_pci_passthrough(vm));
+
+    // Get dependent modules to unload
+    let modules_to_unload = config.original_driver.dependent_modules();
+    let unload_modules_cmd = if !modules_to_unload.is_empty() {
+        modules_to_unload
+            .iter()
+            .map(|m| format!("    modprobe -r {} 2>/dev/null || true", m))
+            .collect::<Vec<_>>()
+            .join("\n")
+    } else {
+        "    # No additional modules to unload".to_string()
+    };
+
+    format!(
+        r#"#!/bin/bash
+# Single GPU Passthrough: bind GPU to vfio-pci
+# Generated by vm-curator for: {vm_name}
+#
+# Sourced by single-gpu-start.sh, which calls vfio_bind. It can also be run
+# on its own as root (with the display manager already stopped) to test or
+# debug the bind step, and reused from custom hooks. Undo with unbind.sh.
+
+GPU_ADDR="{gpu_addr}"
+AUDIO_ADDR="{audio_addr}"
+ORIGINAL_DRIVER="{original_driver}"
+{extra_pci_addrs}
+
+vfio_bind() {{
+    # Detach virtual consoles from the GPU framebuffer. Unloading the GPU driver
+    # while fbcon still renders the active TTY through it can hard-hang or even
+    # power off the machine — especially on AMD APUs (issue #61).
+    echo "Detaching virtual consoles from GPU framebuffer..."
+    for vtcon in /sys/class/vtconsole/vtcon*; do
+        if grep -q "frame buffer" "$vtcon/name" 2>/dev/null; then
+            echo 0 > "$vtcon/bind" 2>/dev/null || true
+        fi
+    done
+
+    # Unbind the generic EFI/simple framebuffer if still present
+    echo "efi-framebuffer.0" > /sys/bus/platform/drivers/efi-framebuffer/unbind 2>/dev/null || true
+    echo "simple-framebuffer.0" > /sys/bus/platform/drivers/simple-framebuffer/unbind 2>/dev/null || true
+    sleep 1
+
+    # Unload driver modules
+{unload_modules_cmd}
+
+    # The GPU driver must have released the device by now. Force-unbinding a
+    # driver that is still in use can hard-hang or power off the machine
+    # (issue #61), so fail gracefully instead — the caller restores the display.
+    if [[ -e "/sys/bus/pci/devices/$GPU_ADDR/driver" ]]; then
+        local current_driver
+        current_driver=$(basename "$(readlink "/sys/bus/pci/devices/$GPU_ADDR/driver")")
+        if [[ "$current_driver" != "vfio-pci" ]]; then
+            echo "ERROR: GPU is still bound to '$current_driver' — driver did not unload."
+            echo "Something is still using the GPU. Aborting and restoring the display."
+            return 1
+        fi
+    fi
+
+    if [[ -n "$AUDIO_ADDR" ]] && [[ -e "/sys/bus/pci/devices/$AUDIO_ADDR/driver" ]]; then
+        local audio_driver
+        audio_driver=$(basename "$(readlink "/sys/bus/pci/devices/$AUDIO_ADDR/driver")")
+        if [[ "$audio_driver" != "vfio-pci" ]]; then
+            echo "$AUDIO_ADDR" > /sys/bus/pci/drivers/$audio_driver/unbind 2>/dev/null || true
+        fi
+    fi
+
+    echo "Binding GPU to vfio-pci..."
+
+    modprobe vfio_pci
+
+    # Set driver override and bind
+    echo "vfio-pci" > /sys/bus/pci/devices/$GPU_ADDR/driver_override
+    echo "$GPU_ADDR" > /sys/bus/pci/drivers/vfio-pci/bind
+
+    if [[ -n "$AUDIO_ADDR" ]]; then
+        echo "vfio-pci" > /sys/bus/pci/devices/$AUDIO_ADDR/driver_override
+        echo "$AUDIO_ADDR" > /sys/bus/pci/drivers/vfio-pci/bind
+    fi
+
+    # Bind extra PCI devices (network cards, USB controllers, NVMe, etc.)
+    for addr in "${{EXTRA_PCI_ADDRS[@]}}"; do
+        echo "Binding $addr to vfio-pci..."
+        # Unbind from current driver if bound
+        if [[ -e "/sys/bus/pci/devices/$addr/driver" ]]; then
+            local dev_driver
+            dev_driver=$(basename "$(readlink "/sys/bus/pci/devices/$addr/driver")")
+            if [[ "$dev_driver" != "vfio-pci" ]]; then
+                echo "$addr" > /sys/bus/pci/drivers/$dev_driver/unbind 2>/dev/null || true
+            fi
+        fi
+        echo "vfio-pci" > /sys/bus/pci/devices/$addr/driver_override
+        echo "$addr" > /sys/bus/pci/drivers/vfio-pci/bind
+    done
+
+    # Verify binding
+    if [[ ! -e "/sys/bus/pci/drivers/vfio-pci/$GPU_ADDR" ]]; then
+        echo "ERROR: Failed to bind GPU to vfio-pci"
+        return 1
+    fi
+
+    echo "GPU successfully bound to vfio-pci"
+}}
+
+# Run directly (not sourced): bind now
+if [[ "${{BASH_SOURCE[0]}}" == "$0" ]]; then
+    set -e
+    if [[ $EUID -ne 0 ]]; then
+        echo "This script must be run as root (use sudo)"
+        exit 1
+    fi
+    vfio_bind
+fi
+"#,
+        vm_name = vm.display_name(),
+        gpu_addr = gpu_addr,
+        audio_addr = audio_addr,
+        original_driver = original_driver,
+        extra_pci_addrs = extra_pci_addrs_str,
+        unload_modules_cmd = unload_modules_cmd,
+    )
+}
+
+/// Generate unbind.sh: returns the GPU (and audio/extra PCI devices) from
+/// vfio-pci to the host driver via PCI remove+rescan and reattaches the
+/// framebuffer consoles. Sourced by the start and restore scripts; running it
+/// directly as root performs both steps.
+fn generate_unbind_script(vm: &DiscoveredVm, config: &SingleGpuConfig) -> String {
+    let gpu_addr = &config.gpu.address;
+    let audio_addr = config
+        .audio
+        .as_ref()
+        .map(|a| a.address.as_str())
+        .unwrap_or("");
+    let original_driver = config.original_driver.module_name();
+    let extra_pci_addrs_str = extra_pci_addrs_line(&load_pci_passthrough(vm));
+
+    let driver_load = if original_driver == "nvidia" {
+        r#"        # Load NVIDIA modules in dependency order
+        echo "Loading NVIDIA modules..."
+        modprobe nvidia 2>/dev/null || true
+        sleep 1
+        modprobe nvidia_modeset 2>/dev/null || true
+        sleep 0.5
+        modprobe nvidia_drm 2>/dev/null || true
+        modprobe nvidia_uvm 2>/dev/null || true
+        sleep 1"#
+            .to_string()
+    } else {
+        format!(
+            r#"        echo "Loading {0} driver..."
+        modprobe "{0}" 2>/dev/null || true
+        sleep 2"#,
+            original_driver
+        )
+    };
+
+    format!(
+        r#"#!/bin/bash
+# Single GPU Passthrough: return GPU from vfio-pci to the host
+# Generated by vm-curator for: {vm_name}
+#
+# Sourced by single-gpu-start.sh (on VM exit) and single-gpu-restore.sh, which
+# call vfio_unbind and reattach_consoles. It can also be run on its own as root
+# to test or debug the unbind step, and reused from custom hooks.
+
+GPU_ADDR="{gpu_addr}"
+AUDIO_ADDR="{audio_addr}"
+ORIGINAL_DRIVER="{original_driver}"
+{extra_pci_addrs}
+
+# Return the GPU to its original driver via PCI remove+rescan
+# (more reliable than a plain unbind)
+vfio_unbind() {{
+    if [[ -e "/sys/bus/pci/devices/$GPU_ADDR" ]]; then
+        echo "Removing GPU from PCI bus..."
+        echo 1 > /sys/bus/pci/devices/$GPU_ADDR/remove 2>/dev/null || true
+    fi
+    if [[ -n "$AUDIO_ADDR" ]] && [[ -e "/sys/bus/pci/devices/$AUDIO_ADDR" ]]; then
+        echo 1 > /sys/bus/pci/devices/$AUDIO_ADDR/remove 2>/dev/null || true
+    fi
+
+    # Remove extra PCI devices from bus (will be re-bound on rescan)
+    for addr in "${{EXTRA_PCI_ADDRS[@]}}"; do
+        if [[ -e "/sys/bus/pci/devices/$addr" ]]; then
+            echo "Removing $addr from PCI bus..."
+            echo 1 > /sys/bus/pci/devices/$addr/remove 2>/dev/null || true
+        fi
+    done
+    sleep 2
+
+    # Rescan PCI bus
+    echo "Rescanning PCI bus..."
+    echo 1 > /sys/bus/pci/rescan
+    sleep 3
+
+    # Unload VFIO modules
+    echo "Unloading VFIO modules..."
+    modprobe -r vfio_pci 2>/dev/null || true
+    modprobe -r vfio_iommu_type1 2>/dev/null || true
+    modprobe -r vfio 2>/dev/null || true
+    sleep 1
+
+    # Load original driver
+    if [[ -n "$ORIGINAL_DRIVER" ]] && [[ "$ORIGINAL_DRIVER" != "vfio-pci" ]]; then
+{driver_load}
+
+        # Manual bind fallback if GPU doesn't auto-bind
+        if [[ -e "/sys/bus/pci/devices/$GPU_ADDR" ]] && [[ ! -e "/sys/bus/pci/devices/$GPU_ADDR/driver" ]]; then
+            echo "Manual bind to $ORIGINAL_DRIVER..."
+            echo "$GPU_ADDR" > /sys/bus/pci/drivers/$ORIGINAL_DRIVER/bind 2>/dev/null || true
+        fi
+    fi
+}}
+
+# Reattach the EFI framebuffer and virtual consoles (issue #61)
+reattach_consoles() {{
+    echo "efi-framebuffer.0" > /sys/bus/platform/drivers/efi-framebuffer/bind 2>/dev/null || true
+    for vtcon in /sys/class/vtconsole/vtcon*; do
+        if grep -q "frame buffer" "$vtcon/name" 2>/dev/null; then
+            echo 1 > "$vtcon/bind" 2>/dev/null || true
+        fi
+    done
+}}
+
+# Run directly (not sourced): unbind now
+if [[ "${{BASH_SOURCE[0]}}" == "$0" ]]; then
+    set -e
+    if [[ $EUID -ne 0 ]]; then
+        echo "This script must be run as root (use sudo)"
+        exit 1
+    fi
+    vfio_unbind
+    reattach_consoles
+fi
+"#,
+        vm_name = vm.display_name(),
+        gpu_addr = gpu_addr,
+        audio_addr = audio_addr,
+        original_driver = original_driver,
+        extra_pci_addrs = extra_pci_addrs_str,
+        driver_load = driver_load,
+    )
+}
+
 /// Extract PCI addresses from passthrough args (e.g., "-device vfio-pci,host=0000:47:00.0")
 fn extract_pci_addresses(pci_args: &[String]) -> Vec<String> {
     pci_args
@@ -816,17 +950,8 @@ fn generate_usb_passthrough_args(devices: &[crate::vm::UsbPassthrough]) -> Strin
 
     // Add each USB device, attaching USB 3.0 devices to xHCI controller
     for dev in devices {
-        if dev.is_usb3() {
-            args.push(format!(
-                "-device usb-host,bus=xhci.0,vendorid=0x{:04x},productid=0x{:04x}",
-                dev.vendor_id, dev.product_id
-            ));
-        } else {
-            args.push(format!(
-                "-device usb-host,vendorid=0x{:04x},productid=0x{:04x}",
-                dev.vendor_id, dev.product_id
-            ));
-        }
+        let bus = dev.is_usb3().then_some("xhci.0");
+        args.push(format!("-device {}", dev.device_spec(bus)));
     }
     args.join(" \\\n    ")
 }
@@ -849,21 +974,7 @@ fn generate_restore_script(vm: &DiscoveredVm, config: &SingleGpuConfig) -> Strin
     let components = parse_launch_script(&launch_script);
 
     // Load PCI passthrough for extra devices
-    let pci_passthrough_args = load_pci_passthrough(vm);
-    let extra_pci_addrs =
-        filter_bindable_pci_addresses(extract_pci_addresses(&pci_passthrough_args));
-    let extra_pci_addrs_str = if extra_pci_addrs.is_empty() {
-        "EXTRA_PCI_ADDRS=()".to_string()
-    } else {
-        format!(
-            "EXTRA_PCI_ADDRS=({})",
-            extra_pci_addrs
-                .iter()
-                .map(|a| format!("\"{}\"", a))
-                .collect::<Vec<_>>()
-                .join(" ")
-        )
-    };
+    let extra_pci_addrs_str = extra_pci_addrs_line(&load_pci_passthrough(vm));
 
     let tpm_cleanup = if components.has_tpm {
         let tpm_dir = components.tpm_dir.as_deref().unwrap_or("$VM_DIR/tpm");
@@ -879,28 +990,6 @@ pkill -f "swtpm.*$TPM_DIR" 2>/dev/null || true
         String::new()
     };
 
-    let nvidia_module_load = if original_driver == "nvidia" {
-        r#"
-    # Load NVIDIA modules in dependency order
-    echo "Loading NVIDIA modules..."
-    modprobe nvidia 2>/dev/null || true
-    sleep 1
-    modprobe nvidia_modeset 2>/dev/null || true
-    sleep 0.5
-    modprobe nvidia_drm 2>/dev/null || true
-    modprobe nvidia_uvm 2>/dev/null || true
-    sleep 1"#
-            .to_string()
-    } else {
-        format!(
-            r#"
-    echo "Loading {} driver..."
-    modprobe "{}" 2>/dev/null || true
-    sleep 2"#,
-            original_driver, original_driver
-        )
-    };
-
     format!(
         r#"#!/bin/bash
 # Single GPU Passthrough Restore Script
@@ -931,54 +1020,10 @@ echo "Restoring display..."
 echo "Killing any QEMU processes..."
 pkill -f "qemu.*$VM_NAME" 2>/dev/null || true
 {tpm_cleanup}
-# Remove and rescan PCI devices (more reliable than unbind)
-echo "Removing GPU from PCI bus..."
-if [[ -e "/sys/bus/pci/devices/$GPU_ADDR" ]]; then
-    echo 1 > /sys/bus/pci/devices/$GPU_ADDR/remove 2>/dev/null || true
-fi
-if [[ -n "$AUDIO_ADDR" ]] && [[ -e "/sys/bus/pci/devices/$AUDIO_ADDR" ]]; then
-    echo 1 > /sys/bus/pci/devices/$AUDIO_ADDR/remove 2>/dev/null || true
-fi
-
-# Remove extra PCI devices from bus (will be re-bound on rescan)
-for addr in "${{EXTRA_PCI_ADDRS[@]}}"; do
-    if [[ -e "/sys/bus/pci/devices/$addr" ]]; then
-        echo "Removing $addr from PCI bus..."
-        echo 1 > /sys/bus/pci/devices/$addr/remove 2>/dev/null || true
-    fi
-done
-sleep 2
-
-# Rescan PCI bus
-echo "Rescanning PCI bus..."
-echo 1 > /sys/bus/pci/rescan
-sleep 3
-
-# Unload VFIO modules
-echo "Unloading VFIO modules..."
-modprobe -r vfio_pci 2>/dev/null || true
-modprobe -r vfio_iommu_type1 2>/dev/null || true
-modprobe -r vfio 2>/dev/null || true
-sleep 1
-
-# Load original driver
-if [[ -n "$ORIGINAL_DRIVER" ]] && [[ "$ORIGINAL_DRIVER" != "vfio-pci" ]]; then
-{nvidia_module_load}
-
-    # Manual bind fallback if GPU doesn't auto-bind
-    if [[ -e "/sys/bus/pci/devices/$GPU_ADDR" ]] && [[ ! -e "/sys/bus/pci/devices/$GPU_ADDR/driver" ]]; then
-        echo "Manual bind to $ORIGINAL_DRIVER..."
-        echo "$GPU_ADDR" > /sys/bus/pci/drivers/$ORIGINAL_DRIVER/bind 2>/dev/null || true
-    fi
-fi
-
-# Reattach the EFI framebuffer and virtual consoles (issue #61)
-echo "efi-framebuffer.0" > /sys/bus/platform/drivers/efi-framebuffer/bind 2>/dev/null || true
-for vtcon in /sys/class/vtconsole/vtcon*; do
-    if grep -q "frame buffer" "$vtcon/name" 2>/dev/null; then
-        echo 1 > "$vtcon/bind" 2>/dev/null || true
-    fi
-done
+# Return the GPU to its original driver and reattach consoles (unbind.sh)
+source "$VM_DIR/unbind.sh"
+vfio_unbind
+reattach_consoles
 
 # Restart display manager
 echo "Starting display manager..."
@@ -996,7 +1041,6 @@ echo "If display still doesn't work, try rebooting."
         display_manager = display_manager,
         extra_pci_addrs = extra_pci_addrs_str,
         tpm_cleanup = tpm_cleanup,
-        nvidia_module_load = nvidia_module_load,
     )
 }
 
@@ -1517,7 +1561,12 @@ fn generate_basic_qemu_command(
 
 /// Delete single GPU scripts for a VM
 pub fn delete_scripts(vm_path: &Path) -> Result<()> {
-    let scripts = ["single-gpu-start.sh", "single-gpu-restore.sh"];
+    let scripts = [
+        "single-gpu-start.sh",
+        "single-gpu-restore.sh",
+        "bind.sh",
+        "unbind.sh",
+    ];
 
     for script in scripts {
         let path = vm_path.join(script);
@@ -1713,18 +1762,25 @@ mod tests {
             custom_name: None,
             os_profile: None,
             notes: None,
+            unrecognized_args: Vec::new(),
+            last_launched: None,
+            launch_count: 0,
+            autostart: false,
+            first_boot_done: None,
+            custom_ascii: None,
+            shared_disks: Vec::new(),
         }
     }
 
-    /// Regression test for issue #61: the start script must detach fbcon and the
+    /// Regression test for issue #61: the bind step must detach fbcon and the
     /// generic framebuffers before unloading the GPU driver — unloading while
     /// fbcon still renders the active TTY through the GPU can hard-hang or power
     /// off the host, especially on AMD APUs.
     #[test]
-    fn start_script_detaches_consoles_before_driver_unload() {
+    fn bind_script_detaches_consoles_before_driver_unload() {
         let tmp = tempfile::tempdir().unwrap();
         let vm = test_vm(tmp.path());
-        let script = generate_start_script(&vm, &amd_gpu_config(None)).unwrap();
+        let script = generate_bind_script(&vm, &amd_gpu_config(None));
 
         let detach = script
             .find("echo 0 > \"$vtcon/bind\"")
@@ -1745,14 +1801,18 @@ mod tests {
     fn start_script_aborts_instead_of_force_unbinding_gpu() {
         let tmp = tempfile::tempdir().unwrap();
         let vm = test_vm(tmp.path());
-        let script = generate_start_script(&vm, &amd_gpu_config(None)).unwrap();
+        let cfg = amd_gpu_config(None);
 
-        assert!(script.contains("driver did not unload"));
-        assert!(
-            !script.contains("echo \"$GPU_ADDR\" > /sys/bus/pci/drivers/$current_driver/unbind")
-        );
-        // The abort path must also skip the PCI remove/rescan teardown in cleanup.
-        assert!(script.contains("skipping PCI rebind"));
+        let bind = generate_bind_script(&vm, &cfg);
+        assert!(bind.contains("driver did not unload"));
+        assert!(!bind.contains("echo \"$GPU_ADDR\" > /sys/bus/pci/drivers/$current_driver/unbind"));
+
+        // vfio_bind runs under set -e so its failure aborts the start script,
+        // and the abort path must skip the PCI remove/rescan teardown in cleanup.
+        let start = generate_start_script(&vm, &cfg).unwrap();
+        assert!(start.contains("set -e"));
+        assert!(start.contains("\nvfio_bind\n"));
+        assert!(start.contains("skipping PCI rebind"));
     }
 
     /// Issue #61: cleanup and the emergency restore script must reattach the
@@ -1763,13 +1823,40 @@ mod tests {
         let vm = test_vm(tmp.path());
         let cfg = amd_gpu_config(None);
 
+        let unbind = generate_unbind_script(&vm, &cfg);
+        assert!(unbind.contains("echo 1 > \"$vtcon/bind\""));
+        assert!(unbind.contains("efi-framebuffer/bind 2>"));
+
         let start = generate_start_script(&vm, &cfg).unwrap();
-        assert!(start.contains("echo 1 > \"$vtcon/bind\""));
-        assert!(start.contains("efi-framebuffer/bind 2>"));
+        assert!(start.contains("source \"$VM_DIR/unbind.sh\""));
+        assert!(start.contains("    reattach_consoles"));
 
         let restore = generate_restore_script(&vm, &cfg);
-        assert!(restore.contains("echo 1 > \"$vtcon/bind\""));
-        assert!(restore.contains("efi-framebuffer/bind 2>"));
+        assert!(restore.contains("source \"$VM_DIR/unbind.sh\""));
+        assert!(restore.contains("\nreattach_consoles"));
+    }
+
+    /// bind.sh/unbind.sh only act when executed directly, so sourcing them
+    /// from the start and restore scripts just defines the functions.
+    #[test]
+    fn bind_scripts_run_standalone_only_when_executed() {
+        let tmp = tempfile::tempdir().unwrap();
+        let vm = test_vm(tmp.path());
+        let cfg = amd_gpu_config(None);
+
+        let bind = generate_bind_script(&vm, &cfg);
+        let guard = bind
+            .find("if [[ \"${BASH_SOURCE[0]}\" == \"$0\" ]]")
+            .expect("direct-run guard missing");
+        assert!(bind[guard..].contains("    vfio_bind\n"));
+        assert!(bind.contains("GPU_ADDR=\"0000:e4:00.0\""));
+
+        let unbind = generate_unbind_script(&vm, &cfg);
+        let guard = unbind
+            .find("if [[ \"${BASH_SOURCE[0]}\" == \"$0\" ]]")
+            .expect("direct-run guard missing");
+        assert!(unbind[guard..].contains("    vfio_unbind\n"));
+        assert!(unbind.contains("modprobe \"amdgpu\""));
     }
 
     /// Integrated GPUs (the amd_gpu_config fixture is a Rembrandt 680M APU)
@@ -1,19 +1,22 @@
 use anyhow::{bail, Context, Result};
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 use std::path::Path;
 use std::process::Command;
 
-/// A snapshot of a VM disk
-#[derive(Debug, Clone)]
+/// A snapshot of a VM disk. Serialized for `vm-curator snapshot <vm> list --json`.
+#[derive(Debug, Clone, Serialize)]
 pub struct Snapshot {
-    /// Snapshot ID from qemu-img - reserved for future use
-    #[allow(dead_code)]
+    /// Snapshot ID from qemu-img
     pub id: String,
     pub name: String,
+    /// Saved VM state, human-readable
     pub size: String,
+    /// Saved VM state in bytes
+    pub vm_state_bytes: u64,
     pub date: String,
-    /// VM clock time - reserved for display in future UI
-    #[allow(dead_code)]
+    /// When the snapshot was taken, as a Unix timestamp
+    pub date_sec: i64,
+    /// Guest run time when the snapshot was taken
     pub vm_clock: String,
 }
 
@@ -97,8 +100,9 @@ pub fn validate_snapshot_name(name: &str) -> Result<String> {
 /// List snapshots for a qcow2 disk image using JSON output
 pub fn list_snapshots(disk_path: &Path) -> Result<Vec<Snapshot>> {
     let disk_str = path_to_str(disk_path)?;
+    // -U (force share) lets this read the image while the VM holds its lock
     let output = Command::new("qemu-img")
-        .args(["info", "--output=json", disk_str])
+        .args(["info", "-U", "--output=json", disk_str])
         .output()
         .context("Failed to run qemu-img info")?;
 
@@ -128,7 +132,9 @@ pub fn list_snapshots(disk_path: &Path) -> Result<Vec<Snapshot>> {
                 id: s.id,
                 name: s.name,
                 size,
+                vm_state_bytes: s.vm_state_size,
                 date,
+                date_sec: s.date_sec,
                 vm_clock,
             }
         })
@@ -138,7 +144,7 @@ pub fn list_snapshots(disk_path: &Path) -> Result<Vec<Snapshot>> {
 }
 
 /// Format bytes to human-readable size
-fn format_size(bytes: u64) -> String {
+pub(crate) fn format_size(bytes: u64) -> String {
     const KB: u64 = 1024;
     const MB: u64 = KB * 1024;
     const GB: u64 = MB * 1024;
@@ -207,7 +213,8 @@ pub fn restore_snapshot(disk_path: &Path, name: &str) -> Result<()> {
     Ok(())
 }
 
-/// Delete a snapshot
+/// Delete a snapshot. There is no rename to go with it: `qemu-img snapshot`
+/// can only create, apply, list and delete internal snapshots.
 pub fn delete_snapshot(disk_path: &Path, name: &str) -> Result<()> {
     let disk_str = path_to_str(disk_path)?;
     let sanitized_name = validate_snapshot_name(name)?;
@@ -224,6 +231,100 @@ pub fn delete_snapshot(disk_path: &Path, name: &str) -> Result<()> {
     Ok(())
 }
 
+/// Name prefix of snapshots taken by the periodic scheduler. Only these are
+/// pruned by the retention limit; manual snapshots are never touched.
+pub const SCHEDULED_SNAPSHOT_PREFIX: &str = "auto-";
+
+/// Metadata key in vm-curator.toml holding a VM's snapshot interval
+const SNAPSHOT_INTERVAL_KEY: &str = "snapshot_interval_minutes";
+
+/// Name for a new scheduled snapshot, e.g. `auto-20260116-142500`
+pub fn scheduled_snapshot_name() -> String {
+    format!(
+        "{}{}",
+        SCHEDULED_SNAPSHOT_PREFIX,
+        chrono::Local::now().format("%Y%m%d-%H%M%S")
+    )
+}
+
+/// Scheduled snapshots to delete so that at most `keep` remain, oldest first.
+/// The timestamp in the name sorts chronologically.
+pub fn scheduled_snapshots_to_prune(snapshots: &[Snapshot], keep: usize) -> Vec<String> {
+    let mut scheduled: Vec<&str> = snapshots
+        .iter()
+        .map(|s| s.name.as_str())
+        .filter(|n| n.starts_with(SCHEDULED_SNAPSHOT_PREFIX))
+        .collect();
+    scheduled.sort_unstable();
+    let excess = scheduled.len().saturating_sub(keep);
+    scheduled[..excess].iter().map(|n| n.to_string()).collect()
+}
+
+/// Snapshot a VM's disk: `savevm` over QMP while it runs (qemu-img can't
+/// write an image QEMU holds open), `qemu-img snapshot` otherwise
+pub fn snapshot_vm(vm_path: &Path, disk_path: &Path, name: &str, running: bool) -> Result<()> {
+    if running {
+        let name = validate_snapshot_name(name)?;
+        super::lifecycle::qmp_human_command(
+            vm_path,
+            &format!("savevm {}", name),
+            std::time::Duration::from_secs(300),
+        )
+        .context("savevm failed")?;
+        Ok(())
+    } else {
+        create_snapshot(disk_path, name)
+    }
+}
+
+/// Take a live snapshot of a running VM through its QMP socket (`savevm`),
+/// then delete the oldest scheduled snapshots beyond `keep`.
+/// Returns the new snapshot name and the number pruned.
+pub fn take_scheduled_snapshot(
+    vm_path: &Path,
+    disk_path: &Path,
+    keep: usize,
+) -> Result<(String, usize)> {
+    use std::time::Duration;
+
+    let name = scheduled_snapshot_name();
+    // savevm pauses the guest while RAM is written, which can take a while
+    super::lifecycle::qmp_human_command(
+        vm_path,
+        &format!("savevm {}", name),
+        Duration::from_secs(300),
+    )
+    .context("savevm failed")?;
+
+    let mut pruned = 0;
+    if keep > 0 {
+        let snapshots = list_snapshots(disk_path)?;
+        for old in scheduled_snapshots_to_prune(&snapshots, keep) {
+            super::lifecycle::qmp_human_command(
+                vm_path,
+                &format!("delvm {}", old),
+                Duration::from_secs(120),
+            )
+            .with_context(|| format!("delvm {} failed", old))?;
+            pruned += 1;
+        }
+    }
+    Ok((name, pruned))
+}
+
+/// Read a VM's snapshot interval in minutes (None = scheduling off)
+pub fn load_snapshot_schedule(vm_path: &Path) -> Option<u32> {
+    super::discovery::read_vm_metadata_value(vm_path, SNAPSHOT_INTERVAL_KEY)
+        .and_then(|v| v.parse().ok())
+        .filter(|&m| m > 0)
+}
+
+/// Save a VM's snapshot interval in minutes (None turns scheduling off)
+pub fn save_snapshot_schedule(vm_path: &Path, minutes: Option<u32>) -> Result<()> {
+    let value = minutes.filter(|&m| m > 0).map(|m| m.to_string());
+    super::create::set_vm_metadata_value(vm_path, SNAPSHOT_INTERVAL_KEY, value.as_deref())
+}
+
 /// Get information about a disk image using JSON output
 #[allow(dead_code)]
 pub fn get_disk_info(disk_path: &Path) -> Result<DiskInfo> {
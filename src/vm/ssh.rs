@@ -0,0 +1,115 @@
+//! SSH into a guest
+//!
+//! NAT and passt guests are reached through a host port forwarded to guest
+//! port 22; bridged guests directly at the address their guest agent
+//! reports. The login name is remembered per VM in vm-curator.toml.
+
+use anyhow::Result;
+use std::net::Ipv4Addr;
+use std::path::Path;
+
+use super::qemu_config::{NetworkBackend, NetworkConfig, PortProtocol};
+
+/// vm-curator.toml key holding the last SSH login name
+const SSH_USER_KEY: &str = "ssh_user";
+
+/// Where to point ssh for a guest
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SshTarget {
+    /// localhost, on the host port forwarded to guest port 22
+    Forward(u16),
+    /// The guest's own address on the bridged network
+    Direct(Ipv4Addr),
+}
+
+impl SshTarget {
+    /// `ssh` arguments to log in as `user`. Forwarded guests all share
+    /// localhost, so each VM gets its own known_hosts alias to keep their
+    /// host keys apart.
+    pub fn ssh_args(&self, user: &str, vm_id: &str) -> Vec<String> {
+        match self {
+            SshTarget::Forward(port) => vec![
+                "-p".to_string(),
+                port.to_string(),
+                "-o".to_string(),
+                format!("HostKeyAlias=vm-curator-{}", vm_id),
+                format!("{}@localhost", user),
+            ],
+            SshTarget::Direct(ip) => vec![format!("{}@{}", user, ip)],
+        }
+    }
+}
+
+impl std::fmt::Display for SshTarget {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SshTarget::Forward(port) => write!(f, "localhost:{}", port),
+            SshTarget::Direct(ip) => write!(f, "{}", ip),
+        }
+    }
+}
+
+/// Host port forwarded to the guest's SSH port, if the network has one
+pub fn ssh_forward_port(network: &NetworkConfig) -> Option<u16> {
+    if !matches!(
+        network.backend,
+        NetworkBackend::User | NetworkBackend::Passt
+    ) {
+        return None;
+    }
+    network
+        .port_forwards
+        .iter()
+        .find(|f| f.protocol == PortProtocol::Tcp && f.guest_port == 22)
+        .map(|f| f.host_port)
+}
+
+/// Whether ssh can reach the guest at all: a forward to port 22, or a
+/// bridge (the address comes from the guest agent at connect time)
+pub fn can_ssh(network: Option<&NetworkConfig>) -> bool {
+    network.is_some_and(|n| {
+        ssh_forward_port(n).is_some() || matches!(n.backend, NetworkBackend::Bridge(_))
+    })
+}
+
+/// Where to ssh to. `guest_addresses` are the guest agent's
+/// (`eth0 192.168.1.50/24`), used for bridged guests.
+pub fn ssh_target(network: &NetworkConfig, guest_addresses: &[String]) -> Option<SshTarget> {
+    if let Some(port) = ssh_forward_port(network) {
+        return Some(SshTarget::Forward(port));
+    }
+    if !matches!(network.backend, NetworkBackend::Bridge(_)) {
+        return None;
+    }
+    guest_addresses
+        .iter()
+        .filter_map(|addr| {
+            let ip = addr.split_whitespace().last()?.split('/').next()?;
+            ip.parse::<Ipv4Addr>().ok()
+        })
+        .find(|ip| !ip.is_loopback() && !ip.is_link_local())
+        .map(SshTarget::Direct)
+}
+
+/// Whether `user` is a plausible login name (and safe on a command line)
+pub fn is_valid_user(user: &str) -> bool {
+    !user.is_empty()
+        && !user.starts_with('-')
+        && user
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.'))
+}
+
+/// The login name last used for this VM
+pub fn load_ssh_user(vm_path: &Path) -> Option<String> {
+    super::discovery::read_vm_metadata_value(vm_path, SSH_USER_KEY).filter(|u| is_valid_user(u))
+}
+
+/// Remember the login name for this VM
+pub fn save_ssh_user(vm_path: &Path, user: &str) -> Result<()> {
+    super::create::set_vm_metadata_value(vm_path, SSH_USER_KEY, Some(&format!("\"{}\"", user)))
+}
+
+#[cfg(test)]
+#[path = "tests/ssh.rs"]
+mod tests;
@@ -65,6 +65,22 @@ fn test_generate_launch_script() {
     assert!(script.contains("--install"));
     assert!(script.contains("--cdrom"));
     assert!(script.contains("--recovery"));
+    assert_eq!(
+        crate::vm::lifecycle::script_window_title(&script),
+        Some("Test VM".to_string())
+    );
+    // Every QEMU command carries the title and process tag
+    let qemu_lines = script
+        .lines()
+        .filter(|l| l.trim_start().starts_with("qemu-system-"))
+        .count();
+    assert!(qemu_lines > 0);
+    assert_eq!(
+        script
+            .matches(crate::vm::qemu_config::WINDOW_NAME_ARG)
+            .count(),
+        qemu_lines
+    );
 }
 
 #[test]
@@ -72,19 +88,26 @@ fn test_build_qemu_command_basic() {
     let config = WizardQemuConfig {
         emulator: "qemu-system-x86_64".to_string(),
         memory_mb: 2048,
+        max_memory_mb: None,
         cpu_cores: 2,
+        cpu_topology: None,
         cpu_model: Some("host".to_string()),
         machine: Some("q35".to_string()),
         vga: "std".to_string(),
         audio: vec![],
+        audio_backend: "pa".to_string(),
         network_model: "e1000".to_string(),
         disk_interface: "ide".to_string(),
         enable_kvm: true,
         uefi: false,
         tpm: false,
+        secure_boot: false,
         rtc_localtime: false,
-        usb_tablet: true,
+        rtc: crate::vm::qemu_config::RtcConfig::default(),
+        input: InputDevices::UsbTablet,
+        apple_smc: false,
         display: "gtk".to_string(),
+        keymap: None,
         gl_acceleration: false,
         network_backend: "user".to_string(),
         port_forwards: vec![],
@@ -92,6 +115,11 @@ fn test_build_qemu_command_basic() {
         mac_address: None,
         extra_args: vec![],
         bios_path: None,
+        ovmf_paths: None,
+        guest_agent: false,
+        balloon: false,
+        clipboard_sharing: true,
+        nested_virt: None,
     };
 
     let cmd = build_qemu_command_with_os(&config, "disk.qcow2", &InstallMedia::None, None, None);
@@ -201,6 +229,44 @@ fn test_build_qemu_command_with_audio() {
     assert!(cmd.contains("-audiodev pa,id=audio0"));
     assert!(cmd.contains("-device intel-hda"));
     assert!(cmd.contains("-device hda-duplex,audiodev=audio0"));
+
+    let config = WizardQemuConfig {
+        audio_backend: "pipewire".to_string(),
+        ..config
+    };
+    let cmd = build_qemu_command_with_os(&config, "disk.qcow2", &InstallMedia::None, None, None);
+    assert!(cmd.contains("-audiodev pipewire,id=audio0"));
+    assert!(!cmd.contains("-audiodev pa"));
+}
+
+#[test]
+fn test_build_qemu_command_with_keymap() {
+    let config = WizardQemuConfig {
+        display: "vnc".to_string(),
+        keymap: Some("de-ch".to_string()),
+        ..Default::default()
+    };
+    let cmd = build_qemu_command_with_os(&config, "disk.qcow2", &InstallMedia::None, None, None);
+    assert!(cmd.contains("-k de-ch"));
+
+    let parsed = crate::vm::launch_parser::parse_launch_script(
+        std::path::Path::new("/tmp/launch.sh"),
+        &format!("#!/bin/bash\n{}\n", cmd),
+    )
+    .unwrap();
+    assert_eq!(
+        wizard_config_from_parsed(&parsed).keymap.as_deref(),
+        Some("de-ch")
+    );
+
+    let cmd = build_qemu_command_with_os(
+        &WizardQemuConfig::default(),
+        "disk.qcow2",
+        &InstallMedia::None,
+        None,
+        None,
+    );
+    assert!(!cmd.contains("-k "));
 }
 
 #[test]
@@ -208,26 +274,38 @@ fn test_build_qemu_command_with_bios() {
     let config = WizardQemuConfig {
         emulator: "qemu-system-m68k".to_string(),
         memory_mb: 32,
+        max_memory_mb: None,
         cpu_cores: 1,
+        cpu_topology: None,
         cpu_model: Some("m68040".to_string()),
         machine: Some("q800".to_string()),
         vga: "none".to_string(),
         audio: vec![],
+        audio_backend: "pa".to_string(),
         network_model: "none".to_string(),
         disk_interface: "scsi".to_string(),
         enable_kvm: false,
         gl_acceleration: false,
         uefi: false,
         tpm: false,
+        secure_boot: false,
         rtc_localtime: false,
-        usb_tablet: false,
+        rtc: crate::vm::qemu_config::RtcConfig::default(),
+        input: InputDevices::Ps2,
+        apple_smc: false,
         display: "gtk".to_string(),
+        keymap: None,
         network_backend: "user".to_string(),
         port_forwards: vec![],
         bridge_name: None,
         mac_address: None,
         extra_args: vec![],
         bios_path: Some(PathBuf::from("MacROM.bin")),
+        ovmf_paths: None,
+        guest_agent: false,
+        balloon: false,
+        clipboard_sharing: true,
+        nested_virt: None,
     };
 
     let cmd = build_qemu_command_with_os(
@@ -554,26 +632,38 @@ fn macos_uefi_config() -> WizardQemuConfig {
     WizardQemuConfig {
         emulator: "qemu-system-x86_64".to_string(),
         memory_mb: 8192,
+        max_memory_mb: None,
         cpu_cores: 4,
+        cpu_topology: None,
         cpu_model: Some("Penryn,kvm=on,vendor=GenuineIntel,+invtsc,vmware-cpuid-freq=on,+ssse3,+sse4.2,+popcnt,+avx,+aes,+xsave,+xsaveopt,check".to_string()),
         machine: Some("q35".to_string()),
         vga: "none".to_string(),
         audio: vec!["intel-hda".to_string(), "hda-duplex".to_string()],
+        audio_backend: "spice".to_string(),
         network_model: "vmxnet3".to_string(),
         disk_interface: "ide".to_string(),
         enable_kvm: true,
         gl_acceleration: false,
         uefi: true,
         tpm: false,
+        secure_boot: false,
         rtc_localtime: false,
-        usb_tablet: true,
+        rtc: crate::vm::qemu_config::RtcConfig::default(),
+        input: InputDevices::UsbTablet,
+        apple_smc: false,
         display: "spice-app".to_string(),
+        keymap: None,
         network_backend: "passt".to_string(),
         port_forwards: vec![],
         bridge_name: None,
         mac_address: None,
         extra_args: vec!["-device vmware-svga,vgamem_mb=256".to_string()],
         bios_path: Some(PathBuf::from("OpenCore.qcow2")),
+        ovmf_paths: None,
+        guest_agent: false,
+        balloon: false,
+        clipboard_sharing: true,
+        nested_virt: None,
     }
 }
 
@@ -582,26 +672,38 @@ fn macos_non_uefi_config() -> WizardQemuConfig {
     WizardQemuConfig {
         emulator: "qemu-system-x86_64".to_string(),
         memory_mb: 2048,
+        max_memory_mb: None,
         cpu_cores: 2,
+        cpu_topology: None,
         cpu_model: Some("Penryn,kvm=on,vendor=GenuineIntel".to_string()),
         machine: Some("q35".to_string()),
         vga: "none".to_string(),
         audio: vec!["intel-hda".to_string(), "hda-duplex".to_string()],
+        audio_backend: "spice".to_string(),
         network_model: "vmxnet3".to_string(),
         disk_interface: "ide".to_string(),
         enable_kvm: true,
         gl_acceleration: false,
         uefi: false,
         tpm: false,
+        secure_boot: false,
         rtc_localtime: false,
-        usb_tablet: true,
+        rtc: crate::vm::qemu_config::RtcConfig::default(),
+        input: InputDevices::UsbTablet,
+        apple_smc: false,
         display: "spice-app".to_string(),
+        keymap: None,
         network_backend: "passt".to_string(),
         port_forwards: vec![],
         bridge_name: None,
         mac_address: None,
         extra_args: vec!["-device vmware-svga,vgamem_mb=256".to_string()],
         bios_path: None,
+        ovmf_paths: None,
+        guest_agent: false,
+        balloon: false,
+        clipboard_sharing: true,
+        nested_virt: None,
     }
 }
 
@@ -767,6 +869,28 @@ fn test_non_spice_display_has_no_agent_channel() {
     }
 }
 
+#[test]
+fn test_spice_clipboard_sharing_can_be_disabled() {
+    let config = WizardQemuConfig {
+        display: "spice-app".to_string(),
+        clipboard_sharing: false,
+        ..WizardQemuConfig::default()
+    };
+    let cmd = build_qemu_command_with_os(&config, "disk.qcow2", &InstallMedia::None, None, None);
+
+    assert!(
+        cmd.contains("-display spice-app"),
+        "display still spice-app"
+    );
+    for arg in SPICE_AGENT_ARGS {
+        assert!(
+            !cmd.contains(arg),
+            "clipboard off should drop agent arg `{}`",
+            arg
+        );
+    }
+}
+
 #[test]
 fn test_spice_agent_channel_with_gl_acceleration() {
     // virtio-vga-gl + spice-app should still carry the agent channel.
@@ -788,6 +912,43 @@ fn test_spice_agent_channel_with_gl_acceleration() {
     }
 }
 
+#[test]
+fn test_guest_agent_channel_is_opt_in() {
+    let config = WizardQemuConfig::default();
+    let cmd = build_qemu_command_with_os(&config, "disk.qcow2", &InstallMedia::None, None, None);
+    assert!(
+        !cmd.contains("org.qemu.guest_agent.0"),
+        "guest agent must not be emitted unless enabled"
+    );
+
+    let config = WizardQemuConfig {
+        guest_agent: true,
+        ..WizardQemuConfig::default()
+    };
+    let cmd = build_qemu_command_with_os(&config, "disk.qcow2", &InstallMedia::None, None, None);
+    for arg in GUEST_AGENT_ARGS {
+        assert!(cmd.contains(arg), "guest agent arg `{}` present", arg);
+    }
+}
+
+#[test]
+fn test_balloon_and_max_memory_are_opt_in() {
+    let config = WizardQemuConfig::default();
+    let cmd = build_qemu_command_with_os(&config, "disk.qcow2", &InstallMedia::None, None, None);
+    assert!(cmd.contains("-m 2048M \\"), "plain -m without a ceiling");
+    assert!(!cmd.contains("virtio-balloon"), "no balloon unless enabled");
+
+    let config = WizardQemuConfig {
+        balloon: true,
+        clipboard_sharing: true,
+        max_memory_mb: Some(8192),
+        ..WizardQemuConfig::default()
+    };
+    let cmd = build_qemu_command_with_os(&config, "disk.qcow2", &InstallMedia::None, None, None);
+    assert!(cmd.contains("-m 2048M,slots=4,maxmem=8192M"));
+    assert!(cmd.contains(&format!("-device {}", BALLOON_DEVICE)));
+}
+
 #[test]
 fn test_set_spice_agent_args_add_remove_roundtrip() {
     let original = "#!/bin/bash\nqemu-system-x86_64 \\\n        -m 2048 \\\n        -display gtk \\\n        -qmp unix:sock,server=on,wait=off\n";
@@ -849,7 +1010,7 @@ fn test_ppc_macos_no_smc() {
         disk_interface: "ide".to_string(),
         enable_kvm: false,
         uefi: false,
-        usb_tablet: false,
+        input: InputDevices::Ps2,
         display: "gtk".to_string(),
         network_backend: "user".to_string(),
         ..Default::default()
@@ -916,6 +1077,34 @@ fn test_non_macos_unchanged() {
     );
 }
 
+#[test]
+fn test_apple_smc_profile_flag_marks_macos() {
+    // The generic profile's id isn't macos-*, so the profile flag carries it
+    let store = crate::metadata::QemuProfileStore::load_embedded();
+    let config = WizardQemuConfig::from_profile(store.get("generic-macos").unwrap());
+    assert!(config.apple_smc);
+    let cmd = build_qemu_command_with_os(
+        &config,
+        "disk.qcow2",
+        &InstallMedia::None,
+        Some("generic-macos"),
+        None,
+    );
+    assert!(cmd.contains("isa-applesmc,osk="));
+    assert!(cmd.contains("-smbios type=2"));
+    assert!(cmd.contains("ich9-ahci"));
+    assert!(cmd.contains("vmware-svga"));
+    assert!(cmd.contains("usb-kbd"));
+
+    // Not on a non-x86 emulator
+    let mut ppc = config.clone();
+    ppc.apply_emulator_defaults("qemu-system-ppc");
+    assert!(
+        !build_qemu_command_with_os(&ppc, "disk.qcow2", &InstallMedia::None, None, None)
+            .contains("applesmc")
+    );
+}
+
 #[test]
 fn test_macos_uefi_iso_no_boot_d() {
     let config = macos_uefi_config();
@@ -984,6 +1173,93 @@ fn test_macos_non_uefi_uses_bios() {
     );
 }
 
+#[test]
+fn test_ovmf_override_from_config() -> Result<()> {
+    let dir = tempfile::tempdir()?;
+    let code = dir.path().join("CODE.qcow2");
+    let vars = dir.path().join("VARS.qcow2");
+    std::fs::write(&code, b"code")?;
+    std::fs::write(&vars, b"vars")?;
+    let mut config = macos_uefi_config();
+    config.ovmf_paths = Some(OvmfPaths {
+        code: code.clone(),
+        vars_template: vars.clone(),
+    });
+
+    let script =
+        generate_launch_script_with_os("Test", "disk.qcow2", None, false, &config, None, None);
+    assert!(script.contains(&format!(
+        "-drive if=pflash,format=qcow2,readonly=on,file={}",
+        code.display()
+    )));
+    assert!(script.contains("OVMF_VARS=\"$VM_DIR/OVMF_VARS.qcow2\""));
+
+    // Each VM gets its own NVRAM, and an existing one is left alone
+    let firmware = uefi_firmware(&config, None)?.unwrap();
+    copy_ovmf_vars(dir.path(), &firmware)?;
+    assert_eq!(std::fs::read(dir.path().join("OVMF_VARS.qcow2"))?, b"vars");
+    std::fs::write(dir.path().join("OVMF_VARS.qcow2"), b"boot entries")?;
+    copy_ovmf_vars(dir.path(), &firmware)?;
+    assert_eq!(
+        std::fs::read(dir.path().join("OVMF_VARS.qcow2"))?,
+        b"boot entries"
+    );
+
+    // A BIOS VM needs no firmware at all
+    config.uefi = false;
+    assert!(uefi_firmware(&config, None)?.is_none());
+    Ok(())
+}
+
+#[test]
+fn test_ovmf_override_must_match_secure_boot() -> Result<()> {
+    let dir = tempfile::tempdir()?;
+    let plain = OvmfPaths {
+        code: dir.path().join("OVMF_CODE.fd"),
+        vars_template: dir.path().join("OVMF_VARS.fd"),
+    };
+    let secboot = OvmfPaths {
+        code: dir.path().join("OVMF_CODE.secboot.fd"),
+        vars_template: dir.path().join("OVMF_VARS.ms.fd"),
+    };
+    for path in [
+        &plain.code,
+        &plain.vars_template,
+        &secboot.code,
+        &secboot.vars_template,
+    ] {
+        std::fs::write(path, b"fw")?;
+    }
+
+    assert!(resolve_ovmf_firmware(Some(&plain), false).is_ok());
+    assert!(resolve_ovmf_firmware(Some(&secboot), true).is_ok());
+    let err = resolve_ovmf_firmware(Some(&plain), true)
+        .unwrap_err()
+        .to_string();
+    assert!(err.contains("not a Secure Boot build"), "{}", err);
+    let err = resolve_ovmf_firmware(Some(&secboot), false)
+        .unwrap_err()
+        .to_string();
+    assert!(err.contains("needs Secure Boot on"), "{}", err);
+
+    // Windows 11 implies Secure Boot, so a plain override is refused
+    let mut config = macos_uefi_config();
+    config.ovmf_paths = Some(plain);
+    assert!(uefi_firmware(&config, Some("windows-11")).is_err());
+    Ok(())
+}
+
+#[test]
+fn test_ovmf_override_missing_file() {
+    let mut config = macos_uefi_config();
+    config.ovmf_paths = Some(OvmfPaths {
+        code: PathBuf::from("/nonexistent/OVMF_CODE.fd"),
+        vars_template: PathBuf::from("/nonexistent/OVMF_VARS.fd"),
+    });
+    let err = uefi_firmware(&config, None).unwrap_err().to_string();
+    assert!(err.contains("/nonexistent/OVMF_CODE.fd"), "{}", err);
+}
+
 // ---------------------------------------------------------------------------
 // update_network_in_script — regression coverage for issue #38
 // ---------------------------------------------------------------------------
@@ -1237,3 +1513,240 @@ fn test_default_ovmf_firmware_is_raw() {
     assert!(fw.code.ends_with(".fd"));
     assert!(fw.vars_template.ends_with(".fd"));
 }
+
+#[test]
+fn test_nested_virt_forces_host_cpu_with_virt_flag() {
+    let config = WizardQemuConfig {
+        cpu_model: Some("qemu64".to_string()),
+        nested_virt: Some("vmx".to_string()),
+        ..WizardQemuConfig::default()
+    };
+    let cmd = build_qemu_command_with_os(&config, "disk.qcow2", &InstallMedia::None, None, None);
+    assert!(cmd.contains("-cpu 'host,+vmx'"), "{}", cmd);
+
+    // Without KVM the toggle has no effect
+    let config = WizardQemuConfig {
+        cpu_model: Some("qemu64".to_string()),
+        enable_kvm: false,
+        nested_virt: Some("vmx".to_string()),
+        ..WizardQemuConfig::default()
+    };
+    let cmd = build_qemu_command_with_os(&config, "disk.qcow2", &InstallMedia::None, None, None);
+    assert!(cmd.contains("-cpu qemu64"));
+}
+
+#[test]
+fn test_vm_metadata_values_survive_rewrite() -> Result<()> {
+    use crate::vm::discovery::read_vm_metadata_value;
+
+    let vm_dir = tempfile::tempdir()?;
+    // A notes line that looks like a key must not be mistaken for one
+    let notes = "line one\nsnapshot_interval_minutes = 5";
+    write_vm_metadata(vm_dir.path(), "Test VM", None, Some(notes))?;
+    assert_eq!(
+        read_vm_metadata_value(vm_dir.path(), "snapshot_interval_minutes"),
+        None
+    );
+
+    set_vm_metadata_value(vm_dir.path(), "snapshot_interval_minutes", Some("30"))?;
+    write_vm_metadata(vm_dir.path(), "Renamed VM", None, Some(notes))?;
+    assert_eq!(
+        read_vm_metadata_value(vm_dir.path(), "snapshot_interval_minutes").as_deref(),
+        Some("30")
+    );
+    assert_eq!(
+        read_vm_metadata_value(vm_dir.path(), "display_name").as_deref(),
+        Some("Renamed VM")
+    );
+
+    set_vm_metadata_value(vm_dir.path(), "snapshot_interval_minutes", None)?;
+    assert_eq!(
+        read_vm_metadata_value(vm_dir.path(), "snapshot_interval_minutes"),
+        None
+    );
+    let content = std::fs::read_to_string(vm_dir.path().join("vm-curator.toml"))?;
+    assert!(content.contains(notes));
+    Ok(())
+}
+
+fn regenerate_test_vm(dir: &Path, os_profile: Option<&str>) -> DiscoveredVm {
+    DiscoveredVm {
+        id: "test-vm".to_string(),
+        path: dir.to_path_buf(),
+        launch_script: dir.join("launch.sh"),
+        config: QemuConfig::default(),
+        custom_name: Some("Test VM".to_string()),
+        os_profile: os_profile.map(str::to_string),
+        notes: None,
+        unrecognized_args: Vec::new(),
+        last_launched: None,
+        launch_count: 0,
+        autostart: false,
+        first_boot_done: None,
+        custom_ascii: None,
+        shared_disks: Vec::new(),
+    }
+}
+
+#[test]
+fn test_regenerate_round_trips_wizard_script() -> Result<()> {
+    let vm_dir = tempfile::tempdir()?;
+    let config = WizardQemuConfig {
+        memory_mb: 4096,
+        max_memory_mb: Some(8192),
+        cpu_cores: 4,
+        cpu_topology: Some(CpuTopology {
+            sockets: 2,
+            cores: 2,
+            threads: 1,
+        }),
+        vga: "qxl".to_string(),
+        network_model: "virtio".to_string(),
+        port_forwards: vec![PortForward {
+            protocol: PortProtocol::Tcp,
+            host_port: 2222,
+            guest_port: 22,
+        }],
+        mac_address: Some("52:54:00:12:34:56".to_string()),
+        disk_interface: "virtio".to_string(),
+        rtc_localtime: true,
+        guest_agent: true,
+        balloon: true,
+        display: "spice-app".to_string(),
+        audio_backend: "pipewire".to_string(),
+        ..WizardQemuConfig::default()
+    };
+    let original = generate_launch_script_with_os(
+        "Test VM",
+        "disk.qcow2",
+        None,
+        false,
+        &config,
+        Some("windows-10"),
+        None,
+    );
+    write_launch_script(vm_dir.path(), &original)?;
+
+    let vm = regenerate_test_vm(vm_dir.path(), Some("windows-10"));
+    // Everything, including the per-VM SMBIOS identity, comes back unchanged
+    assert_eq!(regenerate_launch_script(&vm, None)?, original);
+    Ok(())
+}
+
+#[test]
+fn test_regenerate_keeps_managed_sections_and_backs_up() -> Result<()> {
+    let vm_dir = tempfile::tempdir()?;
+    let custom = r#"#!/bin/bash
+VM_DIR="$(dirname "$(readlink -f "$0")")"
+# >>> USB Passthrough (managed by vm-curator) >>>
+USB_PASSTHROUGH_ARGS="-device usb-host,vendorid=0x046d,productid=0xc52b"
+# <<< USB Passthrough <<<
+qemu-system-x86_64 -enable-kvm -m 2048 -smp 2 \
+    -drive file="$VM_DIR/hand.qcow2",if=virtio \
+    -vga virtio -display gtk -my-custom-flag $USB_PASSTHROUGH_ARGS
+"#;
+    write_launch_script(vm_dir.path(), custom)?;
+    let vm = regenerate_test_vm(vm_dir.path(), None);
+
+    let regenerated = regenerate_launch_script(&vm, None)?;
+    assert!(regenerated.contains("DISK=\"$VM_DIR/hand.qcow2\""));
+    assert!(regenerated.contains("if=virtio"));
+    assert!(regenerated.contains("-m 2048M"));
+    // Options the wizard doesn't model are carried over
+    assert!(regenerated.contains("-my-custom-flag"));
+    assert!(regenerated.contains("USB_PASSTHROUGH_ARGS=\"-device usb-host"));
+    // Every QEMU command in the case statement picks the section up again
+    assert_eq!(
+        regenerated.matches("$USB_PASSTHROUGH_ARGS").count(),
+        regenerated.matches("-qmp").count()
+    );
+
+    let backup = replace_launch_script(&vm, &regenerated)?;
+    assert_eq!(backup, vm_dir.path().join(LAUNCH_SCRIPT_BACKUP));
+    assert_eq!(std::fs::read_to_string(&backup)?, custom);
+    assert_eq!(std::fs::read_to_string(&vm.launch_script)?, regenerated);
+    Ok(())
+}
+
+#[test]
+fn test_regenerate_keeps_unmodeled_options() -> Result<()> {
+    let vm_dir = tempfile::tempdir()?;
+    let custom = r#"#!/bin/bash
+VM_DIR="$(dirname "$(readlink -f "$0")")"
+qemu-system-x86_64 -enable-kvm -m 2048 -smp 2 \
+    -cpu 'host,+vmx' \
+    -drive file="$VM_DIR/disk.qcow2",if=virtio,throttling.iops-read=500 \
+    -device "usb-serial,chardev=ser1" -chardev pty,id=ser1 \
+    -serial unix:"$VM_DIR/serial.sock",server=on,wait=off \
+    -vga virtio -display gtk
+"#;
+    write_launch_script(vm_dir.path(), custom)?;
+    let vm = regenerate_test_vm(vm_dir.path(), None);
+
+    let parsed = crate::vm::launch_parser::parse_launch_script(&vm.launch_script, custom)?;
+    assert_eq!(
+        wizard_config_from_parsed(&parsed).nested_virt.as_deref(),
+        Some("vmx")
+    );
+
+    let regenerated = regenerate_launch_script(&vm, None)?;
+    assert!(regenerated.contains("-device \"usb-serial,chardev=ser1\""));
+    assert!(regenerated.contains("-chardev pty,id=ser1"));
+    assert!(regenerated.contains("-serial unix:\"$VM_DIR/serial.sock\",server=on,wait=off"));
+    assert!(regenerated.contains("throttling.iops-read=500"));
+    assert!(regenerated.contains("+vmx"));
+    Ok(())
+}
+
+#[test]
+fn test_wizard_config_template_copies_settings_but_not_mac() -> Result<()> {
+    let script = r#"#!/bin/bash
+qemu-system-x86_64 -enable-kvm -m 6144 -smp 4 \
+    -drive file="$VM_DIR/disk.qcow2",if=virtio \
+    -vga qxl -display spice-app \
+    -netdev user,id=net0 -device e1000,netdev=net0,mac=52:54:00:12:34:56
+"#;
+    let parsed =
+        crate::vm::launch_parser::parse_launch_script(Path::new("/vms/src/launch.sh"), script)?;
+    assert_eq!(
+        wizard_config_from_parsed(&parsed).mac_address.as_deref(),
+        Some("52:54:00:12:34:56")
+    );
+
+    let config = wizard_config_template(&parsed);
+    assert_eq!(config.memory_mb, 6144);
+    assert_eq!(config.cpu_cores, 4);
+    assert_eq!(config.vga, "qxl");
+    assert_eq!(config.display, "spice-app");
+    assert_eq!(config.network_model, "e1000");
+    assert_eq!(config.disk_interface, "virtio");
+    assert_eq!(config.mac_address, None);
+    Ok(())
+}
+
+#[test]
+fn test_build_qemu_command_input_devices() {
+    let cmd_for = |input| {
+        let config = WizardQemuConfig {
+            input,
+            ..Default::default()
+        };
+        build_qemu_command_with_os(&config, "disk.qcow2", &InstallMedia::None, None, None)
+    };
+
+    let ps2 = cmd_for(InputDevices::Ps2);
+    assert!(!ps2.contains("usb-tablet"));
+    assert!(!ps2.contains("virtio-tablet"));
+    assert_eq!(InputDevices::from_script(&ps2), InputDevices::Ps2);
+
+    let usb = cmd_for(InputDevices::UsbTablet);
+    assert!(usb.contains("-usb"));
+    assert!(usb.contains("-device usb-tablet"));
+    assert_eq!(InputDevices::from_script(&usb), InputDevices::UsbTablet);
+
+    let virtio = cmd_for(InputDevices::Virtio);
+    assert!(virtio.contains("-device virtio-keyboard-pci"));
+    assert!(virtio.contains("-device virtio-tablet-pci"));
+    assert!(!virtio.contains("usb-tablet"));
+    assert_eq!(InputDevices::from_script(&virtio), InputDevices::Virtio);
+}
@@ -10,6 +10,13 @@ fn test_display_name() {
         custom_name: None,
         os_profile: None,
         notes: None,
+        unrecognized_args: Vec::new(),
+        last_launched: None,
+        launch_count: 0,
+        autostart: false,
+        first_boot_done: None,
+        custom_ascii: None,
+        shared_disks: Vec::new(),
     };
     assert_eq!(vm.display_name(), "Microsoft® Windows 95");
 }
@@ -24,11 +31,51 @@ fn test_custom_display_name() {
         custom_name: Some("CachyOS Gaming Rig".to_string()),
         os_profile: Some("linux-cachyos".to_string()),
         notes: None,
+        unrecognized_args: Vec::new(),
+        last_launched: None,
+        launch_count: 0,
+        autostart: false,
+        first_boot_done: None,
+        custom_ascii: None,
+        shared_disks: Vec::new(),
     };
     // Custom name takes priority
     assert_eq!(vm.display_name(), "CachyOS Gaming Rig");
 }
 
+#[test]
+fn test_find_by_display_name_ignores_case_and_self() {
+    let vms: Vec<DiscoveredVm> = [
+        ("windows-95", None),
+        ("linux-cachyos-2", Some("Gaming Rig")),
+    ]
+    .into_iter()
+    .map(|(id, name)| DiscoveredVm {
+        id: id.to_string(),
+        path: PathBuf::from("/test").join(id),
+        launch_script: PathBuf::from("/test").join(id).join("launch.sh"),
+        config: QemuConfig::default(),
+        custom_name: name.map(str::to_string),
+        os_profile: None,
+        notes: None,
+        unrecognized_args: Vec::new(),
+        last_launched: None,
+        launch_count: 0,
+        autostart: false,
+        first_boot_done: None,
+        custom_ascii: None,
+        shared_disks: Vec::new(),
+    })
+    .collect();
+
+    let found = find_by_display_name(&vms, "  gaming RIG ", None).map(|vm| vm.id.as_str());
+    assert_eq!(found, Some("linux-cachyos-2"));
+    assert!(find_by_display_name(&vms, "microsoft® windows 95", None).is_some());
+    // Renaming a VM to its own name is not a collision
+    assert!(find_by_display_name(&vms, "Gaming Rig", Some("linux-cachyos-2")).is_none());
+    assert!(find_by_display_name(&vms, "Office", None).is_none());
+}
+
 #[test]
 fn test_linux_display_names() {
     assert_eq!(format_os_display_name("linux-cachyos"), "CachyOS (rolling)");
@@ -88,3 +135,201 @@ fn test_custom_names() {
         "Microsoft® MS-DOS / Windows 3.1 (My First PC)"
     );
 }
+
+#[test]
+fn test_time_ago_buckets() {
+    let now = chrono::Local::now();
+    let ago = |secs: i64| time_ago(now - chrono::Duration::seconds(secs), now);
+    assert_eq!(ago(5), "just now");
+    assert_eq!(ago(60), "1 minute ago");
+    assert_eq!(ago(3 * 3600 + 10), "3 hours ago");
+    assert_eq!(ago(3 * 86_400), "3 days ago");
+    assert_eq!(ago(400 * 86_400), "1 year ago");
+    // Clock skew never yields a negative age
+    assert_eq!(ago(-30), "just now");
+}
+
+#[test]
+fn test_is_stale_uses_last_launch_then_script_age() {
+    let tmp = tempfile::tempdir().unwrap();
+    let script = tmp.path().join("launch.sh");
+    std::fs::write(&script, "#!/bin/bash\n").unwrap();
+    let now = chrono::Local::now();
+    let mut vm = DiscoveredVm {
+        id: "old-vm".to_string(),
+        path: tmp.path().to_path_buf(),
+        launch_script: script,
+        config: QemuConfig::default(),
+        custom_name: None,
+        os_profile: None,
+        notes: None,
+        unrecognized_args: Vec::new(),
+        last_launched: None,
+        launch_count: 0,
+        autostart: false,
+        first_boot_done: None,
+        custom_ascii: None,
+        shared_disks: Vec::new(),
+    };
+
+    // Never launched, but launch.sh was just written
+    assert!(!is_stale(&vm, 30, now));
+
+    vm.last_launched = Some(now - chrono::Duration::days(45));
+    assert!(is_stale(&vm, 30, now));
+    assert!(!is_stale(&vm, 90, now));
+}
+
+/// A VM whose launch.sh attaches `disks`
+fn vm_with_disks(id: &str, disks: &[&Path]) -> DiscoveredVm {
+    let mut config = QemuConfig::default();
+    for disk in disks {
+        config.disks.push(crate::vm::qemu_config::DiskConfig {
+            path: disk.to_path_buf(),
+            format: crate::vm::qemu_config::DiskFormat::Qcow2,
+            interface: "virtio".to_string(),
+            throttle: Default::default(),
+        });
+    }
+    DiscoveredVm {
+        id: id.to_string(),
+        path: PathBuf::from("/test").join(id),
+        launch_script: PathBuf::from("/test").join(id).join("launch.sh"),
+        config,
+        custom_name: None,
+        os_profile: None,
+        notes: None,
+        unrecognized_args: Vec::new(),
+        last_launched: None,
+        launch_count: 0,
+        autostart: false,
+        first_boot_done: None,
+        custom_ascii: None,
+        shared_disks: Vec::new(),
+    }
+}
+
+/// A minimal qcow2 header naming `backing` as the backing file
+fn write_qcow2_overlay(path: &Path, backing: &str) {
+    let mut header = Vec::new();
+    header.extend_from_slice(b"QFI\xfb");
+    header.extend_from_slice(&3u32.to_be_bytes());
+    header.extend_from_slice(&104u64.to_be_bytes());
+    header.extend_from_slice(&(backing.len() as u32).to_be_bytes());
+    header.resize(104, 0);
+    header.extend_from_slice(backing.as_bytes());
+    std::fs::write(path, header).unwrap();
+}
+
+#[test]
+fn test_qcow2_backing_file_resolves_relative_names() {
+    let tmp = tempfile::tempdir().unwrap();
+    let overlay = tmp.path().join("overlay.qcow2");
+    write_qcow2_overlay(&overlay, "base.qcow2");
+    assert_eq!(
+        qcow2_backing_file(&overlay),
+        Some(tmp.path().join("base.qcow2"))
+    );
+
+    let raw = tmp.path().join("disk.img");
+    std::fs::write(&raw, [0u8; 512]).unwrap();
+    assert_eq!(qcow2_backing_file(&raw), None);
+}
+
+#[test]
+fn test_mark_shared_disks_flags_same_disk() {
+    let tmp = tempfile::tempdir().unwrap();
+    let disk = tmp.path().join("disk.qcow2");
+    let iso = tmp.path().join("install.iso");
+    std::fs::write(&disk, "").unwrap();
+    let own = tmp.path().join("own.qcow2");
+
+    // The second VM names the disk through a different spelling of the path
+    let mut vms = vec![
+        vm_with_disks("alpha", &[&disk, &iso]),
+        vm_with_disks("beta", &[&tmp.path().join("sub/../disk.qcow2"), &iso]),
+        vm_with_disks("gamma", &[&own, &iso]),
+    ];
+    std::fs::create_dir(tmp.path().join("sub")).unwrap();
+    mark_shared_disks(&mut vms);
+
+    let disk = std::fs::canonicalize(&disk).unwrap();
+    assert_eq!(
+        vms[0].shared_disks,
+        vec![SharedDisk {
+            path: disk.clone(),
+            vm_ids: vec!["beta".to_string()],
+        }]
+    );
+    assert_eq!(vms[1].shared_disks[0].vm_ids, ["alpha"]);
+    // Install media is opened read-only, so sharing it is fine
+    assert!(vms[2].shared_disks.is_empty());
+}
+
+#[test]
+fn test_mark_shared_disks_follows_backing_files() {
+    let tmp = tempfile::tempdir().unwrap();
+    let base = tmp.path().join("base.qcow2");
+    std::fs::write(&base, "").unwrap();
+    let overlay_a = tmp.path().join("a.qcow2");
+    let overlay_b = tmp.path().join("b.qcow2");
+    write_qcow2_overlay(&overlay_a, "base.qcow2");
+    write_qcow2_overlay(&overlay_b, "base.qcow2");
+
+    // Linked clones of one base image don't conflict
+    let mut vms = vec![
+        vm_with_disks("clone-a", &[&overlay_a]),
+        vm_with_disks("clone-b", &[&overlay_b]),
+    ];
+    mark_shared_disks(&mut vms);
+    assert!(vms.iter().all(|vm| vm.shared_disks.is_empty()));
+
+    // A VM writing the base image would corrupt both clones
+    vms.push(vm_with_disks("base-vm", &[&base]));
+    mark_shared_disks(&mut vms);
+    assert_eq!(vms[0].shared_disks[0].vm_ids, ["base-vm"]);
+    assert_eq!(vms[1].shared_disks[0].vm_ids, ["base-vm"]);
+    assert_eq!(vms[2].shared_disks[0].vm_ids, ["clone-a", "clone-b"]);
+    assert!(shared_disk_warnings(&vms[0], &vms)[0].ends_with("is also used by Base Vm"));
+}
+
+#[test]
+fn test_affects_discovery_only_for_discovered_files() {
+    let lib = Path::new("/vms");
+    assert!(affects_discovery(lib, Path::new("/vms/win98")));
+    assert!(affects_discovery(lib, Path::new("/vms/dos/launch.sh")));
+    assert!(affects_discovery(
+        lib,
+        Path::new("/vms/dos/vm-curator.toml")
+    ));
+
+    // Runtime files such as disks, sockets and logs don't count
+    assert!(!affects_discovery(lib, Path::new("/vms/dos/dos.qcow2")));
+    assert!(!affects_discovery(lib, Path::new("/vms/dos/launch.log")));
+    assert!(!affects_discovery(
+        lib,
+        Path::new("/vms/dos/snapshots/launch.sh")
+    ));
+    assert!(!affects_discovery(lib, Path::new("/vms")));
+    assert!(!affects_discovery(
+        lib,
+        Path::new("/elsewhere/dos/launch.sh")
+    ));
+}
+
+#[test]
+fn library_issues_are_told_apart() -> anyhow::Result<()> {
+    let tmp = tempfile::tempdir()?;
+    assert_eq!(library_issue(tmp.path()), None);
+
+    let missing = tmp.path().join("vms");
+    assert_eq!(library_issue(&missing), Some(LibraryIssue::Missing));
+    assert!(LibraryIssue::Missing
+        .describe(&missing)
+        .contains(&missing.display().to_string()));
+
+    let file = tmp.path().join("vms.txt");
+    std::fs::write(&file, "")?;
+    assert_eq!(library_issue(&file), Some(LibraryIssue::NotADirectory));
+    Ok(())
+}
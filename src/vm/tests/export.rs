@@ -0,0 +1,103 @@
+use super::*;
+use crate::vm::import::parse_libvirt_xml_str;
+use crate::vm::qemu_config::{NetworkConfig, PortForward, PortProtocol};
+
+fn sample_config() -> QemuConfig {
+    QemuConfig {
+        emulator: QemuEmulator::X86_64,
+        memory_mb: 8192,
+        cpu_cores: 4,
+        cpu_model: Some("host".to_string()),
+        machine: Some("q35".to_string()),
+        vga: VgaType::Virtio,
+        audio_devices: vec![AudioDevice::Hda],
+        network: Some(NetworkConfig {
+            model: "virtio-net-pci".to_string(),
+            backend: NetworkBackend::Bridge("br0".to_string()),
+            port_forwards: Vec::new(),
+            user_net: false,
+            bridge: Some("br0".to_string()),
+            mac_address: Some("52:54:00:12:34:56".to_string()),
+            shaping: Default::default(),
+        }),
+        disks: vec![
+            DiskConfig {
+                path: PathBuf::from("/usr/share/edk2/x64/OVMF_CODE.4m.fd"),
+                format: DiskFormat::Other("fd".to_string()),
+                interface: "ide".to_string(),
+                throttle: Default::default(),
+            },
+            DiskConfig {
+                path: PathBuf::from("/vms/win11/disk.qcow2"),
+                format: DiskFormat::Qcow2,
+                interface: "virtio".to_string(),
+                throttle: Default::default(),
+            },
+        ],
+        enable_kvm: true,
+        uefi: true,
+        tpm: true,
+        extra_args: vec!["-display sdl".to_string()],
+        ..QemuConfig::default()
+    }
+}
+
+#[test]
+fn test_export_round_trips_through_importer() {
+    let config = sample_config();
+    let xml = libvirt_domain_xml("win11", &config);
+    let vm = parse_libvirt_xml_str(&xml, Path::new("/tmp/win11.xml")).unwrap();
+    let imported = vm.qemu_config;
+
+    assert_eq!(vm.name, "win11");
+    assert_eq!(imported.emulator, "qemu-system-x86_64");
+    assert_eq!(imported.memory_mb, 8192);
+    assert_eq!(imported.cpu_cores, 4);
+    assert_eq!(imported.machine, Some("q35".to_string()));
+    assert!(imported.enable_kvm);
+    assert!(imported.uefi);
+    assert!(imported.tpm);
+    assert_eq!(imported.vga, "virtio");
+    assert_eq!(imported.display, "sdl");
+    assert_eq!(imported.network_backend, "bridge");
+    assert_eq!(imported.bridge_name, Some("br0".to_string()));
+    assert_eq!(imported.network_model, "virtio-net-pci");
+    assert_eq!(imported.disk_interface, "virtio");
+    // The OVMF image becomes the <loader>, not a disk
+    assert_eq!(vm.disk_paths, vec![PathBuf::from("/vms/win11/disk.qcow2")]);
+}
+
+#[test]
+fn test_export_comments_untranslated_settings() {
+    let mut config = sample_config();
+    config.cpu_model = Some("host,kvm=off".to_string());
+    config.extra_args.push("-device ivshmem-plain".to_string());
+    if let Some(ref mut net) = config.network {
+        net.backend = NetworkBackend::User;
+        net.port_forwards.push(PortForward {
+            protocol: PortProtocol::Tcp,
+            host_port: 2222,
+            guest_port: 22,
+        });
+    }
+
+    let xml = libvirt_domain_xml("a<b", &config);
+    assert!(xml.contains("<name>a&lt;b</name>"));
+    assert!(xml.contains("<!-- CPU flags not translated: kvm=off -->"));
+    assert!(xml.contains("Port forwards not translated"));
+    assert!(xml.contains("-device ivshmem-plain"));
+    // Still parses as a domain
+    assert!(parse_libvirt_xml_str(&xml, Path::new("/tmp/x.xml")).is_ok());
+}
+
+#[test]
+fn test_export_without_kvm_is_qemu_domain() {
+    let config = QemuConfig {
+        emulator: QemuEmulator::I386,
+        ..QemuConfig::default()
+    };
+    let xml = libvirt_domain_xml("dos", &config);
+    assert!(xml.starts_with("<domain type='qemu'>"));
+    assert!(xml.contains("arch='i686'"));
+    assert!(!xml.contains("<cpu"));
+}
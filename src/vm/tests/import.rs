@@ -1,4 +1,5 @@
 use super::*;
+use crate::wizard_types::OvmfPaths;
 use std::path::Path;
 
 #[test]
@@ -338,6 +339,7 @@ fn test_execute_import_preserves_raw_img_disk_format() -> Result<()> {
         "Imported Raw",
         "imported-raw",
         ImportDiskAction::Copy,
+        |_, _| {},
     )?;
     let imported_disk = vm_dir.join("imported-raw.raw");
 
@@ -349,3 +351,287 @@ fn test_execute_import_preserves_raw_img_disk_format() -> Result<()> {
     assert!(launch_script.contains("format=raw,if=ide,index=0,media=disk"));
     Ok(())
 }
+
+#[test]
+fn test_foreign_disk_format_falls_back_to_extension() -> Result<()> {
+    let dir = tempfile::tempdir()?;
+    // Not a real VDI; qemu-img (if present) reports raw, so the extension decides
+    let vdi = dir.path().join("guest.vdi");
+    std::fs::write(&vdi, b"not really a vdi")?;
+    let img = dir.path().join("guest.img");
+    std::fs::write(&img, b"raw image")?;
+
+    assert_eq!(foreign_disk_format(&vdi), Some("vdi"));
+    assert_eq!(foreign_disk_format(&img), None);
+    assert_eq!(
+        foreign_format_from_extension(Path::new("a/b/disk.VHD")),
+        Some("vpc")
+    );
+    assert_eq!(foreign_format_from_extension(Path::new("disk.qcow2")), None);
+    Ok(())
+}
+
+#[test]
+fn test_execute_import_keeps_unconverted_foreign_disk_format() -> Result<()> {
+    let source_dir = tempfile::tempdir()?;
+    let library = tempfile::tempdir()?;
+    let source_disk = source_dir.path().join("guest.vdi");
+    std::fs::write(&source_disk, b"vdi fixture")?;
+
+    let vm = ImportableVm {
+        name: "VirtualBox Guest".to_string(),
+        config_path: source_dir.path().join("guest.conf"),
+        source: ImportSource::Quickemu,
+        qemu_config: WizardQemuConfig::default(),
+        disk_paths: vec![source_disk],
+        detected_os_profile: None,
+        import_notes: Vec::new(),
+        disks_readable: vec![true],
+    };
+
+    let vm_dir = execute_import(
+        library.path(),
+        &vm,
+        "VirtualBox Guest",
+        "vbox-guest",
+        ImportDiskAction::Copy,
+        |label, _| assert!(label.starts_with("Copying"), "copy must not convert"),
+    )?;
+    assert_eq!(
+        std::fs::read(vm_dir.join("vbox-guest.vdi"))?,
+        b"vdi fixture"
+    );
+
+    let launch_script = std::fs::read_to_string(vm_dir.join("launch.sh"))?;
+    assert!(launch_script.contains("DISK=\"$VM_DIR/vbox-guest.vdi\""));
+    assert!(launch_script.contains("format=vdi,"));
+    Ok(())
+}
+
+const SAMPLE_OVF: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<Envelope xmlns="http://schemas.dmtf.org/ovf/envelope/1"
+  xmlns:ovf="http://schemas.dmtf.org/ovf/envelope/1"
+  xmlns:rasd="http://schemas.dmtf.org/wbem/wscim/1/cim-schema/2/CIM_ResourceAllocationSettingData"
+  xmlns:vmw="http://www.vmware.com/schema/ovf">
+  <References>
+    <File ovf:id="file1" ovf:href="appliance-disk1.vmdk" ovf:size="1024"/>
+  </References>
+  <DiskSection>
+    <Disk ovf:diskId="vmdisk1" ovf:fileRef="file1" ovf:capacity="16"/>
+  </DiskSection>
+  <VirtualSystem ovf:id="appliance">
+    <Name>Ubuntu Appliance</Name>
+    <OperatingSystemSection ovf:id="94">
+      <Description>Ubuntu Linux (64-bit)</Description>
+    </OperatingSystemSection>
+    <VirtualHardwareSection>
+      <Item>
+        <rasd:ElementName>2 virtual CPU(s)</rasd:ElementName>
+        <rasd:InstanceID>1</rasd:InstanceID>
+        <rasd:ResourceType>3</rasd:ResourceType>
+        <rasd:VirtualQuantity>2</rasd:VirtualQuantity>
+      </Item>
+      <Item>
+        <rasd:AllocationUnits>byte * 2^20</rasd:AllocationUnits>
+        <rasd:InstanceID>2</rasd:InstanceID>
+        <rasd:ResourceType>4</rasd:ResourceType>
+        <rasd:VirtualQuantity>4096</rasd:VirtualQuantity>
+      </Item>
+      <Item>
+        <rasd:InstanceID>3</rasd:InstanceID>
+        <rasd:ResourceSubType>lsilogic</rasd:ResourceSubType>
+        <rasd:ResourceType>6</rasd:ResourceType>
+      </Item>
+      <Item>
+        <rasd:InstanceID>4</rasd:InstanceID>
+        <rasd:ResourceType>5</rasd:ResourceType>
+      </Item>
+      <Item>
+        <rasd:HostResource>ovf:/disk/vmdisk1</rasd:HostResource>
+        <rasd:InstanceID>5</rasd:InstanceID>
+        <rasd:Parent>3</rasd:Parent>
+        <rasd:ResourceType>17</rasd:ResourceType>
+      </Item>
+      <Item>
+        <rasd:InstanceID>6</rasd:InstanceID>
+        <rasd:ResourceSubType>VmxNet3</rasd:ResourceSubType>
+        <rasd:ResourceType>10</rasd:ResourceType>
+      </Item>
+      <Item>
+        <rasd:ElementName>Serial port 1</rasd:ElementName>
+        <rasd:InstanceID>7</rasd:InstanceID>
+        <rasd:ResourceType>21</rasd:ResourceType>
+      </Item>
+      <vmw:Config ovf:required="false" vmw:key="firmware" vmw:value="efi"/>
+    </VirtualHardwareSection>
+  </VirtualSystem>
+</Envelope>
+"#;
+
+#[test]
+fn test_parse_ovf_descriptor() {
+    let vm = parse_ovf_str(
+        SAMPLE_OVF,
+        Path::new("/tmp/appliance.ovf"),
+        Path::new("/tmp"),
+    )
+    .unwrap();
+
+    assert_eq!(vm.name, "Ubuntu Appliance");
+    assert_eq!(vm.source, ImportSource::Ova);
+    assert_eq!(vm.qemu_config.cpu_cores, 2);
+    assert_eq!(vm.qemu_config.memory_mb, 4096);
+    assert_eq!(vm.qemu_config.disk_interface, "scsi");
+    assert_eq!(vm.qemu_config.network_model, "e1000");
+    assert!(vm.qemu_config.uefi);
+    assert_eq!(vm.detected_os_profile, Some("linux-ubuntu".to_string()));
+    assert_eq!(
+        vm.disk_paths,
+        vec![PathBuf::from("/tmp/appliance-disk1.vmdk")]
+    );
+    let notes = vm.import_notes.join("\n");
+    assert!(notes.contains("VmxNet3"), "{}", notes);
+    assert!(notes.contains("serial ports"), "{}", notes);
+    assert!(notes.contains("converted to qcow2"), "{}", notes);
+}
+
+#[test]
+fn test_ovf_unsupported_hardware_listed_once() {
+    let extra = r#"<Item>
+        <rasd:InstanceID>8</rasd:InstanceID>
+        <rasd:ResourceType>10</rasd:ResourceType>
+      </Item>
+      <Item>
+        <rasd:InstanceID>9</rasd:InstanceID>
+        <rasd:ResourceType>21</rasd:ResourceType>
+      </Item>
+      <vmw:Config"#;
+    let ovf = SAMPLE_OVF.replace("<vmw:Config", extra);
+    let vm = parse_ovf_str(&ovf, Path::new("/tmp/appliance.ovf"), Path::new("/tmp")).unwrap();
+    let note = vm
+        .import_notes
+        .iter()
+        .find(|n| n.starts_with("Hardware not imported"))
+        .unwrap();
+    assert_eq!(
+        note,
+        "Hardware not imported: serial ports, additional network adapters"
+    );
+}
+
+#[test]
+fn test_ovf_memory_units() {
+    assert_eq!(ovf_memory_mb(2048, "byte * 2^20"), 2048);
+    assert_eq!(ovf_memory_mb(4, "byte * 2^30"), 4096);
+    assert_eq!(ovf_memory_mb(1024, "MegaBytes"), 1024);
+    assert_eq!(ovf_memory_mb(2, "GigaBytes"), 2048);
+}
+
+#[test]
+fn test_import_ova_extracts_disk() -> Result<()> {
+    let source_dir = tempfile::tempdir()?;
+    let library = tempfile::tempdir()?;
+    let ovf = SAMPLE_OVF.replace("appliance-disk1.vmdk", "appliance-disk1.img");
+    std::fs::write(source_dir.path().join("appliance.ovf"), ovf)?;
+    std::fs::write(source_dir.path().join("appliance.mf"), "SHA256(x)= 00")?;
+    std::fs::write(source_dir.path().join("appliance-disk1.img"), b"raw disk")?;
+    let ova = source_dir.path().join("appliance.ova");
+    let status = std::process::Command::new("tar")
+        .arg("-cf")
+        .arg(&ova)
+        .arg("-C")
+        .arg(source_dir.path())
+        .args(["appliance.ovf", "appliance.mf", "appliance-disk1.img"])
+        .status()?;
+    assert!(status.success());
+
+    let mut vm = parse_config_file(&ova)?;
+    assert_eq!(vm.disk_paths, vec![ova.join("appliance-disk1.img")]);
+    assert_eq!(vm.disks_readable, vec![true]);
+
+    // The appliance boots UEFI; use stand-in firmware so the test doesn't
+    // depend on OVMF being installed
+    let code = source_dir.path().join("OVMF_CODE.fd");
+    let vars = source_dir.path().join("OVMF_VARS.fd");
+    std::fs::write(&code, b"code")?;
+    std::fs::write(&vars, b"vars")?;
+    vm.qemu_config.ovmf_paths = Some(OvmfPaths {
+        code,
+        vars_template: vars,
+    });
+
+    let vm_dir = execute_import(
+        library.path(),
+        &vm,
+        "Appliance",
+        "appliance",
+        ImportDiskAction::Symlink,
+        |_, _| {},
+    )?;
+    assert_eq!(std::fs::read(vm_dir.join("appliance.raw"))?, b"raw disk");
+    assert!(!vm_dir.join("appliance-disk1.img").exists());
+    assert_eq!(std::fs::read(vm_dir.join("OVMF_VARS.fd"))?, b"vars");
+    Ok(())
+}
+
+#[test]
+fn test_qemu_argv_script_pairs_options_with_values() {
+    let argv: Vec<String> = [
+        "/usr/bin/qemu-system-x86_64",
+        "-enable-kvm",
+        "-m",
+        "4096",
+        "-drive",
+        "file=/vms/my disk.qcow2,if=virtio",
+    ]
+    .iter()
+    .map(|s| s.to_string())
+    .collect();
+
+    let script = qemu_argv_script(&argv);
+    assert!(script.contains("qemu-system-x86_64 \\\n    -enable-kvm \\\n    -m 4096 \\\n"));
+    assert!(script.contains("-drive \"file=/vms/my disk.qcow2,if=virtio\""));
+}
+
+#[test]
+fn test_parse_qemu_argv_reads_running_process() -> Result<()> {
+    let cwd = tempfile::tempdir()?;
+    let library = tempfile::tempdir()?;
+    std::fs::write(cwd.path().join("debian.qcow2"), b"disk")?;
+    let argv: Vec<String> = [
+        "qemu-system-x86_64",
+        "-name",
+        "guest=debian-test,debug-threads=on",
+        "-enable-kvm",
+        "-m",
+        "2048",
+        "-smp",
+        "2",
+        "-drive",
+        "file=debian.qcow2,if=virtio",
+        "-monitor",
+        "stdio",
+    ]
+    .iter()
+    .map(|s| s.to_string())
+    .collect();
+
+    let vm = parse_qemu_argv(&argv, cwd.path(), 4242, library.path())?;
+
+    assert_eq!(vm.name, "debian-test");
+    assert_eq!(vm.source, ImportSource::Process);
+    assert_eq!(vm.config_path, Path::new("/proc/4242"));
+    assert_eq!(vm.detected_os_profile.as_deref(), Some("linux-debian"));
+    assert_eq!(vm.qemu_config.memory_mb, 2048);
+    assert_eq!(vm.qemu_config.cpu_cores, 2);
+    assert_eq!(vm.disk_paths, vec![cwd.path().join("debian.qcow2")]);
+    assert_eq!(vm.disks_readable, vec![true]);
+    assert!(vm
+        .import_notes
+        .iter()
+        .any(|n| n.contains("outside the VM library")));
+    assert!(vm.import_notes.iter().any(|n| n.contains("-monitor stdio")));
+
+    assert!(parse_qemu_argv(&["bash".to_string()], cwd.path(), 1, library.path()).is_err());
+    Ok(())
+}
@@ -0,0 +1,121 @@
+use super::*;
+use std::io::Cursor;
+
+const SECTOR: usize = SECTOR_SIZE as usize;
+
+/// One ISO9660 directory record, with an optional Rock Ridge `NM` entry
+fn record(name: &[u8], lba: u32, size: u32, is_dir: bool, rr_name: Option<&str>) -> Vec<u8> {
+    let mut rec = vec![0u8; 33];
+    rec[2..6].copy_from_slice(&lba.to_le_bytes());
+    rec[10..14].copy_from_slice(&size.to_le_bytes());
+    rec[25] = if is_dir { 0x02 } else { 0 };
+    rec[32] = name.len() as u8;
+    rec.extend_from_slice(name);
+    if name.len().is_multiple_of(2) {
+        rec.push(0);
+    }
+    if let Some(rr) = rr_name {
+        rec.extend_from_slice(b"NM");
+        rec.push(5 + rr.len() as u8);
+        rec.extend_from_slice(&[1, 0]);
+        rec.extend_from_slice(rr.as_bytes());
+    }
+    rec[0] = rec.len() as u8;
+    rec
+}
+
+fn dir_sector(lba: u32, children: &[Vec<u8>]) -> Vec<u8> {
+    let mut data = record(&[0], lba, SECTOR as u32, true, None);
+    data.extend(record(&[1], lba, SECTOR as u32, true, None));
+    for child in children {
+        data.extend_from_slice(child);
+    }
+    data
+}
+
+/// Build an image with a root directory at sector 18 and extra sectors after
+fn build_iso(label: &str, root: &[Vec<u8>], extra: &[(usize, Vec<u8>)]) -> Vec<u8> {
+    let mut iso = vec![0u8; 24 * SECTOR];
+    let pvd = &mut iso[16 * SECTOR..17 * SECTOR];
+    pvd[0] = 1;
+    pvd[1..6].copy_from_slice(b"CD001");
+    pvd[40..72].fill(b' ');
+    pvd[40..40 + label.len()].copy_from_slice(label.as_bytes());
+    let root_record = record(&[0], 18, SECTOR as u32, true, None);
+    pvd[156..156 + root_record.len()].copy_from_slice(&root_record);
+
+    let root_dir = dir_sector(18, root);
+    iso[18 * SECTOR..18 * SECTOR + root_dir.len()].copy_from_slice(&root_dir);
+    for (lba, data) in extra {
+        iso[lba * SECTOR..lba * SECTOR + data.len()].copy_from_slice(data);
+    }
+    iso
+}
+
+#[test]
+fn test_detect_ubuntu_from_disk_info() {
+    let info = b"Ubuntu 24.04 LTS \"Noble Numbat\" - Release amd64 (20240424)\n";
+    let iso = build_iso(
+        "Ubuntu 24.04 LTS amd64",
+        &[record(b"_DISK", 19, SECTOR as u32, true, Some(".disk"))],
+        &[
+            (
+                19,
+                dir_sector(
+                    19,
+                    &[record(b"INFO.;1", 20, info.len() as u32, false, None)],
+                ),
+            ),
+            (20, info.to_vec()),
+        ],
+    );
+
+    let detected = detect_iso_os_from(&mut Cursor::new(iso)).unwrap();
+    assert_eq!(detected.profile_id, "linux-ubuntu");
+    assert_eq!(detected.description, "Ubuntu 24.04 LTS");
+}
+
+#[test]
+fn test_detect_from_volume_label() {
+    let iso = build_iso("Fedora-WS-Live-40-1-14", &[], &[]);
+    let detected = detect_iso_os_from(&mut Cursor::new(iso)).unwrap();
+    assert_eq!(detected.profile_id, "linux-fedora");
+    assert_eq!(detected.description, "Fedora-WS-Live-40-1-14");
+
+    let iso = build_iso("ARCH_202405", &[], &[]);
+    let detected = detect_iso_os_from(&mut Cursor::new(iso)).unwrap();
+    assert_eq!(detected.profile_id, "linux-arch");
+}
+
+#[test]
+fn test_detect_windows_needs_sources_and_known_label() {
+    let sources = record(b"SOURCES", 21, SECTOR as u32, true, None);
+
+    let iso = build_iso("GRMCULFRER_EN_DVD", std::slice::from_ref(&sources), &[]);
+    let detected = detect_iso_os_from(&mut Cursor::new(iso)).unwrap();
+    assert_eq!(detected.profile_id, "windows-7");
+
+    // Windows 10 and 11 media are indistinguishable by label
+    let iso = build_iso("CCCOMA_X64FRE_EN-US_DV9", &[sources], &[]);
+    assert_eq!(detect_iso_os_from(&mut Cursor::new(iso)), None);
+}
+
+#[test]
+fn test_detect_inconclusive() {
+    let iso = build_iso("CDROM", &[], &[]);
+    assert_eq!(detect_iso_os_from(&mut Cursor::new(iso)), None);
+
+    // Not an ISO9660 image at all
+    let data = vec![0u8; 20 * SECTOR];
+    assert_eq!(detect_iso_os_from(&mut Cursor::new(data)), None);
+    assert_eq!(detect_iso_os_from(&mut Cursor::new(Vec::new())), None);
+}
+
+#[test]
+fn test_release_name() {
+    assert_eq!(
+        release_name("Linux Mint 21.3 \"Virginia\" - Release amd64 20240114"),
+        "Linux Mint 21.3"
+    );
+    assert_eq!(release_name("Pop_OS 22.04 - Release amd64"), "Pop_OS 22.04");
+}
@@ -0,0 +1,112 @@
+use super::*;
+
+/// A minimal ISO9660 image with only a volume label and an empty root
+fn labelled_iso(label: &str) -> Vec<u8> {
+    let mut iso = vec![0u8; 19 * 2048];
+    let pvd = &mut iso[16 * 2048..17 * 2048];
+    pvd[0] = 1;
+    pvd[1..6].copy_from_slice(b"CD001");
+    pvd[40..72].fill(b' ');
+    pvd[40..40 + label.len()].copy_from_slice(label.as_bytes());
+    let root = &mut pvd[156..190];
+    root[0] = 34;
+    root[2..6].copy_from_slice(&18u32.to_le_bytes());
+    root[10..14].copy_from_slice(&2048u32.to_le_bytes());
+    root[25] = 0x02;
+    root[32] = 1;
+    iso
+}
+
+#[test]
+fn lists_isos_with_size_and_os() {
+    let tmp = tempfile::tempdir().unwrap();
+    std::fs::write(
+        tmp.path().join("Fedora.ISO"),
+        labelled_iso("Fedora-WS-Live-40"),
+    )
+    .unwrap();
+    std::fs::write(tmp.path().join("mystery.iso"), b"not an iso").unwrap();
+    std::fs::write(tmp.path().join("ubuntu.iso.part"), b"half").unwrap();
+    std::fs::write(tmp.path().join("notes.txt"), b"").unwrap();
+    std::fs::create_dir(tmp.path().join("folder.iso")).unwrap();
+
+    let isos = list_isos(tmp.path());
+    let names: Vec<&str> = isos.iter().map(|i| i.name.as_str()).collect();
+    assert_eq!(names, vec!["Fedora.ISO", "mystery.iso", "ubuntu.iso"]);
+
+    assert_eq!(isos[0].size_bytes, 19 * 2048);
+    assert_eq!(
+        isos[0].os.as_ref().map(|os| os.profile_id.as_str()),
+        Some("linux-fedora")
+    );
+    assert!(isos[0].modified.is_some());
+    assert_eq!(isos[1].os, None);
+    assert!(isos[2].partial);
+    assert_eq!(isos[2].path, tmp.path().join("ubuntu.iso.part"));
+}
+
+#[test]
+fn missing_directory_is_empty() {
+    let tmp = tempfile::tempdir().unwrap();
+    assert!(list_isos(&tmp.path().join("isos")).is_empty());
+}
+
+#[test]
+fn delete_removes_the_file() {
+    let tmp = tempfile::tempdir().unwrap();
+    std::fs::write(tmp.path().join("old.iso"), b"data").unwrap();
+    let isos = list_isos(tmp.path());
+    delete_iso(&isos[0], None).unwrap();
+    assert!(list_isos(tmp.path()).is_empty());
+    assert!(delete_iso(&isos[0], None).is_err());
+}
+
+#[test]
+fn delete_refuses_the_running_download() {
+    let tmp = tempfile::tempdir().unwrap();
+    std::fs::write(tmp.path().join("fedora.iso.part"), b"half").unwrap();
+    std::fs::write(tmp.path().join("debian.iso"), b"data").unwrap();
+    let dest = tmp.path().join("fedora.iso");
+    let isos = list_isos(tmp.path());
+
+    assert!(!is_download_target(&isos[0], &dest));
+    assert!(is_download_target(&isos[1], &dest));
+    let err = delete_iso(&isos[1], Some(&dest)).unwrap_err();
+    assert!(err.to_string().contains("still downloading"), "{err}");
+    assert!(tmp.path().join("fedora.iso.part").exists());
+    delete_iso(&isos[0], Some(&dest)).unwrap();
+}
+
+#[test]
+fn finds_the_vms_using_an_iso() {
+    let library = tempfile::tempdir().unwrap();
+    let iso = library.path().join("isos").join("debian.iso");
+    for (id, iso_line) in [
+        ("uses-it", format!("ISO={}", iso.display())),
+        ("uses-backup", format!("ISO={}.bak", iso.display())),
+        ("no-iso", "ISO=\"\"".to_string()),
+    ] {
+        let dir = library.path().join(id);
+        std::fs::create_dir(&dir).unwrap();
+        std::fs::write(
+            dir.join("launch.sh"),
+            format!(
+                "#!/bin/bash\n{}\nqemu-system-x86_64 -m 1024 -cdrom \"$ISO\"\n",
+                iso_line
+            ),
+        )
+        .unwrap();
+    }
+    let vms = crate::vm::discover_vms(library.path()).unwrap();
+    assert_eq!(vms.len(), 3);
+
+    let used_by = vms_using_iso(&vms, &iso);
+    assert_eq!(used_by.len(), 1);
+    assert_eq!(
+        Some(&used_by[0]),
+        vms.iter()
+            .find(|vm| vm.id == "uses-it")
+            .map(|vm| vm.display_name())
+            .as_ref()
+    );
+}
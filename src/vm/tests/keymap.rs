@@ -0,0 +1,86 @@
+use super::*;
+
+#[test]
+fn xkb_layouts_map_to_qemu_keymaps() {
+    assert_eq!(keymap_for_layout("de", ""), Some("de"));
+    assert_eq!(keymap_for_layout("us,de", ""), Some("en-us"));
+    assert_eq!(keymap_for_layout("gb", ""), Some("en-gb"));
+    assert_eq!(keymap_for_layout("ch", ""), Some("de-ch"));
+    assert_eq!(keymap_for_layout("ch", "fr"), Some("fr-ch"));
+    assert_eq!(keymap_for_layout("fr", "bepo"), Some("bepo"));
+    assert_eq!(keymap_for_layout("br", ""), Some("pt-br"));
+    assert_eq!(keymap_for_layout("se", ""), Some("sv"));
+    assert_eq!(keymap_for_layout("kr", ""), None);
+}
+
+#[test]
+fn console_keymaps_map_to_qemu_keymaps() {
+    assert_eq!(keymap_for_layout("de-latin1-nodeadkeys", ""), Some("de"));
+    assert_eq!(keymap_for_layout("uk", ""), Some("en-gb"));
+    assert_eq!(keymap_for_layout("jp106", ""), Some("ja"));
+    assert_eq!(keymap_for_layout("fr_CH", ""), Some("fr-ch"));
+}
+
+#[test]
+fn host_keymap_from_config_files() {
+    let xorg = r#"Section "InputClass"
+        Identifier "system-keyboard"
+        MatchIsKeyboard "on"
+        Option "XkbLayout" "fr"
+        Option "XkbVariant" "bepo"
+EndSection"#;
+    assert_eq!(keymap_from_xorg_conf(xorg), Some("bepo"));
+    assert_eq!(
+        keymap_from_shell_conf("KEYMAP=de-latin1\nFONT=eurlatgr\n"),
+        Some("de")
+    );
+    assert_eq!(
+        keymap_from_shell_conf("XKBMODEL=\"pc105\"\nXKBLAYOUT=\"dk\"\nXKBVARIANT=\"\"\n"),
+        Some("da")
+    );
+    assert_eq!(keymap_from_shell_conf("FONT=eurlatgr\n"), None);
+}
+
+#[test]
+fn next_keymap_starts_at_host_and_wraps_to_unset() {
+    let keymaps: Vec<String> = ["de", "en-us", "fr"]
+        .iter()
+        .map(|k| k.to_string())
+        .collect();
+    assert_eq!(
+        next_keymap(&keymaps, None, Some("en-us"), false).as_deref(),
+        Some("en-us")
+    );
+    assert_eq!(
+        next_keymap(&keymaps, None, None, false).as_deref(),
+        Some("de")
+    );
+    assert_eq!(
+        next_keymap(&keymaps, Some("de"), None, false).as_deref(),
+        Some("en-us")
+    );
+    assert_eq!(next_keymap(&keymaps, Some("fr"), None, false), None);
+    assert_eq!(next_keymap(&keymaps, Some("de"), None, true), None);
+    assert_eq!(
+        next_keymap(&keymaps, None, None, true).as_deref(),
+        Some("fr")
+    );
+}
+
+#[test]
+fn set_script_keymap_adds_replaces_and_removes() {
+    let script = "#!/bin/bash\n# qemu-system-x86_64 -k fr\nqemu-system-x86_64 \\\n    -m 2048 \\\n    -display vnc=:1\n";
+    let with = set_script_keymap(script, Some("de"));
+    assert!(with.contains("qemu-system-x86_64 -k de \\"));
+    assert_eq!(script_keymap(&with).as_deref(), Some("de"));
+    // The comment is left alone
+    assert!(with.contains("# qemu-system-x86_64 -k fr"));
+
+    let replaced = set_script_keymap(&with, Some("fr-ch"));
+    assert_eq!(replaced.matches("-k ").count(), 2);
+    assert_eq!(script_keymap(&replaced).as_deref(), Some("fr-ch"));
+
+    let removed = set_script_keymap(&replaced, None);
+    assert_eq!(removed, script);
+    assert_eq!(script_keymap(&removed), None);
+}
@@ -7,6 +7,18 @@ fn test_extract_memory() {
     assert_eq!(extract_memory("qemu -m 1024 -cpu host"), Some(1024));
 }
 
+#[test]
+fn test_extract_cpu_cores() {
+    assert_eq!(
+        extract_cpu_cores("-smp 4,sockets=1,cores=4,threads=1"),
+        Some(4)
+    );
+    assert_eq!(
+        extract_cpu_cores("-smp cpus=8,sockets=2,cores=2,threads=2"),
+        Some(8)
+    );
+}
+
 #[test]
 fn test_extract_emulator() {
     assert_eq!(
@@ -184,3 +196,99 @@ qemu-system-m68k \
     // Should NOT trigger UEFI detection
     assert!(!config.uefi, "Bios ROM should not trigger UEFI");
 }
+
+#[test]
+fn test_parse_direct_kernel_inline() {
+    let script = r#"#!/bin/bash
+VM_DIR="$(dirname "$(readlink -f "$0")")"
+qemu-system-x86_64 -enable-kvm -m 1024 \
+    -kernel "$VM_DIR/vmlinuz" -initrd initrd.img \
+    -append "console=ttyS0 root=/dev/vda"
+"#;
+    let config = parse_launch_script(Path::new("/vms/tiny/launch.sh"), script).unwrap();
+    let boot = config.direct_kernel.expect("direct kernel boot");
+    assert_eq!(boot.kernel, PathBuf::from("/vms/tiny/vmlinuz"));
+    assert_eq!(boot.initrd, Some(PathBuf::from("/vms/tiny/initrd.img")));
+    assert_eq!(boot.append, "console=ttyS0 root=/dev/vda");
+    assert!(unrecognized_args(script).is_empty());
+}
+
+#[test]
+fn test_extract_serial_socket_from_chardev() {
+    let script = r#"#!/bin/bash
+qemu-system-x86_64 -m 1024 -display none \
+    -chardev socket,id=con0,path=/run/vms/router.sock,server=on,wait=off \
+    -device isa-serial,chardev=con0
+"#;
+    let config = parse_launch_script(Path::new("/vms/router/launch.sh"), script).unwrap();
+    assert_eq!(
+        config.serial_socket,
+        Some(PathBuf::from("/run/vms/router.sock"))
+    );
+
+    // A serial port on stdio has no socket to attach to
+    let stdio = "#!/bin/bash\nqemu-system-x86_64 -m 1024 -nographic -serial mon:stdio\n";
+    let config = parse_launch_script(Path::new("/vms/router/launch.sh"), stdio).unwrap();
+    assert_eq!(config.serial_socket, None);
+}
+
+#[test]
+fn test_unrecognized_args_reports_custom_device() {
+    let script = r#"#!/bin/bash
+VM_DIR="$(dirname "$(readlink -f "$0")")"
+# qemu-system-x86_64 -device commented-out
+qemu-system-x86_64 -enable-kvm -m 2048 -smp 2 \
+    -drive file="$VM_DIR/disk.qcow2",if=virtio \
+    -device qemu-xhci,id=xhci \
+    -device "e1000,netdev=net0" -netdev user,id=net0 \
+    -no-reboot -serial mon:stdio \
+    -vga std $USB_PASSTHROUGH_ARGS
+"#;
+    let config = parse_launch_script(Path::new("/vms/custom/launch.sh"), script).unwrap();
+    assert!(config.network.is_some());
+    assert_eq!(
+        unrecognized_args(script),
+        vec![
+            "-device qemu-xhci,id=xhci".to_string(),
+            "-no-reboot".to_string(),
+            "-serial mon:stdio".to_string(),
+        ]
+    );
+}
+
+#[test]
+fn test_unrecognized_args_empty_for_generated_script() {
+    use crate::wizard_types::WizardQemuConfig;
+
+    let config = WizardQemuConfig {
+        display: "spice-app".to_string(),
+        guest_agent: true,
+        balloon: true,
+        rtc_localtime: true,
+        ..WizardQemuConfig::default()
+    };
+    let script = crate::vm::create::generate_launch_script_with_os(
+        "Windows 11",
+        "disk.qcow2",
+        None,
+        false,
+        &config,
+        Some("windows-11"),
+        None,
+    );
+    assert_eq!(unrecognized_args(&script), Vec::<String>::new());
+}
+
+#[test]
+fn test_parse_rtc_fixed_date() {
+    use crate::vm::qemu_config::{RtcBase, RtcClock};
+
+    let script = "#!/bin/bash\nqemu-system-x86_64 \\\n    -rtc base=1999-12-31T23:59:00,clock=vm \\\n    -m 2048\n";
+    let config = parse_launch_script(Path::new("/vms/clock/launch.sh"), script).unwrap();
+    assert_eq!(
+        config.rtc.base,
+        RtcBase::Fixed("1999-12-31T23:59:00".to_string())
+    );
+    assert_eq!(config.rtc.clock, RtcClock::Vm);
+    assert_eq!(unrecognized_args(script), Vec::<String>::new());
+}
@@ -367,3 +367,1479 @@ esac\n";
         "retrofitted QMP arg should be quoted:\n{patched}"
     );
 }
+
+#[test]
+fn test_generate_boot_order_section_default_is_empty() {
+    assert!(generate_boot_order_section(&BootOrder::default()).is_empty());
+}
+
+#[test]
+fn test_boot_order_section_keeps_media_boot_device() {
+    let boot = BootOrder {
+        order: Some("dc".to_string()),
+        menu: false,
+    };
+    let section = generate_boot_order_section(&boot);
+    assert!(section.contains("BOOT_ORDER_ARGS=\"-boot order=dc\""));
+    // Install/CD-ROM branches must not get the normal-boot order
+    assert!(section.contains("if [[ -n \"$1\" ]]; then\n    BOOT_ORDER_ARGS=\"\"\nfi"));
+}
+
+#[test]
+fn test_boot_order_section_menu_only_has_no_branch_override() {
+    let boot = BootOrder {
+        order: None,
+        menu: true,
+    };
+    let section = generate_boot_order_section(&boot);
+    assert!(section.contains("BOOT_ORDER_ARGS=\"-boot menu=on\""));
+    assert!(!section.contains("if [[ -n \"$1\" ]]"));
+}
+
+#[test]
+fn test_roundtrip_boot_order() {
+    let script = "#!/bin/bash\ncase \"$1\" in\n    --install)\n        qemu-system-x86_64 \\\n        -boot d\n        ;;\n    \"\")\n        qemu-system-x86_64 -m 2048\n        ;;\nesac\n";
+    let boot = BootOrder {
+        order: Some("cdn".to_string()),
+        menu: true,
+    };
+    let section = generate_boot_order_section(&boot);
+    let with_section = insert_args_section(script, &section, "$BOOT_ORDER_ARGS");
+
+    assert_eq!(with_section.matches("$BOOT_ORDER_ARGS").count(), 2);
+    assert!(with_section.contains("-boot d $BOOT_ORDER_ARGS"));
+    assert_eq!(parse_boot_order_section(&with_section), boot);
+
+    let removed = remove_boot_order_section(&with_section);
+    assert!(!removed.contains("BOOT_ORDER"));
+    assert_eq!(parse_boot_order_section(&removed), BootOrder::default());
+}
+
+#[test]
+fn test_generate_tools_iso_section() {
+    let section = generate_tools_iso_section(Path::new("/isos/virtio-win.iso"));
+    assert!(section.contains("TOOLS_ISO=/isos/virtio-win.iso\n"));
+    assert!(
+        section.contains("TOOLS_ISO_ARGS=(-drive \"file=${TOOLS_ISO//,/,,},media=cdrom,index=3\")")
+    );
+}
+
+#[test]
+fn test_roundtrip_floppy_drive() {
+    let script = "#!/bin/bash\ncase \"$1\" in\n    --floppy)\n        qemu-system-i386 -fda \"$2\"\n        ;;\n    \"\")\n        qemu-system-i386 -m 64\n        ;;\nesac\n";
+    let image = Path::new("/home/user/Floppies/Monkey Island (disk 1).img");
+    let section = generate_floppy_drive_section(image);
+    assert!(section.contains(
+        "FLOPPY_DRIVE_ARGS=(-drive \"file=${FLOPPY_DRIVE//,/,,},if=floppy,index=1,format=raw\")"
+    ));
+    let with_section = insert_args_section(script, &section, FLOPPY_DRIVE_VAR_REF);
+    assert_eq!(with_section.matches(FLOPPY_DRIVE_VAR_REF).count(), 2);
+    assert_eq!(
+        parse_floppy_drive_section(&with_section).as_deref(),
+        Some(image)
+    );
+    // The floppy isn't mistaken for the VM's disk
+    let parsed = crate::vm::launch_parser::parse_launch_script(
+        Path::new("/vms/dos/launch.sh"),
+        &with_section,
+    )
+    .unwrap();
+    assert!(parsed.disks.is_empty());
+
+    let removed = remove_floppy_drive_section(&with_section);
+    assert!(!removed.contains("FLOPPY_DRIVE"));
+    assert_eq!(parse_floppy_drive_section(&removed), None);
+}
+
+#[test]
+fn test_roundtrip_tools_iso_with_spaces() {
+    let script = "#!/bin/bash\ncase \"$1\" in\n    --install)\n        qemu-system-x86_64 \\\n        -drive file=\"$ISO\",media=cdrom,index=1\n        ;;\n    \"\")\n        qemu-system-x86_64 -m 2048\n        ;;\nesac\n";
+    let iso = Path::new("/home/user/My ISOs/virtio-win's.iso");
+    let section = generate_tools_iso_section(iso);
+    let with_section = insert_args_section(script, &section, TOOLS_ISO_VAR_REF);
+
+    assert_eq!(with_section.matches(TOOLS_ISO_VAR_REF).count(), 2);
+    assert_eq!(parse_tools_iso_section(&with_section).as_deref(), Some(iso));
+
+    // Removing the tools ISO leaves no trace in the QEMU commands
+    let removed = remove_tools_iso_section(&with_section);
+    assert!(!removed.contains("TOOLS_ISO"));
+    assert!(removed.contains("-drive file=\"$ISO\",media=cdrom,index=1\n"));
+    assert!(removed.contains("qemu-system-x86_64 -m 2048\n"));
+    assert_eq!(parse_tools_iso_section(&removed), None);
+}
+
+#[test]
+fn test_roundtrip_direct_kernel_with_spaces() {
+    use crate::vm::launch_parser::parse_launch_script;
+
+    let script = "#!/bin/bash\ncase \"$1\" in\n    --install)\n        qemu-system-x86_64 -m 2048\n        ;;\n    \"\")\n        qemu-system-x86_64 -m 2048\n        ;;\nesac\n";
+    let boot = DirectKernelBoot {
+        kernel: PathBuf::from("/boot/My Kernels/vmlinuz-6.1"),
+        initrd: Some(PathBuf::from("/boot/initrd.img")),
+        append: "root=/dev/vda1 console=ttyS0 quiet".to_string(),
+    };
+    let section = generate_direct_kernel_section(&boot);
+    let with_section = insert_args_section(script, &section, DIRECT_KERNEL_VAR_REF);
+
+    assert_eq!(with_section.matches(DIRECT_KERNEL_VAR_REF).count(), 2);
+    let config = parse_launch_script(Path::new("/vms/test/launch.sh"), &with_section).unwrap();
+    assert_eq!(config.direct_kernel, Some(boot));
+
+    // Removing the section leaves no trace in the QEMU commands
+    let removed = remove_args_section(
+        &with_section,
+        DIRECT_KERNEL_MARKER_START,
+        DIRECT_KERNEL_MARKER_END,
+        DIRECT_KERNEL_VAR_REF,
+    );
+    assert!(!removed.contains("DIRECT_KERNEL"));
+    assert_eq!(removed.matches("qemu-system-x86_64 -m 2048\n").count(), 2);
+}
+
+#[test]
+fn test_with_serial_socket_appends_to_every_command() {
+    use crate::vm::launch_parser::parse_launch_script;
+
+    let script = "#!/bin/bash\nVM_DIR=\"$(dirname \"$(readlink -f \"$0\")\")\"\ncase \"$1\" in\n    --install)\n        qemu-system-x86_64 -m 2048 \\\n        -display none\n        ;;\n    \"\")\n        qemu-system-x86_64 -m 2048 \\\n        -display none\n        ;;\nesac\n";
+    let patched = with_serial_socket(script).unwrap();
+    assert_eq!(patched.matches(SERIAL_SOCKET_ARG).count(), 2);
+
+    let config = parse_launch_script(Path::new("/vms/server/launch.sh"), &patched).unwrap();
+    assert_eq!(
+        config.serial_socket,
+        Some(PathBuf::from("/vms/server").join(SERIAL_SOCKET))
+    );
+}
+
+#[test]
+fn test_with_serial_socket_replaces_terminal_serial() {
+    let script = "#!/bin/bash\nqemu-system-x86_64 -m 512 -nographic -serial mon:stdio\n";
+    let patched = with_serial_socket(script).unwrap();
+    assert_eq!(
+        patched,
+        format!(
+            "#!/bin/bash\nqemu-system-x86_64 -m 512 -nographic {}\n",
+            SERIAL_SOCKET_ARG
+        )
+    );
+
+    // Nowhere to put the arg in a script without a recognizable QEMU block
+    assert!(with_serial_socket("#!/bin/bash\nexec qemu-system-x86_64 -m 512\n").is_err());
+}
+
+#[test]
+fn test_relay_console_forwards_input_until_detach() {
+    use std::io::Read;
+    use std::os::unix::net::{UnixListener, UnixStream};
+
+    let dir = tempfile::tempdir().unwrap();
+    let socket = dir.path().join(SERIAL_SOCKET);
+    let listener = UnixListener::bind(&socket).unwrap();
+    let guest = thread::spawn(move || {
+        let (mut conn, _) = listener.accept().unwrap();
+        let mut received = Vec::new();
+        conn.read_to_end(&mut received).unwrap();
+        received
+    });
+
+    let stream = UnixStream::connect(&socket).unwrap();
+    let input = std::io::Cursor::new(b"root\r\x1dnot sent".to_vec());
+    relay_console(stream, input, std::io::sink()).unwrap();
+
+    assert_eq!(guest.join().unwrap(), b"root\r");
+}
+
+#[test]
+fn test_replace_kvm_with_tcg_rewrites_accel_and_cpu() {
+    let script = "#!/bin/bash\n\
+# -enable-kvm is mentioned in a comment\n\
+qemu-system-x86_64 \\\n\
+    -enable-kvm \\\n\
+    -machine q35,accel=kvm,smm=on \\\n\
+    -cpu host,kvm=off \\\n\
+    -m 4096M\n";
+    let out = replace_kvm_with_tcg(script);
+    assert!(out.contains("# -enable-kvm is mentioned in a comment"));
+    assert!(!out.contains("    -enable-kvm"));
+    assert!(out.contains("-machine q35,accel=tcg,smm=on"));
+    assert!(out.contains("-cpu max,kvm=off"));
+    assert!(out.ends_with("-m 4096M\n"));
+}
+
+#[test]
+fn test_replace_kvm_with_tcg_rewrites_quoted_cpu_values() {
+    // The generator and the CPU editor quote values that carry flags
+    let script = [
+        "qemu-system-x86_64 \\",
+        "    -cpu 'host,kvm=off,hv_vendor_id=1234567890ab,hypervisor=off' \\",
+        "    -m 4096M",
+        "",
+    ]
+    .join("\n");
+    let out = replace_kvm_with_tcg(&script);
+    assert!(out.contains("    -cpu 'max,kvm=off,hv_vendor_id=1234567890ab,hypervisor=off' \\\n"));
+
+    let out = replace_kvm_with_tcg("qemu-system-x86_64 -cpu \"host,+svm,hv-passthrough\" -m 1G\n");
+    assert_eq!(out, "qemu-system-x86_64 -cpu \"max,+svm\" -m 1G\n");
+
+    let out = replace_kvm_with_tcg("qemu-system-x86_64 -cpu 'host' -m 1G\n");
+    assert_eq!(out, "qemu-system-x86_64 -cpu 'max' -m 1G\n");
+    assert_eq!(
+        replace_kvm_with_tcg("qemu-system-x86_64 -cpu 'qemu64,+ssse3'\n"),
+        "qemu-system-x86_64 -cpu 'qemu64,+ssse3'\n"
+    );
+}
+
+#[test]
+fn test_replace_kvm_with_tcg_leaves_other_cpu_models() {
+    let out = replace_kvm_with_tcg("qemu-system-i386 -accel kvm -cpu hostile-model\n");
+    assert_eq!(out, "qemu-system-i386 -accel tcg -cpu hostile-model\n");
+}
+
+#[test]
+fn test_set_cpu_arg_replaces_existing_model() {
+    let script = [
+        "#!/bin/bash",
+        "# -cpu host in a comment stays",
+        "qemu-system-x86_64 \\",
+        "    -cpu host \\",
+        "    -m 4096M",
+        "",
+    ]
+    .join("\n");
+    let out = set_cpu_arg(&script, "host,kvm=off,hv_relaxed");
+    assert!(out.contains("# -cpu host in a comment stays"));
+    assert!(out.contains("    -cpu 'host,kvm=off,hv_relaxed' \\\n"));
+    assert!(out.ends_with("-m 4096M\n"));
+
+    // Quoted values written by the generator are replaced whole
+    let again = set_cpu_arg(&out, "qemu64");
+    assert!(again.contains("    -cpu qemu64 \\\n"));
+    assert!(!again.contains("hv_relaxed"));
+}
+
+#[test]
+fn test_set_cpu_arg_adds_missing_cpu() {
+    let script = "qemu-system-x86_64 \\\n    -m 512M\n";
+    let out = set_cpu_arg(script, "max");
+    assert_eq!(out, "qemu-system-x86_64 -cpu max \\\n    -m 512M\n");
+}
+
+#[test]
+fn test_append_qemu_arg_after_last_arg_of_each_block() {
+    let script = "#!/bin/bash\n\
+case \"$1\" in\n\
+    --install)\n\
+        qemu-system-x86_64 \\\n\
+        -cdrom \"$ISO\"\n\
+        ;;\n\
+    *)\n\
+        qemu-system-x86_64 \\\n\
+        -display gtk\n\
+        ;;\n\
+esac\n";
+
+    let patched = append_qemu_arg(script, INCOMING_ARG);
+
+    assert_eq!(patched.matches("-incoming").count(), 2);
+    assert!(patched.contains("-display gtk \\\n        -incoming \"exec:gzip -c -d"));
+    assert!(patched.contains("suspend.state.gz'\"\n;;"));
+}
+
+#[test]
+fn test_migration_status() {
+    let reply = r#"{"return": {"status": "active", "total-time": 120}}"#;
+    assert_eq!(migration_status(reply).as_deref(), Some("active"));
+    assert_eq!(migration_status(r#"{"return": {}}"#), None);
+}
+
+#[test]
+fn test_qmp_reply_error() {
+    assert!(qmp_reply_error(r#"{"return": {}}"#).is_ok());
+    let err =
+        qmp_reply_error(r#"{"error": {"class": "GenericError", "desc": "busy"}}"#).unwrap_err();
+    assert_eq!(err.to_string(), "busy");
+}
+
+/// Serve `connections` QMP sessions on the VM's socket, answering each
+/// command with `reply(command)`. Returns the commands in the order sent.
+fn fake_qmp(
+    vm_path: &Path,
+    connections: usize,
+    reply: impl Fn(&str) -> String + Send + 'static,
+) -> thread::JoinHandle<Vec<String>> {
+    use std::io::{BufRead, BufReader, Write};
+    use std::os::unix::net::UnixListener;
+
+    let listener = UnixListener::bind(vm_path.join("qemu.sock")).unwrap();
+    thread::spawn(move || {
+        let mut commands = Vec::new();
+        for _ in 0..connections {
+            let (mut conn, _) = listener.accept().unwrap();
+            let mut reader = BufReader::new(conn.try_clone().unwrap());
+            let mut line = String::new();
+            conn.write_all(b"{\"QMP\": {}}\n").unwrap();
+            reader.read_line(&mut line).unwrap();
+            conn.write_all(b"{\"return\": {}}\n").unwrap();
+            line.clear();
+            reader.read_line(&mut line).unwrap();
+            let request: serde_json::Value = serde_json::from_str(&line).unwrap();
+            let command = request["execute"].as_str().unwrap().to_string();
+            conn.write_all(format!("{}\n", reply(&command)).as_bytes())
+                .unwrap();
+            commands.push(command);
+        }
+        commands
+    })
+}
+
+#[test]
+fn test_finish_resume_continues_the_guest() {
+    let dir = tempfile::tempdir().unwrap();
+    std::fs::write(dir.path().join(SUSPEND_STATE_FILE), b"state").unwrap();
+    let polls = std::sync::atomic::AtomicUsize::new(0);
+    let qemu = fake_qmp(dir.path(), 3, move |command| match command {
+        "query-migrate" => {
+            let status = match polls.fetch_add(1, std::sync::atomic::Ordering::SeqCst) {
+                0 => "active",
+                _ => "completed",
+            };
+            format!(r#"{{"return": {{"status": "{}"}}}}"#, status)
+        }
+        _ => r#"{"return": {}}"#.to_string(),
+    });
+
+    finish_resume(dir.path()).unwrap();
+
+    assert_eq!(
+        qemu.join().unwrap(),
+        vec!["query-migrate", "query-migrate", "cont"]
+    );
+    assert!(!has_suspend_state(dir.path()));
+}
+
+#[test]
+fn test_media_boots_refused_while_suspended() {
+    let dir = tempfile::tempdir().unwrap();
+    let iso = PathBuf::from("/isos/installer.iso");
+    assert!(check_boot_keeps_suspend_state(dir.path(), &BootMode::Install).is_ok());
+
+    std::fs::write(dir.path().join(SUSPEND_STATE_FILE), b"state").unwrap();
+    assert!(check_boot_keeps_suspend_state(dir.path(), &BootMode::Normal).is_ok());
+    assert!(check_boot_keeps_suspend_state(dir.path(), &BootMode::Ephemeral).is_ok());
+    assert!(check_boot_keeps_suspend_state(dir.path(), &BootMode::Install).is_err());
+    assert!(check_boot_keeps_suspend_state(dir.path(), &BootMode::Cdrom(iso)).is_err());
+}
+
+#[test]
+fn test_balloon_actual_mb() {
+    let reply = r#"{"return": {"actual": 2147483648}}"#;
+    assert_eq!(balloon_actual_mb(reply), Some(2048));
+    assert_eq!(balloon_actual_mb(r#"{"return": {}}"#), None);
+}
+
+#[test]
+fn test_hotplugged_usb_from_qom_list() {
+    let reply = r#"{"return": [
+        {"name": "type", "type": "string"},
+        {"name": "usbhot-3-7", "type": "child<usb-host>"},
+        {"name": "net0", "type": "child<virtio-net-pci>"},
+        {"name": "usbhot-bad", "type": "child<usb-host>"}
+    ]}"#;
+    assert_eq!(hotplugged_usb_from_qom_list(reply), vec![(3, 7)]);
+    assert_eq!(usb_hotplug_id(3, 7), "usbhot-3-7");
+    assert!(hotplugged_usb_from_qom_list("{}").is_empty());
+}
+
+#[test]
+fn test_removable_drives_from_query_block() {
+    let reply = r#"{"return": [
+        {"device": "ide0-hd0", "removable": false, "inserted": {"file": "/vms/dos/disk.qcow2"}},
+        {"device": "floppy1", "removable": true, "tray_open": false},
+        {"device": "floppy0", "removable": true, "inserted": {"file": "/isos/disk1.img"}},
+        {"device": "ide1-cd0", "removable": true, "inserted": {"file": "/isos/dos622.iso"}},
+        {"device": "cd0", "removable": true}
+    ]}"#;
+    let floppies = removable_drives_from_query_block(reply, MediaKind::Floppy);
+    assert_eq!(
+        floppies,
+        vec![
+            RemovableDrive {
+                device: "floppy0".to_string(),
+                kind: MediaKind::Floppy,
+                image: Some(PathBuf::from("/isos/disk1.img")),
+            },
+            RemovableDrive {
+                device: "floppy1".to_string(),
+                kind: MediaKind::Floppy,
+                image: None,
+            },
+        ]
+    );
+    assert_eq!(floppies[0].label(), "Drive A:");
+    assert_eq!(floppies[1].label(), "Drive B:");
+
+    let cdroms = removable_drives_from_query_block(reply, MediaKind::Cdrom);
+    let devices: Vec<&str> = cdroms.iter().map(|d| d.device.as_str()).collect();
+    assert_eq!(devices, vec!["cd0", "ide1-cd0"]);
+    assert_eq!(cdroms[1].image, Some(PathBuf::from("/isos/dos622.iso")));
+    assert_eq!(cdroms[0].label(), "CD-ROM cd0");
+
+    assert!(removable_drives_from_query_block("{}", MediaKind::Cdrom).is_empty());
+    assert!(removable_drives_from_query_block("not json", MediaKind::Floppy).is_empty());
+}
+
+#[test]
+fn test_usb_section_roundtrip_with_port() {
+    let devices = vec![
+        UsbPassthrough {
+            vendor_id: 0x0781,
+            product_id: 0x5581,
+            usb_version: UsbVersion::Usb3,
+            port: Some(UsbPort {
+                bus: 2,
+                path: "1.4".to_string(),
+            }),
+        },
+        UsbPassthrough {
+            vendor_id: 0x046d,
+            product_id: 0xc077,
+            usb_version: UsbVersion::Usb2,
+            port: None,
+        },
+    ];
+    let section = generate_usb_section(&devices);
+    assert!(section.contains(
+        "-device usb-host,bus=xhci.0,hostbus=2,hostport=1.4,vendorid=0x0781,productid=0x5581"
+    ));
+    assert!(section.contains("-device usb-host,vendorid=0x046d,productid=0xc077\""));
+
+    let parsed = parse_usb_section(&section);
+    assert_eq!(parsed.len(), 2);
+    assert_eq!(
+        parsed[0].port,
+        Some(UsbPort {
+            bus: 2,
+            path: "1.4".to_string()
+        })
+    );
+    assert!(parsed[0].is_usb3());
+    assert_eq!(parsed[1].port, None);
+    assert_eq!(parsed[1].vendor_id, 0x046d);
+}
+
+#[test]
+fn test_summarize_launch_errors_prefers_error_lines() {
+    let lines = vec![
+        "starting up".to_string(),
+        "qemu-system-x86_64: -drive file=x: Could not open 'x'".to_string(),
+    ];
+    assert_eq!(
+        summarize_launch_errors(&lines, "exit status: 1"),
+        "qemu-system-x86_64: -drive file=x: Could not open 'x'"
+    );
+    assert_eq!(
+        summarize_launch_errors(&["just noise".to_string()], "exit status: 1"),
+        "just noise"
+    );
+    assert_eq!(
+        summarize_launch_errors(&[], "exit status: 1"),
+        "VM process exited with code: exit status: 1"
+    );
+}
+
+#[test]
+fn test_launch_timeout_leaves_no_qemu_behind() {
+    use std::os::unix::fs::PermissionsExt;
+
+    let dir = tempfile::tempdir().unwrap();
+    // A "QEMU" that never answers on QMP and records its pid
+    let qemu = dir.path().join("qemu-system-x86_64");
+    std::fs::write(&qemu, "#!/bin/bash\necho $$ > qemu.pid\nexec sleep 30\n").unwrap();
+    std::fs::set_permissions(&qemu, std::fs::Permissions::from_mode(0o755)).unwrap();
+    std::fs::write(
+        dir.path().join("launch.sh"),
+        "#!/bin/bash\n# -qmp unix:qemu.sock\n./qemu-system-x86_64 -m 64\n",
+    )
+    .unwrap();
+    let vm = process_test_vm("hang", dir.path(), &[]);
+
+    let result = test_launch(&vm, Duration::from_secs(1)).unwrap();
+    assert!(matches!(result, TestLaunch::TimedOut { .. }), "{result:?}");
+
+    let pid = std::fs::read_to_string(dir.path().join("qemu.pid")).unwrap();
+    let stat = format!("/proc/{}/stat", pid.trim());
+    let deadline = Instant::now() + Duration::from_secs(5);
+    // Gone, or a zombie waiting for init to reap it
+    while std::fs::read_to_string(&stat).is_ok_and(|s| !s.contains(") Z ")) {
+        assert!(
+            Instant::now() < deadline,
+            "QEMU survived the test launch timeout"
+        );
+        thread::sleep(Duration::from_millis(100));
+    }
+}
+
+#[test]
+fn test_launch_stage_lines() {
+    assert_eq!(
+        launch_stage("vm-curator: binding PCI devices to vfio-pci (waiting for pkexec/sudo)"),
+        Some("binding PCI devices to vfio-pci (waiting for pkexec/sudo)")
+    );
+    assert_eq!(launch_stage("qemu-system-x86_64: warning"), None);
+}
+
+/// Spawn `sh -c script` in `dir` and watch it like a launched VM
+fn watch_script(dir: &std::path::Path, script: &str) -> StartupWatch {
+    let mut child = Command::new("sh")
+        .arg("-c")
+        .arg(script)
+        .stdout(Stdio::null())
+        .stderr(Stdio::piped())
+        .spawn()
+        .unwrap();
+    let (output, _) = collect_stderr(child.stderr.take().unwrap());
+    StartupWatch {
+        child,
+        output,
+        vm_path: dir.to_path_buf(),
+    }
+}
+
+#[test]
+fn test_startup_watch_reports_stuck_elevation() {
+    let dir = tempfile::tempdir().unwrap();
+    let mut watch = watch_script(
+        dir.path(),
+        "echo 'vm-curator: waiting for pkexec/sudo' >&2; exec sleep 3",
+    );
+    let mut stages = Vec::new();
+    let health = watch.wait(Duration::from_millis(700), |s| stages.push(s.to_string()));
+    watch.child.kill().unwrap();
+    watch.child.wait().unwrap();
+
+    assert_eq!(stages, vec!["waiting for pkexec/sudo"]);
+    match health {
+        StartupHealth::Stuck { stage, output } => {
+            assert_eq!(stage.as_deref(), Some("waiting for pkexec/sudo"));
+            assert!(output.contains("waiting for pkexec/sudo"));
+        }
+        other => panic!("expected Stuck, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_startup_watch_reports_late_failure() {
+    let dir = tempfile::tempdir().unwrap();
+    let mut watch = watch_script(dir.path(), "echo 'qemu: vfio error: no IOMMU' >&2; exit 1");
+    let health = watch.wait(Duration::from_secs(5), |_| {});
+    assert_eq!(
+        health,
+        StartupHealth::Exited("qemu: vfio error: no IOMMU".to_string())
+    );
+}
+
+#[test]
+fn test_launch_warnings_skip_benign_lines() {
+    let lines: Vec<String> = [
+        "vm-curator: waiting for pkexec/sudo",
+        "qemu-system-x86_64: warning: host doesn't support requested feature: CPUID.01H:ECX.vmx [bit 5]",
+        "(qemu-system-x86_64:4242): Gtk-WARNING **: 12:00:00.000: Theme parsing error",
+        "dbind-WARNING **: Couldn't connect to accessibility bus",
+        "qemu-system-x86_64: -soundhw hda: '-soundhw' is deprecated, please use '-audiodev'",
+        "VNC server running on ::1:5900",
+    ]
+    .iter()
+    .map(|s| s.to_string())
+    .collect();
+    assert_eq!(
+        launch_warnings(&lines),
+        vec![
+            "qemu-system-x86_64: warning: host doesn't support requested feature: CPUID.01H:ECX.vmx [bit 5]",
+            "qemu-system-x86_64: -soundhw hda: '-soundhw' is deprecated, please use '-audiodev'",
+        ]
+    );
+    assert!(launch_warnings(&[]).is_empty());
+}
+
+#[test]
+fn test_failing_option_names_the_device() {
+    let lines: Vec<String> = [
+        "vm-curator: binding 0000:01:00.0 to vfio-pci",
+        "qemu-system-x86_64: -device vfio-pci,host=0000:01:00.0,romfile=/vms/gpu.rom: \
+         failed to find romfile \"/vms/gpu.rom\"",
+    ]
+    .iter()
+    .map(|s| s.to_string())
+    .collect();
+    assert_eq!(
+        failing_option(&lines).as_deref(),
+        Some("-device vfio-pci,host=0000:01:00.0,romfile=/vms/gpu.rom")
+    );
+    // Errors that don't name an argument
+    let lines = vec!["qemu-system-x86_64: failed to initialize kvm: Permission denied".to_string()];
+    assert_eq!(failing_option(&lines), None);
+}
+
+#[test]
+fn test_pci_devices_in_script() {
+    let script = format!(
+        "#!/bin/bash\n{}\nPCI_PASSTHROUGH_ARGS=\"-device vfio-pci,host=0000:01:00.0\"\n\
+         PCI_DEVICES=(\"0000:01:00.0\" \"0000:01:00.1\")\n{}\n",
+        PCI_MARKER_START, PCI_MARKER_END
+    );
+    assert_eq!(
+        pci_devices_in_script(&script),
+        vec!["0000:01:00.0", "0000:01:00.1"]
+    );
+    assert!(pci_devices_in_script("PCI_DEVICES=(\"0000:01:00.0\")").is_empty());
+}
+
+#[test]
+fn test_is_polkit_agent() {
+    for agent in [
+        "polkit-gnome-au",
+        "polkit-kde-auth",
+        "lxpolkit",
+        "hyprpolkitagent",
+        "gnome-shell\n",
+    ] {
+        assert!(is_polkit_agent(agent), "{agent}");
+    }
+    assert!(!is_polkit_agent("polkitd"));
+    assert!(!is_polkit_agent("bash"));
+}
+
+#[test]
+fn test_add_cached_sudo_branch() {
+    let compact = "_pci_elevated() {\n    if [[ $EUID -eq 0 ]]; then sh -c \"$1\"\n    elif command -v pkexec >/dev/null 2>&1; then pkexec sh -c \"$1\"\n";
+    let patched = add_cached_sudo_branch(compact);
+    assert!(patched.contains(
+        "then sh -c \"$1\"\n    elif sudo -n true 2>/dev/null; then sudo sh -c \"$1\"\n    elif command -v pkexec"
+    ));
+    assert_eq!(add_cached_sudo_branch(&patched), patched);
+
+    let long = "    if [[ $EUID -eq 0 ]]; then\n        sh -c \"$1\"\n    elif command -v pkexec >/dev/null 2>&1; then\n";
+    let patched = add_cached_sudo_branch(long);
+    assert!(
+        patched.contains("    elif sudo -n true 2>/dev/null; then\n        sudo sh -c \"$1\"\n")
+    );
+    assert_eq!(add_cached_sudo_branch(&patched), patched);
+}
+
+/// Run the hook wrapper around `launch` (a launch.sh body) in `dir`
+fn run_hook_wrapper(dir: &Path, launch: &str) -> std::process::Output {
+    std::fs::write(dir.join("launch.sh"), launch).unwrap();
+    Command::new("bash")
+        .current_dir(dir)
+        .arg("-c")
+        .arg(HOOK_WRAPPER)
+        .arg("vm-curator-hooks")
+        .arg(dir.join("launch.sh"))
+        .env("VM_DIR", dir)
+        .output()
+        .unwrap()
+}
+
+#[test]
+fn test_failing_pre_launch_hook_aborts_launch() {
+    let dir = tempfile::tempdir().unwrap();
+    std::fs::write(
+        dir.path().join(PRE_LAUNCH_HOOK),
+        "echo stopping display manager\nexit 3\n",
+    )
+    .unwrap();
+
+    let out = run_hook_wrapper(dir.path(), "touch launched\n");
+    assert_eq!(out.status.code(), Some(3));
+    assert!(!dir.path().join("launched").exists());
+
+    let stderr = String::from_utf8_lossy(&out.stderr);
+    assert!(stderr.contains("pre_launch.sh failed with exit code 3"));
+    let log = std::fs::read_to_string(dir.path().join(LAUNCH_LOG)).unwrap();
+    assert!(log.contains("stopping display manager"));
+}
+
+#[test]
+fn test_post_stop_hook_runs_after_vm_exits() {
+    let dir = tempfile::tempdir().unwrap();
+    std::fs::write(dir.path().join(PRE_LAUNCH_HOOK), "echo pre ok\n").unwrap();
+    std::fs::write(
+        dir.path().join(POST_STOP_HOOK),
+        "echo \"post exit=$VM_EXIT_CODE pid=${VM_PID:+set}\"\n",
+    )
+    .unwrap();
+
+    let out = run_hook_wrapper(dir.path(), "echo qemu-ran >&2\nexit 5\n");
+    // The launch script's own status and stderr pass through unchanged
+    assert_eq!(out.status.code(), Some(5));
+    assert!(String::from_utf8_lossy(&out.stderr).contains("qemu-ran"));
+
+    let log = std::fs::read_to_string(dir.path().join(LAUNCH_LOG)).unwrap();
+    let pre = log.find("pre ok").expect("pre-launch output logged");
+    let post = log
+        .find("post exit=5 pid=set")
+        .expect("post-stop output logged");
+    assert!(pre < post);
+}
+
+#[test]
+fn test_hook_template_documents_env_vars() {
+    let template = hook_template(PRE_LAUNCH_HOOK);
+    assert!(template.starts_with("#!/bin/bash\n"));
+    assert!(template.contains("aborts the launch"));
+    for (name, _) in HOOK_ENV_VARS {
+        assert!(template.contains(name), "template should mention {}", name);
+    }
+    assert!(!hook_template(POST_STOP_HOOK).contains("aborts the launch"));
+}
+
+#[test]
+fn test_record_launch_roundtrips_through_metadata() {
+    let tmp = tempfile::tempdir().unwrap();
+    std::fs::write(
+        tmp.path().join("vm-curator.toml"),
+        "display_name = \"Test VM\"\n",
+    )
+    .unwrap();
+    assert!(load_last_launched(tmp.path()).is_none());
+    assert_eq!(load_launch_count(tmp.path()), 0);
+
+    let before = chrono::Local::now() - chrono::Duration::seconds(1);
+    record_launch(tmp.path()).unwrap();
+    let launched = load_last_launched(tmp.path()).expect("launch time recorded");
+    assert!(launched >= before && launched <= chrono::Local::now());
+    assert_eq!(load_launch_count(tmp.path()), 1);
+    record_launch(tmp.path()).unwrap();
+    assert_eq!(load_launch_count(tmp.path()), 2);
+
+    // Other metadata is kept
+    let content = std::fs::read_to_string(tmp.path().join("vm-curator.toml")).unwrap();
+    assert!(content.contains("display_name = \"Test VM\""));
+}
+
+#[test]
+fn test_autostart_roundtrips_through_metadata() {
+    let tmp = tempfile::tempdir().unwrap();
+    std::fs::write(
+        tmp.path().join("vm-curator.toml"),
+        "display_name = \"Test VM\"\n",
+    )
+    .unwrap();
+    assert!(!load_autostart(tmp.path()));
+
+    set_autostart(tmp.path(), true).unwrap();
+    assert!(load_autostart(tmp.path()));
+    set_autostart(tmp.path(), false).unwrap();
+    assert!(!load_autostart(tmp.path()));
+
+    let content = std::fs::read_to_string(tmp.path().join("vm-curator.toml")).unwrap();
+    assert!(!content.contains("autostart"));
+    assert!(content.contains("display_name = \"Test VM\""));
+}
+
+#[test]
+fn test_skip_install_media_boot_keeps_cdrom_attached() {
+    let script = "#!/bin/bash\n# -boot d\nqemu-system-x86_64 \\\n    -boot order=dc,menu=on \\\n    -drive file=disk.qcow2,if=virtio \\\n    -cdrom install.iso\n";
+    let skipped = skip_install_media_boot(script);
+    assert!(skipped.contains("-boot order=c,menu=on \\\n"));
+    assert!(skipped.contains("-cdrom install.iso\n"));
+    assert!(skipped.contains("# -boot d\n"), "comments are left alone");
+
+    let rewrite = |line: &str| skip_install_media_boot(line);
+    assert_eq!(rewrite("qemu -boot d -m 2048"), "qemu -boot c -m 2048");
+    assert_eq!(rewrite("qemu -boot once=d"), "qemu -boot order=c");
+    assert_eq!(rewrite("qemu -boot once=d,menu=on"), "qemu -boot menu=on");
+    assert_eq!(rewrite("qemu -boot $BOOT_FLAGS"), "qemu -boot $BOOT_FLAGS");
+    assert_eq!(
+        rewrite(
+            "qemu -device ide-cd,drive=cd0,bootindex=0 -device virtio-blk,drive=hd,bootindex=1"
+        ),
+        "qemu -device ide-cd,drive=cd0 -device virtio-blk,drive=hd,bootindex=1"
+    );
+    // Nothing to skip leaves the script as it was
+    let plain = "qemu-system-x86_64 -boot c -drive file=disk.qcow2\n";
+    assert_eq!(skip_install_media_boot(plain), plain);
+}
+
+#[test]
+fn test_skip_install_media_boot_rewrites_boot_order_section() {
+    let boot = BootOrder {
+        order: Some("dc".to_string()),
+        menu: true,
+    };
+    let section = generate_boot_order_section(&boot);
+    assert!(section.contains("BOOT_ORDER_ARGS=\"-boot order=dc,menu=on\"\n"));
+
+    let skipped = skip_install_media_boot(&section);
+    assert!(
+        skipped.contains("BOOT_ORDER_ARGS=\"-boot order=c,menu=on\"\n"),
+        "{skipped}"
+    );
+    assert_eq!(
+        skip_install_media_boot("BOOT_ORDER_ARGS=\"-boot order=dc\""),
+        "BOOT_ORDER_ARGS=\"-boot order=c\""
+    );
+    assert_eq!(
+        skip_install_media_boot("BOOT_ORDER_ARGS='-boot d'"),
+        "BOOT_ORDER_ARGS='-boot c'"
+    );
+}
+
+#[test]
+fn test_first_boot_done_roundtrips_through_metadata() {
+    let tmp = tempfile::tempdir().unwrap();
+    assert_eq!(load_first_boot_done(tmp.path()), None);
+    set_first_boot_done(tmp.path(), true).unwrap();
+    assert_eq!(load_first_boot_done(tmp.path()), Some(true));
+    set_first_boot_done(tmp.path(), false).unwrap();
+    assert_eq!(load_first_boot_done(tmp.path()), Some(false));
+}
+
+/// A VM with id `id` in `dir`, using the disk images `disks`
+fn process_test_vm(id: &str, dir: &Path, disks: &[&Path]) -> DiscoveredVm {
+    use crate::vm::qemu_config::{DiskConfig, DiskFormat, QemuConfig};
+
+    let mut config = QemuConfig::default();
+    for disk in disks {
+        config.disks.push(DiskConfig {
+            path: disk.to_path_buf(),
+            format: DiskFormat::Qcow2,
+            interface: "virtio".to_string(),
+            throttle: Default::default(),
+        });
+    }
+    DiscoveredVm {
+        id: id.to_string(),
+        path: dir.to_path_buf(),
+        launch_script: dir.join("launch.sh"),
+        config,
+        custom_name: None,
+        os_profile: None,
+        notes: None,
+        unrecognized_args: Vec::new(),
+        last_launched: None,
+        launch_count: 0,
+        autostart: true,
+        first_boot_done: None,
+        custom_ascii: None,
+        shared_disks: Vec::new(),
+    }
+}
+
+/// A QEMU process `pid` running in `cwd` with `args` (emulator omitted)
+fn test_process(pid: u32, cwd: Option<&Path>, args: &[&str]) -> QemuProcess {
+    let mut argv = vec!["qemu-system-x86_64".to_string()];
+    argv.extend(args.iter().map(|a| a.to_string()));
+    QemuProcess {
+        pid,
+        cmdline: argv.join(" "),
+        process_tag: process_tag(&argv),
+        args: argv,
+        cwd: cwd.map(Path::to_path_buf),
+        guest_agent_ready: false,
+        balloon_mb: None,
+    }
+}
+
+#[test]
+fn test_vm_process_pid_matches_cwd_then_disk_path() {
+    let vm = process_test_vm(
+        "alpha",
+        Path::new("/vms/alpha"),
+        &[Path::new("/vms/alpha/disk.qcow2")],
+    );
+    let process = |pid, cwd: Option<&str>, cmdline: &str| QemuProcess {
+        pid,
+        cmdline: cmdline.to_string(),
+        args: Vec::new(),
+        cwd: cwd.map(PathBuf::from),
+        process_tag: None,
+        guest_agent_ready: false,
+        balloon_mb: None,
+    };
+
+    let other = process(
+        10,
+        Some("/vms/beta"),
+        "qemu-system-x86_64 /vms/alpha/disk.qcow2",
+    );
+    assert_eq!(vm_process_pid(&vm, &[other]), None);
+    let by_cwd = process(11, Some("/vms/alpha"), "qemu-system-x86_64");
+    assert_eq!(vm_process_pid(&vm, &[by_cwd]), Some(11));
+    let by_disk = process(12, None, "qemu-system-x86_64 -hda /vms/alpha/disk.qcow2");
+    assert_eq!(vm_process_pid(&vm, &[by_disk]), Some(12));
+
+    // The process= tag wins over a cwd that can't be read or points elsewhere
+    let mut tagged = process(13, None, "alpha");
+    tagged.process_tag = Some(vm.id.clone());
+    let elsewhere = process(14, Some("/vms/alpha"), "qemu-system-x86_64");
+    assert_eq!(vm_process_pid(&vm, &[elsewhere, tagged]), Some(13));
+}
+
+#[test]
+fn test_vm_process_pid_compares_whole_disk_paths() {
+    let vm = process_test_vm(
+        "alpha",
+        Path::new("/vms/alpha"),
+        &[Path::new("/vms/alpha/disk.qcow2")],
+    );
+    // A longer path starting with the VM's disk is another image
+    let longer = test_process(
+        1,
+        None,
+        &["-drive", "file=/vms/alpha/disk.qcow2.old,if=virtio"],
+    );
+    assert_eq!(vm_process_pid(&vm, &[longer]), None);
+    let exact = test_process(
+        2,
+        None,
+        &[
+            "-snapshot",
+            "-drive",
+            "file=/vms/alpha/disk.qcow2,if=virtio",
+        ],
+    );
+    assert_eq!(vm_process_pid(&vm, &[exact]), Some(2));
+
+    // -daemonize leaves QEMU in /, so its disks decide
+    let daemonized = test_process(
+        3,
+        Some(Path::new("/")),
+        &[
+            "-daemonize",
+            "-blockdev",
+            "driver=file,node-name=d0,filename=/vms/alpha/disk.qcow2",
+        ],
+    );
+    assert_eq!(vm_process_pid(&vm, &[daemonized]), Some(3));
+    let relative = test_process(4, Some(Path::new("/")), &["-hda", "disk.qcow2"]);
+    assert_eq!(vm_process_pid(&vm, &[relative]), None);
+}
+
+#[test]
+fn test_vm_process_pid_resolves_symlinked_library() -> anyhow::Result<()> {
+    let tmp = tempfile::tempdir()?;
+    let real = tmp.path().join("real");
+    std::fs::create_dir_all(real.join("alpha"))?;
+    std::fs::write(real.join("alpha/disk.qcow2"), b"")?;
+    let link = tmp.path().join("library");
+    std::os::unix::fs::symlink(&real, &link)?;
+
+    // The library is configured through the symlink; /proc reports real paths
+    let vm = process_test_vm("alpha", &link.join("alpha"), &[Path::new("disk.qcow2")]);
+    let by_cwd = test_process(1, Some(&real.join("alpha")), &["-hda", "disk.qcow2"]);
+    assert_eq!(vm_process_pid(&vm, &[by_cwd]), Some(1));
+    let disk = real.join("alpha/disk.qcow2");
+    let by_disk = test_process(2, None, &["-hda", disk.to_str().unwrap()]);
+    assert_eq!(vm_process_pid(&vm, &[by_disk]), Some(2));
+    Ok(())
+}
+
+#[test]
+fn test_running_vm_pids_are_unambiguous() {
+    let shared = Path::new("/vms/shared.qcow2");
+    let win = process_test_vm(
+        "win",
+        Path::new("/vms/win"),
+        &[Path::new("/vms/win/disk.qcow2"), shared],
+    );
+    let win_old = process_test_vm(
+        "win-old",
+        Path::new("/vms/win-old"),
+        &[Path::new("/vms/win-old/disk.qcow2"), shared],
+    );
+    let vms = vec![win, win_old];
+
+    // Runs in win-old's directory; "/vms/win" is only a prefix of it
+    let by_cwd = test_process(
+        10,
+        Some(Path::new("/vms/win-old")),
+        &["-hda", "/vms/shared.qcow2"],
+    );
+    // Only the shared disk to go on: could be either VM
+    let shared_only = test_process(11, None, &["-hda", "/vms/shared.qcow2"]);
+    // Tagged by a generated launch script
+    let tagged = test_process(
+        12,
+        Some(Path::new("/")),
+        &["-name", "Windows,process=win", "-daemonize"],
+    );
+
+    let pids = running_vm_pids(&vms, &[by_cwd, shared_only]);
+    assert_eq!(pids.get("win-old"), Some(&10));
+    assert_eq!(pids.get("win"), None);
+
+    let pids = running_vm_pids(&vms, &[tagged]);
+    assert_eq!(pids.get("win"), Some(&12));
+    assert_eq!(pids.len(), 1);
+}
+
+#[test]
+fn test_process_tag() {
+    let argv = |args: &[&str]| args.iter().map(|a| a.to_string()).collect::<Vec<_>>();
+    assert_eq!(
+        process_tag(&argv(&[
+            "qemu-system-x86_64",
+            "-name",
+            "Windows 98, SE,,process=x,process=win98",
+            "-m",
+            "512",
+        ])),
+        Some("win98".to_string())
+    );
+    assert_eq!(
+        process_tag(&argv(&[
+            "qemu-system-x86_64",
+            "-name",
+            "guest=dos,process=dos-622"
+        ])),
+        Some("dos-622".to_string())
+    );
+    assert_eq!(
+        process_tag(&argv(&["qemu-system-x86_64", "-name", "plain"])),
+        None
+    );
+    assert_eq!(
+        process_tag(&argv(&["qemu-system-x86_64", "-m", "512"])),
+        None
+    );
+}
+
+#[test]
+fn test_window_title_in_generated_script() {
+    let script = "#!/bin/bash\nVM_DIR=\"$(dirname \"$(readlink -f \"$0\")\")\"\nVM_TITLE='Windows 98'\nqemu-system-i386 -name \"${VM_TITLE//,/,,},process=${VM_DIR##*/}\" -m 512\n";
+    assert_eq!(script_window_title(script), Some("Windows 98".to_string()));
+
+    let updated = set_script_window_title(script, "Bob's DOS box").unwrap();
+    assert_eq!(
+        script_window_title(&updated),
+        Some("Bob's DOS box".to_string())
+    );
+    assert_eq!(updated.matches("-name").count(), 1);
+    assert_eq!(updated.lines().count(), script.lines().count());
+    assert!(updated.ends_with('\n'));
+}
+
+#[test]
+fn test_window_title_added_to_older_scripts() {
+    let script =
+        "#!/bin/bash\ncd /vms/dos\nqemu-system-i386 -name dos -m 512 \\\n    -hda disk.img\n";
+    let updated = set_script_window_title(script, "DOS").unwrap();
+    assert_eq!(
+        updated,
+        format!(
+            "#!/bin/bash\n{}\nVM_TITLE=DOS\ncd /vms/dos\nqemu-system-i386 {} -m 512 \\\n    -hda disk.img\n",
+            VM_DIR_LINE, WINDOW_NAME_ARG
+        )
+    );
+    assert_eq!(script_window_title(&updated), Some("DOS".to_string()));
+    assert!(set_script_window_title("#!/bin/bash\necho hi\n", "DOS").is_none());
+}
+
+#[test]
+fn test_display_window_roundtrip_and_backend_filtering() {
+    let script = "#!/bin/bash\nqemu-system-x86_64 \\\n    -vga virtio \\\n    -display gtk,gl=on \\\n    -m 4096\n";
+    let window = DisplayWindow {
+        zoom_to_fit: true,
+        full_screen: true,
+        resolution: Some((1920, 1080)),
+        num_displays: 2,
+    };
+
+    let applied = apply_display_window(script, &window);
+    assert!(applied.contains("-display gtk,gl=on,zoom-to-fit=on \\\n"));
+    assert!(applied.contains("-global virtio-gpu-base.xres=1920"));
+    assert!(applied.contains("-global virtio-gpu-base.max_outputs=2"));
+    assert_eq!(parse_display_window(&applied), window);
+
+    // Applying again doesn't duplicate anything
+    let reapplied = apply_display_window(&applied, &window);
+    assert_eq!(reapplied.matches("$DISPLAY_WINDOW_ARGS").count(), 1);
+    assert_eq!(reapplied.matches("zoom-to-fit").count(), 1);
+    assert_eq!(reapplied.matches("-full-screen").count(), 1);
+
+    // SDL has no zoom-to-fit; VNC has no local window at all
+    let sdl = fit_display_window_to_backend(&applied.replace("-display gtk", "-display sdl"));
+    assert!(sdl.contains("-display sdl,gl=on \\\n"));
+    assert_eq!(
+        parse_display_window(&sdl),
+        DisplayWindow {
+            zoom_to_fit: false,
+            ..window
+        }
+    );
+    let vnc = fit_display_window_to_backend(&sdl.replace("-display sdl", "-display vnc"));
+    assert!(!vnc.contains("-full-screen"));
+    assert_eq!(parse_display_window(&vnc).resolution, Some((1920, 1080)));
+    assert_eq!(parse_display_window(&vnc).num_displays, 1);
+
+    // Turning everything off removes the managed section
+    let cleared = apply_display_window(&applied, &DisplayWindow::default());
+    assert!(!cleared.contains("DISPLAY_WINDOW"));
+    assert!(cleared.contains("-display gtk,gl=on \\\n"));
+}
+
+#[test]
+fn test_display_window_qxl_heads_are_extra_devices() {
+    let script = "#!/bin/bash\nqemu-system-x86_64 \\\n    -vga qxl \\\n    -display spice-app \\\n    -m 4096\n";
+    assert_eq!(script_graphics(script), VgaType::Qxl);
+
+    let window = DisplayWindow {
+        resolution: Some((1280, 720)),
+        num_displays: 3,
+        ..Default::default()
+    };
+    let applied = apply_display_window(script, &window);
+    assert_eq!(applied.matches("-device qxl").count(), 2);
+    assert!(!applied.contains("max_outputs"));
+    // The -global in the managed section doesn't make it look like virtio-gpu
+    assert_eq!(script_graphics(&applied), VgaType::Qxl);
+    assert_eq!(parse_display_window(&applied), window);
+
+    assert_eq!(DisplayWindow::default().num_displays, 1);
+    let mut window = DisplayWindow {
+        num_displays: crate::vm::qemu_config::MAX_DISPLAYS,
+        ..Default::default()
+    };
+    window.num_displays = window.next_num_displays();
+    assert_eq!(window.num_displays, 1);
+}
+
+#[test]
+fn test_display_window_next_resolution_wraps_to_guest_default() {
+    use crate::vm::qemu_config::RESOLUTION_PRESETS;
+
+    let mut window = DisplayWindow::default();
+    for preset in RESOLUTION_PRESETS {
+        window.resolution = window.next_resolution();
+        assert_eq!(window.resolution, Some(*preset));
+    }
+    assert_eq!(window.next_resolution(), None);
+}
+
+#[test]
+fn test_apply_rtc_replaces_appends_and_removes() {
+    use crate::vm::qemu_config::{RtcBase, RtcClock, RtcConfig};
+
+    let script = "#!/bin/bash\nqemu-system-x86_64 \\\n    -rtc base=localtime \\\n    -m 4096\n";
+    let fixed = RtcConfig {
+        base: RtcBase::Fixed("2001-01-01".to_string()),
+        clock: RtcClock::Vm,
+        extra: Vec::new(),
+    };
+    let replaced = apply_rtc(script, &fixed).unwrap();
+    assert!(replaced.contains("    -rtc base=2001-01-01,clock=vm \\\n    -m 4096\n"));
+    assert!(!replaced.contains("localtime"));
+
+    let removed = apply_rtc(&replaced, &RtcConfig::default()).unwrap();
+    assert_eq!(removed, "#!/bin/bash\nqemu-system-x86_64 \\\n    -m 4096\n");
+
+    // Removing the last option drops the continuation before it
+    let last = "#!/bin/bash\nqemu-system-x86_64 \\\n    -m 4096 \\\n    -rtc base=localtime\n";
+    assert_eq!(
+        apply_rtc(last, &RtcConfig::default()).unwrap(),
+        "#!/bin/bash\nqemu-system-x86_64 \\\n    -m 4096\n"
+    );
+}
+
+#[test]
+fn test_set_custom_ascii_round_trip() {
+    let tmp = tempfile::tempdir().unwrap();
+    let source = tmp.path().join("logo.txt");
+    std::fs::write(&source, " /\\_/\\\n( o.o )\n").unwrap();
+
+    let art = set_custom_ascii(tmp.path(), Some(&source)).unwrap();
+    assert_eq!(art.as_deref(), Some(" /\\_/\\\n( o.o )\n"));
+    assert_eq!(load_custom_ascii(tmp.path()), art);
+
+    assert_eq!(set_custom_ascii(tmp.path(), None).unwrap(), None);
+    assert!(!tmp.path().join(CUSTOM_ASCII_FILE).exists());
+    assert_eq!(load_custom_ascii(tmp.path()), None);
+}
+
+#[test]
+fn test_set_custom_ascii_rejects_escape_sequences() {
+    let tmp = tempfile::tempdir().unwrap();
+    let source = tmp.path().join("logo.txt");
+    std::fs::write(&source, "\x1b[31mred\x1b[0m\n").unwrap();
+
+    assert!(set_custom_ascii(tmp.path(), Some(&source)).is_err());
+    assert!(!tmp.path().join(CUSTOM_ASCII_FILE).exists());
+}
+
+#[test]
+fn test_check_shared_disks_blocks_while_other_vm_runs() {
+    use crate::vm::discovery::SharedDisk;
+    use crate::vm::qemu_config::QemuConfig;
+
+    let vm_with = |id: &str, shared: Vec<SharedDisk>| DiscoveredVm {
+        id: id.to_string(),
+        path: PathBuf::from("/vms").join(id),
+        launch_script: PathBuf::from("/vms").join(id).join("launch.sh"),
+        config: QemuConfig::default(),
+        custom_name: None,
+        os_profile: None,
+        notes: None,
+        unrecognized_args: Vec::new(),
+        last_launched: None,
+        launch_count: 0,
+        autostart: false,
+        first_boot_done: None,
+        custom_ascii: None,
+        shared_disks: shared,
+    };
+    let shared = |other: &str| {
+        vec![SharedDisk {
+            path: PathBuf::from("/vms/alpha/disk.qcow2"),
+            vm_ids: vec![other.to_string()],
+        }]
+    };
+    let vms = vec![
+        vm_with("alpha", shared("beta")),
+        vm_with("beta", shared("alpha")),
+    ];
+
+    assert!(check_shared_disks(&vms[0], &vms, |_| false).is_ok());
+    let err = check_shared_disks(&vms[0], &vms, |id| id == "beta").unwrap_err();
+    assert!(err.to_string().contains("also used by Beta"));
+    // The VM's own running state is checked elsewhere
+    assert!(check_shared_disks(&vms[0], &vms, |id| id == "alpha").is_ok());
+}
+
+#[test]
+fn test_check_disks_not_open_finds_qemu_process_and_loop_device() {
+    use crate::vm::qemu_config::{DiskConfig, DiskFormat, QemuConfig};
+
+    let tmp = tempfile::tempdir().unwrap();
+    let disk = tmp.path().join("disk.qcow2");
+    std::fs::write(&disk, "").unwrap();
+    let sys_block = tmp.path().join("sys-block");
+    std::fs::create_dir_all(sys_block.join("loop3/loop")).unwrap();
+
+    let mut config = QemuConfig::default();
+    config.disks.push(DiskConfig {
+        path: disk.clone(),
+        format: DiskFormat::Qcow2,
+        interface: "virtio".to_string(),
+        throttle: Default::default(),
+    });
+    let vm = DiscoveredVm {
+        id: "alpha".to_string(),
+        path: tmp.path().to_path_buf(),
+        launch_script: tmp.path().join("launch.sh"),
+        config,
+        custom_name: None,
+        os_profile: None,
+        notes: None,
+        unrecognized_args: Vec::new(),
+        last_launched: None,
+        launch_count: 0,
+        autostart: false,
+        first_boot_done: None,
+        custom_ascii: None,
+        shared_disks: Vec::new(),
+    };
+    let process = |cwd: &Path, cmdline: &str| QemuProcess {
+        pid: 42,
+        cmdline: cmdline.to_string(),
+        args: Vec::new(),
+        cwd: Some(cwd.to_path_buf()),
+        process_tag: None,
+        guest_agent_ready: false,
+        balloon_mb: None,
+    };
+
+    // Another disk with a similar name doesn't count
+    let unrelated = process(tmp.path(), "qemu-system-x86_64 -hda old-disk.qcow2");
+    assert_eq!(process_using_disk(&disk, &[unrelated]), None);
+    let by_name = process(
+        tmp.path(),
+        "qemu-system-x86_64 -drive file=disk.qcow2,if=virtio",
+    );
+    assert_eq!(process_using_disk(&disk, &[by_name]), Some(42));
+    let by_path = process(
+        Path::new("/"),
+        &format!("qemu-system-x86_64 -hda {}", disk.display()),
+    );
+    let err = check_disks_not_open(&vm, &[by_path], &sys_block).unwrap_err();
+    assert!(err.to_string().contains("QEMU process 42"));
+
+    std::fs::write(
+        sys_block.join("loop3/loop/backing_file"),
+        format!("{}\n", std::fs::canonicalize(&disk).unwrap().display()),
+    )
+    .unwrap();
+    let err = check_disks_not_open(&vm, &[], &sys_block).unwrap_err();
+    assert!(err.to_string().contains("/dev/loop3"));
+}
+
+#[test]
+fn test_add_snapshot_arg_patches_each_qemu_command() {
+    let script = "#!/bin/bash\n\
+# qemu-system-x86_64 is started below\n\
+command -v qemu-system-x86_64 >/dev/null || exit 1\n\
+case \"$1\" in\n\
+    --install)\n\
+        qemu-system-x86_64 \\\n\
+        -cdrom \"$ISO\"\n\
+        ;;\n\
+    *)\n\
+        exec /usr/bin/qemu-system-x86_64 -m 2048 \\\n\
+        -display gtk\n\
+        ;;\n\
+esac\n";
+
+    let patched = add_snapshot_arg(script).unwrap();
+
+    assert_eq!(patched.matches("-snapshot").count(), 2);
+    assert!(patched.contains("\nqemu-system-x86_64 -snapshot \\\n"));
+    assert!(patched.contains("exec /usr/bin/qemu-system-x86_64 -snapshot -m 2048 \\\n"));
+    // Comments and availability checks are left alone
+    assert!(patched.contains("# qemu-system-x86_64 is started below\n"));
+    assert!(patched.contains("command -v qemu-system-x86_64 >/dev/null"));
+    assert!(patched.ends_with("esac\n"));
+
+    assert_eq!(add_snapshot_arg("#!/bin/bash\n\"$QEMU\" -m 512\n"), None);
+}
+
+#[test]
+fn test_serve_spice_over_socket() {
+    let script = "#!/bin/bash\nqemu-system-x86_64 -m 2048 \\\n        -display spice-app,gl=on \\\n        -device virtio-vga-gl\n";
+    let endpoint = SpiceEndpoint::Socket(PathBuf::from("/vms/my vm/spice.sock"));
+
+    let served = serve_spice(script, &endpoint, Some("remote-viewer")).unwrap();
+
+    assert!(served.starts_with("#!/bin/bash\n# SPICE server"));
+    assert!(!served.contains("spice-app"));
+    assert!(served
+        .contains("-spice 'unix=on,addr=/vms/my vm/spice.sock,disable-ticketing=on,gl=on' \\\n"));
+    // A stale socket from an unclean stop is removed first, and on exit
+    assert!(served.contains("rm -f '/vms/my vm/spice.sock'\n"));
+    assert!(served.contains("trap \"rm -f '/vms/my vm/spice.sock'\" EXIT\n"));
+    assert!(served.contains("exec remote-viewer 'spice+unix:///vms/my vm/spice.sock'"));
+
+    assert_eq!(
+        serve_spice(
+            "#!/bin/bash\nqemu-system-x86_64 -display gtk\n",
+            &endpoint,
+            None
+        ),
+        None
+    );
+}
+
+#[test]
+fn test_serve_spice_over_tcp() {
+    let script = "qemu-system-x86_64 -display spice-app,gl=on\n";
+
+    let served = serve_spice(script, &SpiceEndpoint::Tcp(5931), None).unwrap();
+
+    // GL needs the local socket; no viewer installed means no viewer job
+    assert!(served.ends_with("qemu-system-x86_64 -spice port=5931,disable-ticketing=on\n"));
+    assert!(!served.contains("remote-viewer"));
+    assert!(!served.contains("rm -f"));
+    assert_eq!(SpiceEndpoint::Tcp(5931).uri(), "spice://127.0.0.1:5931");
+}
+
+#[test]
+fn test_apply_disk_throttle_rewrites_each_drive_of_the_disk() {
+    let vm_dir = Path::new("/vms/alpha");
+    let script = "#!/bin/bash\n\
+VM_DIR=\"$(dirname \"$(readlink -f \"$0\")\")\"\n\
+DISK=\"$VM_DIR/disk.qcow2\"\n\
+case \"$1\" in\n\
+    --install)\n\
+        qemu-system-x86_64 -drive file=\"$DISK\",format=qcow2,if=virtio,iops_rd=10 \\\n\
+        -drive file=\"$ISO\",media=cdrom,index=1\n\
+        ;;\n\
+    *)\n\
+        qemu-system-x86_64 -drive file=\"$DISK\",format=qcow2,if=virtio \\\n\
+        -drive file=other.qcow2,format=qcow2,if=virtio\n\
+        ;;\n\
+esac\n";
+    let throttle = DiskThrottle {
+        iops_read: Some(500),
+        bps_write: Some(10 * 1024 * 1024),
+        ..Default::default()
+    };
+
+    let patched =
+        apply_disk_throttle(script, vm_dir, &vm_dir.join("disk.qcow2"), &throttle).unwrap();
+
+    let limits = "throttling.iops-read=500,throttling.bps-write=10485760";
+    assert_eq!(patched.matches(limits).count(), 2);
+    assert!(patched.contains(&format!("if=virtio,{} \\\n", limits)));
+    assert!(!patched.contains("iops_rd"));
+    // Other drives are untouched
+    assert!(patched.contains("-drive file=\"$ISO\",media=cdrom,index=1\n"));
+    assert!(patched.contains("-drive file=other.qcow2,format=qcow2,if=virtio\n"));
+
+    // Clearing the limits leaves the drive as it was
+    let cleared = apply_disk_throttle(
+        &patched,
+        vm_dir,
+        &vm_dir.join("disk.qcow2"),
+        &DiskThrottle::default(),
+    )
+    .unwrap();
+    assert!(!cleared.contains("throttling."));
+
+    assert!(apply_disk_throttle(script, vm_dir, Path::new("/elsewhere.qcow2"), &throttle).is_err());
+}
+
+#[test]
+fn test_apply_network_shaping_adds_and_removes_section() {
+    use crate::vm::launch_parser::parse_launch_script;
+
+    let script = "#!/bin/bash\n\
+VM_DIR=\"$(dirname \"$(readlink -f \"$0\")\")\"\n\
+qemu-system-x86_64 -m 2048 \\\n\
+    -netdev bridge,id=net0,br=br0 \\\n\
+    -device virtio-net-pci,netdev=net0\n";
+    let shaping = NetworkShaping {
+        rate_kbit: Some(1024),
+        delay_ms: Some(80),
+        loss_percent: None,
+    };
+
+    let shaped = apply_network_shaping(script, &shaping);
+    assert!(shaped.contains(NET_SHAPING_MARKER_START));
+    assert!(shaped.contains("NET_SHAPING=\"delay 80ms rate 1024kbit\"\n"));
+    // The section goes before QEMU and adds no arguments to it
+    assert!(shaped.find("shape_network </dev/null").unwrap() < shaped.find("qemu-system").unwrap());
+    assert!(shaped.ends_with("-device virtio-net-pci,netdev=net0\n"));
+
+    let config = parse_launch_script(Path::new("/vms/alpha/launch.sh"), &shaped).unwrap();
+    assert_eq!(config.network.unwrap().shaping, shaping);
+
+    // Saving again replaces the section rather than adding a second one
+    let reshaped = apply_network_shaping(&shaped, &shaping);
+    assert_eq!(reshaped.matches(NET_SHAPING_MARKER_START).count(), 1);
+
+    let cleared = apply_network_shaping(&shaped, &NetworkShaping::default());
+    assert!(!cleared.contains("NET_SHAPING"));
+    assert!(!cleared.contains("shape_network"));
+    assert!(cleared.ends_with("-device virtio-net-pci,netdev=net0\n"));
+}
+
+#[test]
+fn guest_addresses_skip_loopback_and_link_local() {
+    let reply = serde_json::json!([
+        {
+            "name": "lo",
+            "ip-addresses": [{"ip-address-type": "ipv4", "ip-address": "127.0.0.1", "prefix": 8}]
+        },
+        {
+            "name": "eth0",
+            "hardware-address": "52:54:00:12:34:56",
+            "ip-addresses": [
+                {"ip-address-type": "ipv4", "ip-address": "192.168.1.50", "prefix": 24},
+                {"ip-address-type": "ipv6", "ip-address": "fe80::5054:ff:fe12:3456", "prefix": 64}
+            ]
+        }
+    ]);
+    assert_eq!(guest_addresses(&reply), vec!["eth0 192.168.1.50/24"]);
+    assert!(guest_addresses(&serde_json::json!({})).is_empty());
+}
@@ -0,0 +1,67 @@
+use super::*;
+
+const STAT: &str = "4242 (qemu (x86) vm) S 1 4242 4242 0 -1 4194560 100 0 0 0 \
+                    250 50 0 0 20 0 5 0 1000 123456 789\n";
+
+#[test]
+fn test_parse_stat_counts_fields_after_comm() {
+    assert_eq!(
+        parse_stat(STAT),
+        Some(StatFields {
+            cpu_ticks: 300,
+            start_ticks: 1000,
+        })
+    );
+    assert_eq!(parse_stat("4242 (qemu) S 1"), None);
+}
+
+#[test]
+fn test_parse_vm_rss_kb() {
+    let status = "Name:\tqemu-system-x86\nVmPeak:\t 9000 kB\nVmRSS:\t  524288 kB\n";
+    assert_eq!(parse_vm_rss_kb(status), Some(524288));
+    assert_eq!(parse_vm_rss_kb("Name:\tkthreadd\n"), None);
+}
+
+#[test]
+fn test_sample_in() {
+    let dir = tempfile::tempdir().unwrap();
+    let pid_dir = dir.path().join("4242");
+    std::fs::create_dir(&pid_dir).unwrap();
+    std::fs::write(pid_dir.join("stat"), STAT).unwrap();
+    std::fs::write(pid_dir.join("status"), "VmRSS:\t  2048 kB\n").unwrap();
+    std::fs::write(dir.path().join("uptime"), "3610.50 7000.00\n").unwrap();
+
+    let sample = sample_in(dir.path(), 4242).unwrap();
+    assert_eq!(sample.cpu_ticks, 300);
+    assert_eq!(sample.rss_bytes, 2048 * 1024);
+    // Started 10s after boot
+    assert_eq!(sample.uptime.as_secs(), 3600);
+
+    assert_eq!(sample_in(dir.path(), 1), None);
+}
+
+#[test]
+fn test_cpu_percent() {
+    let sample = |cpu_ticks| ProcSample {
+        cpu_ticks,
+        rss_bytes: 0,
+        uptime: Duration::ZERO,
+    };
+    // 150 ticks in 1s is 1.5 CPUs
+    let pct = cpu_percent(&sample(100), &sample(250), Duration::from_secs(1));
+    assert!((pct - 150.0).abs() < 0.01);
+    assert_eq!(cpu_percent(&sample(100), &sample(250), Duration::ZERO), 0.0);
+}
+
+#[test]
+fn test_format_uptime() {
+    assert_eq!(format_uptime(Duration::from_secs(59)), "00:00:59");
+    assert_eq!(
+        format_uptime(Duration::from_secs(3 * 3600 + 61)),
+        "03:01:01"
+    );
+    assert_eq!(
+        format_uptime(Duration::from_secs(2 * 86400 + 5)),
+        "2d 00:00:05"
+    );
+}
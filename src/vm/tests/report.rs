@@ -0,0 +1,92 @@
+use super::*;
+use crate::vm::qemu_config::QemuConfig;
+
+fn redaction() -> Redaction {
+    Redaction {
+        home: Some(PathBuf::from("/home/alice")),
+        secrets: true,
+    }
+}
+
+#[test]
+fn redact_shortens_home_paths() {
+    let text = "-drive file=/home/alice/vm-space/win/disk.qcow2\n";
+    assert_eq!(
+        redact(text, &redaction()),
+        "-drive file=~/vm-space/win/disk.qcow2\n"
+    );
+}
+
+#[test]
+fn redact_matches_home_on_path_boundaries() {
+    let redaction = Redaction {
+        home: Some(PathBuf::from("/home/al")),
+        secrets: false,
+    };
+    let text = "file=/home/al/disk.qcow2 file=/home/alice/disk.qcow2\n\
+                ISO=\"/home/al\" cd /mnt/home/al/isos; ls /home/al";
+    assert_eq!(
+        redact(text, &redaction),
+        "file=~/disk.qcow2 file=/home/alice/disk.qcow2\n\
+         ISO=\"~\" cd /mnt/home/al/isos; ls ~"
+    );
+}
+
+#[test]
+fn redact_blanks_passwords_and_secrets() {
+    let text = "SPICE_PASSWORD=\"hunter2\"\n\
+                -spice port=5930,password=hunter2,disable-ticketing=off\n\
+                -object secret,id=sec0,data=hunter2\n\
+                -vnc :1,password-secret=sec0";
+    let redacted = redact(text, &redaction());
+    assert!(!redacted.contains("hunter2"), "{}", redacted);
+    assert!(redacted.contains("SPICE_PASSWORD=<redacted>"));
+    assert!(redacted.contains("password=<redacted>,disable-ticketing=off"));
+    assert!(redacted.contains("data=<redacted>"));
+}
+
+#[test]
+fn redact_can_be_turned_off() {
+    let text = "file=/home/alice/disk.qcow2,password=hunter2";
+    assert_eq!(redact(text, &Redaction::default()), text);
+}
+
+#[test]
+fn bug_report_bundles_script_config_and_log() {
+    let tmp = tempfile::tempdir().unwrap();
+    let dir = tmp.path();
+    let script = "#!/bin/bash\nqemu-system-x86_64 -m 2048 -spice port=5930,password=hunter2\n";
+    std::fs::write(dir.join("launch.sh"), script).unwrap();
+    std::fs::write(dir.join(LAUNCH_LOG), "booted\n").unwrap();
+    let vm = DiscoveredVm {
+        id: "test-vm".to_string(),
+        path: dir.to_path_buf(),
+        launch_script: dir.join("launch.sh"),
+        config: QemuConfig {
+            raw_script: script.to_string(),
+            ..Default::default()
+        },
+        custom_name: None,
+        os_profile: None,
+        notes: None,
+        unrecognized_args: Vec::new(),
+        last_launched: None,
+        launch_count: 0,
+        autostart: false,
+        first_boot_done: None,
+        custom_ascii: None,
+        shared_disks: Vec::new(),
+    };
+
+    let report = bug_report(&vm, "[CPU]\nModel: test\n", &redaction());
+    assert!(report.contains("qemu-system-x86_64 -m 2048"));
+    assert!(report.contains("Model: test"));
+    assert!(report.contains("booted"));
+    assert!(report.contains("## Parsed configuration"));
+    assert!(!report.contains("hunter2"));
+    // launch.sh appears once, not again inside the parsed config
+    assert_eq!(report.matches("qemu-system-x86_64 -m 2048").count(), 1);
+
+    let path = save_bug_report(dir, &vm.id, &report).unwrap();
+    assert_eq!(std::fs::read_to_string(&path).unwrap(), report);
+}
@@ -71,3 +71,39 @@ fn test_validate_snapshot_name() {
     let result = validate_snapshot_name("-test").unwrap();
     assert_eq!(result, "_-test");
 }
+
+fn snap(name: &str) -> Snapshot {
+    Snapshot {
+        id: String::new(),
+        name: name.to_string(),
+        size: String::new(),
+        vm_state_bytes: 0,
+        date: String::new(),
+        date_sec: 0,
+        vm_clock: String::new(),
+    }
+}
+
+#[test]
+fn test_scheduled_snapshots_to_prune() {
+    let snapshots = vec![
+        snap("auto-20260103-120000"),
+        snap("before-update"),
+        snap("auto-20260101-120000"),
+        snap("auto-20260102-120000"),
+    ];
+    assert_eq!(
+        scheduled_snapshots_to_prune(&snapshots, 2),
+        vec!["auto-20260101-120000".to_string()]
+    );
+    assert!(scheduled_snapshots_to_prune(&snapshots, 3).is_empty());
+    // Manual snapshots never count toward or get removed by the limit
+    assert_eq!(scheduled_snapshots_to_prune(&snapshots, 0).len(), 3);
+}
+
+#[test]
+fn test_scheduled_snapshot_name_is_valid() {
+    let name = scheduled_snapshot_name();
+    assert!(name.starts_with(SCHEDULED_SNAPSHOT_PREFIX));
+    assert_eq!(validate_snapshot_name(&name).unwrap(), name);
+}
@@ -0,0 +1,91 @@
+use super::*;
+use crate::vm::qemu_config::PortForward;
+
+fn forward(protocol: PortProtocol, host_port: u16, guest_port: u16) -> PortForward {
+    PortForward {
+        protocol,
+        host_port,
+        guest_port,
+    }
+}
+
+#[test]
+fn nat_guests_use_the_forward_to_port_22() {
+    let network = NetworkConfig {
+        port_forwards: vec![
+            forward(PortProtocol::Udp, 2200, 22),
+            forward(PortProtocol::Tcp, 8080, 80),
+            forward(PortProtocol::Tcp, 2222, 22),
+        ],
+        ..Default::default()
+    };
+    assert_eq!(ssh_target(&network, &[]), Some(SshTarget::Forward(2222)));
+    assert!(can_ssh(Some(&network)));
+
+    let no_ssh = NetworkConfig {
+        port_forwards: vec![forward(PortProtocol::Tcp, 8080, 80)],
+        ..Default::default()
+    };
+    assert_eq!(ssh_target(&no_ssh, &[]), None);
+    assert!(!can_ssh(Some(&no_ssh)));
+    assert!(!can_ssh(None));
+}
+
+#[test]
+fn bridged_guests_use_the_agent_address() {
+    let network = NetworkConfig {
+        backend: NetworkBackend::Bridge("br0".to_string()),
+        ..Default::default()
+    };
+    assert!(can_ssh(Some(&network)));
+    assert_eq!(ssh_target(&network, &[]), None);
+
+    let addresses = vec![
+        "eth0 fd00::5/64".to_string(),
+        "eth0 169.254.3.4/16".to_string(),
+        "eth0 192.168.1.50/24".to_string(),
+    ];
+    assert_eq!(
+        ssh_target(&network, &addresses),
+        Some(SshTarget::Direct(Ipv4Addr::new(192, 168, 1, 50)))
+    );
+}
+
+#[test]
+fn ssh_args_keep_forwarded_host_keys_apart() {
+    assert_eq!(
+        SshTarget::Forward(2222).ssh_args("alice", "debian"),
+        vec![
+            "-p",
+            "2222",
+            "-o",
+            "HostKeyAlias=vm-curator-debian",
+            "alice@localhost"
+        ]
+    );
+    assert_eq!(
+        SshTarget::Direct(Ipv4Addr::new(10, 0, 0, 7)).ssh_args("bob", "arch"),
+        vec!["bob@10.0.0.7"]
+    );
+}
+
+#[test]
+fn login_names_are_validated() {
+    assert!(is_valid_user("alice"));
+    assert!(is_valid_user("build_bot.2"));
+    assert!(!is_valid_user(""));
+    assert!(!is_valid_user("-oProxyCommand=x"));
+    assert!(!is_valid_user("a b"));
+    assert!(!is_valid_user("root@host"));
+}
+
+#[test]
+fn ssh_user_is_remembered() -> anyhow::Result<()> {
+    let dir = tempfile::tempdir()?;
+    assert_eq!(load_ssh_user(dir.path()), None);
+    save_ssh_user(dir.path(), "alice")?;
+    assert_eq!(load_ssh_user(dir.path()), Some("alice".to_string()));
+    save_ssh_user(dir.path(), "bob")?;
+    assert_eq!(load_ssh_user(dir.path()), Some("bob".to_string()));
+    Ok(())
+}
@@ -0,0 +1,82 @@
+use super::*;
+
+fn make_vm(library: &Path, id: &str) -> PathBuf {
+    let vm = library.join(id);
+    std::fs::create_dir_all(&vm).unwrap();
+    std::fs::write(vm.join("launch.sh"), "#!/bin/bash\n").unwrap();
+    std::fs::write(vm.join("vm-curator.toml"), "display_name = \"My VM\"\n").unwrap();
+    vm
+}
+
+#[test]
+fn test_trash_and_restore_roundtrip() {
+    let tmp = tempfile::tempdir().unwrap();
+    let library = tmp.path().join("library");
+    let trash = tmp.path().join("trash");
+    let vm = make_vm(&library, "my-vm");
+
+    let trashed = move_to_trash(&vm, &trash, |_| {}).unwrap();
+    assert!(!vm.exists());
+    assert!(trashed.join("launch.sh").exists());
+
+    let entries = list_trash(&trash, &library);
+    assert_eq!(entries.len(), 1);
+    assert_eq!(entries[0].id, "my-vm");
+    assert_eq!(entries[0].name, "My VM");
+    assert_eq!(entries[0].original_path, vm);
+    assert!(entries[0].deleted_at.is_some());
+
+    restore(&entries[0], |_| {}).unwrap();
+    assert!(vm.join("launch.sh").exists());
+    assert!(list_trash(&trash, &library).is_empty());
+    // The info file goes with it
+    assert_eq!(std::fs::read_dir(&trash).unwrap().count(), 0);
+}
+
+#[test]
+fn test_trash_keeps_same_id_deletions_apart() {
+    let tmp = tempfile::tempdir().unwrap();
+    let library = tmp.path().join("library");
+    let trash = tmp.path().join("trash");
+
+    let first = move_to_trash(&make_vm(&library, "my-vm"), &trash, |_| {}).unwrap();
+    let second = move_to_trash(&make_vm(&library, "my-vm"), &trash, |_| {}).unwrap();
+    assert_ne!(first, second);
+
+    let entries = list_trash(&trash, &library);
+    assert_eq!(entries.len(), 2);
+
+    // Restoring over a VM that's back in the library is refused
+    restore(&entries[0], |_| {}).unwrap();
+    assert!(restore(&entries[1], |_| {}).is_err());
+
+    purge(&entries[1]).unwrap();
+    assert!(list_trash(&trash, &library).is_empty());
+}
+
+#[test]
+fn test_move_dir_across_filesystems() {
+    use std::os::unix::fs::MetadataExt;
+
+    // Needs a second filesystem; /dev/shm is tmpfs on most Linux hosts
+    let Ok(shm) = tempfile::tempdir_in("/dev/shm") else {
+        return;
+    };
+    let tmp = tempfile::tempdir().unwrap();
+    let dev = |p: &Path| std::fs::metadata(p).unwrap().dev();
+    if dev(shm.path()) == dev(tmp.path()) {
+        return;
+    }
+    let vm = make_vm(shm.path(), "my-vm");
+    std::fs::write(vm.join("disk.qcow2"), vec![1u8; 4 * 1024 * 1024]).unwrap();
+
+    let trashed = move_to_trash(&vm, &tmp.path().join("trash"), |p| {
+        assert!((0.0..=100.0).contains(&p))
+    })
+    .unwrap();
+    assert!(!vm.exists());
+    assert_eq!(
+        std::fs::read(trashed.join("disk.qcow2")).unwrap().len(),
+        4 * 1024 * 1024
+    );
+}
@@ -0,0 +1,99 @@
+use super::*;
+use crate::wizard_types::WizardQemuConfig;
+
+/// A launch.sh generated for a UEFI VM booting `CODE.fd`/`VARS.fd` in `fw`
+fn uefi_script(fw: &Path) -> String {
+    let code = fw.join("CODE.fd");
+    let vars = fw.join("VARS.fd");
+    std::fs::write(&code, b"code").unwrap();
+    std::fs::write(&vars, b"vars").unwrap();
+    let config = WizardQemuConfig {
+        uefi: true,
+        ovmf_paths: Some(OvmfPaths {
+            code,
+            vars_template: vars,
+        }),
+        ..WizardQemuConfig::default()
+    };
+    crate::vm::create::generate_launch_script_with_os(
+        "Test",
+        "test.qcow2",
+        None,
+        false,
+        &config,
+        None,
+        None,
+    )
+}
+
+#[test]
+fn test_read_uefi_vars() {
+    let fw = tempfile::tempdir().unwrap();
+    let script = uefi_script(fw.path());
+    let uefi = read_uefi_vars(Path::new("/vms/win"), &script).unwrap();
+    assert_eq!(uefi.vars, PathBuf::from("/vms/win/OVMF_VARS.fd"));
+    assert_eq!(uefi.template, Some(fw.path().join("VARS.fd")));
+    assert!(!uefi.secure_boot);
+
+    assert_eq!(
+        read_uefi_vars(Path::new("/vms/dos"), "qemu-system-i386 -m 32\n"),
+        None
+    );
+}
+
+#[test]
+fn test_reset_nvram_backs_up_old_store() -> Result<()> {
+    let dir = tempfile::tempdir()?;
+    let template = dir.path().join("VARS.fd");
+    std::fs::write(&template, b"template")?;
+    let uefi = UefiVars {
+        vars: dir.path().join("OVMF_VARS.fd"),
+        template: Some(template),
+        secure_boot: false,
+    };
+
+    // Nothing to back up before the first launch
+    assert!(backup_nvram(dir.path(), &uefi).is_err());
+    assert_eq!(reset_nvram(dir.path(), &uefi)?, None);
+
+    std::fs::write(&uefi.vars, b"broken boot entries")?;
+    let backup = reset_nvram(dir.path(), &uefi)?.unwrap();
+    assert!(backup.starts_with(dir.path().join(NVRAM_BACKUP_DIR)));
+    assert_eq!(std::fs::read(&backup)?, b"broken boot entries");
+    assert_eq!(std::fs::read(&uefi.vars)?, b"template");
+    Ok(())
+}
+
+#[test]
+fn test_rewrite_firmware_toggles_secure_boot() {
+    let fw = tempfile::tempdir().unwrap();
+    let script = uefi_script(fw.path());
+    let secboot = OvmfFirmware {
+        code: "/fw/CODE.secboot.qcow2".to_string(),
+        vars_template: "/fw/VARS.ms.qcow2".to_string(),
+        format: "qcow2",
+    };
+
+    let on = rewrite_firmware(&script, &secboot, true);
+    assert!(on.contains("-drive if=pflash,format=qcow2,readonly=on,file=/fw/CODE.secboot.qcow2 \\"));
+    assert!(on.contains("-drive if=pflash,format=qcow2,file=\"$OVMF_VARS\" \\"));
+    assert!(on.contains("OVMF_VARS=\"$VM_DIR/OVMF_VARS.qcow2\""));
+    assert!(on.contains("OVMF_VARS_TEMPLATE=\"/fw/VARS.ms.qcow2\""));
+    let uefi = read_uefi_vars(Path::new("/vm"), &on).unwrap();
+    assert!(uefi.secure_boot);
+    // One flag per QEMU command (the script has one per boot mode)
+    let commands = script.matches("file=\"$OVMF_VARS\"").count();
+    assert_eq!(on.matches(SECURE_PFLASH).count(), commands);
+
+    // Toggling it on again doesn't add a second flag
+    let again = rewrite_firmware(&on, &secboot, true);
+    assert_eq!(again.matches(SECURE_PFLASH).count(), commands);
+    assert_eq!(again.matches(",smm=on").count(), commands);
+
+    let plain = OvmfFirmware {
+        code: fw.path().join("CODE.fd").display().to_string(),
+        vars_template: fw.path().join("VARS.fd").display().to_string(),
+        format: "raw",
+    };
+    assert_eq!(rewrite_firmware(&on, &plain, false), script);
+}
@@ -0,0 +1,190 @@
+//! VM Trash
+//!
+//! Deleted VMs are moved whole into a trash directory under a timestamped
+//! name, with a `.trashinfo` file beside each recording where the folder
+//! came from, so they can be listed, restored or purged later.
+
+use anyhow::{bail, Context, Result};
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+
+/// Extension of the file recording a trashed folder's origin
+const TRASH_INFO_EXT: &str = "trashinfo";
+
+/// Contents of a `.trashinfo` file
+#[derive(Debug, Serialize, Deserialize)]
+struct TrashInfo {
+    original_path: PathBuf,
+    /// RFC 3339
+    deleted_at: String,
+}
+
+/// A VM folder in the trash
+#[derive(Debug, Clone)]
+pub struct TrashedVm {
+    /// The folder inside the trash directory
+    pub path: PathBuf,
+    /// Directory name the VM had in the library
+    pub id: String,
+    /// Display name from the VM's vm-curator.toml, or generated from its id
+    pub name: String,
+    /// Where the folder is restored to
+    pub original_path: PathBuf,
+    pub deleted_at: Option<chrono::DateTime<chrono::Local>>,
+    /// Bytes allocated by the folder
+    pub size_bytes: u64,
+}
+
+fn info_path(entry: &Path) -> PathBuf {
+    let mut name = entry.as_os_str().to_owned();
+    name.push(".");
+    name.push(TRASH_INFO_EXT);
+    PathBuf::from(name)
+}
+
+/// Move a folder, falling back to `mv` (copy and delete) when `rename`
+/// can't cross filesystems. That copies every disk, so `on_progress` gets
+/// the percent copied so far while it runs.
+fn move_dir(from: &Path, to: &Path, mut on_progress: impl FnMut(f64)) -> Result<()> {
+    if std::fs::rename(from, to).is_ok() {
+        return Ok(());
+    }
+    let total = crate::fs::allocated_size(from);
+    let mut child = Command::new("mv")
+        .arg("-T")
+        .arg(from)
+        .arg(to)
+        .stdout(Stdio::null())
+        .stderr(Stdio::piped())
+        .spawn()
+        .context("Failed to run mv")?;
+    while child.try_wait().context("Failed to wait for mv")?.is_none() {
+        if total > 0 {
+            let copied = crate::fs::allocated_size(to);
+            on_progress((copied as f64 * 100.0 / total as f64).min(100.0));
+        }
+        std::thread::sleep(std::time::Duration::from_millis(250));
+    }
+    let output = child.wait_with_output().context("Failed to wait for mv")?;
+    if !output.status.success() {
+        bail!(
+            "Failed to move {} to {}: {}",
+            from.display(),
+            to.display(),
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+    Ok(())
+}
+
+/// Move a VM folder into `trash_dir` as `<id>-<timestamp>`, recording where
+/// it came from. Returns the folder's path in the trash. `on_progress` is as
+/// for [`move_dir`].
+pub fn move_to_trash(
+    vm_path: &Path,
+    trash_dir: &Path,
+    on_progress: impl FnMut(f64),
+) -> Result<PathBuf> {
+    let id = vm_path
+        .file_name()
+        .and_then(|n| n.to_str())
+        .context("VM folder has no name")?;
+    std::fs::create_dir_all(trash_dir)
+        .with_context(|| format!("Failed to create trash directory {}", trash_dir.display()))?;
+
+    let now = chrono::Local::now();
+    let stem = format!("{}-{}", id, now.format("%Y%m%d-%H%M%S"));
+    let mut dest = trash_dir.join(&stem);
+    let mut n = 1;
+    while dest.exists() || info_path(&dest).exists() {
+        n += 1;
+        dest = trash_dir.join(format!("{}-{}", stem, n));
+    }
+
+    let info = TrashInfo {
+        original_path: vm_path.to_path_buf(),
+        deleted_at: now.to_rfc3339_opts(chrono::SecondsFormat::Secs, false),
+    };
+    std::fs::write(info_path(&dest), toml::to_string(&info)?)
+        .context("Failed to write trash info")?;
+    if let Err(e) = move_dir(vm_path, &dest, on_progress) {
+        let _ = std::fs::remove_file(info_path(&dest));
+        return Err(e);
+    }
+    Ok(dest)
+}
+
+/// VM folders in `trash_dir`, most recently deleted first. Folders without
+/// a `.trashinfo` (e.g. from older versions) restore into `library_path`.
+pub fn list_trash(trash_dir: &Path, library_path: &Path) -> Vec<TrashedVm> {
+    let Ok(entries) = std::fs::read_dir(trash_dir) else {
+        return Vec::new();
+    };
+    let mut trashed: Vec<TrashedVm> = entries
+        .flatten()
+        .map(|e| e.path())
+        .filter(|path| path.is_dir())
+        .map(|path| {
+            let info: Option<TrashInfo> = std::fs::read_to_string(info_path(&path))
+                .ok()
+                .and_then(|content| toml::from_str(&content).ok());
+            let folder = path
+                .file_name()
+                .map(|n| n.to_string_lossy().to_string())
+                .unwrap_or_default();
+            let original_path = info
+                .as_ref()
+                .map(|i| i.original_path.clone())
+                .unwrap_or_else(|| library_path.join(&folder));
+            let id = original_path
+                .file_name()
+                .map(|n| n.to_string_lossy().to_string())
+                .unwrap_or(folder);
+            let name = super::discovery::read_vm_metadata_value(&path, "display_name")
+                .unwrap_or_else(|| super::discovery::format_os_display_name(&id));
+            TrashedVm {
+                deleted_at: info
+                    .and_then(|i| chrono::DateTime::parse_from_rfc3339(&i.deleted_at).ok())
+                    .map(|t| t.with_timezone(&chrono::Local)),
+                size_bytes: crate::fs::allocated_size(&path),
+                path,
+                id,
+                name,
+                original_path,
+            }
+        })
+        .collect();
+    trashed.sort_by(|a, b| b.deleted_at.cmp(&a.deleted_at).then(a.id.cmp(&b.id)));
+    trashed
+}
+
+/// Move a trashed VM back to where it was deleted from. `on_progress` is as
+/// for [`move_dir`].
+pub fn restore(entry: &TrashedVm, on_progress: impl FnMut(f64)) -> Result<PathBuf> {
+    if entry.original_path.exists() {
+        bail!(
+            "{} already exists; rename or remove it first",
+            entry.original_path.display()
+        );
+    }
+    if let Some(parent) = entry.original_path.parent() {
+        std::fs::create_dir_all(parent)
+            .with_context(|| format!("Failed to create {}", parent.display()))?;
+    }
+    move_dir(&entry.path, &entry.original_path, on_progress)?;
+    let _ = std::fs::remove_file(info_path(&entry.path));
+    Ok(entry.original_path.clone())
+}
+
+/// Permanently delete a trashed VM
+pub fn purge(entry: &TrashedVm) -> Result<()> {
+    std::fs::remove_dir_all(&entry.path)
+        .with_context(|| format!("Failed to delete {}", entry.path.display()))?;
+    let _ = std::fs::remove_file(info_path(&entry.path));
+    Ok(())
+}
+
+#[cfg(test)]
+#[path = "tests/trash.rs"]
+mod tests;
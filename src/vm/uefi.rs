@@ -0,0 +1,205 @@
+//! UEFI variable store (NVRAM) of a VM
+//!
+//! UEFI VMs boot a read-only OVMF_CODE image plus their own writable copy of
+//! an OVMF_VARS template, kept in the VM folder next to the disk. The copy
+//! holds boot entries and the Secure Boot keys; when it gets corrupted the
+//! guest drops into the UEFI shell, and copying the template over it again
+//! fixes that.
+
+use anyhow::{bail, Context, Result};
+use std::path::{Path, PathBuf};
+
+use super::create::{resolve_ovmf_firmware, OvmfFirmware};
+use super::discovery::DiscoveredVm;
+use crate::wizard_types::OvmfPaths;
+
+/// QEMU argument that makes the pflash enforce Secure Boot
+const SECURE_PFLASH: &str = "-global driver=cfi.pflash01,property=secure,value=on";
+
+/// Folder in the VM directory that variable store backups go to
+pub const NVRAM_BACKUP_DIR: &str = "nvram-backups";
+
+/// UEFI setup of a VM, read from its launch.sh
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UefiVars {
+    /// The VM's own variable store
+    pub vars: PathBuf,
+    /// Template the store is copied from
+    pub template: Option<PathBuf>,
+    /// Whether the pflash enforces Secure Boot
+    pub secure_boot: bool,
+}
+
+/// Value of a `NAME="..."` line in a script, without quotes
+fn script_value(script: &str, name: &str) -> Option<String> {
+    let prefix = format!("{}=", name);
+    script.lines().find_map(|line| {
+        let value = line.trim().strip_prefix(&prefix)?;
+        Some(value.trim_matches(|c| c == '"' || c == '\'').to_string())
+    })
+}
+
+/// The UEFI variable store a script boots with, or `None` when the script
+/// doesn't keep one per VM (BIOS VMs and hand-written UEFI scripts)
+pub fn read_uefi_vars(vm_path: &Path, script: &str) -> Option<UefiVars> {
+    let vm_dir = vm_path.to_string_lossy();
+    let vars = script_value(script, "OVMF_VARS")?
+        .replace("${VM_DIR}", &vm_dir)
+        .replace("$VM_DIR", &vm_dir);
+    Some(UefiVars {
+        vars: PathBuf::from(vars),
+        template: script_value(script, "OVMF_VARS_TEMPLATE").map(PathBuf::from),
+        secure_boot: script.contains(SECURE_PFLASH),
+    })
+}
+
+/// Copy the variable store into the VM's backup folder under a timestamped
+/// name, returning the copy's path
+pub fn backup_nvram(vm_path: &Path, uefi: &UefiVars) -> Result<PathBuf> {
+    if !uefi.vars.exists() {
+        bail!("The VM has no UEFI variables yet; they are created on its first launch");
+    }
+    let dir = vm_path.join(NVRAM_BACKUP_DIR);
+    std::fs::create_dir_all(&dir).with_context(|| format!("Failed to create {}", dir.display()))?;
+    let name = uefi
+        .vars
+        .file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_else(|| "OVMF_VARS.fd".to_string());
+    let dest = dir.join(format!(
+        "{}-{}",
+        chrono::Local::now().format("%Y%m%d-%H%M%S"),
+        name
+    ));
+    std::fs::copy(&uefi.vars, &dest)
+        .with_context(|| format!("Failed to back up {}", uefi.vars.display()))?;
+    Ok(dest)
+}
+
+/// Replace the variable store with a fresh copy of its template. The old
+/// store is backed up first; its backup path is returned.
+pub fn reset_nvram(vm_path: &Path, uefi: &UefiVars) -> Result<Option<PathBuf>> {
+    let template = uefi
+        .template
+        .as_ref()
+        .context("launch.sh names no OVMF_VARS_TEMPLATE to reset from")?;
+    if !template.exists() {
+        bail!("UEFI variables template not found: {}", template.display());
+    }
+    let backup = if uefi.vars.exists() {
+        Some(backup_nvram(vm_path, uefi)?)
+    } else {
+        None
+    };
+    std::fs::copy(template, &uefi.vars)
+        .with_context(|| format!("Failed to reset {}", uefi.vars.display()))?;
+    Ok(backup)
+}
+
+/// Switch a UEFI VM to the Secure Boot firmware pair (or back). Secure Boot
+/// keys live in the variable store, so it is replaced with the new pair's
+/// template after a backup, whose path is returned.
+pub fn set_secure_boot(
+    vm: &DiscoveredVm,
+    enabled: bool,
+    ovmf_paths: Option<&OvmfPaths>,
+) -> Result<Option<PathBuf>> {
+    let script = std::fs::read_to_string(&vm.launch_script).context("Failed to read launch.sh")?;
+    let current = read_uefi_vars(&vm.path, &script).context(
+        "launch.sh doesn't keep a UEFI variable store in the VM folder; regenerate it first",
+    )?;
+    let firmware = resolve_ovmf_firmware(ovmf_paths, enabled)?;
+    let updated = rewrite_firmware(&script, &firmware, enabled);
+    let new = read_uefi_vars(&vm.path, &updated).context("Failed to update launch.sh")?;
+
+    let backup = if current.vars.exists() {
+        Some(backup_nvram(&vm.path, &current)?)
+    } else {
+        None
+    };
+    std::fs::write(&vm.launch_script, updated).context("Failed to write launch.sh")?;
+    if current.vars.exists() {
+        std::fs::remove_file(&current.vars)
+            .with_context(|| format!("Failed to remove {}", current.vars.display()))?;
+    }
+    reset_nvram(&vm.path, &new)?;
+    Ok(backup)
+}
+
+/// A `-machine` argument with `smm=on` added or removed; Secure Boot needs
+/// System Management Mode to protect the variable store
+fn machine_with_smm(arg: &str, smm: bool) -> String {
+    let mut opts: Vec<&str> = arg.split(',').filter(|o| *o != "smm=on").collect();
+    if smm {
+        opts.push("smm=on");
+    }
+    opts.join(",")
+}
+
+/// Point a generated script's UEFI setup at `firmware`, adding or removing
+/// the Secure Boot pflash flag and SMM
+pub(crate) fn rewrite_firmware(script: &str, firmware: &OvmfFirmware, secure_boot: bool) -> String {
+    let mut out: Vec<String> = Vec::new();
+    for line in script.lines() {
+        let trimmed = line.trim_start();
+        let indent = &line[..line.len() - trimmed.len()];
+        let cont = if trimmed.ends_with('\\') { " \\" } else { "" };
+
+        if let Some(arg) = trimmed.strip_prefix("-machine ") {
+            let arg = arg.trim_end_matches('\\').trim_end();
+            out.push(format!(
+                "{}-machine {}{}",
+                indent,
+                machine_with_smm(arg, secure_boot),
+                cont
+            ));
+        } else if trimmed.starts_with("OVMF_VARS_TEMPLATE=") {
+            out.push(format!(
+                "{}OVMF_VARS_TEMPLATE=\"{}\"",
+                indent, firmware.vars_template
+            ));
+        } else if trimmed.starts_with("OVMF_VARS=") {
+            out.push(format!(
+                "{}OVMF_VARS=\"$VM_DIR/{}\"",
+                indent,
+                firmware.vars_filename()
+            ));
+        } else if trimmed.starts_with(SECURE_PFLASH) {
+            // Re-added after the vars drive when still wanted
+            if cont.is_empty() {
+                if let Some(prev) = out.last_mut() {
+                    *prev = prev.trim_end_matches('\\').trim_end().to_string();
+                }
+            }
+        } else if trimmed.starts_with("-drive if=pflash,") && trimmed.contains("readonly=on") {
+            out.push(format!(
+                "{}-drive if=pflash,format={},readonly=on,file={}{}",
+                indent, firmware.format, firmware.code, cont
+            ));
+        } else if trimmed.starts_with("-drive if=pflash,") {
+            if secure_boot {
+                out.push(format!(
+                    "{}-drive if=pflash,format={},file=\"$OVMF_VARS\" \\",
+                    indent, firmware.format
+                ));
+                out.push(format!("{}{}{}", indent, SECURE_PFLASH, cont));
+            } else {
+                out.push(format!(
+                    "{}-drive if=pflash,format={},file=\"$OVMF_VARS\"{}",
+                    indent, firmware.format, cont
+                ));
+            }
+        } else {
+            out.push(line.to_string());
+        }
+    }
+    let mut result = out.join("\n");
+    if script.ends_with('\n') {
+        result.push('\n');
+    }
+    result
+}
+
+#[cfg(test)]
+#[path = "tests/uefi.rs"]
+mod tests;
@@ -1,8 +1,11 @@
 //! Wizard and import state types, extracted from app.rs so they can be
 //! exposed via the library target without pulling in the TUI (ratatui/crossterm).
 
-use crate::vm::qemu_config::{PortForward, PortProtocol};
+use crate::vm::qemu_config::{
+    CpuTopology, InputDevices, NetworkShaping, PortForward, PortProtocol, RtcBase, RtcConfig,
+};
 use anyhow::Result;
+use serde::{Deserialize, Serialize};
 use std::path::{Path, PathBuf};
 
 /// Disk image format to create for new VMs
@@ -176,14 +179,19 @@ impl WizardStep {
 }
 
 /// QEMU configuration settings for the wizard
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
 pub struct WizardQemuConfig {
     /// QEMU emulator command
     pub emulator: String,
     /// RAM in megabytes
     pub memory_mb: u32,
+    /// Hotplug ceiling in megabytes (`-m ...,maxmem=`); `None` keeps RAM fixed
+    pub max_memory_mb: Option<u32>,
     /// CPU cores
     pub cpu_cores: u32,
+    /// Sockets/cores/threads split of `cpu_cores`; `None` keeps one socket
+    pub cpu_topology: Option<CpuTopology>,
     /// CPU model (host, qemu64, pentium, etc.)
     pub cpu_model: Option<String>,
     /// Machine type (q35, pc, etc.)
@@ -192,6 +200,8 @@ pub struct WizardQemuConfig {
     pub vga: String,
     /// Audio devices
     pub audio: Vec<String>,
+    /// Host audio backend the devices play through (`-audiodev` driver)
+    pub audio_backend: String,
     /// Network adapter model
     pub network_model: String,
     /// Disk interface
@@ -204,12 +214,33 @@ pub struct WizardQemuConfig {
     pub uefi: bool,
     /// TPM emulation
     pub tpm: bool,
+    /// Secure Boot firmware and keys (UEFI only; always on with TPM)
+    pub secure_boot: bool,
     /// RTC uses local time (for Windows)
     pub rtc_localtime: bool,
-    /// USB tablet for mouse
-    pub usb_tablet: bool,
+    /// Other RTC settings (clock source, fixed start date) carried over from
+    /// an existing script; `rtc_localtime` picks UTC or local time
+    pub rtc: RtcConfig,
+    /// Keyboard and pointer devices (PS/2, USB tablet or virtio)
+    pub input: InputDevices,
+    /// Intel macOS guest: Apple SMC, SMBIOS table and the OpenCore/AHCI disk
+    /// layout (implied by the versioned macOS profiles)
+    pub apple_smc: bool,
+    /// QEMU guest agent channel (`qga.sock` in the VM directory)
+    pub guest_agent: bool,
+    /// virtio-balloon device so RAM can be reclaimed from a running guest
+    pub balloon: bool,
+    /// SPICE guest-agent channel for host/guest copy-paste (spice-app display
+    /// only; needs `spice-vdagent` in the guest)
+    pub clipboard_sharing: bool,
+    /// Nested virtualization: the host's virtualization flag (`vmx` or
+    /// `svm`) to pass through, picked when the toggle is set (needs KVM)
+    pub nested_virt: Option<String>,
     /// Display output
     pub display: String,
+    /// Guest keyboard layout (`-k`); `None` leaves QEMU's en-us, or the
+    /// host's layout for a new VNC VM
+    pub keymap: Option<String>,
     /// Network backend
     pub network_backend: String,
     /// Port forwarding rules (user & passt backends)
@@ -222,6 +253,19 @@ pub struct WizardQemuConfig {
     pub extra_args: Vec<String>,
     /// BIOS/ROM file path (for classic Mac and other systems needing custom firmware)
     pub bios_path: Option<PathBuf>,
+    /// OVMF firmware set in config.toml; `None` auto-detects it. A host
+    /// setting, so it is never saved with templates.
+    #[serde(skip)]
+    pub ovmf_paths: Option<OvmfPaths>,
+}
+
+/// UEFI firmware files picked by hand instead of auto-detected
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OvmfPaths {
+    /// Read-only OVMF_CODE image
+    pub code: PathBuf,
+    /// OVMF_VARS template each VM gets its own copy of
+    pub vars_template: PathBuf,
 }
 
 impl Default for WizardQemuConfig {
@@ -229,26 +273,38 @@ impl Default for WizardQemuConfig {
         Self {
             emulator: "qemu-system-x86_64".to_string(),
             memory_mb: 2048,
+            max_memory_mb: None,
             cpu_cores: 2,
+            cpu_topology: None,
             cpu_model: Some("host".to_string()),
             machine: Some("q35".to_string()),
             vga: "std".to_string(),
             audio: vec!["intel-hda".to_string(), "hda-duplex".to_string()],
+            audio_backend: "pa".to_string(),
             network_model: "e1000".to_string(),
             disk_interface: "ide".to_string(),
             enable_kvm: true,
             gl_acceleration: false,
             uefi: false,
             tpm: false,
+            secure_boot: false,
             rtc_localtime: false,
-            usb_tablet: true,
+            rtc: RtcConfig::default(),
+            input: InputDevices::default(),
+            apple_smc: false,
+            guest_agent: false,
+            balloon: false,
+            clipboard_sharing: true,
+            nested_virt: None,
             display: "gtk".to_string(),
+            keymap: None,
             network_backend: "user".to_string(),
             port_forwards: Vec::new(),
             bridge_name: None,
             mac_address: None,
             extra_args: Vec::new(),
             bios_path: None,
+            ovmf_paths: None,
         }
     }
 }
@@ -264,51 +320,127 @@ impl WizardQemuConfig {
         Self {
             emulator: profile.emulator.clone(),
             memory_mb: profile.memory_mb,
+            max_memory_mb: None,
             cpu_cores: profile.cpu_cores,
+            cpu_topology: None,
             cpu_model: profile.cpu_model.clone(),
             machine: profile.machine.clone(),
             vga: profile.vga.clone(),
             audio: profile.audio.clone(),
+            audio_backend: Self::default_audio_backend(&profile.emulator, &profile.display),
             network_model: profile.network_model.clone(),
             disk_interface: profile.disk_interface.clone(),
             enable_kvm: profile.enable_kvm,
             gl_acceleration,
             uefi: profile.uefi,
             tpm: profile.tpm,
+            secure_boot: profile.uefi && profile.tpm,
             rtc_localtime: profile.rtc_localtime,
-            usb_tablet: profile.usb_tablet,
+            rtc: RtcConfig::default(),
+            input: profile.input_devices(),
+            apple_smc: profile.apple_smc,
+            guest_agent: false,
+            balloon: false,
+            clipboard_sharing: true,
+            nested_virt: None,
             display: profile.display.clone(),
+            keymap: None,
             network_backend: profile.network_backend.clone(),
             port_forwards: Vec::new(),
             bridge_name: None,
             mac_address: None,
             extra_args: profile.extra_args.clone(),
             bios_path: None,
+            ovmf_paths: None,
         }
     }
+
+    /// RTC settings to emit, with the local time toggle applied
+    pub fn rtc_config(&self) -> RtcConfig {
+        let mut rtc = self.rtc.clone();
+        if self.rtc_localtime {
+            rtc.base = RtcBase::Localtime;
+        } else if rtc.base == RtcBase::Localtime {
+            rtc.base = RtcBase::Utc;
+        }
+        rtc
+    }
+
+    /// Audio backend for a new VM: SPICE carries its own audio, otherwise
+    /// use the sound server running in this session
+    pub fn default_audio_backend(emulator: &str, display: &str) -> String {
+        if display == "spice-app" {
+            "spice".to_string()
+        } else {
+            crate::commands::qemu_system::detect_audio_backend(emulator).to_string()
+        }
+    }
+
+    /// Switch to a different emulator binary and adapt the machine, CPU and
+    /// device defaults to what that target can actually run.
+    ///
+    /// Switching between the two PC emulators only swaps machine/CPU, so the
+    /// user's device choices survive; any other target gets a full set of
+    /// board defaults and loses the PC-only features (KVM, UEFI, TPM, GL).
+    pub fn apply_emulator_defaults(&mut self, emulator: &str) {
+        let is_pc = |e: &str| matches!(e, "qemu-system-x86_64" | "qemu-system-i386");
+        let was_pc = is_pc(&self.emulator);
+        self.emulator = emulator.to_string();
+
+        let (machine, cpu, vga, network, disk) = match emulator {
+            "qemu-system-x86_64" => ("q35", "host", "std", "e1000", "ide"),
+            "qemu-system-i386" => ("pc", "pentium", "std", "e1000", "ide"),
+            "qemu-system-ppc" => ("mac99", "g3", "std", "sungem", "ide"),
+            "qemu-system-m68k" => ("q800", "m68040", "none", "none", "scsi"),
+            "qemu-system-arm" => ("virt", "cortex-a15", "none", "virtio", "virtio"),
+            "qemu-system-aarch64" => ("virt", "cortex-a72", "none", "virtio", "virtio"),
+            _ => return,
+        };
+        self.machine = Some(machine.to_string());
+        self.cpu_model = Some(cpu.to_string());
+
+        if is_pc(emulator) {
+            if !was_pc {
+                self.vga = vga.to_string();
+                self.network_model = network.to_string();
+                self.disk_interface = disk.to_string();
+                self.enable_kvm = true;
+            }
+            return;
+        }
+
+        self.vga = vga.to_string();
+        self.network_model = network.to_string();
+        self.disk_interface = disk.to_string();
+        self.audio.clear();
+        self.enable_kvm = false;
+        self.nested_virt = None;
+        self.gl_acceleration = false;
+        self.uefi = false;
+        self.tpm = false;
+        self.secure_boot = false;
+        self.input = InputDevices::Ps2;
+        self.apple_smc = false;
+    }
 }
 
 /// Custom OS entry for when user selects "Other"
 ///
-/// Some fields below are `#[allow(dead_code)]`: they are captured by the entry
-/// form but not yet consumed, reserved for the planned "save custom OS to user
-/// metadata" feature. They are intentional, not dead.
+/// The release date, blurbs and fun facts are not asked for by the entry
+/// form yet; they are written to the user metadata as-is when the OS is
+/// saved as a profile.
 #[derive(Debug, Clone, Default)]
 pub struct CustomOsEntry {
     pub id: String,
     pub name: String,
     pub publisher: String,
-    #[allow(dead_code)]
     pub release_date: Option<String>,
     pub architecture: String,
-    #[allow(dead_code)]
     pub short_blurb: String,
-    #[allow(dead_code)]
     pub long_blurb: String,
-    #[allow(dead_code)]
     pub fun_facts: Vec<String>,
     pub base_profile: String,
-    #[allow(dead_code)]
+    /// Save the OS as a user QEMU profile so later VMs can pick it
     pub save_to_user: bool,
 }
 
@@ -338,9 +470,13 @@ pub struct CreateWizardState {
     pub selected_os: Option<String>,
     pub custom_os: Option<CustomOsEntry>,
     pub iso_path: Option<PathBuf>,
+    /// OS guessed from the chosen ISO, awaiting the user's yes/no
+    pub iso_detection: Option<crate::vm::IsoDetection>,
     pub is_recovery_image: bool,
     pub iso_downloading: bool,
     pub iso_download_progress: f32,
+    /// Bytes fetched so far, for servers that don't report a size
+    pub iso_download_bytes: u64,
     pub disk_size_gb: u32,
     pub use_existing_disk: bool,
     pub existing_disk_path: Option<PathBuf>,
@@ -361,6 +497,10 @@ pub struct CreateWizardState {
     pub error_message: Option<String>,
     pub editing_field: Option<WizardField>,
     pub wizard_edit_buffer: String,
+    /// VM name the user chose to keep despite matching an existing VM
+    pub duplicate_name_acknowledged: Option<String>,
+    /// Template the settings were loaded from
+    pub template: Option<String>,
 }
 
 impl Default for CreateWizardState {
@@ -372,9 +512,11 @@ impl Default for CreateWizardState {
             selected_os: None,
             custom_os: None,
             iso_path: None,
+            iso_detection: None,
             is_recovery_image: false,
             iso_downloading: false,
             iso_download_progress: 0.0,
+            iso_download_bytes: 0,
             disk_size_gb: 32,
             use_existing_disk: false,
             existing_disk_path: None,
@@ -387,11 +529,17 @@ impl Default for CreateWizardState {
             os_list_scroll: 0,
             os_filter: String::new(),
             selected_category: 0,
-            expanded_categories: vec!["windows".to_string(), "linux".to_string()],
+            expanded_categories: vec![
+                "templates".to_string(),
+                "windows".to_string(),
+                "linux".to_string(),
+            ],
             os_list_selected: 0,
             error_message: None,
             editing_field: None,
             wizard_edit_buffer: String::new(),
+            duplicate_name_acknowledged: None,
+            template: None,
         }
     }
 }
@@ -477,6 +625,14 @@ impl CreateWizardState {
                 if self.qemu_config.cpu_cores == 0 {
                     return Err("CPU cores must be greater than 0".to_string());
                 }
+                if let Some(ref topology) = self.qemu_config.cpu_topology {
+                    topology.validate(self.qemu_config.cpu_cores)?;
+                }
+                if let Some(max) = self.qemu_config.max_memory_mb {
+                    if max <= self.qemu_config.memory_mb {
+                        return Err("Max memory must be greater than memory".to_string());
+                    }
+                }
                 Ok(())
             }
             WizardStep::Confirm => Ok(()),
@@ -510,6 +666,10 @@ pub struct NetworkSettingsState {
     pub editing_port_forwards: bool,
     pub pf_selected: usize,
     pub adding_pf: Option<AddingPortForward>,
+    /// Poor network emulation (bridge backend only)
+    pub shaping: NetworkShaping,
+    pub shaping_edit_buffer: String,
+    pub editing_shaping: bool,
 }
 
 /// State when adding a new port forward rule
@@ -538,6 +698,11 @@ pub enum AddPfStep {
 pub enum ImportSource {
     Libvirt,
     Quickemu,
+    /// OVA archive or unpacked OVF descriptor
+    Ova,
+    /// QEMU process started outside vm-curator; `config_path` is its
+    /// `/proc/<pid>` directory
+    Process,
 }
 
 /// Disk handling action during import
@@ -547,6 +712,8 @@ pub enum ImportDiskAction {
     Symlink,
     Copy,
     Move,
+    /// Copy into the VM folder as qcow2 via `qemu-img convert`
+    Convert,
 }
 
 /// Steps in the import wizard
@@ -584,6 +751,8 @@ pub struct ImportWizardState {
     pub vm_name: String,
     pub folder_name: String,
     pub disk_action: ImportDiskAction,
+    /// QEMU format name of each selected disk that isn't qcow2/raw (vmdk, vdi, ...)
+    pub foreign_disk_formats: Vec<Option<String>>,
     pub field_focus: usize,
     pub error_message: Option<String>,
     pub editing_name: bool,
@@ -601,6 +770,7 @@ impl Default for ImportWizardState {
             vm_name: String::new(),
             folder_name: String::new(),
             disk_action: ImportDiskAction::Symlink,
+            foreign_disk_formats: Vec::new(),
             field_focus: 0,
             error_message: None,
             editing_name: false,
@@ -659,4 +829,39 @@ mod tests {
             None
         );
     }
+
+    #[test]
+    fn apply_emulator_defaults_keeps_devices_between_pc_targets() {
+        let mut cfg = WizardQemuConfig {
+            vga: "virtio".to_string(),
+            network_model: "virtio".to_string(),
+            ..WizardQemuConfig::default()
+        };
+        cfg.apply_emulator_defaults("qemu-system-i386");
+        assert_eq!(cfg.emulator, "qemu-system-i386");
+        assert_eq!(cfg.machine.as_deref(), Some("pc"));
+        assert_eq!(cfg.vga, "virtio");
+        assert_eq!(cfg.network_model, "virtio");
+        assert!(cfg.enable_kvm);
+    }
+
+    #[test]
+    fn apply_emulator_defaults_resets_board_for_foreign_targets() {
+        let mut cfg = WizardQemuConfig {
+            uefi: true,
+            tpm: true,
+            ..WizardQemuConfig::default()
+        };
+        cfg.apply_emulator_defaults("qemu-system-ppc");
+        assert_eq!(cfg.machine.as_deref(), Some("mac99"));
+        assert_eq!(cfg.cpu_model.as_deref(), Some("g3"));
+        assert_eq!(cfg.network_model, "sungem");
+        assert!(!cfg.enable_kvm && !cfg.uefi && !cfg.tpm);
+
+        // Coming back to x86_64 restores PC devices and KVM
+        cfg.apply_emulator_defaults("qemu-system-x86_64");
+        assert_eq!(cfg.machine.as_deref(), Some("q35"));
+        assert_eq!(cfg.network_model, "e1000");
+        assert!(cfg.enable_kvm);
+    }
 }